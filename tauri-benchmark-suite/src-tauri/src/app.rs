@@ -0,0 +1,4109 @@
+//! 图形界面应用：Tauri命令、会话管理和事件发送。只在启用tauri-app特性（默认）时编译
+use crate::{app_info, benchmark, format, heartbeat, i18n, ipc, launch, logging, session, startup, telemetry, timeseries, upload, webhook};
+use app_info::{AppInfo, RunIdentity, RunMode};
+use benchmark::system_info::{collect_system_info, SystemInfo};
+use benchmark::copy::{CopyBenchmark, CopyTestConfig, CopyTestResult};
+use benchmark::cpu::{CpuBenchmark, CpuTestConfig, CpuTestResult};
+use benchmark::memory::{MemoryBenchmark, MemoryTestConfig, MemoryTestResult};
+use benchmark::gpu::{GpuAdapterInfo, GpuBenchmark, GpuTestConfig, GpuTestResult};
+use benchmark::network::{NetworkBenchmark, NetworkTestConfig, NetworkTestResult};
+use benchmark::ipc_overhead::{IpcBenchmark, IpcBenchmarkConfig, IpcBenchmarkResult, IpcEchoReply};
+use benchmark::process::{ProcessBenchmark, ProcessTestConfig, ProcessTestResult};
+use benchmark::storage::{default_test_file_path, StorageBenchmark, StorageTestConfig, StorageTestResult};
+use benchmark::power::{
+    compare_power_plans, detect_power_info, measure_plan, platform_switcher, ManualPlanSwitcher, PlanSwitcher, PowerComparisonConfig,
+    PowerPlanComparison, PowerSwitchCoordinator,
+};
+use benchmark::webview::{WebviewBridge, WebviewCoordinator, WebviewSubmission, WebviewTestConfig, WebviewTestResult};
+use benchmark::error::BenchmarkError;
+use benchmark::aggregation::aggregate_runs;
+use benchmark::anonymous::AnonymousSummary;
+use benchmark::calibration::{plan_auto_duration, Calibration};
+use benchmark::comparison::RegressionThresholds;
+use benchmark::core::{
+    failure_warning, network_issues, run_with_watchdog, runner_estimates, suite_runners, BenchmarkConfig, BenchmarkRegistry, BenchmarkRunner, CancellationToken, IssueSeverity, LoadedConfig,
+    ProgressSink, RunContext, RunIssues, TestLabels, TestOutcome, TestResult, WarningSink,
+};
+use benchmark::capability::{default_test_dir, CapabilityMap, ElevationOutcome, Feature};
+use benchmark::estimate::{estimate_run, preflight_warnings, PreflightEnvironment, RunEstimate};
+use benchmark::progress::{SuiteProgress, SuiteProgressTracker};
+use benchmark::ranking::{RankingReport, ReferenceDataset};
+use benchmark::ci_output;
+use benchmark::composite::{export_result, ResultExportFormat};
+use benchmark::leaderboard::{parse_leaderboard_entries, LeaderboardEntry};
+use benchmark::metrics::{LiveSample, MetricsSink, SystemMonitor};
+use benchmark::results::{CompactionReport, DeletionReport, HistoryEntry, HistoryPage, HistoryQuery, ResultComparison, ResultStore, ResultSummary};
+use benchmark::schema::{supported_result_versions, RESULT_SCHEMA_VERSION};
+use session::{cleanup_expired_sessions, LogEntry, LogLevel, ProgressMilestones, RetentionConfig, SessionRecord, SessionRegistry};
+use heartbeat::{HeartbeatConfig, Watchdog, WatchdogAction};
+use startup::{
+    append_startup_history, detect_launch_kind, load_startup_history, StartupMetrics, StartupStage, StartupTracker, LAUNCH_MARKER_FILE,
+    STARTUP_HISTORY_FILE, STARTUP_HISTORY_LIMIT,
+};
+use format::{FormatOptions, FormatSettings};
+use i18n::{Locale, Message};
+use upload::{AnonymousSummaryConfig, AnonymousSummarySettings, ResultUploader, UploadConfig, UploadOptions, UploadSettings, UploadState, UploadStatus};
+use telemetry::{exporter_address, ExporterStatus, MetricsExporter, Telemetry, DEFAULT_EXPORTER_PORT};
+use timeseries::{TimeseriesFormat, TimeseriesStore};
+use webhook::{webhook_options, CompletionNotification, SuiteOutcome, WebhookConfig, WebhookNotifier, WebhookSettings};
+use launch::{exit_code, parse_launch_args, LaunchArgError, LaunchOptions, EXIT_USAGE, USAGE};
+use ipc::{events, ErrorCatalogEntry, EventBuffer, SupportedSchemaVersions, EVENT_SCHEMA_VERSION, SuiteEvent, WarningFilter, WarningManager, WarningRecord, HeartbeatEvent, RegressionDetectedEvent, IpcError, IpcSchema, ProgressEmitter, RecentEvents, ProgressThrottle, SessionStatusChangedEvent, BenchmarkProgress, TestCompletion, TestStatus, TestType, ProgressUpdate, TestSession, SystemMonitoringData, RealTimePerformanceData, TestWarningEvent, UiStateSnapshot, WarningSeverity};
+use tauri::ipc::Channel;
+use tauri::webview::PageLoadEvent;
+use tauri::{AppHandle, Manager, Runtime};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::Instrument;
+use uuid::Uuid;
+
+// 全局测试状态管理，状态变化后落盘以便崩溃后恢复
+type TestSessions = Arc<Mutex<SessionRegistry>>;
+
+// 会话保留策略，后台清理任务每轮读取
+type SharedRetention = Arc<Mutex<RetentionConfig>>;
+
+// 持久化的测试结果存储
+type SharedResultStore = Arc<Mutex<ResultStore>>;
+
+// 结果上传配置（默认关闭）
+type SharedUploadSettings = Arc<Mutex<UploadSettings>>;
+
+// 匿名摘要的同意状态和提交地址（默认不同意）
+type SharedAnonymousSettings = Arc<Mutex<AnonymousSummarySettings>>;
+
+// 套件完成通知的地址和模板（默认关闭），未管理该状态时不发送
+type SharedWebhookSettings = Arc<Mutex<WebhookSettings>>;
+
+// 报告和通知摘要中数值的地区格式、单位制和精度
+type SharedFormatSettings = Arc<Mutex<FormatSettings>>;
+
+// 进度事件限速状态，所有会话共享
+type SharedProgressThrottle = Arc<ProgressThrottle>;
+
+// 最近发送的事件，前端重新连接后据此补齐
+type SharedEventBuffer = Arc<EventBuffer>;
+
+// 警告去重和各会话的警告历史
+type SharedWarnings = Arc<WarningManager>;
+
+// 警告过滤配置在应用数据目录中的文件名
+const WARNING_FILTER_FILE: &str = "warning_filter.json";
+
+// 用于排名的参考数据，启动时加载，可替换为用户提供的文件
+type SharedReferenceDataset = Arc<Mutex<ReferenceDataset>>;
+
+// 用户提供的参考数据在应用数据目录中的副本，存在时替代内置数据
+const REFERENCE_DATASET_FILE: &str = "reference_dataset.json";
+
+// 心跳间隔和停滞阈值，会话开始时读取
+type SharedHeartbeat = Arc<Mutex<HeartbeatConfig>>;
+
+// 可在配置的extra_tests中引用的测试模块，启动时注册
+type SharedBenchmarkRegistry = Arc<BenchmarkRegistry>;
+
+// 事件消息文本使用的语言，新建的发送器读取当前值
+type SharedLocale = Arc<Mutex<Locale>>;
+
+// 套件事件是否在会话通道之外仍然全局广播（已弃用的兼容模式），会话开始时读取
+type SharedLegacyEvents = Arc<Mutex<bool>>;
+
+// 正在运行的复制测试是否已被取消，每次开始时重置
+type SharedCopyCancellation = Arc<AtomicBool>;
+
+// 正在运行的单独网络测试是否已被取消，每次开始时重置。单独的类型，不与复制测试的标志共用托管状态
+#[derive(Default)]
+struct NetworkCancellation(AtomicBool);
+type SharedNetworkCancellation = Arc<NetworkCancellation>;
+
+// 本次启动各阶段的耗时，在run()开始时创建
+type SharedStartup = Arc<Mutex<StartupTracker>>;
+
+// WebView测试发出的请求和前端的提交，单独的命令和套件共用
+type SharedWebviewCoordinator = Arc<WebviewCoordinator>;
+type SharedPowerSwitchCoordinator = Arc<PowerSwitchCoordinator>;
+type SharedTelemetry = Arc<Telemetry>;
+type SharedMetricsExporter = Arc<tokio::sync::Mutex<MetricsExporter>>;
+
+// 各会话的监控和实时指标时间序列，供导出CSV
+type SharedTimeseries = Arc<TimeseriesStore>;
+
+// 整机资源占用的采样器，get_system_monitoring_data和套件运行共用，保证CPU占用按最小间隔刷新
+type SharedSystemMonitor = Arc<Mutex<SystemMonitor>>;
+
+// 启动参数错误，前端加载后取出并弹出对话框
+type SharedLaunchError = Arc<Mutex<Option<String>>>;
+
+// 测试失败时附加到结果中的日志条数
+const FAILURE_LOG_LIMIT: usize = 200;
+
+// Tauri命令：获取系统信息
+#[tauri::command]
+async fn get_system_info() -> Result<SystemInfo, IpcError> {
+    collect_system_info().map_err(IpcError::from)
+}
+
+// Tauri命令：列出本机的GPU适配器，序号可填入gpu_test.adapter_index
+#[tauri::command]
+async fn list_gpu_adapters() -> Result<Vec<GpuAdapterInfo>, IpcError> {
+    tokio::task::spawn_blocking(benchmark::gpu::list_adapters)
+        .await
+        .map_err(|e| IpcError::from(BenchmarkError::GpuTestError(e.to_string())))
+}
+
+// Tauri命令：启动完整基准测试套件，该会话的进度、警告和完成事件通过on_event通道发送
+#[tauri::command]
+async fn start_benchmark_suite(
+    app: AppHandle,
+    config: BenchmarkConfig,
+    on_event: Channel<SuiteEvent>,
+) -> Result<String, IpcError> {
+    start_suite_session(app, config, Some(on_event), RunMode::Interactive, |_, _| {})
+}
+
+// 创建套件会话并在后台运行，图形界面和启动参数发起的运行共用。
+// 套件结束（包括失败）后以会话ID和结束方式调用on_finished
+fn start_suite_session<R: Runtime>(
+    app: AppHandle<R>,
+    config: BenchmarkConfig,
+    on_event: Option<Channel<SuiteEvent>>,
+    mode: RunMode,
+    on_finished: impl FnOnce(&str, SuiteOutcome) + Send + 'static,
+) -> Result<String, IpcError> {
+    // 无效配置在创建会话前拒绝，系统信息不可用时只做不依赖硬件的检查
+    let system_info = collect_system_info().ok();
+    app.state::<SharedBenchmarkRegistry>()
+        .validate(&config, system_info.as_ref())
+        .map_err(|issues| IpcError::from(BenchmarkError::InvalidConfig(issues)))?;
+    
+    let session_id = Uuid::new_v4().to_string();
+    let mut emitter = session_emitter(&app, &session_id);
+    if let Some(on_event) = on_event {
+        let broadcast = *app.state::<SharedLegacyEvents>().lock()?;
+        emitter = emitter.with_channel(on_event, broadcast);
+    }
+    let sessions = app.state::<TestSessions>().inner().clone();
+    
+    // 初始化测试会话，排队等待后台任务开始
+    {
+        let mut sessions_guard = sessions.lock()?;
+        let mut record = SessionRecord::new(Some(config.clone()));
+        transition_session(&emitter, &mut record, TestStatus::Queued, None).map_err(IpcError::from)?;
+        sessions_guard.insert(session_id.clone(), record);
+        // 会话记录落盘失败不影响测试本身
+        let _ = sessions_guard.persist();
+    }
+    
+    // 在后台线程中运行测试
+    let results = app.state::<SharedResultStore>().inner().clone();
+    let upload = app.state::<SharedUploadSettings>().inner().clone();
+    let heartbeat = app.state::<SharedHeartbeat>().lock()?.clone();
+    
+    tauri::async_runtime::spawn(run_heartbeat_watchdog(emitter.clone(), sessions.clone(), heartbeat));
+    tauri::async_runtime::spawn(run_metric_flusher(emitter.clone(), sessions.clone()));
+    tauri::async_runtime::spawn(async move {
+        // 套件任务本身panic时JoinHandle返回错误，同样按失败结束会话，不会一直停留在运行状态
+        // 套件中的日志都归入该会话：写入会话日志文件，Info及以上同时进入会话记录
+        let span = logging::session_span(emitter.session_id(), &sessions);
+        let suite = tokio::spawn(
+            run_full_benchmark_suite(app.clone(), emitter.clone(), config, sessions.clone(), results, upload, mode).instrument(span),
+        );
+        let outcome = suite.await.unwrap_or_else(|e| match e.try_into_panic() {
+            Ok(payload) => Err(BenchmarkError::from_panic(TestType::Suite.as_str(), payload.as_ref())),
+            Err(e) => Err(BenchmarkError::Internal {
+                test: TestType::Suite.as_str().to_string(),
+                panic_message: e.to_string(),
+            }),
+        });
+        match outcome {
+            Ok(outcome) => on_finished(emitter.session_id(), outcome),
+            Err(e) => {
+                // 发送错误事件
+                emitter.emit(events::BENCHMARK_ERROR, emitter.render(&Message::new("suite.failed").param("error", &e)));
+                spawn_completion_webhook(&app, &emitter, &sessions, CompletionNotification::failed(emitter.session_id(), e.to_string()));
+                
+                // 更新会话状态
+                append_session_log(&sessions, emitter.session_id(), LogLevel::Error, format!("测试套件失败: {}", e));
+                {
+                    let mut sessions_guard = sessions.lock().unwrap();
+                    if let Some(record) = sessions_guard.get_mut(emitter.session_id()) {
+                        // 已被取消的会话保持取消状态
+                        let code = IpcError::from(e).code;
+                        let _ = transition_session(&emitter, record, TestStatus::Failed, Some(&code));
+                    }
+                    let _ = sessions_guard.persist();
+                }
+                emitter.finish();
+                on_finished(emitter.session_id(), SuiteOutcome::Failure);
+            }
+        }
+    });
+    
+    Ok(session_id)
+}
+
+// Tauri命令：取消测试
+#[tauri::command]
+async fn cancel_benchmark(
+    app: AppHandle,
+    session_id: String,
+    sessions: tauri::State<'_, TestSessions>,
+) -> Result<(), IpcError> {
+    cancel_session(&session_emitter(&app, &session_id), &sessions)
+}
+
+// 把会话标记为取消，运行中的测试在下一次进度检查时（通常不超过半秒）停止
+fn cancel_session<R: Runtime>(emitter: &ProgressEmitter<R>, sessions: &TestSessions) -> Result<(), IpcError> {
+    {
+        let mut sessions_guard = sessions.lock()?;
+        let Some(record) = sessions_guard.get_mut(emitter.session_id()) else {
+            return Err(IpcError::session_not_found(emitter.session_id()));
+        };
+        transition_session(emitter, record, TestStatus::Cancelled, Some("CANCELLED")).map_err(IpcError::from)?;
+        let _ = sessions_guard.persist();
+    }
+    append_session_log(sessions, emitter.session_id(), LogLevel::Warning, "用户取消了测试");
+    Ok(())
+}
+
+// Tauri命令：获取所有测试会话
+#[tauri::command]
+async fn get_all_test_sessions(
+    sessions: tauri::State<'_, TestSessions>,
+) -> Result<Vec<TestSession>, IpcError> {
+    let sessions_guard = sessions.lock()?;
+    let test_sessions: Vec<TestSession> = sessions_guard
+        .iter()
+        .map(|(session_id, record)| record.to_test_session(session_id))
+        .collect();
+    Ok(test_sessions)
+}
+
+// Tauri命令：获取会话日志，since_index用于增量拉取
+#[tauri::command]
+async fn get_session_logs(
+    session_id: String,
+    since_index: Option<u64>,
+    sessions: tauri::State<'_, TestSessions>,
+) -> Result<Vec<LogEntry>, IpcError> {
+    let sessions_guard = sessions.lock()?;
+    sessions_guard
+        .get(&session_id)
+        .map(|record| record.logs.since(since_index.unwrap_or(0)))
+        .ok_or_else(|| IpcError::session_not_found(&session_id))
+}
+
+// 向会话日志追加一条记录，经tracing同时写入会话日志文件，会话已被清理时忽略。
+// 调用时不能持有会话记录的锁
+fn append_session_log(sessions: &TestSessions, session_id: &str, level: LogLevel, message: impl Into<String>) {
+    logging::session_log(sessions, session_id, level, message);
+}
+
+/// 把节流后实际发送的进度同步到会话记录，供get_session_status和get_ui_state轮询
+fn record_session_progress(
+    sessions: &TestSessions,
+    session_id: &str,
+    test_type: &str,
+    progress: &SuiteProgress,
+    test_progress: f64,
+    phase: Option<String>,
+) {
+    if let Some(record) = sessions.lock().unwrap().get_mut(session_id) {
+        record.record_progress(test_type, progress);
+        record.record_test_progress(test_type, test_progress, phase);
+    }
+}
+
+// 警告经去重后发送，同时记入会话记录供get_ui_state轮询
+fn warn_session<R: Runtime>(emitter: &ProgressEmitter<R>, sessions: &TestSessions, warning: TestWarningEvent) {
+    if let Some(record) = sessions.lock().unwrap().get_mut(emitter.session_id()) {
+        record.record_warning(warning.clone());
+    }
+    emitter.warn(warning);
+}
+
+// Tauri命令：获取渲染运行界面所需的全部状态。只读取会话记录，不经过事件系统，
+// 事件无法可靠送达WebView时前端改为每秒轮询此命令
+#[tauri::command]
+async fn get_ui_state(session_id: String, sessions: tauri::State<'_, TestSessions>) -> Result<UiStateSnapshot, IpcError> {
+    let sessions_guard = sessions.lock()?;
+    sessions_guard
+        .get(&session_id)
+        .map(|record| record.ui_state(&session_id))
+        .ok_or_else(|| IpcError::session_not_found(&session_id))
+}
+
+// Tauri命令：分页查询历史测试结果
+#[tauri::command]
+async fn get_result_history(
+    query: Option<HistoryQuery>,
+    results: tauri::State<'_, SharedResultStore>,
+) -> Result<HistoryPage, IpcError> {
+    let results_guard = results.lock()?;
+    results_guard.query(&query.unwrap_or_default()).map_err(IpcError::from)
+}
+
+// Tauri命令：按会话ID读取一次套件运行的完整结果，错过完成事件时据此取回结果
+#[tauri::command]
+async fn get_benchmark_result(
+    session_id: String,
+    results: tauri::State<'_, SharedResultStore>,
+) -> Result<TestResult, IpcError> {
+    results.lock()?.load_session(&session_id).map_err(IpcError::from)
+}
+
+// Tauri命令：列出各次套件运行（会话ID、时间和总分），最新的在前
+#[tauri::command]
+async fn list_benchmark_history(results: tauri::State<'_, SharedResultStore>) -> Result<Vec<HistoryEntry>, IpcError> {
+    Ok(results.lock()?.session_history())
+}
+
+// Tauri命令：删除一次套件运行的结果，重复运行时各次的原始结果一并删除
+#[tauri::command]
+async fn delete_benchmark_result(
+    session_id: String,
+    results: tauri::State<'_, SharedResultStore>,
+) -> Result<DeletionReport, IpcError> {
+    results.lock()?.delete_session(&session_id, false).map_err(IpcError::from)
+}
+
+// Tauri命令：删除单个历史结果（含关联的导出文件）
+#[tauri::command]
+async fn delete_result(
+    result_id: String,
+    dry_run: Option<bool>,
+    results: tauri::State<'_, SharedResultStore>,
+) -> Result<DeletionReport, IpcError> {
+    let mut results_guard = results.lock()?;
+    results_guard.delete(&result_id, dry_run.unwrap_or(false)).map_err(IpcError::from)
+}
+
+// Tauri命令：批量清理历史结果，older_than为空时清空全部
+#[tauri::command]
+async fn clear_result_history(
+    older_than: Option<String>,
+    dry_run: Option<bool>,
+    results: tauri::State<'_, SharedResultStore>,
+) -> Result<DeletionReport, IpcError> {
+    let mut results_guard = results.lock()?;
+    results_guard
+        .clear(older_than.as_deref(), dry_run.unwrap_or(false))
+        .map_err(IpcError::from)
+}
+
+// Tauri命令：把旧版本保存的未压缩结果改写为压缩格式，返回节省的空间
+#[tauri::command]
+async fn compact_result_store(results: tauri::State<'_, SharedResultStore>) -> Result<CompactionReport, IpcError> {
+    results.lock()?.compact().map_err(IpcError::from)
+}
+
+// Tauri命令：删除被标记为重复的历史结果，每组只保留最早的一个
+#[tauri::command]
+async fn merge_duplicates(
+    dry_run: Option<bool>,
+    results: tauri::State<'_, SharedResultStore>,
+) -> Result<DeletionReport, IpcError> {
+    results.lock()?.merge_duplicates(dry_run.unwrap_or(false)).map_err(IpcError::from)
+}
+
+// Tauri命令：对比两个历史结果，未提供阈值时使用默认的回归阈值
+#[tauri::command]
+async fn compare_results(
+    baseline_id: String,
+    candidate_id: String,
+    thresholds: Option<RegressionThresholds>,
+    results: tauri::State<'_, SharedResultStore>,
+) -> Result<ResultComparison, IpcError> {
+    let results_guard = results.lock()?;
+    results_guard
+        .compare(&baseline_id, &candidate_id, &thresholds.unwrap_or_default())
+        .map_err(IpcError::from)
+}
+
+// Tauri命令：从TOML文件读取回归阈值
+#[tauri::command]
+async fn load_regression_thresholds(path: String) -> Result<RegressionThresholds, IpcError> {
+    RegressionThresholds::from_file(&path).map_err(IpcError::from)
+}
+
+// Tauri命令：把历史结果与参考机器对比，给出各项指标所在的档位和大致百分位
+#[tauri::command]
+async fn rank_result(
+    result_id: String,
+    results: tauri::State<'_, SharedResultStore>,
+    dataset: tauri::State<'_, SharedReferenceDataset>,
+) -> Result<RankingReport, IpcError> {
+    let result = results.lock()?.load(&result_id)?;
+    Ok(dataset.lock()?.rank(&result))
+}
+
+// Tauri命令：使用用户提供的参考数据（校验后复制到应用数据目录），path为空时恢复内置数据
+#[tauri::command]
+async fn set_reference_dataset<R: Runtime>(
+    app: AppHandle<R>,
+    path: Option<String>,
+    dataset: tauri::State<'_, SharedReferenceDataset>,
+) -> Result<ReferenceDataset, IpcError> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| BenchmarkError::DataSaveError(format!("无法获取应用数据目录: {}", e)))?;
+    let copy_path = app_data_dir.join(REFERENCE_DATASET_FILE);
+    let loaded = match path {
+        Some(path) => {
+            let loaded = ReferenceDataset::from_file(&path)?;
+            std::fs::copy(&path, &copy_path)
+                .map_err(|e| BenchmarkError::DataSaveError(format!("无法保存参考数据: {}", e)))?;
+            loaded
+        }
+        None => {
+            if copy_path.exists() {
+                std::fs::remove_file(&copy_path)
+                    .map_err(|e| BenchmarkError::DataSaveError(format!("无法删除参考数据: {}", e)))?;
+            }
+            ReferenceDataset::embedded()?
+        }
+    };
+    *dataset.lock()? = loaded.clone();
+    Ok(loaded)
+}
+
+// Tauri命令：导入外部导出的结果文件，重复导入时返回已有结果的ID
+#[tauri::command]
+async fn import_result(
+    path: String,
+    results: tauri::State<'_, SharedResultStore>,
+) -> Result<String, IpcError> {
+    let mut results_guard = results.lock()?;
+    results_guard
+        .import_file(std::path::Path::new(&path))
+        .map_err(IpcError::from)
+}
+
+// Tauri命令：从TOML或JSON文件读取测试配置，未知字段被忽略并在unknownKeys中列出
+#[tauri::command]
+async fn load_benchmark_config(path: String) -> Result<LoadedConfig, IpcError> {
+    BenchmarkConfig::load_file(&path).map_err(IpcError::from)
+}
+
+// Tauri命令：把测试配置保存为TOML或JSON文件，格式由扩展名决定
+#[tauri::command]
+async fn save_benchmark_config(config: BenchmarkConfig, path: String) -> Result<(), IpcError> {
+    config.save_to_file(&path).map_err(IpcError::from)
+}
+
+// Tauri命令：配置结果上传，endpoint为空表示关闭上传
+#[tauri::command]
+async fn configure_result_upload(
+    endpoint: Option<String>,
+    token: Option<String>,
+    auto_upload: bool,
+    upload: tauri::State<'_, SharedUploadSettings>,
+) -> Result<(), IpcError> {
+    let config = UploadConfig {
+        endpoint,
+        token,
+        auto_upload,
+    };
+    config.validate().map_err(IpcError::from)?;
+    
+    let mut upload_guard = upload.lock()?;
+    config.save(&upload_guard.path).map_err(IpcError::from)?;
+    upload_guard.config = config;
+    Ok(())
+}
+
+// Tauri命令：配置套件完成通知，模板中的{{summary}}等占位符按通知内容替换，为空时发送默认的JSON
+#[tauri::command]
+async fn configure_completion_webhook(
+    url: Option<String>,
+    template: Option<String>,
+    enabled: bool,
+    webhook: tauri::State<'_, SharedWebhookSettings>,
+) -> Result<(), IpcError> {
+    let config = WebhookConfig { url, template, enabled };
+    config.validate()?;
+    
+    let mut webhook_guard = webhook.lock()?;
+    config.save(&webhook_guard.path)?;
+    webhook_guard.config = config;
+    Ok(())
+}
+
+// Tauri命令：设置报告和通知摘要中数值的展示格式，保存到应用数据目录。JSON导出不受影响
+#[tauri::command]
+async fn set_format_options(
+    options: FormatOptions,
+    format: tauri::State<'_, SharedFormatSettings>,
+) -> Result<(), IpcError> {
+    let mut format_guard = format.lock()?;
+    options.save(&format_guard.path)?;
+    format_guard.options = options;
+    Ok(())
+}
+
+// 用户开启完成通知时在后台发送，失败只记录日志和低严重程度的警告，不影响会话结果
+fn spawn_completion_webhook<R: Runtime>(
+    app: &AppHandle<R>,
+    emitter: &ProgressEmitter<R>,
+    sessions: &TestSessions,
+    notification: CompletionNotification,
+) {
+    let Some(settings) = app.try_state::<SharedWebhookSettings>() else {
+        return;
+    };
+    let config = settings.lock().unwrap().config.clone();
+    if !config.is_enabled() {
+        return;
+    }
+    let format = app
+        .try_state::<SharedFormatSettings>()
+        .map(|settings| settings.lock().unwrap().options)
+        .unwrap_or_default();
+    let notification = notification.with_format(format);
+    let emitter = emitter.clone();
+    let sessions = sessions.clone();
+    tokio::spawn(async move {
+        let outcome = match WebhookNotifier::new(&config, webhook_options()) {
+            Ok(notifier) => notifier.notify(&notification).await,
+            Err(e) => Err(e.to_string()),
+        };
+        let Err(error) = outcome else {
+            return;
+        };
+        let session_id = emitter.session_id().to_string();
+        append_session_log(&sessions, &session_id, LogLevel::Warning, format!("完成通知发送失败: {}", error));
+        let warning = Message::new("suite.webhook_failed").param("error", error);
+        warn_session(&emitter, &sessions, TestWarningEvent {
+            session_id,
+            test_type: TestType::Suite,
+            warning_type: "completion_webhook_failed".to_string(),
+            message: emitter.render(&warning),
+            message_key: warning.key.to_string(),
+            params: warning.params,
+            severity: WarningSeverity::Low,
+        });
+    });
+}
+
+// Tauri命令：手动上传一个历史结果
+#[tauri::command]
+async fn upload_result(
+    result_id: String,
+    results: tauri::State<'_, SharedResultStore>,
+    upload: tauri::State<'_, SharedUploadSettings>,
+) -> Result<UploadStatus, IpcError> {
+    let config = upload.lock()?.config.clone();
+    upload_stored_result(&result_id, results.inner(), &config)
+        .await
+        .map_err(IpcError::from)
+}
+
+// Tauri命令：从通过完整性校验的历史结果生成排行榜条目
+#[tauri::command]
+async fn export_leaderboard_entry(
+    result_id: String,
+    results: tauri::State<'_, SharedResultStore>,
+    dataset: tauri::State<'_, SharedReferenceDataset>,
+) -> Result<LeaderboardEntry, IpcError> {
+    let result = results.lock()?.load(&result_id)?;
+    LeaderboardEntry::from_result(&result, &*dataset.lock()?).map_err(IpcError::from)
+}
+
+// Tauri命令：把历史结果的排行榜条目提交到配置的上传地址，重试规则与上传结果相同
+#[tauri::command]
+async fn submit_leaderboard_entry(
+    result_id: String,
+    results: tauri::State<'_, SharedResultStore>,
+    upload: tauri::State<'_, SharedUploadSettings>,
+    dataset: tauri::State<'_, SharedReferenceDataset>,
+) -> Result<UploadStatus, IpcError> {
+    let config = upload.lock()?.config.clone();
+    let uploader = ResultUploader::new(&config, UploadOptions::default())?;
+    let result = results.lock()?.load(&result_id)?;
+    let entry = LeaderboardEntry::from_result(&result, &*dataset.lock()?)?;
+    Ok(uploader.submit_leaderboard(&entry).await)
+}
+
+// Tauri命令：解析服务器导出的排行榜条目，返回可在对比界面中展示的伪结果，不写入结果存储
+#[tauri::command]
+async fn import_leaderboard_entries(json: String) -> Result<Vec<ResultSummary>, IpcError> {
+    let entries = parse_leaderboard_entries(&json)?;
+    Ok(entries.iter().map(LeaderboardEntry::to_summary).collect())
+}
+
+// Tauri命令：按指定格式导出历史结果。composite_v1按分区和负载组织，带相对基准系统的归一化得分；csv每个数值一行，用于电子表格；
+// html为可直接打开的独立报告
+#[tauri::command]
+async fn export_results(
+    result_id: String,
+    format: ResultExportFormat,
+    path: String,
+    results: tauri::State<'_, SharedResultStore>,
+) -> Result<(), IpcError> {
+    let result = results.lock()?.load(&result_id)?;
+    export_result(&result_id, &result, format, std::path::Path::new(&path)).map_err(IpcError::from)
+}
+
+// Tauri命令：把一次套件运行保存的结果导出为CSV（section,metric,value,unit，每个数值一行），等同于export_results的csv格式
+#[tauri::command]
+async fn export_result_csv(
+    session_id: String,
+    path: String,
+    results: tauri::State<'_, SharedResultStore>,
+) -> Result<(), IpcError> {
+    let result = results.lock()?.load_session(&session_id)?;
+    export_result(&session_id, &result, ResultExportFormat::Csv, std::path::Path::new(&path)).map_err(IpcError::from)
+}
+
+// Tauri命令：把已保存的测试结果导出为独立的HTML报告（样式内联，不引用外部资源）
+#[tauri::command]
+async fn export_result_html(
+    result_id: String,
+    path: String,
+    results: tauri::State<'_, SharedResultStore>,
+) -> Result<(), IpcError> {
+    let result = results.lock()?.load(&result_id)?;
+    export_result(&result_id, &result, ResultExportFormat::Html, std::path::Path::new(&path)).map_err(IpcError::from)
+}
+
+// Tauri命令：查询结果的上传状态
+#[tauri::command]
+async fn get_upload_status(
+    result_id: String,
+    results: tauri::State<'_, SharedResultStore>,
+) -> Result<Option<UploadStatus>, IpcError> {
+    let results_guard = results.lock()?;
+    results_guard
+        .summary(&result_id)
+        .map(|s| s.upload.clone())
+        .ok_or_else(|| BenchmarkError::DataLoadError(format!("结果不存在: {}", result_id)).into())
+}
+
+// 上传已保存的结果，并把状态记录到结果存储中
+async fn upload_stored_result(
+    result_id: &str,
+    results: &SharedResultStore,
+    config: &UploadConfig,
+) -> Result<UploadStatus, BenchmarkError> {
+    let uploader = ResultUploader::new(config, UploadOptions::default())?;
+    let result = {
+        let mut results_guard = results.lock().unwrap();
+        let result = results_guard.load(result_id)?;
+        results_guard.set_upload_status(result_id, UploadStatus::pending())?;
+        result
+    };
+    
+    let status = uploader.upload(&result).await;
+    results.lock().unwrap().set_upload_status(result_id, status.clone())?;
+    Ok(status)
+}
+
+// Tauri命令：预览某个结果的匿名摘要，即提交时实际发送的全部内容
+#[tauri::command]
+async fn preview_anonymous_summary(
+    result_id: String,
+    results: tauri::State<'_, SharedResultStore>,
+) -> Result<AnonymousSummary, IpcError> {
+    let result = results.lock()?.load(&result_id).map_err(IpcError::from)?;
+    Ok(AnonymousSummary::from(&result))
+}
+
+// Tauri命令：设置是否同意提交匿名摘要及提交地址
+#[tauri::command]
+async fn configure_anonymous_summary(
+    consent: bool,
+    endpoint: Option<String>,
+    anonymous: tauri::State<'_, SharedAnonymousSettings>,
+) -> Result<AnonymousSummaryConfig, IpcError> {
+    let mut anonymous_guard = anonymous.lock()?;
+    let mut config = anonymous_guard.config.clone();
+    config.endpoint = endpoint;
+    config.set_consent(consent);
+    config.validate().map_err(IpcError::from)?;
+
+    config.save(&anonymous_guard.path).map_err(IpcError::from)?;
+    anonymous_guard.config = config.clone();
+    Ok(config)
+}
+
+// Tauri命令：获取匿名摘要的同意状态和提交地址
+#[tauri::command]
+async fn get_anonymous_summary_settings(
+    anonymous: tauri::State<'_, SharedAnonymousSettings>,
+) -> Result<AnonymousSummaryConfig, IpcError> {
+    Ok(anonymous.lock()?.config.clone())
+}
+
+// Tauri命令：提交某个结果的匿名摘要，未同意时返回错误
+#[tauri::command]
+async fn submit_anonymous_summary(
+    result_id: String,
+    results: tauri::State<'_, SharedResultStore>,
+    anonymous: tauri::State<'_, SharedAnonymousSettings>,
+) -> Result<UploadStatus, IpcError> {
+    let config = anonymous.lock()?.config.clone();
+    submit_stored_summary(&result_id, results.inner(), &config, UploadOptions::default())
+        .await
+        .map_err(IpcError::from)
+}
+
+// 提交已保存结果的匿名摘要，内容与preview_anonymous_summary返回的相同
+async fn submit_stored_summary(
+    result_id: &str,
+    results: &SharedResultStore,
+    config: &AnonymousSummaryConfig,
+    options: UploadOptions,
+) -> Result<UploadStatus, BenchmarkError> {
+    let uploader = ResultUploader::anonymous(config, options)?;
+    let result = results.lock().unwrap().load(result_id)?;
+    Ok(uploader.submit_anonymous(&AnonymousSummary::from(&result)).await)
+}
+
+// Tauri命令：获取系统监控数据（整机CPU占用、内存占用和CPU温度）
+#[tauri::command]
+async fn get_system_monitoring_data(
+    monitor: tauri::State<'_, SharedSystemMonitor>,
+) -> Result<SystemMonitoringData, IpcError> {
+    Ok(monitor.lock()?.sample())
+}
+
+// Tauri命令：清理已完成的测试会话
+#[tauri::command]
+async fn cleanup_completed_sessions(
+    sessions: tauri::State<'_, TestSessions>,
+) -> Result<usize, IpcError> {
+    let mut sessions_guard = sessions.lock()?;
+    let initial_count = sessions_guard.len();
+    
+    sessions_guard.retain(|_, record| !record.is_terminal());
+    
+    let cleaned_count = initial_count - sessions_guard.len();
+    let _ = sessions_guard.persist();
+    Ok(cleaned_count)
+}
+
+// Tauri命令：配置会话保留时间和后台清理间隔（秒）
+#[tauri::command]
+async fn configure_session_retention(
+    ttl_secs: u64,
+    interval_secs: u64,
+    retention: tauri::State<'_, SharedRetention>,
+) -> Result<(), IpcError> {
+    let config = RetentionConfig { ttl_secs, interval_secs };
+    config.validate().map_err(|e| IpcError::invalid_argument("intervalSecs", e))?;
+    *retention.lock()? = config;
+    Ok(())
+}
+
+// Tauri命令：配置心跳间隔和停滞警告阈值（秒），对之后开始的测试生效
+#[tauri::command]
+async fn configure_heartbeat(
+    interval_secs: u64,
+    stall_threshold_secs: u64,
+    heartbeat: tauri::State<'_, SharedHeartbeat>,
+) -> Result<(), IpcError> {
+    let config = HeartbeatConfig { interval_secs, stall_threshold_secs };
+    config.validate().map_err(|e| {
+        let field = if interval_secs == 0 { "intervalSecs" } else { "stallThresholdSecs" };
+        IpcError::invalid_argument(field, e)
+    })?;
+    *heartbeat.lock()? = config;
+    Ok(())
+}
+
+// Tauri命令：运行时调整会话日志文件的详细程度（error、warn、info、debug、trace），
+// 会话记录中的日志始终包含Info及以上
+#[tauri::command]
+async fn set_log_level(level: String) -> Result<(), IpcError> {
+    let filter = logging::parse_level(&level)
+        .ok_or_else(|| IpcError::invalid_argument("level", format!("未知的日志级别: {}，可选 error、warn、info、debug、trace", level)))?;
+    if let Some(control) = logging::control() {
+        control.set_level(filter);
+    }
+    Ok(())
+}
+
+// 后台任务：会话运行中长时间没有进度时发送心跳，超过阈值时发出停滞警告。
+// 测试本身在另一个线程上阻塞运行，看门狗不受其影响
+async fn run_heartbeat_watchdog<R: Runtime>(emitter: ProgressEmitter<R>, sessions: TestSessions, config: HeartbeatConfig) {
+    let mut watchdog = Watchdog::new(config);
+    let mut stalled_at = None; // 发出停滞警告时的最近进度时间
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        let status = sessions.lock().unwrap().get(emitter.session_id()).map(|record| record.status.clone());
+        match status {
+            None => break,
+            Some(status) if status.is_terminal() => break,
+            // 排队和暂停中的会话本来就没有进度
+            Some(TestStatus::Running) => {}
+            Some(_) => {
+                emitter.touch();
+                continue;
+            }
+        }
+
+        let (last_progress, phase) = emitter.last_progress();
+        // 进度恢复，停滞条件解除
+        if stalled_at.is_some_and(|at| at != last_progress) {
+            stalled_at = None;
+            emitter.clear_warning(TestType::Suite, "stall");
+        }
+        for action in watchdog.check(last_progress, std::time::Instant::now()) {
+            match action {
+                WatchdogAction::Heartbeat { seconds_since_progress } => {
+                    emitter.emit(events::BENCHMARK_HEARTBEAT, HeartbeatEvent {
+                        session_id: emitter.session_id().to_string(),
+                        phase: phase.clone(),
+                        seconds_since_progress,
+                    });
+                }
+                WatchdogAction::Stalled { seconds_since_progress } => {
+                    stalled_at = Some(last_progress);
+                    append_session_log(&sessions, emitter.session_id(), LogLevel::Warning, format!(
+                        "阶段 {} 已 {:.0} 秒没有进度",
+                        phase, seconds_since_progress
+                    ));
+                    let warning = Message::new("suite.stalled")
+                        .param("phase", &phase)
+                        .param("seconds", format!("{:.0}", seconds_since_progress));
+                    warn_session(&emitter, &sessions, TestWarningEvent {
+                        session_id: emitter.session_id().to_string(),
+                        test_type: TestType::Suite,
+                        warning_type: "stall".to_string(),
+                        message: emitter.render(&warning),
+                        message_key: warning.key.to_string(),
+                        params: warning.params,
+                        severity: WarningSeverity::High,
+                    });
+                }
+            }
+        }
+    }
+}
+
+// Tauri命令：开启或关闭Prometheus指标导出，端口为空时使用默认端口、为0时由系统分配。
+// 默认只绑定127.0.0.1，绑定其他地址须allow_remote为true
+#[tauri::command]
+async fn configure_metrics_exporter(
+    enabled: bool,
+    port: Option<u16>,
+    bind_address: Option<String>,
+    allow_remote: Option<bool>,
+    telemetry: tauri::State<'_, SharedTelemetry>,
+    exporter: tauri::State<'_, SharedMetricsExporter>,
+) -> Result<ExporterStatus, IpcError> {
+    let mut exporter = exporter.lock().await;
+    if !enabled {
+        exporter.stop().await;
+        return Ok(exporter.status());
+    }
+    let address = exporter_address(bind_address.as_deref(), port.unwrap_or(DEFAULT_EXPORTER_PORT))?;
+    exporter
+        .start(telemetry.inner().clone(), address, allow_remote.unwrap_or(false))
+        .await
+        .map_err(IpcError::from)
+}
+
+// Tauri命令：把会话的时间序列导出为CSV。per_stream（默认）时path是目录，每个序列一个文件；
+// long时path是文件，列为timestamp,stream,metric,value。返回写出的文件路径
+#[tauri::command]
+async fn export_timeseries(
+    session_id: String,
+    path: String,
+    format: Option<TimeseriesFormat>,
+    timeseries: tauri::State<'_, SharedTimeseries>,
+) -> Result<Vec<String>, IpcError> {
+    let series = timeseries
+        .session(&session_id)
+        .filter(|series| !series.is_empty())
+        .ok_or_else(|| IpcError::session_not_found(&session_id))?;
+    let written = series.export(std::path::Path::new(&path), format.unwrap_or_default())?;
+    Ok(written.iter().map(|file| file.display().to_string()).collect())
+}
+
+// Tauri命令：开启后套件事件在会话通道之外仍然全局广播，供尚未迁移到通道的前端使用
+#[tauri::command]
+async fn configure_legacy_events(
+    enabled: bool,
+    legacy: tauri::State<'_, SharedLegacyEvents>,
+) -> Result<(), IpcError> {
+    *legacy.lock()? = enabled;
+    Ok(())
+}
+
+// Tauri命令：设置每个会话每秒最多发送的进度事件数，0表示不限速
+#[tauri::command]
+async fn configure_progress_events(
+    max_per_second: u32,
+    throttle: tauri::State<'_, SharedProgressThrottle>,
+) -> Result<(), IpcError> {
+    throttle.set_max_per_second(max_per_second);
+    Ok(())
+}
+
+// 后台任务：定期清理过期的终止会话并通知前端
+async fn run_session_cleanup(
+    emitter: ProgressEmitter<tauri::Wry>,
+    sessions: TestSessions,
+    results: SharedResultStore,
+    retention: SharedRetention,
+) {
+    loop {
+        let config = *retention.lock().unwrap();
+        tokio::time::sleep(std::time::Duration::from_secs(config.interval_secs)).await;
+        
+        let ttl = chrono::Duration::seconds(config.ttl_secs.min(i64::MAX as u64) as i64);
+        let removed = {
+            let mut sessions_guard = sessions.lock().unwrap();
+            let mut results_guard = results.lock().unwrap();
+            let removed = cleanup_expired_sessions(&mut sessions_guard, &mut results_guard, ttl, chrono::Utc::now());
+            if removed > 0 {
+                let _ = sessions_guard.persist();
+            }
+            removed
+        };
+        if removed > 0 {
+            emitter.emit(events::SESSIONS_CLEANED, removed);
+        }
+    }
+}
+
+// Tauri命令：暂停正在运行的测试，运行器在下一次进度检查时停下等待，暂停的时间不计入测试时长和分数
+#[tauri::command]
+async fn pause_benchmark(
+    app: AppHandle,
+    session_id: String,
+    sessions: tauri::State<'_, TestSessions>,
+) -> Result<(), IpcError> {
+    pause_session(&session_emitter(&app, &session_id), &sessions)
+}
+
+// Tauri命令：恢复暂停的测试，从暂停处继续
+#[tauri::command]
+async fn resume_benchmark(
+    app: AppHandle,
+    session_id: String,
+    sessions: tauri::State<'_, TestSessions>,
+) -> Result<(), IpcError> {
+    resume_session(&session_emitter(&app, &session_id), &sessions)
+}
+
+// 把运行中的会话标记为暂停，其他状态返回InvalidTransition
+fn pause_session<R: Runtime>(emitter: &ProgressEmitter<R>, sessions: &TestSessions) -> Result<(), IpcError> {
+    {
+        let mut sessions_guard = sessions.lock()?;
+        let Some(record) = sessions_guard.get_mut(emitter.session_id()) else {
+            return Err(IpcError::session_not_found(emitter.session_id()));
+        };
+        transition_session(emitter, record, TestStatus::Paused, None).map_err(IpcError::from)?;
+        let _ = sessions_guard.persist();
+    }
+    append_session_log(sessions, emitter.session_id(), LogLevel::Info, "用户暂停了测试");
+    Ok(())
+}
+
+// 恢复暂停的会话，并发送一条说明已恢复的进度事件，进度取暂停前的值
+fn resume_session<R: Runtime>(emitter: &ProgressEmitter<R>, sessions: &TestSessions) -> Result<(), IpcError> {
+    let progress = {
+        let mut sessions_guard = sessions.lock()?;
+        let Some(record) = sessions_guard.get_mut(emitter.session_id()) else {
+            return Err(IpcError::session_not_found(emitter.session_id()));
+        };
+        // Queued→Running也是合法转换，恢复只接受暂停中的会话
+        if record.status != TestStatus::Paused {
+            return Err(BenchmarkError::InvalidTransition {
+                from: record.status.clone(),
+                to: TestStatus::Running,
+            }
+            .into());
+        }
+        transition_session(emitter, record, TestStatus::Running, None).map_err(IpcError::from)?;
+        let current = record.current_test.clone().unwrap_or_default();
+        let message = Message::new("suite.resumed");
+        let progress = BenchmarkProgress {
+            session_id: emitter.session_id().to_string(),
+            current_test: TestType::ALL
+                .into_iter()
+                .find(|test_type| test_type.as_str() == current)
+                .map_or(current.clone(), |test_type| TestLabels::of(test_type).name),
+            overall_progress: record.overall_progress,
+            test_progress: record.test_progress.iter().find(|test| test.test_type == current).map_or(0.0, |test| test.progress),
+            message: emitter.render(&message),
+            message_key: message.key.to_string(),
+            params: message.params,
+            estimated_time_remaining: record.eta_seconds,
+            estimated_completion_time: record.estimated_completion().map(|t| t.to_rfc3339()),
+        };
+        let _ = sessions_guard.persist();
+        progress
+    };
+    emitter.emit(events::BENCHMARK_PROGRESS, progress);
+    append_session_log(sessions, emitter.session_id(), LogLevel::Info, "测试已恢复");
+    Ok(())
+}
+
+// Tauri命令：获取测试状态
+#[tauri::command]
+async fn get_test_status(
+    session_id: String,
+    sessions: tauri::State<'_, TestSessions>,
+) -> Result<TestStatus, IpcError> {
+    let sessions_guard = sessions.lock()?;
+    sessions_guard.get(&session_id)
+        .map(|record| record.status.clone())
+        .ok_or_else(|| IpcError::session_not_found(&session_id))
+}
+
+// Tauri命令：运行单个CPU基准测试
+#[tauri::command]
+async fn run_cpu_benchmark(
+    app: AppHandle,
+    config: CpuTestConfig,
+) -> Result<CpuTestResult, IpcError> {
+    let benchmark = CpuBenchmark::new(config);
+    // 单项测试不属于任何会话，按测试类型限速
+    let emitter = session_emitter(&app, "cpu");
+    
+    // 创建进度回调
+    let progress_callback = move |progress: f64, message: Message| {
+        emitter.emit_progress(events::CPU_TEST_PROGRESS, progress, || ProgressUpdate {
+            progress,
+            message: emitter.render(&message),
+            message_key: message.key.to_string(),
+            params: message.params,
+            test_type: TestType::Cpu,
+        });
+    };
+    
+    benchmark.run_benchmark_with_progress(progress_callback).map_err(IpcError::from)
+}
+
+// Tauri命令：运行单个内存基准测试
+#[tauri::command]
+async fn run_memory_benchmark(
+    app: AppHandle,
+    config: MemoryTestConfig,
+) -> Result<MemoryTestResult, IpcError> {
+    let benchmark = MemoryBenchmark::new(config);
+    // 单项测试不属于任何会话，按测试类型限速
+    let emitter = session_emitter(&app, "memory");
+    
+    // 创建进度回调
+    let progress_callback = move |progress: f64, message: Message| {
+        emitter.emit_progress(events::MEMORY_TEST_PROGRESS, progress, || ProgressUpdate {
+            progress,
+            message: emitter.render(&message),
+            message_key: message.key.to_string(),
+            params: message.params,
+            test_type: TestType::Memory,
+        });
+    };
+    
+    benchmark.run_benchmark_with_progress(progress_callback).map_err(IpcError::from)
+}
+
+// Tauri命令：运行单个存储基准测试
+#[tauri::command]
+async fn run_storage_benchmark(
+    app: AppHandle,
+    config: StorageTestConfig,
+) -> Result<StorageTestResult, IpcError> {
+    let benchmark = StorageBenchmark::new(config);
+    // 单项测试不属于任何会话，按测试类型限速
+    let emitter = session_emitter(&app, "storage");
+    
+    // 创建进度回调
+    let progress_callback = move |progress: f64, message: Message| {
+        emitter.emit_progress(events::STORAGE_TEST_PROGRESS, progress, || ProgressUpdate {
+            progress,
+            message: emitter.render(&message),
+            message_key: message.key.to_string(),
+            params: message.params,
+            test_type: TestType::Storage,
+        });
+    };
+    
+    benchmark.run_benchmark_with_progress(progress_callback).map_err(IpcError::from)
+}
+
+// Tauri命令：运行单个GPU基准测试，本机没有可用的适配器时返回GPU_TEST_ERROR
+#[tauri::command]
+async fn run_gpu_benchmark(
+    app: AppHandle,
+    config: GpuTestConfig,
+) -> Result<GpuTestResult, IpcError> {
+    let benchmark = GpuBenchmark::new(config);
+    // 单项测试不属于任何会话，按测试类型限速
+    let emitter = session_emitter(&app, "gpu");
+    
+    // 创建进度回调
+    let progress_callback = move |progress: f64, message: Message| {
+        emitter.emit_progress(events::GPU_TEST_PROGRESS, progress, || ProgressUpdate {
+            progress,
+            message: emitter.render(&message),
+            message_key: message.key.to_string(),
+            params: message.params,
+            test_type: TestType::Gpu,
+        });
+    };
+    
+    // 适配器初始化和计算内核会阻塞，放到阻塞线程池中运行
+    tokio::task::spawn_blocking(move || benchmark.run_benchmark_with_progress(progress_callback))
+        .await
+        .map_err(|e| IpcError::from(BenchmarkError::GpuTestError(e.to_string())))?
+        .map_err(IpcError::from)
+}
+
+// Tauri命令：运行单个网络基准测试，不指定target时对本机回环服务器测试。
+// 配置按套件中相同的上限检查，超出时在建立任何连接之前拒绝
+#[tauri::command]
+async fn run_network_benchmark(
+    app: AppHandle,
+    config: NetworkTestConfig,
+    cancelled: tauri::State<'_, SharedNetworkCancellation>,
+) -> Result<NetworkTestResult, IpcError> {
+    let issues = network_issues(&config);
+    if issues.iter().any(|issue| issue.severity == IssueSeverity::Error) {
+        return Err(IpcError::from(BenchmarkError::InvalidConfig(issues)));
+    }
+    cancelled.0.store(false, Ordering::SeqCst);
+    let flag = cancelled.inner().clone();
+    let cancellation = CancellationToken::new(move || flag.0.load(Ordering::SeqCst));
+    let benchmark = NetworkBenchmark::new(config);
+    // 单项测试不属于任何会话，按测试类型限速
+    let emitter = session_emitter(&app, "network");
+    
+    // 创建进度回调
+    let progress_callback = move |progress: f64, message: Message| {
+        emitter.emit_progress(events::NETWORK_TEST_PROGRESS, progress, || ProgressUpdate {
+            progress,
+            message: emitter.render(&message),
+            message_key: message.key.to_string(),
+            params: message.params,
+            test_type: TestType::Network,
+        });
+    };
+    
+    // 网络读写是阻塞的，放到阻塞线程池中运行
+    tokio::task::spawn_blocking(move || benchmark.run_benchmark_cancellable(progress_callback, &cancellation))
+        .await
+        .map_err(|e| IpcError::from(BenchmarkError::NetworkTestError(e.to_string())))?
+        .map_err(IpcError::from)
+}
+
+// Tauri命令：取消正在运行的网络测试，正在进行的传输在下一次检查时停止
+#[tauri::command]
+fn cancel_network_benchmark(cancelled: tauri::State<'_, SharedNetworkCancellation>) {
+    cancelled.0.store(true, Ordering::SeqCst);
+}
+
+// Tauri命令：在两个卷之间运行复制测试，进度通过copy-test-progress发送
+#[tauri::command]
+async fn run_copy_benchmark(
+    app: AppHandle,
+    config: CopyTestConfig,
+    cancelled: tauri::State<'_, SharedCopyCancellation>,
+) -> Result<CopyTestResult, IpcError> {
+    cancelled.store(false, Ordering::SeqCst);
+    let flag = cancelled.inner().clone();
+    let cancellation = CancellationToken::new(move || flag.load(Ordering::SeqCst));
+    let emitter = session_emitter(&app, "copy");
+    let timeseries = app.state::<SharedTimeseries>().inner().clone();
+    
+    let progress_callback = move |progress: f64, message: Message| {
+        emitter.emit_progress(events::COPY_TEST_PROGRESS, progress, || ProgressUpdate {
+            progress,
+            message: emitter.render(&message),
+            message_key: message.key.to_string(),
+            params: message.params,
+            test_type: TestType::Storage,
+        });
+    };
+    
+    let result = CopyBenchmark::new(config).run_benchmark_cancellable(progress_callback, &cancellation)?;
+    // 复制测试的持续写入曲线记入"copy"会话的时间序列，采样时间从复制开始（不含生成源文件）算起
+    let started = chrono::Utc::now() - chrono::Duration::milliseconds((result.duration_seconds * 1000.0) as i64);
+    timeseries.record_copy("copy", started, &result);
+    Ok(result)
+}
+
+// Tauri命令：取消正在运行的复制测试，临时文件随即删除
+#[tauri::command]
+fn cancel_copy_benchmark(cancelled: tauri::State<'_, SharedCopyCancellation>) {
+    cancelled.store(true, Ordering::SeqCst);
+}
+
+// Tauri命令：原样返回JSON负载，附带处理耗时，前端据此测量IPC往返开销
+#[tauri::command]
+fn ipc_echo(payload: String) -> IpcEchoReply {
+    benchmark::ipc_overhead::echo(payload, std::time::Instant::now())
+}
+
+// Tauri命令：原样返回原始字节请求体，测量不经过JSON编码的IPC路径
+#[tauri::command]
+fn ipc_echo_raw(request: tauri::ipc::Request<'_>) -> Result<tauri::ipc::Response, IpcError> {
+    match request.body() {
+        tauri::ipc::InvokeBody::Raw(bytes) => Ok(tauri::ipc::Response::new(bytes.clone())),
+        tauri::ipc::InvokeBody::Json(_) => Err(IpcError::invalid_argument("body", "请求体必须是原始字节（ArrayBuffer或Uint8Array）")),
+    }
+}
+
+// Tauri命令：在进程内测量IPC编码和回显的开销
+#[tauri::command]
+async fn run_ipc_benchmark(config: IpcBenchmarkConfig) -> Result<IpcBenchmarkResult, IpcError> {
+    IpcBenchmark::new(config).run_benchmark().map_err(IpcError::from)
+}
+
+// Tauri命令：运行进程启动测试，按配置额外测量创建一个隐藏WebView窗口的耗时
+#[tauri::command]
+async fn run_process_benchmark(app: AppHandle, config: ProcessTestConfig) -> Result<ProcessTestResult, IpcError> {
+    let measure_window = config.measure_window_creation;
+    let mut result = ProcessBenchmark::new(config).run_benchmark().map_err(IpcError::from)?;
+    if measure_window {
+        let started = std::time::Instant::now();
+        let window = tauri::WebviewWindowBuilder::new(&app, "process-benchmark-probe", tauri::WebviewUrl::App("index.html".into()))
+            .visible(false)
+            .build()
+            .map_err(|e| IpcError::from(BenchmarkError::ProcessTestError(format!("创建窗口失败: {}", e))))?;
+        result.window_creation_ms = Some(started.elapsed().as_secs_f64() * 1000.0);
+        let _ = window.destroy();
+    }
+    Ok(result)
+}
+
+// WebView测试通过webview-benchmark-request事件请前端运行负载，未管理协调器（如测试中）时使用临时的协调器
+fn webview_bridge<R: Runtime>(app: &AppHandle<R>) -> WebviewBridge {
+    use tauri::Emitter;
+    let coordinator = app
+        .try_state::<SharedWebviewCoordinator>()
+        .map(|coordinator| coordinator.inner().clone())
+        .unwrap_or_default();
+    let app = app.clone();
+    WebviewBridge::new(coordinator, move |request| {
+        app.emit(events::WEBVIEW_BENCHMARK_REQUEST.as_str(), request).map_err(|e| e.to_string())
+    })
+}
+
+// Tauri命令：单独运行WebView测试，等待前端通过submit_webview_results提交结果，超时未提交时失败
+#[tauri::command]
+async fn start_webview_benchmark(app: AppHandle, config: WebviewTestConfig) -> Result<WebviewTestResult, IpcError> {
+    let bridge = webview_bridge(&app);
+    tokio::task::spawn_blocking(move || bridge.run(&config, &CancellationToken::never()))
+        .await
+        .map_err(|e| IpcError::from(BenchmarkError::WebviewTestError(e.to_string())))?
+        .map_err(IpcError::from)
+}
+
+// Tauri命令：前端提交WebView测试负载的计时，nonce须与当前请求一致
+#[tauri::command]
+fn submit_webview_results(submission: WebviewSubmission, coordinator: tauri::State<'_, SharedWebviewCoordinator>) -> Result<(), IpcError> {
+    coordinator.submit(submission).map_err(IpcError::from)
+}
+
+// Tauri命令：在当前电源计划和高性能计划下各运行一次短时CPU和内存负载并对比，结束时恢复原计划。
+// Windows上须在配置中同意自动切换，其他平台通过power-plan-switch-request请用户手动切换
+#[tauri::command]
+async fn run_power_plan_comparison(app: AppHandle, config: PowerComparisonConfig) -> Result<PowerPlanComparison, IpcError> {
+    use tauri::Emitter;
+    let coordinator = app.state::<SharedPowerSwitchCoordinator>().inner().clone();
+    let emitter = app.clone();
+    let manual = ManualPlanSwitcher::new(coordinator, std::time::Duration::from_secs(config.confirmation_timeout_seconds), move |request| {
+        emitter.emit(events::POWER_PLAN_SWITCH_REQUEST.as_str(), request).map_err(|e| e.to_string())
+    });
+    tokio::task::spawn_blocking(move || {
+        let switcher = platform_switcher(&config, manual);
+        let cancellation = CancellationToken::never();
+        compare_power_plans(
+            &detect_power_info(),
+            switcher.as_deref().map(|switcher| switcher as &dyn PlanSwitcher),
+            || measure_plan(&config, &cancellation),
+            &cancellation,
+        )
+    })
+    .await
+    .map_err(|e| IpcError::from(BenchmarkError::PowerPlanError(e.to_string())))?
+    .map_err(IpcError::from)
+}
+
+// Tauri命令：前端确认用户已经（或拒绝）按提示切换电源计划，nonce须与当前请求一致
+#[tauri::command]
+fn confirm_power_plan_switch(nonce: String, confirmed: bool, coordinator: tauri::State<'_, SharedPowerSwitchCoordinator>) -> Result<(), IpcError> {
+    coordinator.confirm(&nonce, confirmed).map_err(IpcError::from)
+}
+
+// Tauri命令：获取事件名和负载字段，供前端校验绑定
+#[tauri::command]
+fn get_event_schema() -> IpcSchema {
+    ipc::event_schema()
+}
+
+// Tauri命令：获取配置、结果和事件负载的JSON Schema，按类型名索引
+#[tauri::command]
+fn get_json_schemas() -> std::collections::BTreeMap<String, serde_json::Value> {
+    ipc::json_schemas()
+}
+
+// Tauri命令：获取全部错误码及其按当前后端语言的默认消息
+#[tauri::command]
+fn get_error_catalog(locale: tauri::State<'_, SharedLocale>) -> Result<Vec<ErrorCatalogEntry>, IpcError> {
+    Ok(ipc::error_catalog(*locale.lock()?))
+}
+
+// Tauri命令：估算测试配置的耗时、内存和磁盘占用，不运行任何测试
+#[tauri::command]
+fn estimate_benchmark_run(config: BenchmarkConfig, locale: tauri::State<'_, SharedLocale>) -> Result<RunEstimate, IpcError> {
+    let locale = *locale.lock()?;
+    let env = PreflightEnvironment::detect(&default_test_file_path());
+    Ok(estimate_run(&config, &Calibration::default_estimates(), &env, locale))
+}
+
+// Tauri命令：检测需要更高权限的功能（直接I/O、RAPL、SMART、大页）是否可用，以及不可用时的授权方法
+#[tauri::command]
+async fn get_capabilities() -> Result<CapabilityMap, IpcError> {
+    tokio::task::spawn_blocking(|| CapabilityMap::detect(&default_test_dir()))
+        .await
+        .map_err(|e| IpcError::from(BenchmarkError::SystemInfoError(e.to_string())))
+}
+
+// Tauri命令：请求使用需要更高权限的功能。Windows上弹出UAC提示以管理员身份探测，
+// 其他平台返回说明所需用户组或capability的PERMISSION_ERROR
+#[tauri::command]
+async fn request_elevation(feature: Feature) -> Result<ElevationOutcome, IpcError> {
+    tokio::task::spawn_blocking(move || benchmark::capability::request_elevation(feature, &default_test_dir()))
+        .await
+        .map_err(|e| IpcError::from(BenchmarkError::SystemInfoError(e.to_string())))?
+        .map_err(IpcError::from)
+}
+
+// Tauri命令：获取当前的事件负载版本和可读取的结果文档版本
+#[tauri::command]
+fn get_supported_schema_versions() -> SupportedSchemaVersions {
+    SupportedSchemaVersions {
+        event: EVENT_SCHEMA_VERSION,
+        result: RESULT_SCHEMA_VERSION,
+        readable_results: supported_result_versions(),
+    }
+}
+
+// Tauri命令：获取会话中序号大于since_seq的事件，供重新连接的前端补齐
+#[tauri::command]
+fn get_recent_events(
+    session_id: String,
+    since_seq: Option<u64>,
+    limit: Option<usize>,
+    replay: tauri::State<'_, SharedEventBuffer>,
+) -> RecentEvents {
+    replay.recent(&session_id, since_seq.unwrap_or(0), limit)
+}
+
+// Tauri命令：获取会话的警告历史，包括被去重抑制的次数
+#[tauri::command]
+fn get_session_warnings(session_id: String, warnings: tauri::State<'_, SharedWarnings>) -> Vec<WarningRecord> {
+    warnings.history(&session_id)
+}
+
+// Tauri命令：设置实时发送的警告的最低严重程度和屏蔽的类型，保存到应用数据目录。
+// 被过滤的警告仍然记入会话警告历史和测试结果
+#[tauri::command]
+async fn configure_warning_filter<R: Runtime>(
+    app: AppHandle<R>,
+    min_severity: WarningSeverity,
+    muted_types: Vec<String>,
+    warnings: tauri::State<'_, SharedWarnings>,
+) -> Result<(), IpcError> {
+    let filter = WarningFilter { min_severity, muted_types };
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| BenchmarkError::DataSaveError(format!("无法获取应用数据目录: {}", e)))?;
+    filter.save(&app_data_dir.join(WARNING_FILTER_FILE))?;
+    warnings.set_filter(filter);
+    Ok(())
+}
+
+// Tauri命令：设置事件消息和错误消息的语言（en/zh），事件消息对之后开始的测试生效
+#[tauri::command]
+fn set_backend_locale(locale: String, current: tauri::State<'_, SharedLocale>) -> Result<(), IpcError> {
+    let parsed = Locale::parse(&locale)
+        .ok_or_else(|| IpcError::invalid_argument("locale", format!("不支持的语言: {}", locale)))?;
+    *current.lock()? = parsed;
+    i18n::set_backend_locale(parsed);
+    Ok(())
+}
+
+// 按当前的限速、重放缓冲区、语言和警告去重设置创建事件发送器
+fn session_emitter<R: Runtime>(app: &AppHandle<R>, session_id: &str) -> ProgressEmitter<R> {
+    ProgressEmitter::new(
+        app.clone(),
+        app.state::<SharedProgressThrottle>().inner().clone(),
+        app.state::<SharedEventBuffer>().inner().clone(),
+        session_id,
+    )
+    .with_locale(*app.state::<SharedLocale>().lock().unwrap())
+    .with_warnings(app.state::<SharedWarnings>().inner().clone())
+    .with_telemetry(app.state::<SharedTelemetry>().inner().clone())
+    .with_timeseries(app.state::<SharedTimeseries>().inner().clone())
+}
+
+// 把测试上报的实时采样转发为real-time-performance事件。每个(测试, 阶段)是一个指标流，
+// 只保留最新值，由刷新任务按限速频率发送；阶段结束时的汇总采样是该流的最后一个值，不会丢失
+fn performance_sink<R: Runtime>(emitter: &ProgressEmitter<R>, sessions: &TestSessions, test_type: TestType) -> MetricsSink {
+    let emitter = emitter.clone();
+    let sessions = sessions.clone();
+    Arc::new(move |sample: LiveSample| {
+        let stream = format!("{}/{}", test_type, sample.phase);
+        let data = RealTimePerformanceData {
+            session_id: emitter.session_id().to_string(),
+            test_type,
+            phase: sample.phase.to_string(),
+            metrics: sample.metrics,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+        // 最新采样同时留在会话记录中，轮询界面状态时直接读取
+        if let Some(record) = sessions.lock().unwrap().get_mut(emitter.session_id()) {
+            record.latest_sample = Some(data.clone());
+        }
+        emitter.emit_latest(events::REAL_TIME_PERFORMANCE, &stream, data);
+    })
+}
+
+// 按限速频率发送各指标流的最新值，并把发送统计写入会话记录，会话结束后退出
+async fn run_metric_flusher<R: Runtime>(emitter: ProgressEmitter<R>, sessions: TestSessions) {
+    loop {
+        tokio::time::sleep(emitter.flush_interval()).await;
+        emitter.flush_latest();
+        let mut sessions_guard = sessions.lock().unwrap();
+        let Some(record) = sessions_guard.get_mut(emitter.session_id()) else {
+            break;
+        };
+        record.metric_samples = emitter.metric_samples();
+        if record.is_terminal() {
+            break;
+        }
+    }
+}
+
+// 套件运行期间的系统监控线程：按间隔采样整机资源占用并发送system-monitoring事件，
+// 同时写入会话记录供界面状态快照使用。测试在异步运行时的线程上阻塞运行，因此使用独立线程；
+// 会话结束或被清理后线程自行退出，drop时通知线程退出并等待其结束
+struct SystemMonitoring {
+    stop: Option<std::sync::mpsc::Sender<()>>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl SystemMonitoring {
+    fn start<R: Runtime>(
+        emitter: ProgressEmitter<R>,
+        sessions: TestSessions,
+        monitor: SharedSystemMonitor,
+        interval: std::time::Duration,
+    ) -> Self {
+        let (stop, stopped) = std::sync::mpsc::channel::<()>();
+        let thread = std::thread::spawn(move || loop {
+            // 开始时立即采样一次，第一项测试开始前界面就有数据
+            let running = sessions.lock().unwrap().get(emitter.session_id()).is_some_and(|record| !record.is_terminal());
+            if !running {
+                break;
+            }
+            let data = SystemMonitoringData {
+                session_id: Some(emitter.session_id().to_string()),
+                ..monitor.lock().unwrap().sample()
+            };
+            if let Some(record) = sessions.lock().unwrap().get_mut(emitter.session_id()) {
+                record.monitoring = Some(data.clone());
+            }
+            emitter.emit(events::SYSTEM_MONITORING, data);
+            if stopped.recv_timeout(interval) != Err(std::sync::mpsc::RecvTimeoutError::Timeout) {
+                break;
+            }
+        });
+        Self {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for SystemMonitoring {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+// 转换会话状态并通知前端，所有状态变化都经过这里。
+// 失败和取消时reason为错误码
+fn transition_session<R: Runtime>(
+    emitter: &ProgressEmitter<R>,
+    record: &mut SessionRecord,
+    to: TestStatus,
+    reason: Option<&str>,
+) -> Result<(), BenchmarkError> {
+    let old_status = record.transition(to.clone())?;
+    emitter.emit(events::SESSION_STATUS_CHANGED, SessionStatusChangedEvent {
+        session_id: emitter.session_id().to_string(),
+        old_status,
+        new_status: to,
+        timestamp: record.last_update.to_rfc3339(),
+        reason: reason.map(str::to_string),
+    });
+    Ok(())
+}
+
+// 依次运行各项测试，每项测试的事件、日志、耗时和结果记录都在这里统一处理。
+// 会话被取消后不再开始新的测试，超时的测试记为失败后继续下一项；返回是否有测试失败
+fn run_suite_tests<R: Runtime>(
+    emitter: &ProgressEmitter<R>,
+    sessions: &TestSessions,
+    tracker: &Arc<SuiteProgressTracker>,
+    runners: &[Box<dyn BenchmarkRunner>],
+    config: &BenchmarkConfig,
+    test_result: &mut TestResult,
+) -> bool {
+    let session_id = emitter.session_id().to_string();
+    let log = |level: LogLevel, message: String| append_session_log(sessions, &session_id, level, message);
+    let cancellation = session_cancellation(sessions, &session_id);
+    
+    let mut any_failed = false;
+    for runner in runners {
+        // 在两项测试之间暂停时在这里等到恢复
+        if cancellation.check(TestType::Suite, 0.0).is_err() {
+            break;
+        }
+        let test_type = runner.name();
+        let labels = runner.labels();
+        let _test = tracing::info_span!("test", test_type = %test_type.as_str()).entered();
+        log(LogLevel::Info, format!("{}开始", labels.name));
+        emitter.record_progress(test_type.as_str());
+        
+        let suite_progress = tracker.start_test(test_type.as_str());
+        record_session_progress(sessions, &session_id, test_type.as_str(), &suite_progress, 0.0, Some(emitter.render(&labels.start)));
+        emitter.emit(events::BENCHMARK_PROGRESS, BenchmarkProgress {
+            session_id: session_id.clone(),
+            current_test: labels.name.to_string(),
+            overall_progress: suite_progress.overall,
+            test_progress: 0.0,
+            message: emitter.render(&labels.start),
+            message_key: labels.start.key.to_string(),
+            params: HashMap::new(),
+            estimated_time_remaining: suite_progress.eta_seconds,
+            estimated_completion_time: suite_progress.estimated_completion.map(|t| t.to_rfc3339()),
+        });
+        
+        // 记录测试创建的临时文件，应用中途退出时下次启动据此清理
+        let artifacts = runner.artifacts();
+        if !artifacts.is_empty() {
+            let mut sessions_guard = sessions.lock().unwrap();
+            if let Some(record) = sessions_guard.get_mut(&session_id) {
+                record.artifacts.extend(artifacts);
+            }
+            let _ = sessions_guard.persist();
+        }
+        
+        let ctx = RunContext {
+            progress: test_progress_sink(emitter, sessions, tracker, test_type, &labels.name),
+            cancellation: cancellation.clone(),
+            warnings: test_warning_sink(emitter, sessions, test_type),
+            issues: RunIssues::default(),
+        };
+        let started = cancellation.clock();
+        let outcome = run_with_watchdog(runner.as_ref(), &ctx, config.test_timeout(runner.as_ref()));
+        // 成功与失败都记录实际耗时，不含暂停的时间
+        *test_result.per_test_durations.entry(test_type).or_insert(0.0) += started.elapsed().as_secs_f64();
+        let issues = ctx.issues.take();
+        let mut unavailable = false;
+        let (result, error) = match outcome {
+            Ok(TestOutcome::Completed(payload)) => {
+                test_result.record_payload(payload.clone());
+                match TestCompletion::classify(false, &issues) {
+                    TestCompletion::CompletedWithIssues => {
+                        log(LogLevel::Warning, format!("{}结束: 完成但有问题（{}项）", labels.name, issues.len()));
+                    }
+                    _ => log(LogLevel::Info, format!("{}结束: 成功", labels.name)),
+                }
+                (Some(payload), None)
+            }
+            // 本机不支持（运行器已发出警告），记为跳过，继续后面的测试
+            Ok(TestOutcome::Unavailable) => {
+                log(LogLevel::Warning, format!("{}结束: 跳过（本机不支持）", labels.name));
+                unavailable = true;
+                (None, None)
+            }
+            // 运行器发现会话已取消而提前停止，不计为完成
+            Ok(TestOutcome::Skipped) => {
+                log(LogLevel::Warning, format!("{}结束: 已取消", labels.name));
+                break;
+            }
+            // 运行器中途取消：不计为失败、不发送test-error，会话转为取消状态，已完成的测试结果保留
+            Err(BenchmarkError::Cancelled { at_progress, .. }) => {
+                log(LogLevel::Warning, format!("{}结束: 已取消（进度 {:.0}%）", labels.name, at_progress));
+                mark_session_cancelled(emitter, sessions);
+                break;
+            }
+            Err(e) => {
+                any_failed = true;
+                log(LogLevel::Error, format!("{}结束: 失败 - {}", labels.name, e));
+                (None, Some(e))
+            }
+        };
+        
+        // 成功或失败都计为该测试已完成，并发送完成事件；失败时同时发送test-error和警告
+        let event = ipc::TestCompleteEvent {
+            session_id: session_id.clone(),
+            test_type,
+            success: error.is_none(),
+            result,
+            error: error.as_ref().map(|e| e.to_string()),
+            elapsed_seconds: started.elapsed().as_secs_f64(),
+            outcome: if unavailable {
+                TestCompletion::Skipped
+            } else {
+                TestCompletion::classify(error.is_some(), &issues)
+            },
+            issues,
+        };
+        let suite_progress = tracker.finish_test(test_type.as_str());
+        record_session_progress(sessions, &session_id, test_type.as_str(), &suite_progress, 100.0, None);
+        // 该测试期间被抑制的重复警告先于完成事件汇总发送
+        emitter.finish_test(test_type);
+        if !event.success {
+            emitter.emit(events::TEST_ERROR, event.clone());
+        }
+        emitter.emit(events::TEST_COMPLETE, event);
+        
+        if let Some(e) = error {
+            let (warning_type, severity) = failure_warning(&e);
+            let warning = labels.failure(&e);
+            warn_session(emitter, sessions, TestWarningEvent {
+                session_id: session_id.clone(),
+                test_type,
+                warning_type: warning_type.to_string(),
+                message: emitter.render(&warning),
+                message_key: warning.key.to_string(),
+                params: warning.params,
+                severity,
+            });
+        }
+    }
+    any_failed
+}
+
+// 运行器自行取消时会话可能还在运行，转为取消状态；用户已取消的会话保持不变
+fn mark_session_cancelled<R: Runtime>(emitter: &ProgressEmitter<R>, sessions: &TestSessions) {
+    let mut sessions_guard = sessions.lock().unwrap();
+    let transitioned = match sessions_guard.get_mut(emitter.session_id()) {
+        Some(record) if record.status != TestStatus::Cancelled => {
+            transition_session(emitter, record, TestStatus::Cancelled, Some("CANCELLED")).is_ok()
+        }
+        _ => false,
+    };
+    if transitioned {
+        let _ = sessions_guard.persist();
+    }
+}
+
+// 检查会话是否已被取消或暂停
+fn session_cancellation(sessions: &TestSessions, session_id: &str) -> CancellationToken {
+    let status_is = move |status: TestStatus| {
+        let sessions = sessions.clone();
+        let session_id = session_id.to_string();
+        move || {
+            let sessions_guard = sessions.lock().unwrap();
+            sessions_guard.get(&session_id).is_some_and(|record| record.status == status)
+        }
+    };
+    CancellationToken::new(status_is(TestStatus::Cancelled)).with_pause(status_is(TestStatus::Paused))
+}
+
+// 把单项测试的进度转换为benchmark-progress事件，并在25/50/75%时写入会话日志
+fn test_progress_sink<R: Runtime>(
+    emitter: &ProgressEmitter<R>,
+    sessions: &TestSessions,
+    tracker: &Arc<SuiteProgressTracker>,
+    test_type: TestType,
+    name: &str,
+) -> ProgressSink {
+    let session_id = emitter.session_id().to_string();
+    let emitter = emitter.clone();
+    let sessions = sessions.clone();
+    let tracker = tracker.clone();
+    let name = name.to_string();
+    let milestones = ProgressMilestones::default();
+    Arc::new(move |progress: f64, message: Message| {
+        emitter.record_progress(message.phase());
+        if let Some(milestone) = milestones.crossed(progress) {
+            append_session_log(&sessions, &session_id, LogLevel::Info, format!("{}进度 {}%", name, milestone));
+        }
+        
+        let suite_progress = tracker.update(test_type.as_str(), progress);
+        let phase = emitter.render(&message);
+        let sent = emitter.emit_progress(events::BENCHMARK_PROGRESS, progress, || BenchmarkProgress {
+            session_id: session_id.clone(),
+            current_test: name.to_string(),
+            overall_progress: suite_progress.overall,
+            test_progress: progress,
+            message: phase.clone(),
+            message_key: message.key.to_string(),
+            params: message.params,
+            estimated_time_remaining: suite_progress.eta_seconds,
+            estimated_completion_time: suite_progress.estimated_completion.map(|t| t.to_rfc3339()),
+        });
+        if sent {
+            record_session_progress(&sessions, &session_id, test_type.as_str(), &suite_progress, progress, Some(phase));
+        }
+    })
+}
+
+// 运行器上报的问题作为test-warning事件发送，警告类型取消息键
+fn test_warning_sink<R: Runtime>(emitter: &ProgressEmitter<R>, sessions: &TestSessions, test_type: TestType) -> WarningSink {
+    let emitter = emitter.clone();
+    let sessions = sessions.clone();
+    Arc::new(move |warning: Message, severity: WarningSeverity| {
+        warn_session(&emitter, &sessions, TestWarningEvent {
+            session_id: emitter.session_id().to_string(),
+            test_type,
+            warning_type: warning.key.to_string(),
+            message: emitter.render(&warning),
+            message_key: warning.key.to_string(),
+            params: warning.params,
+            severity,
+        });
+    })
+}
+
+// 与本机上一次的套件结果对比，对比结果附加到新结果中，有指标回归时发送提示事件。
+// 硬件指纹不同的结果不参与对比，更换硬件后的第一次运行不会误报
+fn check_regression<R: Runtime>(
+    emitter: &ProgressEmitter<R>,
+    results: &SharedResultStore,
+    config: &BenchmarkConfig,
+    test_result: &mut TestResult,
+    log: &impl Fn(LogLevel, String),
+) {
+    let session_id = emitter.session_id();
+    let thresholds = config.regression_thresholds.clone().unwrap_or_default();
+    let comparison = match results.lock().unwrap().check_regression(session_id, test_result, &thresholds) {
+        Ok(Some(comparison)) => comparison,
+        Ok(None) => return,
+        Err(e) => {
+            log(LogLevel::Warning, format!("无法与上一次结果对比: {}", e));
+            return;
+        }
+    };
+    let regressions: Vec<_> = comparison.report.regressions().cloned().collect();
+    if !regressions.is_empty() {
+        log(LogLevel::Warning, format!(
+            "与 {} 相比有 {} 项指标回归: {}",
+            comparison.baseline_id,
+            regressions.len(),
+            regressions.iter().map(|metric| metric.metric.as_str()).collect::<Vec<_>>().join(", ")
+        ));
+        let baseline_timestamp = results
+            .lock()
+            .unwrap()
+            .summary(&comparison.baseline_id)
+            .map(|summary| summary.timestamp.clone())
+            .unwrap_or_default();
+        emitter.emit(events::PERFORMANCE_REGRESSION_DETECTED, RegressionDetectedEvent {
+            session_id: session_id.to_string(),
+            result_id: session_id.to_string(),
+            baseline_id: comparison.baseline_id.clone(),
+            baseline_timestamp,
+            regressions,
+        });
+    }
+    test_result.regression_check = Some(comparison);
+}
+
+// 运行完整基准测试套件的内部函数
+async fn run_full_benchmark_suite<R: Runtime>(
+    app: AppHandle<R>,
+    emitter: ProgressEmitter<R>,
+    config: BenchmarkConfig,
+    sessions: TestSessions,
+    results: SharedResultStore,
+    upload: SharedUploadSettings,
+    mode: RunMode,
+) -> Result<SuiteOutcome, BenchmarkError> {
+    let session_id = emitter.session_id().to_string();
+    
+    // 排队中已被取消的会话不再运行，没有结果，按失败结束
+    {
+        let mut sessions_guard = sessions.lock().unwrap();
+        let Some(record) = sessions_guard.get_mut(&session_id) else {
+            return Ok(SuiteOutcome::Failure);
+        };
+        if transition_session(&emitter, record, TestStatus::Running, None).is_err() {
+            return Ok(SuiteOutcome::Failure);
+        }
+        let _ = sessions_guard.persist();
+    }
+    
+    // 整个套件期间按固定间隔发送系统监控数据，函数返回时监控线程随之结束
+    let monitor = app
+        .try_state::<SharedSystemMonitor>()
+        .map(|monitor| monitor.inner().clone())
+        .unwrap_or_default();
+    let monitoring_interval = std::time::Duration::from_millis(config.monitoring_interval_ms.max(1));
+    let _monitoring = SystemMonitoring::start(emitter.clone(), sessions.clone(), monitor, monitoring_interval);
+    
+    let total_tests = [config.cpu_test.enabled, config.memory_test.enabled, config.storage_test.enabled]
+        .iter()
+        .filter(|&&enabled| enabled)
+        .count() as f64;
+    
+    // 运行标识在套件开始时生成，图形界面发起的运行记为interactive，启动参数发起的自动运行记为headless
+    let system_info = collect_system_info()?;
+    let app_info = AppInfo::new(app.package_info().version.to_string());
+    let identity = RunIdentity::new(&system_info, &app_info, mode);
+    let mut test_result = TestResult {
+        schema_version: RESULT_SCHEMA_VERSION,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        system_info,
+        cpu_results: None,
+        memory_results: None,
+        storage_results: None,
+        gpu_results: None,
+        webview_results: None,
+        network_results: None,
+        overall_score: 0.0,
+        category_scores: std::collections::BTreeMap::new(),
+        imported: false,
+        auto_duration: None,
+        app_info: Some(app_info),
+        failure_log: Vec::new(),
+        warnings: Vec::new(),
+        config_used: None,
+        per_test_durations: std::collections::BTreeMap::new(),
+        session_id: Some(session_id.clone()),
+        runs: Vec::new(),
+        partial: false,
+        extra_results: std::collections::BTreeMap::new(),
+        regression_check: None,
+        integrity: None,
+        identity: Some(identity),
+    };
+    let log = |level: LogLevel, message: String| append_session_log(&sessions, &session_id, level, message);
+    log(LogLevel::Info, format!("测试套件开始，共 {} 项测试", total_tests));
+    
+    // 自动时长模式：校准后推导各测试参数，校准耗时计入目标时长
+    let mut config = config;
+    if let Some(target_seconds) = config.auto_duration_target_seconds {
+        emitter.record_progress("calibration");
+        emitter.emit(events::BENCHMARK_PROGRESS, BenchmarkProgress {
+            session_id: session_id.clone(),
+            current_test: "校准".to_string(),
+            overall_progress: 0.0,
+            test_progress: 0.0,
+            message: emitter.render(&Message::new("suite.calibrating")),
+            message_key: "suite.calibrating".to_string(),
+            params: HashMap::new(),
+            estimated_time_remaining: Some(target_seconds),
+            estimated_completion_time: Some((chrono::Utc::now() + chrono::Duration::seconds(target_seconds as i64)).to_rfc3339()),
+        });
+        
+        let calibration = if config.skip_calibration {
+            Calibration::default_estimates()
+        } else {
+            Calibration::measure(&config).unwrap_or_else(|e| {
+                log(LogLevel::Warning, format!("校准失败，使用内置估计: {}", e));
+                Calibration::default_estimates()
+            })
+        };
+        let plan = plan_auto_duration(&config, target_seconds, calibration);
+        plan.apply(&mut config);
+        log(LogLevel::Info, format!(
+            "自动时长: 目标 {}秒，估计 {:.0}秒，CPU时长 {:?}秒，内存迭代 {:?}次，存储文件 {:?}MB",
+            target_seconds, plan.estimated_seconds, plan.cpu_duration, plan.memory_iterations, plan.storage_file_size
+        ));
+        if !plan.within_tolerance {
+            log(LogLevel::Warning, "无法在目标时长±20%内安排测试，已使用最接近的参数".to_string());
+        }
+        test_result.auto_duration = Some(plan);
+    }
+    
+    // 预检：与estimate_benchmark_run使用同一套检查
+    let preflight_env = PreflightEnvironment::detect(&default_test_file_path());
+    for warning in preflight_warnings(&config, &preflight_env) {
+        log(LogLevel::Warning, format!("预检警告: {}", warning.render(Locale::Zh)));
+        warn_session(&emitter, &sessions, TestWarningEvent {
+            session_id: session_id.clone(),
+            test_type: TestType::Suite,
+            warning_type: "preflight".to_string(),
+            message: emitter.render(&warning),
+            message_key: warning.key.to_string(),
+            params: warning.params,
+            severity: WarningSeverity::Medium,
+        });
+    }
+    
+    // 总体进度和剩余时间都按各测试的估计耗时加权计算
+    let eta_calibration = test_result
+        .auto_duration
+        .as_ref()
+        .map(|plan| plan.calibration.clone())
+        .unwrap_or_else(Calibration::default_estimates);
+    let registry = app
+        .try_state::<SharedBenchmarkRegistry>()
+        .map(|registry| registry.inner().clone())
+        .unwrap_or_default();
+    let webview = webview_bridge(&app);
+    let runners = suite_runners(&config, &eta_calibration, &registry, |test_type| Some(performance_sink(&emitter, &sessions, test_type)), Some(&webview))?;
+    let repeat = config.repeat.max(1);
+    let tracker = Arc::new(SuiteProgressTracker::new(&runner_estimates(&runners, repeat)));
+    test_result.config_used = Some(config.clone());
+    
+    // 重复运行：每次运行单独计分，中途取消时未跑完的那次不计入汇总
+    let cancellation = session_cancellation(&sessions, &session_id);
+    let mut runs = Vec::with_capacity(repeat);
+    let mut unfinished = None;
+    let mut any_failed = false;
+    for run_index in 1..=repeat {
+        if cancellation.is_cancelled() {
+            break;
+        }
+        if repeat > 1 {
+            log(LogLevel::Info, format!("第 {}/{} 次运行开始", run_index, repeat));
+        }
+        let mut run = TestResult {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            ..test_result.clone()
+        };
+        if repeat > 1 {
+            // 每次原始结果有各自的run_id，汇总结果沿用套件开始时的标识
+            run.identity = run.identity.as_ref().map(RunIdentity::renewed);
+        }
+        any_failed |= run_suite_tests(&emitter, &sessions, &tracker, &runners, &config, &mut run);
+        run.update_scores();
+        if cancellation.is_cancelled() {
+            unfinished = Some(run);
+            break;
+        }
+        runs.push(run);
+    }
+    
+    if repeat > 1 && !runs.is_empty() {
+        // 各次运行的原始结果单独保存到历史中，汇总结果使用会话ID
+        for (index, run) in runs.iter().enumerate() {
+            if let Err(e) = results.lock().unwrap().save(&format!("{}-run{}", session_id, index + 1), run) {
+                log(LogLevel::Warning, format!("第 {} 次运行的结果保存失败: {}", index + 1, e));
+            }
+        }
+        let partial = runs.len() < repeat;
+        let identity = test_result.identity.clone();
+        test_result = aggregate_runs(&runs, config.aggregation).unwrap_or(test_result);
+        test_result.partial = partial;
+        test_result.identity = identity;
+        if partial {
+            log(LogLevel::Warning, format!("运行已取消，汇总了已完成的 {}/{} 次运行", runs.len(), repeat));
+        }
+    } else if let Some(run) = unfinished.or_else(|| runs.pop()) {
+        // 单次运行，或者第一次运行就被取消时保留已完成的测试结果
+        test_result = TestResult {
+            partial: repeat > 1,
+            identity: test_result.identity.clone(),
+            ..run
+        };
+    }
+    log(LogLevel::Info, format!("测试套件结束，总分 {:.2}", test_result.overall_score));
+    
+    // 有测试失败时把日志附加到结果中，便于用户反馈问题
+    if any_failed {
+        if let Some(record) = sessions.lock().unwrap().get(&session_id) {
+            test_result.failure_log = record.logs.tail(FAILURE_LOG_LIMIT);
+        }
+    }
+    
+    test_result.warnings = emitter.warning_history();
+    
+    if !config.skip_regression_check {
+        check_regression(&emitter, &results, &config, &mut test_result, &log);
+    }
+    
+    // 保存结果到历史记录，失败时统一按DataSaveError随完成事件发送
+    let save_result = results.lock().unwrap().save(&session_id, &test_result).map_err(|e| match e {
+        BenchmarkError::DataSaveError(_) => e,
+        e => BenchmarkError::DataSaveError(e.to_string()),
+    });
+    let result_id = save_result.as_ref().ok().map(|_| session_id.clone());
+    let mut save_error = None;
+    match save_result {
+        Ok(_) => {
+            // 用户开启自动上传时在后台上传，不阻塞完成事件
+            let upload_config = upload.lock().unwrap().config.clone();
+            if upload_config.auto_upload && upload_config.is_enabled() {
+                let emitter_clone = emitter.clone();
+                let results_clone = results.clone();
+                let sessions_clone = sessions.clone();
+                let session_id_clone = session_id.clone();
+                tokio::spawn(async move {
+                    let error = match upload_stored_result(&session_id_clone, &results_clone, &upload_config).await {
+                        Ok(status) if status.state == UploadState::Succeeded => return,
+                        Ok(status) => status.error.unwrap_or_default(),
+                        Err(e) => e.to_string(),
+                    };
+                    append_session_log(&sessions_clone, &session_id_clone, LogLevel::Warning, format!("测试结果上传失败: {}", error));
+                    let warning = Message::new("suite.upload_failed").param("error", error);
+                    warn_session(&emitter_clone, &sessions_clone, TestWarningEvent {
+                        session_id: session_id_clone,
+                        test_type: TestType::Suite,
+                        warning_type: "result_upload_failed".to_string(),
+                        message: emitter_clone.render(&warning),
+                        message_key: warning.key.to_string(),
+                        params: warning.params,
+                        severity: WarningSeverity::Low,
+                    });
+                });
+            }
+        }
+        Err(e) => {
+            log(LogLevel::Warning, format!("测试结果保存失败: {}", e));
+            let warning = Message::new("suite.save_failed").param("error", &e);
+            warn_session(&emitter, &sessions, TestWarningEvent {
+                session_id: session_id.clone(),
+                test_type: TestType::Suite,
+                warning_type: "result_save_failed".to_string(),
+                message: emitter.render(&warning),
+                message_key: warning.key.to_string(),
+                params: warning.params,
+                severity: WarningSeverity::Medium,
+            });
+            // 保存失败的警告随结果一起发送
+            test_result.warnings = emitter.warning_history();
+            save_error = Some(IpcError::from(e));
+        }
+    }
+    
+    let cancelled = cancellation.is_cancelled();
+    let outcome = if any_failed || test_result.partial || cancelled { SuiteOutcome::Partial } else { SuiteOutcome::Success };
+    spawn_completion_webhook(&app, &emitter, &sessions, CompletionNotification::from_result(&session_id, &test_result, outcome, result_id.clone()));
+    
+    // 发送完成事件，被取消的会话仍带上已完成的测试结果
+    let cancelled_error = cancelled.then(|| {
+        let at_progress = sessions.lock().unwrap().get(&session_id).map_or(0.0, |record| record.overall_progress);
+        BenchmarkError::Cancelled { test: TestType::Suite.as_str().to_string(), at_progress }.to_string()
+    });
+    emitter.emit(events::BENCHMARK_COMPLETE, ipc::BenchmarkSuiteCompleteEvent {
+        session_id: session_id.clone(),
+        success: !cancelled,
+        results: Some(test_result.clone()),
+        error: cancelled_error,
+        completed_at: chrono::Utc::now().to_rfc3339(),
+        save_error,
+    });
+    
+    // 更新会话状态
+    {
+        let mut sessions_guard = sessions.lock().unwrap();
+        if let Some(record) = sessions_guard.get_mut(&session_id) {
+            record.result = Some(test_result);
+            record.result_id = result_id;
+            record.metric_samples = emitter.metric_samples();
+            // 运行期间被取消的会话保持取消状态
+            let _ = transition_session(&emitter, record, TestStatus::Completed, None);
+        }
+        let _ = sessions_guard.persist();
+    }
+    emitter.finish();
+    
+    Ok(outcome)
+}
+
+// 按启动参数开始自动运行。参数有误时输出到stderr：无人值守时以EX_USAGE退出，否则照常显示界面并由前端弹出对话框
+fn start_launch_run<R: Runtime>(app: &AppHandle<R>, launch: Result<LaunchOptions, LaunchArgError>) {
+    let planned = launch.and_then(|options| Ok((options.config()?, options)));
+    let (config, options) = match planned {
+        Ok((Some(config), options)) => (config, options),
+        Ok((None, _)) => return,
+        Err(e) => return report_launch_error(app, &e),
+    };
+    if options.no_window {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.hide();
+        }
+    }
+    
+    let handle = app.clone();
+    let unattended = options.is_unattended();
+    let started = start_suite_session(app.clone(), config, None, RunMode::Headless, move |session_id, outcome| {
+        let code = finish_launch_run(&handle, &options, session_id, outcome);
+        if options.exit_when_done {
+            handle.exit(code);
+        }
+    });
+    if let Err(e) = started {
+        report_launch_error(app, &LaunchArgError { message: e.message, unattended });
+    }
+}
+
+fn report_launch_error<R: Runtime>(app: &AppHandle<R>, error: &LaunchArgError) {
+    eprintln!("{}\n{}", error, USAGE);
+    if error.unattended {
+        app.exit(EXIT_USAGE);
+    } else {
+        *app.state::<SharedLaunchError>().lock().unwrap() = Some(error.to_string());
+    }
+}
+
+// 自动运行结束：按--output-format把结果写到--output指定的路径或标准输出，返回反映结束方式和回归判定的退出码。
+// 结果读取或写出失败按执行出错处理
+fn finish_launch_run<R: Runtime>(app: &AppHandle<R>, options: &LaunchOptions, session_id: &str, outcome: SuiteOutcome) -> i32 {
+    let mut outcome = outcome;
+    let result = app.state::<SharedResultStore>().lock().unwrap().load(session_id);
+    let regressed = result.as_ref().is_ok_and(ci_output::has_regressions);
+    if options.output.is_some() || options.output_format.is_some() {
+        let format = options.output_format.unwrap_or_default();
+        let written = result.and_then(|result| ci_output::write(session_id, &result, format, options.output.as_deref()));
+        if let Err(e) = written {
+            eprintln!("结果写出失败: {}", e);
+            outcome = SuiteOutcome::Failure;
+        }
+    }
+    if regressed {
+        eprintln!("会话 {} 结束: {}，有指标超过回归阈值", session_id, outcome.as_str());
+    } else {
+        eprintln!("会话 {} 结束: {}", session_id, outcome.as_str());
+    }
+    exit_code(outcome, regressed)
+}
+
+// Tauri命令：取出启动参数错误，前端加载后调用并弹出对话框，之后再调用返回空
+#[tauri::command]
+async fn take_launch_error(launch_error: tauri::State<'_, SharedLaunchError>) -> Result<Option<String>, IpcError> {
+    Ok(launch_error.lock()?.take())
+}
+
+// Tauri命令：获取应用、引擎版本和构建信息
+#[tauri::command]
+fn get_app_info<R: Runtime>(app: AppHandle<R>) -> AppInfo {
+    AppInfo::new(app.package_info().version.to_string())
+}
+
+// Tauri命令：前端完成首次有意义的绘制后调用，结束本次启动测量并写入启动历史
+#[tauri::command]
+async fn report_frontend_ready(startup: tauri::State<'_, SharedStartup>) -> Result<StartupMetrics, IpcError> {
+    let mut tracker = startup.lock()?;
+    if tracker.record(StartupStage::FrontendReady, std::time::Instant::now()) {
+        if let Some(path) = tracker.history_path() {
+            append_startup_history(path, &tracker.metrics(), STARTUP_HISTORY_LIMIT)?;
+        }
+    }
+    Ok(tracker.metrics())
+}
+
+// Tauri命令：获取本次启动各阶段的耗时和冷热启动判断
+#[tauri::command]
+async fn get_startup_metrics(startup: tauri::State<'_, SharedStartup>) -> Result<StartupMetrics, IpcError> {
+    Ok(startup.lock()?.metrics())
+}
+
+// Tauri命令：获取最近的启动测量，最早的在前
+#[tauri::command]
+async fn get_startup_history(startup: tauri::State<'_, SharedStartup>) -> Result<Vec<StartupMetrics>, IpcError> {
+    let path = startup.lock()?.history_path().map(|path| path.to_path_buf());
+    match path {
+        Some(path) => load_startup_history(&path).map_err(IpcError::from),
+        None => Ok(Vec::new()),
+    }
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    let startup = StartupTracker::start();
+    let launch = parse_launch_args(std::env::args().skip(1));
+    // 提权探测子进程只运行探测，不启动图形界面
+    if let Ok(LaunchOptions { elevated_probe: Some(feature), output, .. }) = &launch {
+        std::process::exit(benchmark::capability::run_elevated_helper(*feature, output.as_deref()));
+    }
+    run_app(BenchmarkRegistry::with_builtin(), startup, launch)
+}
+
+/// 以自定义的测试模块注册表启动应用，注册的模块可在配置的extra_tests中引用。
+/// 命令行参数留给嵌入方自行处理，不做自动运行
+pub fn run_with_registry(registry: BenchmarkRegistry) {
+    run_app(registry, StartupTracker::start(), Ok(LaunchOptions::default()))
+}
+
+fn run_app(registry: BenchmarkRegistry, startup: StartupTracker, launch: Result<LaunchOptions, LaunchArgError>) {
+    let startup: SharedStartup = Arc::new(Mutex::new(startup));
+    let page_load_startup = startup.clone();
+    let test_sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+    let retention: SharedRetention = Arc::new(Mutex::new(RetentionConfig::default()));
+    let throttle: SharedProgressThrottle = Arc::new(ProgressThrottle::default());
+    let locale: SharedLocale = Arc::new(Mutex::new(Locale::default()));
+    let replay: SharedEventBuffer = Arc::new(EventBuffer::default());
+    let heartbeat: SharedHeartbeat = Arc::new(Mutex::new(HeartbeatConfig::default()));
+    let warnings: SharedWarnings = Arc::new(WarningManager::default());
+    let legacy_events: SharedLegacyEvents = Arc::new(Mutex::new(false));
+    let copy_cancellation: SharedCopyCancellation = Arc::new(AtomicBool::new(false));
+    
+    tauri::Builder::default()
+        .plugin(tauri_plugin_opener::init())
+        .manage(test_sessions.clone())
+        .manage(retention.clone())
+        .manage(throttle)
+        .manage(locale)
+        .manage(replay)
+        .manage(heartbeat)
+        .manage(warnings.clone())
+        .manage(legacy_events)
+        .manage(copy_cancellation)
+        .manage::<SharedNetworkCancellation>(Arc::default())
+        .manage::<SharedBenchmarkRegistry>(Arc::new(registry))
+        .manage(startup.clone())
+        .manage::<SharedWebviewCoordinator>(Arc::new(WebviewCoordinator::new()))
+        .manage::<SharedPowerSwitchCoordinator>(Arc::new(PowerSwitchCoordinator::new()))
+        .manage::<SharedTelemetry>(Arc::new(Telemetry::new()))
+        .manage::<SharedTimeseries>(Arc::new(TimeseriesStore::default()))
+        .manage::<SharedSystemMonitor>(Arc::default())
+        .manage::<SharedMetricsExporter>(Arc::new(tokio::sync::Mutex::new(MetricsExporter::new())))
+        .manage::<SharedLaunchError>(Arc::default())
+        // 第一个WebView开始加载和加载完成的时间计入启动耗时，之后的窗口（如进程测试的探测窗口）不影响
+        .on_page_load(move |_webview, payload| {
+            let stage = match payload.event() {
+                PageLoadEvent::Started => StartupStage::WindowCreated,
+                PageLoadEvent::Finished => StartupStage::PageLoaded,
+            };
+            page_load_startup.lock().unwrap().record(stage, std::time::Instant::now());
+        })
+        .setup(move |app| {
+            let app_data_dir = app.path().app_data_dir()?;
+            // 每个会话一个日志文件，写入应用日志目录
+            if let Some(log_control) = logging::init() {
+                log_control.set_dir(app.path().app_log_dir().ok());
+            }
+            let boot_time = std::time::UNIX_EPOCH + std::time::Duration::from_secs(sysinfo::System::boot_time());
+            startup.lock().unwrap().configure(
+                app.handle().package_info().version.to_string(),
+                detect_launch_kind(&app_data_dir.join(LAUNCH_MARKER_FILE), boot_time),
+                app_data_dir.join(STARTUP_HISTORY_FILE),
+            );
+            
+            // 恢复上次运行遗留的会话，未正常结束的标记为中断
+            let mut registry = SessionRegistry::open(app_data_dir.join("sessions.json"))?;
+            let recovered = registry.recover_interrupted();
+            registry.persist()?;
+            *test_sessions.lock().unwrap() = registry;
+            if !recovered.is_empty() {
+                session_emitter(app.handle(), "startup").emit(events::SESSIONS_RECOVERED, recovered);
+            }
+            
+            let result_store: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(app_data_dir.join("results"))?));
+            app.manage(result_store.clone());
+            
+            let upload_path = app_data_dir.join("upload.json");
+            let upload_settings: SharedUploadSettings = Arc::new(Mutex::new(UploadSettings {
+                config: UploadConfig::load(&upload_path)?,
+                path: upload_path,
+            }));
+            app.manage(upload_settings);
+
+            let anonymous_path = app_data_dir.join("anonymous_summary.json");
+            let anonymous_settings: SharedAnonymousSettings = Arc::new(Mutex::new(AnonymousSummarySettings {
+                config: AnonymousSummaryConfig::load(&anonymous_path)?,
+                path: anonymous_path,
+            }));
+            app.manage(anonymous_settings);
+
+            let webhook_path = app_data_dir.join("webhook.json");
+            let webhook_settings: SharedWebhookSettings = Arc::new(Mutex::new(WebhookSettings {
+                config: WebhookConfig::load(&webhook_path)?,
+                path: webhook_path,
+            }));
+            app.manage(webhook_settings);
+
+            let format_path = app_data_dir.join("format_options.json");
+            let format_settings: SharedFormatSettings = Arc::new(Mutex::new(FormatSettings {
+                options: FormatOptions::load(&format_path)?,
+                path: format_path,
+            }));
+            app.manage(format_settings);
+            
+            warnings.set_filter(WarningFilter::load(&app_data_dir.join(WARNING_FILTER_FILE))?);
+            
+            // 参考数据在启动时校验。用户数据保存前已校验过，应用升级后格式不再受支持时回退到内置数据
+            let dataset_path = app_data_dir.join(REFERENCE_DATASET_FILE);
+            let dataset = match ReferenceDataset::from_file(&dataset_path) {
+                Ok(dataset) if dataset_path.exists() => dataset,
+                _ => ReferenceDataset::embedded()?,
+            };
+            app.manage::<SharedReferenceDataset>(Arc::new(Mutex::new(dataset)));
+            
+            tauri::async_runtime::spawn(run_session_cleanup(
+                session_emitter(app.handle(), "cleanup"),
+                test_sessions,
+                result_store,
+                retention,
+            ));
+            startup.lock().unwrap().record(StartupStage::Setup, std::time::Instant::now());
+            
+            // 启动参数指定了预设或配置文件时立即开始自动运行
+            start_launch_run(app.handle(), launch);
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            get_app_info,
+            get_system_info,
+            list_gpu_adapters,
+            estimate_benchmark_run,
+            get_event_schema,
+            get_json_schemas,
+            get_supported_schema_versions,
+            get_capabilities,
+            request_elevation,
+            get_error_catalog,
+            set_backend_locale,
+            get_recent_events,
+            get_session_warnings,
+            configure_warning_filter,
+            start_benchmark_suite,
+            cancel_benchmark,
+            get_test_status,
+            get_all_test_sessions,
+            get_session_logs,
+            get_result_history,
+            get_benchmark_result,
+            list_benchmark_history,
+            delete_benchmark_result,
+            delete_result,
+            clear_result_history,
+            compact_result_store,
+            merge_duplicates,
+            import_result,
+            load_benchmark_config,
+            save_benchmark_config,
+            compare_results,
+            load_regression_thresholds,
+            rank_result,
+            set_reference_dataset,
+            configure_result_upload,
+            upload_result,
+            export_leaderboard_entry,
+            submit_leaderboard_entry,
+            import_leaderboard_entries,
+            export_results,
+            export_result_csv,
+            export_result_html,
+            configure_completion_webhook,
+            set_format_options,
+            get_upload_status,
+            preview_anonymous_summary,
+            configure_anonymous_summary,
+            get_anonymous_summary_settings,
+            submit_anonymous_summary,
+            get_system_monitoring_data,
+            cleanup_completed_sessions,
+            configure_session_retention,
+            configure_progress_events,
+            configure_legacy_events,
+            configure_heartbeat,
+            set_log_level,
+            get_ui_state,
+            pause_benchmark,
+            resume_benchmark,
+            run_cpu_benchmark,
+            run_memory_benchmark,
+            run_storage_benchmark,
+            run_gpu_benchmark,
+            run_network_benchmark,
+            cancel_network_benchmark,
+            run_copy_benchmark,
+            cancel_copy_benchmark,
+            ipc_echo,
+            ipc_echo_raw,
+            run_ipc_benchmark,
+            run_process_benchmark,
+            start_webview_benchmark,
+            submit_webview_results,
+            run_power_plan_comparison,
+            confirm_power_plan_switch,
+            configure_metrics_exporter,
+            export_timeseries,
+            take_launch_error,
+            report_frontend_ready,
+            get_startup_metrics,
+            get_startup_history
+        ])
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            // 退出时关闭指标导出，释放端口
+            if let tauri::RunEvent::Exit = event {
+                let exporter = app.state::<SharedMetricsExporter>().inner().clone();
+                tauri::async_runtime::block_on(async move { exporter.lock().await.stop().await });
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use benchmark::core::Toggle;
+    use benchmark::scoring::calculate_overall_score;
+
+    #[test]
+    fn test_calculate_overall_score() {
+        let mut test_result = benchmark::test_support::sample_test_result("2024-01-01T00:00:00Z", 0.0);
+        test_result.storage_results = None;
+
+        let score = calculate_overall_score(&test_result);
+        assert!(score > 0.0, "Overall score should be greater than 0");
+        
+        // 各项指标先按参考基准归一化（参考机器为1000分），预期分数是CPU和内存两部分的平均值，
+        // 没有存储结果时存储部分不计入，而不是按0分计
+        let normalized = |value: f64, baseline: f64| value / baseline * 1000.0;
+        let cpu_scores = [normalized(100.0, 110.0), normalized(200.0, 450.0), normalized(150.0, 300.0)];
+        let expected_cpu_score = cpu_scores.iter().sum::<f64>() / 3.0;
+        let expected_memory_score = normalized((1000.0 + 800.0) / 2.0, 1350.0);
+        let expected_overall = (expected_cpu_score + expected_memory_score) / 2.0;
+        
+        assert!((score - expected_overall).abs() < 0.1, "Score calculation should be accurate");
+
+        // 权重只在有结果的部分之间归一化，没有结果的存储部分的权重不影响总分
+        let mut weighted = test_result.clone();
+        let mut config = BenchmarkConfig::default();
+        config.score_weights = [("cpu".to_string(), 2.0), ("storage".to_string(), 5.0)].into_iter().collect();
+        weighted.config_used = Some(config);
+        weighted.update_scores();
+        let expected_weighted = (expected_cpu_score * 2.0 + expected_memory_score) / 3.0;
+        assert!((weighted.overall_score - expected_weighted).abs() < 0.1);
+        assert_eq!(weighted.category_scores.keys().collect::<Vec<_>>(), ["cpu", "memory"]);
+        assert!((weighted.category_scores["memory"] - expected_memory_score).abs() < 0.1);
+
+        // 压缩和哈希测试计入CPU部分，其余扩展测试不计入
+        test_result.extra_results.insert("sleep".to_string(), serde_json::json!({ "score": 1e6 }));
+        test_result.extra_results.insert(
+            "compression".to_string(),
+            serde_json::json!({
+                "corpus_bytes": 1024,
+                "seed": 1,
+                "codecs": [{ "algorithm": "zstd", "level": 1, "compress_throughput": 300.0, "decompress_throughput": 500.0, "ratio": 3.0 }],
+                "test_duration": 0.1
+            }),
+        );
+        test_result.extra_results.insert(
+            "hashing".to_string(),
+            serde_json::json!({
+                "keys": 1000,
+                "lookups": 1000,
+                "hashers": [{ "algorithm": "fxhash", "insert_ops_per_second": 40e6, "lookup_ops_per_second": 60e6 }],
+                "prngs": [{ "algorithm": "xoshiro256", "numbers_per_second": 800e6 }],
+                "test_duration": 0.1
+            }),
+        );
+        let expected_cpu_score = (cpu_scores.iter().sum::<f64>() + normalized(400.0, 500.0) + normalized(300.0, 200.0)) / 5.0;
+        let expected_overall = (expected_cpu_score + expected_memory_score) / 2.0;
+        assert!((calculate_overall_score(&test_result) - expected_overall).abs() < 0.1);
+
+        // 数据库测试单独构成存储部分（本例没有顺序读写结果）
+        test_result.extra_results.insert(
+            "database".to_string(),
+            serde_json::json!({
+                "runs": [{ "journal_mode": "wal", "rows": 10, "phases": [{ "phase": "insert", "operations": 10, "ops_per_second": 30000.0, "p95_latency_ms": 1.0 }] }],
+                "test_duration": 0.1
+            }),
+        );
+        let expected_storage_score = normalized(30.0, 50.0);
+        let expected_overall = (expected_cpu_score + expected_memory_score + expected_storage_score) / 3.0;
+        assert!((calculate_overall_score(&test_result) - expected_overall).abs() < 0.1);
+
+        // WebView测试单独作为一部分
+        test_result.webview_results = Some(serde_json::from_value(serde_json::json!({
+            "workloads": [{ "workload": "dom_nodes", "operations": 1000, "duration_ms": 20.0, "ops_per_second": 50000.0 }],
+            "test_duration": 1.0
+        })).unwrap());
+        let expected_overall = (expected_cpu_score + expected_memory_score + expected_storage_score + normalized(50.0, 100.0)) / 4.0;
+        assert!((calculate_overall_score(&test_result) - expected_overall).abs() < 0.1);
+    }
+
+    #[tokio::test]
+    async fn test_suite_logs_phase_markers() {
+        let app = tauri::test::mock_app();
+        let dir = tempfile::tempdir().unwrap();
+        let session_id = "session-logs".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        let upload: SharedUploadSettings = Arc::new(Mutex::new(UploadSettings {
+            path: dir.path().join("upload.json"),
+            config: UploadConfig::default(),
+        }));
+        let config = BenchmarkConfig {
+            cpu_test: Toggle::new(true, CpuTestConfig { thread_count: 1, test_duration: 1, ..Default::default() }),
+            memory_test: Toggle::new(true, MemoryTestConfig { buffer_size: 1, iterations: 1, ..Default::default() }),
+            storage_test: Toggle::new(false, StorageTestConfig { file_size: 1, block_size: 4, ..Default::default() }),
+            auto_duration_target_seconds: None,
+            skip_calibration: false,
+            ..BenchmarkConfig::default()
+        };
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+        run_full_benchmark_suite(app.handle().clone(), emitter, config, sessions.clone(), results.clone(), upload, RunMode::Interactive)
+            .await
+            .unwrap();
+        
+        // 新结果带有版本信息
+        let stored = results.lock().unwrap().load(&session_id).unwrap();
+        assert_eq!(stored.app_info, Some(get_app_info(app.handle().clone())));
+
+        let messages: Vec<String> = sessions.lock().unwrap()[&session_id]
+            .logs
+            .since(0)
+            .into_iter()
+            .map(|entry| entry.message)
+            .collect();
+        for marker in ["CPU基准测试开始", "CPU基准测试结束", "内存基准测试开始", "内存基准测试结束"] {
+            assert!(messages.iter().any(|m| m.starts_with(marker)), "日志缺少标记: {}", marker);
+        }
+        assert!(!messages.iter().any(|m| m.starts_with("存储基准测试")));
+        assert!(messages.first().unwrap().starts_with("测试套件开始"));
+        assert!(messages.last().unwrap().starts_with("测试套件结束"));
+        assert_eq!(sessions.lock().unwrap()[&session_id].status, TestStatus::Completed);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_completion_webhook_failure_does_not_change_outcome() {
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+        let server = MockServer::start().await;
+        Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let app = tauri::test::mock_app();
+        let dir = tempfile::tempdir().unwrap();
+        app.manage::<SharedWebhookSettings>(Arc::new(Mutex::new(WebhookSettings {
+            path: dir.path().join("webhook.json"),
+            config: WebhookConfig {
+                url: Some(format!("{}/hook", server.uri())),
+                template: None,
+                enabled: true,
+            },
+        })));
+        let session_id = "session-webhook".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        let upload: SharedUploadSettings = Arc::new(Mutex::new(UploadSettings {
+            path: dir.path().join("upload.json"),
+            config: UploadConfig::default(),
+        }));
+        let config = BenchmarkConfig {
+            cpu_test: Toggle::new(false, CpuTestConfig::default()),
+            memory_test: Toggle::new(true, MemoryTestConfig { buffer_size: 1, iterations: 1, ..Default::default() }),
+            storage_test: Toggle::new(false, StorageTestConfig::default()),
+            auto_duration_target_seconds: None,
+            skip_calibration: true,
+            ..BenchmarkConfig::default()
+        };
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+        run_full_benchmark_suite(app.handle().clone(), emitter, config, sessions.clone(), results.clone(), upload, RunMode::Interactive)
+            .await
+            .unwrap();
+        assert_eq!(sessions.lock().unwrap()[&session_id].status, TestStatus::Completed);
+
+        // 通知在后台发送，重试一次后失败只留下警告日志
+        let logged = |sessions: &TestSessions| {
+            sessions.lock().unwrap()[&session_id]
+                .logs
+                .since(0)
+                .into_iter()
+                .any(|entry| entry.message.starts_with("完成通知发送失败"))
+        };
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        while !logged(&sessions) && std::time::Instant::now() < deadline {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+        assert!(logged(&sessions));
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["session_id"], session_id.as_str());
+        assert_eq!(body["outcome"], "success");
+        assert_eq!(body["result_id"], session_id.as_str());
+        assert_eq!(sessions.lock().unwrap()[&session_id].status, TestStatus::Completed);
+        assert!(results.lock().unwrap().load(&session_id).is_ok());
+    }
+
+    #[test]
+    fn test_launch_run_exit_code_follows_outcome() {
+        let app = tauri::test::mock_app();
+        let dir = tempfile::tempdir().unwrap();
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        app.manage(results.clone());
+        let saved = benchmark::test_support::sample_test_result("2024-06-01T09:00:00Z", 42.0);
+        results.lock().unwrap().save("saved", &saved).unwrap();
+
+        let output = dir.path().join("out").join("result.json");
+        let options = parse_launch_args(["--run-preset", "quick", "--exit-when-done", "--output", output.to_str().unwrap()]).unwrap();
+        // 注入的结束方式决定退出码，结果写到--output指定的路径
+        assert_eq!(finish_launch_run(app.handle(), &options, "saved", SuiteOutcome::Success), 0);
+        let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(written["overall_score"], 42.0);
+        assert_eq!(finish_launch_run(app.handle(), &options, "saved", SuiteOutcome::Partial), 2);
+        assert_eq!(finish_launch_run(app.handle(), &options, "missing", SuiteOutcome::Failure), 2);
+        // 结果写出失败时即使测试成功也按执行出错退出
+        assert_eq!(finish_launch_run(app.handle(), &options, "missing", SuiteOutcome::Success), 2);
+        let without_output = parse_launch_args(["--run-preset", "quick"]).unwrap();
+        assert_eq!(finish_launch_run(app.handle(), &without_output, "missing", SuiteOutcome::Success), 0);
+
+        // 与上一次结果相比有指标回归时退出码为1，JUnit报告中对应的用例失败
+        let mut regressed = benchmark::test_support::sample_test_result("2024-06-02T09:00:00Z", 30.0);
+        regressed.regression_check = Some(ResultComparison::between("saved", &saved, "regressed", &regressed, &RegressionThresholds::default()));
+        results.lock().unwrap().save("regressed", &regressed).unwrap();
+        let report = dir.path().join("report.xml");
+        let junit = parse_launch_args(["--run-preset", "quick", "--output-format=junit", "--output", report.to_str().unwrap()]).unwrap();
+        assert_eq!(finish_launch_run(app.handle(), &junit, "regressed", SuiteOutcome::Success), 1);
+        assert!(std::fs::read_to_string(&report).unwrap().contains("<failure type=\"regression\""));
+        assert_eq!(finish_launch_run(app.handle(), &junit, "regressed", SuiteOutcome::Failure), 2);
+    }
+
+    #[tokio::test]
+    async fn test_exports_load_saved_results_by_id() {
+        let app = tauri::test::mock_app();
+        let dir = tempfile::tempdir().unwrap();
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        app.manage(results.clone());
+        // 会话记录已被清理，只有以会话ID保存的结果
+        let mut saved = benchmark::test_support::sample_test_result("2024-06-01T09:00:00Z", 42.0);
+        saved.session_id = Some("saved".to_string());
+        results.lock().unwrap().save("saved", &saved).unwrap();
+
+        let html = dir.path().join("report.html");
+        export_result_html("saved".to_string(), html.to_string_lossy().into_owned(), app.state()).await.unwrap();
+        assert!(std::fs::read_to_string(&html).unwrap().contains("<div class=\"score\">42.00</div>"));
+        let csv = dir.path().join("result.csv");
+        export_result_csv("saved".to_string(), csv.to_string_lossy().into_owned(), app.state()).await.unwrap();
+        assert!(std::fs::read_to_string(&csv).unwrap().contains("overall,Overall Score,42.000,points"));
+
+        let missing = export_result_html("missing".to_string(), html.to_string_lossy().into_owned(), app.state()).await;
+        assert!(missing.is_err());
+        let missing = export_result_csv("missing".to_string(), csv.to_string_lossy().into_owned(), app.state()).await;
+        assert_eq!(missing.unwrap_err().code, "DATA_LOAD_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_history_commands_list_and_delete_sessions() {
+        let app = tauri::test::mock_app();
+        let dir = tempfile::tempdir().unwrap();
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        app.manage(results.clone());
+        let mut saved = benchmark::test_support::sample_test_result("2024-06-01T09:00:00Z", 42.0);
+        saved.session_id = Some("session-a".to_string());
+        results.lock().unwrap().save("session-a", &saved).unwrap();
+        results.lock().unwrap().save("session-a-run1", &saved).unwrap();
+
+        let history = list_benchmark_history(app.state()).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!((history[0].session_id.as_str(), history[0].overall_score), ("session-a", 42.0));
+        let loaded = get_benchmark_result("session-a".to_string(), app.state()).await.unwrap();
+        assert_eq!(loaded.session_id.as_deref(), Some("session-a"));
+        assert_eq!(get_benchmark_result("session-a-run1".to_string(), app.state()).await.unwrap_err().code, "DATA_LOAD_ERROR");
+
+        let report = delete_benchmark_result("session-a".to_string(), app.state()).await.unwrap();
+        assert_eq!(report.removed, 2);
+        assert!(list_benchmark_history(app.state()).await.unwrap().is_empty());
+        let missing = delete_benchmark_result("session-a".to_string(), app.state()).await.unwrap_err();
+        assert_eq!(missing.code, "DATA_LOAD_ERROR");
+    }
+
+    #[tokio::test]
+    async fn test_poisoned_session_lock_returns_internal_error() {
+        let app = tauri::test::mock_app();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        sessions.lock().unwrap().insert("session-a".to_string(), SessionRecord::new(None));
+        app.manage(sessions.clone());
+
+        // 持锁的线程panic后锁被毒化，命令返回错误而不是跟着panic
+        let poisoner = sessions.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("poison");
+        })
+        .join();
+        assert!(sessions.is_poisoned());
+
+        let error = get_ui_state("session-a".to_string(), app.state()).await.unwrap_err();
+        assert_eq!(error.code, "INTERNAL_ERROR");
+        assert_eq!(get_all_test_sessions(app.state()).await.unwrap_err().code, "INTERNAL_ERROR");
+
+        let locale: SharedLocale = Arc::default();
+        app.manage(locale.clone());
+        let _ = std::thread::spawn(move || {
+            let _guard = locale.lock().unwrap();
+            panic!("poison");
+        })
+        .join();
+        assert_eq!(get_error_catalog(app.state()).unwrap_err().code, "INTERNAL_ERROR");
+        assert_eq!(estimate_benchmark_run(BenchmarkConfig::quick(), app.state()).unwrap_err().code, "INTERNAL_ERROR");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_save_failure_is_reported_in_completion_event() {
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let completions = Arc::new(Mutex::new(Vec::new()));
+        let completions_clone = completions.clone();
+        app.handle().listen_any(events::BENCHMARK_COMPLETE, move |event| {
+            let payload: ipc::BenchmarkSuiteCompleteEvent = serde_json::from_str(event.payload()).unwrap();
+            completions_clone.lock().unwrap().push(payload);
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let session_id = "session-save-failed".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        // 结果目录在打开后被替换为普通文件，保存必然失败
+        let results_dir = dir.path().join("results");
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(&results_dir).unwrap()));
+        std::fs::remove_dir_all(&results_dir).unwrap();
+        std::fs::write(&results_dir, "").unwrap();
+        let upload: SharedUploadSettings = Arc::new(Mutex::new(UploadSettings {
+            path: dir.path().join("upload.json"),
+            config: UploadConfig::default(),
+        }));
+        let config = BenchmarkConfig {
+            cpu_test: Toggle::new(true, CpuTestConfig { thread_count: 1, test_duration: 1, ..Default::default() }),
+            memory_test: Toggle::new(false, MemoryTestConfig::default()),
+            storage_test: Toggle::new(false, StorageTestConfig::default()),
+            auto_duration_target_seconds: None,
+            skip_calibration: true,
+            skip_regression_check: true,
+            ..BenchmarkConfig::default()
+        };
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+        run_full_benchmark_suite(app.handle().clone(), emitter, config, sessions.clone(), results, upload, RunMode::Interactive)
+            .await
+            .unwrap();
+
+        let completions = completions.lock().unwrap();
+        assert_eq!(completions.len(), 1);
+        let complete = &completions[0];
+        assert!(complete.success);
+        assert_eq!(complete.save_error.as_ref().unwrap().code, "DATA_SAVE_ERROR");
+        let warnings = &complete.results.as_ref().unwrap().warnings;
+        assert!(warnings.iter().any(|warning| warning.warning_type == "result_save_failed"), "{:?}", warnings);
+        assert_eq!(sessions.lock().unwrap()[&session_id].result_id, None);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_session_record_tracks_progress_mid_run() {
+        let app = tauri::test::mock_app();
+        let dir = tempfile::tempdir().unwrap();
+        let session_id = "session-progress".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        let upload: SharedUploadSettings = Arc::new(Mutex::new(UploadSettings {
+            path: dir.path().join("upload.json"),
+            config: UploadConfig::default(),
+        }));
+        let config = BenchmarkConfig {
+            cpu_test: Toggle::new(true, CpuTestConfig { thread_count: 1, test_duration: 1, ..Default::default() }),
+            memory_test: Toggle::new(true, MemoryTestConfig { buffer_size: 1, iterations: 1, ..Default::default() }),
+            storage_test: Toggle::new(false, StorageTestConfig { file_size: 1, block_size: 4, ..Default::default() }),
+            auto_duration_target_seconds: None,
+            skip_calibration: true,
+            ..BenchmarkConfig::default()
+        };
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+        let suite = tokio::spawn(run_full_benchmark_suite(
+            app.handle().clone(),
+            emitter,
+            config,
+            sessions.clone(),
+            results,
+            upload,
+            RunMode::Interactive,
+        ));
+
+        // 运行期间轮询会话记录，和前端调用get_session_status看到的一致
+        let mut mid_run = None;
+        while !suite.is_finished() {
+            let session = sessions.lock().unwrap()[&session_id].to_test_session(&session_id);
+            if session.overall_progress > 0.0 && session.overall_progress < 100.0 {
+                mid_run = Some(session);
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let mid_run = mid_run.expect("运行期间没有观察到进度");
+        assert!(!mid_run.current_test.as_deref().unwrap_or_default().is_empty());
+        assert!(!mid_run.last_update.is_empty());
+
+        suite.await.unwrap().unwrap();
+        let session = sessions.lock().unwrap()[&session_id].to_test_session(&session_id);
+        assert_eq!(session.status, TestStatus::Completed);
+        assert_eq!(session.overall_progress, 100.0);
+        assert_eq!(session.current_test, None);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_ui_state_snapshot_matches_session_record() {
+        let app = tauri::test::mock_app();
+        let dir = tempfile::tempdir().unwrap();
+        let session_id = "session-ui-state".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        let upload: SharedUploadSettings = Arc::new(Mutex::new(UploadSettings {
+            path: dir.path().join("upload.json"),
+            config: UploadConfig::default(),
+        }));
+        let config = BenchmarkConfig {
+            cpu_test: Toggle::new(true, CpuTestConfig { thread_count: 1, test_duration: 1, ..Default::default() }),
+            memory_test: Toggle::new(true, MemoryTestConfig { buffer_size: 1, iterations: 1, ..Default::default() }),
+            storage_test: Toggle::new(false, StorageTestConfig { file_size: 1, block_size: 4, ..Default::default() }),
+            auto_duration_target_seconds: None,
+            skip_calibration: true,
+            skip_regression_check: true,
+            ..BenchmarkConfig::default()
+        };
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+        let suite = tokio::spawn(run_full_benchmark_suite(
+            app.handle().clone(),
+            emitter,
+            config,
+            sessions.clone(),
+            results.clone(),
+            upload,
+            RunMode::Interactive,
+        ));
+
+        // 运行期间的快照与同一时刻的会话记录一致
+        let mut mid_run = None;
+        while !suite.is_finished() {
+            {
+                let sessions_guard = sessions.lock().unwrap();
+                let record = &sessions_guard[&session_id];
+                let snapshot = record.ui_state(&session_id);
+                // 第一次监控采样要等满CPU占用的最小刷新间隔，等到有监控数据时再比较
+                let observed = snapshot.latest_sample.is_some() && snapshot.monitoring.is_some();
+                if snapshot.overall_progress > 0.0 && snapshot.overall_progress < 100.0 && observed {
+                    assert_eq!(snapshot.status, record.status);
+                    assert_eq!(snapshot.overall_progress, record.overall_progress);
+                    assert_eq!(snapshot.current_test, record.current_test);
+                    assert_eq!(snapshot.phase, record.phase);
+                    assert_eq!(snapshot.tests, record.test_progress);
+                    assert_eq!(snapshot.estimated_time_remaining, record.eta_seconds);
+                    mid_run = Some(snapshot);
+                    break;
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let mid_run = mid_run.expect("运行期间没有观察到进度");
+        assert_eq!(mid_run.status, TestStatus::Running);
+        assert!(!mid_run.phase.as_deref().unwrap_or_default().is_empty());
+        assert_eq!(mid_run.tests.first().map(|test| test.test_type.as_str()), Some("cpu"));
+        assert!(mid_run.tests.iter().all(|test| (0.0..=100.0).contains(&test.progress)));
+        assert_eq!(mid_run.current_test.as_deref(), mid_run.tests.last().map(|test| test.test_type.as_str()));
+        assert!(mid_run.monitoring.is_some());
+        assert_eq!(mid_run.result_id, None);
+        assert_eq!(mid_run.end_time, None);
+
+        // 结束后带有结果ID，各测试进度为100，不再有剩余时间
+        suite.await.unwrap().unwrap();
+        let snapshot = sessions.lock().unwrap()[&session_id].ui_state(&session_id);
+        assert_eq!(snapshot.status, TestStatus::Completed);
+        assert_eq!(snapshot.overall_progress, 100.0);
+        assert_eq!(snapshot.current_test, None);
+        let tests: Vec<(&str, f64)> = snapshot.tests.iter().map(|test| (test.test_type.as_str(), test.progress)).collect();
+        assert_eq!(tests, [("cpu", 100.0), ("memory", 100.0)]);
+        assert_eq!(snapshot.result_id.as_deref(), Some(session_id.as_str()));
+        assert!(results.lock().unwrap().load(&session_id).is_ok());
+        assert_eq!(snapshot.estimated_time_remaining, None);
+        assert!(snapshot.end_time.is_some());
+        assert_eq!(snapshot.latest_sample.as_ref().map(|sample| sample.session_id.as_str()), Some(session_id.as_str()));
+
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["resultId"], session_id);
+        assert!(json["recentWarnings"].is_array());
+    }
+
+    #[tokio::test]
+    async fn test_completion_estimate_matches_actual_completion() {
+        use tauri::ipc::InvokeResponseBody;
+
+        let app = tauri::test::mock_app();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        let channel = Channel::new(move |body| {
+            if let InvokeResponseBody::Json(json) = body {
+                received_clone.lock().unwrap().push(serde_json::from_str::<serde_json::Value>(&json).unwrap());
+            }
+            Ok(())
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let session_id = "session-estimate".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        let upload: SharedUploadSettings = Arc::new(Mutex::new(UploadSettings {
+            path: dir.path().join("upload.json"),
+            config: UploadConfig::default(),
+        }));
+        // 最短的CPU测试：4个阶段各1秒，耗时可预期
+        let config = BenchmarkConfig {
+            cpu_test: Toggle::new(true, CpuTestConfig { thread_count: 1, test_duration: 1, ..Default::default() }),
+            memory_test: Toggle::new(false, MemoryTestConfig { buffer_size: 1, iterations: 1, ..Default::default() }),
+            storage_test: Toggle::new(false, StorageTestConfig { file_size: 1, block_size: 4, ..Default::default() }),
+            auto_duration_target_seconds: None,
+            skip_calibration: true,
+            ..BenchmarkConfig::default()
+        };
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id)
+            .with_channel(channel, false);
+        let started = chrono::Utc::now();
+        run_full_benchmark_suite(app.handle().clone(), emitter, config, sessions.clone(), results, upload, RunMode::Interactive)
+            .await
+            .unwrap();
+
+        let received = received.lock().unwrap();
+        let timestamp = |value: &serde_json::Value| chrono::DateTime::parse_from_rfc3339(value.as_str().unwrap()).unwrap();
+        let estimates: Vec<_> = received
+            .iter()
+            .filter(|e| e["event"] == "progress")
+            .map(|e| timestamp(&e["data"]["estimatedCompletionTime"]))
+            .collect();
+        let complete = received.iter().find(|e| e["event"] == "suiteComplete").unwrap();
+        let completed_at = timestamp(&complete["data"]["completedAt"]);
+        // 开始时的估计与实际完成时间相差不超过几秒
+        let error = (*estimates.first().unwrap() - completed_at).num_milliseconds().abs();
+        assert!(error <= 3000, "估计偏差 {} 毫秒", error);
+        assert!(estimates.iter().all(|estimate| *estimate >= started));
+
+        // 结束后会话记录保留最后的估计，可以与结束时间对照
+        let session = sessions.lock().unwrap()[&session_id].to_test_session(&session_id);
+        assert!(session.estimated_completion_time.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_suite_events_go_through_session_channel() {
+        use tauri::ipc::InvokeResponseBody;
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let global = Arc::new(Mutex::new(Vec::new()));
+        for event_name in events::SUITE_STREAM.iter().chain([&events::SESSION_STATUS_CHANGED]) {
+            let global_clone = global.clone();
+            app.handle().listen_any(*event_name, move |_| global_clone.lock().unwrap().push(event_name.to_string()));
+        }
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        let channel = Channel::new(move |body| {
+            if let InvokeResponseBody::Json(json) = body {
+                received_clone.lock().unwrap().push(serde_json::from_str::<serde_json::Value>(&json).unwrap());
+            }
+            Ok(())
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let session_id = "session-channel".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        let upload: SharedUploadSettings = Arc::new(Mutex::new(UploadSettings {
+            path: dir.path().join("upload.json"),
+            config: UploadConfig::default(),
+        }));
+        let config = BenchmarkConfig {
+            cpu_test: Toggle::new(true, CpuTestConfig { thread_count: 1, test_duration: 1, ..Default::default() }),
+            memory_test: Toggle::new(true, MemoryTestConfig { buffer_size: 1, iterations: 1, ..Default::default() }),
+            storage_test: Toggle::new(false, StorageTestConfig { file_size: 1, block_size: 4, ..Default::default() }),
+            auto_duration_target_seconds: None,
+            skip_calibration: true,
+            ..BenchmarkConfig::default()
+        };
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id)
+            .with_channel(channel, false);
+        run_full_benchmark_suite(app.handle().clone(), emitter, config, sessions, results, upload, RunMode::Interactive)
+            .await
+            .unwrap();
+
+        // 去掉相邻重复后的事件顺序
+        let received = received.lock().unwrap();
+        let mut sequence: Vec<&str> = received.iter().map(|e| e["event"].as_str().unwrap()).collect();
+        sequence.dedup();
+        let position = |name: &str| sequence.iter().position(|e| *e == name).unwrap_or_else(|| panic!("缺少 {}: {:?}", name, sequence));
+        let milestones: Vec<&str> = sequence.iter().copied().filter(|e| e.ends_with("Complete")).collect();
+        assert_eq!(milestones, ["testComplete", "testComplete", "suiteComplete"]);
+        assert_eq!(*sequence.last().unwrap(), "suiteComplete", "{:?}", sequence);
+        assert!(position("progress") < position("testComplete"));
+        assert!(position("monitoring") < position("testComplete"));
+        assert!(position("performance") < position("testComplete"));
+        assert!(!sequence.contains(&"error"));
+
+        let overall: Vec<f64> = received
+            .iter()
+            .filter(|e| e["event"] == "progress")
+            .map(|e| e["data"]["overallProgress"].as_f64().unwrap())
+            .collect();
+        assert!(overall.windows(2).all(|pair| pair[1] >= pair[0]), "{:?}", overall);
+        assert!(overall.iter().all(|v| (0.0..=100.0).contains(v)), "{:?}", overall);
+
+        let completed: Vec<&serde_json::Value> = received.iter().filter(|e| e["event"] == "testComplete").map(|e| &e["data"]).collect();
+        assert_eq!(completed.len(), 2);
+        assert_eq!(completed[0]["testType"], serde_json::json!("cpu"));
+        assert_eq!(completed[1]["testType"], serde_json::json!("memory"));
+        assert!(completed.iter().all(|c| c["success"] == serde_json::json!(true)));
+        let suite = &received.last().unwrap()["data"];
+        assert_eq!(suite["sessionId"], serde_json::json!(session_id));
+        assert_eq!(suite["success"], serde_json::json!(true));
+
+        // 关闭兼容广播后只有会话状态变化仍是全局事件
+        let global = global.lock().unwrap();
+        assert!(!global.is_empty());
+        assert!(global.iter().all(|name| *name == events::SESSION_STATUS_CHANGED), "{:?}", global);
+    }
+
+    #[tokio::test]
+    async fn test_suite_emits_typed_completion_events() {
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let completed = Arc::new(Mutex::new(Vec::new()));
+        let completed_clone = completed.clone();
+        app.handle().listen_any(events::TEST_COMPLETE, move |event| {
+            let payload: serde_json::Value = serde_json::from_str(event.payload()).unwrap();
+            completed_clone.lock().unwrap().push(payload);
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let session_id = "session-complete-events".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        let upload: SharedUploadSettings = Arc::new(Mutex::new(UploadSettings {
+            path: dir.path().join("upload.json"),
+            config: UploadConfig::default(),
+        }));
+        let config = BenchmarkConfig {
+            cpu_test: Toggle::new(true, CpuTestConfig { thread_count: 1, test_duration: 1, ..Default::default() }),
+            memory_test: Toggle::new(true, MemoryTestConfig { buffer_size: 1, iterations: 1, ..Default::default() }),
+            storage_test: Toggle::new(true, StorageTestConfig { file_size: 1, block_size: 4, ..Default::default() }),
+            auto_duration_target_seconds: None,
+            skip_calibration: false,
+            ..BenchmarkConfig::default()
+        };
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+        run_full_benchmark_suite(app.handle().clone(), emitter, config, sessions, results, upload, RunMode::Interactive)
+            .await
+            .unwrap();
+
+        let completed = completed.lock().unwrap();
+        let order: Vec<&str> = completed.iter().map(|event| event["testType"].as_str().unwrap()).collect();
+        assert_eq!(order, ["cpu", "memory", "storage"]);
+        for event in completed.iter() {
+            assert_eq!(event["success"], serde_json::json!(true));
+            assert_eq!(event["result"]["type"], event["testType"]);
+            assert!(event["elapsedSeconds"].as_f64().unwrap() > 0.0);
+        }
+        let cpu: CpuTestResult = serde_json::from_value(completed[0]["result"]["data"].clone()).unwrap();
+        assert!(cpu.single_thread_score > 0.0);
+    }
+
+    #[test]
+    fn test_regression_against_previous_run_on_same_machine() {
+        use benchmark::comparison::{MetricStatus, Verdict};
+        use benchmark::test_support::sample_test_result;
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let detected = Arc::new(Mutex::new(Vec::new()));
+        let detected_clone = detected.clone();
+        app.handle().listen_any(events::PERFORMANCE_REGRESSION_DETECTED, move |event| {
+            let payload: serde_json::Value = serde_json::from_str(event.payload()).unwrap();
+            detected_clone.lock().unwrap().push(payload);
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path()).unwrap()));
+        let result = |timestamp: &str, session_id: &str, cpu_name: &str| {
+            let mut result = sample_test_result(timestamp, 500.0);
+            result.session_id = Some(session_id.to_string());
+            result.system_info.cpu.name = cpu_name.to_string();
+            result
+        };
+        results.lock().unwrap().save("previous", &result("2024-01-01T00:00:00Z", "previous", "CPU A")).unwrap();
+        // 更晚但硬件不同的结果不作为基准
+        let mut other_machine = result("2024-01-15T00:00:00Z", "other", "CPU B");
+        other_machine.storage_results.as_mut().unwrap().sequential_write.throughput = 100.0;
+        results.lock().unwrap().save("other", &other_machine).unwrap();
+
+        // 存储写入比上个月慢了30%
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), "current");
+        let mut current = result("2024-02-01T00:00:00Z", "current", "CPU A");
+        current.storage_results.as_mut().unwrap().sequential_write.throughput = 280.0;
+        check_regression(&emitter, &results, &BenchmarkConfig::default(), &mut current, &|_, _| {});
+
+        let comparison = current.regression_check.as_ref().unwrap();
+        assert_eq!(comparison.baseline_id, "previous");
+        assert_eq!(comparison.candidate_id, "current");
+        assert_eq!(comparison.report.verdict, Verdict::Fail);
+        let event = detected.lock().unwrap()[0].clone();
+        assert_eq!(event["baselineId"], "previous");
+        assert_eq!(event["baselineTimestamp"], "2024-01-01T00:00:00Z");
+        let regressions = event["regressions"].as_array().unwrap();
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0]["metric"], "storage.sequential_write.throughput");
+        assert_eq!(regressions[0]["change_percent"], -30.0);
+        assert_eq!(
+            comparison.report.metric("storage.sequential_write.throughput").unwrap().status,
+            MetricStatus::Regressed
+        );
+
+        // 更换硬件后的第一次运行没有可比的结果，不对比也不提示
+        let mut upgraded = result("2024-03-01T00:00:00Z", "upgraded", "CPU C");
+        upgraded.storage_results.as_mut().unwrap().sequential_write.throughput = 50.0;
+        check_regression(&emitter, &results, &BenchmarkConfig::default(), &mut upgraded, &|_, _| {});
+        assert!(upgraded.regression_check.is_none());
+        // 没有回归时只附加对比结果，不发送事件
+        let mut unchanged = result("2024-03-01T00:00:00Z", "unchanged", "CPU A");
+        check_regression(&emitter, &results, &BenchmarkConfig::default(), &mut unchanged, &|_, _| {});
+        assert_eq!(unchanged.regression_check.unwrap().report.verdict, Verdict::Pass);
+        assert_eq!(detected.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_suite_runs_registered_extra_test() {
+        use benchmark::core::ExtraTestSpec;
+        use benchmark::test_support::SleepRunner;
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let mut registry = BenchmarkRegistry::with_builtin();
+        SleepRunner::register(&mut registry);
+        app.manage::<SharedBenchmarkRegistry>(Arc::new(registry));
+        let completed = Arc::new(Mutex::new(Vec::new()));
+        let completed_clone = completed.clone();
+        app.handle().listen_any(events::TEST_COMPLETE, move |event| {
+            let payload: serde_json::Value = serde_json::from_str(event.payload()).unwrap();
+            completed_clone.lock().unwrap().push(payload);
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let session_id = "session-extra".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        let upload: SharedUploadSettings = Arc::new(Mutex::new(UploadSettings {
+            path: dir.path().join("upload.json"),
+            config: UploadConfig::default(),
+        }));
+        let config = BenchmarkConfig {
+            cpu_test: Toggle::new(false, CpuTestConfig::default()),
+            memory_test: Toggle::new(true, MemoryTestConfig { buffer_size: 1, iterations: 1, ..Default::default() }),
+            storage_test: Toggle::new(false, StorageTestConfig::default()),
+            extra_tests: vec![ExtraTestSpec {
+                name: "sleep".to_string(),
+                config: serde_json::json!({ "millis": 20, "score": 3.0 }),
+            }],
+            ..BenchmarkConfig::default()
+        };
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+        run_full_benchmark_suite(app.handle().clone(), emitter, config, sessions.clone(), results.clone(), upload, RunMode::Interactive)
+            .await
+            .unwrap();
+
+        // 扩展测试与内置测试一样发送完成事件、写入日志，结果归入注册名
+        let completed = completed.lock().unwrap();
+        let order: Vec<&str> = completed.iter().map(|event| event["testType"].as_str().unwrap()).collect();
+        assert_eq!(order, ["memory", "extra"]);
+        assert_eq!(completed[1]["result"]["data"]["name"], "sleep");
+        let stored = results.lock().unwrap().load(&session_id).unwrap();
+        assert_eq!(stored.extra_results["sleep"], serde_json::json!({ "score": 3.0 }));
+        let messages: Vec<String> = sessions.lock().unwrap()[&session_id]
+            .logs
+            .since(0)
+            .into_iter()
+            .map(|entry| entry.message)
+            .collect();
+        assert!(messages.iter().any(|m| m == "扩展测试sleep结束: 成功"), "{:?}", messages);
+    }
+
+    #[tokio::test]
+    async fn test_panicking_runner_fails_and_session_finishes() {
+        use benchmark::core::ExtraTestSpec;
+        use benchmark::test_support::SleepRunner;
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let mut registry = BenchmarkRegistry::with_builtin();
+        SleepRunner::register(&mut registry);
+        registry.register("panic", |_| {
+            Ok(MockRunner::boxed(TestType::Extra, |_| panic!("sensor handle closed")).0)
+        });
+        app.manage::<SharedBenchmarkRegistry>(Arc::new(registry));
+        let completed = Arc::new(Mutex::new(Vec::new()));
+        let completed_clone = completed.clone();
+        app.handle().listen_any(events::TEST_COMPLETE, move |event| {
+            let payload: ipc::TestCompleteEvent = serde_json::from_str(event.payload()).unwrap();
+            completed_clone.lock().unwrap().push(payload);
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let session_id = "session-panic".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        let upload: SharedUploadSettings = Arc::new(Mutex::new(UploadSettings {
+            path: dir.path().join("upload.json"),
+            config: UploadConfig::default(),
+        }));
+        let config = BenchmarkConfig {
+            cpu_test: Toggle::new(false, CpuTestConfig::default()),
+            memory_test: Toggle::new(false, MemoryTestConfig::default()),
+            storage_test: Toggle::new(false, StorageTestConfig::default()),
+            extra_tests: vec![
+                ExtraTestSpec { name: "panic".to_string(), config: serde_json::json!({}) },
+                ExtraTestSpec { name: "sleep".to_string(), config: serde_json::json!({ "millis": 10, "score": 1.0 }) },
+            ],
+            skip_calibration: true,
+            ..BenchmarkConfig::default()
+        };
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+        run_full_benchmark_suite(app.handle().clone(), emitter, config, sessions.clone(), results.clone(), upload, RunMode::Interactive)
+            .await
+            .unwrap();
+
+        // panic的测试记为失败，后面的测试照常运行
+        let completed = completed.lock().unwrap();
+        let outcomes: Vec<bool> = completed.iter().map(|event| event.success).collect();
+        assert_eq!(outcomes, [false, true]);
+        assert!(completed[0].error.as_deref().unwrap().contains("sensor handle closed"));
+
+        // 会话进入终止状态，panic消息进入日志和Critical警告
+        let sessions_guard = sessions.lock().unwrap();
+        let record = &sessions_guard[&session_id];
+        assert_eq!(record.status, TestStatus::Completed);
+        assert!(record.logs.since(0).iter().any(|entry| entry.message.contains("sensor handle closed")));
+        let stored = results.lock().unwrap().load(&session_id).unwrap();
+        let warning = stored.warnings.iter().find(|warning| warning.warning_type == "test_panic").unwrap();
+        assert_eq!(warning.severity, WarningSeverity::Critical);
+        assert!(stored.extra_results.contains_key("sleep"));
+    }
+
+    #[tokio::test]
+    async fn test_suite_streams_performance_for_every_test() {
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let samples_clone = samples.clone();
+        app.handle().listen_any(events::REAL_TIME_PERFORMANCE, move |event| {
+            let payload: RealTimePerformanceData = serde_json::from_str(event.payload()).unwrap();
+            samples_clone.lock().unwrap().push(payload);
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let session_id = "session-performance".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        let upload: SharedUploadSettings = Arc::new(Mutex::new(UploadSettings {
+            path: dir.path().join("upload.json"),
+            config: UploadConfig::default(),
+        }));
+        let config = BenchmarkConfig {
+            cpu_test: Toggle::new(true, CpuTestConfig { thread_count: 1, test_duration: 1, ..Default::default() }),
+            memory_test: Toggle::new(true, MemoryTestConfig { buffer_size: 1, iterations: 1, ..Default::default() }),
+            storage_test: Toggle::new(true, StorageTestConfig { file_size: 1, block_size: 4, ..Default::default() }),
+            auto_duration_target_seconds: None,
+            skip_calibration: false,
+            ..BenchmarkConfig::default()
+        };
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+        run_full_benchmark_suite(app.handle().clone(), emitter, config, sessions, results, upload, RunMode::Interactive)
+            .await
+            .unwrap();
+
+        let samples = samples.lock().unwrap();
+        for test_type in [TestType::Cpu, TestType::Memory, TestType::Storage] {
+            let of_type: Vec<&RealTimePerformanceData> = samples.iter().filter(|s| s.test_type == test_type).collect();
+            assert!(!of_type.is_empty(), "{} 没有实时性能事件", test_type);
+            for sample in of_type {
+                assert_eq!(sample.session_id, session_id);
+                assert!(!sample.phase.is_empty());
+                for key in benchmark::metrics::required_metrics(test_type.as_str()) {
+                    let value = sample.metrics.get(*key).unwrap_or_else(|| panic!("{} 缺少指标 {}", test_type, key));
+                    assert!(value.is_finite() && *value >= 0.0, "{} {} = {}", test_type, key, value);
+                }
+            }
+        }
+        // 每个阶段结束时都有汇总采样
+        let storage_phases: Vec<&str> = samples
+            .iter()
+            .filter(|s| s.test_type == TestType::Storage && s.metrics["mb_per_sec"] > 0.0)
+            .map(|s| s.phase.as_str())
+            .collect();
+        for phase in ["seq_write", "seq_read", "random_write", "random_read"] {
+            assert!(storage_phases.contains(&phase), "缺少阶段 {}", phase);
+        }
+    }
+
+    // 测试用运行器：按给定的行为返回结果，并记录被调用的次数
+    type MockBehavior = Box<dyn Fn(&RunContext) -> Result<TestOutcome<ipc::TestResultPayload>, BenchmarkError> + Send + Sync>;
+
+    struct MockRunner {
+        test_type: TestType,
+        runs: Arc<std::sync::atomic::AtomicUsize>,
+        behavior: MockBehavior,
+    }
+
+    impl MockRunner {
+        fn boxed(
+            test_type: TestType,
+            behavior: impl Fn(&RunContext) -> Result<TestOutcome<ipc::TestResultPayload>, BenchmarkError> + Send + Sync + 'static,
+        ) -> (Box<dyn BenchmarkRunner>, Arc<std::sync::atomic::AtomicUsize>) {
+            let runs = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+            let runner = Self {
+                test_type,
+                runs: runs.clone(),
+                behavior: Box::new(behavior),
+            };
+            (Box::new(runner), runs)
+        }
+    }
+
+    impl BenchmarkRunner for MockRunner {
+        fn name(&self) -> TestType {
+            self.test_type
+        }
+
+        fn estimated_duration(&self) -> u64 {
+            1
+        }
+
+        fn run(&self, ctx: &RunContext) -> Result<TestOutcome<ipc::TestResultPayload>, BenchmarkError> {
+            self.runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            (self.behavior)(ctx)
+        }
+    }
+
+    #[test]
+    fn test_suite_loop_handles_runner_outcomes_uniformly() {
+        use std::sync::atomic::Ordering;
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let completed = Arc::new(Mutex::new(Vec::new()));
+        let completed_clone = completed.clone();
+        app.handle().listen_any(events::TEST_COMPLETE, move |event| {
+            let payload: ipc::TestCompleteEvent = serde_json::from_str(event.payload()).unwrap();
+            completed_clone.lock().unwrap().push(payload);
+        });
+
+        let session_id = "session-runners".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        record.transition(TestStatus::Running).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+
+        let sample = benchmark::test_support::sample_test_result("2024-01-01T00:00:00Z", 0.0);
+        let cpu_result = sample.cpu_results.clone().unwrap();
+        let (cpu, _) = MockRunner::boxed(TestType::Cpu, move |ctx| {
+            ctx.progress_callback()(50.0, Message::new("cpu.multi_thread.start"));
+            ctx.warn(Message::new("stability.thermal_throttling"));
+            Ok(TestOutcome::Completed(ipc::TestResultPayload::Cpu(cpu_result.clone())))
+        });
+        let (memory, _) = MockRunner::boxed(TestType::Memory, |_| Err(BenchmarkError::MemoryTestError("分配失败".to_string())));
+        // 运行期间会话被取消，运行器通过取消令牌发现后提前停止
+        let sessions_clone = sessions.clone();
+        let session_id_clone = session_id.clone();
+        let (storage, _) = MockRunner::boxed(TestType::Storage, move |ctx| {
+            let mut sessions_guard = sessions_clone.lock().unwrap();
+            sessions_guard.get_mut(&session_id_clone).unwrap().transition(TestStatus::Cancelled).unwrap();
+            drop(sessions_guard);
+            assert!(ctx.is_cancelled());
+            Ok(TestOutcome::Skipped)
+        });
+        let runners = vec![cpu, memory, storage];
+        let tracker = Arc::new(SuiteProgressTracker::new(&runner_estimates(&runners, 1)));
+        let mut test_result = sample.clone();
+        test_result.cpu_results = None;
+        test_result.memory_results = None;
+        test_result.storage_results = None;
+
+        assert!(run_suite_tests(&emitter, &sessions, &tracker, &runners, &BenchmarkConfig::default(), &mut test_result));
+        assert!(test_result.cpu_results.is_some());
+        assert!(test_result.memory_results.is_none() && test_result.storage_results.is_none());
+        assert_eq!(test_result.per_test_durations.len(), 3);
+
+        // 成功和失败都发送完成事件，取消的测试不发送
+        let completed = completed.lock().unwrap();
+        let outcomes: Vec<(TestType, bool)> = completed.iter().map(|event| (event.test_type, event.success)).collect();
+        assert_eq!(outcomes, [(TestType::Cpu, true), (TestType::Memory, false)]);
+        assert!(completed[1].error.as_deref().unwrap().contains("分配失败"));
+
+        // 运行器的警告和测试失败都进入警告历史
+        let warnings: Vec<(TestType, String)> = emitter
+            .warning_history()
+            .into_iter()
+            .map(|warning| (warning.test_type, warning.warning_type))
+            .collect();
+        assert!(warnings.contains(&(TestType::Cpu, "stability.thermal_throttling".to_string())));
+        assert!(warnings.contains(&(TestType::Memory, "test_failure".to_string())));
+
+        let messages: Vec<String> = sessions.lock().unwrap()[&session_id]
+            .logs
+            .since(0)
+            .into_iter()
+            .map(|entry| entry.message)
+            .collect();
+        for marker in ["CPU基准测试结束: 成功", "内存基准测试结束: 失败", "存储基准测试结束: 已取消"] {
+            assert!(messages.iter().any(|m| m.starts_with(marker)), "日志缺少标记: {}", marker);
+        }
+
+        // 会话已取消，后续的测试不再开始
+        let (later, later_runs) = MockRunner::boxed(TestType::Cpu, |_| unreachable!("取消后不应运行"));
+        assert!(!run_suite_tests(&emitter, &sessions, &tracker, &[later], &BenchmarkConfig::default(), &mut test_result));
+        assert_eq!(later_runs.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_runner_issues_reach_result_and_downgrade_outcome() {
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let completed = Arc::new(Mutex::new(Vec::new()));
+        let completed_clone = completed.clone();
+        app.handle().listen_any(events::TEST_COMPLETE, move |event| {
+            let payload: ipc::TestCompleteEvent = serde_json::from_str(event.payload()).unwrap();
+            completed_clone.lock().unwrap().push(payload);
+        });
+
+        let session_id = "session-issues".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        record.transition(TestStatus::Running).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+
+        let sample = benchmark::test_support::sample_test_result("2024-01-01T00:00:00Z", 0.0);
+        let cpu_result = sample.cpu_results.clone().unwrap();
+        let memory_result = sample.memory_results.clone().unwrap();
+        // 低严重程度的问题只记录，不影响完成情况
+        let (cpu, _) = MockRunner::boxed(TestType::Cpu, move |ctx| {
+            ctx.report(Message::new("stability.thermal_throttling"), WarningSeverity::Low);
+            Ok(TestOutcome::Completed(ipc::TestResultPayload::Cpu(cpu_result.clone())))
+        });
+        // Critical问题不中止测试，结果保留，完成情况降为CompletedWithIssues
+        let (memory, _) = MockRunner::boxed(TestType::Memory, move |ctx| {
+            ctx.report(
+                Message::new("storage.cleanup_failed").param("path", "/tmp/x").param("error", "busy"),
+                WarningSeverity::Critical,
+            );
+            Ok(TestOutcome::Completed(ipc::TestResultPayload::Memory(memory_result.clone())))
+        });
+        // 失败的测试仍然带上失败前上报的问题
+        let (storage, _) = MockRunner::boxed(TestType::Storage, |ctx| {
+            ctx.report(Message::new("stability.thermal_throttling"), WarningSeverity::Critical);
+            Err(BenchmarkError::StorageTestError("e".to_string()))
+        });
+        let runners = vec![cpu, memory, storage];
+        let tracker = Arc::new(SuiteProgressTracker::new(&runner_estimates(&runners, 1)));
+        let mut test_result = sample.clone();
+        test_result.cpu_results = None;
+        test_result.memory_results = None;
+        test_result.storage_results = None;
+
+        assert!(run_suite_tests(&emitter, &sessions, &tracker, &runners, &BenchmarkConfig::default(), &mut test_result));
+        assert!(test_result.cpu_results.is_some() && test_result.memory_results.is_some());
+
+        let completed = completed.lock().unwrap();
+        let outcomes: Vec<(TestType, bool, TestCompletion, usize)> = completed
+            .iter()
+            .map(|event| (event.test_type, event.success, event.outcome, event.issues.len()))
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                (TestType::Cpu, true, TestCompletion::Completed, 1),
+                (TestType::Memory, true, TestCompletion::CompletedWithIssues, 1),
+                (TestType::Storage, false, TestCompletion::Failed, 1),
+            ]
+        );
+        let issue = &completed[1].issues[0];
+        assert_eq!((issue.code.as_str(), &issue.severity), ("storage.cleanup_failed", &WarningSeverity::Critical));
+        assert!(issue.message.contains("/tmp/x"), "{}", issue.message);
+
+        // 问题同时进入套件结果的警告列表，并保留严重程度
+        test_result.warnings = emitter.warning_history();
+        let warnings: Vec<(TestType, &str, &WarningSeverity)> = test_result
+            .warnings
+            .iter()
+            .map(|warning| (warning.test_type, warning.warning_type.as_str(), &warning.severity))
+            .collect();
+        assert!(warnings.contains(&(TestType::Cpu, "stability.thermal_throttling", &WarningSeverity::Low)));
+        assert!(warnings.contains(&(TestType::Memory, "storage.cleanup_failed", &WarningSeverity::Critical)));
+        assert!(warnings.contains(&(TestType::Storage, "stability.thermal_throttling", &WarningSeverity::Critical)));
+
+        let messages: Vec<String> = sessions.lock().unwrap()[&session_id]
+            .logs
+            .since(0)
+            .into_iter()
+            .map(|entry| entry.message)
+            .collect();
+        assert!(messages.iter().any(|m| m == "内存基准测试结束: 完成但有问题（1项）"), "{:?}", messages);
+    }
+
+    #[test]
+    fn test_runner_cancelled_mid_plan_keeps_completed_results() {
+        use std::sync::atomic::Ordering;
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        for event in [events::TEST_COMPLETE, events::TEST_ERROR, events::SESSION_STATUS_CHANGED] {
+            let received_clone = received.clone();
+            app.handle().listen_any(event.as_str(), move |payload| {
+                received_clone.lock().unwrap().push((event, payload.payload().to_string()));
+            });
+        }
+
+        let session_id = "session-runner-cancelled".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        record.transition(TestStatus::Running).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+
+        let sample = benchmark::test_support::sample_test_result("2024-01-01T00:00:00Z", 0.0);
+        let cpu_result = sample.cpu_results.clone().unwrap();
+        let (cpu, _) = MockRunner::boxed(TestType::Cpu, move |_| {
+            Ok(TestOutcome::Completed(ipc::TestResultPayload::Cpu(cpu_result.clone())))
+        });
+        // 会话仍在运行，运行器自己决定停止
+        let (memory, _) = MockRunner::boxed(TestType::Memory, |ctx| {
+            assert!(!ctx.is_cancelled());
+            Err(BenchmarkError::Cancelled { test: "memory".to_string(), at_progress: 50.0 })
+        });
+        let (storage, storage_runs) = MockRunner::boxed(TestType::Storage, |_| unreachable!("取消后不应运行"));
+        let runners = vec![cpu, memory, storage];
+        let tracker = Arc::new(SuiteProgressTracker::new(&runner_estimates(&runners, 1)));
+        let mut test_result = sample.clone();
+        test_result.cpu_results = None;
+        test_result.memory_results = None;
+        test_result.storage_results = None;
+
+        // 取消不算测试失败
+        assert!(!run_suite_tests(&emitter, &sessions, &tracker, &runners, &BenchmarkConfig::default(), &mut test_result));
+        assert_eq!(storage_runs.load(Ordering::SeqCst), 0);
+        assert!(test_result.cpu_results.is_some());
+        assert!(test_result.memory_results.is_none());
+        assert!(emitter.warning_history().is_empty());
+
+        let received = received.lock().unwrap();
+        let names: Vec<&str> = received.iter().map(|(event, _)| event.as_str()).collect();
+        assert_eq!(names, [events::TEST_COMPLETE.as_str(), events::SESSION_STATUS_CHANGED.as_str()]);
+        let change: SessionStatusChangedEvent = serde_json::from_str(&received[1].1).unwrap();
+        assert_eq!((change.old_status, change.new_status), (TestStatus::Running, TestStatus::Cancelled));
+        assert_eq!(change.reason.as_deref(), Some("CANCELLED"));
+
+        let sessions_guard = sessions.lock().unwrap();
+        assert_eq!(sessions_guard[&session_id].status, TestStatus::Cancelled);
+        let messages: Vec<String> = sessions_guard[&session_id].logs.since(0).into_iter().map(|entry| entry.message).collect();
+        assert!(messages.iter().any(|m| m == "内存基准测试结束: 已取消（进度 50%）"), "{:?}", messages);
+    }
+
+    #[test]
+    fn test_timed_out_runner_fails_and_suite_continues() {
+        use std::sync::atomic::Ordering;
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let completed = Arc::new(Mutex::new(Vec::new()));
+        let completed_clone = completed.clone();
+        app.handle().listen_any(events::TEST_COMPLETE, move |event| {
+            let payload: ipc::TestCompleteEvent = serde_json::from_str(event.payload()).unwrap();
+            completed_clone.lock().unwrap().push(payload);
+        });
+        let errors = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let errors_clone = errors.clone();
+        app.handle().listen_any(events::TEST_ERROR, move |_| {
+            errors_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+        let session_id = "session-timeout".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        record.transition(TestStatus::Running).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+
+        // 短测试按10秒估计，倍数0.02，时限200毫秒；运行器休眠超过时限
+        let sample = benchmark::test_support::sample_test_result("2024-01-01T00:00:00Z", 0.0);
+        let memory_result = sample.memory_results.clone().unwrap();
+        let (memory, _) = MockRunner::boxed(TestType::Memory, move |_| {
+            std::thread::sleep(std::time::Duration::from_millis(600));
+            Ok(TestOutcome::Completed(ipc::TestResultPayload::Memory(memory_result.clone())))
+        });
+        let storage_result = sample.storage_results.clone().unwrap();
+        let (storage, storage_runs) = MockRunner::boxed(TestType::Storage, move |ctx| {
+            assert!(!ctx.is_cancelled());
+            Ok(TestOutcome::Completed(ipc::TestResultPayload::Storage(storage_result.clone())))
+        });
+        let runners = vec![memory, storage];
+        let tracker = Arc::new(SuiteProgressTracker::new(&runner_estimates(&runners, 1)));
+        let config = BenchmarkConfig {
+            per_test_timeout_factor: 0.02,
+            ..BenchmarkConfig::default()
+        };
+        let mut test_result = sample.clone();
+        test_result.memory_results = None;
+        test_result.storage_results = None;
+
+        assert!(run_suite_tests(&emitter, &sessions, &tracker, &runners, &config, &mut test_result));
+        assert_eq!(storage_runs.load(Ordering::SeqCst), 1);
+        assert!(test_result.memory_results.is_none());
+        assert!(test_result.storage_results.is_some());
+        assert_eq!(sessions.lock().unwrap()[&session_id].status, TestStatus::Running);
+
+        let completed = completed.lock().unwrap();
+        let outcomes: Vec<(TestType, bool)> = completed.iter().map(|event| (event.test_type, event.success)).collect();
+        assert_eq!(outcomes, [(TestType::Memory, false), (TestType::Storage, true)]);
+        assert!(completed[0].error.as_deref().unwrap().contains("timed out"));
+        assert_eq!(errors.load(Ordering::SeqCst), 1);
+
+        let timeout = emitter
+            .warning_history()
+            .into_iter()
+            .find(|warning| warning.warning_type == "test_timeout")
+            .unwrap();
+        assert_eq!((timeout.test_type, timeout.severity), (TestType::Memory, WarningSeverity::High));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_cancel_emits_single_status_change_with_reason() {
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let changes_clone = changes.clone();
+        app.handle().listen_any(events::SESSION_STATUS_CHANGED, move |event| {
+            let change: SessionStatusChangedEvent = serde_json::from_str(event.payload()).unwrap();
+            changes_clone.lock().unwrap().push(change);
+        });
+        let completions = Arc::new(Mutex::new(Vec::new()));
+        let completions_clone = completions.clone();
+        app.handle().listen_any(events::BENCHMARK_COMPLETE, move |event| {
+            let complete: ipc::BenchmarkSuiteCompleteEvent = serde_json::from_str(event.payload()).unwrap();
+            completions_clone.lock().unwrap().push(complete);
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let session_id = "session-cancel".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        let upload: SharedUploadSettings = Arc::new(Mutex::new(UploadSettings {
+            path: dir.path().join("upload.json"),
+            config: UploadConfig::default(),
+        }));
+        let config = BenchmarkConfig {
+            cpu_test: Toggle::new(true, CpuTestConfig { thread_count: 1, test_duration: 30, ..Default::default() }),
+            memory_test: Toggle::new(true, MemoryTestConfig { buffer_size: 1, iterations: 1, ..Default::default() }),
+            storage_test: Toggle::new(false, StorageTestConfig { file_size: 1, block_size: 4, ..Default::default() }),
+            auto_duration_target_seconds: None,
+            skip_calibration: true,
+            ..BenchmarkConfig::default()
+        };
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+        let suite = tokio::spawn(run_full_benchmark_suite(
+            app.handle().clone(),
+            emitter.clone(),
+            config,
+            sessions.clone(),
+            results,
+            upload,
+            RunMode::Interactive,
+        ));
+        while sessions.lock().unwrap()[&session_id].status != TestStatus::Running {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        cancel_session(&emitter, &sessions).unwrap();
+        // 正在运行的CPU测试在下一次进度检查时停止，不会跑满30秒
+        let cancelled_at = std::time::Instant::now();
+        suite.await.unwrap().unwrap();
+        assert!(cancelled_at.elapsed() < std::time::Duration::from_secs(2), "{:?}", cancelled_at.elapsed());
+        let completions = completions.lock().unwrap();
+        assert_eq!(completions.len(), 1);
+        assert!(!completions[0].success);
+        assert!(completions[0].error.as_deref().is_some_and(|error| error.starts_with("suite cancelled")));
+
+        let changes = changes.lock().unwrap();
+        let transitions: Vec<(TestStatus, TestStatus)> = changes
+            .iter()
+            .map(|change| (change.old_status.clone(), change.new_status.clone()))
+            .collect();
+        assert_eq!(
+            transitions,
+            [(TestStatus::Queued, TestStatus::Running), (TestStatus::Running, TestStatus::Cancelled)]
+        );
+        assert_eq!(changes[0].reason, None);
+        assert_eq!(changes[1].reason.as_deref(), Some("CANCELLED"));
+        assert!(changes.iter().all(|change| chrono::DateTime::parse_from_rfc3339(&change.timestamp).is_ok()));
+        assert_eq!(sessions.lock().unwrap()[&session_id].status, TestStatus::Cancelled);
+        // 已取消的会话不能再取消
+        assert_eq!(cancel_session(&emitter, &sessions).unwrap_err().code, "INVALID_TRANSITION");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_pause_and_resume_require_matching_status() {
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let progress_clone = progress.clone();
+        app.handle().listen_any(events::BENCHMARK_PROGRESS, move |event| {
+            let payload: serde_json::Value = serde_json::from_str(event.payload()).unwrap();
+            progress_clone.lock().unwrap().push(payload);
+        });
+
+        let session_id = "session-pause".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+
+        // 尚未运行的会话既不能暂停也不能恢复
+        assert_eq!(pause_session(&emitter, &sessions).unwrap_err().code, "INVALID_TRANSITION");
+        assert_eq!(resume_session(&emitter, &sessions).unwrap_err().code, "INVALID_TRANSITION");
+
+        {
+            let mut sessions_guard = sessions.lock().unwrap();
+            let record = sessions_guard.get_mut(&session_id).unwrap();
+            record.transition(TestStatus::Running).unwrap();
+            record.overall_progress = 40.0;
+            record.current_test = Some("cpu".to_string());
+        }
+        pause_session(&emitter, &sessions).unwrap();
+        assert_eq!(sessions.lock().unwrap()[&session_id].status, TestStatus::Paused);
+        assert_eq!(pause_session(&emitter, &sessions).unwrap_err().code, "INVALID_TRANSITION");
+
+        resume_session(&emitter, &sessions).unwrap();
+        assert_eq!(sessions.lock().unwrap()[&session_id].status, TestStatus::Running);
+        assert_eq!(resume_session(&emitter, &sessions).unwrap_err().code, "INVALID_TRANSITION");
+
+        // 恢复时发送一条进度事件，进度保持暂停前的值
+        let progress = progress.lock().unwrap();
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0]["messageKey"], "suite.resumed");
+        assert_eq!(progress[0]["overallProgress"], 40.0);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_cancel_between_repetitions_keeps_completed_runs() {
+        let app = tauri::test::mock_app();
+        let dir = tempfile::tempdir().unwrap();
+        let session_id = "session-repeat".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        let upload: SharedUploadSettings = Arc::new(Mutex::new(UploadSettings {
+            path: dir.path().join("upload.json"),
+            config: UploadConfig::default(),
+        }));
+        let config = BenchmarkConfig {
+            cpu_test: Toggle::new(true, CpuTestConfig { thread_count: 1, test_duration: 1, ..Default::default() }),
+            memory_test: Toggle::new(false, MemoryTestConfig::default()),
+            storage_test: Toggle::new(false, StorageTestConfig::default()),
+            repeat: 3,
+            ..BenchmarkConfig::default()
+        };
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+        let suite = tokio::spawn(run_full_benchmark_suite(
+            app.handle().clone(),
+            emitter.clone(),
+            config,
+            sessions.clone(),
+            results.clone(),
+            upload,
+            RunMode::Interactive,
+        ));
+        // 第二次运行开始后取消
+        let second_run_started = || {
+            sessions.lock().unwrap()[&session_id]
+                .logs
+                .since(0)
+                .iter()
+                .any(|entry| entry.message == "第 2/3 次运行开始")
+        };
+        while !second_run_started() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        cancel_session(&emitter, &sessions).unwrap();
+        suite.await.unwrap().unwrap();
+
+        // 只汇总已完成的第一次运行，并标记为部分结果
+        let stored = results.lock().unwrap().load(&session_id).unwrap();
+        assert!(stored.partial);
+        assert_eq!(stored.runs.len(), 1);
+        let first_run = results.lock().unwrap().load(&format!("{}-run1", session_id)).unwrap();
+        assert_eq!(stored.runs[0].timestamp, first_run.timestamp);
+        assert_eq!(stored.overall_score, first_run.overall_score);
+        assert!(results.lock().unwrap().load(&format!("{}-run2", session_id)).is_err());
+        assert_eq!(sessions.lock().unwrap()[&session_id].status, TestStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_system_monitoring_emits_until_session_ends() {
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        app.handle().listen_any(events::SYSTEM_MONITORING, move |event| {
+            let data: SystemMonitoringData = serde_json::from_str(event.payload()).unwrap();
+            received_clone.lock().unwrap().push(data);
+        });
+
+        let session_id = "session-monitoring".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        record.transition(TestStatus::Running).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+        let interval = std::time::Duration::from_millis(250);
+        let monitoring = SystemMonitoring::start(emitter.clone(), sessions.clone(), SharedSystemMonitor::default(), interval);
+        std::thread::sleep(std::time::Duration::from_millis(1200));
+        sessions.lock().unwrap().get_mut(&session_id).unwrap().transition(TestStatus::Cancelled).unwrap();
+        // 会话结束后最多再过一个间隔线程就退出，之后不再有采样
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        let count = received.lock().unwrap().len();
+        assert!(count >= 3, "{}", count);
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        assert_eq!(received.lock().unwrap().len(), count);
+        drop(monitoring);
+
+        {
+            let received = received.lock().unwrap();
+            assert!(received.iter().all(|data| data.session_id.as_deref() == Some(session_id.as_str())));
+            assert!(received.iter().all(|data| (0.0..=100.0).contains(&data.cpu_usage)));
+        }
+        assert!(sessions.lock().unwrap()[&session_id].monitoring.is_some());
+
+        // 套件提前返回时drop立即结束线程，不等会话进入终止状态
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        record.transition(TestStatus::Running).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+        let monitoring = SystemMonitoring::start(emitter, sessions, SharedSystemMonitor::default(), std::time::Duration::from_secs(60));
+        let started = std::time::Instant::now();
+        drop(monitoring);
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_watchdog_stops_on_terminal_state() {
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        for event_name in [events::BENCHMARK_HEARTBEAT, events::TEST_WARNING] {
+            let received_clone = received.clone();
+            app.handle().listen_any(event_name, move |event| {
+                let payload: serde_json::Value = serde_json::from_str(event.payload()).unwrap();
+                let kind = payload["warningType"].as_str().unwrap_or("heartbeat").to_string();
+                received_clone.lock().unwrap().push((kind, payload));
+            });
+        }
+
+        let session_id = "session-heartbeat".to_string();
+        let sessions: TestSessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        record.transition(TestStatus::Running).unwrap();
+        sessions.lock().unwrap().insert(session_id.clone(), record);
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), &session_id);
+        emitter.record_progress("storage.seq_write");
+        let config = HeartbeatConfig { interval_secs: 1, stall_threshold_secs: 2 };
+        let watchdog = tokio::spawn(run_heartbeat_watchdog(emitter, sessions.clone(), config));
+
+        tokio::time::sleep(std::time::Duration::from_millis(3500)).await;
+        sessions.lock().unwrap().get_mut(&session_id).unwrap().transition(TestStatus::Completed).unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(3), watchdog).await.unwrap().unwrap();
+
+        let received = received.lock().unwrap();
+        let heartbeats: Vec<&serde_json::Value> = received.iter().filter(|(kind, _)| kind == "heartbeat").map(|(_, p)| p).collect();
+        assert!(heartbeats.len() >= 2, "{:?}", received);
+        assert_eq!(heartbeats[0]["phase"], serde_json::json!("storage.seq_write"));
+        assert!(heartbeats[0]["secondsSinceProgress"].as_f64().unwrap() >= 1.0);
+        let stalls: Vec<&serde_json::Value> = received.iter().filter(|(kind, _)| kind == "stall").map(|(_, p)| p).collect();
+        assert_eq!(stalls.len(), 1);
+        assert_eq!(stalls[0]["messageKey"], serde_json::json!("suite.stalled"));
+        // 停滞警告在第一次心跳之后
+        let first_stall = received.iter().position(|(kind, _)| kind == "stall").unwrap();
+        assert!(first_stall > 0);
+    }
+
+    #[test]
+    fn test_get_app_info() {
+        let app = tauri::test::mock_app();
+        let info = get_app_info(app.handle().clone());
+        assert!(!info.app_version.is_empty());
+        assert_eq!(info.engine_version, env!("CARGO_PKG_VERSION"));
+        assert!(!info.git_commit.is_empty());
+        assert!(!info.build_profile.is_empty());
+        assert_eq!(info.schema_version, RESULT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_ipc_error_conversion() {
+        let benchmark_error = BenchmarkError::CpuTestError("Test error".to_string());
+        let ipc_error: ipc::IpcError = benchmark_error.into();
+        
+        assert_eq!(ipc_error.code, "CPU_TEST_ERROR");
+        // 消息按后端当前语言从错误码目录查找，原始内容保留在details中
+        let entry = ipc::error_catalog(i18n::backend_locale()).into_iter().find(|entry| entry.code == "CPU_TEST_ERROR").unwrap();
+        assert_eq!(ipc_error.message, entry.message);
+        assert_eq!(ipc_error.details, Some("Test error".to_string()));
+        
+        let cases = [
+            (BenchmarkError::SystemInfoError("e".to_string()), "SYSTEM_INFO_ERROR", false),
+            (BenchmarkError::CpuTestError("e".to_string()), "CPU_TEST_ERROR", false),
+            (BenchmarkError::MemoryTestError("e".to_string()), "MEMORY_TEST_ERROR", false),
+            (BenchmarkError::StorageTestError("e".to_string()), "STORAGE_TEST_ERROR", true),
+            (BenchmarkError::GpuTestError("e".to_string()), "GPU_TEST_ERROR", false),
+            (BenchmarkError::IpcTestError("e".to_string()), "IPC_TEST_ERROR", false),
+            (BenchmarkError::ProcessTestError("e".to_string()), "PROCESS_TEST_ERROR", false),
+            (BenchmarkError::CompressionTestError("e".to_string()), "COMPRESSION_TEST_ERROR", false),
+            (BenchmarkError::CryptoTestError("e".to_string()), "CRYPTO_TEST_ERROR", false),
+            (BenchmarkError::DatabaseTestError("e".to_string()), "DATABASE_TEST_ERROR", true),
+            (BenchmarkError::JsonTestError("e".to_string()), "JSON_TEST_ERROR", false),
+            (BenchmarkError::StabilityTestError("e".to_string()), "STABILITY_TEST_ERROR", false),
+            (BenchmarkError::ImageTestError("e".to_string()), "IMAGE_TEST_ERROR", false),
+            (BenchmarkError::PowerPlanError("e".to_string()), "POWER_PLAN_ERROR", false),
+            (BenchmarkError::WebviewTestError("e".to_string()), "WEBVIEW_TEST_ERROR", true),
+            (BenchmarkError::NetworkTestError("e".to_string()), "NETWORK_TEST_ERROR", true),
+            (
+                BenchmarkError::io("写入失败")(std::io::Error::from(std::io::ErrorKind::StorageFull)),
+                "IO_STORAGE_FULL",
+                false,
+            ),
+            (BenchmarkError::DataSaveError("e".to_string()), "DATA_SAVE_ERROR", true),
+            (BenchmarkError::DataLoadError("e".to_string()), "DATA_LOAD_ERROR", false),
+            (BenchmarkError::UploadError("e".to_string()), "UPLOAD_ERROR", true),
+            (
+                BenchmarkError::PermissionError { feature: "smart".to_string(), message: "e".to_string(), remediation: None },
+                "PERMISSION_ERROR",
+                false,
+            ),
+            (
+                BenchmarkError::InvalidTransition { from: TestStatus::Completed, to: TestStatus::Cancelled },
+                "INVALID_TRANSITION",
+                false,
+            ),
+        ];
+        for (error, code, retryable) in cases {
+            let ipc_error = IpcError::from(error);
+            assert_eq!(ipc_error.code, code);
+            assert_eq!(ipc_error.retryable, retryable, "{}", code);
+            assert!(ipc_error.details.is_some());
+            assert!(ipc_error.field.is_none());
+        }
+        
+        // 前端invoke收到的拒绝值
+        let rejection = serde_json::to_value(IpcError::from(BenchmarkError::UploadError("连接超时".to_string()))).unwrap();
+        assert_eq!(
+            rejection,
+            serde_json::json!({
+                "code": "UPLOAD_ERROR",
+                "message": "结果上传失败",
+                "details": "连接超时",
+                "retryable": true,
+                "field": null
+            })
+        );
+        let invalid = serde_json::to_value(IpcError::invalid_argument("intervalSecs", "清理间隔必须大于0")).unwrap();
+        assert_eq!(invalid["code"], "INVALID_ARGUMENT");
+        assert_eq!(invalid["field"], "intervalSecs");
+    }
+
+    #[tokio::test]
+    async fn test_anonymous_summary_requires_consent_and_matches_preview() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let dir = tempfile::tempdir().unwrap();
+        let results: SharedResultStore = Arc::new(Mutex::new(ResultStore::open(dir.path().join("results")).unwrap()));
+        let result = benchmark::test_support::sample_test_result("2024-01-01T00:00:00Z", 42.0);
+        results.lock().unwrap().save("local", &result).unwrap();
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let mut config = AnonymousSummaryConfig {
+            endpoint: Some(server.uri()),
+            ..Default::default()
+        };
+        let err = submit_stored_summary("local", &results, &config, UploadOptions::default()).await.unwrap_err();
+        assert!(matches!(err, BenchmarkError::UploadError(_)));
+
+        config.set_consent(true);
+        let status = submit_stored_summary("local", &results, &config, UploadOptions::default()).await.unwrap();
+        assert_eq!(status.state, UploadState::Succeeded);
+
+        let requests = server.received_requests().await.unwrap();
+        let mut json = String::new();
+        std::io::Read::read_to_string(&mut flate2::read::GzDecoder::new(requests[0].body.as_slice()), &mut json).unwrap();
+        let preview = AnonymousSummary::from(&results.lock().unwrap().load("local").unwrap());
+        assert_eq!(serde_json::from_str::<AnonymousSummary>(&json).unwrap(), preview);
+    }
+}
\ No newline at end of file
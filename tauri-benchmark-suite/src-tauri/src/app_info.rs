@@ -0,0 +1,68 @@
+use crate::benchmark::schema::RESULT_SCHEMA_VERSION;
+use crate::benchmark::system_info::SystemInfo;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 应用与基准测试引擎的版本信息，同时写入每个测试结果以便追溯
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AppInfo {
+    pub app_version: String,    // tauri.conf.json中的应用版本
+    pub engine_version: String, // 基准测试引擎（本crate）版本
+    pub git_commit: String,     // 构建时的git提交哈希，由build.rs写入
+    pub build_profile: String,  // debug / release
+    pub schema_version: u32,    // 结果文档结构版本
+}
+
+impl AppInfo {
+    pub fn new(app_version: impl Into<String>) -> Self {
+        Self {
+            app_version: app_version.into(),
+            engine_version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: option_env!("GIT_COMMIT_HASH").unwrap_or("unknown").to_string(),
+            build_profile: option_env!("BUILD_PROFILE").unwrap_or("unknown").to_string(),
+            schema_version: RESULT_SCHEMA_VERSION,
+        }
+    }
+}
+
+/// 运行方式：图形界面中发起，或者无界面（命令行、嵌入调用）运行
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RunMode {
+    Interactive,
+    Headless,
+}
+
+/// 一次运行的机器可读标识，汇总多台机器的结果时按run_id去重、按指纹归并
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RunIdentity {
+    pub run_id: String,              // UUID v4，每次运行唯一
+    pub machine_fingerprint: String, // 匿名的硬件指纹，见SystemInfo::hardware_fingerprint
+    pub app_version: String,
+    pub engine_version: String,
+    pub os_build: String,            // 操作系统名称、版本和内核版本
+    pub mode: RunMode,
+}
+
+impl RunIdentity {
+    /// 在运行开始时生成，版本信息取自app_info，机器信息取自system_info
+    pub fn new(system_info: &SystemInfo, app_info: &AppInfo, mode: RunMode) -> Self {
+        Self {
+            run_id: Uuid::new_v4().to_string(),
+            machine_fingerprint: system_info.hardware_fingerprint(),
+            app_version: app_info.app_version.clone(),
+            engine_version: app_info.engine_version.clone(),
+            os_build: format!("{} (kernel {})", system_info.os, system_info.system_details.kernel_version),
+            mode,
+        }
+    }
+
+    /// 同一环境下的另一次运行（如重复运行中的每一次），只更换run_id
+    pub fn renewed(&self) -> Self {
+        Self {
+            run_id: Uuid::new_v4().to_string(),
+            ..self.clone()
+        }
+    }
+}
@@ -0,0 +1,410 @@
+//! 重复运行的汇总：套件按配置运行多次，各次原始结果按所选策略合并为一个结果
+use crate::benchmark::archive::{ArchiveExtraction, ArchiveMetrics};
+use crate::benchmark::core::TestResult;
+use crate::benchmark::cpu::CpuTestResult;
+use crate::benchmark::gpu::GpuTestResult;
+use crate::benchmark::memory::{InterferenceMeasurement, InterferenceResult, MemoryTestResult};
+use crate::benchmark::network::NetworkTestResult;
+use crate::benchmark::results::HeadlineMetrics;
+use crate::benchmark::storage::{SmallFileMetrics, SmallFilePhase, StorageMetrics, StorageTestResult};
+use crate::benchmark::webview::{WebviewTestResult, WebviewWorkloadResult};
+use crate::ipc::WarningRecord;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// 多次运行结果的合并方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Aggregation {
+    Best, // 取总分最高的一次运行
+    #[default]
+    Median, // 各项指标分别取中位数
+    Mean, // 各项指标分别取平均值
+}
+
+impl Aggregation {
+    /// 按中位数或平均值合并同一指标的多个取值，Best不逐项合并，按中位数处理
+    fn combine(self, mut values: Vec<f64>) -> f64 {
+        if values.is_empty() {
+            return 0.0;
+        }
+        match self {
+            Aggregation::Mean => values.iter().sum::<f64>() / values.len() as f64,
+            Aggregation::Best | Aggregation::Median => {
+                values.sort_by(f64::total_cmp);
+                let middle = values.len() / 2;
+                // 偶数次运行取中间两个值的平均
+                if values.len().is_multiple_of(2) {
+                    (values[middle - 1] + values[middle]) / 2.0
+                } else {
+                    values[middle]
+                }
+            }
+        }
+    }
+}
+
+/// 汇总结果中的一次运行，用于展示各次运行之间的波动
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RunSummary {
+    pub timestamp: String,
+    pub overall_score: f64,
+    pub headline: HeadlineMetrics,
+}
+
+impl RunSummary {
+    pub fn of(result: &TestResult) -> Self {
+        Self {
+            timestamp: result.timestamp.clone(),
+            overall_score: result.overall_score,
+            headline: HeadlineMetrics::from_result(result),
+        }
+    }
+}
+
+/// 合并多次运行的结果。运行信息（时间、系统信息、配置等）取第一次运行，Best策略取总分最高的一次；
+/// 各测试的耗时为所有运行之和，警告合并所有运行，runs中按顺序列出每次运行的关键指标。没有运行时返回None
+pub fn aggregate_runs(runs: &[TestResult], aggregation: Aggregation) -> Option<TestResult> {
+    let first = runs.first()?;
+    let mut aggregate = match aggregation {
+        // 同分时取较早的一次
+        Aggregation::Best => runs
+            .iter()
+            .fold(first, |best, run| if run.overall_score > best.overall_score { run } else { best })
+            .clone(),
+        Aggregation::Median | Aggregation::Mean => {
+            let mut aggregate = first.clone();
+            aggregate.cpu_results = combine_present(runs, |run| run.cpu_results.as_ref(), |results| {
+                aggregate_cpu(&results, aggregation)
+            });
+            aggregate.memory_results = combine_present(runs, |run| run.memory_results.as_ref(), |results| {
+                aggregate_memory(&results, aggregation)
+            });
+            aggregate.storage_results = combine_present(runs, |run| run.storage_results.as_ref(), |results| {
+                aggregate_storage(&results, aggregation)
+            });
+            aggregate.gpu_results = combine_present(runs, |run| run.gpu_results.as_ref(), |results| {
+                aggregate_gpu(&results, aggregation)
+            });
+            aggregate.webview_results = combine_present(runs, |run| run.webview_results.as_ref(), |results| {
+                aggregate_webview(&results, aggregation)
+            });
+            aggregate.network_results = combine_present(runs, |run| run.network_results.as_ref(), |results| {
+                aggregate_network(&results, aggregation)
+            });
+            aggregate.update_scores();
+            aggregate
+        }
+    };
+    aggregate.timestamp = first.timestamp.clone();
+    aggregate.per_test_durations.clear();
+    for run in runs {
+        for (test_type, seconds) in &run.per_test_durations {
+            *aggregate.per_test_durations.entry(*test_type).or_insert(0.0) += seconds;
+        }
+    }
+    aggregate.warnings = merge_warnings(runs);
+    aggregate.runs = runs.iter().map(RunSummary::of).collect();
+    Some(aggregate)
+}
+
+/// 各次运行的警告按(test_type, warning_type)合并，次数相加，保留最早出现时的消息
+fn merge_warnings(runs: &[TestResult]) -> Vec<WarningRecord> {
+    let mut merged: Vec<WarningRecord> = Vec::new();
+    for warning in runs.iter().flat_map(|run| &run.warnings) {
+        match merged
+            .iter_mut()
+            .find(|existing| existing.test_type == warning.test_type && existing.warning_type == warning.warning_type)
+        {
+            Some(existing) => {
+                existing.count += warning.count;
+                existing.emitted += warning.emitted;
+                existing.active = warning.active;
+                existing.last_seen = warning.last_seen.clone();
+            }
+            None => merged.push(warning.clone()),
+        }
+    }
+    merged
+}
+
+/// 只合并包含该项测试结果的运行，都不包含时为None
+fn combine_present<T, U>(
+    runs: &[TestResult],
+    select: impl Fn(&TestResult) -> Option<&T>,
+    combine: impl FnOnce(Vec<&T>) -> U,
+) -> Option<U> {
+    let present: Vec<&T> = runs.iter().filter_map(select).collect();
+    (!present.is_empty()).then(|| combine(present))
+}
+
+fn field<T>(results: &[&T], aggregation: Aggregation, value: impl Fn(&T) -> f64) -> f64 {
+    aggregation.combine(results.iter().map(|result| value(result)).collect())
+}
+
+/// 只合并有取值的运行（如读到温度传感器的运行），都没有时为None
+fn optional_field<T>(results: &[&T], aggregation: Aggregation, value: impl Fn(&T) -> Option<f64>) -> Option<f64> {
+    let values: Vec<f64> = results.iter().filter_map(|result| value(result)).collect();
+    (!values.is_empty()).then(|| aggregation.combine(values))
+}
+
+fn aggregate_cpu(results: &[&CpuTestResult], aggregation: Aggregation) -> CpuTestResult {
+    let combine = |value: fn(&CpuTestResult) -> f64| field(results, aggregation, value);
+    CpuTestResult {
+        single_thread_score: combine(|r| r.single_thread_score),
+        multi_thread_score: combine(|r| r.multi_thread_score),
+        floating_point_score: combine(|r| r.floating_point_score),
+        average_temperature: optional_field(results, aggregation, |r| r.average_temperature.map(f64::from)).map(|value| value as f32),
+        max_temperature: optional_field(results, aggregation, |r| r.max_temperature.map(f64::from)).map(|value| value as f32),
+        test_duration: combine(|r| r.test_duration as f64).round() as u64,
+        operations_per_second: combine(|r| r.operations_per_second as f64).round() as u64,
+    }
+}
+
+fn aggregate_memory(results: &[&MemoryTestResult], aggregation: Aggregation) -> MemoryTestResult {
+    let combine = |value: fn(&MemoryTestResult) -> f64| field(results, aggregation, value);
+    MemoryTestResult {
+        sequential_read_speed: combine(|r| r.sequential_read_speed),
+        sequential_write_speed: combine(|r| r.sequential_write_speed),
+        random_access_speed: combine(|r| r.random_access_speed),
+        latency: combine(|r| r.latency),
+        memory_usage_peak: combine(|r| r.memory_usage_peak as f64).round() as u64,
+        error_rate: combine(|r| r.error_rate),
+        test_duration: combine(|r| r.test_duration as f64).round() as u64,
+        interference: aggregate_interference(results, aggregation),
+    }
+}
+
+// 只合并运行了干扰测试的结果，核心数和各阶段的负载线程数在同一台机器上相同
+fn aggregate_interference(results: &[&MemoryTestResult], aggregation: Aggregation) -> Option<InterferenceResult> {
+    let present: Vec<&InterferenceResult> = results.iter().filter_map(|result| result.interference.as_ref()).collect();
+    let first = present.first()?;
+    let measurements = (0..first.measurements.len())
+        .map(|index| {
+            let phases: Vec<&InterferenceMeasurement> =
+                present.iter().filter_map(|result| result.measurements.get(index)).collect();
+            InterferenceMeasurement {
+                load_threads: phases[0].load_threads,
+                sequential_read_speed: field(&phases, aggregation, |m| m.sequential_read_speed),
+                retention: field(&phases, aggregation, |m| m.retention),
+            }
+        })
+        .collect();
+    Some(InterferenceResult {
+        logical_cores: first.logical_cores,
+        measurements,
+    })
+}
+
+fn aggregate_storage(results: &[&StorageTestResult], aggregation: Aggregation) -> StorageTestResult {
+    let metrics = |select: fn(&StorageTestResult) -> &StorageMetrics| {
+        let metrics: Vec<&StorageMetrics> = results.iter().map(|result| select(result)).collect();
+        StorageMetrics {
+            throughput: field(&metrics, aggregation, |m| m.throughput),
+            iops: field(&metrics, aggregation, |m| m.iops as f64).round() as u64,
+            latency: field(&metrics, aggregation, |m| m.latency),
+        }
+    };
+    StorageTestResult {
+        sequential_read: metrics(|r| &r.sequential_read),
+        sequential_write: metrics(|r| &r.sequential_write),
+        random_read: metrics(|r| &r.random_read),
+        random_write: metrics(|r| &r.random_write),
+        test_duration: field(results, aggregation, |r| r.test_duration as f64).round() as u64,
+        total_data_processed: field(results, aggregation, |r| r.total_data_processed as f64).round() as u64,
+        small_files: aggregate_small_files(results, aggregation),
+        archive: aggregate_archive(results, aggregation),
+    }
+}
+
+// 只合并运行了小文件测试的结果，文件数和总字节数由配置决定，各次运行相同
+fn aggregate_small_files(results: &[&StorageTestResult], aggregation: Aggregation) -> Option<SmallFileMetrics> {
+    let present: Vec<&SmallFileMetrics> = results.iter().filter_map(|result| result.small_files.as_ref()).collect();
+    let first = present.first()?;
+    let phase = |select: fn(&SmallFileMetrics) -> &SmallFilePhase| {
+        let phases: Vec<&SmallFilePhase> = present.iter().map(|metrics| select(metrics)).collect();
+        SmallFilePhase {
+            files: phases[0].files,
+            files_per_second: field(&phases, aggregation, |p| p.files_per_second),
+            p50_ms: field(&phases, aggregation, |p| p.p50_ms),
+            p95_ms: field(&phases, aggregation, |p| p.p95_ms),
+            p99_ms: field(&phases, aggregation, |p| p.p99_ms),
+        }
+    };
+    Some(SmallFileMetrics {
+        file_count: first.file_count,
+        total_bytes: first.total_bytes,
+        create: phase(|m| &m.create),
+        stat: phase(|m| &m.stat),
+        read: phase(|m| &m.read),
+        delete: phase(|m| &m.delete),
+    })
+}
+
+// 只合并运行了压缩包解压测试的结果，按第一次的格式顺序逐项合并；压缩包大小由种子决定，各次运行相同
+fn aggregate_archive(results: &[&StorageTestResult], aggregation: Aggregation) -> Option<ArchiveMetrics> {
+    let present: Vec<&ArchiveMetrics> = results.iter().filter_map(|result| result.archive.as_ref()).collect();
+    let first = present.first()?;
+    let extractions = first
+        .extractions
+        .iter()
+        .map(|extraction| {
+            let matching: Vec<&ArchiveExtraction> = present
+                .iter()
+                .filter_map(|metrics| metrics.extractions.iter().find(|e| e.format == extraction.format))
+                .collect();
+            ArchiveExtraction {
+                seconds: field(&matching, aggregation, |e| e.seconds),
+                throughput: field(&matching, aggregation, |e| e.throughput),
+                files_per_second: field(&matching, aggregation, |e| e.files_per_second),
+                ..extraction.clone()
+            }
+        })
+        .collect();
+    Some(ArchiveMetrics {
+        extractions,
+        ..(*first).clone()
+    })
+}
+
+// 适配器在各次运行中相同，名称和后端取第一次
+fn aggregate_gpu(results: &[&GpuTestResult], aggregation: Aggregation) -> GpuTestResult {
+    GpuTestResult {
+        compute_score: field(results, aggregation, |r| r.compute_score),
+        memory_bandwidth_gb_s: field(results, aggregation, |r| r.memory_bandwidth_gb_s),
+        ..results[0].clone()
+    }
+}
+
+// 各次运行请求的负载相同，按第一次的顺序逐项合并吞吐；操作数和耗时取第一次
+fn aggregate_webview(results: &[&WebviewTestResult], aggregation: Aggregation) -> WebviewTestResult {
+    let workloads = results[0]
+        .workloads
+        .iter()
+        .map(|first| {
+            let rates: Vec<f64> = results
+                .iter()
+                .filter_map(|result| result.workloads.iter().find(|w| w.workload == first.workload))
+                .map(|w| w.ops_per_second)
+                .collect();
+            WebviewWorkloadResult {
+                ops_per_second: aggregation.combine(rates),
+                ..first.clone()
+            }
+        })
+        .collect();
+    WebviewTestResult {
+        workloads,
+        test_duration: field(results, aggregation, |r| r.test_duration),
+        ..results[0].clone()
+    }
+}
+
+// 目标和连接数在各次运行中相同，取第一次
+fn aggregate_network(results: &[&NetworkTestResult], aggregation: Aggregation) -> NetworkTestResult {
+    let combine = |value: fn(&NetworkTestResult) -> f64| field(results, aggregation, value);
+    NetworkTestResult {
+        download_mbps: combine(|r| r.download_mbps),
+        upload_mbps: combine(|r| r.upload_mbps),
+        latency_avg_ms: combine(|r| r.latency_avg_ms),
+        latency_min_ms: combine(|r| r.latency_min_ms),
+        latency_max_ms: combine(|r| r.latency_max_ms),
+        jitter_ms: combine(|r| r.jitter_ms),
+        loss_percent: combine(|r| r.loss_percent),
+        test_duration: combine(|r| r.test_duration),
+        ..results[0].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::test_support::sample_test_result;
+    use crate::benchmark::scoring::calculate_overall_score;
+    use crate::ipc::TestType;
+
+    // 三次运行的单线程分数、内存读取和存储写入分别为乱序的三个值，第二次缺少内存结果
+    fn three_runs() -> Vec<TestResult> {
+        let runs = [
+            ("2024-01-01T00:00:00Z", 150.0, Some(1200.0), 400.0, 10.0),
+            ("2024-01-01T00:01:00Z", 90.0, None, 500.0, 12.0),
+            ("2024-01-01T00:02:00Z", 120.0, Some(1000.0), 300.0, 11.0),
+        ];
+        runs.iter()
+            .map(|(timestamp, single, memory_read, storage_write, seconds)| {
+                let mut run = sample_test_result(timestamp, 0.0);
+                run.cpu_results.as_mut().unwrap().single_thread_score = *single;
+                match memory_read {
+                    Some(read) => run.memory_results.as_mut().unwrap().sequential_read_speed = *read,
+                    None => run.memory_results = None,
+                }
+                run.storage_results.as_mut().unwrap().sequential_write.throughput = *storage_write;
+                run.storage_results.as_mut().unwrap().random_read.iops = (*storage_write as u64) * 10;
+                run.per_test_durations.insert(TestType::Cpu, *seconds);
+                run.update_scores();
+                run
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_median_of_three_runs() {
+        let runs = three_runs();
+        let aggregate = aggregate_runs(&runs, Aggregation::Median).unwrap();
+
+        assert_eq!(aggregate.cpu_results.as_ref().unwrap().single_thread_score, 120.0);
+        // 只有两次运行有内存结果，中位数取两者的平均
+        assert_eq!(aggregate.memory_results.as_ref().unwrap().sequential_read_speed, 1100.0);
+        let storage = aggregate.storage_results.as_ref().unwrap();
+        assert_eq!(storage.sequential_write.throughput, 400.0);
+        assert_eq!(storage.random_read.iops, 4000);
+        assert_eq!(aggregate.overall_score, calculate_overall_score(&aggregate));
+
+        assert_eq!(aggregate.timestamp, "2024-01-01T00:00:00Z");
+        assert_eq!(aggregate.per_test_durations[&TestType::Cpu], 33.0);
+        assert_eq!(aggregate.runs.len(), 3);
+        assert_eq!(
+            aggregate.runs.iter().map(|run| run.headline.cpu_single_thread).collect::<Vec<_>>(),
+            [Some(150.0), Some(90.0), Some(120.0)]
+        );
+        assert_eq!(aggregate.runs[1].headline.memory_read, None);
+        assert_eq!(aggregate.runs[2].overall_score, runs[2].overall_score);
+
+        // GPU结果同样只合并包含它的运行
+        let mut runs = runs;
+        for (run, compute_score) in runs.iter_mut().zip([Some(800.0), None, Some(600.0)]) {
+            run.gpu_results = compute_score.map(|compute_score| GpuTestResult {
+                compute_score,
+                memory_bandwidth_gb_s: 100.0,
+                adapter_name: "Test GPU".to_string(),
+                backend: "vulkan".to_string(),
+            });
+        }
+        let gpu = aggregate_runs(&runs, Aggregation::Median).unwrap().gpu_results.unwrap();
+        assert_eq!(gpu.compute_score, 700.0);
+        assert_eq!(gpu.adapter_name, "Test GPU");
+    }
+
+    #[test]
+    fn test_mean_and_best_aggregation() {
+        let runs = three_runs();
+        let mean = aggregate_runs(&runs, Aggregation::Mean).unwrap();
+        assert_eq!(mean.cpu_results.as_ref().unwrap().single_thread_score, 120.0);
+        assert_eq!(mean.memory_results.as_ref().unwrap().sequential_read_speed, 1100.0);
+        assert_eq!(mean.storage_results.as_ref().unwrap().random_read.iops, 4000);
+
+        let best = aggregate_runs(&runs, Aggregation::Best).unwrap();
+        let highest = runs.iter().map(|run| run.overall_score).fold(f64::NEG_INFINITY, f64::max);
+        assert_eq!(best.overall_score, highest);
+        // Best取整次运行的结果，而不是逐项取最大值
+        let source = runs.iter().find(|run| run.overall_score == highest).unwrap();
+        assert_eq!(
+            HeadlineMetrics::from_result(&best),
+            HeadlineMetrics::from_result(source)
+        );
+        assert_eq!(best.timestamp, runs[0].timestamp);
+        assert_eq!(best.runs.len(), 3);
+
+        assert!(aggregate_runs(&[], Aggregation::Median).is_none());
+    }
+}
@@ -0,0 +1,161 @@
+//! 匿名摘要：从完整结果中按白名单提取硬件型号、核心数、关键指标和版本，用于公开的百分位统计。
+//! 主机名、路径、挂载点、指纹、run_id以及以后新增的字段都不会进入摘要
+use crate::benchmark::core::TestResult;
+use crate::benchmark::results::HeadlineMetrics;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// 摘要结构版本，增删字段时递增
+pub const ANONYMOUS_SUMMARY_VERSION: u32 = 1;
+
+/// 可以匿名提交的结果摘要，每个字段都是显式挑选的
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AnonymousSummary {
+    pub summary_version: u32,
+    pub cpu_model: String,
+    pub cpu_cores: usize,
+    pub cpu_threads: usize,
+    pub cpu_architecture: String,
+    pub memory_total_gb: u64,
+    pub overall_score: f64,
+    pub headline: HeadlineMetrics,
+    pub app_version: Option<String>,    // 旧结果没有版本信息时为空
+    pub engine_version: Option<String>,
+}
+
+impl From<&TestResult> for AnonymousSummary {
+    fn from(result: &TestResult) -> Self {
+        let cpu = &result.system_info.cpu;
+        Self {
+            summary_version: ANONYMOUS_SUMMARY_VERSION,
+            cpu_model: cpu.name.clone(),
+            cpu_cores: cpu.cores,
+            cpu_threads: cpu.threads,
+            cpu_architecture: cpu.architecture.clone(),
+            memory_total_gb: result.system_info.memory.total,
+            overall_score: result.overall_score,
+            headline: HeadlineMetrics::from_result(result),
+            app_version: result.app_info.as_ref().map(|info| info.app_version.clone()),
+            engine_version: result.app_info.as_ref().map(|info| info.engine_version.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_info::{AppInfo, RunIdentity, RunMode};
+    use crate::benchmark::test_support::sample_test_result;
+    use serde_json::{json, Value};
+
+    const HOSTNAME: &str = "alice-workstation.corp.example";
+
+    /// 每个可选字段都有内容的结果，其中多处带有主机名和文件路径
+    fn full_result() -> TestResult {
+        let mut result = sample_test_result("2024-06-01T09:00:00Z", 123.5);
+        result.system_info.system_details.hostname = HOSTNAME.to_string();
+        result.system_info.storage[0].mount_point = "/Users/alice/Volumes/Scratch".to_string();
+        result.app_info = Some(AppInfo::new("2.1.0"));
+        result.identity = Some(RunIdentity::new(&result.system_info, &AppInfo::new("2.1.0"), RunMode::Interactive));
+        result.session_id = Some("session-1".to_string());
+        result.config_used = Some(Default::default());
+
+        let mut document = serde_json::to_value(&result).unwrap();
+        document["failure_log"] = json!([{
+            "index": 0,
+            "timestamp": "2024-06-01T09:00:01Z",
+            "level": "error",
+            "message": format!("无法写入 /Users/alice/Library/bench/tmp.bin ({})", HOSTNAME),
+        }]);
+        document["warnings"] = json!([{
+            "testType": "storage",
+            "warningType": "low_space",
+            "severity": "Medium",
+            "message": "C:\\Users\\alice\\AppData\\Local\\Temp 空间不足",
+            "messageKey": "storage.low_space",
+            "count": 1,
+            "emitted": 1,
+            "active": false,
+            "firstSeen": "2024-06-01T09:00:02Z",
+            "lastSeen": "2024-06-01T09:00:02Z",
+        }]);
+        document["extra_results"] = json!({ "sleep": { "score": 7.5, "log": "/var/tmp/alice/sleep.log" } });
+        document["per_test_durations"] = json!({ "cpu": 10.0, "storage": 4.5 });
+        let mut result: TestResult = serde_json::from_value(document).unwrap();
+        result.seal();
+        result
+    }
+
+    fn strings(value: &Value, out: &mut Vec<String>) {
+        match value {
+            Value::String(s) => out.push(s.clone()),
+            Value::Array(items) => items.iter().for_each(|item| strings(item, out)),
+            Value::Object(map) => map.iter().for_each(|(key, item)| {
+                out.push(key.clone());
+                strings(item, out);
+            }),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_summary_is_an_allowlist_of_the_full_result() {
+        let result = full_result();
+        let summary = AnonymousSummary::from(&result);
+        let document = serde_json::to_value(&summary).unwrap();
+
+        // 只有白名单中的字段；结果新增字段不会出现在这里，摘要新增字段需要同时更新此列表
+        let mut keys: Vec<&str> = document.as_object().unwrap().keys().map(String::as_str).collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            [
+                "app_version",
+                "cpu_architecture",
+                "cpu_cores",
+                "cpu_model",
+                "cpu_threads",
+                "engine_version",
+                "headline",
+                "memory_total_gb",
+                "overall_score",
+                "summary_version",
+            ]
+        );
+        assert_eq!(summary.cpu_model, "Test CPU");
+        assert_eq!(summary.cpu_cores, 4);
+        assert_eq!(summary.overall_score, 123.5);
+        assert_eq!(summary.headline, HeadlineMetrics::from_result(&result));
+        assert_eq!(summary.app_version.as_deref(), Some("2.1.0"));
+
+        // 结果中的顶层字段大多被丢弃
+        let source = serde_json::to_value(&result).unwrap();
+        assert!(source.as_object().unwrap().len() > keys.len());
+    }
+
+    #[test]
+    fn test_summary_contains_no_hostname_or_paths() {
+        let result = full_result();
+        let source = serde_json::to_value(&result).unwrap();
+        let mut source_strings = Vec::new();
+        strings(&source, &mut source_strings);
+        let paths: Vec<&String> = source_strings
+            .iter()
+            .filter(|s| s.contains('/') || s.contains('\\'))
+            .filter(|s| s.len() > 1)
+            .collect();
+        assert!(paths.len() >= 4, "{:?}", paths);
+
+        let summary = serde_json::to_string(&AnonymousSummary::from(&result)).unwrap();
+        assert!(!summary.contains(HOSTNAME));
+        assert!(!summary.contains("alice"));
+        for path in paths {
+            assert!(!summary.contains(path.as_str()), "摘要包含路径 {}", path);
+        }
+        // 指纹和运行标识同样不会出现
+        let identity = result.identity.as_ref().unwrap();
+        assert!(!summary.contains(&identity.machine_fingerprint));
+        assert!(!summary.contains(&identity.run_id));
+        assert!(!summary.contains(&result.integrity.as_ref().unwrap().hash));
+    }
+}
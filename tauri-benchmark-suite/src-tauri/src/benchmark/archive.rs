@@ -0,0 +1,381 @@
+//! 压缩包解压测试：先生成内容由种子决定的压缩包（大量小文件加少数大文件，生成不计时），再计时解压到存储测试的目标磁盘。
+//! 解压同时考验解压缩的CPU开销和小文件写入，接近软件安装和游戏更新的实际负载。作为存储测试的可选子测试运行，
+//! 压缩包和解压出的目录放在同一个工作目录中，成功、失败和取消时都会整个删除
+use crate::benchmark::compression::generate_corpus;
+use crate::benchmark::core::CancellationToken;
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::estimate::available_disk_bytes;
+use crate::benchmark::rng::Xoshiro256;
+use crate::benchmark::storage::available_inodes;
+use crate::i18n::Message;
+use crate::ipc::TestType;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const FILES_PER_DIR: usize = 100; // 压缩包中每个子目录的小文件数
+const SMALL_FILE_MIN_BYTES: usize = 1024;
+const SMALL_FILE_MAX_BYTES: usize = 64 * 1024;
+const CORPUS_BYTES: usize = 4 * 1024 * 1024; // 小文件内容取自这段语料的随机位置
+const FS_BLOCK_BYTES: u64 = 4096; // 每个文件至少占用一个文件系统块
+const HEADROOM: u64 = 2; // 可用空间和inode至少是所需的这么多倍
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum ArchiveFormat {
+    #[serde(rename = "zip")]
+    Zip, // deflate压缩
+    #[serde(rename = "tar_zst")]
+    TarZstd,
+}
+
+impl ArchiveFormat {
+    pub const ALL: [ArchiveFormat; 2] = [ArchiveFormat::Zip, ArchiveFormat::TarZstd];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "zip",
+            ArchiveFormat::TarZstd => "tar_zst",
+        }
+    }
+
+    fn file_name(self) -> &'static str {
+        match self {
+            ArchiveFormat::Zip => "archive.zip",
+            ArchiveFormat::TarZstd => "archive.tar.zst",
+        }
+    }
+}
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ArchiveTestConfig {
+    pub formats: Vec<ArchiveFormat>,
+    #[serde(alias = "smallFiles")]
+    pub small_files: usize, // 1~64KB的小文件数
+    #[serde(alias = "largeFiles")]
+    pub large_files: usize,
+    #[serde(alias = "largeFileSize")]
+    pub large_file_size: u64, // MB
+    pub seed: u64, // 文件大小和内容的随机种子
+}
+
+impl Default for ArchiveTestConfig {
+    fn default() -> Self {
+        Self {
+            formats: ArchiveFormat::ALL.to_vec(),
+            small_files: 5_000,
+            large_files: 4,
+            large_file_size: 32,
+            seed: 0xA5C1,
+        }
+    }
+}
+
+impl ArchiveTestConfig {
+    pub fn file_count(&self) -> usize {
+        self.small_files + self.large_files
+    }
+
+    fn small_file_sizes(&self) -> Vec<usize> {
+        let mut rng = Xoshiro256::new(self.seed);
+        (0..self.small_files)
+            .map(|_| SMALL_FILE_MIN_BYTES + rng.index(SMALL_FILE_MAX_BYTES - SMALL_FILE_MIN_BYTES + 1))
+            .collect()
+    }
+
+    /// 解压出的文件占用的空间，小文件按文件系统块向上取整；小文件大小随机，按平均值估计
+    pub fn extracted_disk_bytes(&self) -> u64 {
+        let small = self.small_files as u64 * ((SMALL_FILE_MIN_BYTES + SMALL_FILE_MAX_BYTES) as u64 / 2).div_ceil(FS_BLOCK_BYTES) * FS_BLOCK_BYTES;
+        small + self.large_files as u64 * self.large_file_size * 1024 * 1024
+    }
+
+    /// 同一时间磁盘上最多有一个压缩包和一份解压出的文件，压缩包按不大于原始数据计
+    pub fn disk_bytes(&self) -> u64 {
+        if self.formats.is_empty() {
+            return 0;
+        }
+        self.extracted_disk_bytes() * 2
+    }
+}
+
+/// 一种格式的解压结果
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ArchiveExtraction {
+    pub format: ArchiveFormat,
+    pub archive_bytes: u64, // 压缩包大小
+    pub seconds: f64, // 解压耗时
+    pub throughput: f64, // MB/s，按解压出的数据量计算
+    pub files_per_second: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ArchiveMetrics {
+    pub file_count: usize,
+    pub total_bytes: u64, // 解压出的数据量
+    pub extractions: Vec<ArchiveExtraction>,
+}
+
+// 测试的工作目录，离开作用域时整个删除，取消和出错时同样生效
+struct ArchiveWorkspace {
+    root: PathBuf,
+}
+
+impl ArchiveWorkspace {
+    fn create(root: &Path) -> Result<Self, BenchmarkError> {
+        // 上次运行中断后残留的目录先删除
+        if root.is_dir() {
+            std::fs::remove_dir_all(root).map_err(BenchmarkError::io("无法删除残留的压缩包测试目录"))?;
+        }
+        std::fs::create_dir_all(root).map_err(BenchmarkError::io("无法创建压缩包测试目录"))?;
+        Ok(Self { root: root.to_path_buf() })
+    }
+
+    fn extract_dir(&self) -> PathBuf {
+        self.root.join("extracted")
+    }
+}
+
+impl Drop for ArchiveWorkspace {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
+
+fn small_file_name(index: usize) -> String {
+    format!("small/{:04}/{:06}.dat", index / FILES_PER_DIR, index)
+}
+
+fn large_file_name(index: usize) -> String {
+    format!("large/{:02}.bin", index)
+}
+
+// 依次产生压缩包中的每个条目（名称和内容），在小文件之间检查取消
+fn for_each_entry(
+    config: &ArchiveTestConfig,
+    cancellation: &CancellationToken,
+    progress: f64,
+    mut add: impl FnMut(&str, &[u8]) -> Result<(), BenchmarkError>,
+) -> Result<(), BenchmarkError> {
+    let corpus = generate_corpus(CORPUS_BYTES, config.seed);
+    let mut rng = Xoshiro256::new(config.seed ^ 0x0FF5E7);
+    for (index, size) in config.small_file_sizes().into_iter().enumerate() {
+        if index % FILES_PER_DIR == 0 {
+            cancellation.check(TestType::Storage, progress)?;
+        }
+        let offset = rng.index(CORPUS_BYTES - size);
+        add(&small_file_name(index), &corpus[offset..offset + size])?;
+    }
+    for index in 0..config.large_files {
+        cancellation.check(TestType::Storage, progress)?;
+        let contents = generate_corpus(config.large_file_size as usize * 1024 * 1024, config.seed.wrapping_add(index as u64 + 1));
+        add(&large_file_name(index), &contents)?;
+    }
+    Ok(())
+}
+
+fn create_archive(format: ArchiveFormat, config: &ArchiveTestConfig, path: &Path, cancellation: &CancellationToken, progress: f64) -> Result<(), BenchmarkError> {
+    let file = BufWriter::new(File::create(path).map_err(BenchmarkError::io("无法创建压缩包"))?);
+    let write_error = |e: std::io::Error| BenchmarkError::io("无法写入压缩包")(e);
+    match format {
+        ArchiveFormat::Zip => {
+            let mut writer = zip::ZipWriter::new(file);
+            let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+            for_each_entry(config, cancellation, progress, |name, contents| {
+                writer.start_file(name, options).map_err(|e| BenchmarkError::StorageTestError(format!("无法写入压缩包: {}", e)))?;
+                writer.write_all(contents).map_err(write_error)
+            })?;
+            writer
+                .finish()
+                .map_err(|e| BenchmarkError::StorageTestError(format!("无法写入压缩包: {}", e)))?
+                .flush()
+                .map_err(write_error)
+        }
+        ArchiveFormat::TarZstd => {
+            let encoder = zstd::Encoder::new(file, ZSTD_LEVEL).map_err(write_error)?;
+            let mut builder = tar::Builder::new(encoder);
+            for_each_entry(config, cancellation, progress, |name, contents| {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(contents.len() as u64);
+                header.set_mode(0o644);
+                header.set_cksum();
+                builder.append_data(&mut header, name, contents).map_err(write_error)
+            })?;
+            builder.into_inner().map_err(write_error)?.finish().map_err(write_error)?.flush().map_err(write_error)
+        }
+    }
+}
+
+fn extract_archive(format: ArchiveFormat, path: &Path, target: &Path) -> Result<(), BenchmarkError> {
+    let file = File::open(path).map_err(BenchmarkError::io("无法打开压缩包"))?;
+    match format {
+        ArchiveFormat::Zip => zip::ZipArchive::new(file)
+            .and_then(|mut archive| archive.extract(target))
+            .map_err(|e| BenchmarkError::StorageTestError(format!("zip解压失败: {}", e))),
+        ArchiveFormat::TarZstd => {
+            let decoder = zstd::Decoder::new(file).map_err(BenchmarkError::io("tar.zst解压失败"))?;
+            tar::Archive::new(decoder).unpack(target).map_err(BenchmarkError::io("tar.zst解压失败"))
+        }
+    }
+}
+
+// 解压出的文件数和总字节数
+fn count_files(dir: &Path) -> std::io::Result<(usize, u64)> {
+    let mut files = 0;
+    let mut bytes = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            let (nested_files, nested_bytes) = count_files(&entry.path())?;
+            files += nested_files;
+            bytes += nested_bytes;
+        } else if file_type.is_file() {
+            files += 1;
+            bytes += entry.metadata()?.len();
+        }
+    }
+    Ok((files, bytes))
+}
+
+// 目标磁盘的可用空间和inode不足所需的两倍时拒绝运行，无法确定时不检查
+fn check_headroom(config: &ArchiveTestConfig, dir: &Path) -> Result<(), BenchmarkError> {
+    let required_bytes = config.disk_bytes() * HEADROOM;
+    let required_inodes = (config.file_count() + config.small_files.div_ceil(FILES_PER_DIR) + 4) as u64 * HEADROOM;
+    let absolute = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    if let Some(available) = available_disk_bytes(&absolute) {
+        if available < required_bytes {
+            return Err(BenchmarkError::StorageTestError(format!(
+                "{}的可用空间不足: 压缩包测试需要{}MB，只剩{}MB",
+                dir.display(),
+                required_bytes / (1024 * 1024),
+                available / (1024 * 1024)
+            )));
+        }
+    }
+    if let Some(available) = available_inodes(&absolute) {
+        if available < required_inodes {
+            return Err(BenchmarkError::StorageTestError(format!(
+                "{}的可用inode不足: 压缩包测试需要{}个，只剩{}个",
+                dir.display(),
+                required_inodes,
+                available
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// 在root目录中依次生成各格式的压缩包并计时解压，进度落在[base, base + span]中。root在返回前删除
+pub fn run_archive_test<F>(
+    config: &ArchiveTestConfig,
+    root: &Path,
+    progress_callback: &F,
+    cancellation: &CancellationToken,
+    base: f64,
+    span: f64,
+) -> Result<ArchiveMetrics, BenchmarkError>
+where
+    F: Fn(f64, Message),
+{
+    let parent = root.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    check_headroom(config, parent)?;
+    let workspace = ArchiveWorkspace::create(root)?;
+    let expected_files = config.file_count();
+    let format_span = span / config.formats.len().max(1) as f64;
+
+    let mut total_bytes = 0;
+    let mut extractions = Vec::with_capacity(config.formats.len());
+    for (index, &format) in config.formats.iter().enumerate() {
+        let progress = base + format_span * index as f64;
+        cancellation.check(TestType::Storage, progress)?;
+        progress_callback(progress, Message::new("storage.archive.create").param("format", format.as_str()));
+        let archive_path = workspace.root.join(format.file_name());
+        create_archive(format, config, &archive_path, cancellation, progress)?;
+        let archive_bytes = std::fs::metadata(&archive_path).map_err(BenchmarkError::io("无法读取压缩包大小"))?.len();
+
+        let progress = progress + format_span / 2.0;
+        cancellation.check(TestType::Storage, progress)?;
+        progress_callback(progress, Message::new("storage.archive.extract").param("format", format.as_str()));
+        let target = workspace.extract_dir();
+        std::fs::create_dir_all(&target).map_err(BenchmarkError::io("无法创建解压目录"))?;
+        let started = Instant::now();
+        extract_archive(format, &archive_path, &target)?;
+        let seconds = started.elapsed().as_secs_f64().max(f64::EPSILON);
+
+        let (files, bytes) = count_files(&target).map_err(BenchmarkError::io("无法统计解压出的文件"))?;
+        if files != expected_files {
+            return Err(BenchmarkError::StorageTestError(format!(
+                "{}解压出{}个文件，应为{}个",
+                format.as_str(),
+                files,
+                expected_files
+            )));
+        }
+        total_bytes = bytes;
+        extractions.push(ArchiveExtraction {
+            format,
+            archive_bytes,
+            seconds,
+            throughput: bytes as f64 / (1024.0 * 1024.0) / seconds,
+            files_per_second: files as f64 / seconds,
+        });
+
+        // 下一种格式开始前腾出空间
+        std::fs::remove_dir_all(&target).map_err(BenchmarkError::io("无法删除解压出的文件"))?;
+        std::fs::remove_file(&archive_path).map_err(BenchmarkError::io("无法删除压缩包"))?;
+    }
+
+    Ok(ArchiveMetrics {
+        file_count: expected_files,
+        total_bytes,
+        extractions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scaled_down() -> ArchiveTestConfig {
+        ArchiveTestConfig {
+            small_files: 200,
+            large_files: 1,
+            large_file_size: 1,
+            ..ArchiveTestConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_extracts_every_file_and_cleans_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("archive");
+        let config = scaled_down();
+        let metrics = run_archive_test(&config, &root, &|_, _| {}, &CancellationToken::never(), 0.0, 100.0).unwrap();
+
+        assert_eq!(metrics.file_count, 201);
+        let small: u64 = config.small_file_sizes().iter().map(|&size| size as u64).sum();
+        assert_eq!(metrics.total_bytes, small + 1024 * 1024);
+        let formats: Vec<ArchiveFormat> = metrics.extractions.iter().map(|e| e.format).collect();
+        assert_eq!(formats, ArchiveFormat::ALL);
+        for extraction in &metrics.extractions {
+            assert!(extraction.throughput > 0.0 && extraction.files_per_second > 0.0, "{:?}", extraction);
+            // 语料可压缩，压缩包小于原始数据
+            assert!(extraction.archive_bytes > 0 && extraction.archive_bytes < metrics.total_bytes, "{:?}", extraction);
+        }
+        assert!(!root.exists(), "压缩包和解压出的文件应已删除");
+    }
+
+    #[test]
+    fn test_cancellation_cleans_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("archive");
+        let error = run_archive_test(&scaled_down(), &root, &|_, _| {}, &CancellationToken::new(|| true), 0.0, 100.0).unwrap_err();
+        assert_eq!(error.code(), "CANCELLED");
+        assert!(!root.exists());
+    }
+}
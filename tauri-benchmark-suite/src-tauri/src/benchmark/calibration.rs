@@ -0,0 +1,281 @@
+//! 自动时长模式：先用很小的负载校准各项测试的耗时，再反推测试参数，
+//! 使整个套件的运行时间接近用户给定的目标
+use crate::benchmark::core::BenchmarkConfig;
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::estimate::{cpu_test_seconds, memory_test_seconds, storage_test_seconds, CPU_PHASES};
+use crate::benchmark::memory::{MemoryBenchmark, MemoryTestConfig};
+use crate::benchmark::storage::{StorageBenchmark, StorageTestConfig};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// 估计总时长与目标的允许偏差
+pub const AUTO_DURATION_TOLERANCE: f64 = 0.2;
+
+// 各项测试分配到的时间比例（只在启用的测试之间归一化）
+const CPU_WEIGHT: f64 = 0.5;
+const MEMORY_WEIGHT: f64 = 0.2;
+const STORAGE_WEIGHT: f64 = 0.3;
+
+/// 线性耗时模型：seconds = fixed_secs + secs_per_unit * units
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CostModel {
+    pub fixed_secs: f64,
+    pub secs_per_unit: f64,
+}
+
+impl CostModel {
+    /// 由两次不同负载的测量拟合，负载大的样本应放在第二个
+    pub fn from_samples(small: (f64, f64), large: (f64, f64)) -> Self {
+        let (small_units, small_secs) = small;
+        let (large_units, large_secs) = large;
+        let secs_per_unit = if large_units > small_units {
+            ((large_secs - small_secs) / (large_units - small_units)).max(0.0)
+        } else {
+            0.0
+        };
+        // 计时抖动可能让斜率为0，此时退化为按单位平均
+        let secs_per_unit = if secs_per_unit > 0.0 {
+            secs_per_unit
+        } else {
+            large_secs / large_units.max(1.0)
+        };
+        Self {
+            fixed_secs: (small_secs - secs_per_unit * small_units).max(0.0),
+            secs_per_unit,
+        }
+    }
+
+    pub fn estimate(&self, units: f64) -> f64 {
+        self.fixed_secs + self.secs_per_unit * units
+    }
+
+    /// 在`seconds`内能完成的负载单位数
+    pub fn solve(&self, seconds: f64) -> f64 {
+        if self.secs_per_unit <= 0.0 {
+            return 0.0;
+        }
+        ((seconds - self.fixed_secs) / self.secs_per_unit).max(0.0)
+    }
+}
+
+/// 校准结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Calibration {
+    pub memory: CostModel,  // 单位：缓冲区MB × 迭代次数
+    pub storage: CostModel, // 单位：测试文件MB
+    pub elapsed_secs: f64,  // 校准本身的耗时，计入目标时长
+    pub measured: bool,     // false表示跳过了校准，使用内置的保守估计
+}
+
+impl Calibration {
+    /// 跳过校准时使用的内置估计
+    pub fn default_estimates() -> Self {
+        Self {
+            memory: CostModel {
+                fixed_secs: 0.5,
+                secs_per_unit: 0.004,
+            },
+            storage: CostModel {
+                fixed_secs: 1.0,
+                secs_per_unit: 0.02,
+            },
+            elapsed_secs: 0.0,
+            measured: false,
+        }
+    }
+
+    /// 对启用的内存和存储测试各做两次小负载运行（合计约2秒）
+    pub fn measure(config: &BenchmarkConfig) -> Result<Self, BenchmarkError> {
+        let start = Instant::now();
+        let mut calibration = Self::default_estimates();
+        calibration.measured = true;
+
+        if config.memory_test.enabled {
+            let run = |iterations: usize| -> Result<(f64, f64), BenchmarkError> {
+                let buffer_size = 4;
+                let started = Instant::now();
+                MemoryBenchmark::new(MemoryTestConfig {
+                    buffer_size,
+                    iterations,
+                    test_duration: 1,
+                    enable_usage_monitoring: false,
+                    interference_duration: 0,
+                })
+                .run_benchmark()?;
+                Ok(((buffer_size * iterations) as f64, started.elapsed().as_secs_f64()))
+            };
+            calibration.memory = CostModel::from_samples(run(1)?, run(4)?);
+        }
+
+        if config.storage_test.enabled {
+            let test_file_path = std::env::temp_dir()
+                .join("tauri_benchmark_calibration.dat")
+                .to_string_lossy()
+                .into_owned();
+            let run = |file_size: u64| -> Result<(f64, f64), BenchmarkError> {
+                let started = Instant::now();
+                StorageBenchmark::new(StorageTestConfig {
+                    file_size,
+                    block_size: config.storage_test.block_size.max(1),
+                    test_duration: 1,
+                    test_file_path: Some(test_file_path.clone()),
+                    small_file_count: 0,
+                    archive: None,
+                })
+                .run_benchmark()?;
+                Ok((file_size as f64, started.elapsed().as_secs_f64()))
+            };
+            calibration.storage = CostModel::from_samples(run(4)?, run(16)?);
+        }
+
+        calibration.elapsed_secs = start.elapsed().as_secs_f64();
+        Ok(calibration)
+    }
+}
+
+/// 自动时长模式推导出的测试参数，记录在测试结果中
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AutoDurationPlan {
+    pub target_seconds: u64,
+    pub calibration: Calibration,
+    pub cpu_duration: Option<u64>,         // seconds
+    pub memory_iterations: Option<usize>,
+    pub storage_file_size: Option<u64>,    // MB
+    pub estimated_seconds: f64,            // 含校准耗时
+    pub within_tolerance: bool,            // 估计值是否在目标±20%以内
+}
+
+impl AutoDurationPlan {
+    /// 把推导出的参数写入配置，其余字段（缓冲区、块大小等）保持用户设置
+    pub fn apply(&self, config: &mut BenchmarkConfig) {
+        if let Some(duration) = self.cpu_duration {
+            config.cpu_test.test_duration = duration;
+        }
+        if let Some(iterations) = self.memory_iterations {
+            config.memory_test.iterations = iterations;
+        }
+        if let Some(file_size) = self.storage_file_size {
+            config.storage_test.file_size = file_size;
+        }
+    }
+}
+
+/// 根据校准结果求解各项测试参数，使估计总时长接近目标。重复运行时目标时长由各次运行平分
+pub fn plan_auto_duration(config: &BenchmarkConfig, target_seconds: u64, calibration: Calibration) -> AutoDurationPlan {
+    let repeat = config.repeat.max(1) as f64;
+    let budget = (target_seconds as f64 - calibration.elapsed_secs).max(0.0) / repeat;
+    let total_weight: f64 = [
+        (config.cpu_test.enabled, CPU_WEIGHT),
+        (config.memory_test.enabled, MEMORY_WEIGHT),
+        (config.storage_test.enabled, STORAGE_WEIGHT),
+    ]
+    .iter()
+    .filter(|(enabled, _)| *enabled)
+    .map(|(_, weight)| weight)
+    .sum();
+    let share = |weight: f64| if total_weight > 0.0 { budget * weight / total_weight } else { 0.0 };
+
+    let buffer_size = config.memory_test.buffer_size.max(1);
+    let memory_iterations = config.memory_test.enabled.then(|| {
+        let units = calibration.memory.solve(share(MEMORY_WEIGHT));
+        ((units / buffer_size as f64).round() as usize).max(1)
+    });
+    let storage_file_size = config
+        .storage_test
+        .enabled
+        .then(|| (calibration.storage.solve(share(STORAGE_WEIGHT)).round() as u64).max(1));
+
+    let memory_secs = memory_iterations
+        .map(|iterations| memory_test_seconds(buffer_size, iterations, &calibration))
+        .unwrap_or(0.0);
+    let storage_secs = storage_file_size
+        .map(|file_size| storage_test_seconds(file_size, &calibration))
+        .unwrap_or(0.0);
+
+    // 内存和存储的参数取整或触到下限后产生的偏差由CPU时长吸收
+    let cpu_duration = config.cpu_test.enabled.then(|| {
+        let remaining = budget - memory_secs - storage_secs;
+        ((remaining / CPU_PHASES).round() as u64).max(1)
+    });
+    let cpu_secs = cpu_duration.map(cpu_test_seconds).unwrap_or(0.0);
+
+    let estimated_seconds = calibration.elapsed_secs + (cpu_secs + memory_secs + storage_secs) * repeat;
+    let target = target_seconds as f64;
+    AutoDurationPlan {
+        target_seconds,
+        calibration,
+        cpu_duration,
+        memory_iterations,
+        storage_file_size,
+        estimated_seconds,
+        within_tolerance: (estimated_seconds - target).abs() <= target * AUTO_DURATION_TOLERANCE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(cpu: bool, memory: bool, storage: bool) -> BenchmarkConfig {
+        let mut config = BenchmarkConfig::default();
+        config.cpu_test.enabled = cpu;
+        config.memory_test.enabled = memory;
+        config.storage_test.enabled = storage;
+        config
+    }
+
+    fn synthetic_calibration() -> Calibration {
+        Calibration {
+            memory: CostModel { fixed_secs: 0.2, secs_per_unit: 0.001 }, // 约1GB/s
+            storage: CostModel { fixed_secs: 0.5, secs_per_unit: 0.01 }, // 约100MB/s
+            elapsed_secs: 2.0,
+            measured: true,
+        }
+    }
+
+    #[test]
+    fn test_cost_model_fit() {
+        let model = CostModel::from_samples((4.0, 0.3), (16.0, 0.9));
+        assert!((model.secs_per_unit - 0.05).abs() < 1e-9);
+        assert!((model.fixed_secs - 0.1).abs() < 1e-9);
+        assert!((model.solve(model.estimate(100.0)) - 100.0).abs() < 1e-9);
+
+        // 较大负载反而更快（计时抖动）时不产生负斜率
+        let jittery = CostModel::from_samples((4.0, 0.5), (16.0, 0.4));
+        assert!(jittery.secs_per_unit > 0.0);
+    }
+
+    #[test]
+    fn test_solver_hits_sixty_second_target() {
+        let config = config(true, true, true);
+        let plan = plan_auto_duration(&config, 60, synthetic_calibration());
+
+        assert!(plan.within_tolerance, "估计时长 {} 超出范围", plan.estimated_seconds);
+        assert!((plan.estimated_seconds - 60.0).abs() <= 60.0 * AUTO_DURATION_TOLERANCE);
+
+        // 58秒预算中存储分到30%：(17.4 - 0.5) / 0.01 ≈ 1690MB
+        assert_eq!(plan.storage_file_size, Some(1690));
+        // 内存分到20%：(11.6 - 0.2) / 0.001 / 1024MB ≈ 11次
+        assert_eq!(plan.memory_iterations, Some(11));
+        assert!(plan.cpu_duration.unwrap() >= 7);
+
+        let mut applied = config.clone();
+        plan.apply(&mut applied);
+        assert_eq!(applied.storage_test.file_size, 1690);
+        assert_eq!(applied.storage_test.block_size, config.storage_test.block_size);
+    }
+
+    #[test]
+    fn test_solver_only_plans_enabled_tests() {
+        let plan = plan_auto_duration(&config(false, true, false), 30, synthetic_calibration());
+        assert_eq!(plan.cpu_duration, None);
+        assert_eq!(plan.storage_file_size, None);
+        assert!(plan.within_tolerance);
+
+        // 目标比校准本身还短时，参数取最小值并标记为超出范围
+        let plan = plan_auto_duration(&config(true, false, false), 1, synthetic_calibration());
+        assert_eq!(plan.cpu_duration, Some(1));
+        assert!(!plan.within_tolerance);
+    }
+}
@@ -0,0 +1,454 @@
+//! 需要更高权限的功能检测：直接I/O、RAPL能耗读取、SMART查询和大页内存。
+//! 每项功能探测为可用、权限不足或本平台不支持，权限不足时附带授权方法；
+//! 套件预检据此降级并发出警告，而不是运行到一半才报出难以理解的错误。
+//! Windows上可以通过UAC提示以管理员身份重新运行探测，其他平台说明需要的用户组或capability
+use crate::benchmark::error::BenchmarkError;
+use crate::ipc::TestType;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 提权后由子进程运行的探测参数，见`run_elevated_helper`
+pub const ELEVATED_PROBE_ARG: &str = "--elevated-probe";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    DirectIo,   // 绕过系统缓存读写测试文件
+    RaplPower,  // 读取CPU能耗计数器
+    Smart,      // 查询磁盘SMART信息
+    LargePages, // 以大页分配内存测试缓冲区
+}
+
+impl Feature {
+    pub const ALL: [Feature; 4] = [Feature::DirectIo, Feature::RaplPower, Feature::Smart, Feature::LargePages];
+    pub const NAMES: &'static str = "direct_io、rapl_power、smart、large_pages";
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Feature::DirectIo => "direct_io",
+            Feature::RaplPower => "rapl_power",
+            Feature::Smart => "smart",
+            Feature::LargePages => "large_pages",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|feature| feature.name() == value)
+    }
+
+    /// 使用该功能的测试，预检时只检查已启用的测试
+    pub fn test_type(&self) -> TestType {
+        match self {
+            Feature::DirectIo | Feature::Smart => TestType::Storage,
+            Feature::RaplPower => TestType::Cpu,
+            Feature::LargePages => TestType::Memory,
+        }
+    }
+
+    /// 本平台上的授权方法，不需要或无法授权时为空
+    pub fn remediation(&self, status: CapabilityStatus) -> Option<String> {
+        let hint = match (status, self) {
+            (CapabilityStatus::Available, _) => return None,
+            (CapabilityStatus::Unsupported, Feature::DirectIo) => {
+                "测试文件所在的文件系统不支持直接I/O（如tmpfs），请把测试文件路径改到磁盘分区上"
+            }
+            (CapabilityStatus::Unsupported, _) => return None,
+            (CapabilityStatus::PermissionDenied, feature) if cfg!(windows) => match feature {
+                Feature::LargePages => {
+                    "在本地安全策略中为当前用户授予“锁定内存页”权限（SeLockMemoryPrivilege），重新登录后以管理员身份运行"
+                }
+                _ => "以管理员身份运行，或在功能检测中请求提权",
+            },
+            (CapabilityStatus::PermissionDenied, Feature::DirectIo) => "确认当前用户对测试文件所在目录有读写权限",
+            (CapabilityStatus::PermissionDenied, Feature::RaplPower) => {
+                "RAPL能耗计数器默认只有root可读：以root运行，或通过udev规则把/sys/class/powercap/intel-rapl:*/energy_uj的读权限授予当前用户所在的组"
+            }
+            (CapabilityStatus::PermissionDenied, Feature::Smart) => {
+                "将当前用户加入disk组（sudo usermod -aG disk $USER，重新登录后生效），或为程序授予CAP_SYS_RAWIO（sudo setcap cap_sys_rawio+ep <程序路径>）"
+            }
+            (CapabilityStatus::PermissionDenied, Feature::LargePages) => {
+                "预留大页（sudo sysctl vm.nr_hugepages=512），或启用透明大页（echo madvise | sudo tee /sys/kernel/mm/transparent_hugepage/enabled）"
+            }
+        };
+        Some(hint.to_string())
+    }
+
+    /// 该功能权限不足的错误，附带本平台的授权方法
+    pub fn permission_error(&self, message: impl Into<String>) -> BenchmarkError {
+        BenchmarkError::PermissionError {
+            feature: self.name().to_string(),
+            message: message.into(),
+            remediation: self.remediation(CapabilityStatus::PermissionDenied),
+        }
+    }
+
+    /// 把使用该功能时遇到的权限错误转换为带功能名和授权方法的PermissionError，其它错误原样返回
+    pub fn enrich(&self, error: BenchmarkError) -> BenchmarkError {
+        match error {
+            BenchmarkError::Io {
+                context,
+                kind: io::ErrorKind::PermissionDenied,
+                message,
+            } => self.permission_error(format!("{}: {}", context, message)),
+            BenchmarkError::PermissionError { message, remediation: None, .. } => self.permission_error(message),
+            other => other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CapabilityStatus {
+    Available,
+    PermissionDenied, // 本平台支持，但当前用户没有权限
+    Unsupported,      // 本平台、硬件或文件系统不支持
+}
+
+/// 单项探测的结果，detail说明不可用的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProbeOutcome {
+    Available,
+    PermissionDenied(String),
+    Unsupported(String),
+}
+
+impl ProbeOutcome {
+    /// 文件访问失败的原因：权限不足之外都视为不支持（如文件不存在、文件系统拒绝O_DIRECT）
+    fn from_io(error: &io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::PermissionDenied => ProbeOutcome::PermissionDenied(error.to_string()),
+            _ => ProbeOutcome::Unsupported(error.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Capability {
+    pub feature: Feature,
+    pub status: CapabilityStatus,
+    pub detail: Option<String>,      // 不可用的原因
+    pub remediation: Option<String>, // 本平台上的授权方法
+}
+
+impl Capability {
+    pub fn new(feature: Feature, outcome: ProbeOutcome) -> Self {
+        let (status, detail) = match outcome {
+            ProbeOutcome::Available => (CapabilityStatus::Available, None),
+            ProbeOutcome::PermissionDenied(detail) => (CapabilityStatus::PermissionDenied, Some(detail)),
+            ProbeOutcome::Unsupported(detail) => (CapabilityStatus::Unsupported, Some(detail)),
+        };
+        Self {
+            feature,
+            status,
+            detail,
+            remediation: feature.remediation(status),
+        }
+    }
+
+    /// 探测本机的一项功能
+    pub fn detect(feature: Feature, test_dir: &Path) -> Self {
+        Self::new(feature, probe(feature, test_dir))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityMap {
+    pub capabilities: Vec<Capability>,
+}
+
+impl CapabilityMap {
+    /// 按Feature::ALL的顺序逐项探测，测试中可以注入探测函数
+    pub fn assemble(probe: impl Fn(Feature) -> ProbeOutcome) -> Self {
+        Self {
+            capabilities: Feature::ALL.into_iter().map(|feature| Capability::new(feature, probe(feature))).collect(),
+        }
+    }
+
+    /// 探测本机，直接I/O在test_dir（存储测试文件所在目录）中检测
+    pub fn detect(test_dir: &Path) -> Self {
+        Self::assemble(|feature| probe(feature, test_dir))
+    }
+
+    pub fn get(&self, feature: Feature) -> Option<&Capability> {
+        self.capabilities.iter().find(|capability| capability.feature == feature)
+    }
+
+    /// 未探测的功能为None
+    pub fn status(&self, feature: Feature) -> Option<CapabilityStatus> {
+        self.get(feature).map(|capability| capability.status)
+    }
+}
+
+/// 请求提权的结果。prompted表示是否弹出了UAC提示，capability为以管理员身份探测的结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ElevationOutcome {
+    pub feature: Feature,
+    pub prompted: bool,
+    pub capability: Capability,
+}
+
+/// 请求使用需要更高权限的功能。已可用或本平台不支持时直接返回当前探测结果；
+/// 权限不足时，Windows上通过UAC提示以管理员身份重新运行探测，用户拒绝时返回PermissionError；
+/// 其他平台无法在运行中提权，返回附带所需用户组或capability说明的PermissionError
+pub fn request_elevation(feature: Feature, test_dir: &Path) -> Result<ElevationOutcome, BenchmarkError> {
+    let capability = Capability::detect(feature, test_dir);
+    if capability.status != CapabilityStatus::PermissionDenied {
+        return Ok(ElevationOutcome { feature, prompted: false, capability });
+    }
+    if cfg!(windows) {
+        let capability = run_elevated_probe(feature)?;
+        return Ok(ElevationOutcome { feature, prompted: true, capability });
+    }
+    Err(feature.permission_error(capability.detail.unwrap_or_else(|| "需要更高权限".to_string())))
+}
+
+/// 通过PowerShell的Start-Process -Verb RunAs弹出UAC提示，以管理员身份运行本程序的探测模式，
+/// 子进程把探测结果写入临时文件
+fn run_elevated_probe(feature: Feature) -> Result<Capability, BenchmarkError> {
+    let exe = std::env::current_exe().map_err(BenchmarkError::io("无法定位程序路径"))?;
+    let output = std::env::temp_dir().join(format!("capability-{}.json", uuid::Uuid::new_v4()));
+    // PowerShell单引号字符串中的单引号写作两个；路径另加双引号，含空格时作为一个参数传给子进程
+    let quote = |value: &str| format!("'{}'", value.replace('\'', "''"));
+    let script = format!(
+        "$p = Start-Process -FilePath {} -ArgumentList {},{},'--output',{} -Verb RunAs -Wait -PassThru; exit $p.ExitCode",
+        quote(&exe.to_string_lossy()),
+        quote(ELEVATED_PROBE_ARG),
+        quote(feature.name()),
+        quote(&format!("\"{}\"", output.display())),
+    );
+    let status = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .status()
+        .map_err(BenchmarkError::io("无法启动PowerShell"))?;
+    let content = fs::read_to_string(&output);
+    let _ = fs::remove_file(&output);
+    // 用户在UAC提示中选择“否”时Start-Process失败，子进程没有运行，也就没有结果文件
+    let content = content.map_err(|_| feature.permission_error(format!("用户取消了提权（PowerShell退出码 {:?}）", status.code())))?;
+    serde_json::from_str(&content).map_err(|e| BenchmarkError::DataLoadError(format!("提权探测结果无效: {}", e)))
+}
+
+/// 提权子进程的入口：探测一项功能，把结果写入output（未指定时写到标准输出）。
+/// 可用时退出码为0，否则为1
+pub fn run_elevated_helper(feature: Feature, output: Option<&Path>) -> i32 {
+    let capability = Capability::detect(feature, &default_test_dir());
+    let Ok(json) = serde_json::to_string(&capability) else {
+        return 1;
+    };
+    let written = match output {
+        Some(path) => fs::write(path, json).is_ok(),
+        None => {
+            println!("{}", json);
+            true
+        }
+    };
+    i32::from(!(written && capability.status == CapabilityStatus::Available))
+}
+
+/// 存储测试文件默认所在的目录，未指定测试目录时在这里检测直接I/O
+pub fn default_test_dir() -> PathBuf {
+    let path = crate::benchmark::storage::default_test_file_path();
+    path.parent().map(Path::to_path_buf).unwrap_or(path)
+}
+
+fn probe(feature: Feature, test_dir: &Path) -> ProbeOutcome {
+    match feature {
+        Feature::DirectIo => probe_direct_io(test_dir),
+        Feature::RaplPower => probe_rapl(),
+        Feature::Smart => probe_smart(),
+        Feature::LargePages => probe_large_pages(),
+    }
+}
+
+// Linux上O_DIRECT是否可用取决于文件系统，在测试目录中实际打开一个文件检测
+#[cfg(target_os = "linux")]
+fn probe_direct_io(test_dir: &Path) -> ProbeOutcome {
+    use std::os::unix::fs::OpenOptionsExt;
+    let path = test_dir.join(format!(".direct-io-probe-{}", std::process::id()));
+    let opened = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .custom_flags(libc::O_DIRECT)
+        .open(&path);
+    let _ = fs::remove_file(&path);
+    match opened {
+        Ok(_) => ProbeOutcome::Available,
+        Err(e) => ProbeOutcome::from_io(&e),
+    }
+}
+
+// Windows的FILE_FLAG_NO_BUFFERING和macOS的F_NOCACHE对普通文件不需要额外权限
+#[cfg(not(target_os = "linux"))]
+fn probe_direct_io(_test_dir: &Path) -> ProbeOutcome {
+    if cfg!(any(windows, target_os = "macos")) {
+        ProbeOutcome::Available
+    } else {
+        ProbeOutcome::Unsupported("本平台不支持绕过系统缓存".to_string())
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn probe_rapl() -> ProbeOutcome {
+    match fs::read_to_string("/sys/class/powercap/intel-rapl:0/energy_uj") {
+        Ok(_) => ProbeOutcome::Available,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            ProbeOutcome::Unsupported("没有找到RAPL能耗计数器（需要Intel或AMD处理器及intel_rapl驱动）".to_string())
+        }
+        Err(e) => ProbeOutcome::from_io(&e),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn probe_rapl() -> ProbeOutcome {
+    ProbeOutcome::Unsupported("本平台读取能耗计数器需要内核驱动".to_string())
+}
+
+// 打开第一块物理磁盘的设备文件，SMART查询需要对其发送ioctl
+#[cfg(target_os = "linux")]
+fn probe_smart() -> ProbeOutcome {
+    let disk = fs::read_dir("/sys/block").ok().and_then(|entries| {
+        entries
+            .flatten()
+            .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+            .find(|name| !["loop", "ram", "zram", "dm-", "md", "sr"].iter().any(|prefix| name.starts_with(prefix)))
+    });
+    let Some(disk) = disk else {
+        return ProbeOutcome::Unsupported("没有找到物理磁盘".to_string());
+    };
+    match fs::File::open(Path::new("/dev").join(&disk)) {
+        Ok(_) => ProbeOutcome::Available,
+        Err(e) => ProbeOutcome::from_io(&e),
+    }
+}
+
+#[cfg(windows)]
+fn probe_smart() -> ProbeOutcome {
+    if is_elevated() {
+        ProbeOutcome::Available
+    } else {
+        ProbeOutcome::PermissionDenied("查询SMART需要管理员权限".to_string())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn probe_smart() -> ProbeOutcome {
+    ProbeOutcome::Unsupported("本平台不支持直接查询SMART".to_string())
+}
+
+// 已预留大页，或透明大页处于always/madvise模式时可用
+#[cfg(target_os = "linux")]
+fn probe_large_pages() -> ProbeOutcome {
+    let reserved = match fs::read_to_string("/proc/sys/vm/nr_hugepages") {
+        Ok(content) => content.trim().parse::<u64>().unwrap_or(0),
+        Err(e) => return ProbeOutcome::from_io(&e),
+    };
+    let transparent = fs::read_to_string("/sys/kernel/mm/transparent_hugepage/enabled")
+        .is_ok_and(|mode| mode.contains("[always]") || mode.contains("[madvise]"));
+    if reserved > 0 || transparent {
+        ProbeOutcome::Available
+    } else {
+        ProbeOutcome::PermissionDenied("没有预留大页，透明大页也未启用".to_string())
+    }
+}
+
+// 大页分配需要SeLockMemoryPrivilege，whoami /priv列出当前令牌拥有的权限
+#[cfg(windows)]
+fn probe_large_pages() -> ProbeOutcome {
+    let output = std::process::Command::new("whoami").arg("/priv").output();
+    match output {
+        Ok(output) if String::from_utf8_lossy(&output.stdout).contains("SeLockMemoryPrivilege") => ProbeOutcome::Available,
+        Ok(_) => ProbeOutcome::PermissionDenied("当前用户没有SeLockMemoryPrivilege权限".to_string()),
+        Err(e) => ProbeOutcome::Unsupported(e.to_string()),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn probe_large_pages() -> ProbeOutcome {
+    ProbeOutcome::Unsupported("本平台不支持为测试缓冲区指定大页".to_string())
+}
+
+// net session只有管理员才能成功执行
+#[cfg(windows)]
+fn is_elevated() -> bool {
+    std::process::Command::new("net")
+        .arg("session")
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assemble_from_injected_probes() {
+        let map = CapabilityMap::assemble(|feature| match feature {
+            Feature::DirectIo => ProbeOutcome::Available,
+            Feature::RaplPower => ProbeOutcome::PermissionDenied("energy_uj: Permission denied".to_string()),
+            Feature::Smart => ProbeOutcome::Unsupported("没有找到物理磁盘".to_string()),
+            Feature::LargePages => ProbeOutcome::Unsupported("tmpfs".to_string()),
+        });
+        let features: Vec<Feature> = map.capabilities.iter().map(|capability| capability.feature).collect();
+        assert_eq!(features, Feature::ALL);
+
+        let direct_io = map.get(Feature::DirectIo).unwrap();
+        assert_eq!((direct_io.status, &direct_io.detail, &direct_io.remediation), (CapabilityStatus::Available, &None, &None));
+        let rapl = map.get(Feature::RaplPower).unwrap();
+        assert_eq!(rapl.status, CapabilityStatus::PermissionDenied);
+        assert_eq!(rapl.detail.as_deref(), Some("energy_uj: Permission denied"));
+        assert!(rapl.remediation.is_some());
+        // 不支持的功能只在可以换个位置运行时给出提示
+        assert_eq!(map.status(Feature::Smart), Some(CapabilityStatus::Unsupported));
+        assert_eq!(map.get(Feature::Smart).unwrap().remediation, None);
+        assert!(Feature::DirectIo.remediation(CapabilityStatus::Unsupported).unwrap().contains("tmpfs"));
+        assert_eq!(CapabilityMap::default().status(Feature::Smart), None);
+
+        let value = serde_json::to_value(&map).unwrap();
+        assert_eq!(value["capabilities"][1]["feature"], "rapl_power");
+        assert_eq!(value["capabilities"][1]["status"], "permission_denied");
+        assert_eq!(Feature::parse("large_pages"), Some(Feature::LargePages));
+        assert_eq!(Feature::parse("huge_pages"), None);
+        // 文件系统拒绝O_DIRECT时返回EINVAL，属于不支持而不是权限不足
+        assert!(matches!(ProbeOutcome::from_io(&io::Error::from(io::ErrorKind::InvalidInput)), ProbeOutcome::Unsupported(_)));
+
+        // 本机探测不会失败，每项功能都有结果
+        let detected = CapabilityMap::detect(&std::env::temp_dir());
+        assert_eq!(detected.capabilities.len(), Feature::ALL.len());
+    }
+
+    #[test]
+    fn test_permission_errors_carry_feature_and_remediation() {
+        let io_error = BenchmarkError::io("读取能耗计数器")(io::Error::from(io::ErrorKind::PermissionDenied));
+        let BenchmarkError::PermissionError { feature, message, remediation } = Feature::RaplPower.enrich(io_error) else {
+            panic!("权限不足的IO错误应转换为PermissionError");
+        };
+        assert_eq!(feature, "rapl_power");
+        assert!(message.starts_with("读取能耗计数器: "), "{}", message);
+        assert_eq!(remediation, Feature::RaplPower.remediation(CapabilityStatus::PermissionDenied));
+        assert!(remediation.is_some());
+
+        // 其它错误原样返回，已有授权方法的PermissionError不被覆盖
+        let not_found = BenchmarkError::io("读取能耗计数器")(io::Error::from(io::ErrorKind::NotFound));
+        assert_eq!(Feature::RaplPower.enrich(not_found).io_kind(), Some(io::ErrorKind::NotFound));
+        let custom = BenchmarkError::PermissionError {
+            feature: "smart".to_string(),
+            message: "e".to_string(),
+            remediation: Some("自定义".to_string()),
+        };
+        assert!(matches!(Feature::Smart.enrich(custom), BenchmarkError::PermissionError { remediation: Some(ref r), .. } if r == "自定义"));
+
+        // 转换为IPC错误时details依次包含功能名、原因和授权方法
+        let error = crate::ipc::IpcError::from(Feature::Smart.permission_error("无法打开/dev/sda"));
+        assert_eq!(error.code, "PERMISSION_ERROR");
+        let remediation = Feature::Smart.remediation(CapabilityStatus::PermissionDenied).unwrap();
+        assert_eq!(error.details, Some(format!("smart: 无法打开/dev/sda（{}）", remediation)));
+    }
+}
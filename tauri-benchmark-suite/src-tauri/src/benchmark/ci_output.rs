@@ -0,0 +1,265 @@
+//! 无人值守运行的机器可读输出：完整结果JSON、JUnit XML和libtest bencher格式的文本行。
+//! JUnit中每项指标与阈值的对比是一个测试用例，判定来自套件结束时与本机上一次结果的回归对比，
+//! 与历史对比命令使用同一套阈值逻辑
+use crate::benchmark::comparison::{metric_values, MetricComparison, MetricStatus};
+use crate::benchmark::core::TestResult;
+use crate::benchmark::error::BenchmarkError;
+use crate::format::{format_number, NumberLocale};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// --output-format的取值
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Json, // 完整结果
+    Junit,   // 每项指标的阈值检查是一个测试用例，CI界面按通过/失败显示
+    Bencher, // `test cpu::single_thread_score ... bench: 123 ns/iter (+/- 0)`
+}
+
+impl OutputFormat {
+    pub const NAMES: &'static str = "json、junit、bencher";
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "json" => Some(OutputFormat::Json),
+            "junit" => Some(OutputFormat::Junit),
+            "bencher" => Some(OutputFormat::Bencher),
+            _ => None,
+        }
+    }
+}
+
+/// 结果是否有指标超过回归阈值，没有做回归对比时为false
+pub fn has_regressions(result: &TestResult) -> bool {
+    result.regression_check.as_ref().is_some_and(|check| !check.report.passed())
+}
+
+pub fn render(result_id: &str, result: &TestResult, format: OutputFormat) -> Result<String, BenchmarkError> {
+    match format {
+        OutputFormat::Json => serde_json::to_string_pretty(result)
+            .map(|json| json + "\n")
+            .map_err(|e| BenchmarkError::DataSaveError(format!("结果序列化失败: {}", e))),
+        OutputFormat::Junit => Ok(render_junit(result_id, result)),
+        OutputFormat::Bencher => Ok(render_bencher(result)),
+    }
+}
+
+/// 写到path，未指定路径时写到标准输出
+pub fn write(result_id: &str, result: &TestResult, format: OutputFormat, path: Option<&Path>) -> Result<(), BenchmarkError> {
+    let output = render(result_id, result, format)?;
+    let Some(path) = path else {
+        print!("{}", output);
+        return Ok(());
+    };
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(BenchmarkError::io("创建结果输出目录"))?;
+    }
+    fs::write(path, output).map_err(BenchmarkError::io("写入结果输出文件"))
+}
+
+// 指标名`cpu.single_thread_score`拆分为类名cpu和用例名single_thread_score，总分归入overall
+fn split_metric(metric: &str) -> (&str, &str) {
+    metric.split_once('.').unwrap_or(("overall", metric))
+}
+
+fn render_junit(result_id: &str, result: &TestResult) -> String {
+    let check = result.regression_check.as_ref();
+    let metrics: &[MetricComparison] = check.map(|check| check.report.metrics.as_slice()).unwrap_or_default();
+    let count = |status: MetricStatus| metrics.iter().filter(|metric| metric.status == status).count();
+    let (failures, skipped) = (count(MetricStatus::Regressed), count(MetricStatus::Missing));
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"tauri-benchmark-suite\" tests=\"{}\" failures=\"{}\" skipped=\"{}\">",
+        metrics.len(),
+        failures,
+        skipped
+    );
+    let _ = writeln!(
+        xml,
+        "  <testsuite name=\"regression\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" timestamp=\"{}\">",
+        metrics.len(),
+        failures,
+        skipped,
+        escape_xml(&result.timestamp)
+    );
+    xml.push_str("    <properties>\n");
+    let mut properties = vec![
+        ("result_id", result_id.to_string()),
+        ("overall_score", format!("{:.2}", result.overall_score)),
+    ];
+    if let Some(check) = check {
+        properties.push(("baseline_id", check.baseline_id.clone()));
+    }
+    for (name, value) in properties {
+        let _ = writeln!(xml, "      <property name=\"{}\" value=\"{}\"/>", name, escape_xml(&value));
+    }
+    xml.push_str("    </properties>\n");
+
+    for metric in metrics {
+        let (class, name) = split_metric(&metric.metric);
+        let open = format!("    <testcase classname=\"{}\" name=\"{}\" time=\"0\"", escape_xml(class), escape_xml(name));
+        let detail = format!(
+            "baseline={} candidate={} threshold={}%",
+            format_optional(metric.baseline),
+            format_optional(metric.candidate),
+            metric.threshold_percent
+        );
+        match metric.status {
+            MetricStatus::Regressed => {
+                let message = format!(
+                    "变化 {:+.2}% 超过阈值 {}%",
+                    metric.change_percent.unwrap_or_default(),
+                    metric.threshold_percent
+                );
+                let _ = writeln!(xml, "{}>", open);
+                let _ = writeln!(
+                    xml,
+                    "      <failure type=\"regression\" message=\"{}\">{}</failure>",
+                    escape_xml(&message),
+                    escape_xml(&detail)
+                );
+                xml.push_str("    </testcase>\n");
+            }
+            MetricStatus::Missing => {
+                let _ = writeln!(xml, "{}>", open);
+                let _ = writeln!(xml, "      <skipped message=\"{}\"/>", escape_xml(&format!("无法比较: {}", detail)));
+                xml.push_str("    </testcase>\n");
+            }
+            MetricStatus::Improved | MetricStatus::Unchanged => {
+                let _ = writeln!(xml, "{}/>", open);
+            }
+        }
+    }
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+// 与libtest的bencher输出一致：数值取整并加千位分隔符。格式固定为ns/iter，
+// 数值是指标本身的单位（分数、MB/s、IOPS或延迟），读取方需按指标区分越高越好还是越低越好
+fn render_bencher(result: &TestResult) -> String {
+    let mut output = String::new();
+    for (metric, value) in metric_values(result) {
+        let (group, name) = split_metric(metric);
+        let name = name.replace('.', "::");
+        let _ = writeln!(output, "test {}::{} ... bench: {:>11} ns/iter (+/- 0)", group, name, with_separators(value));
+    }
+    output
+}
+
+fn with_separators(value: f64) -> String {
+    format_number(value.round().max(0.0), 0, NumberLocale::EnUs)
+}
+
+fn format_optional(value: Option<f64>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_else(|| "-".to_string())
+}
+
+pub(crate) fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::comparison::RegressionThresholds;
+    use crate::benchmark::results::ResultComparison;
+    use crate::benchmark::test_support::sample_test_result;
+
+    // 存储顺序写入下降4%，超过该指标3%的阈值
+    fn regressed_result() -> TestResult {
+        let baseline = sample_test_result("2026-01-01T00:00:00Z", 100.0);
+        let mut result = sample_test_result("2026-01-02T00:00:00Z", 101.0);
+        result.storage_results.as_mut().unwrap().sequential_write.throughput = 384.0;
+        result.cpu_results.as_mut().unwrap().single_thread_score = 1234.6;
+        let thresholds = RegressionThresholds::parse(include_str!("../../tests/fixtures/regression_thresholds.toml")).unwrap();
+        result.regression_check = Some(ResultComparison::between("baseline-1", &baseline, "result-2", &result, &thresholds));
+        result
+    }
+
+    #[test]
+    fn test_output_formats_match_snapshots() {
+        // UPDATE_SNAPSHOTS=1 cargo test 重新生成
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ci_output");
+        let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+        let result = regressed_result();
+        for (format, file) in [
+            (OutputFormat::Json, "result.json"),
+            (OutputFormat::Junit, "junit.xml"),
+            (OutputFormat::Bencher, "bencher.txt"),
+        ] {
+            let path = dir.join(file);
+            let rendered = render("result-2", &result, format).unwrap();
+            if update {
+                fs::create_dir_all(&dir).unwrap();
+                fs::write(&path, rendered).unwrap();
+            } else {
+                let committed = fs::read_to_string(&path).unwrap_or_default();
+                assert!(committed == rendered, "{} 已过期，运行 UPDATE_SNAPSHOTS=1 cargo test 更新", path.display());
+            }
+        }
+    }
+
+    #[test]
+    fn test_junit_parses_and_reports_regressions() {
+        let result = regressed_result();
+        let xml = render("result-2", &result, OutputFormat::Junit).unwrap();
+        let document = roxmltree::Document::parse(&xml).unwrap();
+        let root = document.root_element();
+        assert_eq!(root.tag_name().name(), "testsuites");
+
+        let cases: Vec<_> = root.descendants().filter(|node| node.has_tag_name("testcase")).collect();
+        let report = &result.regression_check.as_ref().unwrap().report;
+        assert_eq!(cases.len(), report.metrics.len());
+        assert_eq!(root.attribute("tests"), Some(cases.len().to_string().as_str()));
+        let failed: Vec<(&str, &str)> = cases
+            .iter()
+            .filter(|case| case.children().any(|child| child.has_tag_name("failure")))
+            .map(|case| (case.attribute("classname").unwrap(), case.attribute("name").unwrap()))
+            .collect();
+        assert_eq!(failed, [("storage", "sequential_write.throughput")]);
+        assert_eq!(root.attribute("failures"), Some("1"));
+        assert!(has_regressions(&result));
+
+        // 没有回归对比时没有测试用例，也不算回归
+        let mut first_run = result.clone();
+        first_run.regression_check = None;
+        let xml = render("result-2", &first_run, OutputFormat::Junit).unwrap();
+        let document = roxmltree::Document::parse(&xml).unwrap();
+        assert!(!document.descendants().any(|node| node.has_tag_name("testcase")));
+        assert!(!has_regressions(&first_run));
+
+        // 属性值中的特殊字符被转义
+        let xml = render("a<\"&'>b", &first_run, OutputFormat::Junit).unwrap();
+        let document = roxmltree::Document::parse(&xml).unwrap();
+        let property = document.descendants().find(|node| node.attribute("name") == Some("result_id")).unwrap();
+        assert_eq!(property.attribute("value"), Some("a<\"&'>b"));
+    }
+
+    #[test]
+    fn test_bencher_lines_follow_libtest_format() {
+        let output = render("result-2", &regressed_result(), OutputFormat::Bencher).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), metric_values(&regressed_result()).len());
+        assert!(lines.contains(&"test cpu::single_thread_score ... bench:       1,235 ns/iter (+/- 0)"), "{}", output);
+        assert!(lines.iter().all(|line| line.starts_with("test ") && line.ends_with(" ns/iter (+/- 0)")));
+        assert_eq!(with_separators(1234567.4), "1,234,567");
+        assert_eq!(with_separators(999.0), "999");
+        assert_eq!(OutputFormat::parse("junit"), Some(OutputFormat::Junit));
+        assert_eq!(OutputFormat::parse("xml"), None);
+    }
+}
@@ -0,0 +1,307 @@
+//! 结果对比和回归判定：逐项指标计算相对基准的变化，按阈值判定是否回归。
+//! 历史对比命令和自动化检查使用同一套逻辑
+use crate::benchmark::core::TestResult;
+use crate::benchmark::error::BenchmarkError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// 参与对比的一项指标，名称为点分隔的字段路径
+struct MetricDefinition {
+    name: &'static str,
+    higher_is_better: bool,
+    value: fn(&TestResult) -> Option<f64>,
+}
+
+const METRICS: &[MetricDefinition] = &[
+    MetricDefinition { name: "overall_score", higher_is_better: true, value: |r| Some(r.overall_score) },
+    MetricDefinition { name: "cpu.single_thread_score", higher_is_better: true, value: |r| r.cpu_results.as_ref().map(|c| c.single_thread_score) },
+    MetricDefinition { name: "cpu.multi_thread_score", higher_is_better: true, value: |r| r.cpu_results.as_ref().map(|c| c.multi_thread_score) },
+    MetricDefinition { name: "cpu.floating_point_score", higher_is_better: true, value: |r| r.cpu_results.as_ref().map(|c| c.floating_point_score) },
+    MetricDefinition { name: "memory.sequential_read_speed", higher_is_better: true, value: |r| r.memory_results.as_ref().map(|m| m.sequential_read_speed) },
+    MetricDefinition { name: "memory.sequential_write_speed", higher_is_better: true, value: |r| r.memory_results.as_ref().map(|m| m.sequential_write_speed) },
+    MetricDefinition { name: "memory.random_access_speed", higher_is_better: true, value: |r| r.memory_results.as_ref().map(|m| m.random_access_speed) },
+    MetricDefinition { name: "memory.latency", higher_is_better: false, value: |r| r.memory_results.as_ref().map(|m| m.latency) },
+    MetricDefinition { name: "storage.sequential_read.throughput", higher_is_better: true, value: |r| r.storage_results.as_ref().map(|s| s.sequential_read.throughput) },
+    MetricDefinition { name: "storage.sequential_write.throughput", higher_is_better: true, value: |r| r.storage_results.as_ref().map(|s| s.sequential_write.throughput) },
+    MetricDefinition { name: "storage.random_read.iops", higher_is_better: true, value: |r| r.storage_results.as_ref().map(|s| s.random_read.iops as f64) },
+    MetricDefinition { name: "storage.random_write.iops", higher_is_better: true, value: |r| r.storage_results.as_ref().map(|s| s.random_write.iops as f64) },
+    MetricDefinition { name: "storage.random_read.latency", higher_is_better: false, value: |r| r.storage_results.as_ref().map(|s| s.random_read.latency) },
+    MetricDefinition { name: "storage.random_write.latency", higher_is_better: false, value: |r| r.storage_results.as_ref().map(|s| s.random_write.latency) },
+];
+
+/// 可以在阈值配置中引用的指标名，按对比报告中的顺序
+pub fn comparison_metric_names() -> Vec<&'static str> {
+    METRICS.iter().map(|metric| metric.name).collect()
+}
+
+/// 结果中存在的对比指标及其数值，按对比报告中的顺序
+pub fn metric_values(result: &TestResult) -> Vec<(&'static str, f64)> {
+    METRICS
+        .iter()
+        .filter_map(|metric| (metric.value)(result).map(|value| (metric.name, value)))
+        .collect()
+}
+
+/// 回归阈值：指标向变差的方向变化超过该百分比时判定为回归。
+/// TOML格式如下，未列出的指标使用default_percent：
+///
+/// ```toml
+/// default_percent = 5.0
+/// [metrics]
+/// "storage.sequential_write.throughput" = 3.0
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct RegressionThresholds {
+    #[serde(alias = "defaultPercent")]
+    pub default_percent: f64,
+    pub metrics: BTreeMap<String, f64>, // 单项指标的阈值（百分比）
+}
+
+impl Default for RegressionThresholds {
+    fn default() -> Self {
+        Self {
+            default_percent: 5.0,
+            metrics: BTreeMap::new(),
+        }
+    }
+}
+
+impl RegressionThresholds {
+    pub fn threshold_for(&self, metric: &str) -> f64 {
+        self.metrics.get(metric).copied().unwrap_or(self.default_percent)
+    }
+
+    /// 从TOML文件读取阈值，拒绝未知的指标名和负数阈值
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, BenchmarkError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| BenchmarkError::DataLoadError(format!("无法读取阈值文件 {}: {}", path.display(), e)))?;
+        Self::parse(&contents)
+            .map_err(|e| BenchmarkError::DataLoadError(format!("阈值文件 {} {}", path.display(), e)))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let thresholds: Self = toml::from_str(contents).map_err(|e| format!("解析失败: {}", e.message()))?;
+        thresholds.validate()?;
+        Ok(thresholds)
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        let names = comparison_metric_names();
+        if let Some(unknown) = self.metrics.keys().find(|metric| !names.contains(&metric.as_str())) {
+            return Err(format!("未知的指标 {}，可用的指标: {}", unknown, names.join(", ")));
+        }
+        let thresholds = std::iter::once(("default_percent", &self.default_percent))
+            .chain(self.metrics.iter().map(|(metric, percent)| (metric.as_str(), percent)));
+        for (metric, percent) in thresholds {
+            if !percent.is_finite() || *percent < 0.0 {
+                return Err(format!("指标 {} 的阈值必须是非负数，当前为 {}", metric, percent));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 单项指标的判定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricStatus {
+    Improved,  // 向变好的方向变化超过阈值
+    Unchanged, // 变化在阈值以内
+    Regressed, // 向变差的方向变化超过阈值
+    Missing,   // 任一侧缺少该指标或基准为0，无法比较
+}
+
+/// 单项指标的对比结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct MetricComparison {
+    pub metric: String,
+    pub baseline: Option<f64>,
+    pub candidate: Option<f64>,
+    pub change_percent: Option<f64>, // 相对基准的变化百分比，无法比较时为空
+    pub threshold_percent: f64,
+    pub higher_is_better: bool,
+    pub status: MetricStatus,
+}
+
+/// 总体判定：任一指标回归即为Fail，缺失的指标不影响判定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Pass,
+    Fail,
+}
+
+/// 两个结果的对比，`warnings`提示可能影响可比性的差异
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ComparisonReport {
+    pub metrics: Vec<MetricComparison>,
+    pub verdict: Verdict,
+    pub warnings: Vec<String>,
+}
+
+impl ComparisonReport {
+    pub fn passed(&self) -> bool {
+        self.verdict == Verdict::Pass
+    }
+
+    pub fn regressions(&self) -> impl Iterator<Item = &MetricComparison> {
+        self.metrics.iter().filter(|metric| metric.status == MetricStatus::Regressed)
+    }
+
+    pub fn metric(&self, name: &str) -> Option<&MetricComparison> {
+        self.metrics.iter().find(|metric| metric.metric == name)
+    }
+}
+
+impl TestResult {
+    /// 以baseline为基准对比本结果。两侧都没有的指标（如都未运行存储测试）不列出
+    pub fn compare(&self, baseline: &TestResult, thresholds: &RegressionThresholds) -> ComparisonReport {
+        let metrics: Vec<MetricComparison> = METRICS
+            .iter()
+            .filter_map(|definition| {
+                let (b, c) = ((definition.value)(baseline), (definition.value)(self));
+                if b.is_none() && c.is_none() {
+                    return None;
+                }
+                let change_percent = match (b, c) {
+                    (Some(b), Some(c)) if b != 0.0 => Some((c - b) / b * 100.0),
+                    _ => None,
+                };
+                let threshold_percent = thresholds.threshold_for(definition.name);
+                let status = match change_percent {
+                    None => MetricStatus::Missing,
+                    Some(change) => {
+                        // 统一为"变好的百分比"，越低越好的指标取反
+                        let improvement = if definition.higher_is_better { change } else { -change };
+                        if improvement < -threshold_percent {
+                            MetricStatus::Regressed
+                        } else if improvement > threshold_percent {
+                            MetricStatus::Improved
+                        } else {
+                            MetricStatus::Unchanged
+                        }
+                    }
+                };
+                Some(MetricComparison {
+                    metric: definition.name.to_string(),
+                    baseline: b,
+                    candidate: c,
+                    change_percent,
+                    threshold_percent,
+                    higher_is_better: definition.higher_is_better,
+                    status,
+                })
+            })
+            .collect();
+
+        // 负载实现随引擎版本变化，跨版本的分数不一定可比
+        let mut warnings = Vec::new();
+        let engine_version = |r: &TestResult| r.app_info.as_ref().map(|info| info.engine_version.clone());
+        match (engine_version(baseline), engine_version(self)) {
+            (Some(b), Some(c)) if b != c => warnings.push(format!(
+                "两个结果来自不同的引擎版本（{} 与 {}），分数可能不可比",
+                b, c
+            )),
+            (Some(_), Some(_)) => {}
+            _ => warnings.push("至少一个结果缺少引擎版本信息，无法确认分数是否可比".to_string()),
+        }
+
+        let verdict = if metrics.iter().any(|metric| metric.status == MetricStatus::Regressed) {
+            Verdict::Fail
+        } else {
+            Verdict::Pass
+        };
+        ComparisonReport { metrics, verdict, warnings }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::test_support::sample_test_result;
+
+    fn thresholds() -> RegressionThresholds {
+        RegressionThresholds::parse(include_str!("../../tests/fixtures/regression_thresholds.toml")).unwrap()
+    }
+
+    #[test]
+    fn test_regression_fails_the_comparison() {
+        let baseline = sample_test_result("2024-01-01T00:00:00Z", 100.0);
+        let mut candidate = sample_test_result("2024-01-02T00:00:00Z", 100.0);
+        // 顺序写入从400降到384（-4%），超过该指标3%的阈值
+        candidate.storage_results.as_mut().unwrap().sequential_write.throughput = 384.0;
+
+        let report = candidate.compare(&baseline, &thresholds());
+        assert_eq!(report.verdict, Verdict::Fail);
+        let regressions: Vec<&str> = report.regressions().map(|metric| metric.metric.as_str()).collect();
+        assert_eq!(regressions, ["storage.sequential_write.throughput"]);
+        let write = report.metric("storage.sequential_write.throughput").unwrap();
+        assert!((write.change_percent.unwrap() + 4.0).abs() < 1e-9);
+        assert_eq!(write.threshold_percent, 3.0);
+        // 同样的变化在默认5%阈值下不算回归
+        assert!(candidate.compare(&baseline, &RegressionThresholds::default()).passed());
+    }
+
+    #[test]
+    fn test_improvement_and_within_threshold_changes_pass() {
+        let baseline = sample_test_result("2024-01-01T00:00:00Z", 100.0);
+        let mut candidate = sample_test_result("2024-01-02T00:00:00Z", 104.0);
+        candidate.cpu_results.as_mut().unwrap().single_thread_score = 130.0;
+        // 延迟越低越好：从100ns降到80ns是改进
+        candidate.memory_results.as_mut().unwrap().latency = 80.0;
+
+        let report = candidate.compare(&baseline, &thresholds());
+        assert!(report.passed());
+        assert_eq!(report.metric("cpu.single_thread_score").unwrap().status, MetricStatus::Improved);
+        assert_eq!(report.metric("memory.latency").unwrap().status, MetricStatus::Improved);
+        // 总分+4%在阈值以内
+        assert_eq!(report.metric("overall_score").unwrap().status, MetricStatus::Unchanged);
+        assert_eq!(report.metric("cpu.multi_thread_score").unwrap().status, MetricStatus::Unchanged);
+
+        // 延迟上升超过阈值是回归
+        candidate.memory_results.as_mut().unwrap().latency = 120.0;
+        let report = candidate.compare(&baseline, &thresholds());
+        assert_eq!(report.regressions().map(|m| m.metric.as_str()).collect::<Vec<_>>(), ["memory.latency"]);
+    }
+
+    #[test]
+    fn test_metric_missing_from_baseline_does_not_fail() {
+        let mut baseline = sample_test_result("2024-01-01T00:00:00Z", 100.0);
+        baseline.storage_results = None;
+        let candidate = sample_test_result("2024-01-02T00:00:00Z", 100.0);
+
+        let report = candidate.compare(&baseline, &thresholds());
+        assert!(report.passed());
+        let read = report.metric("storage.sequential_read.throughput").unwrap();
+        assert_eq!((read.baseline, read.candidate, read.change_percent), (None, Some(500.0), None));
+        assert_eq!(read.status, MetricStatus::Missing);
+
+        // 两侧都没有的指标不列出
+        let mut candidate = candidate;
+        candidate.storage_results = None;
+        let report = candidate.compare(&baseline, &thresholds());
+        assert!(report.metric("storage.sequential_read.throughput").is_none());
+        assert_eq!(report.metrics.len(), comparison_metric_names().len() - 6);
+    }
+
+    #[test]
+    fn test_thresholds_file_is_validated() {
+        let thresholds = thresholds();
+        assert_eq!(thresholds.threshold_for("storage.sequential_write.throughput"), 3.0);
+        assert_eq!(thresholds.threshold_for("cpu.single_thread_score"), 10.0);
+        assert_eq!(RegressionThresholds::parse("").unwrap(), RegressionThresholds::default());
+
+        let unknown = RegressionThresholds::parse("[metrics]\n\"storage.write\" = 3.0\n").unwrap_err();
+        assert!(unknown.contains("storage.write"), "{}", unknown);
+        assert!(RegressionThresholds::parse("default_percent = -1.0\n").is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("thresholds.toml");
+        std::fs::write(&path, "default_percent = \"high\"\n").unwrap();
+        assert!(RegressionThresholds::from_file(&path).unwrap_err().to_string().contains("thresholds.toml"));
+    }
+}
@@ -0,0 +1,598 @@
+//! composite_v1导出格式：按常见基准测试JSON的结构（sections → workloads → 数值/单位/得分）
+//! 重新组织TestResult，供已有的分析工具读取。各项指标到分区和负载的映射全部在本模块的映射表中，
+//! 测试保证结果中的每个数值字段都有映射，新增字段不会在导出中被悄悄遗漏。
+//! CSV导出使用同一张映射表，每个负载一行，便于在电子表格中对比多台机器
+use crate::benchmark::core::TestResult;
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::report;
+use crate::benchmark::scoring::{
+    CPU_FLOATING_POINT_BASELINE, CPU_MULTI_THREAD_BASELINE, CPU_SINGLE_THREAD_BASELINE, GPU_BANDWIDTH_BASELINE,
+    GPU_COMPUTE_BASELINE, MEMORY_SEQUENTIAL_READ_BASELINE, MEMORY_SEQUENTIAL_WRITE_BASELINE, NETWORK_DOWNLOAD_BASELINE,
+    NETWORK_UPLOAD_BASELINE, REFERENCE_SCORE, STORAGE_SEQUENTIAL_READ_BASELINE, STORAGE_SEQUENTIAL_WRITE_BASELINE,
+};
+use crate::format::csv_field;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// 导出文档中的格式名，结构变化时改用新的名称
+pub const COMPOSITE_FORMAT: &str = "composite_v1";
+
+/// 归一化得分的基准系统：内置参考数据中的2019笔记本，达到基准值的负载得分为1000
+pub const BASELINE_SYSTEM: &str = "builtin/modern_laptop";
+const BASELINE_SCORE: f64 = REFERENCE_SCORE;
+
+/// CSV导出的表头，每行为一个系统信息项或一个负载的数值
+pub const CSV_HEADER: &str = "section,metric,value,unit";
+
+// CSV中数值的小数位数，固定位数使各行在电子表格中对齐
+const CSV_PRECISION: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompositeSection {
+    CpuSingleCore,
+    CpuMultiCore,
+    Memory,
+    Storage,
+    Gpu,
+    Webview,
+    Network,
+}
+
+impl CompositeSection {
+    pub const ALL: [CompositeSection; 7] = [
+        CompositeSection::CpuSingleCore,
+        CompositeSection::CpuMultiCore,
+        CompositeSection::Memory,
+        CompositeSection::Storage,
+        CompositeSection::Gpu,
+        CompositeSection::Webview,
+        CompositeSection::Network,
+    ];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            CompositeSection::CpuSingleCore => "cpu_single_core",
+            CompositeSection::CpuMultiCore => "cpu_multi_core",
+            CompositeSection::Memory => "memory",
+            CompositeSection::Storage => "storage",
+            CompositeSection::Gpu => "gpu",
+            CompositeSection::Webview => "webview",
+            CompositeSection::Network => "network",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CompositeSection::CpuSingleCore => "CPU Single-Core",
+            CompositeSection::CpuMultiCore => "CPU Multi-Core",
+            CompositeSection::Memory => "Memory",
+            CompositeSection::Storage => "Storage",
+            CompositeSection::Gpu => "GPU",
+            CompositeSection::Webview => "WebView",
+            CompositeSection::Network => "Network",
+        }
+    }
+}
+
+/// export_results支持的格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultExportFormat {
+    #[default]
+    Json, // 原始结果文档
+    CompositeV1,
+    Csv, // 每个数值一行，见CSV_HEADER
+    Html, // 独立的HTML报告，见report模块
+}
+
+/// 按指定格式把结果写入文件，父目录不存在时自动创建。
+/// 内容在写入前全部生成，目标不可写时返回DataSaveError，不会留下空文件。
+/// 错误信息中带有io::ErrorKind，以区分权限不足、磁盘已满等情况
+pub fn export_result(result_id: &str, result: &TestResult, format: ResultExportFormat, path: &Path) -> Result<(), BenchmarkError> {
+    let content = match format {
+        ResultExportFormat::Json => serde_json::to_string_pretty(result),
+        ResultExportFormat::CompositeV1 => serde_json::to_string_pretty(&CompositeExport::from_result(result_id, result)),
+        ResultExportFormat::Csv => Ok(result_csv(result_id, result)),
+        ResultExportFormat::Html => Ok(report::render_html(result_id, result)),
+    }
+    .map_err(|e| BenchmarkError::DataSaveError(format!("结果序列化失败: {}", e)))?;
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| {
+            BenchmarkError::DataSaveError(format!("无法创建导出目录 {}: {} ({:?})", parent.display(), e, e.kind()))
+        })?;
+    }
+    fs::write(path, content)
+        .map_err(|e| BenchmarkError::DataSaveError(format!("无法写入导出文件 {}: {} ({:?})", path.display(), e, e.kind())))
+}
+
+/// 把结果展开为CSV：先是系统信息和总分，然后按映射表每个负载一行（CPU得分、内存速度和延迟、
+/// 各存储测试的吞吐量、IOPS和延迟等），扩展测试的数值字段在最后。数值保留CSV_PRECISION位小数
+pub fn result_csv(result_id: &str, result: &TestResult) -> String {
+    let system = &result.system_info;
+    let number = |value: f64| format!("{:.*}", CSV_PRECISION, value);
+    let mut rows: Vec<[String; 4]> = vec![
+        ["result".to_string(), "Result ID".to_string(), result_id.to_string(), String::new()],
+        ["result".to_string(), "Timestamp".to_string(), result.timestamp.clone(), String::new()],
+        ["system".to_string(), "OS".to_string(), system.os.clone(), String::new()],
+        ["system".to_string(), "CPU".to_string(), system.cpu.name.clone(), String::new()],
+        ["system".to_string(), "CPU Cores".to_string(), system.cpu.cores.to_string(), "count".to_string()],
+        ["system".to_string(), "CPU Threads".to_string(), system.cpu.threads.to_string(), "count".to_string()],
+        ["system".to_string(), "Memory Total".to_string(), system.memory.total.to_string(), "GB".to_string()],
+        ["overall".to_string(), "Overall Score".to_string(), number(result.overall_score), "points".to_string()],
+    ];
+    for section in CompositeExport::from_result(result_id, result).sections {
+        for workload in section.workloads {
+            rows.push([section.id.clone(), workload.name, number(workload.value), workload.unit]);
+        }
+    }
+
+    let mut csv = format!("{}\n", CSV_HEADER);
+    for row in rows {
+        let _ = writeln!(csv, "{}", row.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(","));
+    }
+    csv
+}
+
+/// 基准系统上的数值，以及数值越高越好还是越低越好
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Baseline {
+    Higher(f64),
+    Lower(f64),
+}
+
+impl Baseline {
+    fn score(&self, value: f64) -> Option<u32> {
+        let ratio = match *self {
+            Baseline::Higher(baseline) => value / baseline,
+            Baseline::Lower(baseline) if value > 0.0 => baseline / value,
+            Baseline::Lower(_) => return None,
+        };
+        (ratio.is_finite() && ratio >= 0.0).then(|| (ratio * BASELINE_SCORE).round() as u32)
+    }
+}
+
+/// 映射表的一行。pointer是序列化后的TestResult中的JSON指针，`*`表示数组的每个元素，
+/// 此时负载名称中的`{}`替换为元素的label字段
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkloadMapping {
+    pub section: CompositeSection,
+    pub name: &'static str,
+    pub pointer: &'static str,
+    pub label: Option<&'static str>,
+    pub unit: &'static str,
+    pub baseline: Option<Baseline>, // 没有基准值的负载只导出原始数值
+}
+
+fn mapping(section: CompositeSection, name: &'static str, pointer: &'static str, unit: &'static str, baseline: Option<Baseline>) -> WorkloadMapping {
+    WorkloadMapping { section, name, pointer, label: None, unit, baseline }
+}
+
+fn per_item(
+    section: CompositeSection,
+    name: &'static str,
+    pointer: &'static str,
+    label: &'static str,
+    unit: &'static str,
+) -> WorkloadMapping {
+    WorkloadMapping { section, name, pointer, label: Some(label), unit, baseline: None }
+}
+
+/// 全部指标的映射，顺序即导出中负载的顺序
+pub fn mapping_table() -> Vec<WorkloadMapping> {
+    use Baseline::{Higher, Lower};
+    use CompositeSection::*;
+
+    let mut table = vec![
+        mapping(CpuSingleCore, "Integer", "/cpu_results/single_thread_score", "points", Some(Higher(CPU_SINGLE_THREAD_BASELINE))),
+        mapping(CpuMultiCore, "Integer", "/cpu_results/multi_thread_score", "points", Some(Higher(CPU_MULTI_THREAD_BASELINE))),
+        mapping(CpuMultiCore, "Floating Point", "/cpu_results/floating_point_score", "points", Some(Higher(CPU_FLOATING_POINT_BASELINE))),
+        mapping(CpuMultiCore, "Operations", "/cpu_results/operations_per_second", "ops/s", None),
+        mapping(CpuMultiCore, "Average Temperature", "/cpu_results/average_temperature", "°C", None),
+        mapping(CpuMultiCore, "Max Temperature", "/cpu_results/max_temperature", "°C", None),
+        mapping(CpuMultiCore, "Duration", "/cpu_results/test_duration", "s", None),
+        mapping(Memory, "Sequential Read", "/memory_results/sequential_read_speed", "MB/s", Some(Higher(MEMORY_SEQUENTIAL_READ_BASELINE))),
+        mapping(Memory, "Sequential Write", "/memory_results/sequential_write_speed", "MB/s", Some(Higher(MEMORY_SEQUENTIAL_WRITE_BASELINE))),
+        mapping(Memory, "Random Access", "/memory_results/random_access_speed", "MB/s", Some(Higher(400.0))),
+        mapping(Memory, "Latency", "/memory_results/latency", "ns", Some(Lower(100.0))),
+        mapping(Memory, "Peak Usage", "/memory_results/memory_usage_peak", "MB", None),
+        mapping(Memory, "Error Rate", "/memory_results/error_rate", "%", None),
+        mapping(Memory, "Duration", "/memory_results/test_duration", "s", None),
+        mapping(Memory, "Interference Logical Cores", "/memory_results/interference/logical_cores", "count", None),
+        per_item(Memory, "Read Under Load ({} threads)", "/memory_results/interference/measurements/*/sequential_read_speed", "load_threads", "MB/s"),
+        per_item(Memory, "Retention Under Load ({} threads)", "/memory_results/interference/measurements/*/retention", "load_threads", "%"),
+    ];
+
+    let storage_patterns: [(&str, &str, Option<Baseline>, Option<Baseline>); 4] = [
+        ("Sequential Read", "sequential_read", Some(Higher(STORAGE_SEQUENTIAL_READ_BASELINE)), None),
+        ("Sequential Write", "sequential_write", Some(Higher(STORAGE_SEQUENTIAL_WRITE_BASELINE)), None),
+        ("Random Read", "random_read", None, Some(Higher(10_000.0))),
+        ("Random Write", "random_write", None, Some(Higher(8_000.0))),
+    ];
+    for (name, field, throughput_baseline, iops_baseline) in storage_patterns {
+        table.push(mapping(Storage, name, leak(format!("/storage_results/{}/throughput", field)), "MB/s", throughput_baseline));
+        table.push(mapping(Storage, leak(format!("{} IOPS", name)), leak(format!("/storage_results/{}/iops", field)), "IOPS", iops_baseline));
+        table.push(mapping(Storage, leak(format!("{} Latency", name)), leak(format!("/storage_results/{}/latency", field)), "ms", None));
+    }
+    table.extend([
+        mapping(Storage, "Duration", "/storage_results/test_duration", "s", None),
+        mapping(Storage, "Data Processed", "/storage_results/total_data_processed", "MB", None),
+        mapping(Storage, "Small Files", "/storage_results/small_files/file_count", "count", None),
+        mapping(Storage, "Small File Bytes", "/storage_results/small_files/total_bytes", "bytes", None),
+    ]);
+    for (name, phase) in [("Create", "create"), ("Stat", "stat"), ("Read", "read"), ("Delete", "delete")] {
+        let pointer = |field: &str| leak(format!("/storage_results/small_files/{}/{}", phase, field));
+        table.extend([
+            mapping(Storage, leak(format!("Small File {} Files", name)), pointer("files"), "count", None),
+            mapping(Storage, leak(format!("Small File {}", name)), pointer("files_per_second"), "files/s", None),
+            mapping(Storage, leak(format!("Small File {} p50", name)), pointer("p50_ms"), "ms", None),
+            mapping(Storage, leak(format!("Small File {} p95", name)), pointer("p95_ms"), "ms", None),
+            mapping(Storage, leak(format!("Small File {} p99", name)), pointer("p99_ms"), "ms", None),
+        ]);
+    }
+    table.extend([
+        mapping(Storage, "Archive Files", "/storage_results/archive/file_count", "count", None),
+        mapping(Storage, "Archive Bytes", "/storage_results/archive/total_bytes", "bytes", None),
+        per_item(Storage, "Archive Extraction ({})", "/storage_results/archive/extractions/*/throughput", "format", "MB/s"),
+        per_item(Storage, "Archive Extraction Files ({})", "/storage_results/archive/extractions/*/files_per_second", "format", "files/s"),
+        per_item(Storage, "Archive Extraction Time ({})", "/storage_results/archive/extractions/*/seconds", "format", "s"),
+        per_item(Storage, "Archive Size ({})", "/storage_results/archive/extractions/*/archive_bytes", "format", "bytes"),
+        mapping(Gpu, "Compute", "/gpu_results/compute_score", "GFLOPS", Some(Higher(GPU_COMPUTE_BASELINE))),
+        mapping(Gpu, "Memory Bandwidth", "/gpu_results/memory_bandwidth_gb_s", "GB/s", Some(Higher(GPU_BANDWIDTH_BASELINE))),
+        per_item(Webview, "{}", "/webview_results/workloads/*/ops_per_second", "workload", "ops/s"),
+        per_item(Webview, "{} Operations", "/webview_results/workloads/*/operations", "workload", "count"),
+        per_item(Webview, "{} Time", "/webview_results/workloads/*/duration_ms", "workload", "ms"),
+        mapping(Webview, "Duration", "/webview_results/test_duration", "s", None),
+        mapping(Network, "Download", "/network_results/download_mbps", "Mbps", Some(Higher(NETWORK_DOWNLOAD_BASELINE))),
+        mapping(Network, "Upload", "/network_results/upload_mbps", "Mbps", Some(Higher(NETWORK_UPLOAD_BASELINE))),
+        mapping(Network, "Latency", "/network_results/latency_avg_ms", "ms", Some(Lower(20.0))),
+        mapping(Network, "Min Latency", "/network_results/latency_min_ms", "ms", None),
+        mapping(Network, "Max Latency", "/network_results/latency_max_ms", "ms", None),
+        mapping(Network, "Jitter", "/network_results/jitter_ms", "ms", None),
+        mapping(Network, "Loss", "/network_results/loss_percent", "%", None),
+        mapping(Network, "Connections", "/network_results/parallel_connections", "count", None),
+        mapping(Network, "Duration", "/network_results/test_duration", "s", None),
+    ]);
+    table
+}
+
+// 映射表中拼出的名称和指针只生成一次，生命周期与进程相同
+fn leak(value: String) -> &'static str {
+    Box::leak(value.into_boxed_str())
+}
+
+/// 导出文档
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CompositeExport {
+    pub format: String,
+    pub metadata: CompositeMetadata,
+    pub sections: Vec<CompositeSectionResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CompositeMetadata {
+    pub result_id: String,
+    pub timestamp: String,
+    pub overall_score: f64,
+    pub baseline_system: String,
+    pub baseline_score: f64, // 基准系统上每个负载的得分
+    pub app_version: Option<String>,
+    pub engine_version: Option<String>,
+    pub result_schema_version: u32,
+    pub os: String,
+    pub cpu_model: String,
+    pub cpu_cores: usize,
+    pub cpu_threads: usize,
+    pub memory_total_gb: u64,
+    pub gpu_model: Option<String>,
+    pub verified: Option<bool>, // 完整性校验状态
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CompositeSectionResult {
+    pub id: String,
+    pub name: String,
+    pub score: Option<u32>, // 有得分的负载的几何平均，没有时为空
+    pub workloads: Vec<CompositeWorkload>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CompositeWorkload {
+    pub name: String,
+    pub value: f64,
+    pub unit: String,
+    pub score: Option<u32>, // 相对基准系统的得分，没有基准值时为空
+}
+
+impl CompositeExport {
+    /// 按映射表导出，没有结果的分区不列出；扩展测试各自作为一个分区，数值字段原样列为负载
+    pub fn from_result(result_id: &str, result: &TestResult) -> Self {
+        let document = serde_json::to_value(result).unwrap_or(Value::Null);
+        let table = mapping_table();
+        let mut sections: Vec<CompositeSectionResult> = CompositeSection::ALL
+            .iter()
+            .map(|section| {
+                let workloads = table
+                    .iter()
+                    .filter(|mapping| mapping.section == *section)
+                    .flat_map(|mapping| workloads(&document, mapping))
+                    .collect();
+                section_result(section.id().to_string(), section.name().to_string(), workloads)
+            })
+            .filter(|section| !section.workloads.is_empty())
+            .collect();
+        for (name, value) in &result.extra_results {
+            let mut leaves = Vec::new();
+            numeric_leaves(value, String::new(), &mut leaves);
+            let workloads = leaves
+                .into_iter()
+                .map(|(path, value)| CompositeWorkload {
+                    name: path.trim_start_matches('/').replace('/', " "),
+                    value,
+                    unit: String::new(),
+                    score: None,
+                })
+                .collect();
+            sections.push(section_result(format!("extra_{}", name), name.clone(), workloads));
+        }
+
+        let system = &result.system_info;
+        Self {
+            format: COMPOSITE_FORMAT.to_string(),
+            metadata: CompositeMetadata {
+                result_id: result_id.to_string(),
+                timestamp: result.timestamp.clone(),
+                overall_score: result.overall_score,
+                baseline_system: BASELINE_SYSTEM.to_string(),
+                baseline_score: BASELINE_SCORE,
+                app_version: result.app_info.as_ref().map(|info| info.app_version.clone()),
+                engine_version: result.app_info.as_ref().map(|info| info.engine_version.clone()),
+                result_schema_version: result.schema_version,
+                os: system.os.clone(),
+                cpu_model: system.cpu.name.clone(),
+                cpu_cores: system.cpu.cores,
+                cpu_threads: system.cpu.threads,
+                memory_total_gb: system.memory.total,
+                gpu_model: result.gpu_results.as_ref().map(|gpu| gpu.adapter_name.clone()),
+                verified: result.integrity_verified(),
+            },
+            sections,
+        }
+    }
+}
+
+fn section_result(id: String, name: String, workloads: Vec<CompositeWorkload>) -> CompositeSectionResult {
+    let scores: Vec<f64> = workloads.iter().filter_map(|workload| workload.score).map(|score| score.max(1) as f64).collect();
+    let score = (!scores.is_empty())
+        .then(|| (scores.iter().map(|score| score.ln()).sum::<f64>() / scores.len() as f64).exp().round() as u32);
+    CompositeSectionResult { id, name, score, workloads }
+}
+
+fn workloads(document: &Value, mapping: &WorkloadMapping) -> Vec<CompositeWorkload> {
+    let workload = |name: String, value: f64| CompositeWorkload {
+        name,
+        value,
+        unit: mapping.unit.to_string(),
+        score: mapping.baseline.and_then(|baseline| baseline.score(value)),
+    };
+    match mapping.pointer.split_once("/*") {
+        None => document
+            .pointer(mapping.pointer)
+            .and_then(Value::as_f64)
+            .map(|value| workload(mapping.name.to_string(), value))
+            .into_iter()
+            .collect(),
+        Some((array, field)) => document
+            .pointer(array)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|item| {
+                let value = item.pointer(field)?.as_f64()?;
+                let label = match mapping.label.and_then(|label| item.get(label)) {
+                    Some(Value::String(label)) => label.clone(),
+                    Some(label) => label.to_string(),
+                    None => String::new(),
+                };
+                Some(workload(mapping.name.replace("{}", &label), value))
+            })
+            .collect(),
+    }
+}
+
+// 所有数值叶子节点的JSON指针，数组下标原样保留
+fn numeric_leaves(value: &Value, path: String, leaves: &mut Vec<(String, f64)>) {
+    match value {
+        Value::Number(number) => leaves.extend(number.as_f64().map(|number| (path, number))),
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                numeric_leaves(item, format!("{}/{}", path, index), leaves);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                numeric_leaves(item, format!("{}/{}", path, key), leaves);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::test_support::sample_test_result;
+    use serde_json::json;
+    use std::collections::BTreeSet;
+
+    /// 每个可选部分都有值的结果。结构体新增可选字段时也要在这里补上，覆盖检查才能发现遗漏
+    fn full_result() -> TestResult {
+        let mut result = sample_test_result("2024-06-01T09:00:00Z", 512.0);
+        let memory = result.memory_results.as_mut().unwrap();
+        memory.interference = Some(serde_json::from_value(json!({
+            "logical_cores": 8,
+            "measurements": [
+                { "load_threads": 0, "sequential_read_speed": 1000.0, "retention": 100.0 },
+                { "load_threads": 4, "sequential_read_speed": 800.0, "retention": 80.0 }
+            ]
+        })).unwrap());
+        let phase = |files: usize| json!({ "files": files, "files_per_second": 5000.0, "p50_ms": 0.1, "p95_ms": 0.4, "p99_ms": 0.9 });
+        let storage = result.storage_results.as_mut().unwrap();
+        storage.small_files = Some(serde_json::from_value(json!({
+            "file_count": 100, "total_bytes": 409600,
+            "create": phase(100), "stat": phase(100), "read": phase(100), "delete": phase(100)
+        })).unwrap());
+        storage.archive = Some(serde_json::from_value(json!({
+            "file_count": 204, "total_bytes": 134_217_728u64,
+            "extractions": [
+                { "format": "zip", "archive_bytes": 1000, "seconds": 0.5, "throughput": 256.0, "files_per_second": 408.0 },
+                { "format": "tar_zst", "archive_bytes": 900, "seconds": 0.25, "throughput": 512.0, "files_per_second": 816.0 }
+            ]
+        })).unwrap());
+        result.gpu_results = Some(serde_json::from_value(json!({
+            "compute_score": 2000.0, "memory_bandwidth_gb_s": 50.0, "adapter_name": "Test GPU", "backend": "vulkan"
+        })).unwrap());
+        result.webview_results = Some(serde_json::from_value(json!({
+            "workloads": [{ "workload": "dom_nodes", "operations": 1000, "duration_ms": 20.0, "ops_per_second": 50000.0 }],
+            "test_duration": 1.0
+        })).unwrap());
+        result.network_results = Some(serde_json::from_value(json!({
+            "target": "http://127.0.0.1:8080/", "offline": true, "download_mbps": 1000.0, "upload_mbps": 200.0,
+            "latency_avg_ms": 10.0, "latency_min_ms": 5.0, "latency_max_ms": 20.0, "jitter_ms": 2.0,
+            "loss_percent": 0.0, "parallel_connections": 4, "test_duration": 21.0
+        })).unwrap());
+        result.extra_results.insert("compression".to_string(), json!({ "ratio": 2.5, "levels": [{ "mb_per_sec": 300.0 }] }));
+        result
+    }
+
+    // 把数组下标替换为*，与映射表的指针写法一致
+    fn pattern(pointer: &str) -> String {
+        pointer
+            .split('/')
+            .map(|segment| if segment.parse::<usize>().is_ok() { "*" } else { segment })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    #[test]
+    fn test_mapping_table_covers_every_numeric_field() {
+        let document = serde_json::to_value(full_result()).unwrap();
+        let mut covered = BTreeSet::new();
+        for mapping in mapping_table() {
+            assert!(covered.insert(mapping.pointer.to_string()), "重复的映射: {}", mapping.pointer);
+            if let (Some(label), Some((array, _))) = (mapping.label, mapping.pointer.split_once("/*")) {
+                covered.insert(format!("{}/*/{}", array, label));
+            }
+            assert!(!workloads(&document, &mapping).is_empty(), "映射没有对应的字段: {}", mapping.pointer);
+        }
+
+        let mut leaves = Vec::new();
+        for section in ["cpu_results", "memory_results", "storage_results", "gpu_results", "webview_results", "network_results"] {
+            numeric_leaves(&document[section], format!("/{}", section), &mut leaves);
+        }
+        let missing: Vec<String> = leaves
+            .iter()
+            .map(|(pointer, _)| pattern(pointer))
+            .filter(|pointer| !covered.contains(pointer))
+            .collect();
+        assert_eq!(missing, Vec::<String>::new(), "以下指标没有加入映射表");
+    }
+
+    #[test]
+    fn test_export_matches_committed_schema() {
+        let export = CompositeExport::from_result("r1", &full_result());
+        let schema: Value = serde_json::from_str(include_str!("../../schemas/CompositeExport.json")).unwrap();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        let instance = serde_json::to_value(&export).unwrap();
+        let errors: Vec<String> = validator.iter_errors(&instance).map(|error| format!("{}: {}", error.instance_path, error)).collect();
+        assert_eq!(errors, Vec::<String>::new());
+
+        assert_eq!(export.format, "composite_v1");
+        let ids: Vec<&str> = export.sections.iter().map(|section| section.id.as_str()).collect();
+        assert_eq!(ids, ["cpu_single_core", "cpu_multi_core", "memory", "storage", "gpu", "webview", "network", "extra_compression"]);
+        // 单核100分相对基准110分
+        let single = &export.sections[0];
+        assert_eq!(single.workloads[0].score, Some(909));
+        assert_eq!(single.score, Some(909));
+        // GPU：计算2000/1000 → 2000分，带宽50/100 → 500分，几何平均1000
+        assert_eq!(export.sections[4].score, Some(1000));
+        let memory = &export.sections[2];
+        assert!(memory.workloads.iter().any(|workload| workload.name == "Read Under Load (4 threads)" && workload.value == 800.0));
+        let latency = memory.workloads.iter().find(|workload| workload.name == "Latency").unwrap();
+        assert_eq!((latency.unit.as_str(), latency.score), ("ns", Some(1000)));
+        let storage = &export.sections[3];
+        assert!(storage.workloads.iter().any(|workload| workload.name == "Archive Extraction (tar_zst)" && workload.value == 512.0));
+        // 网络：下载、上传和延迟都是基准的两倍
+        assert_eq!(export.sections[6].score, Some(2000));
+        let extra = &export.sections[7];
+        assert_eq!(extra.workloads.len(), 2);
+        assert_eq!(extra.score, None);
+    }
+
+    // 按RFC 4180拆分一行，引号内的逗号不分隔字段
+    fn parse_csv_line(line: &str) -> Vec<String> {
+        let mut fields = vec![String::new()];
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    fields.last_mut().unwrap().push('"');
+                }
+                '"' => quoted = !quoted,
+                ',' if !quoted => fields.push(String::new()),
+                c => fields.last_mut().unwrap().push(c),
+            }
+        }
+        fields
+    }
+
+    #[test]
+    fn test_csv_export_round_trip() {
+        let mut result = full_result();
+        result.system_info.cpu.name = "Test CPU, 8-Core \"Turbo\"".to_string();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("exports").join("r1.csv");
+        export_result("r1", &result, ResultExportFormat::Csv, &path).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let mut lines = content.lines();
+        assert_eq!(lines.next(), Some(CSV_HEADER));
+        let rows: Vec<Vec<String>> = lines.map(parse_csv_line).collect();
+        assert!(rows.iter().all(|row| row.len() == 4), "{:?}", rows);
+        let value = |section: &str, metric: &str| {
+            rows.iter()
+                .find(|row| row[0] == section && row[1] == metric)
+                .map(|row| (row[2].as_str(), row[3].as_str()))
+                .unwrap_or_else(|| panic!("缺少 {} {}", section, metric))
+        };
+        assert_eq!(value("system", "CPU"), ("Test CPU, 8-Core \"Turbo\"", ""));
+        assert_eq!(value("system", "CPU Cores"), ("4", "count"));
+        assert_eq!(value("system", "Memory Total"), ("16", "GB"));
+        assert_eq!(value("overall", "Overall Score"), ("512.000", "points"));
+        assert_eq!(value("cpu_single_core", "Integer"), ("100.000", "points"));
+        assert_eq!(value("memory", "Latency"), ("100.000", "ns"));
+        assert_eq!(value("storage", "Random Read IOPS"), ("12000.000", "IOPS"));
+        assert_eq!(value("storage", "Sequential Write Latency"), ("0.500", "ms"));
+
+        // 每个负载都导出一行，数值与composite导出一致
+        let export = CompositeExport::from_result("r1", &result);
+        let workloads: Vec<&CompositeWorkload> = export.sections.iter().flat_map(|section| &section.workloads).collect();
+        let metric_rows: Vec<&Vec<String>> = rows.iter().filter(|row| !["result", "system", "overall"].contains(&row[0].as_str())).collect();
+        assert_eq!(metric_rows.len(), workloads.len());
+        for (row, workload) in metric_rows.iter().zip(&workloads) {
+            assert_eq!(row[1], workload.name);
+            assert_eq!(row[2].parse::<f64>().unwrap(), (workload.value * 1000.0).round() / 1000.0);
+        }
+
+        // 目标不可写（这里是一个目录）时返回DataSaveError，信息中保留ErrorKind
+        let err = export_result("r1", &result, ResultExportFormat::Csv, dir.path()).unwrap_err();
+        assert!(matches!(err, BenchmarkError::DataSaveError(_)), "{}", err);
+        let kind = fs::write(dir.path(), "").unwrap_err().kind();
+        assert!(err.to_string().contains(&format!("({:?})", kind)), "{}", err);
+    }
+}
@@ -0,0 +1,334 @@
+//! 压缩和解压吞吐：在由种子生成的固定语料上测量zstd各级别和gzip的速度与压缩比。
+//! 语料由文本段和结构化二进制段交替组成，同一种子在任何机器上生成相同的字节，压缩比可以跨机器比较
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, TestOutcome};
+use crate::benchmark::error::BenchmarkError;
+use crate::i18n::Message;
+use crate::ipc::{ExtraTestResult, TestResultPayload, TestType};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::time::Instant;
+
+/// 在BenchmarkRegistry中的注册名，结果记录在extra_results下的同名键中
+pub const COMPRESSION_TEST_NAME: &str = "compression";
+
+const SEGMENT_SIZE: usize = 64 * 1024; // 文本段和二进制段交替，每段的字节数
+
+const WORDS: [&str; 32] = [
+    "benchmark", "the", "of", "throughput", "latency", "and", "a", "memory", "storage", "to", "cache", "in", "thread",
+    "result", "is", "score", "for", "with", "system", "value", "run", "test", "on", "data", "file", "as", "block",
+    "config", "by", "read", "write", "session",
+];
+const RECORD_TAGS: [[u8; 8]; 8] = [
+    *b"CPU_LOAD", *b"MEM_READ", *b"DSK_WRIT", *b"NET_RECV", [0; 8], [0xFF; 8], *b"\x01\x00\x00\x00\x02\x00\x00\x00", *b"IDLE    ",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionAlgorithm {
+    Zstd,
+    Gzip,
+}
+
+impl CompressionAlgorithm {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CompressionAlgorithm::Zstd => "zstd",
+            CompressionAlgorithm::Gzip => "gzip",
+        }
+    }
+}
+
+/// 一种算法和级别；gzip级别为0-9，zstd级别为1-22
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CompressionCodec {
+    pub algorithm: CompressionAlgorithm,
+    pub level: i32,
+}
+
+impl CompressionCodec {
+    fn label(self) -> String {
+        format!("{}-{}", self.algorithm.as_str(), self.level)
+    }
+
+    // 估计耗时用的压缩速度（MB/s），解压远快于压缩，按压缩时间的四分之一计
+    fn estimated_throughput(self) -> f64 {
+        match (self.algorithm, self.level) {
+            (CompressionAlgorithm::Zstd, level) if level <= 3 => 300.0,
+            (CompressionAlgorithm::Zstd, level) if level <= 9 => 80.0,
+            (CompressionAlgorithm::Zstd, _) => 10.0,
+            (CompressionAlgorithm::Gzip, _) => 40.0,
+        }
+    }
+
+    fn compress(self, data: &[u8]) -> Result<Vec<u8>, BenchmarkError> {
+        match self.algorithm {
+            CompressionAlgorithm::Zstd => zstd::bulk::compress(data, self.level).map_err(BenchmarkError::io("zstd压缩失败")),
+            CompressionAlgorithm::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::with_capacity(data.len() / 2), flate2::Compression::new(self.level as u32));
+                encoder.write_all(data).map_err(BenchmarkError::io("gzip压缩失败"))?;
+                encoder.finish().map_err(BenchmarkError::io("gzip压缩失败"))
+            }
+        }
+    }
+
+    fn decompress(self, data: &[u8], original_len: usize) -> Result<Vec<u8>, BenchmarkError> {
+        match self.algorithm {
+            CompressionAlgorithm::Zstd => zstd::bulk::decompress(data, original_len).map_err(BenchmarkError::io("zstd解压失败")),
+            CompressionAlgorithm::Gzip => {
+                let mut output = Vec::with_capacity(original_len);
+                GzDecoder::new(data).read_to_end(&mut output).map_err(BenchmarkError::io("gzip解压失败"))?;
+                Ok(output)
+            }
+        }
+    }
+}
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct CompressionTestConfig {
+    #[serde(alias = "corpusSize")]
+    pub corpus_size: usize, // MB
+    pub seed: u64, // 语料的随机种子，改变后压缩比不再可比
+    pub iterations: usize, // 每种算法压缩和解压整个语料的次数
+    pub codecs: Vec<CompressionCodec>,
+}
+
+impl Default for CompressionTestConfig {
+    fn default() -> Self {
+        let zstd = |level| CompressionCodec { algorithm: CompressionAlgorithm::Zstd, level };
+        Self {
+            corpus_size: 32,
+            seed: 0x5EED,
+            iterations: 3,
+            codecs: vec![
+                zstd(1),
+                zstd(3),
+                zstd(9),
+                CompressionCodec { algorithm: CompressionAlgorithm::Gzip, level: 6 },
+            ],
+        }
+    }
+}
+
+impl CompressionTestConfig {
+    /// 级别超出算法支持的范围时返回错误，供注册表在套件开始前报告
+    pub fn check(&self) -> Result<(), BenchmarkError> {
+        let invalid = self.codecs.iter().find(|codec| match codec.algorithm {
+            CompressionAlgorithm::Zstd => !zstd::compression_level_range().contains(&codec.level),
+            CompressionAlgorithm::Gzip => !(0..=9).contains(&codec.level),
+        });
+        match invalid {
+            Some(codec) => Err(BenchmarkError::CompressionTestError(format!("不支持的压缩级别: {}", codec.label()))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CodecResult {
+    pub algorithm: CompressionAlgorithm,
+    pub level: i32,
+    pub compress_throughput: f64, // MB/s，按原始数据量计算
+    pub decompress_throughput: f64, // MB/s，按原始数据量计算
+    pub ratio: f64, // 原始大小 / 压缩后大小
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CompressionTestResult {
+    pub corpus_bytes: usize,
+    pub seed: u64,
+    pub codecs: Vec<CodecResult>,
+    pub test_duration: f64, // seconds
+}
+
+impl CompressionTestResult {
+    /// 各算法压缩和解压吞吐的平均值，计入总体评分的CPU部分
+    pub fn score(&self) -> f64 {
+        if self.codecs.is_empty() {
+            return 0.0;
+        }
+        self.codecs
+            .iter()
+            .map(|codec| (codec.compress_throughput + codec.decompress_throughput) / 2.0)
+            .sum::<f64>()
+            / self.codecs.len() as f64
+    }
+}
+
+/// xorshift64*：语料只需要跨平台可复现，不需要密码学强度。其他按种子生成数据的测试也使用它
+pub(crate) struct CorpusRng(u64);
+
+impl CorpusRng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+/// 生成len字节的语料：偶数段为随机选词组成的文本，奇数段为16字节的记录（序号、随机值、标签）
+pub fn generate_corpus(len: usize, seed: u64) -> Vec<u8> {
+    let mut rng = CorpusRng::new(seed);
+    let mut corpus = Vec::with_capacity(len + SEGMENT_SIZE);
+    let mut segment = 0usize;
+    let mut record = 0u32;
+    while corpus.len() < len {
+        let end = corpus.len() + SEGMENT_SIZE;
+        if segment.is_multiple_of(2) {
+            while corpus.len() < end {
+                let value = rng.next();
+                corpus.extend_from_slice(WORDS[(value % WORDS.len() as u64) as usize].as_bytes());
+                corpus.push(if value >> 60 == 0 { b'\n' } else { b' ' });
+            }
+        } else {
+            while corpus.len() < end {
+                let value = rng.next();
+                corpus.extend_from_slice(&record.to_le_bytes());
+                corpus.extend_from_slice(&(value as u32).to_le_bytes());
+                corpus.extend_from_slice(&RECORD_TAGS[(value >> 61) as usize]);
+                record = record.wrapping_add(1);
+            }
+        }
+        segment += 1;
+    }
+    corpus.truncate(len);
+    corpus
+}
+
+pub struct CompressionBenchmark {
+    config: CompressionTestConfig,
+}
+
+impl CompressionBenchmark {
+    pub fn new(config: CompressionTestConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn get_config(&self) -> &CompressionTestConfig {
+        &self.config
+    }
+
+    pub fn run_benchmark(&self) -> Result<CompressionTestResult, BenchmarkError> {
+        self.run_benchmark_cancellable(|_progress, _message| {}, &CancellationToken::never())
+    }
+
+    /// 依次测量各算法，每次压缩后都解压并与语料比对，既防止编译器省略计算，也能发现数据损坏
+    pub fn run_benchmark_cancellable<F>(&self, progress_callback: F, cancellation: &CancellationToken) -> Result<CompressionTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message),
+    {
+        let started = Instant::now();
+        let corpus = generate_corpus(self.config.corpus_size * 1024 * 1024, self.config.seed);
+        let iterations = self.config.iterations.max(1);
+        let steps = (self.config.codecs.len() * iterations).max(1);
+        let megabytes = corpus.len() as f64 / (1024.0 * 1024.0);
+
+        let mut codecs = Vec::with_capacity(self.config.codecs.len());
+        for (index, codec) in self.config.codecs.iter().copied().enumerate() {
+            let mut compress_seconds = 0.0;
+            let mut decompress_seconds = 0.0;
+            let mut compressed_len = 0;
+            for iteration in 0..iterations {
+                let progress = (index * iterations + iteration) as f64 / steps as f64 * 100.0;
+                cancellation.check(TestType::Extra, progress)?;
+                progress_callback(progress, Message::progress("compression.progress", progress).param("codec", codec.label()));
+
+                let timer = Instant::now();
+                let compressed = codec.compress(&corpus)?;
+                compress_seconds += timer.elapsed().as_secs_f64();
+
+                let timer = Instant::now();
+                let restored = codec.decompress(&compressed, corpus.len())?;
+                decompress_seconds += timer.elapsed().as_secs_f64();
+
+                if restored != corpus {
+                    return Err(BenchmarkError::CompressionTestError(format!("{}解压后的数据与原始数据不一致", codec.label())));
+                }
+                compressed_len = compressed.len();
+            }
+            codecs.push(CodecResult {
+                algorithm: codec.algorithm,
+                level: codec.level,
+                compress_throughput: megabytes * iterations as f64 / compress_seconds,
+                decompress_throughput: megabytes * iterations as f64 / decompress_seconds,
+                ratio: corpus.len() as f64 / compressed_len.max(1) as f64,
+            });
+        }
+        progress_callback(100.0, Message::new("compression.complete"));
+        Ok(CompressionTestResult {
+            corpus_bytes: corpus.len(),
+            seed: self.config.seed,
+            codecs,
+            test_duration: started.elapsed().as_secs_f64(),
+        })
+    }
+}
+
+impl BenchmarkRunner for CompressionBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Extra
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        let megabytes = (self.config.corpus_size * self.config.iterations.max(1)) as f64;
+        let seconds: f64 = self
+            .config
+            .codecs
+            .iter()
+            .map(|codec| megabytes / codec.estimated_throughput() * 1.25)
+            .sum();
+        seconds.ceil().max(1.0) as u64
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        let result = self.run_benchmark_cancellable(ctx.progress_callback(), &ctx.cancellation)?;
+        Ok(TestOutcome::Completed(TestResultPayload::Extra(ExtraTestResult {
+            name: COMPRESSION_TEST_NAME.to_string(),
+            result: serde_json::to_value(&result)
+                .map_err(|e| BenchmarkError::DataSaveError(format!("测试结果序列化失败: {}", e)))?,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_corpus_ratios_and_throughputs() {
+        assert_eq!(generate_corpus(4096, 7), generate_corpus(4096, 7));
+        assert_ne!(generate_corpus(4096, 7), generate_corpus(4096, 8));
+
+        let config = CompressionTestConfig {
+            corpus_size: 1,
+            iterations: 1,
+            ..CompressionTestConfig::default()
+        };
+        let result = CompressionBenchmark::new(config).run_benchmark().unwrap();
+        assert_eq!(result.corpus_bytes, 1024 * 1024);
+        // zstd 1、3、9和gzip 6在种子0x5EED的1MB语料上的压缩比，容差覆盖库版本间的细微差异
+        let expected = [2.59, 2.69, 2.73, 2.68];
+        for (codec, expected) in result.codecs.iter().zip(expected) {
+            assert!((codec.ratio - expected).abs() < 0.05, "{:?}应接近{}", codec, expected);
+        }
+        assert!(result.codecs[2].ratio > result.codecs[0].ratio);
+        for codec in &result.codecs {
+            assert!(codec.compress_throughput > 0.0 && codec.decompress_throughput > 0.0, "{:?}", codec);
+        }
+        assert!(result.score() > 0.0);
+
+        let mut config = CompressionTestConfig::default();
+        assert!(config.check().is_ok());
+        config.codecs.push(CompressionCodec { algorithm: CompressionAlgorithm::Gzip, level: 12 });
+        assert_eq!(config.check().unwrap_err().code(), "COMPRESSION_TEST_ERROR");
+    }
+}
@@ -0,0 +1,409 @@
+//! 卷间复制测试：在源卷上生成不可压缩的测试文件，按配置的缓冲区大小复制到目标卷，
+//! 测量持续吞吐和吞吐曲线，并判断读写两侧是否中途降速。两个临时文件在任何情况下都会被删除
+use crate::benchmark::core::CancellationToken;
+use crate::benchmark::error::BenchmarkError;
+use crate::i18n::Message;
+use crate::ipc::TestType;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use sysinfo::Disks;
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+// 每隔这么久记录一个吞吐采样
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+// 测试文件最多占用可用空间的这一比例
+const FREE_SPACE_RATIO: f64 = 0.9;
+
+// 后段吞吐低于前段的这一比例时视为降速
+const THROTTLE_RATIO: f64 = 0.7;
+
+// 采样少于这个数时不判断降速
+const MIN_THROTTLE_SAMPLES: usize = 8;
+
+// 生成源文件占整个测试进度的比例，其余为复制
+const GENERATE_PROGRESS: f64 = 30.0;
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct CopyTestConfig {
+    #[serde(alias = "sourceDir")]
+    pub source_dir: PathBuf, // 源卷上的目录，如挂载点
+    #[serde(alias = "destinationDir")]
+    pub destination_dir: PathBuf, // 目标卷上的目录
+    #[serde(alias = "fileSize")]
+    pub file_size: u64, // MB，超过可用空间时自动减小
+    #[serde(alias = "bufferSize")]
+    pub buffer_size: usize, // KB
+}
+
+impl Default for CopyTestConfig {
+    fn default() -> Self {
+        Self {
+            source_dir: std::env::temp_dir(),
+            destination_dir: std::env::temp_dir(),
+            file_size: 4096, // 4GB
+            buffer_size: 1024, // 1MB
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CopyTestResult {
+    pub sustained_throughput: f64, // MB/s，整个复制过程，包括最后落盘
+    pub file_size: u64, // MB，实际复制的大小
+    pub size_clamped: bool, // 可用空间不足，文件小于配置的大小
+    pub buffer_size: usize, // KB
+    pub duration_seconds: f64, // 复制耗时，不含生成源文件
+    pub timeline: Vec<CopySample>,
+    pub source_throttled: bool, // 源卷的读取速度中途明显下降
+    pub destination_throttled: bool, // 目标卷的写入速度中途明显下降
+}
+
+/// 复制过程中一个采样区间的吞吐（MB/s）。读写分别只按各自的耗时计算
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CopySample {
+    pub elapsed_seconds: f64,
+    pub throughput: f64,
+    pub read_throughput: f64,
+    pub write_throughput: f64,
+}
+
+/// 后四分之一采样的中位数低于前四分之一中位数的THROTTLE_RATIO时视为降速
+pub fn detect_throttling(rates: &[f64]) -> bool {
+    if rates.len() < MIN_THROTTLE_SAMPLES {
+        return false;
+    }
+    let quarter = rates.len() / 4;
+    let median = |values: &[f64]| {
+        let mut values = values.to_vec();
+        values.sort_by(f64::total_cmp);
+        values[values.len() / 2]
+    };
+    let early = median(&rates[..quarter]);
+    let late = median(&rates[rates.len() - quarter..]);
+    early > 0.0 && late < early * THROTTLE_RATIO
+}
+
+/// 源和目标各需要一份文件，同一卷上需要两份；可用空间未知时不限制
+pub fn clamp_file_size(requested: u64, source_available: Option<u64>, destination_available: Option<u64>, same_volume: bool) -> u64 {
+    let limit = if same_volume {
+        source_available.map(|available| available / 2)
+    } else {
+        match (source_available, destination_available) {
+            (Some(source), Some(destination)) => Some(source.min(destination)),
+            (source, destination) => source.or(destination),
+        }
+    };
+    match limit {
+        Some(limit) => requested.min((limit as f64 * FREE_SPACE_RATIO) as u64),
+        None => requested,
+    }
+}
+
+/// 包含该路径且挂载点最长的磁盘：(挂载点, 可用字节数)
+fn volume_of(path: &Path, disks: &Disks) -> Option<(PathBuf, u64)> {
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| (disk.mount_point().to_path_buf(), disk.available_space()))
+}
+
+/// 用伪随机数填满缓冲区，每块的种子不同，数据既不可压缩也无法去重
+fn fill_incompressible(buffer: &mut [u8], seed: u64) {
+    let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+    for chunk in buffer.chunks_mut(8) {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let bytes = (state ^ (state >> 29)).to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+}
+
+/// 测试创建的文件，离开作用域时删除，覆盖失败、取消和panic
+struct TempFiles(Vec<PathBuf>);
+
+impl TempFiles {
+    fn create(&mut self, path: PathBuf) -> Result<File, BenchmarkError> {
+        self.0.push(path.clone());
+        OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)
+            .map_err(BenchmarkError::io("无法创建复制测试文件"))
+    }
+}
+
+impl Drop for TempFiles {
+    fn drop(&mut self) {
+        for path in &self.0 {
+            if path.is_file() {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+}
+
+/// 当前采样区间内读写的字节数和耗时
+#[derive(Default)]
+struct SampleWindow {
+    bytes: u64,
+    read_time: Duration,
+    write_time: Duration,
+}
+
+impl SampleWindow {
+    fn sample(&self, elapsed: Duration, window: Duration) -> CopySample {
+        let rate = |time: Duration| match time.as_secs_f64() {
+            seconds if seconds > 0.0 => self.bytes as f64 / BYTES_PER_MB as f64 / seconds,
+            _ => 0.0,
+        };
+        CopySample {
+            elapsed_seconds: elapsed.as_secs_f64(),
+            throughput: rate(window),
+            read_throughput: rate(self.read_time),
+            write_throughput: rate(self.write_time),
+        }
+    }
+}
+
+pub struct CopyBenchmark {
+    config: CopyTestConfig,
+}
+
+impl CopyBenchmark {
+    pub fn new(config: CopyTestConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn get_config(&self) -> &CopyTestConfig {
+        &self.config
+    }
+
+    pub fn run_benchmark(&self) -> Result<CopyTestResult, BenchmarkError> {
+        self.run_benchmark_cancellable(|_progress, _message| {}, &CancellationToken::never())
+    }
+
+    /// 生成源文件后复制到目标目录，每个缓冲区之间检查取消。返回前两个文件都已删除
+    pub fn run_benchmark_cancellable<F>(&self, progress_callback: F, cancellation: &CancellationToken) -> Result<CopyTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message),
+    {
+        let buffer_bytes = self.config.buffer_size as u64 * 1024;
+        if buffer_bytes == 0 || self.config.file_size == 0 {
+            return Err(BenchmarkError::StorageTestError("文件大小和缓冲区大小必须大于0".to_string()));
+        }
+        let source_dir = std::fs::canonicalize(&self.config.source_dir).map_err(BenchmarkError::io("无法访问源目录"))?;
+        let destination_dir =
+            std::fs::canonicalize(&self.config.destination_dir).map_err(BenchmarkError::io("无法访问目标目录"))?;
+
+        let disks = Disks::new_with_refreshed_list();
+        let source_volume = volume_of(&source_dir, &disks);
+        let destination_volume = volume_of(&destination_dir, &disks);
+        let same_volume = match (&source_volume, &destination_volume) {
+            (Some((source, _)), Some((destination, _))) => source == destination,
+            _ => source_dir == destination_dir,
+        };
+        let requested = self.config.file_size * BYTES_PER_MB;
+        let file_bytes = clamp_file_size(
+            requested,
+            source_volume.map(|(_, available)| available),
+            destination_volume.map(|(_, available)| available),
+            same_volume,
+        ) / buffer_bytes
+            * buffer_bytes;
+        if file_bytes == 0 {
+            return Err(BenchmarkError::StorageTestError("可用空间不足以进行复制测试".to_string()));
+        }
+
+        let mut files = TempFiles(Vec::new());
+        let name = format!("tauri_benchmark_copy_{}.dat", std::process::id());
+        let source_path = source_dir.join(format!("src_{}", name));
+        let destination_path = destination_dir.join(format!("dst_{}", name));
+        let mut buffer = vec![0u8; buffer_bytes as usize];
+
+        // 生成源文件
+        progress_callback(0.0, Message::new("copy.generate.start"));
+        let mut source = files.create(source_path.clone())?;
+        let mut last_progress_update = Instant::now();
+        for (index, offset) in (0..file_bytes).step_by(buffer_bytes as usize).enumerate() {
+            let progress = offset as f64 / file_bytes as f64 * GENERATE_PROGRESS;
+            cancellation.check(TestType::Storage, progress)?;
+            fill_incompressible(&mut buffer, index as u64);
+            source.write_all(&buffer).map_err(BenchmarkError::io("写入源文件失败"))?;
+            if last_progress_update.elapsed() >= SAMPLE_INTERVAL {
+                progress_callback(progress, Message::progress("copy.generate.progress", progress));
+                last_progress_update = Instant::now();
+            }
+        }
+        source.sync_all().map_err(BenchmarkError::io("同步失败"))?;
+        drop(source);
+
+        // 复制
+        progress_callback(GENERATE_PROGRESS, Message::new("copy.copy.start"));
+        let mut source = File::open(&source_path).map_err(BenchmarkError::io("无法打开源文件"))?;
+        let mut destination = files.create(destination_path)?;
+        let started = Instant::now();
+        let mut window_started = started;
+        let mut window = SampleWindow::default();
+        let mut timeline = Vec::new();
+        let mut copied = 0u64;
+        while copied < file_bytes {
+            let progress = GENERATE_PROGRESS + copied as f64 / file_bytes as f64 * (100.0 - GENERATE_PROGRESS);
+            cancellation.check(TestType::Storage, progress)?;
+
+            let read_started = Instant::now();
+            let read = source.read(&mut buffer).map_err(BenchmarkError::io("读取源文件失败"))?;
+            if read == 0 {
+                return Err(BenchmarkError::StorageTestError("源文件比预期短".to_string()));
+            }
+            window.read_time += read_started.elapsed();
+            let write_started = Instant::now();
+            destination.write_all(&buffer[..read]).map_err(BenchmarkError::io("写入目标文件失败"))?;
+            window.write_time += write_started.elapsed();
+            window.bytes += read as u64;
+            copied += read as u64;
+
+            if window_started.elapsed() >= SAMPLE_INTERVAL {
+                timeline.push(window.sample(started.elapsed(), window_started.elapsed()));
+                window = SampleWindow::default();
+                window_started = Instant::now();
+                progress_callback(progress, Message::progress("copy.copy.progress", progress));
+            }
+        }
+        let sync_started = Instant::now();
+        destination.sync_all().map_err(BenchmarkError::io("同步失败"))?;
+        window.write_time += sync_started.elapsed();
+        if window.bytes > 0 {
+            timeline.push(window.sample(started.elapsed(), window_started.elapsed()));
+        }
+        let duration = started.elapsed().as_secs_f64();
+
+        let rates = |select: fn(&CopySample) -> f64| timeline.iter().map(select).collect::<Vec<f64>>();
+        let result = CopyTestResult {
+            sustained_throughput: file_bytes as f64 / BYTES_PER_MB as f64 / duration,
+            file_size: file_bytes / BYTES_PER_MB,
+            size_clamped: file_bytes < requested,
+            buffer_size: self.config.buffer_size,
+            duration_seconds: duration,
+            source_throttled: detect_throttling(&rates(|sample| sample.read_throughput)),
+            destination_throttled: detect_throttling(&rates(|sample| sample.write_throughput)),
+            timeline,
+        };
+        progress_callback(100.0, Message::new("copy.complete"));
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    fn leftover_files(dir: &Path) -> Vec<PathBuf> {
+        std::fs::read_dir(dir).unwrap().map(|entry| entry.unwrap().path()).collect()
+    }
+
+    #[test]
+    fn test_copy_between_directories() {
+        let (source, destination) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        let benchmark = CopyBenchmark::new(CopyTestConfig {
+            source_dir: source.path().to_path_buf(),
+            destination_dir: destination.path().to_path_buf(),
+            file_size: 8,
+            buffer_size: 256,
+        });
+        let messages = std::sync::Mutex::new(Vec::new());
+        let result = benchmark
+            .run_benchmark_cancellable(|progress, message| messages.lock().unwrap().push((progress, message.key)), &CancellationToken::never())
+            .unwrap();
+
+        assert_eq!(result.file_size, 8);
+        assert!(!result.size_clamped);
+        assert!(result.sustained_throughput > 0.0);
+        assert!(result.duration_seconds > 0.0);
+        // 最后一个区间总会记录
+        assert!(!result.timeline.is_empty());
+        assert!(result.timeline.iter().all(|sample| sample.throughput >= 0.0 && sample.elapsed_seconds > 0.0));
+        let messages = messages.into_inner().unwrap();
+        assert_eq!(messages.first().unwrap().1, "copy.generate.start");
+        assert_eq!(messages.last().unwrap(), &(100.0, "copy.complete"));
+
+        assert!(leftover_files(source.path()).is_empty());
+        assert!(leftover_files(destination.path()).is_empty());
+    }
+
+    #[test]
+    fn test_cancelled_copy_removes_both_files() {
+        let (source, destination) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        let benchmark = CopyBenchmark::new(CopyTestConfig {
+            source_dir: source.path().to_path_buf(),
+            destination_dir: destination.path().to_path_buf(),
+            file_size: 8,
+            buffer_size: 64,
+        });
+        // 进入复制阶段后取消，此时两个文件都已存在
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+        let token = CancellationToken::new(move || flag.load(Ordering::SeqCst));
+        let error = benchmark
+            .run_benchmark_cancellable(
+                |_, message| {
+                    if message.key == "copy.copy.start" {
+                        cancelled.store(true, Ordering::SeqCst);
+                    }
+                },
+                &token,
+            )
+            .unwrap_err();
+        assert!(matches!(error, BenchmarkError::Cancelled { at_progress, .. } if at_progress == GENERATE_PROGRESS));
+        assert!(leftover_files(source.path()).is_empty());
+        assert!(leftover_files(destination.path()).is_empty());
+
+        // 目录不存在时直接失败
+        let missing = CopyBenchmark::new(CopyTestConfig {
+            source_dir: source.path().join("missing"),
+            ..benchmark.get_config().clone()
+        });
+        assert_eq!(missing.run_benchmark().unwrap_err().io_kind(), Some(std::io::ErrorKind::NotFound));
+    }
+
+    #[test]
+    fn test_file_size_is_clamped_to_free_space() {
+        const GB: u64 = 1024 * 1024 * 1024;
+        assert_eq!(clamp_file_size(4 * GB, Some(100 * GB), Some(100 * GB), false), 4 * GB);
+        // 较小的一侧决定上限，同一卷需要放下两份
+        assert_eq!(clamp_file_size(4 * GB, Some(100 * GB), Some(2 * GB), false), (2.0 * GB as f64 * 0.9) as u64);
+        assert_eq!(clamp_file_size(4 * GB, Some(6 * GB), None, true), (3.0 * GB as f64 * 0.9) as u64);
+        assert_eq!(clamp_file_size(4 * GB, None, Some(GB), false), (GB as f64 * 0.9) as u64);
+        assert_eq!(clamp_file_size(4 * GB, None, None, false), 4 * GB);
+    }
+
+    #[test]
+    fn test_throttling_detection() {
+        let steady = [500.0; 16];
+        assert!(!detect_throttling(&steady));
+        let mut throttled = vec![500.0; 8];
+        throttled.extend([200.0; 8]);
+        assert!(detect_throttling(&throttled));
+        // 采样太少时不判断
+        assert!(!detect_throttling(&[500.0, 500.0, 100.0, 100.0]));
+
+        let mut buffer = vec![0u8; 4096];
+        fill_incompressible(&mut buffer, 1);
+        let distinct: std::collections::HashSet<u8> = buffer.iter().copied().collect();
+        assert!(distinct.len() > 200);
+        let mut other = vec![0u8; 4096];
+        fill_incompressible(&mut other, 2);
+        assert_ne!(buffer, other);
+    }
+}
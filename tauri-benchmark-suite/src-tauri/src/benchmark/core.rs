@@ -1,73 +1,1601 @@
+use crate::app_info::{AppInfo, RunIdentity, RunMode};
+use crate::benchmark::aggregation::{aggregate_runs, Aggregation, RunSummary};
+use crate::benchmark::calibration::{plan_auto_duration, Calibration};
+use crate::benchmark::comparison::RegressionThresholds;
+use crate::benchmark::compression::{self, CompressionBenchmark};
+use crate::benchmark::cpu::{self, CpuBenchmark, CpuTestConfig};
+use crate::benchmark::crypto::{self, CryptoBenchmark};
+use crate::benchmark::database::{self, DatabaseBenchmark};
 use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::estimate::TestEstimate;
+use crate::benchmark::gpu::{GpuBenchmark, GpuTestConfig};
+use crate::benchmark::hashing::{self, HashingBenchmark};
+use crate::benchmark::image::{self, ImageBenchmark};
+use crate::benchmark::ipc_overhead::{self, IpcBenchmark};
+use crate::benchmark::memory::{self, MemoryBenchmark, MemoryTestConfig};
+use crate::benchmark::metrics::MetricsSink;
+use crate::benchmark::network::{self, NetworkBenchmark, NetworkTestConfig};
+use crate::benchmark::process::{self, ProcessBenchmark};
+use crate::benchmark::progress::SuiteProgressTracker;
+use crate::benchmark::results::{HeadlineMetrics, ResultComparison};
+use crate::benchmark::schema::RESULT_SCHEMA_VERSION;
+use crate::benchmark::scoring::{self, calculate_overall_score};
+use crate::benchmark::serde_json_bench::{self, JsonBenchmark};
+use crate::benchmark::stability::{self, StabilityBenchmark};
+use crate::benchmark::storage::{self, StorageBenchmark, StorageTestConfig};
+use crate::benchmark::sync::{self, SyncBenchmark};
+use crate::benchmark::system_info::SystemInfo;
+use crate::benchmark::webview::{WebviewBenchmark, WebviewBridge, WebviewTestConfig};
+use crate::i18n::{Locale, Message};
+use crate::ipc::{EventSink, ExtraTestResult, SuiteEvent, TestCompleteEvent, TestCompletion, TestResultPayload, TestType, TestWarningEvent, WarningRecord, WarningSeverity};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// 配置文件中省略的字段取标准预设的值；别名兼容前端发送的驼峰字段名
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
 pub struct BenchmarkConfig {
-    pub cpu_test: CpuTestConfig,
-    pub memory_test: MemoryTestConfig,
-    pub storage_test: StorageTestConfig,
+    #[serde(alias = "cpuTest")]
+    pub cpu_test: Toggle<CpuTestConfig>,
+    #[serde(alias = "memoryTest")]
+    pub memory_test: Toggle<MemoryTestConfig>,
+    #[serde(alias = "storageTest")]
+    pub storage_test: Toggle<StorageTestConfig>,
+    #[serde(alias = "gpuTest")]
+    pub gpu_test: Toggle<GpuTestConfig>, // 可选的GPU计算测试，默认不启用
+    #[serde(alias = "webviewTest")]
+    pub webview_test: Toggle<WebviewTestConfig>, // 可选的WebView/JS引擎测试，需要前端运行负载，默认不启用
+    #[serde(alias = "networkTest")]
+    pub network_test: Toggle<NetworkTestConfig>, // 可选的网络吞吐和延迟测试，默认不启用
+    #[serde(default, alias = "autoDurationTargetSeconds")]
+    pub auto_duration_target_seconds: Option<u64>, // 设置后自动推导各测试参数，使整个套件接近该时长
+    #[serde(default, alias = "skipCalibration")]
+    pub skip_calibration: bool, // 自动时长模式下跳过校准，使用内置估计
+    pub repeat: usize, // 整个套件的运行次数，多于1次时按aggregation合并结果
+    pub aggregation: Aggregation,
+    #[serde(alias = "extraTests")]
+    pub extra_tests: Vec<ExtraTestSpec>, // 通过BenchmarkRegistry注册的扩展测试，在内置测试之后运行
+    #[serde(alias = "skipRegressionCheck")]
+    pub skip_regression_check: bool, // 套件结束后不与本机上一次的结果对比
+    #[serde(alias = "regressionThresholds")]
+    pub regression_thresholds: Option<RegressionThresholds>, // 自动回归检测的阈值，为空时使用默认阈值
+    #[serde(alias = "perTestTimeoutFactor")]
+    pub per_test_timeout_factor: f64, // 单项测试的时限为其估计耗时的倍数，超时的测试记为失败，套件继续
+    #[serde(alias = "monitoringIntervalMs")]
+    pub monitoring_interval_ms: u64, // 套件运行期间发送system-monitoring事件的间隔
+    #[serde(alias = "scoreWeights", skip_serializing_if = "BTreeMap::is_empty")]
+    pub score_weights: BTreeMap<String, f64>, // 各部分（cpu/memory/storage/gpu/webview/network）在总分中的权重，省略的部分为1
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CpuTestConfig {
-    pub enabled: bool,
-    pub duration: u64, // seconds
-    pub thread_count: usize,
+/// 一项扩展测试：注册名加上交给其工厂函数的配置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ExtraTestSpec {
+    pub name: String,
+    #[serde(default = "empty_extra_config")]
+    pub config: serde_json::Value,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MemoryTestConfig {
-    pub enabled: bool,
-    pub buffer_size: usize, // MB
-    pub iterations: usize,
+// 省略config时为空表，TOML无法表示null
+fn empty_extra_config() -> serde_json::Value {
+    serde_json::Value::Object(serde_json::Map::new())
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct StorageTestConfig {
+/// 套件中的一项测试：测试模块自身的配置加上是否启用。
+/// 序列化时enabled与模块配置的字段平铺在同一层，如 {"enabled": true, "thread_count": 0, ...}
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct Toggle<T> {
     pub enabled: bool,
-    pub file_size: u64,    // MB
-    pub block_size: usize, // KB
+    #[serde(flatten)]
+    pub config: T,
+}
+
+impl<T> Toggle<T> {
+    pub fn new(enabled: bool, config: T) -> Self {
+        Self { enabled, config }
+    }
+}
+
+impl<T: Default> Default for Toggle<T> {
+    fn default() -> Self {
+        Self::new(true, T::default())
+    }
+}
+
+impl<T> std::ops::Deref for Toggle<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.config
+    }
+}
+
+impl<T> std::ops::DerefMut for Toggle<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.config
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// 不使用#[serde(flatten)]反序列化：flatten会先缓冲整个对象，出错时丢失字段路径和行号，
+// 未知字段也不再报告。这里把enabled取出，其余字段原样交给模块配置
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Toggle<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(ToggleVisitor(std::marker::PhantomData))
+    }
+}
+
+struct ToggleVisitor<T>(std::marker::PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> serde::de::Visitor<'de> for ToggleVisitor<T> {
+    type Value = Toggle<T>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("a test config table")
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        let mut enabled = None;
+        let config = T::deserialize(serde::de::value::MapAccessDeserializer::new(WithoutEnabled {
+            map,
+            enabled: &mut enabled,
+        }))?;
+        Ok(Toggle::new(enabled.unwrap_or(true), config))
+    }
+}
+
+/// 跳过enabled字段的MapAccess，读到的值写入enabled
+struct WithoutEnabled<'a, A> {
+    map: A,
+    enabled: &'a mut Option<bool>,
+}
+
+impl<'de, A: serde::de::MapAccess<'de>> serde::de::MapAccess<'de> for WithoutEnabled<'_, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: serde::de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error> {
+        use serde::de::IntoDeserializer;
+        while let Some(key) = self.map.next_key::<String>()? {
+            if key == "enabled" {
+                *self.enabled = Some(self.map.next_value()?);
+            } else {
+                return seed.deserialize(key.into_deserializer()).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<V: serde::de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, A::Error> {
+        self.map.next_value_seed(seed)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TestResult {
+    #[serde(default)]
+    pub schema_version: u32,
     pub timestamp: String,
     pub system_info: crate::benchmark::system_info::SystemInfo,
     pub cpu_results: Option<crate::benchmark::cpu::CpuTestResult>,
     pub memory_results: Option<crate::benchmark::memory::MemoryTestResult>,
     pub storage_results: Option<crate::benchmark::storage::StorageTestResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_results: Option<crate::benchmark::gpu::GpuTestResult>, // 未启用GPU测试或本机没有适配器时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webview_results: Option<crate::benchmark::webview::WebviewTestResult>, // 未启用WebView测试时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_results: Option<crate::benchmark::network::NetworkTestResult>, // 未启用网络测试时为空
     pub overall_score: f64,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub category_scores: BTreeMap<String, f64>, // 各部分按参考基准归一化后的得分，overall_score为其加权平均
+    #[serde(default)]
+    pub imported: bool, // 从外部文件导入，而非本机运行产生
+    #[serde(default)]
+    pub auto_duration: Option<crate::benchmark::calibration::AutoDurationPlan>, // 自动时长模式推导出的参数
+    #[serde(default)]
+    pub app_info: Option<crate::app_info::AppInfo>, // 产生该结果的应用和引擎版本，旧结果为空
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failure_log: Vec<crate::session::LogEntry>, // 有测试失败时附带的会话日志（截断）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<crate::ipc::WarningRecord>, // 运行期间的警告，包括被过滤未实时发送的
+    #[serde(default)]
+    pub config_used: Option<BenchmarkConfig>, // 实际生效的配置（自动时长推导后），旧结果为空
+    #[serde(default)]
+    pub per_test_durations: BTreeMap<TestType, f64>, // 各项测试的实际耗时（秒），包括失败的测试；扩展测试合计在extra下
+    #[serde(default)]
+    pub session_id: Option<String>, // 产生该结果的会话，非套件运行（如BenchmarkCore）为空
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub runs: Vec<RunSummary>, // 重复运行时每次运行的关键指标，单次运行为空
+    #[serde(default)]
+    pub partial: bool, // 重复运行被中途取消，结果只汇总了已完成的运行
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra_results: BTreeMap<String, serde_json::Value>, // 扩展测试的结果，键为注册名
+    #[serde(default)]
+    pub regression_check: Option<ResultComparison>, // 与本机上一次结果的自动对比，未对比时为空
+    #[serde(default)]
+    pub integrity: Option<crate::benchmark::integrity::ResultIntegrity>, // 保存时计算的内容哈希，读取和导入时重新校验
+    #[serde(default)]
+    pub identity: Option<crate::app_info::RunIdentity>, // 运行标识（run_id、机器指纹、版本和系统），旧结果为空
+}
+
+/// 标准预设
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            cpu_test: Toggle::default(),
+            memory_test: Toggle::default(),
+            storage_test: Toggle::default(),
+            gpu_test: Toggle::new(false, GpuTestConfig::default()),
+            webview_test: Toggle::new(false, WebviewTestConfig::default()),
+            network_test: Toggle::new(false, NetworkTestConfig::default()),
+            auto_duration_target_seconds: None,
+            skip_calibration: false,
+            repeat: 1,
+            aggregation: Aggregation::default(),
+            extra_tests: Vec::new(),
+            skip_regression_check: false,
+            regression_thresholds: None,
+            per_test_timeout_factor: DEFAULT_TIMEOUT_FACTOR,
+            monitoring_interval_ms: DEFAULT_MONITORING_INTERVAL_MS,
+            score_weights: BTreeMap::new(),
+        }
+    }
+}
+
+/// 配置文件格式，按扩展名区分
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+impl ConfigFormat {
+    pub fn from_path(path: &Path) -> Result<Self, BenchmarkError> {
+        match path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_ascii_lowercase()).as_deref() {
+            Some("toml") => Ok(ConfigFormat::Toml),
+            Some("json") => Ok(ConfigFormat::Json),
+            _ => Err(BenchmarkError::DataLoadError(format!(
+                "不支持的配置文件格式: {}（仅支持.toml和.json）",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// 从文件读取的配置，unknown_keys为被忽略的未知字段（如拼写错误），不影响加载
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadedConfig {
+    pub config: BenchmarkConfig,
+    pub unknown_keys: Vec<String>,
+}
+
+/// 配置问题的严重程度，只有Error会让validate失败
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+}
+
+/// 配置中的一个问题，field为点分隔的字段路径，如"storage_test.block_size"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigIssue {
+    pub field: String,
+    pub severity: IssueSeverity,
+    pub message: String,
+    pub suggested_value: Option<serde_json::Value>, // 可以直接填回该字段的建议值
+}
+
+impl ConfigIssue {
+    fn error(field: &str, message: String, suggested_value: impl Into<serde_json::Value>) -> Self {
+        Self {
+            field: field.to_string(),
+            severity: IssueSeverity::Error,
+            message,
+            suggested_value: Some(suggested_value.into()),
+        }
+    }
+
+    fn warning(field: &str, message: String, suggested_value: impl Into<serde_json::Value>) -> Self {
+        Self {
+            severity: IssueSeverity::Warning,
+            ..Self::error(field, message, suggested_value)
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// 把多个问题合并为一行，用于错误消息
+pub fn describe_issues(issues: &[ConfigIssue]) -> String {
+    issues.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+}
+
+/// 网络测试配置的问题，套件和单独运行的网络测试共用同一组上限
+pub fn network_issues(network: &NetworkTestConfig) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    if let Some(Err(message)) = network.target.as_deref().map(network::check_target) {
+        // 清空目标即改用本机回环服务器
+        issues.push(ConfigIssue::error("network_test.target", message, serde_json::Value::Null));
+    }
+    if network.test_duration == 0 {
+        issues.push(ConfigIssue::error("network_test.test_duration", "测试时长必须大于0秒".to_string(), 10));
+    } else if network.test_duration > MAX_NETWORK_DURATION_SECONDS {
+        issues.push(ConfigIssue::error(
+            "network_test.test_duration",
+            format!("测试时长不能超过{}秒", MAX_NETWORK_DURATION_SECONDS),
+            MAX_NETWORK_DURATION_SECONDS,
+        ));
+    }
+    if network.parallel_connections == 0 || network.parallel_connections > MAX_NETWORK_CONNECTIONS {
+        issues.push(ConfigIssue::error(
+            "network_test.parallel_connections",
+            format!("并发连接数必须在1到{}之间", MAX_NETWORK_CONNECTIONS),
+            4,
+        ));
+    }
+    if network.latency_samples == 0 {
+        issues.push(ConfigIssue::error("network_test.latency_samples", "延迟请求次数必须大于0".to_string(), 20));
+    }
+    issues
+}
+
+/// 按本机硬件推导默认配置时的上限和比例
+const SYSTEM_MEMORY_BUFFER_RATIO: u64 = 4; // 内存缓冲区约为总内存的1/4
+const SYSTEM_STORAGE_FILE_RATIO: u64 = 10; // 测试文件约为目标磁盘剩余空间的1/10
+const MAX_SYSTEM_STORAGE_FILE_MB: u64 = 4 * 1024;
+
+const MAX_CPU_DURATION_SECONDS: u64 = 3600;
+const MAX_GPU_DURATION_SECONDS: u64 = 600;
+const MAX_NETWORK_DURATION_SECONDS: u64 = 600;
+pub const MAX_NETWORK_CONNECTIONS: usize = 64; // 回环测试服务器按此数量开处理线程
+const MAX_THREAD_COUNT: usize = 1024;
+const MAX_BLOCK_SIZE_KB: usize = 64 * 1024;
+const MAX_SUGGESTED_REPEAT: usize = 20;
+const DEFAULT_TIMEOUT_FACTOR: f64 = 3.0;
+const MIN_TIMEOUT_BASE_SECONDS: u64 = 10; // 短测试的估计不含固定开销，计算时限时至少按10秒计
+const DEFAULT_MONITORING_INTERVAL_MS: u64 = 1000;
+const MIN_MONITORING_INTERVAL_MS: u64 = 200; // sysinfo刷新CPU占用的最小间隔，更短的间隔只会重复上一次的读数
+
+/// 逐项设置BenchmarkConfig，未设置的字段取标准预设，build时校验
+#[derive(Debug, Clone, Default)]
+pub struct BenchmarkConfigBuilder {
+    config: BenchmarkConfig,
+}
+
+impl BenchmarkConfigBuilder {
+    pub fn cpu_duration(mut self, seconds: u64) -> Self {
+        self.config.cpu_test.test_duration = seconds;
+        self
+    }
+
+    /// 0表示使用全部线程
+    pub fn cpu_threads(mut self, threads: usize) -> Self {
+        self.config.cpu_test.thread_count = threads;
+        self
+    }
+
+    pub fn memory_buffer_mb(mut self, buffer_size: usize) -> Self {
+        self.config.memory_test.buffer_size = buffer_size;
+        self
+    }
+
+    pub fn memory_iterations(mut self, iterations: usize) -> Self {
+        self.config.memory_test.iterations = iterations;
+        self
+    }
+
+    pub fn storage_file_mb(mut self, file_size: u64) -> Self {
+        self.config.storage_test.file_size = file_size;
+        self
+    }
+
+    pub fn storage_block_kb(mut self, block_size: usize) -> Self {
+        self.config.storage_test.block_size = block_size;
+        self
+    }
+
+    pub fn disable_cpu(mut self) -> Self {
+        self.config.cpu_test.enabled = false;
+        self
+    }
+
+    pub fn disable_memory(mut self) -> Self {
+        self.config.memory_test.enabled = false;
+        self
+    }
+
+    pub fn disable_storage(mut self) -> Self {
+        self.config.storage_test.enabled = false;
+        self
+    }
+
+    /// 启用GPU测试，每项负载运行seconds秒
+    pub fn enable_gpu(mut self, seconds: u64) -> Self {
+        self.config.gpu_test.enabled = true;
+        self.config.gpu_test.test_duration = seconds;
+        self
+    }
+
+    /// 启用网络测试，下载和上传各运行seconds秒；target为空时使用本机回环服务器
+    pub fn enable_network(mut self, target: Option<String>, seconds: u64) -> Self {
+        self.config.network_test.enabled = true;
+        self.config.network_test.target = target;
+        self.config.network_test.test_duration = seconds;
+        self
+    }
+
+    /// 在内置测试之后运行进程启动测试，计时iterations次启动
+    pub fn enable_process(mut self, iterations: usize) -> Self {
+        self.config.extra_tests.push(ExtraTestSpec {
+            name: process::PROCESS_TEST_NAME.to_string(),
+            config: serde_json::json!({ "iterations": iterations }),
+        });
+        self
+    }
+
+    pub fn auto_duration(mut self, target_seconds: u64) -> Self {
+        self.config.auto_duration_target_seconds = Some(target_seconds);
+        self
+    }
+
+    pub fn skip_calibration(mut self, skip: bool) -> Self {
+        self.config.skip_calibration = skip;
+        self
+    }
+
+    pub fn skip_regression_check(mut self, skip: bool) -> Self {
+        self.config.skip_regression_check = skip;
+        self
+    }
+
+    pub fn per_test_timeout_factor(mut self, factor: f64) -> Self {
+        self.config.per_test_timeout_factor = factor;
+        self
+    }
+
+    pub fn monitoring_interval_ms(mut self, interval_ms: u64) -> Self {
+        self.config.monitoring_interval_ms = interval_ms;
+        self
+    }
+
+    pub fn score_weight(mut self, category: &str, weight: f64) -> Self {
+        self.config.score_weights.insert(category.to_string(), weight);
+        self
+    }
+
+    pub fn repeat(mut self, runs: usize, aggregation: Aggregation) -> Self {
+        self.config.repeat = runs;
+        self.config.aggregation = aggregation;
+        self
+    }
+
+    /// 不依赖本机硬件校验，存在错误时返回全部问题
+    pub fn build(self) -> Result<BenchmarkConfig, Vec<ConfigIssue>> {
+        self.config.validate(None)?;
+        Ok(self.config)
+    }
+}
+
+/// 1起始的行号
+fn line_of(contents: &str, offset: usize) -> usize {
+    contents[..offset.min(contents.len())].matches('\n').count() + 1
+}
+
+impl BenchmarkConfig {
+    pub fn builder() -> BenchmarkConfigBuilder {
+        BenchmarkConfigBuilder::default()
+    }
+
+    /// 按本机硬件推导的默认配置：内存缓冲区约为总内存的25%，测试文件取4GB和
+    /// 默认测试路径所在磁盘剩余空间10%中的较小者，线程数为物理核心数。
+    /// 无法得知的硬件信息保留标准预设
+    pub fn default_for_system(system: &SystemInfo) -> Self {
+        Self::default_for_system_at(system, &storage::default_test_file_path())
+    }
+
+    fn default_for_system_at(system: &SystemInfo, target: &Path) -> Self {
+        let mut config = Self::default();
+        config.cpu_test.thread_count = system.cpu.cores;
+        if system.memory.total > 0 {
+            config.memory_test.buffer_size = (system.memory.total * 1024 / SYSTEM_MEMORY_BUFFER_RATIO) as usize;
+        }
+        // 选择挂载点最长且包含测试文件路径的磁盘
+        let target_disk = system
+            .storage
+            .iter()
+            .filter(|disk| target.starts_with(&disk.mount_point))
+            .max_by_key(|disk| disk.mount_point.len());
+        if let Some(disk) = target_disk {
+            config.storage_test.file_size = (disk.available * 1024 / SYSTEM_STORAGE_FILE_RATIO)
+                .clamp(1, MAX_SYSTEM_STORAGE_FILE_MB);
+        }
+        config
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, BenchmarkError> {
+        Self::load_file(path).map(|loaded| loaded.config)
+    }
+
+    /// 读取配置文件，同时返回被忽略的未知字段
+    pub fn load_file(path: impl AsRef<Path>) -> Result<LoadedConfig, BenchmarkError> {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path)?;
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| BenchmarkError::DataLoadError(format!("无法读取配置文件 {}: {}", path.display(), e)))?;
+        Self::parse(&contents, format)
+            .map_err(|e| BenchmarkError::DataLoadError(format!("配置文件 {} {}", path.display(), e)))
+    }
+
+    /// 解析配置内容，错误信息包含出错的字段路径和行号
+    pub fn parse(contents: &str, format: ConfigFormat) -> Result<LoadedConfig, String> {
+        let mut unknown_keys = Vec::new();
+        let mut record_unknown = |key: serde_ignored::Path| unknown_keys.push(key.to_string());
+        let config = match format {
+            ConfigFormat::Toml => {
+                let deserializer = toml::Deserializer::new(contents);
+                let ignored = serde_ignored::Deserializer::new(deserializer, &mut record_unknown);
+                serde_path_to_error::deserialize(ignored).map_err(|e| {
+                    let line = e.inner().span().map(|span| line_of(contents, span.start));
+                    let message = e.inner().message().to_string();
+                    (e.path().to_string(), line, message)
+                })
+            }
+            ConfigFormat::Json => {
+                let mut deserializer = serde_json::Deserializer::from_str(contents);
+                let ignored = serde_ignored::Deserializer::new(&mut deserializer, &mut record_unknown);
+                serde_path_to_error::deserialize(ignored)
+                    .map_err(|e| (e.path().to_string(), Some(e.inner().line()), e.inner().to_string()))
+                    .and_then(|config| {
+                        deserializer
+                            .end()
+                            .map(|_| config)
+                            .map_err(|e| (".".to_string(), Some(e.line()), e.to_string()))
+                    })
+            }
+        }
+        .map_err(|(key, line, message)| {
+            let line = line.map(|line| format!("第{}行", line)).unwrap_or_default();
+            // 语法错误发生在任何字段之前时，路径为"."
+            if key == "." {
+                format!("{}解析失败: {}", line, message)
+            } else {
+                format!("{}字段 {} 解析失败: {}", line, key, message)
+            }
+        })?;
+        Ok(LoadedConfig { config, unknown_keys })
+    }
+
+    /// 按扩展名保存为TOML或JSON
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), BenchmarkError> {
+        let path = path.as_ref();
+        let contents = match ConfigFormat::from_path(path).map_err(|e| BenchmarkError::DataSaveError(e.to_string()))? {
+            ConfigFormat::Toml => toml::to_string_pretty(self).map_err(|e| e.to_string()),
+            ConfigFormat::Json => serde_json::to_string_pretty(self).map_err(|e| e.to_string()),
+        }
+        .map_err(|e| BenchmarkError::DataSaveError(format!("配置序列化失败: {}", e)))?;
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| BenchmarkError::DataSaveError(format!("无法创建配置目录: {}", e)))?;
+        }
+        std::fs::write(path, contents)
+            .map_err(|e| BenchmarkError::DataSaveError(format!("配置文件 {} 保存失败: {}", path.display(), e)))
+    }
+
+    /// 检查配置，存在Error级别的问题时返回全部问题（包括警告）。
+    /// 未启用的测试不检查；提供系统信息时额外检查线程数和内存缓冲区是否超出本机能力
+    pub fn validate(&self, system: Option<&SystemInfo>) -> Result<(), Vec<ConfigIssue>> {
+        let issues = self.issues(system);
+        if issues.iter().any(|issue| issue.severity == IssueSeverity::Error) {
+            Err(issues)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// 运行器的时限：估计耗时（至少10秒）乘以per_test_timeout_factor
+    pub fn test_timeout(&self, runner: &dyn BenchmarkRunner) -> Duration {
+        let base = runner.estimated_duration().max(MIN_TIMEOUT_BASE_SECONDS);
+        Duration::from_secs_f64(base as f64 * self.per_test_timeout_factor)
+    }
+
+    /// 列出配置中的全部问题
+    pub fn issues(&self, system: Option<&SystemInfo>) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        if self.auto_duration_target_seconds == Some(0) {
+            issues.push(ConfigIssue::error("auto_duration_target_seconds", "目标时长必须大于0秒".to_string(), 60));
+        }
+        if self.repeat == 0 {
+            issues.push(ConfigIssue::error("repeat", "运行次数必须大于0".to_string(), 1));
+        } else if self.repeat > MAX_SUGGESTED_REPEAT {
+            issues.push(ConfigIssue::warning(
+                "repeat",
+                format!("运行{}次耗时很长，超过{}次对结果稳定性帮助不大", self.repeat, MAX_SUGGESTED_REPEAT),
+                MAX_SUGGESTED_REPEAT,
+            ));
+        }
+        let factor = self.per_test_timeout_factor;
+        if !(factor.is_finite() && factor > 0.0) {
+            issues.push(ConfigIssue::error("per_test_timeout_factor", "超时倍数必须大于0".to_string(), DEFAULT_TIMEOUT_FACTOR));
+        } else if factor < 1.0 {
+            issues.push(ConfigIssue::warning(
+                "per_test_timeout_factor",
+                format!("超时倍数{}小于1，测试很可能在正常完成前超时", factor),
+                DEFAULT_TIMEOUT_FACTOR,
+            ));
+        }
+        if self.monitoring_interval_ms == 0 {
+            issues.push(ConfigIssue::error(
+                "monitoring_interval_ms",
+                "监控间隔必须大于0毫秒".to_string(),
+                DEFAULT_MONITORING_INTERVAL_MS,
+            ));
+        } else if self.monitoring_interval_ms < MIN_MONITORING_INTERVAL_MS {
+            issues.push(ConfigIssue::warning(
+                "monitoring_interval_ms",
+                format!("监控间隔{}毫秒小于CPU占用的最小刷新间隔{}毫秒，相邻采样的CPU占用会相同", self.monitoring_interval_ms, MIN_MONITORING_INTERVAL_MS),
+                MIN_MONITORING_INTERVAL_MS,
+            ));
+        }
+        for (category, weight) in &self.score_weights {
+            let field = format!("score_weights.{}", category);
+            if !scoring::CATEGORIES.contains(&category.as_str()) {
+                // 建议删除该项
+                issues.push(ConfigIssue::error(
+                    &field,
+                    format!("未知的评分部分{}，可用的部分: {}", category, scoring::CATEGORIES.join(", ")),
+                    serde_json::Value::Null,
+                ));
+            } else if !(weight.is_finite() && *weight >= 0.0) {
+                issues.push(ConfigIssue::error(&field, format!("权重{}必须是不小于0的数", weight), scoring::DEFAULT_WEIGHT));
+            }
+        }
+        if let Some(Err(message)) = self.regression_thresholds.as_ref().map(RegressionThresholds::validate) {
+            issues.push(ConfigIssue {
+                field: "regression_thresholds".to_string(),
+                severity: IssueSeverity::Error,
+                message,
+                suggested_value: None,
+            });
+        }
+
+        let cpu = &self.cpu_test;
+        if cpu.enabled {
+            if cpu.test_duration == 0 {
+                issues.push(ConfigIssue::error("cpu_test.test_duration", "测试时长必须大于0秒".to_string(), 10));
+            } else if cpu.test_duration > MAX_CPU_DURATION_SECONDS {
+                issues.push(ConfigIssue::error(
+                    "cpu_test.test_duration",
+                    format!("测试时长不能超过{}秒", MAX_CPU_DURATION_SECONDS),
+                    MAX_CPU_DURATION_SECONDS,
+                ));
+            }
+            // thread_count为0表示使用全部线程
+            if cpu.thread_count > MAX_THREAD_COUNT {
+                issues.push(ConfigIssue::error(
+                    "cpu_test.thread_count",
+                    format!("线程数不能超过{}", MAX_THREAD_COUNT),
+                    0,
+                ));
+            } else if let Some(system) = system.filter(|system| system.cpu.threads > 0 && cpu.thread_count > system.cpu.threads) {
+                issues.push(ConfigIssue::warning(
+                    "cpu_test.thread_count",
+                    format!("线程数{}超过本机逻辑CPU数{}", cpu.thread_count, system.cpu.threads),
+                    system.cpu.threads,
+                ));
+            }
+        }
+
+        let memory = &self.memory_test;
+        if memory.enabled {
+            if memory.buffer_size == 0 {
+                issues.push(ConfigIssue::error("memory_test.buffer_size", "缓冲区大小必须大于0MB".to_string(), 64));
+            } else if let Some(system) = system.filter(|system| system.memory.total > 0) {
+                let total_mb = system.memory.total as usize * 1024;
+                let available_mb = system.memory.available as usize * 1024;
+                if memory.buffer_size >= total_mb {
+                    issues.push(ConfigIssue::error(
+                        "memory_test.buffer_size",
+                        format!("缓冲区{}MB不小于本机内存{}MB", memory.buffer_size, total_mb),
+                        total_mb / 4,
+                    ));
+                } else if memory.buffer_size > available_mb {
+                    issues.push(ConfigIssue::warning(
+                        "memory_test.buffer_size",
+                        format!("缓冲区{}MB超过当前可用内存{}MB", memory.buffer_size, available_mb),
+                        available_mb / 2,
+                    ));
+                }
+            }
+            if memory.iterations == 0 {
+                issues.push(ConfigIssue::error("memory_test.iterations", "迭代次数必须大于0".to_string(), 1));
+            }
+        }
+
+        let storage = &self.storage_test;
+        if storage.enabled {
+            if storage.file_size == 0 {
+                issues.push(ConfigIssue::error("storage_test.file_size", "测试文件大小必须大于0MB".to_string(), 32));
+            }
+            if storage.block_size == 0 {
+                issues.push(ConfigIssue::error("storage_test.block_size", "块大小必须大于0KB".to_string(), 4));
+            } else if storage.block_size > MAX_BLOCK_SIZE_KB {
+                issues.push(ConfigIssue::error(
+                    "storage_test.block_size",
+                    format!("块大小不能超过{}KB", MAX_BLOCK_SIZE_KB),
+                    MAX_BLOCK_SIZE_KB,
+                ));
+            } else if storage.file_size > 0 && storage.block_size as u64 > storage.file_size * 1024 {
+                issues.push(ConfigIssue::error(
+                    "storage_test.block_size",
+                    format!("块大小{}KB大于测试文件{}MB", storage.block_size, storage.file_size),
+                    4,
+                ));
+            }
+        }
+
+        let gpu = &self.gpu_test;
+        if gpu.enabled {
+            if gpu.test_duration == 0 {
+                issues.push(ConfigIssue::error("gpu_test.test_duration", "测试时长必须大于0秒".to_string(), 10));
+            } else if gpu.test_duration > MAX_GPU_DURATION_SECONDS {
+                issues.push(ConfigIssue::error(
+                    "gpu_test.test_duration",
+                    format!("测试时长不能超过{}秒", MAX_GPU_DURATION_SECONDS),
+                    MAX_GPU_DURATION_SECONDS,
+                ));
+            }
+            if gpu.workloads.is_empty() {
+                issues.push(ConfigIssue::error(
+                    "gpu_test.workloads",
+                    "至少需要一项负载".to_string(),
+                    serde_json::json!(["compute", "bandwidth"]),
+                ));
+            }
+        }
+
+        let webview = &self.webview_test;
+        if webview.enabled {
+            if webview.workloads.is_empty() {
+                issues.push(ConfigIssue::error(
+                    "webview_test.workloads",
+                    "至少需要一项负载".to_string(),
+                    serde_json::json!(["dom_nodes", "canvas_fill", "json_stringify", "wasm_kernel"]),
+                ));
+            }
+            if webview.iterations == 0 {
+                issues.push(ConfigIssue::error("webview_test.iterations", "重复次数必须大于0".to_string(), 5));
+            }
+            if webview.timeout_seconds == 0 {
+                issues.push(ConfigIssue::error("webview_test.timeout_seconds", "超时时间必须大于0秒".to_string(), 60));
+            }
+        }
+
+        if self.network_test.enabled {
+            issues.extend(network_issues(&self.network_test));
+        }
+        issues
+    }
+
+    /// 快速预设：几秒内跑完全部测试，用于冒烟检查和估算对照
+    pub fn quick() -> Self {
+        Self {
+            cpu_test: Toggle::new(true, CpuTestConfig {
+                test_duration: 1,
+                ..CpuTestConfig::default()
+            }),
+            memory_test: Toggle::new(true, MemoryTestConfig {
+                buffer_size: 64,
+                iterations: 1,
+                interference_duration: 1,
+                ..MemoryTestConfig::default()
+            }),
+            storage_test: Toggle::new(true, StorageTestConfig {
+                file_size: 32,
+                small_file_count: 1_000,
+                ..StorageTestConfig::default()
+            }),
+            ..Self::default()
+        }
+    }
+
+    /// 加长预设：各项测试时长加倍并重复三次取汇总，用于需要稳定数据的正式测量
+    pub fn extended() -> Self {
+        Self {
+            cpu_test: Toggle::new(true, CpuTestConfig {
+                test_duration: 120,
+                ..CpuTestConfig::default()
+            }),
+            memory_test: Toggle::new(true, MemoryTestConfig {
+                test_duration: 60,
+                ..MemoryTestConfig::default()
+            }),
+            storage_test: Toggle::new(true, StorageTestConfig {
+                test_duration: 120,
+                small_file_count: 20_000,
+                ..StorageTestConfig::default()
+            }),
+            repeat: 3,
+            ..Self::default()
+        }
+    }
+}
+
+/// 单项测试的运行结果，配置中未启用或运行器发现会话已取消而提前停止的测试为Skipped，
+/// 本机硬件不支持的测试为Unavailable
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestOutcome<T> {
+    Completed(T),
+    Skipped,
+    Unavailable, // 本机不具备运行条件（如没有GPU适配器），已记录警告，不计为失败
+}
+
+impl<T> TestOutcome<T> {
+    pub fn is_skipped(&self) -> bool {
+        matches!(self, TestOutcome::Skipped)
+    }
+}
+
+/// 接收单项测试的进度（0-100）和消息
+pub type ProgressSink = Arc<dyn Fn(f64, Message) + Send + Sync>;
+/// 接收测试过程中不中止测试的问题及其严重程度
+pub type WarningSink = Arc<dyn Fn(Message, WarningSeverity) + Send + Sync>;
+
+/// 运行器上报的一个不中止测试的问题，如传感器中途消失、大页分配回退
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Issue {
+    pub code: String,    // 消息键
+    pub message: String, // 按后端语言渲染的文本
+    pub severity: WarningSeverity,
+}
+
+/// 收集一项测试运行期间的问题，套件在测试结束后取出
+#[derive(Clone, Default)]
+pub struct RunIssues(Arc<Mutex<Vec<Issue>>>);
+
+impl RunIssues {
+    pub fn push(&self, issue: Issue) {
+        self.0.lock().unwrap().push(issue);
+    }
+
+    pub fn take(&self) -> Vec<Issue> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+// 暂停期间运行器检查是否恢复或取消的间隔
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 查询会话是否已被取消或暂停。套件在每项测试开始前检查，运行器也可以在阶段之间检查
+#[derive(Clone)]
+pub struct CancellationToken {
+    is_cancelled: Arc<dyn Fn() -> bool + Send + Sync>,
+    is_paused: Option<Arc<dyn Fn() -> bool + Send + Sync>>,
+    pauses: Arc<PauseLedger>,
+}
+
+// 令牌的所有副本共享的暂停时间记录。多个线程同时等待时同一次暂停只计一次；
+// 计时循环频繁读取，因此只用原子变量
+struct PauseLedger {
+    epoch: Instant,
+    paused_since: AtomicU64, // 相对epoch的纳秒数加1，0表示未暂停
+    total: AtomicU64,        // 已结束的暂停的总纳秒数
+}
+
+impl PauseLedger {
+    fn new() -> Self {
+        Self {
+            epoch: Instant::now(),
+            paused_since: AtomicU64::new(0),
+            total: AtomicU64::new(0),
+        }
+    }
+
+    fn now(&self) -> u64 {
+        self.epoch.elapsed().as_nanos() as u64 + 1
+    }
+
+    fn begin(&self) {
+        let _ = self.paused_since.compare_exchange(0, self.now(), Ordering::SeqCst, Ordering::SeqCst);
+    }
+
+    fn end(&self) {
+        let since = self.paused_since.swap(0, Ordering::SeqCst);
+        if since != 0 {
+            self.total.fetch_add(self.now().saturating_sub(since), Ordering::SeqCst);
+        }
+    }
+
+    // 包括仍在进行的暂停
+    fn paused_total(&self) -> Duration {
+        let since = self.paused_since.load(Ordering::SeqCst);
+        let ongoing = if since == 0 { 0 } else { self.now().saturating_sub(since) };
+        Duration::from_nanos(self.total.load(Ordering::SeqCst) + ongoing)
+    }
+}
+
+/// 不计暂停时间的计时器。计时测试用它判断是否到时并计算吞吐量，暂停不会拉低分数
+pub struct ActiveClock {
+    start: Instant,
+    paused_at_start: Duration,
+    pauses: Arc<PauseLedger>,
+}
+
+impl ActiveClock {
+    pub fn elapsed(&self) -> Duration {
+        let paused = self.pauses.paused_total().saturating_sub(self.paused_at_start);
+        self.start.elapsed().saturating_sub(paused)
+    }
+}
+
+impl CancellationToken {
+    pub fn new(is_cancelled: impl Fn() -> bool + Send + Sync + 'static) -> Self {
+        Self {
+            is_cancelled: Arc::new(is_cancelled),
+            is_paused: None,
+            pauses: Arc::new(PauseLedger::new()),
+        }
+    }
+
+    /// 不支持取消的调用方（如BenchmarkCore）使用
+    pub fn never() -> Self {
+        Self::new(|| false)
+    }
+
+    /// 支持暂停：is_paused为真时check在调用处等待，直到恢复或取消
+    pub fn with_pause(mut self, is_paused: impl Fn() -> bool + Send + Sync + 'static) -> Self {
+        self.is_paused = Some(Arc::new(is_paused));
+        self
+    }
+
+    /// 增加一个取消条件，暂停状态和暂停时间记录保持共享
+    pub fn or_cancelled(&self, also: impl Fn() -> bool + Send + Sync + 'static) -> Self {
+        let is_cancelled = self.is_cancelled.clone();
+        Self {
+            is_cancelled: Arc::new(move || is_cancelled() || also()),
+            ..self.clone()
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        (self.is_cancelled)()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused.as_ref().is_some_and(|is_paused| is_paused())
+    }
+
+    /// 从现在开始计时，之后的暂停时间不计入
+    pub fn clock(&self) -> ActiveClock {
+        ActiveClock {
+            start: Instant::now(),
+            paused_at_start: self.pauses.paused_total(),
+            pauses: self.pauses.clone(),
+        }
+    }
+
+    // 暂停时阻塞当前线程，恢复或取消后返回
+    fn wait_while_paused(&self) {
+        if !self.is_paused() {
+            return;
+        }
+        self.pauses.begin();
+        while self.is_paused() && !self.is_cancelled() {
+            std::thread::sleep(PAUSE_POLL_INTERVAL);
+        }
+        self.pauses.end();
+    }
+
+    /// 运行器在阶段之间和进度更新时调用：会话暂停时在这里等待恢复，已取消时返回Cancelled错误
+    pub fn check(&self, test: TestType, at_progress: f64) -> Result<(), BenchmarkError> {
+        self.wait_while_paused();
+        if self.is_cancelled() {
+            return Err(BenchmarkError::Cancelled {
+                test: test.as_str().to_string(),
+                at_progress,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// 套件交给运行器的进度、取消和警告通道
+#[derive(Clone)]
+pub struct RunContext {
+    pub progress: ProgressSink,
+    pub cancellation: CancellationToken,
+    pub warnings: WarningSink,
+    pub issues: RunIssues,
+}
+
+impl RunContext {
+    /// 转换为各基准测试run_benchmark_with_progress需要的回调
+    pub fn progress_callback(&self) -> impl Fn(f64, Message) + Send + Sync + 'static {
+        let progress = self.progress.clone();
+        move |value, message| progress(value, message)
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+
+    pub fn warn(&self, message: Message) {
+        self.report(message, WarningSeverity::Medium)
+    }
+
+    /// 记录一个问题并立即作为警告发送；测试继续运行，Critical问题使测试记为"完成但有问题"
+    pub fn report(&self, message: Message, severity: WarningSeverity) {
+        self.issues.push(Issue {
+            code: message.key.to_string(),
+            message: message.render(crate::i18n::backend_locale()),
+            severity: severity.clone(),
+        });
+        (self.warnings)(message, severity)
+    }
+}
+
+/// 可由套件运行的一项基准测试。套件按顺序逐个运行，事件、日志和结果记录统一处理。
+/// 内置模块加入suite_runners，其他模块在BenchmarkRegistry中注册后通过extra_tests启用
+pub trait BenchmarkRunner: Send + Sync {
+    fn name(&self) -> TestType;
+
+    /// 日志和事件中的名称和消息
+    fn labels(&self) -> TestLabels {
+        TestLabels::of(self.name())
+    }
+
+    /// 估计耗时（秒），用于加权总体进度和剩余时间
+    fn estimated_duration(&self) -> u64;
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError>;
+
+    /// 运行期间创建的临时文件，会话中断后启动时据此清理
+    fn artifacts(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+}
+
+// 看门狗与运行器共享的状态，两者在同一把锁下更新，期限前刚好结束的测试不会被判为超时
+#[derive(Default)]
+struct WatchdogState {
+    finished: bool,
+    timed_out: bool,
+}
+
+/// 在看门狗下运行一项测试。超过limit后通过取消令牌通知运行器停止，运行器返回后报告Timeout；
+/// 会话本身被取消时按运行器的结果返回，运行器panic时返回Internal。运行器返回后看门狗线程立即结束并被回收
+pub fn run_with_watchdog(
+    runner: &dyn BenchmarkRunner,
+    ctx: &RunContext,
+    limit: Duration,
+) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+    let state = Arc::new(Mutex::new(WatchdogState::default()));
+    let (done, finished) = mpsc::channel::<()>();
+    let watchdog = {
+        let state = state.clone();
+        let clock = ctx.cancellation.clock();
+        std::thread::spawn(move || {
+            // 运行器返回时发送端被丢弃，recv_timeout立即以Disconnected返回。暂停的时间不计入时限
+            let mut remaining = limit;
+            while let Err(mpsc::RecvTimeoutError::Timeout) = finished.recv_timeout(remaining) {
+                let active = clock.elapsed();
+                if active >= limit {
+                    let mut state = state.lock().unwrap();
+                    state.timed_out = !state.finished;
+                    break;
+                }
+                remaining = limit - active;
+            }
+        })
+    };
+
+    let watched = {
+        let state = state.clone();
+        RunContext {
+            cancellation: ctx.cancellation.or_cancelled(move || state.lock().unwrap().timed_out),
+            ..ctx.clone()
+        }
+    };
+    // 运行器（包括rayon工作线程传回的）panic转换为Internal错误，不会带着会话一起卡住
+    let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| runner.run(&watched)))
+        .unwrap_or_else(|payload| Err(BenchmarkError::from_panic(runner.name().as_str(), payload.as_ref())));
+    let timed_out = {
+        let mut state = state.lock().unwrap();
+        state.finished = true;
+        state.timed_out
+    };
+    drop(done);
+    let _ = watchdog.join();
+
+    if timed_out && !ctx.is_cancelled() && !matches!(outcome, Err(BenchmarkError::Internal { .. })) {
+        return Err(BenchmarkError::Timeout {
+            test: runner.name().as_str().to_string(),
+            limit_seconds: limit.as_secs_f64().ceil() as u64,
+        });
+    }
+    outcome
+}
+
+/// 按套件顺序构造启用的测试，扩展测试通过registry创建并排在内置测试之后。
+/// calibration用于估计内存和存储测试的耗时，metrics_sink为每项内置测试提供实时指标的去向，
+/// webview为WebView测试向前端发送请求的方式，为空时启用的WebView测试会失败
+pub fn suite_runners(
+    config: &BenchmarkConfig,
+    calibration: &Calibration,
+    registry: &BenchmarkRegistry,
+    metrics_sink: impl Fn(TestType) -> Option<MetricsSink>,
+    webview: Option<&WebviewBridge>,
+) -> Result<Vec<Box<dyn BenchmarkRunner>>, BenchmarkError> {
+    let mut runners: Vec<Box<dyn BenchmarkRunner>> = Vec::new();
+    if config.cpu_test.enabled {
+        let mut benchmark = CpuBenchmark::new(config.cpu_test.config.clone());
+        if let Some(sink) = metrics_sink(TestType::Cpu) {
+            benchmark = benchmark.with_metrics_sink(sink);
+        }
+        runners.push(Box::new(benchmark));
+    }
+    if config.memory_test.enabled {
+        let mut benchmark = MemoryBenchmark::new(config.memory_test.config.clone()).with_calibration(calibration.clone());
+        if let Some(sink) = metrics_sink(TestType::Memory) {
+            benchmark = benchmark.with_metrics_sink(sink);
+        }
+        runners.push(Box::new(benchmark));
+    }
+    if config.storage_test.enabled {
+        let mut benchmark = StorageBenchmark::new(config.storage_test.config.clone()).with_calibration(calibration.clone());
+        if let Some(sink) = metrics_sink(TestType::Storage) {
+            benchmark = benchmark.with_metrics_sink(sink);
+        }
+        runners.push(Box::new(benchmark));
+    }
+    if config.gpu_test.enabled {
+        runners.push(Box::new(GpuBenchmark::new(config.gpu_test.config.clone())));
+    }
+    if config.webview_test.enabled {
+        let mut benchmark = WebviewBenchmark::new(config.webview_test.config.clone());
+        if let Some(bridge) = webview {
+            benchmark = benchmark.with_bridge(bridge.clone());
+        }
+        runners.push(Box::new(benchmark));
+    }
+    if config.network_test.enabled {
+        runners.push(Box::new(NetworkBenchmark::new(config.network_test.config.clone())));
+    }
+    for spec in &config.extra_tests {
+        runners.push(registry.create_extra(&with_storage_target(spec, config))?);
+    }
+    Ok(runners)
+}
+
+/// 未指定目录的数据库测试使用存储测试的目标目录，两者测量同一块磁盘
+fn with_storage_target(spec: &ExtraTestSpec, config: &BenchmarkConfig) -> ExtraTestSpec {
+    let mut spec = spec.clone();
+    let target = config.storage_test.test_file_path.as_deref().map(Path::new).and_then(Path::parent);
+    if let (database::DATABASE_TEST_NAME, Some(target), Some(fields)) = (spec.name.as_str(), target, spec.config.as_object_mut()) {
+        if !fields.contains_key("test_dir") && !fields.contains_key("testDir") {
+            fields.insert("test_dir".to_string(), serde_json::json!(target.to_string_lossy()));
+        }
+    }
+    spec
+}
+
+/// 根据配置创建运行器的工厂函数，配置无效时返回错误
+pub type RunnerFactory = Arc<dyn Fn(serde_json::Value) -> Result<Box<dyn BenchmarkRunner>, BenchmarkError> + Send + Sync>;
+
+/// 可按名称创建的测试模块。内置的CPU、内存、存储、GPU和网络测试以"cpu"、"memory"、"storage"、"gpu"、"network"注册，
+/// IPC开销、进程启动、压缩、加密、哈希、图像编解码、数据库、JSON、稳定性和线程同步测试以"ipc"、"process"、"compression"、"crypto"、
+/// "hashing"、"image"、"database"、"json"、"stability"、"sync"注册，在套件中通过extra_tests运行；其余模块（如项目内部的负载）注册后即可在配置的extra_tests中引用
+#[derive(Clone)]
+pub struct BenchmarkRegistry {
+    factories: BTreeMap<String, RunnerFactory>,
+}
+
+impl Default for BenchmarkRegistry {
+    fn default() -> Self {
+        Self::with_builtin()
+    }
+}
+
+impl BenchmarkRegistry {
+    /// 只包含内置测试的注册表
+    pub fn with_builtin() -> Self {
+        let mut registry = Self { factories: BTreeMap::new() };
+        registry.register(TestType::Cpu.as_str(), |config| {
+            Ok(Box::new(CpuBenchmark::new(extra_config(TestType::Cpu.as_str(), config)?)))
+        });
+        registry.register(TestType::Memory.as_str(), |config| {
+            Ok(Box::new(MemoryBenchmark::new(extra_config(TestType::Memory.as_str(), config)?)))
+        });
+        registry.register(TestType::Storage.as_str(), |config| {
+            Ok(Box::new(StorageBenchmark::new(extra_config(TestType::Storage.as_str(), config)?)))
+        });
+        registry.register(TestType::Gpu.as_str(), |config| {
+            Ok(Box::new(GpuBenchmark::new(extra_config(TestType::Gpu.as_str(), config)?)))
+        });
+        registry.register(TestType::Network.as_str(), |config| {
+            Ok(Box::new(NetworkBenchmark::new(extra_config(TestType::Network.as_str(), config)?)))
+        });
+        registry.register(ipc_overhead::IPC_TEST_NAME, |config| {
+            Ok(Box::new(IpcBenchmark::new(extra_config(ipc_overhead::IPC_TEST_NAME, config)?)))
+        });
+        registry.register(compression::COMPRESSION_TEST_NAME, |config| {
+            let config: compression::CompressionTestConfig = extra_config(compression::COMPRESSION_TEST_NAME, config)?;
+            config.check()?;
+            Ok(Box::new(CompressionBenchmark::new(config)))
+        });
+        registry.register(crypto::CRYPTO_TEST_NAME, |config| {
+            Ok(Box::new(CryptoBenchmark::new(extra_config(crypto::CRYPTO_TEST_NAME, config)?)))
+        });
+        registry.register(hashing::HASHING_TEST_NAME, |config| {
+            Ok(Box::new(HashingBenchmark::new(extra_config(hashing::HASHING_TEST_NAME, config)?)))
+        });
+        registry.register(image::IMAGE_TEST_NAME, |config| {
+            let config: image::ImageTestConfig = extra_config(image::IMAGE_TEST_NAME, config)?;
+            config.check()?;
+            Ok(Box::new(ImageBenchmark::new(config)))
+        });
+        registry.register(database::DATABASE_TEST_NAME, |config| {
+            Ok(Box::new(DatabaseBenchmark::new(extra_config(database::DATABASE_TEST_NAME, config)?)))
+        });
+        registry.register(serde_json_bench::JSON_TEST_NAME, |config| {
+            Ok(Box::new(JsonBenchmark::new(extra_config(serde_json_bench::JSON_TEST_NAME, config)?)))
+        });
+        registry.register(stability::STABILITY_TEST_NAME, |config| {
+            Ok(Box::new(StabilityBenchmark::new(extra_config(stability::STABILITY_TEST_NAME, config)?)))
+        });
+        registry.register(sync::SYNC_TEST_NAME, |config| {
+            Ok(Box::new(SyncBenchmark::new(extra_config(sync::SYNC_TEST_NAME, config)?)))
+        });
+        registry.register(process::PROCESS_TEST_NAME, |config| {
+            Ok(Box::new(ProcessBenchmark::new(extra_config(process::PROCESS_TEST_NAME, config)?)))
+        });
+        registry
+    }
+
+    /// 注册测试模块，同名的注册会被替换
+    pub fn register(
+        &mut self,
+        name: &str,
+        factory: impl Fn(serde_json::Value) -> Result<Box<dyn BenchmarkRunner>, BenchmarkError> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.factories.insert(name.to_string(), Arc::new(factory));
+        self
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.factories.keys().map(String::as_str).collect()
+    }
+
+    pub fn create(&self, name: &str, config: serde_json::Value) -> Result<Box<dyn BenchmarkRunner>, BenchmarkError> {
+        let factory = self
+            .factories
+            .get(name)
+            .ok_or_else(|| BenchmarkError::DataLoadError(format!("未注册的测试模块: {}", name)))?;
+        factory(config)
+    }
+
+    /// 创建扩展测试，结果记录在TestResult::extra_results中对应的注册名下
+    fn create_extra(&self, spec: &ExtraTestSpec) -> Result<Box<dyn BenchmarkRunner>, BenchmarkError> {
+        let inner = self.create(&spec.name, spec.config.clone())?;
+        Ok(Box::new(ExtraRunner {
+            name: spec.name.clone(),
+            inner,
+        }))
+    }
+
+    /// 检查配置中的扩展测试：名称必须已注册且不是内置测试，配置必须能被对应模块接受
+    pub fn issues(&self, config: &BenchmarkConfig) -> Vec<ConfigIssue> {
+        let builtin = [TestType::Cpu, TestType::Memory, TestType::Storage, TestType::Gpu, TestType::Network].map(TestType::as_str);
+        config
+            .extra_tests
+            .iter()
+            .enumerate()
+            .filter_map(|(index, spec)| {
+                let (field, message) = if builtin.contains(&spec.name.as_str()) {
+                    ("name", format!("{}是内置测试，请在{}_test中配置", spec.name, spec.name))
+                } else if !self.contains(&spec.name) {
+                    ("name", format!("未注册的测试模块{}，可用的模块: {}", spec.name, self.names().join(", ")))
+                } else {
+                    ("config", self.create(&spec.name, spec.config.clone()).err()?.to_string())
+                };
+                // 名称和模块配置没有通用的建议值
+                Some(ConfigIssue {
+                    field: format!("extra_tests[{}].{}", index, field),
+                    severity: IssueSeverity::Error,
+                    message,
+                    suggested_value: None,
+                })
+            })
+            .collect()
+    }
+
+    /// 与BenchmarkConfig::validate相同，额外检查扩展测试
+    pub fn validate(&self, config: &BenchmarkConfig, system: Option<&SystemInfo>) -> Result<(), Vec<ConfigIssue>> {
+        let mut issues = config.issues(system);
+        issues.extend(self.issues(config));
+        if issues.iter().any(|issue| issue.severity == IssueSeverity::Error) {
+            Err(issues)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// 把JSON配置解析为模块配置，错误信息带上模块名
+pub fn extra_config<T: serde::de::DeserializeOwned>(name: &str, config: serde_json::Value) -> Result<T, BenchmarkError> {
+    serde_json::from_value(config).map_err(|e| BenchmarkError::DataLoadError(format!("测试模块{}的配置无效: {}", name, e)))
+}
+
+/// 扩展测试的包装：以Extra类型和注册名出现在事件和日志中，结果归入注册名
+struct ExtraRunner {
+    name: String,
+    inner: Box<dyn BenchmarkRunner>,
+}
+
+impl BenchmarkRunner for ExtraRunner {
+    fn name(&self) -> TestType {
+        TestType::Extra
+    }
+
+    fn labels(&self) -> TestLabels {
+        TestLabels::extra(&self.name)
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        self.inner.estimated_duration()
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        let result = match self.inner.run(ctx)? {
+            TestOutcome::Skipped => return Ok(TestOutcome::Skipped),
+            TestOutcome::Unavailable => return Ok(TestOutcome::Unavailable),
+            TestOutcome::Completed(TestResultPayload::Extra(extra)) => extra.result,
+            // 内置模块作为扩展测试运行时，其结果原样序列化
+            TestOutcome::Completed(payload) => serde_json::to_value(&payload)
+                .map_err(|e| BenchmarkError::DataSaveError(format!("测试结果序列化失败: {}", e)))?,
+        };
+        Ok(TestOutcome::Completed(TestResultPayload::Extra(ExtraTestResult {
+            name: self.name.clone(),
+            result,
+        })))
+    }
+
+    fn artifacts(&self) -> Vec<PathBuf> {
+        self.inner.artifacts()
+    }
+}
+
+/// 各运行器的估计耗时，用于构造SuiteProgressTracker。重复运行时按运行顺序重复列出，
+/// 总体进度和剩余时间覆盖全部运行
+pub fn runner_estimates(runners: &[Box<dyn BenchmarkRunner>], repeat: usize) -> Vec<TestEstimate> {
+    (0..repeat.max(1))
+        .flat_map(|_| runners.iter())
+        .map(|runner| TestEstimate {
+            test_type: runner.name().as_str().to_string(),
+            seconds: runner.estimated_duration() as f64,
+        })
+        .collect()
+}
+
+/// 单项测试在日志和进度事件中的名称，以及开始、完成、失败时的消息
+#[derive(Debug, Clone)]
+pub struct TestLabels {
+    pub name: String,
+    pub start: Message,
+    pub complete: Message,
+    pub failed: Message, // 发送前补充error参数
+}
+
+impl TestLabels {
+    pub fn of(test_type: TestType) -> Self {
+        match test_type {
+            TestType::Cpu => Self {
+                name: "CPU基准测试".to_string(),
+                start: Message::new("cpu.start"),
+                complete: Message::new("cpu.complete"),
+                failed: Message::new("cpu.failed"),
+            },
+            TestType::Memory => Self {
+                name: "内存基准测试".to_string(),
+                start: Message::new("memory.start"),
+                complete: Message::new("memory.complete"),
+                failed: Message::new("memory.failed"),
+            },
+            TestType::Storage => Self {
+                name: "存储基准测试".to_string(),
+                start: Message::new("storage.start"),
+                complete: Message::new("storage.complete"),
+                failed: Message::new("storage.failed"),
+            },
+            TestType::Gpu => Self {
+                name: "GPU基准测试".to_string(),
+                start: Message::new("gpu.start"),
+                complete: Message::new("gpu.complete"),
+                failed: Message::new("gpu.failed"),
+            },
+            TestType::Webview => Self {
+                name: "WebView基准测试".to_string(),
+                start: Message::new("webview.start"),
+                complete: Message::new("webview.complete"),
+                failed: Message::new("webview.failed"),
+            },
+            TestType::Network => Self {
+                name: "网络基准测试".to_string(),
+                start: Message::new("network.start"),
+                complete: Message::new("network.complete"),
+                failed: Message::new("network.failed"),
+            },
+            TestType::Extra => Self::extra("extra"),
+            TestType::Suite => unreachable!("套件不是单项测试"),
+        }
+    }
+
+    pub fn extra(name: &str) -> Self {
+        Self {
+            name: format!("扩展测试{}", name),
+            start: Message::new("extra.start").param("name", name),
+            complete: Message::new("extra.complete").param("name", name),
+            failed: Message::new("extra.failed").param("name", name),
+        }
+    }
+
+    /// 失败时的消息：磁盘已满和权限不足给出处理建议，其它错误使用failed模板
+    pub fn failure(&self, error: &BenchmarkError) -> Message {
+        use std::io::ErrorKind;
+        let message = match error.io_kind() {
+            Some(ErrorKind::StorageFull | ErrorKind::QuotaExceeded) => Message::new("io.storage_full"),
+            Some(ErrorKind::PermissionDenied | ErrorKind::ReadOnlyFilesystem) => Message::new("io.permission_denied"),
+            _ => self.failed.clone(),
+        };
+        message.param("error", error)
+    }
+}
+
+/// 测试失败时的警告类型和严重程度：超时和panic单独区分
+pub fn failure_warning(error: &BenchmarkError) -> (&'static str, WarningSeverity) {
+    match error {
+        BenchmarkError::Timeout { .. } => ("test_timeout", WarningSeverity::High),
+        BenchmarkError::Internal { .. } => ("test_panic", WarningSeverity::Critical),
+        _ => ("test_failure", WarningSeverity::High),
+    }
+}
+
+impl TestResult {
+    /// 重新计算各部分得分和总体评分，权重取自config_used
+    pub fn update_scores(&mut self) {
+        self.category_scores = scoring::category_scores(self)
+            .into_iter()
+            .map(|(category, score)| (category.to_string(), score))
+            .collect();
+        self.overall_score = calculate_overall_score(self);
+    }
+
+    /// 把运行器的结果写入对应的字段
+    pub fn record_payload(&mut self, payload: TestResultPayload) {
+        match payload {
+            TestResultPayload::Cpu(result) => self.cpu_results = Some(result),
+            TestResultPayload::Memory(result) => self.memory_results = Some(result),
+            TestResultPayload::Storage(result) => self.storage_results = Some(result),
+            TestResultPayload::Gpu(result) => self.gpu_results = Some(result),
+            TestResultPayload::Webview(result) => self.webview_results = Some(result),
+            TestResultPayload::Network(result) => self.network_results = Some(result),
+            TestResultPayload::Extra(extra) => {
+                self.extra_results.insert(extra.name, extra.result);
+            }
+        }
+    }
+}
+
+/// 一项指标在多次运行中的统计，只统计包含该指标的运行
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricAggregate {
+    pub metric: String, // 与HeadlineMetrics::entries中的指标名一致，总分为overall_score
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    pub samples: usize, // 参与统计的运行次数
+}
+
+impl MetricAggregate {
+    fn from_values(metric: &str, values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        Some(Self {
+            metric: metric.to_string(),
+            mean: values.iter().sum::<f64>() / values.len() as f64,
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            samples: values.len(),
+        })
+    }
+}
+
+/// 历史结果的汇总，没有任何运行包含的指标不出现在metrics中
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AggregateScores {
+    pub runs: usize,
+    pub overall_score: Option<MetricAggregate>,
+    pub metrics: Vec<MetricAggregate>,
+}
+
+/// 在作用域线程中运行一项测试，进度经通道转回调用线程，
+/// 这样调用方的进度回调不需要满足Send和'static
+fn run_forwarding_progress<T, R>(
+    run: R,
+    test_type: &str,
+    tracker: &SuiteProgressTracker,
+    locale: Locale,
+    progress: &dyn Fn(f64, String),
+) -> Result<T, BenchmarkError>
+where
+    T: Send,
+    R: FnOnce(mpsc::Sender<(f64, Message)>) -> Result<T, BenchmarkError> + Send,
+{
+    let (sender, receiver) = mpsc::channel();
+    // 测试线程沿用调用线程的日志订阅者和当前span，子阶段的日志归入所属会话和测试
+    let span = tracing::Span::current();
+    let dispatch = tracing::dispatcher::get_default(Clone::clone);
+    std::thread::scope(|scope| {
+        let handle = scope.spawn(move || tracing::dispatcher::with_default(&dispatch, || span.in_scope(|| run(sender))));
+        // 测试结束时发送端被丢弃，循环随之结束
+        for (test_progress, message) in receiver {
+            let suite_progress = tracker.update(test_type, test_progress);
+            progress(suite_progress.overall, message.render(locale));
+        }
+        handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
 }
 
 pub struct BenchmarkCore {
     config: BenchmarkConfig,
     results: Vec<TestResult>,
+    registry: BenchmarkRegistry,
+    event_sink: Option<Arc<dyn EventSink>>,
+}
+
+impl Default for BenchmarkCore {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BenchmarkCore {
     pub fn new() -> Self {
+        // 无法获取系统信息时使用标准预设
+        let config = Self::get_system_info()
+            .map(|system| BenchmarkConfig::default_for_system(&system))
+            .unwrap_or_default();
         Self {
-            config: BenchmarkConfig {
-                cpu_test: CpuTestConfig {
-                    enabled: true,
-                    duration: 60,
-                    thread_count: 0, // 0 means use all available threads
-                },
-                memory_test: MemoryTestConfig {
-                    enabled: true,
-                    buffer_size: 1024, // 1GB
-                    iterations: 100,
-                },
-                storage_test: StorageTestConfig {
-                    enabled: true,
-                    file_size: 1024, // 1GB
-                    block_size: 4,   // 4KB
-                },
-            },
+            config,
             results: Vec::new(),
+            registry: BenchmarkRegistry::with_builtin(),
+            event_sink: None,
         }
     }
 
+    /// 注册扩展测试模块，供配置中的extra_tests引用
+    pub fn registry_mut(&mut self) -> &mut BenchmarkRegistry {
+        &mut self.registry
+    }
+
+    /// run_all向sink发送每项测试的完成事件和运行期间的警告，会话标识为本次运行的run_id
+    pub fn set_event_sink(&mut self, sink: impl EventSink + 'static) {
+        self.event_sink = Some(Arc::new(sink));
+    }
+
     pub fn set_config(&mut self, config: BenchmarkConfig) {
         self.config = config;
     }
@@ -78,32 +1606,910 @@ impl BenchmarkCore {
 
     pub fn run_cpu_benchmark(
         &mut self,
-    ) -> Result<crate::benchmark::cpu::CpuTestResult, BenchmarkError> {
-        // TODO: 实现CPU基准测试
-        Err(BenchmarkError::CpuTestError(
-            "Not implemented yet".to_string(),
-        ))
+    ) -> Result<TestOutcome<cpu::CpuTestResult>, BenchmarkError> {
+        if !self.config.cpu_test.enabled {
+            return Ok(TestOutcome::Skipped);
+        }
+        CpuBenchmark::new(self.config.cpu_test.config.clone())
+            .run_benchmark()
+            .map(TestOutcome::Completed)
     }
 
     pub fn run_memory_benchmark(
         &mut self,
-    ) -> Result<crate::benchmark::memory::MemoryTestResult, BenchmarkError> {
-        // TODO: 实现内存基准测试
-        Err(BenchmarkError::MemoryTestError(
-            "Not implemented yet".to_string(),
-        ))
+    ) -> Result<TestOutcome<memory::MemoryTestResult>, BenchmarkError> {
+        if !self.config.memory_test.enabled {
+            return Ok(TestOutcome::Skipped);
+        }
+        MemoryBenchmark::new(self.config.memory_test.config.clone())
+            .run_benchmark()
+            .map(TestOutcome::Completed)
     }
 
     pub fn run_storage_benchmark(
         &mut self,
-    ) -> Result<crate::benchmark::storage::StorageTestResult, BenchmarkError> {
-        // TODO: 实现存储基准测试
-        Err(BenchmarkError::StorageTestError(
-            "Not implemented yet".to_string(),
-        ))
+    ) -> Result<TestOutcome<storage::StorageTestResult>, BenchmarkError> {
+        if !self.config.storage_test.enabled {
+            return Ok(TestOutcome::Skipped);
+        }
+        StorageBenchmark::new(self.config.storage_test.config.clone())
+            .run_benchmark()
+            .map(TestOutcome::Completed)
+    }
+
+    /// 按当前配置运行整个套件，结果追加到历史中。与run_full_benchmark_suite的流程一致，
+    /// 但不依赖Tauri：progress收到总体进度（0-100）和渲染后的消息，单项测试失败时该项结果为空
+    pub fn run_all(&mut self, progress: impl Fn(f64, String)) -> Result<TestResult, BenchmarkError> {
+        let mut config = self.config.clone();
+        let locale = Locale::default();
+        let system_info = Self::get_system_info()?;
+        self.registry.validate(&config, Some(&system_info)).map_err(BenchmarkError::InvalidConfig)?;
+        let app_info = AppInfo::new(env!("CARGO_PKG_VERSION"));
+        let identity = RunIdentity::new(&system_info, &app_info, RunMode::Headless);
+        let mut test_result = TestResult {
+            schema_version: RESULT_SCHEMA_VERSION,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            system_info,
+            cpu_results: None,
+            memory_results: None,
+            storage_results: None,
+            gpu_results: None,
+            webview_results: None,
+            network_results: None,
+            overall_score: 0.0,
+            category_scores: BTreeMap::new(),
+            imported: false,
+            auto_duration: None,
+            app_info: Some(app_info),
+            failure_log: Vec::new(),
+            warnings: Vec::new(),
+            config_used: None,
+            per_test_durations: BTreeMap::new(),
+            session_id: None,
+            runs: Vec::new(),
+            partial: false,
+            extra_results: BTreeMap::new(),
+            regression_check: None,
+            integrity: None,
+            identity: Some(identity),
+        };
+        
+        // 自动时长模式：先校准再推导各测试参数
+        if let Some(target_seconds) = config.auto_duration_target_seconds {
+            let calibration = if config.skip_calibration {
+                Calibration::default_estimates()
+            } else {
+                Calibration::measure(&config).unwrap_or_else(|_| Calibration::default_estimates())
+            };
+            let plan = plan_auto_duration(&config, target_seconds, calibration);
+            plan.apply(&mut config);
+            test_result.auto_duration = Some(plan);
+        }
+        
+        let eta_calibration = test_result
+            .auto_duration
+            .as_ref()
+            .map(|plan| plan.calibration.clone())
+            .unwrap_or_else(Calibration::default_estimates);
+        let runners = suite_runners(&config, &eta_calibration, &self.registry, |_| None, None)?;
+        let repeat = config.repeat.max(1);
+        let tracker = SuiteProgressTracker::new(&runner_estimates(&runners, repeat));
+        test_result.config_used = Some(config.clone());
+        
+        // 每次运行的原始结果都追加到历史中，多次运行时返回按配置合并的结果
+        let mut runs = Vec::with_capacity(repeat);
+        for _ in 0..repeat {
+            let mut run = TestResult {
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                ..test_result.clone()
+            };
+            if repeat > 1 {
+                // 每次原始结果有各自的run_id，汇总结果沿用套件开始时的标识
+                run.identity = run.identity.as_ref().map(RunIdentity::renewed);
+            }
+            let session_id = run.identity.as_ref().map(|identity| identity.run_id.clone()).unwrap_or_default();
+            let _session = tracing::info_span!("session", session_id = %session_id).entered();
+            for runner in &runners {
+                let test_type = runner.name();
+                let _test = tracing::info_span!("test", test_type = %test_type.as_str()).entered();
+                tracker.start_test(test_type.as_str());
+                let started = std::time::Instant::now();
+                let issues = RunIssues::default();
+                let outcome = run_forwarding_progress(
+                    |sender| {
+                        let ctx = RunContext {
+                            progress: Arc::new(move |p, message| {
+                                let _ = sender.send((p, message));
+                            }),
+                            cancellation: CancellationToken::never(),
+                            warnings: self.warning_sink(&session_id, test_type, locale),
+                            issues: issues.clone(),
+                        };
+                        run_with_watchdog(runner.as_ref(), &ctx, config.test_timeout(runner.as_ref()))
+                    },
+                    test_type.as_str(),
+                    &tracker,
+                    locale,
+                    &progress,
+                );
+                let elapsed_seconds = started.elapsed().as_secs_f64();
+                *run.per_test_durations.entry(test_type).or_insert(0.0) += elapsed_seconds;
+                if let Some(sink) = &self.event_sink {
+                    let issues = issues.take();
+                    let (result, error, outcome) = match &outcome {
+                        Ok(TestOutcome::Completed(payload)) => (Some(payload.clone()), None, TestCompletion::classify(false, &issues)),
+                        Ok(_) => (None, None, TestCompletion::Skipped),
+                        Err(e) => (None, Some(e.to_string()), TestCompletion::Failed),
+                    };
+                    sink.send(SuiteEvent::TestComplete(Box::new(TestCompleteEvent {
+                        session_id: session_id.clone(),
+                        test_type,
+                        success: error.is_none(),
+                        result,
+                        error,
+                        elapsed_seconds,
+                        outcome,
+                        issues,
+                    })));
+                }
+                // 失败（包括超时）记入结果的警告，不依赖是否设置了sink
+                if let Err(e) = &outcome {
+                    let (warning_type, severity) = failure_warning(e);
+                    let warning = runner.labels().failure(e);
+                    let now = chrono::Utc::now().to_rfc3339();
+                    run.warnings.push(WarningRecord {
+                        test_type,
+                        warning_type: warning_type.to_string(),
+                        severity,
+                        message: warning.render(locale),
+                        message_key: warning.key.to_string(),
+                        count: 1,
+                        emitted: 0,
+                        active: false,
+                        first_seen: now.clone(),
+                        last_seen: now,
+                    });
+                }
+                if let Ok(TestOutcome::Completed(payload)) = outcome {
+                    run.record_payload(payload);
+                }
+                let complete = runner.labels().complete;
+                progress(tracker.finish_test(test_type.as_str()).overall, complete.render(locale));
+            }
+            run.update_scores();
+            self.results.push(run.clone());
+            runs.push(run);
+        }
+        
+        if runs.len() == 1 {
+            return Ok(runs.remove(0));
+        }
+        let identity = test_result.identity.clone();
+        let mut aggregate = aggregate_runs(&runs, config.aggregation).unwrap_or(test_result);
+        aggregate.identity = identity;
+        Ok(aggregate)
+    }
+
+    // 与图形界面运行时的警告事件一致，没有设置sink时丢弃
+    fn warning_sink(&self, session_id: &str, test_type: TestType, locale: Locale) -> WarningSink {
+        let Some(sink) = self.event_sink.clone() else {
+            return Arc::new(|_, _| {});
+        };
+        let session_id = session_id.to_string();
+        Arc::new(move |warning: Message, severity: WarningSeverity| {
+            sink.send(SuiteEvent::Warning(TestWarningEvent {
+                session_id: session_id.clone(),
+                test_type,
+                warning_type: warning.key.to_string(),
+                message: warning.render(locale),
+                message_key: warning.key.to_string(),
+                params: warning.params,
+                severity,
+            }));
+        })
+    }
+
+    /// 按运行顺序排列的历史结果
+    pub fn get_results(&self) -> &[TestResult] {
+        &self.results
+    }
+
+    pub fn latest_result(&self) -> Option<&TestResult> {
+        self.results.last()
+    }
+
+    /// 总分最高的结果，同分时取较早的一次
+    pub fn best_result(&self) -> Option<&TestResult> {
+        self.results.iter().fold(None, |best: Option<&TestResult>, result| match best {
+            Some(best) if best.overall_score >= result.overall_score => Some(best),
+            _ => Some(result),
+        })
+    }
+
+    /// 汇总各关键指标的平均、最小和最大值；某次运行缺少的测试类别不参与该类指标的统计
+    pub fn average_scores(&self) -> AggregateScores {
+        let headlines: Vec<HeadlineMetrics> = self.results.iter().map(HeadlineMetrics::from_result).collect();
+        let overall: Vec<f64> = self.results.iter().map(|result| result.overall_score).collect();
+        let metrics = HeadlineMetrics::default()
+            .entries()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (metric, _))| {
+                let values: Vec<f64> = headlines.iter().filter_map(|headline| headline.entries()[index].1).collect();
+                MetricAggregate::from_values(metric, &values)
+            })
+            .collect();
+        AggregateScores {
+            runs: self.results.len(),
+            overall_score: MetricAggregate::from_values("overall_score", &overall),
+            metrics,
+        }
+    }
+
+    pub fn clear_results(&mut self) {
+        self.results.clear();
     }
 
     pub fn get_system_info() -> Result<crate::benchmark::system_info::SystemInfo, BenchmarkError> {
         crate::benchmark::system_info::collect_system_info()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::test_support::quick_suite_config;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_run_all_with_quick_config() {
+        let mut core = BenchmarkCore::new();
+        core.set_config(quick_suite_config());
+        assert_eq!(core.get_config().cpu_test.test_duration, 1);
+        // 进度回调不需要满足Send，可以直接借用本地状态
+        let updates = RefCell::new(Vec::new());
+        let result = core
+            .run_all(|overall, message| updates.borrow_mut().push((overall, message)))
+            .unwrap();
+
+        assert!(result.cpu_results.is_some());
+        assert!(result.memory_results.is_some());
+        assert!(result.storage_results.is_some());
+        assert!(result.overall_score > 0.0);
+        assert_eq!(result.schema_version, RESULT_SCHEMA_VERSION);
+        assert_eq!(result.config_used.as_ref().unwrap().cpu_test.config, core.get_config().cpu_test.config);
+        assert_eq!(
+            result.per_test_durations.keys().copied().collect::<Vec<_>>(),
+            [TestType::Cpu, TestType::Memory, TestType::Storage]
+        );
+        assert!(result.per_test_durations.values().all(|seconds| *seconds > 0.0));
+        assert!(result.session_id.is_none());
+        assert_eq!(core.get_results().len(), 1);
+
+        let updates = updates.into_inner();
+        assert!(updates.windows(2).all(|pair| pair[1].0 >= pair[0].0), "总体进度倒退");
+        assert_eq!(updates.last().unwrap().0, 100.0);
+        assert!(updates.iter().all(|(_, message)| !message.is_empty()));
+    }
+
+    #[test]
+    fn test_run_all_repeats_and_aggregates() {
+        let mut config = quick_suite_config();
+        config.memory_test.enabled = false;
+        config.storage_test.enabled = false;
+        config.repeat = 3;
+        let mut core = BenchmarkCore::new();
+        core.set_config(config);
+        let updates = RefCell::new(Vec::new());
+        let result = core.run_all(|overall, _| updates.borrow_mut().push(overall)).unwrap();
+
+        // 每次运行的原始结果都在历史中，返回的是中位数汇总
+        assert_eq!(core.get_results().len(), 3);
+        assert_eq!(result.runs.len(), 3);
+        assert!(!result.partial);
+        let mut scores: Vec<f64> = core.get_results().iter().map(|run| run.cpu_results.as_ref().unwrap().single_thread_score).collect();
+        scores.sort_by(f64::total_cmp);
+        assert_eq!(result.cpu_results.as_ref().unwrap().single_thread_score, scores[1]);
+
+        // 各次运行的run_id互不相同，机器指纹一致；汇总结果有自己的run_id
+        let identities: Vec<&RunIdentity> = core.get_results().iter().map(|run| run.identity.as_ref().unwrap()).collect();
+        let aggregate_identity = result.identity.as_ref().unwrap();
+        let mut run_ids: Vec<&str> = identities.iter().map(|identity| identity.run_id.as_str()).collect();
+        run_ids.push(&aggregate_identity.run_id);
+        run_ids.sort();
+        run_ids.dedup();
+        assert_eq!(run_ids.len(), 4);
+        assert!(identities.iter().all(|identity| identity.machine_fingerprint == aggregate_identity.machine_fingerprint));
+        assert_eq!(aggregate_identity.machine_fingerprint, result.system_info.hardware_fingerprint());
+        assert_eq!(aggregate_identity.mode, RunMode::Headless);
+        assert_eq!(aggregate_identity.engine_version, env!("CARGO_PKG_VERSION"));
+
+        // 总体进度覆盖全部运行，第一次运行结束时约为1/3
+        let updates = updates.into_inner();
+        assert!(updates.windows(2).all(|pair| pair[1] >= pair[0]), "总体进度倒退");
+        assert!(updates.iter().any(|overall| (33.0..34.0).contains(overall)), "{:?}", updates);
+        assert_eq!(updates.last().copied(), Some(100.0));
+    }
+
+    #[test]
+    fn test_run_all_sends_events_to_sink() {
+        let mut config = quick_suite_config();
+        config.memory_test.enabled = false;
+        config.storage_test.enabled = false;
+        let mut core = BenchmarkCore::new();
+        core.set_config(config);
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let received = events.clone();
+        core.set_event_sink(move |event| received.lock().unwrap().push(event));
+        let result = core.run_all(|_, _| {}).unwrap();
+
+        // 完成事件携带测试结果，会话标识为本次运行的run_id
+        let events = events.lock().unwrap();
+        let completions: Vec<&TestCompleteEvent> = events
+            .iter()
+            .filter_map(|event| match event {
+                SuiteEvent::TestComplete(complete) => Some(complete.as_ref()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(completions.len(), 1);
+        let complete = completions[0];
+        assert_eq!(complete.test_type, TestType::Cpu);
+        assert_eq!(complete.session_id, result.identity.as_ref().unwrap().run_id);
+        assert!(complete.success);
+        assert_ne!(complete.outcome, TestCompletion::Failed);
+        assert!(matches!(complete.result, Some(TestResultPayload::Cpu(_))));
+        assert!(complete.elapsed_seconds > 0.0);
+    }
+
+    #[test]
+    fn test_registered_extra_test_runs_in_suite() {
+        use crate::benchmark::test_support::SleepRunner;
+
+        let mut config = BenchmarkConfig::quick();
+        config.cpu_test.enabled = false;
+        config.memory_test.enabled = false;
+        config.storage_test.enabled = false;
+        config.extra_tests.push(ExtraTestSpec {
+            name: "sleep".to_string(),
+            config: serde_json::json!({ "millis": 50, "score": 7.5 }),
+        });
+        let mut core = BenchmarkCore::new();
+        SleepRunner::register(core.registry_mut());
+        core.set_config(config);
+
+        let messages = RefCell::new(Vec::new());
+        let result = core.run_all(|overall, message| messages.borrow_mut().push((overall, message))).unwrap();
+        assert_eq!(result.extra_results["sleep"], serde_json::json!({ "score": 7.5 }));
+        assert!(result.per_test_durations[&TestType::Extra] >= 0.05);
+        let messages = messages.into_inner();
+        assert_eq!(messages.first().unwrap().1, "开始扩展测试sleep...");
+        assert_eq!(messages.last().unwrap(), &(100.0, "扩展测试sleep完成".to_string()));
+    }
+
+    #[test]
+    fn test_failed_tests_are_recorded_without_event_sink() {
+        use crate::benchmark::test_support::SleepRunner;
+
+        let mut config = BenchmarkConfig::quick();
+        config.cpu_test.enabled = false;
+        config.memory_test.enabled = false;
+        config.storage_test.enabled = false;
+        // 时限为0.1秒，休眠0.5秒的测试必然超时
+        config.per_test_timeout_factor = 0.01;
+        config.repeat = 2;
+        config.extra_tests.push(ExtraTestSpec {
+            name: "sleep".to_string(),
+            config: serde_json::json!({ "millis": 500, "score": 1.0 }),
+        });
+        let mut core = BenchmarkCore::new();
+        SleepRunner::register(core.registry_mut());
+        core.set_config(config);
+
+        let result = core.run_all(|_, _| {}).unwrap();
+        assert!(result.extra_results.is_empty());
+        for run in core.get_results() {
+            assert_eq!(run.warnings.len(), 1);
+            assert_eq!(run.warnings[0].warning_type, "test_timeout");
+        }
+        // 汇总结果合并两次运行的同一警告
+        assert_eq!(result.warnings.len(), 1);
+        let warning = &result.warnings[0];
+        assert_eq!((warning.test_type, warning.warning_type.as_str(), warning.count), (TestType::Extra, "test_timeout", 2));
+        assert_eq!(warning.severity, WarningSeverity::High);
+        assert_eq!(warning.message_key, "extra.failed");
+    }
+
+    #[test]
+    fn test_extra_tests_are_validated_against_registry() {
+        use crate::benchmark::test_support::SleepRunner;
+
+        let mut registry = BenchmarkRegistry::with_builtin();
+        SleepRunner::register(&mut registry);
+        assert_eq!(registry.names(), ["compression", "cpu", "crypto", "database", "gpu", "hashing", "image", "ipc", "json", "memory", "network", "process", "sleep", "stability", "storage", "sync"]);
+
+        let mut config = BenchmarkConfig::quick();
+        config.extra_tests = ["protobuf_decode", "cpu", "sleep"]
+            .iter()
+            .map(|name| ExtraTestSpec {
+                name: name.to_string(),
+                config: serde_json::json!({ "millis": "slow" }),
+            })
+            .collect();
+        let issues = registry.validate(&config, None).unwrap_err();
+        let fields: Vec<&str> = issues.iter().map(|issue| issue.field.as_str()).collect();
+        assert_eq!(fields, ["extra_tests[0].name", "extra_tests[1].name", "extra_tests[2].config"]);
+        assert!(issues[0].message.contains("protobuf_decode"));
+        assert!(issues.iter().all(|issue| issue.suggested_value.is_none()));
+
+        // 内置模块也可以按名称直接创建
+        let runner = registry.create("cpu", serde_json::json!({ "test_duration": 1 })).unwrap();
+        assert_eq!(runner.name(), TestType::Cpu);
+        assert_eq!(registry.create("gpu", serde_json::json!({})).unwrap().name(), TestType::Gpu);
+        assert!(registry.create("npu", serde_json::json!({})).is_err());
+    }
+
+    #[test]
+    fn test_database_test_follows_storage_target() {
+        let mut config = BenchmarkConfig::quick();
+        config.storage_test.test_file_path = Some("/data/bench.dat".to_string());
+        let spec = |config| ExtraTestSpec { name: "database".to_string(), config };
+        let target = with_storage_target(&spec(serde_json::json!({ "rows": 10 })), &config);
+        assert_eq!(target.config, serde_json::json!({ "rows": 10, "test_dir": "/data" }));
+        // 显式指定的目录和其他测试不受影响
+        let explicit = spec(serde_json::json!({ "testDir": "/fast" }));
+        assert_eq!(with_storage_target(&explicit, &config), explicit);
+        let sleep = ExtraTestSpec { name: "sleep".to_string(), config: serde_json::json!({}) };
+        assert_eq!(with_storage_target(&sleep, &config), sleep);
+    }
+
+    #[test]
+    fn test_disabled_tests_are_skipped() {
+        let mut config = BenchmarkConfig::quick();
+        config.cpu_test.enabled = false;
+        config.memory_test.enabled = false;
+        config.storage_test.enabled = false;
+        let mut core = BenchmarkCore::new();
+        core.set_config(config);
+
+        assert!(core.run_cpu_benchmark().unwrap().is_skipped());
+        assert!(core.run_memory_benchmark().unwrap().is_skipped());
+        assert!(core.run_storage_benchmark().unwrap().is_skipped());
+        let result = core.run_all(|_, _| {}).unwrap();
+        assert!(result.cpu_results.is_none() && result.memory_results.is_none() && result.storage_results.is_none());
+        assert_eq!(result.overall_score, 0.0);
+    }
+
+    #[test]
+    fn test_history_aggregation_skips_missing_categories() {
+        use crate::benchmark::test_support::sample_test_result;
+
+        let mut core = BenchmarkCore::new();
+        assert!(core.best_result().is_none());
+        assert_eq!(core.average_scores().runs, 0);
+        assert!(core.average_scores().overall_score.is_none());
+
+        let mut first = sample_test_result("2024-01-01T00:00:00Z", 300.0);
+        first.cpu_results.as_mut().unwrap().single_thread_score = 90.0;
+        let mut second = sample_test_result("2024-01-02T00:00:00Z", 600.0);
+        second.cpu_results.as_mut().unwrap().single_thread_score = 120.0;
+        second.memory_results = None;
+        let mut third = sample_test_result("2024-01-03T00:00:00Z", 450.0);
+        third.cpu_results.as_mut().unwrap().single_thread_score = 150.0;
+        third.memory_results.as_mut().unwrap().sequential_read_speed = 2000.0;
+        third.storage_results = None;
+        core.results = vec![first, second, third];
+
+        assert_eq!(core.latest_result().unwrap().timestamp, "2024-01-03T00:00:00Z");
+        assert_eq!(core.best_result().unwrap().timestamp, "2024-01-02T00:00:00Z");
+
+        let aggregate = core.average_scores();
+        assert_eq!(aggregate.runs, 3);
+        let overall = aggregate.overall_score.unwrap();
+        assert_eq!((overall.mean, overall.min, overall.max, overall.samples), (450.0, 300.0, 600.0, 3));
+        let metric = |name: &str| aggregate.metrics.iter().find(|m| m.metric == name).unwrap().clone();
+
+        let single = metric("cpu_single_thread");
+        assert_eq!((single.mean, single.min, single.max, single.samples), (120.0, 90.0, 150.0, 3));
+        // 第二次运行没有内存结果，平均值只按另外两次计算而不是当作0
+        let memory_read = metric("memory_read");
+        assert_eq!((memory_read.mean, memory_read.min, memory_read.max, memory_read.samples), (1500.0, 1000.0, 2000.0, 2));
+        let storage_write = metric("storage_write");
+        assert_eq!((storage_write.mean, storage_write.samples), (400.0, 2));
+        assert_eq!(aggregate.metrics.len(), 6);
+
+        core.clear_results();
+        assert!(core.get_results().is_empty());
+        assert!(core.latest_result().is_none());
+    }
+
+    #[test]
+    fn test_config_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = BenchmarkConfig::quick();
+        config.cpu_test.thread_count = 3;
+        config.auto_duration_target_seconds = Some(90);
+        config.extra_tests.push(ExtraTestSpec {
+            name: "sleep".to_string(),
+            config: serde_json::json!({ "millis": 5, "score": 1.5 }),
+        });
+        for name in ["config.toml", "nested/config.json"] {
+            let path = dir.path().join(name);
+            config.save_to_file(&path).unwrap();
+            let loaded = BenchmarkConfig::load_file(&path).unwrap();
+            assert!(loaded.unknown_keys.is_empty(), "{}: {:?}", name, loaded.unknown_keys);
+            assert_eq!(
+                serde_json::to_value(BenchmarkConfig::from_file(&path).unwrap()).unwrap(),
+                serde_json::to_value(&config).unwrap(),
+                "{}",
+                name
+            );
+        }
+        let err = config.save_to_file(dir.path().join("config.yaml")).unwrap_err();
+        assert!(err.to_string().contains(".toml"), "{}", err);
+    }
+
+    #[test]
+    fn test_config_file_unknown_keys_are_reported_not_fatal() {
+        let loaded = BenchmarkConfig::parse(include_str!("../../tests/fixtures/config_unknown_keys.toml"), ConfigFormat::Toml).unwrap();
+        let mut unknown = loaded.unknown_keys.clone();
+        unknown.sort();
+        assert_eq!(unknown, ["cpu_test.turbo", "cpu_tset"]);
+        assert_eq!(loaded.config.cpu_test.test_duration, 10);
+        assert_eq!(loaded.config.cpu_test.thread_count, 2);
+        assert!(loaded.config.skip_calibration);
+        // 省略的字段取标准预设
+        let standard = BenchmarkConfig::default();
+        assert!(loaded.config.cpu_test.enabled);
+        assert_eq!(loaded.config.memory_test.buffer_size, standard.memory_test.buffer_size);
+        assert_eq!(loaded.config.storage_test.file_size, standard.storage_test.file_size);
+    }
+
+    #[test]
+    fn test_config_file_type_error_names_key_and_line() {
+        let err = BenchmarkConfig::parse(include_str!("../../tests/fixtures/config_type_error.json"), ConfigFormat::Json).unwrap_err();
+        assert!(err.contains("memory_test.buffer_size"), "{}", err);
+        assert!(err.contains("第7行"), "{}", err);
+
+        let err = BenchmarkConfig::parse(include_str!("../../tests/fixtures/config_type_error.toml"), ConfigFormat::Toml).unwrap_err();
+        assert!(err.contains("storage_test.file_size"), "{}", err);
+        assert!(err.contains("第6行"), "{}", err);
+
+        let err = BenchmarkConfig::parse("[cpu_test\nduration = 1", ConfigFormat::Toml).unwrap_err();
+        assert!(err.contains("第1行"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_reports_field_paths() {
+        use crate::benchmark::test_support::sample_system_info;
+
+        type Case = (fn(&mut BenchmarkConfig), &'static [&'static str]);
+        let cases: Vec<Case> = vec![
+            (|c| c.cpu_test.test_duration = 0, &["cpu_test.test_duration"]),
+            (|c| c.cpu_test.test_duration = 7200, &["cpu_test.test_duration"]),
+            (|c| c.cpu_test.thread_count = 100_000, &["cpu_test.thread_count"]),
+            (|c| c.memory_test.buffer_size = 0, &["memory_test.buffer_size"]),
+            (|c| c.memory_test.iterations = 0, &["memory_test.iterations"]),
+            (|c| c.storage_test.file_size = 0, &["storage_test.file_size"]),
+            (|c| c.storage_test.block_size = 0, &["storage_test.block_size"]),
+            (|c| c.storage_test.block_size = 1024 * 1024, &["storage_test.block_size"]),
+            (|c| {
+                c.storage_test.file_size = 1;
+                c.storage_test.block_size = 2048;
+            }, &["storage_test.block_size"]),
+            (|c| c.auto_duration_target_seconds = Some(0), &["auto_duration_target_seconds"]),
+            (|c| c.per_test_timeout_factor = 0.0, &["per_test_timeout_factor"]),
+            (|c| c.per_test_timeout_factor = f64::NAN, &["per_test_timeout_factor"]),
+            (|c| c.monitoring_interval_ms = 0, &["monitoring_interval_ms"]),
+            (|c| _ = c.score_weights.insert("disk".to_string(), 1.0), &["score_weights.disk"]),
+            (|c| _ = c.score_weights.insert("cpu".to_string(), -1.0), &["score_weights.cpu"]),
+            (|c| c.gpu_test = Toggle::new(true, GpuTestConfig { test_duration: 0, ..Default::default() }), &["gpu_test.test_duration"]),
+            (|c| c.gpu_test = Toggle::new(true, GpuTestConfig { workloads: Vec::new(), ..Default::default() }), &["gpu_test.workloads"]),
+            (|c| c.network_test = Toggle::new(true, NetworkTestConfig { target: Some("https://example.com/".to_string()), ..Default::default() }), &["network_test.target"]),
+            (|c| c.network_test = Toggle::new(true, NetworkTestConfig { parallel_connections: 0, ..Default::default() }), &["network_test.parallel_connections"]),
+            // 本机16GB内存，缓冲区不能占满
+            (|c| c.memory_test.buffer_size = 16 * 1024, &["memory_test.buffer_size"]),
+            (|c| {
+                c.cpu_test.test_duration = 0;
+                c.memory_test.iterations = 0;
+                c.storage_test.file_size = 0;
+                c.storage_test.block_size = 0;
+            }, &["cpu_test.test_duration", "memory_test.iterations", "storage_test.file_size", "storage_test.block_size"]),
+        ];
+        let system = sample_system_info();
+        for (mutate, expected) in cases {
+            let mut config = BenchmarkConfig::quick();
+            mutate(&mut config);
+            let issues = config.validate(Some(&system)).unwrap_err();
+            let fields: Vec<&str> = issues.iter().map(|issue| issue.field.as_str()).collect();
+            assert_eq!(fields, expected);
+            assert!(issues.iter().all(|issue| issue.severity == IssueSeverity::Error && issue.suggested_value.is_some()));
+        }
+
+        // 未启用的测试不检查
+        let mut config = BenchmarkConfig::quick();
+        config.storage_test.enabled = false;
+        config.storage_test.block_size = 0;
+        assert!(config.validate(None).is_ok());
+
+        // 超出本机能力但可以运行的配置只产生警告
+        let mut config = BenchmarkConfig::quick();
+        config.cpu_test.thread_count = 64;
+        config.memory_test.buffer_size = 12 * 1024;
+        assert!(config.validate(Some(&system)).is_ok());
+        let warnings = config.issues(Some(&system));
+        assert_eq!(warnings.iter().map(|issue| issue.field.as_str()).collect::<Vec<_>>(), ["cpu_test.thread_count", "memory_test.buffer_size"]);
+        assert!(warnings.iter().all(|issue| issue.severity == IssueSeverity::Warning));
+        assert_eq!(warnings[0].suggested_value, Some(serde_json::json!(8)));
+        assert!(config.validate(None).is_ok() && config.issues(None).is_empty());
+
+        assert!(BenchmarkConfig::default().validate(Some(&system)).is_ok());
+
+        // 单独运行网络测试时使用同样的上限
+        let network = NetworkTestConfig { test_duration: MAX_NETWORK_DURATION_SECONDS + 1, parallel_connections: MAX_NETWORK_CONNECTIONS + 1, ..Default::default() };
+        let fields: Vec<String> = network_issues(&network).into_iter().map(|issue| issue.field).collect();
+        assert_eq!(fields, ["network_test.test_duration", "network_test.parallel_connections"]);
+        assert!(network_issues(&NetworkTestConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn test_watchdog_converts_runner_panics() {
+        use rayon::prelude::*;
+
+        struct PanicRunner;
+
+        impl BenchmarkRunner for PanicRunner {
+            fn name(&self) -> TestType {
+                TestType::Memory
+            }
+
+            fn estimated_duration(&self) -> u64 {
+                1
+            }
+
+            fn run(&self, _ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+                // panic发生在rayon工作线程中，由rayon传回调用线程
+                (0..64).into_par_iter().for_each(|i| {
+                    if i == 42 {
+                        panic!("worker {} failed", i);
+                    }
+                });
+                unreachable!()
+            }
+        }
+
+        let ctx = RunContext {
+            progress: Arc::new(|_, _| {}),
+            cancellation: CancellationToken::never(),
+            warnings: Arc::new(|_, _| {}),
+            issues: RunIssues::default(),
+        };
+        let err = run_with_watchdog(&PanicRunner, &ctx, Duration::from_secs(30)).unwrap_err();
+        assert!(
+            matches!(err, BenchmarkError::Internal { ref test, ref panic_message } if test == "memory" && panic_message == "worker 42 failed"),
+            "{}",
+            err
+        );
+
+        // 非字符串负载也能转换
+        let payload: Box<dyn std::any::Any + Send> = Box::new(7_u32);
+        assert!(matches!(
+            BenchmarkError::from_panic("cpu", payload.as_ref()),
+            BenchmarkError::Internal { ref panic_message, .. } if panic_message == "unknown panic"
+        ));
+    }
+
+    #[test]
+    fn test_watchdog_times_out_slow_runner() {
+        use crate::benchmark::test_support::SleepRunner;
+        use std::time::Instant;
+
+        let ctx = RunContext {
+            progress: Arc::new(|_, _| {}),
+            cancellation: CancellationToken::never(),
+            warnings: Arc::new(|_, _| {}),
+            issues: RunIssues::default(),
+        };
+        let slow = SleepRunner { millis: 400, score: 1.0 };
+        let err = run_with_watchdog(&slow, &ctx, Duration::from_millis(100)).unwrap_err();
+        assert!(matches!(err, BenchmarkError::Timeout { ref test, limit_seconds: 1 } if test == "extra"), "{}", err);
+
+        // 期限前结束的测试立即返回，不等待看门狗到期
+        let started = Instant::now();
+        let fast = SleepRunner { millis: 0, score: 2.0 };
+        assert!(run_with_watchdog(&fast, &ctx, Duration::from_secs(30)).is_ok());
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        // 会话本身已取消时不报告超时
+        let cancelled = RunContext {
+            cancellation: CancellationToken::new(|| true),
+            ..ctx.clone()
+        };
+        assert!(run_with_watchdog(&slow, &cancelled, Duration::from_millis(100)).is_ok());
+
+        // 默认时限为估计耗时的3倍，短测试按10秒估计计算
+        assert_eq!(BenchmarkConfig::default().test_timeout(&fast), Duration::from_secs(30));
+        let cpu = CpuBenchmark::new(CpuTestConfig { test_duration: 20, ..CpuTestConfig::default() });
+        assert_eq!(BenchmarkConfig::default().test_timeout(&cpu), Duration::from_secs(cpu.estimated_duration() * 3));
+    }
+
+    #[test]
+    fn test_paused_token_parks_and_clock_excludes_pause() {
+        use std::sync::atomic::AtomicBool;
+
+        let paused = Arc::new(AtomicBool::new(true));
+        let token = {
+            let paused = paused.clone();
+            CancellationToken::never().with_pause(move || paused.load(Ordering::SeqCst))
+        };
+        let clock = token.clock();
+        let resume = {
+            let paused = paused.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(300));
+                paused.store(false, Ordering::SeqCst);
+            })
+        };
+        let started = Instant::now();
+        assert!(token.check(TestType::Cpu, 10.0).is_ok());
+        resume.join().unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(300));
+        // 等待的300ms不计入，计时器只走过了几次轮询的误差
+        assert!(clock.elapsed() < Duration::from_millis(150), "{:?}", clock.elapsed());
+
+        // 暂停期间取消时立即返回Cancelled
+        let cancelled = CancellationToken::new(|| true).with_pause(|| true);
+        assert!(matches!(cancelled.check(TestType::Cpu, 10.0), Err(BenchmarkError::Cancelled { .. })));
+    }
+
+    #[test]
+    fn test_builder_sets_fields_and_validates() {
+        let config = BenchmarkConfig::builder()
+            .cpu_duration(5)
+            .cpu_threads(2)
+            .memory_buffer_mb(128)
+            .memory_iterations(3)
+            .storage_file_mb(64)
+            .storage_block_kb(16)
+            .disable_storage()
+            .auto_duration(90)
+            .skip_calibration(true)
+            .repeat(3, Aggregation::Best)
+            .per_test_timeout_factor(5.0)
+            .enable_process(50)
+            .build()
+            .unwrap();
+        assert_eq!((config.cpu_test.test_duration, config.cpu_test.thread_count), (5, 2));
+        assert_eq!((config.memory_test.buffer_size, config.memory_test.iterations), (128, 3));
+        assert_eq!((config.storage_test.file_size, config.storage_test.block_size), (64, 16));
+        assert!(config.cpu_test.enabled && config.memory_test.enabled && !config.storage_test.enabled);
+        assert_eq!(config.auto_duration_target_seconds, Some(90));
+        assert!(config.skip_calibration);
+        assert_eq!((config.repeat, config.aggregation), (3, Aggregation::Best));
+        assert_eq!(config.per_test_timeout_factor, 5.0);
+        assert_eq!(config.extra_tests[0].name, "process");
+        assert!(BenchmarkRegistry::with_builtin().validate(&config, None).is_ok());
+
+        // 未设置的字段取标准预设
+        let standard = BenchmarkConfig::builder().disable_cpu().disable_memory().build().unwrap();
+        assert_eq!(standard.storage_test.file_size, BenchmarkConfig::default().storage_test.file_size);
+        assert_eq!((standard.repeat, standard.aggregation), (1, Aggregation::Median));
+
+        let issues = BenchmarkConfig::builder()
+            .cpu_duration(0)
+            .storage_block_kb(0)
+            .repeat(0, Aggregation::Mean)
+            .build()
+            .unwrap_err();
+        let fields: Vec<&str> = issues.iter().map(|issue| issue.field.as_str()).collect();
+        assert_eq!(fields, ["repeat", "cpu_test.test_duration", "storage_test.block_size"]);
+        // 运行次数过多只是警告
+        let many = BenchmarkConfig::builder().repeat(50, Aggregation::Mean).build().unwrap();
+        assert_eq!(many.issues(None)[0].severity, IssueSeverity::Warning);
+    }
+
+    #[test]
+    fn test_default_for_system_sizes_from_hardware() {
+        use crate::benchmark::system_info::{StorageInfo, StorageType};
+        use crate::benchmark::test_support::sample_system_info;
+
+        let disk = |mount_point: &str, available: u64| StorageInfo {
+            name: mount_point.to_string(),
+            storage_type: StorageType::SSD,
+            capacity: 1024,
+            available,
+            interface: "Unknown".to_string(),
+            file_system: "ext4".to_string(),
+            mount_point: mount_point.to_string(),
+            removable: false,
+        };
+        let target = Path::new("/tmp/tauri_benchmark_test.dat");
+
+        // 16GB内存、4个物理核心；目标路径位于/tmp挂载点，剩余20GB
+        let mut system = sample_system_info();
+        system.storage = vec![disk("/", 500), disk("/tmp", 20)];
+        let config = BenchmarkConfig::default_for_system_at(&system, target);
+        assert_eq!(config.cpu_test.thread_count, 4);
+        assert_eq!(config.memory_test.buffer_size, 4096);
+        assert_eq!(config.storage_test.file_size, 2048);
+        assert!(config.validate(Some(&system)).is_ok());
+
+        // 大磁盘上测试文件不超过4GB
+        system.storage = vec![disk("/", 500)];
+        system.memory.total = 64;
+        let config = BenchmarkConfig::default_for_system_at(&system, target);
+        assert_eq!(config.storage_test.file_size, 4096);
+        assert_eq!(config.memory_test.buffer_size, 16 * 1024);
+
+        // 几乎没有剩余空间时至少保留1MB；硬件信息未知时保留标准预设
+        system.storage = vec![disk("/", 0)];
+        system.memory.total = 0;
+        system.cpu.cores = 0;
+        let config = BenchmarkConfig::default_for_system_at(&system, target);
+        assert_eq!(config.storage_test.file_size, 1);
+        assert_eq!(config.memory_test.buffer_size, BenchmarkConfig::default().memory_test.buffer_size);
+        assert_eq!(config.cpu_test.thread_count, 0);
+        system.storage.clear();
+        let config = BenchmarkConfig::default_for_system_at(&system, target);
+        assert_eq!(config.storage_test.file_size, BenchmarkConfig::default().storage_test.file_size);
+    }
+
+    #[test]
+    fn test_config_accepts_old_and_new_wire_shapes() {
+        // 统一前套件配置的格式，以及前端当前发送的驼峰格式
+        let legacy = serde_json::json!({
+            "cpu_test": { "enabled": true, "duration": 5, "thread_count": 2 },
+            "memory_test": { "enabled": false, "buffer_size": 128, "iterations": 3 },
+            "storage_test": { "enabled": true, "file_size": 64, "block_size": 16 }
+        });
+        let frontend = serde_json::json!({
+            "cpuTest": { "enabled": true, "duration": 5, "threadCount": 2 },
+            "memoryTest": { "enabled": false, "bufferSize": 128, "iterations": 3 },
+            "storageTest": { "enabled": true, "fileSize": 64, "blockSize": 16 }
+        });
+        for old in [legacy, frontend] {
+            let config: BenchmarkConfig = serde_json::from_value(old).unwrap();
+            assert!(config.cpu_test.enabled && !config.memory_test.enabled && config.storage_test.enabled);
+            assert_eq!(config.cpu_test.config, CpuTestConfig { thread_count: 2, test_duration: 5, ..CpuTestConfig::default() });
+            assert_eq!(config.memory_test.config, MemoryTestConfig { buffer_size: 128, iterations: 3, ..MemoryTestConfig::default() });
+            assert_eq!(config.storage_test.config, StorageTestConfig { file_size: 64, block_size: 16, ..StorageTestConfig::default() });
+        }
+
+        // 新格式可以设置模块配置的全部字段，序列化后enabled与模块字段平铺
+        let new = serde_json::json!({
+            "cpu_test": { "enabled": false, "thread_count": 2, "test_duration": 5, "enable_temperature_monitoring": false },
+            "memory_test": { "buffer_size": 128, "iterations": 3, "test_duration": 7, "enable_usage_monitoring": false, "interference_duration": 1 },
+            "storage_test": { "file_size": 64, "block_size": 16, "test_duration": 9, "test_file_path": "/data/bench.dat" }
+        });
+        let config: BenchmarkConfig = serde_json::from_value(new.clone()).unwrap();
+        assert!(!config.cpu_test.enabled && config.memory_test.enabled);
+        let round_trip = serde_json::to_value(&config).unwrap();
+        for test in ["cpu_test", "memory_test", "storage_test"] {
+            for (key, value) in new[test].as_object().unwrap() {
+                assert_eq!(&round_trip[test][key], value, "{}.{}", test, key);
+            }
+        }
+
+        // 套件按原样把模块配置交给各项测试，不丢弃任何字段
+        let cpu = CpuBenchmark::new(config.cpu_test.config.clone());
+        assert_eq!(cpu.get_config(), &CpuTestConfig { thread_count: 2, test_duration: 5, enable_temperature_monitoring: false });
+        let memory = MemoryBenchmark::new(config.memory_test.config.clone());
+        assert_eq!(memory.get_config(), &MemoryTestConfig {
+            buffer_size: 128,
+            iterations: 3,
+            test_duration: 7,
+            enable_usage_monitoring: false,
+            interference_duration: 1,
+        });
+        let storage = StorageBenchmark::new(config.storage_test.config.clone());
+        assert_eq!(storage.get_config(), &StorageTestConfig {
+            file_size: 64,
+            block_size: 16,
+            test_duration: 9,
+            test_file_path: Some("/data/bench.dat".to_string()),
+            small_file_count: 10_000,
+            archive: None,
+        });
+        assert_eq!(storage.get_test_file_path(), Path::new("/data/bench.dat"));
+    }
+}
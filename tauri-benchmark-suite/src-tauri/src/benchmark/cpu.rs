@@ -1,36 +1,115 @@
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, TestOutcome};
 use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::estimate::cpu_test_seconds;
+use crate::benchmark::metrics::{CpuSensors, LiveSample, MetricsSink, RateMeter, CPU_TEMPERATURE};
+use crate::i18n::Message;
+use crate::ipc::{TestResultPayload, TestType};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
-// use std::sync::{Arc, Mutex}; // 暂时不需要
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
 use std::thread;
 use rayon::prelude::*;
-use sysinfo::System;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// 负载运行期间采样CPU温度的间隔，与传感器的刷新间隔一致
+const TEMPERATURE_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// floating_point_kernel每次调用计入的操作数
+pub const FLOATING_POINT_KERNEL_OPERATIONS: u64 = 999;
+
+/// 浮点测试的计算核心。只依赖输入，同一台机器上相同的输入总是得到逐位相同的结果
+pub fn floating_point_kernel(mut result: f64) -> f64 {
+    for i in 1..1000 {
+        let x = i as f64;
+        result = result * x.sin() + x.cos().powi(2) + x.sqrt().ln();
+        
+        // 复杂浮点运算
+        result = result.exp().tanh() + (x * 3.14159).sin();
+    }
+    result
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CpuTestResult {
     pub single_thread_score: f64,
     pub multi_thread_score: f64,
     pub floating_point_score: f64,
-    pub average_temperature: f32,
-    pub max_temperature: f32,
+    #[serde(default)]
+    pub average_temperature: Option<f32>, // °C，三个阶段运行期间的CPU温度；未启用监控或读不到温度传感器时为空
+    #[serde(default)]
+    pub max_temperature: Option<f32>,
     pub test_duration: u64, // seconds
     pub operations_per_second: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// 省略的字段取默认值；别名兼容套件配置的旧格式（duration）和前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
 pub struct CpuTestConfig {
+    #[serde(alias = "threadCount")]
     pub thread_count: usize, // 0 means use all available threads
+    #[serde(alias = "duration", alias = "testDuration")]
     pub test_duration: u64, // seconds
+    #[serde(alias = "enableTemperatureMonitoring")]
     pub enable_temperature_monitoring: bool,
 }
 
+impl Default for CpuTestConfig {
+    fn default() -> Self {
+        Self {
+            thread_count: 0,
+            test_duration: 60,
+            enable_temperature_monitoring: true,
+        }
+    }
+}
+
 pub struct CpuBenchmark {
     config: CpuTestConfig,
+    metrics_sink: Option<MetricsSink>,
+    sensors: Mutex<Option<CpuSensors>>, // 首次上报时才初始化
 }
 
 impl CpuBenchmark {
     pub fn new(config: CpuTestConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            metrics_sink: None,
+            sensors: Mutex::new(None),
+        }
+    }
+
+    pub fn get_config(&self) -> &CpuTestConfig {
+        &self.config
+    }
+
+    /// 测试过程中上报实时指标（每秒操作数、频率、温度）
+    pub fn with_metrics_sink(mut self, sink: MetricsSink) -> Self {
+        self.metrics_sink = Some(sink);
+        self
+    }
+
+    // 未设置sink时不读取传感器
+    fn report_metrics(&self, phase: &'static str, phase_progress: f64, ops_per_sec: f64) {
+        let Some(sink) = &self.metrics_sink else {
+            return;
+        };
+        let (frequency_mhz, temperature_c) = self
+            .sensors
+            .lock()
+            .unwrap()
+            .get_or_insert_with(CpuSensors::new)
+            .read();
+        let mut sample = LiveSample::new(
+            phase,
+            phase_progress,
+            &[("ops_per_sec", ops_per_sec), ("frequency_mhz", frequency_mhz)],
+        );
+        if let Some(temperature) = temperature_c {
+            sample.metrics.insert(CPU_TEMPERATURE.to_string(), temperature);
+        }
+        sink(sample);
     }
 
     pub fn run_benchmark(&self) -> Result<CpuTestResult, BenchmarkError> {
@@ -39,61 +118,98 @@ impl CpuBenchmark {
 
     pub fn run_benchmark_with_progress<F>(&self, progress_callback: F) -> Result<CpuTestResult, BenchmarkError>
     where
-        F: Fn(f64, String) + Send + Sync + 'static,
+        F: Fn(f64, Message) + Send + Sync + 'static,
+    {
+        self.run_benchmark_cancellable(progress_callback, &CancellationToken::never())
+    }
+
+    /// 各阶段的计算循环在每次进度更新时检查取消（间隔不超过500ms），取消后所有线程退出
+    pub fn run_benchmark_cancellable<F>(&self, progress_callback: F, cancellation: &CancellationToken) -> Result<CpuTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message) + Send + Sync + 'static,
     {
         let test_duration = Duration::from_secs(self.config.test_duration);
-        
-        // 运行单线程测试
-        progress_callback(0.0, "开始单线程CPU测试...".to_string());
-        let single_thread_score = self.run_single_thread_test_with_progress(test_duration, &progress_callback)?;
-        
-        // 运行多线程测试
-        progress_callback(33.3, "开始多线程CPU测试...".to_string());
-        let multi_thread_score = self.run_multi_thread_test_with_progress(test_duration, &progress_callback)?;
-        
-        // 运行浮点运算测试
-        progress_callback(66.6, "开始浮点运算测试...".to_string());
-        let floating_point_score = self.run_floating_point_test_with_progress(test_duration, &progress_callback)?;
-        
-        // 监控温度（如果启用）
-        progress_callback(90.0, "收集温度数据...".to_string());
-        let (avg_temp, max_temp) = if self.config.enable_temperature_monitoring {
-            self.monitor_temperature_during_test(test_duration)?
-        } else {
-            (0.0, 0.0)
-        };
+
+        // 温度由后台线程在负载运行的同时采样，反映的是满载时的温度
+        let stop_sampling = AtomicBool::new(false);
+        let (scores, temperatures) = thread::scope(|scope| {
+            let sampler = self
+                .config
+                .enable_temperature_monitoring
+                .then(|| scope.spawn(|| sample_temperatures(&stop_sampling, TEMPERATURE_SAMPLE_INTERVAL)));
+            let scores = self.run_phases(test_duration, &progress_callback, cancellation);
+            stop_sampling.store(true, Ordering::Relaxed);
+            let temperatures = match sampler {
+                Some(sampler) => {
+                    sampler.thread().unpark();
+                    sampler.join().unwrap_or_default()
+                }
+                None => Vec::new(),
+            };
+            (scores, temperatures)
+        });
+        let (single_thread_score, multi_thread_score, floating_point_score) = scores?;
+        let (average_temperature, max_temperature) = summarize_temperatures(&temperatures);
 
         // 计算总操作数
         let operations_per_second = ((single_thread_score + multi_thread_score + floating_point_score) / 3.0) as u64;
 
-        progress_callback(100.0, "CPU测试完成".to_string());
+        progress_callback(100.0, Message::new("cpu.complete"));
 
         Ok(CpuTestResult {
             single_thread_score,
             multi_thread_score,
             floating_point_score,
-            average_temperature: avg_temp,
-            max_temperature: max_temp,
+            average_temperature,
+            max_temperature,
             test_duration: self.config.test_duration,
             operations_per_second,
         })
     }
 
+    /// 依次运行单线程、多线程和浮点三个阶段，返回各自的分数
+    fn run_phases<F>(&self, test_duration: Duration, progress_callback: &F, cancellation: &CancellationToken) -> Result<(f64, f64, f64), BenchmarkError>
+    where
+        F: Fn(f64, Message) + Sync,
+    {
+        // 运行单线程测试
+        cancellation.check(TestType::Cpu, 0.0)?;
+        progress_callback(0.0, Message::new("cpu.single_thread.start"));
+        let single_thread_score = tracing::info_span!("single_thread")
+            .in_scope(|| self.run_single_thread_test_with_progress(test_duration, progress_callback, cancellation))?;
+        
+        // 运行多线程测试
+        cancellation.check(TestType::Cpu, 33.3)?;
+        progress_callback(33.3, Message::new("cpu.multi_thread.start"));
+        let multi_thread_score = tracing::info_span!("multi_thread")
+            .in_scope(|| self.run_multi_thread_test_with_progress(test_duration, progress_callback, cancellation))?;
+        
+        // 运行浮点运算测试
+        cancellation.check(TestType::Cpu, 66.6)?;
+        progress_callback(66.6, Message::new("cpu.floating_point.start"));
+        let floating_point_score = tracing::info_span!("floating_point")
+            .in_scope(|| self.run_floating_point_test_with_progress(test_duration, progress_callback, cancellation))?;
+
+        Ok((single_thread_score, multi_thread_score, floating_point_score))
+    }
+
     fn run_single_thread_test(&self, duration: Duration) -> Result<f64, BenchmarkError> {
-        self.run_single_thread_test_with_progress(duration, &|_progress, _message| {})
+        self.run_single_thread_test_with_progress(duration, &|_progress, _message| {}, &CancellationToken::never())
     }
 
-    fn run_single_thread_test_with_progress<F>(&self, duration: Duration, progress_callback: &F) -> Result<f64, BenchmarkError>
+    fn run_single_thread_test_with_progress<F>(&self, duration: Duration, progress_callback: &F, cancellation: &CancellationToken) -> Result<f64, BenchmarkError>
     where
-        F: Fn(f64, String),
+        F: Fn(f64, Message),
     {
-        let start_time = Instant::now();
+        // 暂停的时间不计入测试时长和分数
+        let clock = cancellation.clock();
         let mut operations = 0u64;
         let mut _result = 1u64;
         let mut last_progress_update = Instant::now();
+        let mut meter = RateMeter::new();
 
         // 执行计算密集型任务
-        while start_time.elapsed() < duration {
+        while clock.elapsed() < duration {
             // 素数计算测试
             _result = self.calculate_primes_up_to(10000);
             operations += 1;
@@ -104,27 +220,31 @@ impl CpuBenchmark {
             }
             operations += 999;
 
-            // 更新进度（每100ms更新一次）
+            // 更新进度并检查取消（每100ms一次）
             if last_progress_update.elapsed() >= Duration::from_millis(100) {
-                let progress = (start_time.elapsed().as_secs_f64() / duration.as_secs_f64() * 100.0).min(100.0);
-                progress_callback(progress, format!("单线程测试进行中... ({:.1}%)", progress));
+                cancellation.check(TestType::Cpu, 0.0)?;
+                let progress = (clock.elapsed().as_secs_f64() / duration.as_secs_f64() * 100.0).min(100.0);
+                progress_callback(progress, Message::progress("cpu.single_thread.progress", progress));
+                self.report_metrics("single_thread", progress, meter.rate(operations as f64));
                 last_progress_update = Instant::now();
             }
         }
 
-        let elapsed = start_time.elapsed().as_secs_f64();
+        let elapsed = clock.elapsed().as_secs_f64();
         let score = operations as f64 / elapsed;
+        self.report_metrics("single_thread", 100.0, score);
+        tracing::debug!(operations, elapsed_seconds = elapsed, score, "单线程测试完成");
         
         Ok(score)
     }
 
     fn run_multi_thread_test(&self, duration: Duration) -> Result<f64, BenchmarkError> {
-        self.run_multi_thread_test_with_progress(duration, &|_progress, _message| {})
+        self.run_multi_thread_test_with_progress(duration, &|_progress, _message| {}, &CancellationToken::never())
     }
 
-    fn run_multi_thread_test_with_progress<F>(&self, duration: Duration, progress_callback: &F) -> Result<f64, BenchmarkError>
+    fn run_multi_thread_test_with_progress<F>(&self, duration: Duration, progress_callback: &F, cancellation: &CancellationToken) -> Result<f64, BenchmarkError>
     where
-        F: Fn(f64, String) + Sync,
+        F: Fn(f64, Message) + Sync,
     {
         let thread_count = if self.config.thread_count == 0 {
             num_cpus::get()
@@ -132,10 +252,15 @@ impl CpuBenchmark {
             self.config.thread_count
         };
 
-        let start_time = Instant::now();
+        // 暂停的时间不计入测试时长和分数
+        let clock = cancellation.clock();
         let test_duration = duration;
 
-        progress_callback(0.0, format!("多线程测试开始 (使用{}个线程)...", thread_count));
+        progress_callback(0.0, Message::new("cpu.multi_thread.threads").param("threads", thread_count));
+
+        // 实时指标按所有线程的累计操作数计算
+        let shared_operations = AtomicU64::new(0);
+        let meter = Mutex::new(RateMeter::new());
 
         // 使用简单的并行计算避免溢出
         let chunk_size = 100u64;
@@ -143,10 +268,10 @@ impl CpuBenchmark {
             .into_par_iter()
             .map(|thread_id| {
                 let mut local_operations = 0u64;
-                let thread_start = Instant::now();
+                let thread_clock = cancellation.clock();
                 let mut last_progress_update = Instant::now();
                 
-                while thread_start.elapsed() < test_duration {
+                while thread_clock.elapsed() < test_duration {
                     // 简单的并行计算密集型任务
                     let _result: u64 = (0..chunk_size)
                         .into_par_iter()
@@ -161,92 +286,70 @@ impl CpuBenchmark {
                         .reduce(|| 0, |a, b| (a + b) % 1000000);
                     
                     local_operations = local_operations.saturating_add(chunk_size);
-
-                    // 只让第一个线程报告进度，避免过多的回调
-                    if thread_id == 0 && last_progress_update.elapsed() >= Duration::from_millis(200) {
-                        let progress = (thread_start.elapsed().as_secs_f64() / test_duration.as_secs_f64() * 100.0).min(100.0);
-                        progress_callback(progress, format!("多线程测试进行中... ({:.1}%)", progress));
+                    shared_operations.fetch_add(chunk_size, Ordering::Relaxed);
+
+                    // 每个线程各自检查取消，只让第一个线程报告进度，避免过多的回调
+                    if last_progress_update.elapsed() >= Duration::from_millis(200) {
+                        cancellation.check(TestType::Cpu, 33.3)?;
+                        if thread_id == 0 {
+                            let progress = (thread_clock.elapsed().as_secs_f64() / test_duration.as_secs_f64() * 100.0).min(100.0);
+                            progress_callback(progress, Message::progress("cpu.multi_thread.progress", progress));
+                            let ops_per_sec = meter.lock().unwrap().rate(shared_operations.load(Ordering::Relaxed) as f64);
+                            self.report_metrics("multi_thread", progress, ops_per_sec);
+                        }
                         last_progress_update = Instant::now();
                     }
                 }
                 
-                local_operations
+                Ok(local_operations)
             })
-            .reduce(|| 0, |a, b| a.saturating_add(b));
+            .try_reduce(|| 0, |a, b| Ok(a.saturating_add(b)))?;
 
-        let elapsed = start_time.elapsed().as_secs_f64();
+        let elapsed = clock.elapsed().as_secs_f64();
         let score = total_operations as f64 / elapsed;
+        self.report_metrics("multi_thread", 100.0, score);
+        tracing::debug!(threads = thread_count, operations = total_operations, elapsed_seconds = elapsed, score, "多线程测试完成");
         
         Ok(score)
     }
 
     fn run_floating_point_test(&self, duration: Duration) -> Result<f64, BenchmarkError> {
-        self.run_floating_point_test_with_progress(duration, &|_progress, _message| {})
+        self.run_floating_point_test_with_progress(duration, &|_progress, _message| {}, &CancellationToken::never())
     }
 
-    fn run_floating_point_test_with_progress<F>(&self, duration: Duration, progress_callback: &F) -> Result<f64, BenchmarkError>
+    fn run_floating_point_test_with_progress<F>(&self, duration: Duration, progress_callback: &F, cancellation: &CancellationToken) -> Result<f64, BenchmarkError>
     where
-        F: Fn(f64, String),
+        F: Fn(f64, Message),
     {
-        let start_time = Instant::now();
+        // 暂停的时间不计入测试时长和分数
+        let clock = cancellation.clock();
         let mut operations = 0u64;
         let mut result = 1.0f64;
         let mut last_progress_update = Instant::now();
+        let mut meter = RateMeter::new();
 
-        while start_time.elapsed() < duration {
-            // 浮点数学运算测试
-            for i in 1..1000 {
-                let x = i as f64;
-                result = result * x.sin() + x.cos().powi(2) + x.sqrt().ln();
-                
-                // 复杂浮点运算
-                result = result.exp().tanh() + (x * 3.14159).sin();
-            }
-            operations += 999;
+        while clock.elapsed() < duration {
+            result = floating_point_kernel(result);
+            operations += FLOATING_POINT_KERNEL_OPERATIONS;
 
-            // 更新进度（每150ms更新一次）
+            // 更新进度并检查取消（每150ms一次）
             if last_progress_update.elapsed() >= Duration::from_millis(150) {
-                let progress = (start_time.elapsed().as_secs_f64() / duration.as_secs_f64() * 100.0).min(100.0);
-                progress_callback(progress, format!("浮点运算测试进行中... ({:.1}%)", progress));
+                cancellation.check(TestType::Cpu, 66.6)?;
+                let progress = (clock.elapsed().as_secs_f64() / duration.as_secs_f64() * 100.0).min(100.0);
+                progress_callback(progress, Message::progress("cpu.floating_point.progress", progress));
+                self.report_metrics("floating_point", progress, meter.rate(operations as f64));
                 last_progress_update = Instant::now();
             }
         }
 
-        let elapsed = start_time.elapsed().as_secs_f64();
+        let elapsed = clock.elapsed().as_secs_f64();
         let score = operations as f64 / elapsed;
+        self.report_metrics("floating_point", 100.0, score);
+        tracing::debug!(operations, elapsed_seconds = elapsed, score, "浮点运算测试完成");
         
         Ok(score)
     }
 
-    fn monitor_temperature_during_test(&self, duration: Duration) -> Result<(f32, f32), BenchmarkError> {
-        let mut sys = System::new_all();
-        let mut temperatures = Vec::new();
-        let start_time = Instant::now();
-        let sample_interval = Duration::from_millis(500); // 每500ms采样一次
-
-        while start_time.elapsed() < duration {
-            sys.refresh_cpu_all();
-            
-            // 收集所有CPU核心的温度
-            for cpu in sys.cpus() {
-                // 注意：sysinfo可能不提供温度信息，这里使用CPU使用率作为替代指标
-                let usage = cpu.cpu_usage();
-                temperatures.push(usage);
-            }
-            
-            thread::sleep(sample_interval);
-        }
-
-        if temperatures.is_empty() {
-            return Ok((0.0, 0.0));
-        }
-
-        let avg_temp = temperatures.iter().sum::<f32>() / temperatures.len() as f32;
-        let max_temp = temperatures.iter().fold(0.0f32, |a, &b| a.max(b));
-
-        Ok((avg_temp, max_temp))
-    }
-
     // 辅助函数：计算素数
     fn calculate_primes_up_to(&self, limit: u64) -> u64 {
         let mut count = 0;
@@ -278,6 +381,46 @@ impl CpuBenchmark {
         true
     }
 }
+
+/// 在stop之前每隔interval读取一次CPU温度，读不到温度传感器时返回空。
+/// 调用方设置stop后unpark采样线程，使其立即结束
+fn sample_temperatures(stop: &AtomicBool, interval: Duration) -> Vec<f64> {
+    let mut sensors = CpuSensors::new();
+    let mut samples = Vec::new();
+    while !stop.load(Ordering::Relaxed) {
+        if let (_, Some(temperature)) = sensors.read() {
+            samples.push(temperature);
+        }
+        thread::park_timeout(interval);
+    }
+    samples
+}
+
+/// (平均温度, 最高温度)，没有采样时都为空
+fn summarize_temperatures(samples: &[f64]) -> (Option<f32>, Option<f32>) {
+    if samples.is_empty() {
+        return (None, None);
+    }
+    let average = samples.iter().sum::<f64>() / samples.len() as f64;
+    let max = samples.iter().copied().fold(f64::MIN, f64::max);
+    (Some(average as f32), Some(max as f32))
+}
+
+impl BenchmarkRunner for CpuBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Cpu
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        cpu_test_seconds(self.config.test_duration).ceil() as u64
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        self.run_benchmark_cancellable(ctx.progress_callback(), &ctx.cancellation)
+            .map(|result| TestOutcome::Completed(TestResultPayload::Cpu(result)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -399,14 +542,21 @@ mod tests {
         };
         
         let benchmark = CpuBenchmark::new(config);
-        let result = benchmark.monitor_temperature_during_test(Duration::from_millis(100));
-        
-        assert!(result.is_ok());
-        let (avg_temp, max_temp) = result.unwrap();
+        let result = benchmark.run_benchmark().unwrap();
         
-        // 温度值应该是合理的范围（这里使用CPU使用率作为替代）
-        assert!(avg_temp >= 0.0);
-        assert!(max_temp >= avg_temp);
+        // 温度在负载期间采样；虚拟机和容器中通常没有温度传感器，此时为空而不是0
+        match (result.average_temperature, result.max_temperature) {
+            (Some(average), Some(max)) => {
+                assert!((0.0..=120.0).contains(&average), "{}°C", average);
+                assert!((0.0..=120.0).contains(&max), "{}°C", max);
+                assert!(max >= average);
+            }
+            (None, None) => {}
+            other => panic!("平均和最高温度应同时存在或同时为空: {:?}", other),
+        }
+
+        assert_eq!(summarize_temperatures(&[]), (None, None));
+        assert_eq!(summarize_temperatures(&[40.0, 60.0, 50.0]), (Some(50.0), Some(60.0)));
     }
 
     #[test]
@@ -424,4 +574,27 @@ mod tests {
         let score = result.unwrap();
         assert!(score > 0.0, "自动线程数测试分数应该大于0");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_cancellation_stops_running_phase() {
+        let config = CpuTestConfig {
+            thread_count: 2,
+            test_duration: 60,
+            enable_temperature_monitoring: false,
+        };
+        // 多线程阶段开始后取消，所有线程都应在下一次检查时退出
+        let started = Instant::now();
+        let token = CancellationToken::new(move || started.elapsed() > Duration::from_millis(300));
+        let error = CpuBenchmark::new(config.clone())
+            .run_multi_thread_test_with_progress(Duration::from_secs(60), &|_, _| {}, &token)
+            .unwrap_err();
+        assert_eq!(error.code(), "CANCELLED");
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let started = Instant::now();
+        let token = CancellationToken::new(move || started.elapsed() > Duration::from_millis(300));
+        let error = CpuBenchmark::new(config).run_benchmark_cancellable(|_, _| {}, &token).unwrap_err();
+        assert!(matches!(error, BenchmarkError::Cancelled { at_progress, .. } if at_progress == 0.0));
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
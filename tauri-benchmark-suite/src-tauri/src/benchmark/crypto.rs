@@ -0,0 +1,295 @@
+//! 加密和哈希吞吐：AES-256-GCM、ChaCha20-Poly1305的加密和解密，SHA-256、BLAKE3的哈希，
+//! 分别以小消息（TLS记录）和大消息（磁盘加密块）测量，并按CPU的指令集扩展标出是否有硬件加速
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, TestOutcome};
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::system_info::detect_cpu_features;
+use crate::i18n::Message;
+use crate::ipc::{ExtraTestResult, TestResultPayload, TestType};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, CHACHA20_POLY1305};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// 在BenchmarkRegistry中的注册名，结果记录在extra_results下的同名键中
+pub const CRYPTO_TEST_NAME: &str = "crypto";
+
+const KEY: [u8; 32] = [0x42; 32]; // 测试用的固定密钥
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CryptoAlgorithm {
+    Aes256Gcm,
+    ChaCha20Poly1305,
+    Sha256,
+    Blake3,
+}
+
+impl CryptoAlgorithm {
+    pub const ALL: [CryptoAlgorithm; 4] = [
+        CryptoAlgorithm::Aes256Gcm,
+        CryptoAlgorithm::ChaCha20Poly1305,
+        CryptoAlgorithm::Sha256,
+        CryptoAlgorithm::Blake3,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CryptoAlgorithm::Aes256Gcm => "aes_256_gcm",
+            CryptoAlgorithm::ChaCha20Poly1305 => "chacha20_poly1305",
+            CryptoAlgorithm::Sha256 => "sha256",
+            CryptoAlgorithm::Blake3 => "blake3",
+        }
+    }
+
+    fn is_aead(self) -> bool {
+        matches!(self, CryptoAlgorithm::Aes256Gcm | CryptoAlgorithm::ChaCha20Poly1305)
+    }
+
+    /// 本机是否有该算法专用的指令。ChaCha20和BLAKE3只用通用的SIMD，不算硬件加速
+    pub fn hardware_accelerated(self, cpu_features: &[String]) -> bool {
+        let has = |feature: &str| cpu_features.iter().any(|f| f == feature);
+        match self {
+            CryptoAlgorithm::Aes256Gcm => has("aes") && (has("pclmulqdq") || has("pmull")),
+            CryptoAlgorithm::Sha256 => has("sha") || has("sha2"),
+            CryptoAlgorithm::ChaCha20Poly1305 | CryptoAlgorithm::Blake3 => false,
+        }
+    }
+}
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct CryptoTestConfig {
+    #[serde(alias = "messageSizes")]
+    pub message_sizes: Vec<usize>, // KB
+    #[serde(alias = "dataSize")]
+    pub data_size: usize, // MB，每种算法和消息大小处理的数据量
+    pub algorithms: Vec<CryptoAlgorithm>,
+}
+
+impl Default for CryptoTestConfig {
+    fn default() -> Self {
+        Self {
+            message_sizes: vec![1, 1024],
+            data_size: 64,
+            algorithms: CryptoAlgorithm::ALL.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CryptoMeasurement {
+    pub algorithm: CryptoAlgorithm,
+    pub message_size: usize, // bytes
+    pub throughput: f64, // MB/s，加密或哈希
+    pub decrypt_throughput: Option<f64>, // MB/s，哈希算法为空
+    pub hardware_accelerated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CryptoTestResult {
+    pub measurements: Vec<CryptoMeasurement>,
+    pub cpu_features: Vec<String>, // 判断硬件加速所依据的指令集扩展
+    pub test_duration: f64, // seconds
+}
+
+fn aead_key(algorithm: CryptoAlgorithm) -> LessSafeKey {
+    let algorithm = match algorithm {
+        CryptoAlgorithm::ChaCha20Poly1305 => &CHACHA20_POLY1305,
+        _ => &AES_256_GCM,
+    };
+    LessSafeKey::new(UnboundKey::new(algorithm, &KEY).expect("密钥长度与算法一致"))
+}
+
+// 每条消息使用不同的nonce，与真实的TLS记录一致
+fn nonce(counter: usize) -> Nonce {
+    let mut bytes = [0u8; 12];
+    bytes[4..].copy_from_slice(&(counter as u64).to_le_bytes());
+    Nonce::assume_unique_for_key(bytes)
+}
+
+fn crypto_error(algorithm: CryptoAlgorithm, what: &str) -> BenchmarkError {
+    BenchmarkError::CryptoTestError(format!("{}{}", algorithm.as_str(), what))
+}
+
+/// 加密count条消息后逐条解密，解密结果必须与明文一致。返回加密和解密的耗时（秒）
+fn measure_aead(algorithm: CryptoAlgorithm, message: &[u8], count: usize) -> Result<(f64, f64), BenchmarkError> {
+    let key = aead_key(algorithm);
+    let mut sealed = Vec::with_capacity(count);
+    let started = Instant::now();
+    for i in 0..count {
+        let mut buffer = message.to_vec();
+        key.seal_in_place_append_tag(nonce(i), Aad::empty(), &mut buffer)
+            .map_err(|_| crypto_error(algorithm, "加密失败"))?;
+        sealed.push(buffer);
+    }
+    let encrypt_seconds = started.elapsed().as_secs_f64();
+
+    let started = Instant::now();
+    for (i, buffer) in sealed.iter_mut().enumerate() {
+        let plaintext = key
+            .open_in_place(nonce(i), Aad::empty(), buffer)
+            .map_err(|_| crypto_error(algorithm, "解密失败，密文或认证标签不一致"))?;
+        if plaintext != message {
+            return Err(crypto_error(algorithm, "解密结果与明文不一致"));
+        }
+    }
+    Ok((encrypt_seconds, started.elapsed().as_secs_f64()))
+}
+
+/// 哈希count条消息，返回耗时（秒）。摘要折叠后交给black_box，防止计算被省略
+fn measure_hash(algorithm: CryptoAlgorithm, message: &[u8], count: usize) -> f64 {
+    let started = Instant::now();
+    let mut fold = 0u8;
+    for _ in 0..count {
+        fold ^= match algorithm {
+            CryptoAlgorithm::Sha256 => ring::digest::digest(&ring::digest::SHA256, message).as_ref()[0],
+            _ => blake3::hash(message).as_bytes()[0],
+        };
+    }
+    std::hint::black_box(fold);
+    started.elapsed().as_secs_f64()
+}
+
+pub struct CryptoBenchmark {
+    config: CryptoTestConfig,
+    cpu_features: Vec<String>,
+}
+
+impl CryptoBenchmark {
+    pub fn new(config: CryptoTestConfig) -> Self {
+        Self {
+            config,
+            cpu_features: detect_cpu_features(),
+        }
+    }
+
+    /// 使用系统信息中的指令集扩展判断硬件加速，如分析导入的结果时
+    pub fn with_cpu_features(mut self, cpu_features: Vec<String>) -> Self {
+        self.cpu_features = cpu_features;
+        self
+    }
+
+    pub fn get_config(&self) -> &CryptoTestConfig {
+        &self.config
+    }
+
+    pub fn run_benchmark(&self) -> Result<CryptoTestResult, BenchmarkError> {
+        self.run_benchmark_cancellable(|_progress, _message| {}, &CancellationToken::never())
+    }
+
+    /// 按算法依次测量各消息大小，每项测量之间检查取消
+    pub fn run_benchmark_cancellable<F>(&self, progress_callback: F, cancellation: &CancellationToken) -> Result<CryptoTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message),
+    {
+        let started = Instant::now();
+        let total = (self.config.algorithms.len() * self.config.message_sizes.len()).max(1);
+        let mut measurements = Vec::with_capacity(total);
+        for &algorithm in &self.config.algorithms {
+            for &size_kb in &self.config.message_sizes {
+                let progress = measurements.len() as f64 / total as f64 * 100.0;
+                cancellation.check(TestType::Extra, progress)?;
+                progress_callback(
+                    progress,
+                    Message::progress("crypto.progress", progress)
+                        .param("algorithm", algorithm.as_str())
+                        .param("size", size_kb),
+                );
+
+                let message_size = size_kb.max(1) * 1024;
+                let message: Vec<u8> = (0..message_size).map(|i| i as u8).collect();
+                let count = (self.config.data_size * 1024 * 1024 / message_size).max(1);
+                let megabytes = (message_size * count) as f64 / (1024.0 * 1024.0);
+                let (seconds, decrypt_seconds) = if algorithm.is_aead() {
+                    let (encrypt, decrypt) = measure_aead(algorithm, &message, count)?;
+                    (encrypt, Some(decrypt))
+                } else {
+                    (measure_hash(algorithm, &message, count), None)
+                };
+                measurements.push(CryptoMeasurement {
+                    algorithm,
+                    message_size,
+                    throughput: megabytes / seconds,
+                    decrypt_throughput: decrypt_seconds.map(|seconds| megabytes / seconds),
+                    hardware_accelerated: algorithm.hardware_accelerated(&self.cpu_features),
+                });
+            }
+        }
+        progress_callback(100.0, Message::new("crypto.complete"));
+        Ok(CryptoTestResult {
+            measurements,
+            cpu_features: self.cpu_features.clone(),
+            test_duration: started.elapsed().as_secs_f64(),
+        })
+    }
+}
+
+impl BenchmarkRunner for CryptoBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Extra
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        // 按最慢的情况（无硬件加速、小消息）约200MB/s估计，AEAD要加密和解密两遍
+        let passes: usize = self.config.algorithms.iter().map(|algorithm| if algorithm.is_aead() { 2 } else { 1 }).sum();
+        let megabytes = (self.config.data_size * self.config.message_sizes.len() * passes) as f64;
+        (megabytes / 200.0).ceil().max(1.0) as u64
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        let result = self.run_benchmark_cancellable(ctx.progress_callback(), &ctx.cancellation)?;
+        Ok(TestOutcome::Completed(TestResultPayload::Extra(ExtraTestResult {
+            name: CRYPTO_TEST_NAME.to_string(),
+            result: serde_json::to_value(&result)
+                .map_err(|e| BenchmarkError::DataSaveError(format!("测试结果序列化失败: {}", e)))?,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aead_round_trips_and_detects_tampering() {
+        let message = b"benchmark payload".repeat(100);
+        for algorithm in [CryptoAlgorithm::Aes256Gcm, CryptoAlgorithm::ChaCha20Poly1305] {
+            let (encrypt, decrypt) = measure_aead(algorithm, &message, 4).unwrap();
+            assert!(encrypt > 0.0 && decrypt > 0.0);
+
+            let key = aead_key(algorithm);
+            let mut buffer = message.clone();
+            key.seal_in_place_append_tag(nonce(0), Aad::empty(), &mut buffer).unwrap();
+            assert_ne!(&buffer[..message.len()], &message[..]);
+            assert_eq!(buffer.len(), message.len() + key.algorithm().tag_len());
+            buffer[0] ^= 1;
+            assert!(key.open_in_place(nonce(0), Aad::empty(), &mut buffer).is_err());
+        }
+    }
+
+    #[test]
+    fn test_throughputs_and_acceleration_follow_cpu_features() {
+        let config = CryptoTestConfig {
+            message_sizes: vec![1, 64],
+            data_size: 1,
+            ..CryptoTestConfig::default()
+        };
+        let result = CryptoBenchmark::new(config.clone()).run_benchmark().unwrap();
+        assert_eq!(result.measurements.len(), 8);
+        assert_eq!(result.cpu_features, detect_cpu_features());
+        for measurement in &result.measurements {
+            assert!(measurement.throughput > 0.0, "{:?}", measurement);
+            assert_eq!(measurement.decrypt_throughput.is_some(), measurement.algorithm.is_aead());
+            assert_eq!(measurement.hardware_accelerated, measurement.algorithm.hardware_accelerated(&detect_cpu_features()));
+        }
+
+        // 指定的指令集扩展决定报告的加速情况
+        let features = vec!["aes".to_string(), "pclmulqdq".to_string()];
+        let result = CryptoBenchmark::new(config).with_cpu_features(features).run_benchmark().unwrap();
+        let accelerated: Vec<_> = result.measurements.iter().filter(|m| m.hardware_accelerated).map(|m| m.algorithm).collect();
+        assert_eq!(accelerated, [CryptoAlgorithm::Aes256Gcm, CryptoAlgorithm::Aes256Gcm]);
+        assert!(!CryptoAlgorithm::Sha256.hardware_accelerated(&["aes".to_string()]));
+        assert!(CryptoAlgorithm::Sha256.hardware_accelerated(&["sha2".to_string()]));
+    }
+}
@@ -0,0 +1,449 @@
+//! 嵌入式数据库负载：在临时SQLite文件上运行固定的负载（分事务批量插入、按索引点查、范围扫描、批量更新），
+//! 分别在WAL和回滚日志模式下测量每个阶段的每秒操作数和p95延迟。Tauri应用几乎都内嵌SQLite，
+//! 这项测试比顺序读写更接近应用的实际存储负载
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, TestOutcome};
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::metrics::percentile;
+use crate::benchmark::storage::default_test_file_path;
+use crate::i18n::Message;
+use crate::ipc::{ExtraTestResult, TestResultPayload, TestType};
+use rusqlite::{params, Connection};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// 在BenchmarkRegistry中的注册名，结果记录在extra_results下的同名键中
+pub const DATABASE_TEST_NAME: &str = "database";
+
+const KEY_MULTIPLIER: i64 = 48_271; // 与行数互质时把行号打散为不重复的键，点查不按插入顺序命中
+const SCAN_ROWS: i64 = 1000; // 每次范围扫描覆盖的键数
+const ESTIMATED_ROWS_PER_SECOND: f64 = 50_000.0; // 估计耗时用，包含每个事务的fsync
+
+// SQLite的日志模式，off对应传统的回滚日志（DELETE）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalMode {
+    Wal,
+    Delete,
+}
+
+impl JournalMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            JournalMode::Wal => "wal",
+            JournalMode::Delete => "delete",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DatabasePhase {
+    Insert, // 每个事务插入batch_size行，延迟按事务计
+    PointQuery, // 按索引列查询单行
+    RangeScan, // 按索引列扫描连续的1000个键
+    Update, // 每个事务按主键更新batch_size行，延迟按事务计
+}
+
+impl DatabasePhase {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DatabasePhase::Insert => "insert",
+            DatabasePhase::PointQuery => "point_query",
+            DatabasePhase::RangeScan => "range_scan",
+            DatabasePhase::Update => "update",
+        }
+    }
+}
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct DatabaseTestConfig {
+    pub rows: usize,
+    #[serde(alias = "batchSize")]
+    pub batch_size: usize, // 每个插入和更新事务的行数
+    #[serde(alias = "pointQueries")]
+    pub point_queries: usize,
+    #[serde(alias = "rangeScans")]
+    pub range_scans: usize,
+    #[serde(alias = "journalModes")]
+    pub journal_modes: Vec<JournalMode>,
+    #[serde(alias = "testDir")]
+    pub test_dir: Option<String>, // 数据库文件所在目录，为空时与存储测试的默认文件相同；套件中取存储测试的目标目录
+}
+
+impl Default for DatabaseTestConfig {
+    fn default() -> Self {
+        Self {
+            rows: 100_000,
+            batch_size: 1000,
+            point_queries: 10_000,
+            range_scans: 100,
+            journal_modes: vec![JournalMode::Wal, JournalMode::Delete],
+            test_dir: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PhaseResult {
+    pub phase: DatabasePhase,
+    pub operations: usize, // 插入和更新为行数，查询和扫描为语句数
+    pub ops_per_second: f64,
+    pub p95_latency_ms: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DatabaseRun {
+    pub journal_mode: String, // SQLite实际采用的日志模式，文件系统不支持WAL时可能与请求的不同
+    pub rows: usize, // 插入阶段结束后表中的行数
+    pub phases: Vec<PhaseResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DatabaseTestResult {
+    pub runs: Vec<DatabaseRun>,
+    pub test_duration: f64, // seconds
+}
+
+impl DatabaseTestResult {
+    /// 各日志模式和阶段每秒千次操作数的平均值，计入总体评分的存储部分
+    pub fn score(&self) -> f64 {
+        let phases: Vec<f64> = self
+            .runs
+            .iter()
+            .flat_map(|run| run.phases.iter().map(|phase| phase.ops_per_second / 1000.0))
+            .collect();
+        if phases.is_empty() {
+            0.0
+        } else {
+            phases.iter().sum::<f64>() / phases.len() as f64
+        }
+    }
+}
+
+fn db_error(e: rusqlite::Error) -> BenchmarkError {
+    BenchmarkError::DatabaseTestError(e.to_string())
+}
+
+/// 数据库文件及SQLite在旁边创建的日志文件
+fn database_files(path: &Path) -> Vec<PathBuf> {
+    let mut files = vec![path.to_path_buf()];
+    for suffix in ["-wal", "-shm", "-journal"] {
+        let mut name = path.as_os_str().to_owned();
+        name.push(suffix);
+        files.push(PathBuf::from(name));
+    }
+    files
+}
+
+/// 析构时删除数据库及其日志文件，测试出错、取消或panic时也会执行。须在连接之前声明，确保连接先关闭
+struct TempDatabase(PathBuf);
+
+impl Drop for TempDatabase {
+    fn drop(&mut self) {
+        for path in database_files(&self.0) {
+            if path.is_file() {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+}
+
+// 一个阶段的计时：每个操作的延迟和阶段总耗时
+struct PhaseTimer {
+    started: Instant,
+    latencies: Vec<f64>,
+}
+
+impl PhaseTimer {
+    fn start() -> Self {
+        Self {
+            started: Instant::now(),
+            latencies: Vec::new(),
+        }
+    }
+
+    fn time<T>(&mut self, op: impl FnOnce() -> Result<T, BenchmarkError>) -> Result<T, BenchmarkError> {
+        let started = Instant::now();
+        let value = op()?;
+        self.latencies.push(started.elapsed().as_secs_f64() * 1000.0);
+        Ok(value)
+    }
+
+    fn finish(mut self, phase: DatabasePhase, operations: usize) -> PhaseResult {
+        let seconds = self.started.elapsed().as_secs_f64();
+        self.latencies.sort_by(|a, b| a.total_cmp(b));
+        PhaseResult {
+            phase,
+            operations,
+            ops_per_second: if seconds > 0.0 { operations as f64 / seconds } else { 0.0 },
+            p95_latency_ms: if self.latencies.is_empty() { 0.0 } else { percentile(&self.latencies, 95.0) },
+        }
+    }
+}
+
+pub struct DatabaseBenchmark {
+    config: DatabaseTestConfig,
+}
+
+impl DatabaseBenchmark {
+    pub fn new(config: DatabaseTestConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn get_config(&self) -> &DatabaseTestConfig {
+        &self.config
+    }
+
+    pub fn database_path(&self, mode: JournalMode) -> PathBuf {
+        let dir = match &self.config.test_dir {
+            Some(dir) => PathBuf::from(dir),
+            None => default_test_file_path().parent().map(Path::to_path_buf).unwrap_or_default(),
+        };
+        dir.join(format!("tauri_benchmark_db_{}.sqlite", mode.as_str()))
+    }
+
+    pub fn run_benchmark(&self) -> Result<DatabaseTestResult, BenchmarkError> {
+        self.run_benchmark_cancellable(|_progress, _message| {}, &CancellationToken::never())
+    }
+
+    /// 每种日志模式使用新的数据库文件，跑完即删除
+    pub fn run_benchmark_cancellable<F>(&self, progress_callback: F, cancellation: &CancellationToken) -> Result<DatabaseTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message),
+    {
+        let started = Instant::now();
+        let modes = self.config.journal_modes.len().max(1);
+        let mut runs = Vec::with_capacity(modes);
+        for (index, mode) in self.config.journal_modes.iter().copied().enumerate() {
+            let span = (index as f64 / modes as f64 * 100.0, 100.0 / modes as f64);
+            runs.push(self.run_mode(mode, span, &progress_callback, cancellation)?);
+        }
+        progress_callback(100.0, Message::new("database.complete"));
+        Ok(DatabaseTestResult {
+            runs,
+            test_duration: started.elapsed().as_secs_f64(),
+        })
+    }
+
+    fn run_mode<F>(&self, mode: JournalMode, (base, width): (f64, f64), progress_callback: &F, cancellation: &CancellationToken) -> Result<DatabaseRun, BenchmarkError>
+    where
+        F: Fn(f64, Message),
+    {
+        let rows = self.config.rows.max(1);
+        let batch_size = self.config.batch_size.max(1);
+        let path = self.database_path(mode);
+        let guard = TempDatabase(path.clone());
+        drop(TempDatabase(path.clone())); // 清理上次中断留下的文件
+
+        let mut connection = Connection::open(&path).map_err(db_error)?;
+        let journal_mode: String = connection
+            .query_row(&format!("PRAGMA journal_mode={}", mode.as_str()), [], |row| row.get(0))
+            .map_err(db_error)?;
+        connection
+            .execute_batch(
+                "CREATE TABLE bench (id INTEGER PRIMARY KEY, key INTEGER NOT NULL, value REAL NOT NULL, payload TEXT NOT NULL);
+                 CREATE INDEX bench_key ON bench(key);",
+            )
+            .map_err(db_error)?;
+
+        let phase_progress = |phase: DatabasePhase, fraction: f64| {
+            let progress = base + width * fraction;
+            cancellation.check(TestType::Extra, progress)?;
+            progress_callback(
+                progress,
+                Message::progress("database.progress", progress)
+                    .param("phase", phase.as_str())
+                    .param("mode", mode.as_str()),
+            );
+            Ok::<(), BenchmarkError>(())
+        };
+        let key_of = |id: i64| id * KEY_MULTIPLIER % rows as i64;
+        let mut phases = Vec::with_capacity(4);
+
+        // 插入：key为打散后的行号，payload模拟短文本列
+        phase_progress(DatabasePhase::Insert, 0.0)?;
+        let mut timer = PhaseTimer::start();
+        for batch_start in (0..rows).step_by(batch_size) {
+            if batch_start > 0 {
+                phase_progress(DatabasePhase::Insert, 0.4 * batch_start as f64 / rows as f64)?;
+            }
+            let batch_end = (batch_start + batch_size).min(rows);
+            timer.time(|| {
+                let tx = connection.transaction().map_err(db_error)?;
+                {
+                    let mut insert = tx
+                        .prepare_cached("INSERT INTO bench (id, key, value, payload) VALUES (?1, ?2, ?3, ?4)")
+                        .map_err(db_error)?;
+                    for id in batch_start as i64..batch_end as i64 {
+                        insert
+                            .execute(params![id, key_of(id), id as f64 * 0.5, format!("row-{:08}", id)])
+                            .map_err(db_error)?;
+                    }
+                }
+                tx.commit().map_err(db_error)
+            })?;
+        }
+        phases.push(timer.finish(DatabasePhase::Insert, rows));
+
+        let count: i64 = connection.query_row("SELECT COUNT(*) FROM bench", [], |row| row.get(0)).map_err(db_error)?;
+        if count != rows as i64 {
+            return Err(BenchmarkError::DatabaseTestError(format!("插入后表中有{}行，应为{}行", count, rows)));
+        }
+
+        // 点查：每个键都存在，查不到说明数据损坏
+        phase_progress(DatabasePhase::PointQuery, 0.4)?;
+        let queries = self.config.point_queries;
+        let mut timer = PhaseTimer::start();
+        {
+            let mut select = connection.prepare_cached("SELECT value FROM bench WHERE key = ?1").map_err(db_error)?;
+            for i in 0..queries {
+                let key = key_of((i * 7 % rows) as i64);
+                let value: f64 = timer.time(|| select.query_row([key], |row| row.get(0)).map_err(db_error))?;
+                std::hint::black_box(value);
+            }
+        }
+        phases.push(timer.finish(DatabasePhase::PointQuery, queries));
+
+        phase_progress(DatabasePhase::RangeScan, 0.6)?;
+        let scans = self.config.range_scans;
+        let mut timer = PhaseTimer::start();
+        {
+            let mut scan = connection
+                .prepare_cached("SELECT COUNT(*), SUM(value) FROM bench WHERE key BETWEEN ?1 AND ?2")
+                .map_err(db_error)?;
+            for i in 0..scans {
+                let low = (i as i64 * SCAN_ROWS) % rows as i64;
+                let (found, _sum): (i64, Option<f64>) =
+                    timer.time(|| scan.query_row([low, low + SCAN_ROWS - 1], |row| Ok((row.get(0)?, row.get(1)?))).map_err(db_error))?;
+                if found != SCAN_ROWS.min(rows as i64 - low) {
+                    return Err(BenchmarkError::DatabaseTestError(format!("范围扫描从{}开始返回了{}行", low, found)));
+                }
+            }
+        }
+        phases.push(timer.finish(DatabasePhase::RangeScan, scans));
+
+        // 更新：按主键更新前十分之一的行
+        phase_progress(DatabasePhase::Update, 0.8)?;
+        let updates = (rows / 10).max(1);
+        let mut timer = PhaseTimer::start();
+        for batch_start in (0..updates).step_by(batch_size) {
+            if batch_start > 0 {
+                phase_progress(DatabasePhase::Update, 0.8 + 0.2 * batch_start as f64 / updates as f64)?;
+            }
+            let batch_end = (batch_start + batch_size).min(updates);
+            timer.time(|| {
+                let tx = connection.transaction().map_err(db_error)?;
+                {
+                    let mut update = tx.prepare_cached("UPDATE bench SET value = value + 1 WHERE id = ?1").map_err(db_error)?;
+                    for id in batch_start as i64..batch_end as i64 {
+                        update.execute([id]).map_err(db_error)?;
+                    }
+                }
+                tx.commit().map_err(db_error)
+            })?;
+        }
+        phases.push(timer.finish(DatabasePhase::Update, updates));
+
+        drop(connection);
+        drop(guard);
+        Ok(DatabaseRun {
+            journal_mode: journal_mode.to_lowercase(),
+            rows: count as usize,
+            phases,
+        })
+    }
+}
+
+impl BenchmarkRunner for DatabaseBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Extra
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        let per_mode = (self.config.rows as f64 * 1.1 + self.config.point_queries as f64) / ESTIMATED_ROWS_PER_SECOND;
+        (per_mode * self.config.journal_modes.len() as f64).ceil().max(1.0) as u64
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        let result = self.run_benchmark_cancellable(ctx.progress_callback(), &ctx.cancellation)?;
+        Ok(TestOutcome::Completed(TestResultPayload::Extra(ExtraTestResult {
+            name: DATABASE_TEST_NAME.to_string(),
+            result: serde_json::to_value(&result)
+                .map_err(|e| BenchmarkError::DataSaveError(format!("测试结果序列化失败: {}", e)))?,
+        })))
+    }
+
+    fn artifacts(&self) -> Vec<PathBuf> {
+        self.config
+            .journal_modes
+            .iter()
+            .flat_map(|mode| database_files(&self.database_path(*mode)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    fn small_config(dir: &Path) -> DatabaseTestConfig {
+        DatabaseTestConfig {
+            rows: 1000,
+            batch_size: 100,
+            point_queries: 200,
+            range_scans: 5,
+            test_dir: Some(dir.to_string_lossy().into_owned()),
+            ..DatabaseTestConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_scaled_down_workload_in_both_journal_modes() {
+        let dir = tempfile::tempdir().unwrap();
+        let benchmark = DatabaseBenchmark::new(small_config(dir.path()));
+        let result = benchmark.run_benchmark().unwrap();
+
+        let modes: Vec<&str> = result.runs.iter().map(|run| run.journal_mode.as_str()).collect();
+        assert_eq!(modes, ["wal", "delete"]);
+        for run in &result.runs {
+            assert_eq!(run.rows, 1000);
+            let phases: Vec<DatabasePhase> = run.phases.iter().map(|phase| phase.phase).collect();
+            assert_eq!(phases, [DatabasePhase::Insert, DatabasePhase::PointQuery, DatabasePhase::RangeScan, DatabasePhase::Update]);
+            for phase in &run.phases {
+                assert!(phase.ops_per_second > 0.0 && phase.p95_latency_ms > 0.0, "{:?}", phase);
+            }
+            assert_eq!((run.phases[0].operations, run.phases[3].operations), (1000, 100));
+        }
+        assert!(result.score() > 0.0);
+        // 数据库和日志文件都已删除
+        assert!(benchmark.artifacts().iter().all(|path| !path.exists()));
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_cancelled_run_removes_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+        let token = CancellationToken::new(move || flag.load(Ordering::SeqCst));
+        let benchmark = DatabaseBenchmark::new(small_config(dir.path()));
+        let error = benchmark
+            .run_benchmark_cancellable(
+                |_progress, message| {
+                    if message.params.get("phase").map(String::as_str) == Some("range_scan") {
+                        cancelled.store(true, Ordering::SeqCst);
+                    }
+                },
+                &token,
+            )
+            .unwrap_err();
+        assert_eq!(error.code(), "CANCELLED");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+}
@@ -1,20 +1,207 @@
+// Display只用于日志和panic，保持简短的英文；给用户看的文本由IpcError按错误码从目录中查找
 #[derive(Debug, thiserror::Error)]
 pub enum BenchmarkError {
-    #[error("系统信息获取失败: {0}")]
+    #[error("system info error: {0}")]
     SystemInfoError(String),
     
-    #[error("CPU测试失败: {0}")]
+    #[error("cpu test error: {0}")]
     CpuTestError(String),
     
-    #[error("内存测试失败: {0}")]
+    #[error("memory test error: {0}")]
     MemoryTestError(String),
     
-    #[error("存储测试失败: {0}")]
+    #[error("storage test error: {0}")]
     StorageTestError(String),
     
-    #[error("数据保存失败: {0}")]
+    #[error("gpu test error: {0}")]
+    GpuTestError(String),
+    
+    #[error("ipc test error: {0}")]
+    IpcTestError(String),
+    
+    #[error("process test error: {0}")]
+    ProcessTestError(String),
+    
+    #[error("compression test error: {0}")]
+    CompressionTestError(String),
+    
+    #[error("crypto test error: {0}")]
+    CryptoTestError(String),
+    
+    #[error("database test error: {0}")]
+    DatabaseTestError(String),
+    
+    #[error("json test error: {0}")]
+    JsonTestError(String),
+    
+    #[error("stability test error: {0}")]
+    StabilityTestError(String),
+    
+    #[error("image test error: {0}")]
+    ImageTestError(String),
+    
+    #[error("power plan error: {0}")]
+    PowerPlanError(String),
+    
+    #[error("webview test error: {0}")]
+    WebviewTestError(String),
+    
+    #[error("network test error: {0}")]
+    NetworkTestError(String),
+    
+    #[error("data save error: {0}")]
     DataSaveError(String),
     
-    #[error("权限不足: {0}")]
-    PermissionError(String),
-}
\ No newline at end of file
+    #[error("data load error: {0}")]
+    DataLoadError(String),
+    
+    #[error("upload error: {0}")]
+    UploadError(String),
+    
+    // feature为需要权限的功能（如smart、metrics_exporter），remediation为本平台上的授权方法
+    #[error("permission error: {feature}: {message}")]
+    PermissionError {
+        feature: String,
+        message: String,
+        remediation: Option<String>,
+    },
+    
+    #[error("invalid config: {}", crate::benchmark::core::describe_issues(.0))]
+    InvalidConfig(Vec<crate::benchmark::core::ConfigIssue>),
+    
+    #[error("invalid transition: {from:?} -> {to:?}")]
+    InvalidTransition {
+        from: crate::ipc::TestStatus,
+        to: crate::ipc::TestStatus,
+    },
+    
+    // 运行器或其工作线程panic，套件捕获后记为失败并继续后面的测试
+    #[error("{test} panicked: {panic_message}")]
+    Internal {
+        test: String,
+        panic_message: String,
+    },
+    
+    // 文件读写失败，保留ErrorKind以便区分磁盘已满、权限不足等情况（source字段名会被thiserror当作错误源，故用message）
+    #[error("{context}: {message}")]
+    Io {
+        context: &'static str,
+        kind: std::io::ErrorKind,
+        message: String,
+    },
+    
+    // 运行器发现会话已取消而提前停止，at_progress为停止时该测试的进度（0-100）
+    #[error("{test} cancelled at {at_progress:.0}%")]
+    Cancelled {
+        test: String,
+        at_progress: f64,
+    },
+    
+    // 超过per_test_timeout_factor推导出的时限，看门狗已通知运行器停止
+    #[error("{test} timed out after {limit_seconds}s")]
+    Timeout {
+        test: String,
+        limit_seconds: u64,
+    },
+}
+
+impl BenchmarkError {
+    /// 稳定的错误码，不随消息文本和语言变化，全部列在get_error_catalog中。
+    /// 这里不使用通配分支，新增变体时编译器会要求补上错误码
+    pub fn code(&self) -> &'static str {
+        match self {
+            BenchmarkError::SystemInfoError(_) => "SYSTEM_INFO_ERROR",
+            BenchmarkError::CpuTestError(_) => "CPU_TEST_ERROR",
+            BenchmarkError::MemoryTestError(_) => "MEMORY_TEST_ERROR",
+            BenchmarkError::StorageTestError(_) => "STORAGE_TEST_ERROR",
+            BenchmarkError::GpuTestError(_) => "GPU_TEST_ERROR",
+            BenchmarkError::IpcTestError(_) => "IPC_TEST_ERROR",
+            BenchmarkError::ProcessTestError(_) => "PROCESS_TEST_ERROR",
+            BenchmarkError::CompressionTestError(_) => "COMPRESSION_TEST_ERROR",
+            BenchmarkError::CryptoTestError(_) => "CRYPTO_TEST_ERROR",
+            BenchmarkError::DatabaseTestError(_) => "DATABASE_TEST_ERROR",
+            BenchmarkError::JsonTestError(_) => "JSON_TEST_ERROR",
+            BenchmarkError::StabilityTestError(_) => "STABILITY_TEST_ERROR",
+            BenchmarkError::ImageTestError(_) => "IMAGE_TEST_ERROR",
+            BenchmarkError::PowerPlanError(_) => "POWER_PLAN_ERROR",
+            BenchmarkError::WebviewTestError(_) => "WEBVIEW_TEST_ERROR",
+            BenchmarkError::NetworkTestError(_) => "NETWORK_TEST_ERROR",
+            BenchmarkError::DataSaveError(_) => "DATA_SAVE_ERROR",
+            BenchmarkError::DataLoadError(_) => "DATA_LOAD_ERROR",
+            BenchmarkError::UploadError(_) => "UPLOAD_ERROR",
+            BenchmarkError::PermissionError { .. } => "PERMISSION_ERROR",
+            BenchmarkError::InvalidConfig(_) => "INVALID_CONFIG",
+            BenchmarkError::InvalidTransition { .. } => "INVALID_TRANSITION",
+            BenchmarkError::Cancelled { .. } => "CANCELLED",
+            BenchmarkError::Timeout { .. } => "TIMEOUT",
+            BenchmarkError::Internal { .. } => "INTERNAL_PANIC",
+            BenchmarkError::Io { kind, .. } => crate::ipc::io_error_code(*kind),
+        }
+    }
+
+    /// 原样重试是否可能成功；配置错误、取消、磁盘已满等需要用户处理后再试
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            BenchmarkError::StorageTestError(_)
+            | BenchmarkError::DatabaseTestError(_)
+            | BenchmarkError::WebviewTestError(_)
+            | BenchmarkError::NetworkTestError(_)
+            | BenchmarkError::DataSaveError(_)
+            | BenchmarkError::UploadError(_)
+            | BenchmarkError::Timeout { .. } => true,
+            BenchmarkError::SystemInfoError(_)
+            | BenchmarkError::CpuTestError(_)
+            | BenchmarkError::MemoryTestError(_)
+            | BenchmarkError::GpuTestError(_)
+            | BenchmarkError::IpcTestError(_)
+            | BenchmarkError::ProcessTestError(_)
+            | BenchmarkError::CompressionTestError(_)
+            | BenchmarkError::CryptoTestError(_)
+            | BenchmarkError::JsonTestError(_)
+            | BenchmarkError::StabilityTestError(_)
+            | BenchmarkError::ImageTestError(_)
+            | BenchmarkError::PowerPlanError(_)
+            | BenchmarkError::DataLoadError(_)
+            | BenchmarkError::PermissionError { .. }
+            | BenchmarkError::InvalidConfig(_)
+            | BenchmarkError::InvalidTransition { .. }
+            | BenchmarkError::Cancelled { .. }
+            | BenchmarkError::Internal { .. } => false,
+            BenchmarkError::Io { kind, .. } => {
+                matches!(kind, std::io::ErrorKind::TimedOut | std::io::ErrorKind::Interrupted)
+            }
+        }
+    }
+
+    /// 为IO错误附加上下文，用法：`.map_err(BenchmarkError::io("写入失败"))`
+    pub fn io(context: &'static str) -> impl FnOnce(std::io::Error) -> Self {
+        move |error| BenchmarkError::Io {
+            context,
+            kind: error.kind(),
+            message: error.to_string(),
+        }
+    }
+
+    /// 由catch_unwind或JoinHandle得到的panic负载转换为Internal错误
+    pub fn from_panic(test: &str, payload: &(dyn std::any::Any + Send)) -> Self {
+        let panic_message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "unknown panic".to_string()
+        };
+        BenchmarkError::Internal {
+            test: test.to_string(),
+            panic_message,
+        }
+    }
+
+    /// IO类错误的ErrorKind，其它错误返回None
+    pub fn io_kind(&self) -> Option<std::io::ErrorKind> {
+        match self {
+            BenchmarkError::Io { kind, .. } => Some(*kind),
+            _ => None,
+        }
+    }
+}
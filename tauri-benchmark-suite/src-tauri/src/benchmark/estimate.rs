@@ -0,0 +1,383 @@
+//! 不运行任何负载，估算一次测试的耗时、内存和磁盘占用，并给出预检警告。
+//! 套件运行时的预检和剩余时间估计也使用这里的函数
+use crate::benchmark::archive::ArchiveTestConfig;
+use crate::benchmark::calibration::{plan_auto_duration, Calibration};
+use crate::benchmark::capability::{CapabilityMap, CapabilityStatus};
+use crate::benchmark::core::BenchmarkConfig;
+use crate::benchmark::storage::{default_test_file_path, disk_usage_bytes, StorageTestConfig};
+use crate::i18n::{Locale, Message};
+use crate::ipc::TestType;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use sysinfo::{Disks, System};
+
+/// CPU测试依次运行单线程、多线程和浮点三个阶段，每段持续duration秒；温度在运行期间采样，不另占时间
+pub const CPU_PHASES: f64 = 3.0;
+
+// 小文件测试的四个阶段，以及估计耗时用的每秒文件操作数
+const SMALL_FILE_PHASES: usize = 4;
+const SMALL_FILE_OPS_PER_SECOND: f64 = 20_000.0;
+
+// 压缩包测试每种格式生成和解压合计的估计吞吐（MB/s），解压出的每个文件另按两次文件操作计
+const ARCHIVE_MB_PER_SECOND: f64 = 100.0;
+const ARCHIVE_OPS_PER_FILE: usize = 2;
+
+// 内存干扰测试依次在无负载、一半核心负载和全部核心负载下测量
+const INTERFERENCE_PHASES: f64 = 3.0;
+
+// GPU测试创建设备、编译着色器和校验结果的固定开销
+const GPU_SETUP_SECONDS: f64 = 2.0;
+
+// WebView测试每项负载每轮的估计耗时，以及发送请求、加载WASM模块的固定开销
+const WEBVIEW_ITERATION_SECONDS: f64 = 0.2;
+const WEBVIEW_SETUP_SECONDS: f64 = 1.0;
+
+// 网络测试每次延迟请求的估计耗时，以及启动回环服务器、解析目标的固定开销
+const NETWORK_LATENCY_SAMPLE_SECONDS: f64 = 0.05;
+const NETWORK_SETUP_SECONDS: f64 = 0.5;
+
+// 内存缓冲区超过可用内存的这一比例时发出警告
+const MEMORY_WARNING_RATIO: f64 = 0.5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestEstimate {
+    pub test_type: String, // "cpu" / "memory" / "storage" / "gpu" / "webview" / "network"
+    pub seconds: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunEstimate {
+    pub total_seconds: f64,
+    pub tests: Vec<TestEstimate>,
+    pub peak_memory_mb: u64,             // 内存测试分配的最大缓冲区
+    pub disk_usage_bytes: u64,           // 存储测试文件的实际占用，包含压缩包测试的临时文件
+    pub disk_path: Option<PathBuf>,      // 存储测试文件位置，未启用存储测试时为空
+    pub warnings: Vec<String>,           // 预检会发出的警告
+}
+
+/// 预检所需的系统状态，测试中可以直接构造
+#[derive(Debug, Clone)]
+pub struct PreflightEnvironment {
+    pub available_memory_mb: u64,
+    pub available_disk_bytes: Option<u64>, // 测试文件所在磁盘的可用空间，无法确定时为空
+    pub logical_cpus: usize,
+    pub capabilities: CapabilityMap, // 需要更高权限的功能，未探测时为空
+}
+
+impl PreflightEnvironment {
+    pub fn detect(disk_path: &Path) -> Self {
+        let mut sys = System::new();
+        sys.refresh_memory();
+
+        Self {
+            available_memory_mb: sys.available_memory() / (1024 * 1024),
+            available_disk_bytes: available_disk_bytes(disk_path),
+            logical_cpus: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            capabilities: CapabilityMap::detect(disk_path.parent().unwrap_or(disk_path)),
+        }
+    }
+}
+
+/// 路径所在磁盘的可用空间，选择挂载点最长且包含该路径的磁盘；找不到时为空
+pub fn available_disk_bytes(path: &Path) -> Option<u64> {
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
+pub fn cpu_test_seconds(duration: u64) -> f64 {
+    duration as f64 * CPU_PHASES
+}
+
+pub fn memory_test_seconds(buffer_size: usize, iterations: usize, calibration: &Calibration) -> f64 {
+    calibration.memory.estimate((buffer_size * iterations) as f64)
+}
+
+pub fn interference_test_seconds(phase_seconds: u64) -> f64 {
+    phase_seconds as f64 * INTERFERENCE_PHASES
+}
+
+pub fn storage_test_seconds(file_size: u64, calibration: &Calibration) -> f64 {
+    calibration.storage.estimate(file_size as f64)
+}
+
+/// 小文件测试依次创建、stat、读取和删除每个文件
+pub fn small_file_test_seconds(file_count: usize) -> f64 {
+    (file_count * SMALL_FILE_PHASES) as f64 / SMALL_FILE_OPS_PER_SECOND
+}
+
+/// 压缩包测试对每种格式生成一次压缩包（不计入结果但占用运行时间）再解压
+pub fn archive_test_seconds(config: &ArchiveTestConfig) -> f64 {
+    let megabytes = config.extracted_disk_bytes() as f64 / (1024.0 * 1024.0);
+    let per_format = megabytes / ARCHIVE_MB_PER_SECOND + (config.file_count() * ARCHIVE_OPS_PER_FILE) as f64 / SMALL_FILE_OPS_PER_SECOND;
+    per_format * config.formats.len() as f64
+}
+
+/// 存储测试同时占用的磁盘空间：测试文件在整个测试期间存在，压缩包测试的文件在此基础上临时增加
+pub fn storage_disk_bytes(config: &StorageTestConfig) -> u64 {
+    disk_usage_bytes(config.file_size, config.block_size) + config.archive.as_ref().map_or(0, ArchiveTestConfig::disk_bytes)
+}
+
+/// 每项GPU负载运行固定时长，另加设备初始化和结果校验
+pub fn gpu_test_seconds(duration: u64, workloads: usize) -> f64 {
+    (duration * workloads as u64) as f64 + GPU_SETUP_SECONDS
+}
+
+/// 前端按轮次运行各项负载，另加请求往返和WASM编译
+pub fn webview_test_seconds(iterations: u32, workloads: usize) -> f64 {
+    iterations as f64 * workloads as f64 * WEBVIEW_ITERATION_SECONDS + WEBVIEW_SETUP_SECONDS
+}
+
+/// 下载和上传各运行duration秒，之前逐个发送延迟请求
+pub fn network_test_seconds(duration: u64, latency_samples: usize) -> f64 {
+    (duration * 2) as f64 + latency_samples as f64 * NETWORK_LATENCY_SAMPLE_SECONDS + NETWORK_SETUP_SECONDS
+}
+
+/// 按测试顺序列出启用的测试及其估计耗时
+pub fn test_estimates(config: &BenchmarkConfig, calibration: &Calibration) -> Vec<TestEstimate> {
+    let mut tests = Vec::new();
+    if config.cpu_test.enabled {
+        tests.push(TestEstimate {
+            test_type: "cpu".to_string(),
+            seconds: cpu_test_seconds(config.cpu_test.test_duration),
+        });
+    }
+    if config.memory_test.enabled {
+        tests.push(TestEstimate {
+            test_type: "memory".to_string(),
+            seconds: memory_test_seconds(config.memory_test.buffer_size, config.memory_test.iterations, calibration)
+                + interference_test_seconds(config.memory_test.interference_duration),
+        });
+    }
+    if config.storage_test.enabled {
+        tests.push(TestEstimate {
+            test_type: "storage".to_string(),
+            seconds: storage_test_seconds(config.storage_test.file_size, calibration)
+                + small_file_test_seconds(config.storage_test.small_file_count)
+                + config.storage_test.archive.as_ref().map_or(0.0, archive_test_seconds),
+        });
+    }
+    if config.gpu_test.enabled {
+        tests.push(TestEstimate {
+            test_type: "gpu".to_string(),
+            seconds: gpu_test_seconds(config.gpu_test.test_duration, config.gpu_test.workloads.len()),
+        });
+    }
+    if config.webview_test.enabled {
+        tests.push(TestEstimate {
+            test_type: "webview".to_string(),
+            seconds: webview_test_seconds(config.webview_test.iterations, config.webview_test.workloads.len()),
+        });
+    }
+    if config.network_test.enabled {
+        tests.push(TestEstimate {
+            test_type: "network".to_string(),
+            seconds: network_test_seconds(config.network_test.test_duration, config.network_test.latency_samples),
+        });
+    }
+    tests
+}
+
+/// 预检：列出会导致测试失败或结果失真的配置问题
+pub fn preflight_warnings(config: &BenchmarkConfig, env: &PreflightEnvironment) -> Vec<Message> {
+    let mut warnings = Vec::new();
+    if !config.cpu_test.enabled
+        && !config.memory_test.enabled
+        && !config.storage_test.enabled
+        && !config.gpu_test.enabled
+        && !config.webview_test.enabled
+        && !config.network_test.enabled
+        && config.extra_tests.is_empty()
+    {
+        warnings.push(Message::new("preflight.no_tests"));
+    }
+
+    if config.cpu_test.enabled && config.cpu_test.thread_count > env.logical_cpus {
+        warnings.push(
+            Message::new("preflight.cpu_threads")
+                .param("threads", config.cpu_test.thread_count)
+                .param("cpus", env.logical_cpus),
+        );
+    }
+
+    if config.memory_test.enabled {
+        let buffer_size = config.memory_test.buffer_size as u64;
+        if buffer_size as f64 > env.available_memory_mb as f64 * MEMORY_WARNING_RATIO {
+            warnings.push(
+                Message::new("preflight.memory_buffer")
+                    .param("buffer_mb", buffer_size)
+                    .param("available_mb", env.available_memory_mb),
+            );
+        }
+    }
+
+    if config.storage_test.enabled {
+        if config.storage_test.block_size == 0 || config.storage_test.file_size == 0 {
+            warnings.push(Message::new("preflight.storage_size"));
+        } else if let Some(available) = env.available_disk_bytes {
+            let required = storage_disk_bytes(&config.storage_test);
+            if required > available {
+                warnings.push(
+                    Message::new("preflight.disk_space")
+                        .param("required_mb", required / (1024 * 1024))
+                        .param("available_mb", available / (1024 * 1024)),
+                );
+            }
+        }
+    }
+
+    // 权限不足的功能在运行时跳过，测试降级完成而不是中途报错
+    for capability in &env.capabilities.capabilities {
+        if capability.status == CapabilityStatus::PermissionDenied && test_enabled(config, capability.feature.test_type()) {
+            warnings.push(Message::new("preflight.capability_permission").param("feature", capability.feature.name()));
+        }
+    }
+    warnings
+}
+
+fn test_enabled(config: &BenchmarkConfig, test_type: TestType) -> bool {
+    match test_type {
+        TestType::Cpu => config.cpu_test.enabled,
+        TestType::Memory => config.memory_test.enabled,
+        TestType::Storage => config.storage_test.enabled,
+        TestType::Gpu => config.gpu_test.enabled,
+        TestType::Webview => config.webview_test.enabled,
+        TestType::Network => config.network_test.enabled,
+        TestType::Extra => !config.extra_tests.is_empty(),
+        TestType::Suite => true,
+    }
+}
+
+/// 估算整次运行，`calibration`通常为内置估计，即不运行任何负载。
+/// 自动时长模式下按求解后的参数估算，警告按`locale`渲染。tests为单次运行的估计，总时长包含全部重复运行
+pub fn estimate_run(
+    config: &BenchmarkConfig,
+    calibration: &Calibration,
+    env: &PreflightEnvironment,
+    locale: Locale,
+) -> RunEstimate {
+    let mut config = config.clone();
+    if let Some(target_seconds) = config.auto_duration_target_seconds {
+        plan_auto_duration(&config, target_seconds, calibration.clone()).apply(&mut config);
+    }
+    let config = &config;
+    let tests = test_estimates(config, calibration);
+    let (disk_usage_bytes, disk_path) = if config.storage_test.enabled {
+        (storage_disk_bytes(&config.storage_test), Some(default_test_file_path()))
+    } else {
+        (0, None)
+    };
+
+    RunEstimate {
+        total_seconds: tests.iter().map(|t| t.seconds).sum::<f64>() * config.repeat.max(1) as f64,
+        tests,
+        peak_memory_mb: if config.memory_test.enabled {
+            config.memory_test.buffer_size as u64
+        } else {
+            0
+        },
+        disk_usage_bytes,
+        disk_path,
+        warnings: preflight_warnings(config, env)
+            .iter()
+            .map(|warning| warning.render(locale))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::capability::{Feature, ProbeOutcome};
+    use crate::benchmark::cpu::CpuBenchmark;
+    use crate::benchmark::memory::MemoryBenchmark;
+    use crate::benchmark::storage::{StorageBenchmark, StorageTestConfig};
+    use std::time::Instant;
+
+    fn roomy_environment() -> PreflightEnvironment {
+        PreflightEnvironment {
+            available_memory_mb: 16 * 1024,
+            available_disk_bytes: Some(100 * 1024 * 1024 * 1024),
+            logical_cpus: 8,
+            capabilities: CapabilityMap::default(),
+        }
+    }
+
+    #[test]
+    fn test_quick_preset_estimate_matches_actual_run() {
+        let config = BenchmarkConfig::quick();
+        let estimate = estimate_run(&config, &Calibration::default_estimates(), &roomy_environment(), Locale::Zh);
+        assert!(estimate.warnings.is_empty());
+        assert_eq!(estimate.tests.len(), 3);
+        assert_eq!(estimate.peak_memory_mb, config.memory_test.buffer_size as u64);
+
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("quick.dat");
+        let started = Instant::now();
+        CpuBenchmark::new(config.cpu_test.config.clone()).run_benchmark().unwrap();
+        MemoryBenchmark::new(config.memory_test.config.clone()).run_benchmark().unwrap();
+        let storage = StorageBenchmark::new(StorageTestConfig {
+            test_file_path: Some(file_path.to_string_lossy().into_owned()),
+            ..config.storage_test.config.clone()
+        });
+        storage.run_benchmark().unwrap();
+        let actual = started.elapsed().as_secs_f64();
+
+        // 测试文件在benchmark释放前仍在磁盘上
+        assert_eq!(std::fs::metadata(&file_path).unwrap().len(), estimate.disk_usage_bytes);
+        drop(storage);
+
+        // 调试构建比发布构建慢得多，只要求数量级一致
+        assert!(
+            actual < estimate.total_seconds * 5.0 && actual > estimate.total_seconds / 5.0,
+            "估计 {:.1}秒，实际 {:.1}秒",
+            estimate.total_seconds,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_disk_usage_rounds_up_to_block_size() {
+        assert_eq!(disk_usage_bytes(16, 4), 16 * 1024 * 1024);
+        // 1MB不是12KB的整数倍，最后一块写满
+        assert_eq!(disk_usage_bytes(1, 12), 86 * 12 * 1024);
+    }
+
+    #[test]
+    fn test_preflight_warnings() {
+        let mut config = BenchmarkConfig::quick();
+        config.cpu_test.thread_count = 64;
+        config.memory_test.buffer_size = 4096;
+        config.storage_test.file_size = 2048;
+        let env = PreflightEnvironment {
+            available_memory_mb: 4096,
+            available_disk_bytes: Some(1024 * 1024 * 1024),
+            logical_cpus: 8,
+            capabilities: CapabilityMap::default(),
+        };
+        let warnings = preflight_warnings(&config, &env);
+        assert_eq!(warnings.len(), 3, "{:?}", warnings);
+
+        // 只对已启用的测试所需、且权限不足的功能发出警告
+        let capabilities = CapabilityMap::assemble(|feature| match feature {
+            Feature::Smart | Feature::RaplPower => ProbeOutcome::PermissionDenied("e".to_string()),
+            Feature::DirectIo => ProbeOutcome::Unsupported("e".to_string()),
+            Feature::LargePages => ProbeOutcome::Available,
+        });
+        let mut storage_only = BenchmarkConfig::quick();
+        storage_only.cpu_test.enabled = false;
+        let warnings = preflight_warnings(&storage_only, &PreflightEnvironment { capabilities, ..roomy_environment() });
+        assert_eq!(warnings, vec![Message::new("preflight.capability_permission").param("feature", "smart")]);
+
+        config.cpu_test.enabled = false;
+        config.memory_test.enabled = false;
+        config.storage_test.enabled = false;
+        let warnings = preflight_warnings(&config, &env);
+        assert_eq!(warnings, vec![Message::new("preflight.no_tests")]);
+        assert_eq!(warnings[0].render(Locale::Zh), "没有启用任何测试");
+    }
+}
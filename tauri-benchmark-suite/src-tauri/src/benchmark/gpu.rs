@@ -0,0 +1,588 @@
+//! GPU计算测试：用wgpu计算着色器测量浮点吞吐（FMA内核）和显存带宽（缓冲区复制），
+//! 在Vulkan、Metal、DX12和GL上都能运行。本机没有硬件适配器时（如无头CI）测试跳过，不计为失败
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, RunIssues, TestOutcome};
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::estimate::gpu_test_seconds;
+use crate::i18n::Message;
+use crate::ipc::{TestResultPayload, TestType, WarningSeverity};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GpuTestResult {
+    pub compute_score: f64, // GFLOPS，未运行计算负载时为0
+    pub memory_bandwidth_gb_s: f64, // 复制带宽（读+写），未运行带宽负载时为0
+    pub adapter_name: String,
+    pub backend: String, // vulkan / metal / dx12 / gl
+}
+
+/// GPU测试中的一项负载，按配置中的顺序运行，每项运行test_duration秒
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuWorkload {
+    Compute,
+    Bandwidth,
+}
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct GpuTestConfig {
+    #[serde(alias = "adapterIndex")]
+    pub adapter_index: Option<usize>, // list_gpu_adapters中的序号，为空时自动选择独立显卡优先
+    #[serde(alias = "testDuration")]
+    pub test_duration: u64, // 每项负载的秒数
+    pub workloads: Vec<GpuWorkload>,
+}
+
+impl Default for GpuTestConfig {
+    fn default() -> Self {
+        Self {
+            adapter_index: None,
+            test_duration: 10,
+            workloads: vec![GpuWorkload::Compute, GpuWorkload::Bandwidth],
+        }
+    }
+}
+
+/// 适配器类型，自动选择时按此顺序优先
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuDeviceType {
+    Discrete,
+    Integrated,
+    Virtual,
+    Other,
+    Cpu, // 软件渲染（如llvmpipe、WARP），不参与自动选择
+}
+
+impl From<wgpu::DeviceType> for GpuDeviceType {
+    fn from(device_type: wgpu::DeviceType) -> Self {
+        match device_type {
+            wgpu::DeviceType::DiscreteGpu => GpuDeviceType::Discrete,
+            wgpu::DeviceType::IntegratedGpu => GpuDeviceType::Integrated,
+            wgpu::DeviceType::VirtualGpu => GpuDeviceType::Virtual,
+            wgpu::DeviceType::Cpu => GpuDeviceType::Cpu,
+            wgpu::DeviceType::Other => GpuDeviceType::Other,
+        }
+    }
+}
+
+/// 本机的一个图形适配器，index为枚举顺序，可填入GpuTestConfig::adapter_index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GpuAdapterInfo {
+    pub index: usize,
+    pub name: String,
+    pub backend: String,
+    pub device_type: GpuDeviceType,
+}
+
+impl GpuAdapterInfo {
+    fn new(index: usize, info: &wgpu::AdapterInfo) -> Self {
+        Self {
+            index,
+            name: info.name.clone(),
+            backend: info.backend.to_str().to_string(),
+            device_type: info.device_type.into(),
+        }
+    }
+}
+
+/// select_adapter的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdapterChoice {
+    Requested(usize),
+    Auto(usize),
+    Fallback { requested: usize, index: usize }, // 指定的序号不存在，改为自动选择
+    None, // 只有软件适配器或没有适配器
+}
+
+/// 指定的序号存在时使用它（软件适配器也可以显式指定），否则按独立、集成、虚拟、其它的顺序自动选择
+pub fn select_adapter(adapters: &[GpuAdapterInfo], requested: Option<usize>) -> AdapterChoice {
+    if let Some(index) = requested.filter(|index| adapters.iter().any(|adapter| adapter.index == *index)) {
+        return AdapterChoice::Requested(index);
+    }
+    let auto = adapters
+        .iter()
+        .filter(|adapter| adapter.device_type != GpuDeviceType::Cpu)
+        .min_by_key(|adapter| (adapter.device_type as u8, adapter.index))
+        .map(|adapter| adapter.index);
+    match (auto, requested) {
+        (Some(index), Some(requested)) => AdapterChoice::Fallback { requested, index },
+        (Some(index), None) => AdapterChoice::Auto(index),
+        (None, _) => AdapterChoice::None,
+    }
+}
+
+fn enumerate_adapters() -> Vec<wgpu::Adapter> {
+    wgpu::Instance::new(&wgpu::InstanceDescriptor::default()).enumerate_adapters(wgpu::Backends::all())
+}
+
+/// 列出本机可用的图形适配器，没有图形驱动时为空
+pub fn list_adapters() -> Vec<GpuAdapterInfo> {
+    enumerate_adapters()
+        .iter()
+        .enumerate()
+        .map(|(index, adapter)| GpuAdapterInfo::new(index, &adapter.get_info()))
+        .collect()
+}
+
+// FMA内核：每个元素是vec4，每次迭代对4个分量各做一次乘加（2次浮点运算）
+const FMA_ITERATIONS: u32 = 256;
+const FMA_WORKGROUP_SIZE: u32 = 64;
+const FMA_ELEMENTS: u32 = 1 << 18; // 4MB
+const FMA_A: f32 = 0.99;
+const FMA_B: [f32; 4] = [0.01, 0.02, 0.03, 0.04];
+const FMA_DISPATCHES_PER_BATCH: u32 = 16;
+const FMA_SHADER: &str = r#"
+@group(0) @binding(0) var<storage, read_write> data: array<vec4<f32>>;
+
+const A: vec4<f32> = vec4<f32>(0.99, 0.99, 0.99, 0.99);
+const B: vec4<f32> = vec4<f32>(0.01, 0.02, 0.03, 0.04);
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= arrayLength(&data)) {
+        return;
+    }
+    var x = data[i];
+    for (var k = 0u; k < 256u; k = k + 1u) {
+        x = fma(x, A, B);
+    }
+    data[i] = x;
+}
+"#;
+
+// 带宽测试：两个缓冲区之间整块复制，每次复制读写各一遍
+const BANDWIDTH_BUFFER_BYTES: u64 = 256 * 1024 * 1024;
+const BANDWIDTH_CHUNK_BYTES: u64 = 4 * 1024 * 1024; // 初始化和校验的分块大小
+const BANDWIDTH_COPIES_PER_BATCH: u32 = 8;
+
+/// FMA内核的CPU参考实现，用于校验GPU的计算结果
+fn fma_reference(mut x: [f32; 4], iterations: u32) -> [f32; 4] {
+    for _ in 0..iterations {
+        for (lane, value) in x.iter_mut().enumerate() {
+            *value = value.mul_add(FMA_A, FMA_B[lane]);
+        }
+    }
+    x
+}
+
+fn fma_input(element: usize) -> [f32; 4] {
+    std::array::from_fn(|lane| ((element * 4 + lane) % 1024) as f32 / 1024.0)
+}
+
+fn bandwidth_pattern(offset: u64, bytes: u64) -> Vec<u8> {
+    (offset / 4..(offset + bytes) / 4)
+        .flat_map(|word| ((word as u32) ^ 0x9E37_79B9).to_le_bytes())
+        .collect()
+}
+
+fn gpu_error(context: &str, error: impl std::fmt::Display) -> BenchmarkError {
+    BenchmarkError::GpuTestError(format!("{}: {}", context, error))
+}
+
+/// 打开的设备；运行期间的验证错误和显存不足记录在uncaptured中，每批结束后检查
+struct GpuDevice {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    limits: wgpu::Limits,
+    uncaptured: Arc<Mutex<Option<String>>>,
+}
+
+impl GpuDevice {
+    fn open(adapter: &wgpu::Adapter) -> Result<Self, BenchmarkError> {
+        let limits = adapter.limits();
+        let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor {
+            label: Some("benchmark"),
+            required_limits: limits.clone(),
+            ..Default::default()
+        }))
+        .map_err(|e| gpu_error("无法创建GPU设备", e))?;
+        let uncaptured = Arc::new(Mutex::new(None));
+        let sink = uncaptured.clone();
+        device.on_uncaptured_error(Box::new(move |error| {
+            sink.lock().unwrap().get_or_insert_with(|| error.to_string());
+        }));
+        Ok(Self { device, queue, limits, uncaptured })
+    }
+
+    /// 等待已提交的命令完成，并报告期间发生的错误
+    fn wait(&self) -> Result<(), BenchmarkError> {
+        self.device
+            .poll(wgpu::PollType::Wait)
+            .map_err(|e| gpu_error("等待GPU完成失败", e))?;
+        match self.uncaptured.lock().unwrap().take() {
+            Some(error) => Err(gpu_error("GPU执行出错", error)),
+            None => Ok(()),
+        }
+    }
+
+    /// 把source中[offset, offset+size)复制到可映射的缓冲区后读回
+    fn read_back(&self, source: &wgpu::Buffer, offset: u64, size: u64) -> Result<Vec<u8>, BenchmarkError> {
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(source, offset, &staging, 0, size);
+        self.queue.submit([encoder.finish()]);
+
+        let slice = staging.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.wait()?;
+        receiver
+            .recv()
+            .map_err(|e| gpu_error("读取结果失败", e))?
+            .map_err(|e| gpu_error("读取结果失败", e))?;
+        let data = slice.get_mapped_range().to_vec();
+        staging.unmap();
+        Ok(data)
+    }
+}
+
+/// 一项负载在整个测试中占的进度区间
+#[derive(Debug, Clone, Copy)]
+struct ProgressSpan {
+    start: f64,
+    width: f64,
+}
+
+impl ProgressSpan {
+    fn at(&self, elapsed: Duration, duration: Duration) -> f64 {
+        self.start + self.width * (elapsed.as_secs_f64() / duration.as_secs_f64()).min(1.0)
+    }
+}
+
+pub struct GpuBenchmark {
+    config: GpuTestConfig,
+}
+
+impl GpuBenchmark {
+    pub fn new(config: GpuTestConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn get_config(&self) -> &GpuTestConfig {
+        &self.config
+    }
+
+    /// 单独运行时使用：与套件中不同，没有可用的适配器视为失败，返回GpuTestError
+    pub fn run_benchmark_with_progress<F>(&self, progress_callback: F) -> Result<GpuTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message) + Send + Sync + 'static,
+    {
+        let ctx = RunContext {
+            progress: Arc::new(progress_callback),
+            cancellation: CancellationToken::never(),
+            warnings: Arc::new(|_, _| {}),
+            issues: RunIssues::default(),
+        };
+        self.run_with_context(&ctx)?
+            .ok_or_else(|| BenchmarkError::GpuTestError("没有可用的GPU适配器".to_string()))
+    }
+
+    /// 选择适配器并依次运行配置中的负载；没有可用的硬件适配器时返回None
+    pub fn run_with_context(&self, ctx: &RunContext) -> Result<Option<GpuTestResult>, BenchmarkError> {
+        let adapters = enumerate_adapters();
+        let infos: Vec<GpuAdapterInfo> = adapters
+            .iter()
+            .enumerate()
+            .map(|(index, adapter)| GpuAdapterInfo::new(index, &adapter.get_info()))
+            .collect();
+        let index = match select_adapter(&infos, self.config.adapter_index) {
+            AdapterChoice::Requested(index) | AdapterChoice::Auto(index) => index,
+            AdapterChoice::Fallback { requested, index } => {
+                ctx.report(
+                    Message::new("gpu.adapter_fallback")
+                        .param("requested", requested)
+                        .param("name", &infos[index].name),
+                    WarningSeverity::Low,
+                );
+                index
+            }
+            AdapterChoice::None => {
+                ctx.report(Message::new("gpu.no_adapter"), WarningSeverity::Medium);
+                return Ok(None);
+            }
+        };
+        let info = &infos[index];
+        let device = GpuDevice::open(&adapters[index])?;
+        let progress = ctx.progress_callback();
+        let duration = Duration::from_secs(self.config.test_duration.max(1));
+        let width = 100.0 / self.config.workloads.len().max(1) as f64;
+
+        let mut result = GpuTestResult {
+            compute_score: 0.0,
+            memory_bandwidth_gb_s: 0.0,
+            adapter_name: info.name.clone(),
+            backend: info.backend.clone(),
+        };
+        for (position, workload) in self.config.workloads.iter().enumerate() {
+            let span = ProgressSpan { start: position as f64 * width, width };
+            match workload {
+                GpuWorkload::Compute => {
+                    progress(span.start, Message::progress("gpu.compute.start", span.start).param("name", &info.name));
+                    result.compute_score = self.run_compute(&device, ctx, span, duration, &progress)?;
+                }
+                GpuWorkload::Bandwidth => {
+                    progress(span.start, Message::progress("gpu.bandwidth.start", span.start));
+                    result.memory_bandwidth_gb_s = self.run_bandwidth(&device, ctx, span, duration, &progress)?;
+                }
+            }
+        }
+        progress(100.0, Message::progress("gpu.complete", 100.0));
+        Ok(Some(result))
+    }
+
+    /// FMA内核的吞吐（GFLOPS）。先运行一次并与CPU参考结果比对，再计时循环提交
+    fn run_compute(
+        &self,
+        gpu: &GpuDevice,
+        ctx: &RunContext,
+        span: ProgressSpan,
+        duration: Duration,
+        progress: &impl Fn(f64, Message),
+    ) -> Result<f64, BenchmarkError> {
+        let device = &gpu.device;
+        let input: Vec<f32> = (0..FMA_ELEMENTS as usize).flat_map(fma_input).collect();
+        let bytes: Vec<u8> = input.iter().flat_map(|value| value.to_le_bytes()).collect();
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("fma-data"),
+            size: bytes.len() as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        gpu.queue.write_buffer(&buffer, 0, &bytes);
+
+        // 着色器编译和管线创建的错误在这里捕获，而不是留给uncaptured处理器
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("fma"),
+            source: wgpu::ShaderSource::Wgsl(FMA_SHADER.into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("fma"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        if let Some(error) = pollster::block_on(device.pop_error_scope()) {
+            return Err(gpu_error("计算管线创建失败", error));
+        }
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("fma"),
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+        });
+        let submit = |dispatches: u32| {
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            {
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+                pass.set_pipeline(&pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                for _ in 0..dispatches {
+                    pass.dispatch_workgroups(FMA_ELEMENTS / FMA_WORKGROUP_SIZE, 1, 1);
+                }
+            }
+            gpu.queue.submit([encoder.finish()]);
+        };
+
+        // 校验：一次内核调用后的结果应与CPU参考一致
+        submit(1);
+        let output = gpu.read_back(&buffer, 0, bytes.len() as u64)?;
+        for (element, chunk) in output.chunks_exact(16).enumerate() {
+            let expected = fma_reference(fma_input(element), FMA_ITERATIONS);
+            for (lane, bytes) in chunk.chunks_exact(4).enumerate() {
+                let actual = f32::from_le_bytes(bytes.try_into().unwrap());
+                if (actual - expected[lane]).abs() > 1e-3 * expected[lane].abs().max(1.0) {
+                    return Err(BenchmarkError::GpuTestError(format!(
+                        "计算结果校验失败: 元素{}分量{}为{}，应为{}",
+                        element, lane, actual, expected[lane]
+                    )));
+                }
+            }
+        }
+
+        let started = Instant::now();
+        let mut dispatches = 0u64;
+        while started.elapsed() < duration {
+            let at = span.at(started.elapsed(), duration);
+            ctx.cancellation.check(TestType::Gpu, at)?;
+            submit(FMA_DISPATCHES_PER_BATCH);
+            gpu.wait()?;
+            dispatches += FMA_DISPATCHES_PER_BATCH as u64;
+            let at = span.at(started.elapsed(), duration);
+            progress(at, Message::progress("gpu.compute.progress", at));
+        }
+        let flops = dispatches as f64 * FMA_ELEMENTS as f64 * FMA_ITERATIONS as f64 * 4.0 * 2.0;
+        Ok(flops / started.elapsed().as_secs_f64() / 1e9)
+    }
+
+    /// 缓冲区间复制的带宽（GB/s），读和写都计入。先复制一次并校验首尾数据
+    fn run_bandwidth(
+        &self,
+        gpu: &GpuDevice,
+        ctx: &RunContext,
+        span: ProgressSpan,
+        duration: Duration,
+        progress: &impl Fn(f64, Message),
+    ) -> Result<f64, BenchmarkError> {
+        let device = &gpu.device;
+        let size = BANDWIDTH_BUFFER_BYTES.min(gpu.limits.max_buffer_size) / BANDWIDTH_CHUNK_BYTES * BANDWIDTH_CHUNK_BYTES;
+        if size == 0 {
+            return Err(BenchmarkError::GpuTestError(format!(
+                "适配器允许的最大缓冲区{}字节过小",
+                gpu.limits.max_buffer_size
+            )));
+        }
+        let buffer = |label| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size,
+                usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        };
+        let (source, destination) = (buffer("bandwidth-src"), buffer("bandwidth-dst"));
+        for offset in (0..size).step_by(BANDWIDTH_CHUNK_BYTES as usize) {
+            gpu.queue.write_buffer(&source, offset, &bandwidth_pattern(offset, BANDWIDTH_CHUNK_BYTES));
+        }
+        let submit = |copies: u32| {
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            for _ in 0..copies {
+                encoder.copy_buffer_to_buffer(&source, 0, &destination, 0, size);
+            }
+            gpu.queue.submit([encoder.finish()]);
+        };
+
+        submit(1);
+        for offset in [0, size - BANDWIDTH_CHUNK_BYTES] {
+            if gpu.read_back(&destination, offset, BANDWIDTH_CHUNK_BYTES)? != bandwidth_pattern(offset, BANDWIDTH_CHUNK_BYTES) {
+                return Err(BenchmarkError::GpuTestError(format!("复制结果校验失败: 偏移{}处的数据不一致", offset)));
+            }
+        }
+
+        let started = Instant::now();
+        let mut copies = 0u64;
+        while started.elapsed() < duration {
+            let at = span.at(started.elapsed(), duration);
+            ctx.cancellation.check(TestType::Gpu, at)?;
+            submit(BANDWIDTH_COPIES_PER_BATCH);
+            gpu.wait()?;
+            copies += BANDWIDTH_COPIES_PER_BATCH as u64;
+            let at = span.at(started.elapsed(), duration);
+            progress(at, Message::progress("gpu.bandwidth.progress", at));
+        }
+        Ok(2.0 * size as f64 * copies as f64 / started.elapsed().as_secs_f64() / 1e9)
+    }
+}
+
+impl BenchmarkRunner for GpuBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Gpu
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        gpu_test_seconds(self.config.test_duration, self.config.workloads.len()).ceil() as u64
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        Ok(match self.run_with_context(ctx)? {
+            Some(result) => TestOutcome::Completed(TestResultPayload::Gpu(result)),
+            None => TestOutcome::Unavailable,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adapter(index: usize, device_type: GpuDeviceType) -> GpuAdapterInfo {
+        GpuAdapterInfo {
+            index,
+            name: format!("adapter-{}", index),
+            backend: "vulkan".to_string(),
+            device_type,
+        }
+    }
+
+    #[test]
+    fn test_adapter_selection_falls_back_to_hardware() {
+        let adapters = [
+            adapter(0, GpuDeviceType::Cpu),
+            adapter(1, GpuDeviceType::Integrated),
+            adapter(2, GpuDeviceType::Discrete),
+        ];
+        // 自动选择独立显卡，指定的序号存在时照办（软件适配器也可以）
+        assert_eq!(select_adapter(&adapters, None), AdapterChoice::Auto(2));
+        assert_eq!(select_adapter(&adapters, Some(1)), AdapterChoice::Requested(1));
+        assert_eq!(select_adapter(&adapters, Some(0)), AdapterChoice::Requested(0));
+        assert_eq!(select_adapter(&adapters, Some(7)), AdapterChoice::Fallback { requested: 7, index: 2 });
+        assert_eq!(select_adapter(&adapters[..2], None), AdapterChoice::Auto(1));
+
+        // 只有软件适配器或没有适配器时不运行
+        assert_eq!(select_adapter(&adapters[..1], None), AdapterChoice::None);
+        assert_eq!(select_adapter(&adapters[..1], Some(3)), AdapterChoice::None);
+        assert_eq!(select_adapter(&[], None), AdapterChoice::None);
+    }
+
+    #[test]
+    fn test_fma_reference_converges() {
+        // x -> 0.99x + b 收敛到 b / 0.01
+        let result = fma_reference([0.0; 4], 5000);
+        for (lane, value) in result.iter().enumerate() {
+            assert!((value - FMA_B[lane] / (1.0 - FMA_A)).abs() < 1e-2, "{:?}", result);
+        }
+        assert_eq!(fma_reference(fma_input(3), 0), fma_input(3));
+        assert_eq!(bandwidth_pattern(8, 8), bandwidth_pattern(0, 16)[8..]);
+    }
+
+    #[test]
+    fn test_gpu_benchmark_skips_or_reports_positive_scores() {
+        let ctx = RunContext {
+            progress: Arc::new(|_, _| {}),
+            cancellation: CancellationToken::never(),
+            warnings: Arc::new(|_, _| {}),
+            issues: RunIssues::default(),
+        };
+        let benchmark = GpuBenchmark::new(GpuTestConfig {
+            test_duration: 1,
+            ..GpuTestConfig::default()
+        });
+        let hardware = select_adapter(&list_adapters(), None) != AdapterChoice::None;
+        let outcome = benchmark.run(&ctx).unwrap();
+        let issues = ctx.issues.take();
+        if !hardware {
+            // 无头环境：跳过并记录一条警告，不计为失败
+            assert!(matches!(outcome, TestOutcome::Unavailable), "{:?}", outcome);
+            assert_eq!(issues.len(), 1);
+            assert_eq!(issues[0].code, "gpu.no_adapter");
+            // 单独运行时没有适配器是错误
+            let standalone = benchmark.run_benchmark_with_progress(|_, _| {});
+            assert!(matches!(standalone, Err(BenchmarkError::GpuTestError(_))), "{:?}", standalone);
+            return;
+        }
+        let TestOutcome::Completed(TestResultPayload::Gpu(result)) = outcome else {
+            panic!("GPU测试没有结果: {:?}", outcome);
+        };
+        assert!(result.compute_score > 0.0);
+        assert!(result.memory_bandwidth_gb_s > 0.0);
+        assert!(!result.adapter_name.is_empty());
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+}
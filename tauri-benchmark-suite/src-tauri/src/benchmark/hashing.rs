@@ -0,0 +1,300 @@
+//! 非密码学哈希和伪随机数生成的吞吐：分别用SipHash（HashMap默认）、FxHash和aHash建立键数相同的HashMap，
+//! 再按相同的随机顺序查找；伪随机数按查找下标的方式生成。结果计入总体评分的CPU部分
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, TestOutcome};
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::rng::{Lcg, Xoshiro256};
+use crate::i18n::Message;
+use crate::ipc::{ExtraTestResult, TestResultPayload, TestType};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::hint::black_box;
+use std::time::Instant;
+
+/// 在BenchmarkRegistry中的注册名，结果记录在extra_results下的同名键中
+pub const HASHING_TEST_NAME: &str = "hashing";
+
+const KEY_SEED: u64 = 0x4B45_5953; // 键和查找顺序的种子，各算法使用相同的键
+const ESTIMATED_OPS_PER_SECOND: f64 = 10_000_000.0; // 估计耗时用，SipHash在缓存未命中时的量级
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum HashAlgorithm {
+    #[serde(rename = "siphash")]
+    Std, // 标准库HashMap默认的SipHash-1-3，带随机密钥
+    #[serde(rename = "fxhash")]
+    Fx,
+    #[serde(rename = "ahash")]
+    AHash,
+}
+
+impl HashAlgorithm {
+    pub const ALL: [HashAlgorithm; 3] = [HashAlgorithm::Std, HashAlgorithm::Fx, HashAlgorithm::AHash];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HashAlgorithm::Std => "siphash",
+            HashAlgorithm::Fx => "fxhash",
+            HashAlgorithm::AHash => "ahash",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PrngAlgorithm {
+    Lcg,
+    Xoshiro256,
+}
+
+impl PrngAlgorithm {
+    pub const ALL: [PrngAlgorithm; 2] = [PrngAlgorithm::Lcg, PrngAlgorithm::Xoshiro256];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PrngAlgorithm::Lcg => "lcg",
+            PrngAlgorithm::Xoshiro256 => "xoshiro256",
+        }
+    }
+}
+
+// 省略的字段取默认值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct HashingTestConfig {
+    pub keys: usize, // HashMap中的键数
+    pub lookups: usize, // 每种哈希算法的查找次数，也是每种生成器生成的随机数个数
+    pub hashers: Vec<HashAlgorithm>,
+    pub prngs: Vec<PrngAlgorithm>,
+}
+
+impl Default for HashingTestConfig {
+    fn default() -> Self {
+        Self {
+            keys: 1_000_000,
+            lookups: 4_000_000,
+            hashers: HashAlgorithm::ALL.to_vec(),
+            prngs: PrngAlgorithm::ALL.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct HasherResult {
+    pub algorithm: HashAlgorithm,
+    pub insert_ops_per_second: f64,
+    pub lookup_ops_per_second: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PrngResult {
+    pub algorithm: PrngAlgorithm,
+    pub numbers_per_second: f64, // 每秒生成并换算为键下标的随机数个数
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct HashingTestResult {
+    pub keys: usize,
+    pub lookups: usize,
+    pub hashers: Vec<HasherResult>,
+    pub prngs: Vec<PrngResult>,
+    pub test_duration: f64, // seconds
+}
+
+impl HashingTestResult {
+    /// 各哈希算法插入和查找、各生成器吞吐的平均值（百万次每秒），计入总体评分的CPU部分
+    pub fn score(&self) -> f64 {
+        let rates: Vec<f64> = self
+            .hashers
+            .iter()
+            .flat_map(|hasher| [hasher.insert_ops_per_second, hasher.lookup_ops_per_second])
+            .chain(self.prngs.iter().map(|prng| prng.numbers_per_second))
+            .collect();
+        if rates.is_empty() {
+            return 0.0;
+        }
+        rates.iter().sum::<f64>() / rates.len() as f64 / 1_000_000.0
+    }
+}
+
+// 按顺序插入全部键后按给定顺序查找，每个键都应命中；返回插入和查找的耗时
+fn measure_map<S: BuildHasher>(hasher: S, keys: &[u64], order: &[usize]) -> Result<(f64, f64), BenchmarkError> {
+    let mut map: HashMap<u64, u64, S> = HashMap::with_capacity_and_hasher(keys.len(), hasher);
+    let started = Instant::now();
+    for (value, &key) in keys.iter().enumerate() {
+        map.insert(black_box(key), value as u64);
+    }
+    let insert_seconds = started.elapsed().as_secs_f64();
+
+    let started = Instant::now();
+    let mut hits = 0usize;
+    for &index in order {
+        if map.get(&black_box(keys[index])) == Some(&(index as u64)) {
+            hits += 1;
+        }
+    }
+    let lookup_seconds = started.elapsed().as_secs_f64();
+    if hits != order.len() {
+        return Err(BenchmarkError::CpuTestError(format!("查找命中{}次，应为{}次", hits, order.len())));
+    }
+    Ok((insert_seconds, lookup_seconds))
+}
+
+// 生成count个[0, keys)内的下标，Lcg按早期测试的方式取模
+fn measure_prng(algorithm: PrngAlgorithm, keys: usize, count: usize) -> f64 {
+    let bound = keys.max(1);
+    let started = Instant::now();
+    let mut sum = 0usize;
+    match algorithm {
+        PrngAlgorithm::Lcg => {
+            let mut rng = Lcg::new(KEY_SEED);
+            for _ in 0..count {
+                sum = sum.wrapping_add(rng.next_u64() as usize % bound);
+            }
+        }
+        PrngAlgorithm::Xoshiro256 => {
+            let mut rng = Xoshiro256::new(KEY_SEED);
+            for _ in 0..count {
+                sum = sum.wrapping_add(rng.index(bound));
+            }
+        }
+    }
+    black_box(sum);
+    started.elapsed().as_secs_f64()
+}
+
+fn rate(operations: usize, seconds: f64) -> f64 {
+    operations as f64 / seconds.max(f64::EPSILON)
+}
+
+pub struct HashingBenchmark {
+    config: HashingTestConfig,
+}
+
+impl HashingBenchmark {
+    pub fn new(config: HashingTestConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn get_config(&self) -> &HashingTestConfig {
+        &self.config
+    }
+
+    pub fn run_benchmark(&self) -> Result<HashingTestResult, BenchmarkError> {
+        self.run_benchmark_cancellable(|_progress, _message| {}, &CancellationToken::never())
+    }
+
+    /// 在各算法之间检查取消
+    pub fn run_benchmark_cancellable<F>(&self, progress_callback: F, cancellation: &CancellationToken) -> Result<HashingTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message),
+    {
+        let started = Instant::now();
+        let mut rng = Xoshiro256::new(KEY_SEED);
+        let keys: Vec<u64> = (0..self.config.keys).map(|_| rng.next_u64()).collect();
+        let order: Vec<usize> = if keys.is_empty() {
+            Vec::new()
+        } else {
+            (0..self.config.lookups).map(|_| rng.index(keys.len())).collect()
+        };
+
+        let total = (self.config.hashers.len() + self.config.prngs.len()).max(1);
+        let mut completed = 0;
+        let mut step = |algorithm: &str| -> Result<(), BenchmarkError> {
+            let progress = completed as f64 / total as f64 * 100.0;
+            cancellation.check(TestType::Extra, progress)?;
+            progress_callback(progress, Message::progress("hashing.progress", progress).param("algorithm", algorithm));
+            completed += 1;
+            Ok(())
+        };
+
+        let mut hashers = Vec::with_capacity(self.config.hashers.len());
+        for &algorithm in &self.config.hashers {
+            step(algorithm.as_str())?;
+            let (insert_seconds, lookup_seconds) = match algorithm {
+                HashAlgorithm::Std => measure_map(RandomState::new(), &keys, &order)?,
+                HashAlgorithm::Fx => measure_map(rustc_hash::FxBuildHasher, &keys, &order)?,
+                HashAlgorithm::AHash => measure_map(ahash::RandomState::new(), &keys, &order)?,
+            };
+            hashers.push(HasherResult {
+                algorithm,
+                insert_ops_per_second: rate(keys.len(), insert_seconds),
+                lookup_ops_per_second: rate(order.len(), lookup_seconds),
+            });
+        }
+
+        let mut prngs = Vec::with_capacity(self.config.prngs.len());
+        for &algorithm in &self.config.prngs {
+            step(algorithm.as_str())?;
+            let seconds = measure_prng(algorithm, self.config.keys, self.config.lookups);
+            prngs.push(PrngResult {
+                algorithm,
+                numbers_per_second: rate(self.config.lookups, seconds),
+            });
+        }
+
+        progress_callback(100.0, Message::new("hashing.complete"));
+        Ok(HashingTestResult {
+            keys: self.config.keys,
+            lookups: self.config.lookups,
+            hashers,
+            prngs,
+            test_duration: started.elapsed().as_secs_f64(),
+        })
+    }
+}
+
+impl BenchmarkRunner for HashingBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Extra
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        let operations = (self.config.keys + self.config.lookups) * self.config.hashers.len() + self.config.lookups * self.config.prngs.len();
+        (operations as f64 / ESTIMATED_OPS_PER_SECOND).ceil().max(1.0) as u64
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        let result = self.run_benchmark_cancellable(ctx.progress_callback(), &ctx.cancellation)?;
+        Ok(TestOutcome::Completed(TestResultPayload::Extra(ExtraTestResult {
+            name: HASHING_TEST_NAME.to_string(),
+            result: serde_json::to_value(&result)
+                .map_err(|e| BenchmarkError::DataSaveError(format!("测试结果序列化失败: {}", e)))?,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throughputs_are_positive() {
+        let config = HashingTestConfig {
+            keys: 10_000,
+            lookups: 50_000,
+            ..HashingTestConfig::default()
+        };
+        let result = HashingBenchmark::new(config).run_benchmark().unwrap();
+        let hashers: Vec<HashAlgorithm> = result.hashers.iter().map(|hasher| hasher.algorithm).collect();
+        assert_eq!(hashers, HashAlgorithm::ALL);
+        assert!(result.hashers.iter().all(|h| h.insert_ops_per_second > 0.0 && h.lookup_ops_per_second > 0.0));
+        assert_eq!(result.prngs.len(), 2);
+        assert!(result.prngs.iter().all(|prng| prng.numbers_per_second > 0.0));
+        assert!(result.score() > 0.0);
+
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(value["hashers"][2]["algorithm"], "ahash");
+        assert_eq!(value["prngs"][1]["algorithm"], "xoshiro256");
+    }
+
+    #[test]
+    fn test_cancellation_between_algorithms() {
+        let token = CancellationToken::new(|| true);
+        let error = HashingBenchmark::new(HashingTestConfig { keys: 10, lookups: 10, ..HashingTestConfig::default() })
+            .run_benchmark_cancellable(|_, _| {}, &token)
+            .unwrap_err();
+        assert_eq!(error.code(), "CANCELLED");
+    }
+}
@@ -0,0 +1,363 @@
+//! 图像编解码吞吐：在由种子生成的固定图像集（渐变、噪声、类照片合成图）上测量PNG和JPEG的编码与解码速度（百万像素每秒）。
+//! 每次解码都计算像素哈希，PNG须与原图一致，JPEG须在各轮之间一致，既防止编译器省略解码，也能发现编解码器的问题
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, TestOutcome};
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::rng::Xoshiro256;
+use crate::i18n::Message;
+use crate::ipc::{ExtraTestResult, TestResultPayload, TestType};
+use ::image::codecs::jpeg::JpegEncoder;
+use ::image::codecs::png::PngEncoder;
+use ::image::{ExtendedColorType, ImageEncoder};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::Instant;
+
+/// 在BenchmarkRegistry中的注册名，结果记录在extra_results下的同名键中
+pub const IMAGE_TEST_NAME: &str = "image";
+
+const CHANNELS: usize = 3; // 所有图像都是RGB8
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+}
+
+impl ImageFormat {
+    pub const ALL: [ImageFormat; 2] = [ImageFormat::Png, ImageFormat::Jpeg];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg => "jpeg",
+        }
+    }
+
+    // 估计耗时用的编码和解码速度（百万像素每秒）
+    fn estimated_megapixels_per_second(self) -> (f64, f64) {
+        match self {
+            ImageFormat::Png => (20.0, 80.0),
+            ImageFormat::Jpeg => (40.0, 80.0),
+        }
+    }
+
+    fn codec_format(self) -> ::image::ImageFormat {
+        match self {
+            ImageFormat::Png => ::image::ImageFormat::Png,
+            ImageFormat::Jpeg => ::image::ImageFormat::Jpeg,
+        }
+    }
+}
+
+/// 图像集中的一类图像，压缩难度依次增加：渐变几乎没有细节，噪声无法压缩，合成图介于两者之间
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyntheticImage {
+    Gradient,
+    Noise,
+    Composite, // 平滑背景上叠加随机的圆形色块和轻微噪声，近似照片的统计特征
+}
+
+impl SyntheticImage {
+    pub const ALL: [SyntheticImage; 3] = [SyntheticImage::Gradient, SyntheticImage::Noise, SyntheticImage::Composite];
+}
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ImageTestConfig {
+    pub width: u32,
+    pub height: u32,
+    pub seed: u64, // 噪声和合成图的随机种子
+    pub iterations: usize, // 每种格式编码和解码整个图像集的次数
+    pub formats: Vec<ImageFormat>,
+    #[serde(alias = "jpegQuality")]
+    pub jpeg_quality: u8, // 1-100
+}
+
+impl Default for ImageTestConfig {
+    fn default() -> Self {
+        Self {
+            width: 1920,
+            height: 1080,
+            seed: 0x1A6E,
+            iterations: 3,
+            formats: ImageFormat::ALL.to_vec(),
+            jpeg_quality: 90,
+        }
+    }
+}
+
+impl ImageTestConfig {
+    /// 尺寸为0或JPEG质量超出范围时返回错误，供注册表在套件开始前报告
+    pub fn check(&self) -> Result<(), BenchmarkError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(BenchmarkError::ImageTestError(format!("图像尺寸无效: {}x{}", self.width, self.height)));
+        }
+        if !(1..=100).contains(&self.jpeg_quality) {
+            return Err(BenchmarkError::ImageTestError(format!("JPEG质量必须在1到100之间: {}", self.jpeg_quality)));
+        }
+        Ok(())
+    }
+
+    fn megapixels(&self) -> f64 {
+        self.width as f64 * self.height as f64 / 1_000_000.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ImageFormatResult {
+    pub format: ImageFormat,
+    pub encode_megapixels_per_second: f64,
+    pub decode_megapixels_per_second: f64,
+    pub encoded_bytes: usize, // 整个图像集编码后的大小
+    pub pixel_hash: String, // 解码后整个图像集像素的SHA-256（十六进制）
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ImageTestResult {
+    pub width: u32,
+    pub height: u32,
+    pub images: Vec<SyntheticImage>,
+    pub formats: Vec<ImageFormatResult>,
+    pub test_duration: f64, // seconds
+}
+
+/// 生成一张RGB8图像，同一种子和尺寸在任何机器上生成相同的像素
+pub fn generate_image(kind: SyntheticImage, width: u32, height: u32, seed: u64) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    let mut pixels = vec![0u8; w * h * CHANNELS];
+    let mut rng = Xoshiro256::new(seed);
+    match kind {
+        SyntheticImage::Gradient => {
+            for (index, pixel) in pixels.chunks_exact_mut(CHANNELS).enumerate() {
+                let (x, y) = (index % w, index / w);
+                pixel[0] = (x * 255 / (w - 1).max(1)) as u8;
+                pixel[1] = (y * 255 / (h - 1).max(1)) as u8;
+                pixel[2] = ((x + y) * 255 / (w + h - 2).max(1)) as u8;
+            }
+        }
+        SyntheticImage::Noise => {
+            for chunk in pixels.chunks_mut(8) {
+                let bytes = rng.next_u64().to_le_bytes();
+                chunk.copy_from_slice(&bytes[..chunk.len()]);
+            }
+        }
+        SyntheticImage::Composite => {
+            // 上亮下暗的背景
+            for (index, pixel) in pixels.chunks_exact_mut(CHANNELS).enumerate() {
+                let y = index / w;
+                let shade = 200 - (y * 140 / h.max(1)) as u8;
+                pixel.copy_from_slice(&[shade / 2, shade.saturating_sub(20), shade]);
+            }
+            // 随机的圆形色块
+            let radius_limit = (w.min(h) / 6).max(2) as u64;
+            for _ in 0..48 {
+                let (cx, cy) = (rng.below(w as u64) as i64, rng.below(h as u64) as i64);
+                let radius = 1 + rng.below(radius_limit) as i64;
+                let color = rng.next_u64().to_le_bytes();
+                for y in (cy - radius).max(0)..(cy + radius).min(h as i64) {
+                    for x in (cx - radius).max(0)..(cx + radius).min(w as i64) {
+                        if (x - cx).pow(2) + (y - cy).pow(2) <= radius * radius {
+                            let offset = (y as usize * w + x as usize) * CHANNELS;
+                            pixels[offset..offset + CHANNELS].copy_from_slice(&color[..CHANNELS]);
+                        }
+                    }
+                }
+            }
+            // 轻微的传感器噪声
+            for value in pixels.iter_mut() {
+                let noise = rng.below(9) as i16 - 4;
+                *value = (*value as i16 + noise).clamp(0, 255) as u8;
+            }
+        }
+    }
+    pixels
+}
+
+fn encode(format: ImageFormat, pixels: &[u8], width: u32, height: u32, jpeg_quality: u8) -> Result<Vec<u8>, BenchmarkError> {
+    let mut output = Vec::with_capacity(pixels.len() / 2);
+    let result = match format {
+        ImageFormat::Png => PngEncoder::new(&mut output).write_image(pixels, width, height, ExtendedColorType::Rgb8),
+        ImageFormat::Jpeg => JpegEncoder::new_with_quality(&mut output, jpeg_quality).write_image(pixels, width, height, ExtendedColorType::Rgb8),
+    };
+    result.map_err(|e| BenchmarkError::ImageTestError(format!("{}编码失败: {}", format.as_str(), e)))?;
+    Ok(output)
+}
+
+fn decode(format: ImageFormat, encoded: &[u8]) -> Result<Vec<u8>, BenchmarkError> {
+    let image = ::image::load_from_memory_with_format(encoded, format.codec_format())
+        .map_err(|e| BenchmarkError::ImageTestError(format!("{}解码失败: {}", format.as_str(), e)))?;
+    Ok(image.into_rgb8().into_raw())
+}
+
+pub struct ImageBenchmark {
+    config: ImageTestConfig,
+}
+
+impl ImageBenchmark {
+    pub fn new(config: ImageTestConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn get_config(&self) -> &ImageTestConfig {
+        &self.config
+    }
+
+    pub fn run_benchmark(&self) -> Result<ImageTestResult, BenchmarkError> {
+        self.run_benchmark_cancellable(|_progress, _message| {}, &CancellationToken::never())
+    }
+
+    /// 依次测量各格式，在每张图像之间检查取消
+    pub fn run_benchmark_cancellable<F>(&self, progress_callback: F, cancellation: &CancellationToken) -> Result<ImageTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message),
+    {
+        self.config.check()?;
+        let started = Instant::now();
+        let (width, height) = (self.config.width, self.config.height);
+        let images: Vec<Vec<u8>> = SyntheticImage::ALL
+            .iter()
+            .enumerate()
+            .map(|(index, &kind)| generate_image(kind, width, height, self.config.seed.wrapping_add(index as u64)))
+            .collect();
+        let mut original = Sha256::new();
+        images.iter().for_each(|pixels| original.update(pixels));
+        let original_hash = format!("{:x}", original.finalize());
+
+        let iterations = self.config.iterations.max(1);
+        let steps = (self.config.formats.len() * iterations * images.len()).max(1);
+        let megapixels = self.config.megapixels() * (iterations * images.len()) as f64;
+        let mut formats = Vec::with_capacity(self.config.formats.len());
+        for (format_index, &format) in self.config.formats.iter().enumerate() {
+            let mut encode_seconds = 0.0;
+            let mut decode_seconds = 0.0;
+            let mut encoded_bytes = 0;
+            let mut pixel_hash: Option<String> = None;
+            for iteration in 0..iterations {
+                let mut decoded_hash = Sha256::new();
+                encoded_bytes = 0;
+                for (image_index, pixels) in images.iter().enumerate() {
+                    let progress = ((format_index * iterations + iteration) * images.len() + image_index) as f64 / steps as f64 * 100.0;
+                    cancellation.check(TestType::Extra, progress)?;
+                    progress_callback(progress, Message::progress("image.progress", progress).param("format", format.as_str()));
+
+                    let timer = Instant::now();
+                    let encoded = encode(format, pixels, width, height, self.config.jpeg_quality)?;
+                    encode_seconds += timer.elapsed().as_secs_f64();
+
+                    let timer = Instant::now();
+                    let decoded = decode(format, &encoded)?;
+                    decode_seconds += timer.elapsed().as_secs_f64();
+
+                    if decoded.len() != pixels.len() {
+                        return Err(BenchmarkError::ImageTestError(format!("{}解码后的尺寸与原图不一致", format.as_str())));
+                    }
+                    decoded_hash.update(&decoded);
+                    encoded_bytes += encoded.len();
+                }
+                let hash = format!("{:x}", decoded_hash.finalize());
+                match &pixel_hash {
+                    Some(previous) if *previous != hash => {
+                        return Err(BenchmarkError::ImageTestError(format!("{}各轮解码的像素不一致", format.as_str())));
+                    }
+                    Some(_) => {}
+                    None => pixel_hash = Some(hash),
+                }
+            }
+            let pixel_hash = pixel_hash.unwrap_or_default();
+            if format == ImageFormat::Png && pixel_hash != original_hash {
+                return Err(BenchmarkError::ImageTestError("PNG解码后的像素与原图不一致".to_string()));
+            }
+            formats.push(ImageFormatResult {
+                format,
+                encode_megapixels_per_second: megapixels / encode_seconds.max(f64::EPSILON),
+                decode_megapixels_per_second: megapixels / decode_seconds.max(f64::EPSILON),
+                encoded_bytes,
+                pixel_hash,
+            });
+        }
+
+        progress_callback(100.0, Message::new("image.complete"));
+        Ok(ImageTestResult {
+            width,
+            height,
+            images: SyntheticImage::ALL.to_vec(),
+            formats,
+            test_duration: started.elapsed().as_secs_f64(),
+        })
+    }
+}
+
+impl BenchmarkRunner for ImageBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Extra
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        let megapixels = self.config.megapixels() * (self.config.iterations.max(1) * SyntheticImage::ALL.len()) as f64;
+        let seconds: f64 = self
+            .config
+            .formats
+            .iter()
+            .map(|format| {
+                let (encode, decode) = format.estimated_megapixels_per_second();
+                megapixels / encode + megapixels / decode
+            })
+            .sum();
+        seconds.ceil().max(1.0) as u64
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        let result = self.run_benchmark_cancellable(ctx.progress_callback(), &ctx.cancellation)?;
+        Ok(TestOutcome::Completed(TestResultPayload::Extra(ExtraTestResult {
+            name: IMAGE_TEST_NAME.to_string(),
+            result: serde_json::to_value(&result)
+                .map_err(|e| BenchmarkError::DataSaveError(format!("测试结果序列化失败: {}", e)))?,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoded_fixture_hash_is_pinned() {
+        // 合成图经PNG往返后的像素哈希，生成算法或解码出错时会变化
+        let pixels = generate_image(SyntheticImage::Composite, 64, 48, 7);
+        let decoded = decode(ImageFormat::Png, &encode(ImageFormat::Png, &pixels, 64, 48, 90).unwrap()).unwrap();
+        assert_eq!(decoded, pixels);
+        assert_eq!(format!("{:x}", Sha256::digest(&decoded)), "f7b502b7b25656276401f6c9658b9dd11e1b72b99466cfbb210afb0e9e4fd45a");
+        assert_ne!(generate_image(SyntheticImage::Composite, 64, 48, 8), pixels);
+    }
+
+    #[test]
+    fn test_throughputs_are_positive() {
+        let config = ImageTestConfig {
+            width: 160,
+            height: 120,
+            iterations: 2,
+            ..ImageTestConfig::default()
+        };
+        let result = ImageBenchmark::new(config).run_benchmark().unwrap();
+        let formats: Vec<ImageFormat> = result.formats.iter().map(|format| format.format).collect();
+        assert_eq!(formats, ImageFormat::ALL);
+        for format in &result.formats {
+            assert!(format.encode_megapixels_per_second > 0.0 && format.decode_megapixels_per_second > 0.0, "{:?}", format);
+            assert!(format.encoded_bytes > 0);
+            assert_eq!(format.pixel_hash.len(), 64);
+        }
+        // JPEG有损，解码后的像素与PNG不同
+        assert_ne!(result.formats[0].pixel_hash, result.formats[1].pixel_hash);
+
+        let invalid = ImageTestConfig { jpeg_quality: 0, ..ImageTestConfig::default() };
+        assert_eq!(invalid.check().unwrap_err().code(), "IMAGE_TEST_ERROR");
+        let error = ImageBenchmark::new(ImageTestConfig { width: 16, height: 16, ..ImageTestConfig::default() })
+            .run_benchmark_cancellable(|_, _| {}, &CancellationToken::new(|| true))
+            .unwrap_err();
+        assert_eq!(error.code(), "CANCELLED");
+    }
+}
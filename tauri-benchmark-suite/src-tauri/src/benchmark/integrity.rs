@@ -0,0 +1,165 @@
+//! 结果完整性校验：保存时对结果的规范化JSON计算SHA-256，读取和导入时重新计算比对，
+//! 不一致的结果标记为未通过校验而不是拒绝
+use crate::benchmark::core::TestResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+// 不参与哈希的顶层字段：校验信息本身，以及导入时才设置的本地标记
+const EXCLUDED_FIELDS: [&str; 2] = ["integrity", "imported"];
+
+/// 保存时写入结果的校验信息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ResultIntegrity {
+    pub hash: String,           // 规范化JSON的SHA-256（十六进制）
+    pub engine_version: String, // 计算哈希的引擎版本
+    #[serde(default)]
+    pub verified: bool, // 读取时重新计算，文件中的值不作为依据
+}
+
+/// 规范化JSON：对象键按字典序排列并省略null值，数字统一按Rust的最短往返格式输出
+/// （整数值的浮点数不带小数点，不使用指数），与平台和JSON库的浮点格式无关
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => {
+            if let Some(u) = n.as_u64() {
+                out.push_str(&u.to_string());
+            } else if let Some(i) = n.as_i64() {
+                out.push_str(&i.to_string());
+            } else {
+                out.push_str(&n.as_f64().unwrap_or_default().to_string());
+            }
+        }
+        Value::String(s) => out.push_str(&Value::String(s.clone()).to_string()),
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().filter(|(_, v)| !v.is_null()).collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (index, (key, item)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(item, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+/// 结果内容的哈希，不含校验信息和导入标记
+pub fn result_hash(result: &TestResult) -> String {
+    let mut value = serde_json::to_value(result).unwrap_or(Value::Null);
+    if let Value::Object(map) = &mut value {
+        for field in EXCLUDED_FIELDS {
+            map.remove(field);
+        }
+    }
+    format!("{:x}", Sha256::digest(canonical_json(&value).as_bytes()))
+}
+
+impl TestResult {
+    /// 计算并写入校验信息
+    pub fn seal(&mut self) {
+        self.integrity = Some(ResultIntegrity {
+            hash: result_hash(self),
+            engine_version: env!("CARGO_PKG_VERSION").to_string(),
+            verified: true,
+        });
+    }
+
+    /// 重新计算哈希并更新verified，没有校验信息时返回None
+    pub fn verify_integrity(&mut self) -> Option<bool> {
+        let hash = result_hash(self);
+        let integrity = self.integrity.as_mut()?;
+        integrity.verified = integrity.hash == hash;
+        Some(integrity.verified)
+    }
+
+    /// 校验状态：None表示结果没有校验信息（旧版本保存或外部生成）
+    pub fn integrity_verified(&self) -> Option<bool> {
+        self.integrity.as_ref().map(|integrity| integrity.verified)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::schema::upgrade_result_document;
+    use crate::benchmark::test_support::sample_test_result;
+    use serde_json::json;
+
+    #[test]
+    fn test_canonical_json_is_ordered_and_float_stable() {
+        let value = json!({
+            "b": [0.1, 0.30000000000000004, 1e-7, 2.5e21, -0.0],
+            "a": { "z": 1.0, "y": 18446744073709551615u64, "x": -3, "w": null },
+            "c": "引号\"和换行\n",
+        });
+        assert_eq!(
+            canonical_json(&value),
+            r#"{"a":{"x":-3,"y":18446744073709551615,"z":1},"b":[0.1,0.30000000000000004,0.0000001,2500000000000000000000,-0],"c":"引号\"和换行\n"}"#
+        );
+        // 同一数值写成整数或浮点数时规范化结果相同
+        assert_eq!(canonical_json(&json!({ "v": 60 })), canonical_json(&json!({ "v": 60.0 })));
+    }
+
+    #[test]
+    fn test_seal_round_trip_and_single_field_edit() {
+        let mut result = sample_test_result("2024-01-01T00:00:00Z", 42.0);
+        result.seal();
+        assert_eq!(result.integrity_verified(), Some(true));
+
+        let mut reloaded: TestResult = serde_json::from_str(&serde_json::to_string_pretty(&result).unwrap()).unwrap();
+        assert_eq!(reloaded.verify_integrity(), Some(true));
+        // 导入标记不影响校验
+        reloaded.imported = true;
+        assert_eq!(reloaded.verify_integrity(), Some(true));
+
+        let mut edited = reloaded.clone();
+        edited.cpu_results.as_mut().unwrap().single_thread_score += 0.5;
+        assert_eq!(edited.verify_integrity(), Some(false));
+
+        // 文件中的verified不可信，读取时重新计算
+        let mut document = serde_json::to_value(&result).unwrap();
+        document["overall_score"] = json!(99.0);
+        let mut tampered: TestResult = serde_json::from_value(document).unwrap();
+        assert!(tampered.integrity.as_ref().unwrap().verified);
+        assert_eq!(tampered.verify_integrity(), Some(false));
+
+        let mut unsealed = sample_test_result("2024-01-01T00:00:00Z", 42.0);
+        assert_eq!(unsealed.verify_integrity(), None);
+    }
+
+    #[test]
+    fn test_fixture_hash_is_stable() {
+        // 固定的结果文件和哈希，规范化规则或浮点格式变化时这里会失败
+        // 固定文件是版本2，升级时会重新计算评分和哈希，因此按原版本直接解析
+        let document: Value = serde_json::from_str(include_str!("../../tests/fixtures/result_sealed.json")).unwrap();
+        let mut result: TestResult = serde_json::from_value(document.clone()).unwrap();
+        let expected = result.integrity.as_ref().unwrap().hash.clone();
+        assert_eq!(result_hash(&result), expected);
+        assert_eq!(result.verify_integrity(), Some(true));
+        assert_eq!(upgrade_result_document(document).unwrap().verify_integrity(), Some(true));
+    }
+}
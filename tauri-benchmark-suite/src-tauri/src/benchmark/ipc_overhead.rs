@@ -0,0 +1,340 @@
+//! Tauri桥本身的开销：按IPC的两种编码路径（JSON参数和原始字节）把不同大小的负载交给回显处理函数，
+//! 测量往返延迟的分位数和吞吐。套件中在进程内完成编码、解析、处理和再编码的全过程，
+//! 前端调用ipc_echo和ipc_echo_raw可以测量经过WebView的完整往返
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, TestOutcome};
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::metrics::percentile;
+use crate::i18n::Message;
+use crate::ipc::{ExtraTestResult, TestResultPayload, TestType};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// 在BenchmarkRegistry中的注册名，结果记录在extra_results下的同名键中
+pub const IPC_TEST_NAME: &str = "ipc";
+
+// 进程内估计耗时用的编码速度：JSON路径每次往返编码和解析4遍，原始字节路径复制2遍
+const JSON_BYTES_PER_SECOND: f64 = 200.0 * 1024.0 * 1024.0;
+const BINARY_BYTES_PER_SECOND: f64 = 4.0 * 1024.0 * 1024.0 * 1024.0;
+
+/// IPC的负载编码路径
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IpcTransport {
+    Json, // 普通命令参数，负载编码为JSON字符串
+    Binary, // 原始字节请求体和响应（tauri::ipc::Request / Response），Android上不可用
+}
+
+impl IpcTransport {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IpcTransport::Json => "json",
+            IpcTransport::Binary => "binary",
+        }
+    }
+}
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct IpcBenchmarkConfig {
+    #[serde(alias = "payloadSizes")]
+    pub payload_sizes: Vec<usize>, // KB
+    pub iterations: usize, // 每种大小和路径的计时次数
+    #[serde(alias = "warmupIterations")]
+    pub warmup_iterations: usize, // 计时前先运行的次数，不计入结果
+    #[serde(alias = "trimPercent")]
+    pub trim_percent: f64, // 排序后两端各去掉的比例（%），排除调度造成的离群值
+    pub transports: Vec<IpcTransport>,
+}
+
+impl Default for IpcBenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            payload_sizes: vec![1, 16, 256, 1024, 4096], // 1KB到4MB
+            iterations: 100,
+            warmup_iterations: 10,
+            trim_percent: 5.0,
+            transports: vec![IpcTransport::Json, IpcTransport::Binary],
+        }
+    }
+}
+
+/// 一种负载大小和编码路径的测量结果，延迟单位为微秒
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct IpcMeasurement {
+    pub transport: IpcTransport,
+    pub payload_bytes: usize,
+    pub samples: usize, // 去掉离群值后的样本数
+    pub p50_us: f64,
+    pub p95_us: f64,
+    pub p99_us: f64,
+    pub mean_us: f64,
+    pub server_mean_us: f64, // 处理函数一侧的耗时（解析参数、回显和编码响应）
+    pub throughput_mb_s: f64, // 往返两个方向的数据量除以往返总耗时
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct IpcBenchmarkResult {
+    pub measurements: Vec<IpcMeasurement>,
+    pub test_duration: f64, // seconds
+}
+
+/// 一次回显：返回的负载字节数和处理函数一侧的耗时
+#[derive(Debug, Clone, Copy)]
+pub struct EchoReply {
+    pub bytes: usize,
+    pub server_time: Duration,
+}
+
+/// ipc_echo的参数和响应
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IpcEchoReply {
+    pub payload: String,
+    pub server_micros: f64, // 从进入处理函数到生成响应的耗时
+}
+
+#[derive(Deserialize)]
+struct EchoArgs {
+    payload: String,
+}
+
+/// ipc_echo的处理逻辑：原样返回负载，附带处理耗时
+pub fn echo(payload: String, received: Instant) -> IpcEchoReply {
+    IpcEchoReply {
+        payload,
+        server_micros: received.elapsed().as_secs_f64() * 1e6,
+    }
+}
+
+fn invalid_reply(error: impl std::fmt::Display) -> BenchmarkError {
+    BenchmarkError::IpcTestError(format!("回显响应无效: {}", error))
+}
+
+/// JSON路径的一次进程内往返：前端编码参数，后端解析、回显并编码响应，前端解析响应
+pub fn json_round_trip(payload: &str) -> Result<EchoReply, BenchmarkError> {
+    let request = serde_json::to_vec(&serde_json::json!({ "payload": payload })).map_err(invalid_reply)?;
+
+    let received = Instant::now();
+    let args: EchoArgs = serde_json::from_slice(&request).map_err(invalid_reply)?;
+    let response = serde_json::to_vec(&echo(args.payload, received)).map_err(invalid_reply)?;
+    let server_time = received.elapsed();
+
+    let reply: IpcEchoReply = serde_json::from_slice(&response).map_err(invalid_reply)?;
+    Ok(EchoReply {
+        bytes: reply.payload.len(),
+        server_time,
+    })
+}
+
+/// 原始字节路径的一次进程内往返：请求体原样作为响应返回，两端各复制一次
+pub fn binary_round_trip(payload: &[u8]) -> EchoReply {
+    let request = payload.to_vec();
+
+    let received = Instant::now();
+    let response = request;
+    let server_time = received.elapsed();
+
+    let reply = std::hint::black_box(response).to_vec();
+    EchoReply {
+        bytes: reply.len(),
+        server_time,
+    }
+}
+
+/// 先预热，再计时iterations次往返。每次都检查返回的字节数，去掉两端离群值后计算分位数和吞吐
+pub fn measure(
+    transport: IpcTransport,
+    payload_bytes: usize,
+    config: &IpcBenchmarkConfig,
+    mut round_trip: impl FnMut() -> Result<EchoReply, BenchmarkError>,
+) -> Result<IpcMeasurement, BenchmarkError> {
+    for _ in 0..config.warmup_iterations {
+        round_trip()?;
+    }
+    let mut samples = Vec::with_capacity(config.iterations);
+    for _ in 0..config.iterations.max(1) {
+        let started = Instant::now();
+        let reply = round_trip()?;
+        let elapsed = started.elapsed();
+        if reply.bytes != payload_bytes {
+            return Err(BenchmarkError::IpcTestError(format!(
+                "回显了{}字节，应为{}字节",
+                reply.bytes, payload_bytes
+            )));
+        }
+        samples.push((elapsed.as_secs_f64() * 1e6, reply.server_time.as_secs_f64() * 1e6));
+    }
+
+    // 按往返耗时排序后两端各去掉trim_percent，至少保留一个样本
+    samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let trim = ((samples.len() as f64 * config.trim_percent.clamp(0.0, 49.0) / 100.0) as usize).min((samples.len() - 1) / 2);
+    let kept = &samples[trim..samples.len() - trim];
+    let round_trips: Vec<f64> = kept.iter().map(|sample| sample.0).collect();
+    let total_us: f64 = round_trips.iter().sum();
+    let mean_us = total_us / kept.len() as f64;
+    Ok(IpcMeasurement {
+        transport,
+        payload_bytes,
+        samples: kept.len(),
+        p50_us: percentile(&round_trips, 50.0),
+        p95_us: percentile(&round_trips, 95.0),
+        p99_us: percentile(&round_trips, 99.0),
+        mean_us,
+        server_mean_us: kept.iter().map(|sample| sample.1).sum::<f64>() / kept.len() as f64,
+        throughput_mb_s: if total_us > 0.0 {
+            (2 * payload_bytes * kept.len()) as f64 / (1024.0 * 1024.0) / (total_us / 1e6)
+        } else {
+            0.0
+        },
+    })
+}
+
+pub struct IpcBenchmark {
+    config: IpcBenchmarkConfig,
+}
+
+impl IpcBenchmark {
+    pub fn new(config: IpcBenchmarkConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn get_config(&self) -> &IpcBenchmarkConfig {
+        &self.config
+    }
+
+    pub fn run_benchmark(&self) -> Result<IpcBenchmarkResult, BenchmarkError> {
+        self.run_benchmark_cancellable(|_progress, _message| {}, &CancellationToken::never())
+    }
+
+    /// 按负载大小从小到大、每种大小依次测量各编码路径，每项测量之间检查取消
+    pub fn run_benchmark_cancellable<F>(&self, progress_callback: F, cancellation: &CancellationToken) -> Result<IpcBenchmarkResult, BenchmarkError>
+    where
+        F: Fn(f64, Message),
+    {
+        let started = Instant::now();
+        let total = (self.config.payload_sizes.len() * self.config.transports.len()).max(1);
+        let mut measurements = Vec::with_capacity(total);
+        for &size_kb in &self.config.payload_sizes {
+            let payload_bytes = size_kb * 1024;
+            let text = "x".repeat(payload_bytes);
+            let bytes = vec![0xA5u8; payload_bytes];
+            for &transport in &self.config.transports {
+                let progress = measurements.len() as f64 / total as f64 * 100.0;
+                cancellation.check(TestType::Extra, progress)?;
+                progress_callback(
+                    progress,
+                    Message::progress("ipc.progress", progress)
+                        .param("size", size_kb)
+                        .param("transport", transport.as_str()),
+                );
+                let measurement = match transport {
+                    IpcTransport::Json => measure(transport, payload_bytes, &self.config, || json_round_trip(&text))?,
+                    IpcTransport::Binary => measure(transport, payload_bytes, &self.config, || Ok(binary_round_trip(&bytes)))?,
+                };
+                measurements.push(measurement);
+            }
+        }
+        progress_callback(100.0, Message::new("ipc.complete"));
+        Ok(IpcBenchmarkResult {
+            measurements,
+            test_duration: started.elapsed().as_secs_f64(),
+        })
+    }
+}
+
+impl BenchmarkRunner for IpcBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Extra
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        let rounds = (self.config.iterations + self.config.warmup_iterations) as f64;
+        let seconds: f64 = self
+            .config
+            .payload_sizes
+            .iter()
+            .flat_map(|size_kb| self.config.transports.iter().map(move |transport| (*size_kb as f64 * 1024.0, transport)))
+            .map(|(bytes, transport)| match transport {
+                IpcTransport::Json => rounds * bytes * 4.0 / JSON_BYTES_PER_SECOND,
+                IpcTransport::Binary => rounds * bytes * 2.0 / BINARY_BYTES_PER_SECOND,
+            })
+            .sum();
+        seconds.ceil().max(1.0) as u64
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        let result = self.run_benchmark_cancellable(ctx.progress_callback(), &ctx.cancellation)?;
+        Ok(TestOutcome::Completed(TestResultPayload::Extra(ExtraTestResult {
+            name: IPC_TEST_NAME.to_string(),
+            result: serde_json::to_value(&result)
+                .map_err(|e| BenchmarkError::DataSaveError(format!("测试结果序列化失败: {}", e)))?,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quick_config() -> IpcBenchmarkConfig {
+        IpcBenchmarkConfig {
+            payload_sizes: vec![1, 1024],
+            iterations: 20,
+            warmup_iterations: 2,
+            ..IpcBenchmarkConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_percentiles_are_ordered_and_scale_with_size() {
+        let result = IpcBenchmark::new(quick_config()).run_benchmark().unwrap();
+        assert_eq!(result.measurements.len(), 4);
+        for measurement in &result.measurements {
+            assert!(measurement.p50_us <= measurement.p95_us, "{:?}", measurement);
+            assert!(measurement.p95_us <= measurement.p99_us, "{:?}", measurement);
+            assert!(measurement.mean_us > 0.0 && measurement.throughput_mb_s > 0.0);
+            assert!(measurement.server_mean_us <= measurement.mean_us);
+            assert_eq!(measurement.samples, 18); // 20次两端各去掉5%
+        }
+        // 同一路径下1MB的往返比1KB慢，JSON路径比原始字节慢
+        let find = |transport, size_kb: usize| {
+            result
+                .measurements
+                .iter()
+                .find(|m| m.transport == transport && m.payload_bytes == size_kb * 1024)
+                .unwrap()
+        };
+        assert!(find(IpcTransport::Json, 1024).p50_us > find(IpcTransport::Json, 1).p50_us);
+        assert!(find(IpcTransport::Binary, 1024).p50_us > find(IpcTransport::Binary, 1).p50_us);
+        assert!(find(IpcTransport::Json, 1024).p50_us > find(IpcTransport::Binary, 1024).p50_us);
+    }
+
+    #[test]
+    fn test_outliers_are_trimmed_and_short_replies_rejected() {
+        let config = IpcBenchmarkConfig {
+            iterations: 20,
+            warmup_iterations: 0,
+            trim_percent: 10.0,
+            ..IpcBenchmarkConfig::default()
+        };
+        // 第一次往返被“调度”拖慢，去掉两端10%后不影响p99
+        let mut calls = 0;
+        let measurement = measure(IpcTransport::Binary, 4, &config, || {
+            calls += 1;
+            if calls == 1 {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            Ok(EchoReply { bytes: 4, server_time: Duration::ZERO })
+        })
+        .unwrap();
+        assert_eq!(calls, 20);
+        assert_eq!(measurement.samples, 16);
+        assert!(measurement.p99_us < 50_000.0, "{:?}", measurement);
+
+        let error = measure(IpcTransport::Json, 8, &config, || Ok(EchoReply { bytes: 4, server_time: Duration::ZERO })).unwrap_err();
+        assert_eq!(error.code(), "IPC_TEST_ERROR");
+        assert_eq!(json_round_trip("abc").unwrap().bytes, 3);
+    }
+}
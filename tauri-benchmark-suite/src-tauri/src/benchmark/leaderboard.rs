@@ -0,0 +1,189 @@
+//! 排行榜提交格式：从通过完整性校验的结果生成的紧凑条目，字段固定并带有格式版本，
+//! 供共享的结果服务器收录；也可以把服务器导出的条目导入为轻量的伪结果，在对比界面中展示
+use crate::benchmark::core::TestResult;
+use crate::benchmark::scoring::category_scores;
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::ranking::ReferenceDataset;
+use crate::benchmark::results::{HeadlineMetrics, ResultSummary};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 条目格式版本，增删或改变字段含义时递增
+pub const LEADERBOARD_FORMAT_VERSION: u32 = 2;
+
+// 导入的条目在历史列表中的ID前缀，与本地结果区分
+const PSEUDO_RESULT_PREFIX: &str = "leaderboard:";
+
+/// 排行榜条目。分数保留两位小数，同一结果在任何平台上生成的条目完全相同
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LeaderboardEntry {
+    pub format_version: u32,
+    pub machine_fingerprint: String,
+    pub cpu_model: String,
+    pub gpu_model: Option<String>, // 未运行GPU测试时为空
+    pub category_scores: BTreeMap<String, f64>, // cpu/memory/storage/gpu/webview/network，与总分的计算方式一致
+    pub overall_score: f64,
+    pub engine_version: String, // 计算结果哈希的引擎版本
+    pub result_schema_version: u32,
+    pub reference_dataset: String, // 排名所用的参考数据
+    pub reference_dataset_version: u32,
+    pub integrity_hash: String,
+    pub timestamp: String,
+}
+
+impl LeaderboardEntry {
+    /// 只接受通过完整性校验的结果：没有校验信息或内容被修改过的结果都会被拒绝
+    pub fn from_result(result: &TestResult, dataset: &ReferenceDataset) -> Result<Self, BenchmarkError> {
+        let integrity = result
+            .integrity
+            .as_ref()
+            .filter(|integrity| integrity.verified)
+            .ok_or_else(|| BenchmarkError::UploadError("结果未通过完整性校验，不能生成排行榜条目".to_string()))?;
+        Ok(Self {
+            format_version: LEADERBOARD_FORMAT_VERSION,
+            machine_fingerprint: result.system_info.hardware_fingerprint(),
+            cpu_model: result.system_info.cpu.name.clone(),
+            gpu_model: result.gpu_results.as_ref().map(|gpu| gpu.adapter_name.clone()),
+            category_scores: category_scores(result)
+                .into_iter()
+                .map(|(category, score)| (category.to_string(), round_score(score)))
+                .collect(),
+            overall_score: round_score(result.overall_score),
+            engine_version: integrity.engine_version.clone(),
+            result_schema_version: result.schema_version,
+            reference_dataset: dataset.name.clone(),
+            reference_dataset_version: dataset.version,
+            integrity_hash: integrity.hash.clone(),
+            timestamp: result.timestamp.clone(),
+        })
+    }
+
+    /// 在历史列表和对比界面中展示的伪结果，没有详细指标，完整性无法在本地校验
+    pub fn to_summary(&self) -> ResultSummary {
+        let hash_prefix: String = self.integrity_hash.chars().take(12).collect();
+        ResultSummary {
+            id: format!("{}{}:{}", PSEUDO_RESULT_PREFIX, self.machine_fingerprint, hash_prefix),
+            timestamp: self.timestamp.clone(),
+            overall_score: self.overall_score,
+            headline: HeadlineMetrics::default(),
+            hardware_fingerprint: self.machine_fingerprint.clone(),
+            has_cpu_results: self.category_scores.contains_key("cpu"),
+            has_memory_results: self.category_scores.contains_key("memory"),
+            has_storage_results: self.category_scores.contains_key("storage"),
+            artifacts: Vec::new(),
+            upload: None,
+            imported: true,
+            content_hash: Some(self.integrity_hash.clone()),
+            run_id: None,
+            session_id: None,
+            verified: None,
+            config_hash: None,
+            duplicate_of: None,
+            schema_version: self.result_schema_version,
+        }
+    }
+}
+
+/// 解析服务器导出的条目：接受条目数组或`{"entries": [...]}`，格式版本不受支持的条目整体拒绝
+pub fn parse_leaderboard_entries(json: &str) -> Result<Vec<LeaderboardEntry>, BenchmarkError> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Document {
+        Entries(Vec<LeaderboardEntry>),
+        Wrapped { entries: Vec<LeaderboardEntry> },
+    }
+
+    let entries = match serde_json::from_str::<Document>(json)
+        .map_err(|e| BenchmarkError::DataLoadError(format!("排行榜条目解析失败: {}", e)))?
+    {
+        Document::Entries(entries) | Document::Wrapped { entries } => entries,
+    };
+    if let Some(entry) = entries.iter().find(|entry| entry.format_version != LEADERBOARD_FORMAT_VERSION) {
+        return Err(BenchmarkError::DataLoadError(format!(
+            "排行榜条目格式版本 {} 不受支持，当前支持版本 {}",
+            entry.format_version, LEADERBOARD_FORMAT_VERSION
+        )));
+    }
+    Ok(entries)
+}
+
+fn round_score(score: f64) -> f64 {
+    (score * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::schema::RESULT_SCHEMA_VERSION;
+    use crate::benchmark::test_support::sample_test_result;
+    use serde_json::json;
+
+    #[test]
+    fn test_entry_snapshot_for_sealed_result() {
+        let mut result = sample_test_result("2024-06-01T09:00:00Z", 123.456);
+        result.seal();
+        let dataset = ReferenceDataset::embedded().unwrap();
+        let entry = LeaderboardEntry::from_result(&result, &dataset).unwrap();
+
+        // 哈希随结果结构变化，引擎版本随发布变化，取自结果本身
+        let integrity = result.integrity.as_ref().unwrap();
+        assert_eq!(
+            serde_json::to_value(&entry).unwrap(),
+            json!({
+                "format_version": 2,
+                "machine_fingerprint": "708e53644861bf4d",
+                "cpu_model": "Test CPU",
+                "gpu_model": null,
+                "category_scores": { "cpu": 617.85, "memory": 666.67, "storage": 300.0 },
+                "overall_score": 123.46,
+                "engine_version": integrity.engine_version,
+                "result_schema_version": RESULT_SCHEMA_VERSION,
+                "reference_dataset": "builtin",
+                "reference_dataset_version": 1,
+                "integrity_hash": integrity.hash,
+                "timestamp": "2024-06-01T09:00:00Z",
+            })
+        );
+        // 同一结果重复生成的条目相同
+        assert_eq!(LeaderboardEntry::from_result(&result, &dataset).unwrap(), entry);
+    }
+
+    #[test]
+    fn test_unverified_results_are_rejected() {
+        let dataset = ReferenceDataset::embedded().unwrap();
+        let mut result = sample_test_result("2024-06-01T09:00:00Z", 10.0);
+        // 没有校验信息
+        assert!(LeaderboardEntry::from_result(&result, &dataset).is_err());
+
+        // 保存后被修改过的结果
+        result.seal();
+        result.overall_score = 9999.0;
+        assert_eq!(result.verify_integrity(), Some(false));
+        assert!(LeaderboardEntry::from_result(&result, &dataset).is_err());
+    }
+
+    #[test]
+    fn test_import_entries_as_pseudo_results() {
+        let mut result = sample_test_result("2024-06-01T09:00:00Z", 50.0);
+        result.seal();
+        let entry = LeaderboardEntry::from_result(&result, &ReferenceDataset::embedded().unwrap()).unwrap();
+
+        let array = serde_json::to_string(&vec![entry.clone()]).unwrap();
+        let wrapped = json!({ "entries": [entry.clone()] }).to_string();
+        assert_eq!(parse_leaderboard_entries(&array).unwrap(), std::slice::from_ref(&entry));
+        assert_eq!(parse_leaderboard_entries(&wrapped).unwrap(), std::slice::from_ref(&entry));
+
+        let summary = entry.to_summary();
+        assert!(summary.id.starts_with(PSEUDO_RESULT_PREFIX));
+        assert_eq!(summary.overall_score, 50.0);
+        assert!(summary.imported);
+        assert_eq!(summary.verified, None);
+        assert!(summary.has_cpu_results);
+
+        let mut future = serde_json::to_value(&entry).unwrap();
+        future["format_version"] = json!(LEADERBOARD_FORMAT_VERSION + 1);
+        assert!(parse_leaderboard_entries(&json!([future]).to_string()).is_err());
+        assert!(parse_leaderboard_entries("{\"rows\": []}").is_err());
+    }
+}
@@ -1,9 +1,22 @@
+use crate::benchmark::calibration::Calibration;
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, TestOutcome};
+use crate::benchmark::cpu::floating_point_kernel;
 use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::estimate::{interference_test_seconds, memory_test_seconds};
+use crate::benchmark::metrics::{LiveSample, MetricsSink, RateMeter};
+use crate::benchmark::rng::Xoshiro256;
+use crate::i18n::Message;
+use crate::ipc::{TestResultPayload, TestType};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::time::Instant;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Barrier;
+use std::thread;
+use std::time::{Duration, Instant};
 use sysinfo::System;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MemoryTestResult {
     pub sequential_read_speed: f64, // MB/s
     pub sequential_write_speed: f64, // MB/s
@@ -12,23 +25,96 @@ pub struct MemoryTestResult {
     pub memory_usage_peak: u64, // MB
     pub error_rate: f64, // percentage
     pub test_duration: u64, // seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interference: Option<InterferenceResult>, // 干扰测试被关闭时为空
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 干扰测试的一个阶段：其他线程运行浮点负载时测得的顺序读取带宽
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct InterferenceMeasurement {
+    pub load_threads: usize, // 同时运行浮点负载的线程数
+    pub sequential_read_speed: f64, // MB/s
+    pub retention: f64, // percentage，相对无负载时的带宽
+}
+
+/// 共享末级缓存和内存控制器的争用程度因CPU而异，单独测带宽时看不出来
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct InterferenceResult {
+    pub logical_cores: usize,
+    pub measurements: Vec<InterferenceMeasurement>, // 依次为无负载、一半核心负载、其余核心全部负载
+}
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
 pub struct MemoryTestConfig {
+    #[serde(alias = "bufferSize")]
     pub buffer_size: usize, // MB
     pub iterations: usize,
+    #[serde(alias = "testDuration")]
     pub test_duration: u64, // seconds
+    #[serde(alias = "enableUsageMonitoring")]
     pub enable_usage_monitoring: bool,
+    #[serde(alias = "interferenceDuration")]
+    pub interference_duration: u64, // 干扰测试每个阶段的秒数，为0时跳过
+}
+
+impl Default for MemoryTestConfig {
+    fn default() -> Self {
+        Self {
+            buffer_size: 1024, // 1GB
+            iterations: 100,
+            test_duration: 30,
+            enable_usage_monitoring: true,
+            interference_duration: 2,
+        }
+    }
+}
+
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+const INTERFERENCE_CHUNK: usize = 1024 * 1024; // 干扰测试每读完这么多字节检查一次时间和取消
+
+/// 干扰测试三个阶段的负载线程数：无负载、一半核心、除读取线程外的全部核心
+pub fn interference_load_threads(logical_cores: usize) -> [usize; 3] {
+    let others = logical_cores.saturating_sub(1);
+    [0, (logical_cores / 2).min(others), others]
 }
 
 pub struct MemoryBenchmark {
     config: MemoryTestConfig,
+    metrics_sink: Option<MetricsSink>,
+    calibration: Calibration, // 只用于估计耗时
 }
 
 impl MemoryBenchmark {
     pub fn new(config: MemoryTestConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            metrics_sink: None,
+            calibration: Calibration::default_estimates(),
+        }
+    }
+
+    pub fn get_config(&self) -> &MemoryTestConfig {
+        &self.config
+    }
+
+    /// 测试过程中上报当前阶段的实时吞吐(MB/s)
+    pub fn with_metrics_sink(mut self, sink: MetricsSink) -> Self {
+        self.metrics_sink = Some(sink);
+        self
+    }
+
+    /// 用实测的校准结果估计耗时，默认使用内置估计
+    pub fn with_calibration(mut self, calibration: Calibration) -> Self {
+        self.calibration = calibration;
+        self
+    }
+
+    fn report_metrics(&self, phase: &'static str, phase_progress: f64, mb_per_sec: f64) {
+        if let Some(sink) = &self.metrics_sink {
+            sink(LiveSample::new(phase, phase_progress, &[("mb_per_sec", mb_per_sec)]));
+        }
     }
 
     pub fn run_benchmark(&self) -> Result<MemoryTestResult, BenchmarkError> {
@@ -37,37 +123,65 @@ impl MemoryBenchmark {
 
     pub fn run_benchmark_with_progress<F>(&self, progress_callback: F) -> Result<MemoryTestResult, BenchmarkError>
     where
-        F: Fn(f64, String) + Send + Sync + 'static,
+        F: Fn(f64, Message) + Send + Sync + 'static,
+    {
+        self.run_benchmark_cancellable(progress_callback, &CancellationToken::never())
+    }
+
+    /// 在各阶段之间和每轮遍历缓冲区前检查取消。每个阶段的缓冲区在阶段结束时释放，取消后不会继续占用内存
+    pub fn run_benchmark_cancellable<F>(
+        &self,
+        progress_callback: F,
+        cancellation: &CancellationToken,
+    ) -> Result<MemoryTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message) + Send + Sync + 'static,
     {
-        let start_time = Instant::now();
+        let clock = cancellation.clock();
         
         // 运行顺序读取测试
-        progress_callback(0.0, "开始内存顺序读取测试...".to_string());
-        let sequential_read_speed = self.test_sequential_read_with_progress(&progress_callback)?;
+        cancellation.check(TestType::Memory, 0.0)?;
+        progress_callback(0.0, Message::new("memory.seq_read.start"));
+        let sequential_read_speed =
+            tracing::info_span!("sequential_read").in_scope(|| self.test_sequential_read_with_progress(&progress_callback, cancellation))?;
         
         // 运行顺序写入测试
-        progress_callback(25.0, "开始内存顺序写入测试...".to_string());
-        let sequential_write_speed = self.test_sequential_write_with_progress(&progress_callback)?;
+        cancellation.check(TestType::Memory, 25.0)?;
+        progress_callback(25.0, Message::new("memory.seq_write.start"));
+        let sequential_write_speed =
+            tracing::info_span!("sequential_write").in_scope(|| self.test_sequential_write_with_progress(&progress_callback, cancellation))?;
         
         // 运行随机访问测试
-        progress_callback(50.0, "开始内存随机访问测试...".to_string());
-        let random_access_speed = self.test_random_access_with_progress(&progress_callback)?;
+        cancellation.check(TestType::Memory, 50.0)?;
+        progress_callback(50.0, Message::new("memory.random_access.start"));
+        let random_access_speed =
+            tracing::info_span!("random_access").in_scope(|| self.test_random_access_with_progress(&progress_callback, cancellation))?;
         
         // 运行内存延迟测试
-        progress_callback(75.0, "开始内存延迟测试...".to_string());
-        let latency = self.test_memory_latency_with_progress(&progress_callback)?;
+        cancellation.check(TestType::Memory, 75.0)?;
+        progress_callback(75.0, Message::new("memory.latency.start"));
+        let latency = tracing::info_span!("latency").in_scope(|| self.test_memory_latency_with_progress(&progress_callback, cancellation))?;
+
+        // 运行带宽干扰测试（如果启用）
+        let interference = if self.config.interference_duration > 0 {
+            cancellation.check(TestType::Memory, 85.0)?;
+            progress_callback(85.0, Message::new("memory.interference.start"));
+            Some(self.test_interference(&progress_callback, cancellation)?)
+        } else {
+            None
+        };
         
         // 监控内存使用量（如果启用）
-        progress_callback(90.0, "监控内存使用量...".to_string());
+        progress_callback(90.0, Message::new("memory.usage"));
         let memory_usage_peak = if self.config.enable_usage_monitoring {
             self.monitor_memory_usage()?
         } else {
             0
         };
 
-        let test_duration = std::cmp::max(start_time.elapsed().as_secs(), 1); // 至少1秒
+        let test_duration = std::cmp::max(clock.elapsed().as_secs(), 1); // 至少1秒
         
-        progress_callback(100.0, "内存测试完成".to_string());
+        progress_callback(100.0, Message::new("memory.complete"));
         
         Ok(MemoryTestResult {
             sequential_read_speed,
@@ -77,16 +191,47 @@ impl MemoryBenchmark {
             memory_usage_peak,
             error_rate: 0.0, // 暂时设为0，实际应用中可以检测内存错误
             test_duration,
+            interference,
         })
     }
 
+    /// 依次在无负载、一半核心和其余全部核心运行浮点负载时测量顺序读取带宽。
+    /// 负载线程和读取在栅栏处同时开始，读取结束后负载线程全部退出才进入下一阶段
+    fn test_interference<F>(&self, progress_callback: &F, cancellation: &CancellationToken) -> Result<InterferenceResult, BenchmarkError>
+    where
+        F: Fn(f64, Message),
+    {
+        let buffer_size_bytes = self.config.buffer_size * 1024 * 1024;
+        let buffer: Vec<u8> = (0..buffer_size_bytes).map(|i| (i % 256) as u8).collect();
+        let logical_cores = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        let phase_duration = Duration::from_secs(self.config.interference_duration);
+
+        let mut measurements: Vec<InterferenceMeasurement> = Vec::with_capacity(3);
+        for (index, load_threads) in interference_load_threads(logical_cores).into_iter().enumerate() {
+            let progress = index as f64 / 3.0 * 100.0;
+            progress_callback(
+                progress,
+                Message::progress("memory.interference.progress", progress).param("threads", load_threads),
+            );
+            let speed_mb_s = measure_read_under_load(&buffer, load_threads, phase_duration, cancellation)?;
+            self.report_metrics("interference", progress, speed_mb_s);
+            let alone = measurements.first().map_or(speed_mb_s, |first| first.sequential_read_speed);
+            measurements.push(InterferenceMeasurement {
+                load_threads,
+                sequential_read_speed: speed_mb_s,
+                retention: if alone > 0.0 { speed_mb_s / alone * 100.0 } else { 0.0 },
+            });
+        }
+        Ok(InterferenceResult { logical_cores, measurements })
+    }
+
     fn test_sequential_read(&self) -> Result<f64, BenchmarkError> {
-        self.test_sequential_read_with_progress(&|_progress, _message| {})
+        self.test_sequential_read_with_progress(&|_progress, _message| {}, &CancellationToken::never())
     }
 
-    fn test_sequential_read_with_progress<F>(&self, progress_callback: &F) -> Result<f64, BenchmarkError>
+    fn test_sequential_read_with_progress<F>(&self, progress_callback: &F, cancellation: &CancellationToken) -> Result<f64, BenchmarkError>
     where
-        F: Fn(f64, String),
+        F: Fn(f64, Message),
     {
         let buffer_size_bytes = self.config.buffer_size * 1024 * 1024; // Convert MB to bytes
         let mut buffer = vec![0u8; buffer_size_bytes];
@@ -96,11 +241,13 @@ impl MemoryBenchmark {
             buffer[i] = (i % 256) as u8;
         }
 
-        let start_time = Instant::now();
+        let clock = cancellation.clock();
         let mut total_bytes = 0u64;
         let mut checksum = 0u64;
+        let mut meter = RateMeter::new();
 
         for iteration in 0..self.config.iterations {
+            cancellation.check(TestType::Memory, 0.0)?;
             // 顺序读取整个缓冲区
             for chunk in buffer.chunks(4096) { // 4KB chunks
                 for &byte in chunk {
@@ -111,11 +258,16 @@ impl MemoryBenchmark {
             
             // 更新进度
             let progress = ((iteration + 1) as f64 / self.config.iterations as f64) * 100.0;
-            progress_callback(progress, format!("顺序读取测试进行中... ({:.1}%)", progress));
+            progress_callback(progress, Message::progress("memory.seq_read.progress", progress));
+            if progress < 100.0 {
+                self.report_metrics("seq_read", progress, meter.rate(total_bytes as f64) / BYTES_PER_MB);
+            }
         }
 
-        let elapsed = start_time.elapsed().as_secs_f64();
+        let elapsed = clock.elapsed().as_secs_f64();
         let speed_mb_s = (total_bytes as f64) / (1024.0 * 1024.0) / elapsed;
+        self.report_metrics("seq_read", 100.0, speed_mb_s);
+        tracing::debug!(bytes = total_bytes, elapsed_seconds = elapsed, speed_mb_s, "顺序读取完成");
         
         // 防止编译器优化掉计算
         if checksum == 0 {
@@ -126,20 +278,22 @@ impl MemoryBenchmark {
     }
 
     fn test_sequential_write(&self) -> Result<f64, BenchmarkError> {
-        self.test_sequential_write_with_progress(&|_progress, _message| {})
+        self.test_sequential_write_with_progress(&|_progress, _message| {}, &CancellationToken::never())
     }
 
-    fn test_sequential_write_with_progress<F>(&self, progress_callback: &F) -> Result<f64, BenchmarkError>
+    fn test_sequential_write_with_progress<F>(&self, progress_callback: &F, cancellation: &CancellationToken) -> Result<f64, BenchmarkError>
     where
-        F: Fn(f64, String),
+        F: Fn(f64, Message),
     {
         let buffer_size_bytes = self.config.buffer_size * 1024 * 1024;
         let mut buffer = vec![0u8; buffer_size_bytes];
 
-        let start_time = Instant::now();
+        let clock = cancellation.clock();
         let mut total_bytes = 0u64;
+        let mut meter = RateMeter::new();
 
         for iteration in 0..self.config.iterations {
+            cancellation.check(TestType::Memory, 25.0)?;
             let pattern = (iteration % 256) as u8;
             
             // 顺序写入整个缓冲区
@@ -153,22 +307,27 @@ impl MemoryBenchmark {
             
             // 更新进度
             let progress = ((iteration + 1) as f64 / self.config.iterations as f64) * 100.0;
-            progress_callback(progress, format!("顺序写入测试进行中... ({:.1}%)", progress));
+            progress_callback(progress, Message::progress("memory.seq_write.progress", progress));
+            if progress < 100.0 {
+                self.report_metrics("seq_write", progress, meter.rate(total_bytes as f64) / BYTES_PER_MB);
+            }
         }
 
-        let elapsed = start_time.elapsed().as_secs_f64();
+        let elapsed = clock.elapsed().as_secs_f64();
         let speed_mb_s = (total_bytes as f64) / (1024.0 * 1024.0) / elapsed;
+        self.report_metrics("seq_write", 100.0, speed_mb_s);
+        tracing::debug!(bytes = total_bytes, elapsed_seconds = elapsed, speed_mb_s, "顺序写入完成");
         
         Ok(speed_mb_s)
     }
 
     fn test_random_access(&self) -> Result<f64, BenchmarkError> {
-        self.test_random_access_with_progress(&|_progress, _message| {})
+        self.test_random_access_with_progress(&|_progress, _message| {}, &CancellationToken::never())
     }
 
-    fn test_random_access_with_progress<F>(&self, progress_callback: &F) -> Result<f64, BenchmarkError>
+    fn test_random_access_with_progress<F>(&self, progress_callback: &F, cancellation: &CancellationToken) -> Result<f64, BenchmarkError>
     where
-        F: Fn(f64, String),
+        F: Fn(f64, Message),
     {
         let buffer_size_bytes = self.config.buffer_size * 1024 * 1024;
         let mut buffer = vec![0u8; buffer_size_bytes];
@@ -178,34 +337,40 @@ impl MemoryBenchmark {
             buffer[i] = (i % 256) as u8;
         }
 
-        let start_time = Instant::now();
+        let clock = cancellation.clock();
         let mut total_accesses = 0u64;
         let mut checksum = 0u64;
         
-        // 使用简单的线性同余生成器生成随机索引
-        let mut rng_state = 12345u64;
+        let mut rng = Xoshiro256::new(12345);
+        let mut meter = RateMeter::new();
         
         for iteration in 0..self.config.iterations {
+            cancellation.check(TestType::Memory, 50.0)?;
             for _ in 0..10000 { // 每次迭代进行10000次随机访问
-                rng_state = rng_state.wrapping_mul(1103515245).wrapping_add(12345);
-                let index = (rng_state as usize) % buffer_size_bytes;
+                let value = rng.next_u64();
+                let index = rng.index(buffer_size_bytes);
                 
                 // 随机读取
                 checksum = checksum.wrapping_add(buffer[index] as u64);
                 
                 // 随机写入
-                buffer[index] = (rng_state % 256) as u8;
+                buffer[index] = value as u8;
                 
                 total_accesses += 2; // 一次读取 + 一次写入
             }
             
             // 更新进度
             let progress = ((iteration + 1) as f64 / self.config.iterations as f64) * 100.0;
-            progress_callback(progress, format!("随机访问测试进行中... ({:.1}%)", progress));
+            progress_callback(progress, Message::progress("memory.random_access.progress", progress));
+            if progress < 100.0 {
+                self.report_metrics("random_access", progress, meter.rate(total_accesses as f64) / BYTES_PER_MB);
+            }
         }
 
-        let elapsed = start_time.elapsed().as_secs_f64();
+        let elapsed = clock.elapsed().as_secs_f64();
         let speed_mb_s = (total_accesses as f64) / (1024.0 * 1024.0) / elapsed;
+        self.report_metrics("random_access", 100.0, speed_mb_s);
+        tracing::debug!(accesses = total_accesses, elapsed_seconds = elapsed, speed_mb_s, "随机访问完成");
         
         // 防止编译器优化
         if checksum == 0 {
@@ -216,26 +381,29 @@ impl MemoryBenchmark {
     }
 
     fn test_memory_latency(&self) -> Result<f64, BenchmarkError> {
-        self.test_memory_latency_with_progress(&|_progress, _message| {})
+        self.test_memory_latency_with_progress(&|_progress, _message| {}, &CancellationToken::never())
     }
 
-    fn test_memory_latency_with_progress<F>(&self, progress_callback: &F) -> Result<f64, BenchmarkError>
+    fn test_memory_latency_with_progress<F>(&self, progress_callback: &F, cancellation: &CancellationToken) -> Result<f64, BenchmarkError>
     where
-        F: Fn(f64, String),
+        F: Fn(f64, Message),
     {
         const LATENCY_TEST_SIZE: usize = 64 * 1024; // 64KB for cache testing
         let mut buffer = vec![0usize; LATENCY_TEST_SIZE / std::mem::size_of::<usize>()];
         
         // 创建随机访问模式
-        let mut rng_state = 54321u64;
-        for i in 0..buffer.len() {
-            rng_state = rng_state.wrapping_mul(1103515245).wrapping_add(12345);
-            buffer[i] = (rng_state as usize) % buffer.len();
+        let mut rng = Xoshiro256::new(54321);
+        let len = buffer.len();
+        for slot in buffer.iter_mut() {
+            *slot = rng.index(len);
         }
 
         let iterations = 1000000; // 100万次访问
-        let start_time = Instant::now();
+        let clock = cancellation.clock();
         let mut last_progress_update = Instant::now();
+        let mut meter = RateMeter::new();
+        // 每次访问读取一个usize
+        let access_bytes = std::mem::size_of::<usize>() as f64;
         
         let mut index = 0;
         for i in 0..iterations {
@@ -243,14 +411,18 @@ impl MemoryBenchmark {
             
             // 每10万次访问更新一次进度
             if i % 100000 == 0 && last_progress_update.elapsed().as_millis() >= 100 {
+                cancellation.check(TestType::Memory, 75.0)?;
                 let progress = (i as f64 / iterations as f64) * 100.0;
-                progress_callback(progress, format!("内存延迟测试进行中... ({:.1}%)", progress));
+                progress_callback(progress, Message::progress("memory.latency.progress", progress));
+                self.report_metrics("latency", progress, meter.rate(i as f64 * access_bytes) / BYTES_PER_MB);
                 last_progress_update = Instant::now();
             }
         }
 
-        let elapsed = start_time.elapsed();
+        let elapsed = clock.elapsed();
         let latency_ns = elapsed.as_nanos() as f64 / iterations as f64;
+        self.report_metrics("latency", 100.0, iterations as f64 * access_bytes / BYTES_PER_MB / elapsed.as_secs_f64());
+        tracing::debug!(iterations, elapsed_seconds = elapsed.as_secs_f64(), latency_ns, "延迟测试完成");
         
         // 防止编译器优化
         if index >= buffer.len() {
@@ -276,6 +448,69 @@ impl MemoryBenchmark {
         let peak_usage_mb = (peak_used - initial_used) / (1024 * 1024);
         Ok(peak_usage_mb)
     }
+}
+
+// 在load_threads个线程上持续运行浮点计算核心，同时在当前线程顺序读取缓冲区duration秒
+fn measure_read_under_load(
+    buffer: &[u8],
+    load_threads: usize,
+    duration: Duration,
+    cancellation: &CancellationToken,
+) -> Result<f64, BenchmarkError> {
+    let stop = AtomicBool::new(false);
+    let barrier = Barrier::new(load_threads + 1);
+    thread::scope(|scope| {
+        for worker in 0..load_threads {
+            let (stop, barrier) = (&stop, &barrier);
+            scope.spawn(move || {
+                barrier.wait();
+                while !stop.load(Ordering::Relaxed) {
+                    black_box(floating_point_kernel(black_box(worker as f64)));
+                }
+            });
+        }
+        // 所有负载线程开始运行后才开始计时
+        barrier.wait();
+        let result = read_for(buffer, duration, cancellation);
+        stop.store(true, Ordering::Relaxed);
+        result
+    })
+}
+
+// 循环顺序读取缓冲区直到经过duration，至少读取一块
+fn read_for(buffer: &[u8], duration: Duration, cancellation: &CancellationToken) -> Result<f64, BenchmarkError> {
+    let clock = cancellation.clock();
+    let mut total_bytes = 0u64;
+    let mut checksum = 0u64;
+    for chunk in buffer.chunks(INTERFERENCE_CHUNK).cycle() {
+        for &byte in chunk {
+            checksum = checksum.wrapping_add(byte as u64);
+        }
+        total_bytes += chunk.len() as u64;
+        cancellation.check(TestType::Memory, 85.0)?;
+        if clock.elapsed() >= duration {
+            break;
+        }
+    }
+    black_box(checksum);
+    Ok(total_bytes as f64 / BYTES_PER_MB / clock.elapsed().as_secs_f64())
+}
+
+impl BenchmarkRunner for MemoryBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Memory
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        (memory_test_seconds(self.config.buffer_size, self.config.iterations, &self.calibration)
+            + interference_test_seconds(self.config.interference_duration))
+        .ceil() as u64
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        self.run_benchmark_cancellable(ctx.progress_callback(), &ctx.cancellation)
+            .map(|result| TestOutcome::Completed(TestResultPayload::Memory(result)))
+    }
 }#
 [cfg(test)]
 mod tests {
@@ -288,6 +523,7 @@ mod tests {
             iterations: 5,
             test_duration: 10,
             enable_usage_monitoring: false,
+            interference_duration: 0,
         };
         
         let benchmark = MemoryBenchmark::new(config);
@@ -303,6 +539,7 @@ mod tests {
             iterations: 2,
             test_duration: 5,
             enable_usage_monitoring: false,
+            interference_duration: 0,
         };
         
         let benchmark = MemoryBenchmark::new(config);
@@ -320,6 +557,7 @@ mod tests {
             iterations: 2,
             test_duration: 5,
             enable_usage_monitoring: false,
+            interference_duration: 0,
         };
         
         let benchmark = MemoryBenchmark::new(config);
@@ -337,6 +575,7 @@ mod tests {
             iterations: 1,
             test_duration: 5,
             enable_usage_monitoring: false,
+            interference_duration: 0,
         };
         
         let benchmark = MemoryBenchmark::new(config);
@@ -354,6 +593,7 @@ mod tests {
             iterations: 1,
             test_duration: 5,
             enable_usage_monitoring: false,
+            interference_duration: 0,
         };
         
         let benchmark = MemoryBenchmark::new(config);
@@ -372,6 +612,7 @@ mod tests {
             iterations: 1,
             test_duration: 5,
             enable_usage_monitoring: true,
+            interference_duration: 0,
         };
         
         let benchmark = MemoryBenchmark::new(config);
@@ -390,6 +631,7 @@ mod tests {
             iterations: 2,
             test_duration: 5,
             enable_usage_monitoring: false,
+            interference_duration: 0,
         };
         
         let benchmark = MemoryBenchmark::new(config);
@@ -413,6 +655,7 @@ mod tests {
             iterations: 1,
             test_duration: 5,
             enable_usage_monitoring: true,
+            interference_duration: 0,
         };
         
         let benchmark = MemoryBenchmark::new(config);
@@ -435,6 +678,7 @@ mod tests {
             iterations: 2,
             test_duration: 5,
             enable_usage_monitoring: false,
+            interference_duration: 0,
         };
         
         let benchmark = MemoryBenchmark::new(config);
@@ -449,4 +693,44 @@ mod tests {
         assert!(write_speed > 0.0);
         assert!(random_speed > 0.0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_interference_phases() {
+        assert_eq!(interference_load_threads(1), [0, 0, 0]);
+        assert_eq!(interference_load_threads(2), [0, 1, 1]);
+        assert_eq!(interference_load_threads(8), [0, 4, 7]);
+
+        let config = MemoryTestConfig {
+            buffer_size: 64,
+            iterations: 1,
+            test_duration: 5,
+            enable_usage_monitoring: false,
+            interference_duration: 1,
+        };
+        let interference = MemoryBenchmark::new(config).run_benchmark().unwrap().interference.unwrap();
+        assert_eq!(interference.measurements.len(), 3);
+        assert_eq!(interference.measurements[0].retention, 100.0);
+        // 单核机器上没有负载线程，三个阶段测的是同一件事
+        for (measurement, load_threads) in interference.measurements.iter().zip(interference_load_threads(interference.logical_cores)) {
+            assert_eq!(measurement.load_threads, load_threads);
+            assert!(measurement.sequential_read_speed > 0.0);
+            if interference.logical_cores > 1 {
+                assert!(measurement.retention <= 110.0, "{:?}", measurement);
+            }
+        }
+    }
+
+    #[test]
+    fn test_interference_cancellation() {
+        let config = MemoryTestConfig {
+            buffer_size: 4,
+            interference_duration: 60,
+            ..MemoryTestConfig::default()
+        };
+        let started = Instant::now();
+        let token = CancellationToken::new(move || started.elapsed() > Duration::from_millis(200));
+        let error = MemoryBenchmark::new(config).test_interference(&|_, _| {}, &token).unwrap_err();
+        assert_eq!(error.code(), "CANCELLED");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,292 @@
+//! 测试运行中的实时指标：各测试在进度回调之外通过`MetricsSink`上报采样，
+//! 套件据此为三种测试发送统一的real-time-performance事件
+use crate::ipc::SystemMonitoringData;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use sysinfo::{Components, System, MINIMUM_CPU_UPDATE_INTERVAL};
+
+/// 每种测试的采样中一定包含的指标键
+pub const CPU_METRICS: &[&str] = &["ops_per_sec", "frequency_mhz"];
+pub const MEMORY_METRICS: &[&str] = &["mb_per_sec"];
+pub const STORAGE_METRICS: &[&str] = &["mb_per_sec", "iops", "p99_latency_ms"];
+
+/// 平台提供CPU温度传感器时，CPU采样附加该指标
+pub const CPU_TEMPERATURE: &str = "temperature_c";
+
+// 滚动p99统计最近的操作数
+const LATENCY_WINDOW: usize = 1000;
+
+// 频率和温度读取较慢，两次刷新之间至少间隔这么久
+const SENSOR_REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+pub fn required_metrics(test_type: &str) -> &'static [&'static str] {
+    match test_type {
+        "cpu" => CPU_METRICS,
+        "memory" => MEMORY_METRICS,
+        "storage" => STORAGE_METRICS,
+        _ => &[],
+    }
+}
+
+/// 一次实时采样
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveSample {
+    pub phase: &'static str,   // 如 "single_thread"、"seq_read"
+    pub phase_progress: f64,   // 当前阶段的进度，100表示阶段结束时的汇总采样
+    pub metrics: HashMap<String, f64>,
+}
+
+impl LiveSample {
+    pub fn new(phase: &'static str, phase_progress: f64, metrics: &[(&str, f64)]) -> Self {
+        Self {
+            phase,
+            phase_progress,
+            metrics: metrics.iter().map(|(key, value)| (key.to_string(), *value)).collect(),
+        }
+    }
+}
+
+pub type MetricsSink = Arc<dyn Fn(LiveSample) + Send + Sync>;
+
+/// 相邻两次采样之间的速率，反映当前而非累计的吞吐
+pub struct RateMeter {
+    last_at: Instant,
+    last_total: f64,
+}
+
+impl RateMeter {
+    pub fn new() -> Self {
+        Self {
+            last_at: Instant::now(),
+            last_total: 0.0,
+        }
+    }
+
+    /// `total`为到目前为止的累计量，返回自上次调用以来每秒的增量
+    pub fn rate(&mut self, total: f64) -> f64 {
+        let elapsed = self.last_at.elapsed().as_secs_f64();
+        let rate = if elapsed > 0.0 {
+            (total - self.last_total) / elapsed
+        } else {
+            0.0
+        };
+        self.last_at = Instant::now();
+        self.last_total = total;
+        rate
+    }
+}
+
+impl Default for RateMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 已排序样本的分位数（最近秩），样本不能为空
+pub fn percentile(sorted: &[f64], percent: f64) -> f64 {
+    let index = ((sorted.len() as f64 * percent / 100.0).ceil() as usize).clamp(1, sorted.len()) - 1;
+    sorted[index]
+}
+
+/// 最近若干次操作延迟的滚动p99
+pub struct RollingLatency {
+    window: VecDeque<f64>,
+}
+
+impl RollingLatency {
+    pub fn new() -> Self {
+        Self {
+            window: VecDeque::with_capacity(LATENCY_WINDOW),
+        }
+    }
+
+    pub fn push(&mut self, latency_ms: f64) {
+        if self.window.len() == LATENCY_WINDOW {
+            self.window.pop_front();
+        }
+        self.window.push_back(latency_ms);
+    }
+
+    pub fn p99(&self) -> f64 {
+        if self.window.is_empty() {
+            return 0.0;
+        }
+        let mut sorted: Vec<f64> = self.window.iter().copied().collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        percentile(&sorted, 99.0)
+    }
+}
+
+impl Default for RollingLatency {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// CPU频率和温度读数，刷新有最小间隔，两次刷新之间返回缓存值
+pub struct CpuSensors {
+    system: System,
+    components: Components,
+    refreshed_at: Option<Instant>,
+    frequency_mhz: f64,
+    temperature_c: Option<f64>,
+}
+
+impl CpuSensors {
+    pub fn new() -> Self {
+        Self {
+            system: System::new(),
+            components: Components::new_with_refreshed_list(),
+            refreshed_at: None,
+            frequency_mhz: 0.0,
+            temperature_c: None,
+        }
+    }
+
+    /// 返回平均频率(MHz)和CPU相关传感器中的最高温度
+    pub fn read(&mut self) -> (f64, Option<f64>) {
+        if self.refreshed_at.is_none_or(|at| at.elapsed() >= SENSOR_REFRESH_INTERVAL) {
+            self.system.refresh_cpu_frequency();
+            let cpus = self.system.cpus();
+            if !cpus.is_empty() {
+                self.frequency_mhz = cpus.iter().map(|cpu| cpu.frequency() as f64).sum::<f64>() / cpus.len() as f64;
+            }
+
+            self.components.refresh(false);
+            self.temperature_c = cpu_temperature(&self.components);
+            self.refreshed_at = Some(Instant::now());
+        }
+        (self.frequency_mhz, self.temperature_c)
+    }
+}
+
+impl Default for CpuSensors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// CPU相关传感器中的最高温度，平台不提供时为None
+fn cpu_temperature(components: &Components) -> Option<f64> {
+    components
+        .iter()
+        .filter(|component| {
+            let label = component.label().to_lowercase();
+            ["cpu", "core", "package", "tctl", "tdie"].iter().any(|name| label.contains(name))
+        })
+        .filter_map(|component| component.temperature())
+        .map(|temperature| temperature as f64)
+        .reduce(f64::max)
+}
+
+/// 整机的CPU占用、内存占用和CPU温度，用于system-monitoring事件。
+/// CPU占用是两次刷新之间的平均值，sysinfo要求两次刷新至少间隔MINIMUM_CPU_UPDATE_INTERVAL，
+/// 间隔内再次读取时沿用上一次的CPU占用和温度
+pub struct SystemMonitor {
+    system: System,
+    components: Components,
+    refreshed_at: Instant,
+    cpu_usage: Option<f64>,
+    temperature: Option<f64>,
+}
+
+impl SystemMonitor {
+    pub fn new() -> Self {
+        let mut system = System::new();
+        system.refresh_cpu_usage();
+        Self {
+            system,
+            components: Components::new_with_refreshed_list(),
+            refreshed_at: Instant::now(),
+            cpu_usage: None,
+            temperature: None,
+        }
+    }
+
+    pub fn sample(&mut self) -> SystemMonitoringData {
+        let since = self.refreshed_at.elapsed();
+        if self.cpu_usage.is_none() || since >= MINIMUM_CPU_UPDATE_INTERVAL {
+            // 第一次读取时等满最小间隔，否则CPU占用总是0
+            if since < MINIMUM_CPU_UPDATE_INTERVAL {
+                std::thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL - since);
+            }
+            self.system.refresh_cpu_usage();
+            self.components.refresh(false);
+            self.cpu_usage = Some(self.system.global_cpu_usage() as f64);
+            self.temperature = cpu_temperature(&self.components);
+            self.refreshed_at = Instant::now();
+        }
+        self.system.refresh_memory();
+        let memory_usage = if self.system.total_memory() > 0 {
+            self.system.used_memory() as f64 / self.system.total_memory() as f64 * 100.0
+        } else {
+            0.0
+        };
+        SystemMonitoringData {
+            cpu_usage: self.cpu_usage.unwrap_or_default().clamp(0.0, 100.0),
+            memory_usage: memory_usage.clamp(0.0, 100.0),
+            temperature: self.temperature,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            session_id: None,
+        }
+    }
+}
+
+impl Default for SystemMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_monitor_samples_real_usage() {
+        let created = Instant::now();
+        let mut monitor = SystemMonitor::new();
+        let first = monitor.sample();
+        // 第一次采样也在创建后等满最小间隔，不会返回两次刷新间隔过短的CPU占用
+        assert!(created.elapsed() >= MINIMUM_CPU_UPDATE_INTERVAL);
+        std::thread::sleep(MINIMUM_CPU_UPDATE_INTERVAL + Duration::from_millis(50));
+        let second = monitor.sample();
+
+        let parse = |timestamp: &str| chrono::DateTime::parse_from_rfc3339(timestamp).unwrap();
+        assert!(parse(&second.timestamp) > parse(&first.timestamp));
+        for data in [&first, &second] {
+            assert!((0.0..=100.0).contains(&data.cpu_usage), "{:?}", data);
+            assert!((0.0..=100.0).contains(&data.memory_usage), "{:?}", data);
+        }
+        // 能读到总内存的平台上，内存占用不会正好为0
+        assert!(second.memory_usage > 0.0);
+    }
+
+    #[test]
+    fn test_rolling_p99_uses_recent_window() {
+        let mut latency = RollingLatency::new();
+        assert_eq!(latency.p99(), 0.0);
+        for i in 1..=100 {
+            latency.push(i as f64);
+        }
+        assert_eq!(latency.p99(), 99.0);
+
+        // 旧的慢操作被挤出窗口后不再影响p99
+        for _ in 0..LATENCY_WINDOW {
+            latency.push(0.5);
+        }
+        assert_eq!(latency.p99(), 0.5);
+    }
+
+    #[test]
+    fn test_rate_meter_reports_increment_per_second() {
+        let mut meter = RateMeter::new();
+        std::thread::sleep(Duration::from_millis(50));
+        let rate = meter.rate(100.0);
+        assert!(rate > 0.0 && rate <= 100.0 / 0.05, "{}", rate);
+        // 累计量未变化时速率为0
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(meter.rate(100.0), 0.0);
+    }
+}
@@ -1,9 +1,45 @@
+pub mod aggregation;
+pub mod anonymous;
+pub mod archive;
+pub mod calibration;
+pub mod capability;
+pub mod ci_output;
+pub mod comparison;
+pub mod compression;
+pub mod copy;
+pub mod composite;
 pub mod core;
+pub mod database;
 pub mod cpu;
+pub mod crypto;
+pub mod estimate;
+pub mod gpu;
+pub mod hashing;
+pub mod image;
+pub mod integrity;
+pub mod ipc_overhead;
+pub mod leaderboard;
 pub mod memory;
+pub mod metrics;
+pub mod network;
+pub mod power;
+pub mod process;
+pub mod progress;
+pub mod ranking;
+pub mod report;
+pub mod results;
+pub mod rng;
+pub mod schema;
+pub mod scoring;
+pub mod serde_json_bench;
+pub mod stability;
 pub mod storage;
+pub mod sync;
+pub mod webview;
 pub mod system_info;
 pub mod error;
+#[cfg(test)]
+pub(crate) mod test_support;
 
 pub use core::BenchmarkCore;
 pub use error::BenchmarkError;
\ No newline at end of file
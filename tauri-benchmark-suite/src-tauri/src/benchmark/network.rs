@@ -0,0 +1,632 @@
+//! 网络测试：对HTTP目标测量下载和上传吞吐，并用重复的小请求测量延迟和抖动。
+//! 没有指定目标时在127.0.0.1上启动测试服务器（离线模式），测得的是本机网络栈的开销。
+//! 客户端和服务器只用标准库的TCP实现最小的HTTP/1.1，基准测试引擎不因此依赖tokio和TLS，
+//! 所以只支持http://目标，https://的测速地址需要换成明文HTTP的地址或自建的测速服务器
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, RunIssues, TestOutcome, MAX_NETWORK_CONNECTIONS};
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::estimate::network_test_seconds;
+use crate::i18n::Message;
+use crate::ipc::{TestResultPayload, TestType, WarningSeverity};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const IO_BUFFER_BYTES: usize = 64 * 1024;
+const DOWNLOAD_REQUEST_BYTES: u64 = 16 * 1024 * 1024; // 回环服务器每次下载请求返回的字节数
+const UPLOAD_REQUEST_BYTES: u64 = 4 * 1024 * 1024; // 每次上传请求的正文大小
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5); // 连接和单次读写的超时，延迟测试中超时的请求计为丢失
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+const LATENCY_PROGRESS: f64 = 10.0; // 延迟测试占的进度，下载和上传平分其余部分
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct NetworkTestConfig {
+    pub target: Option<String>, // http://主机[:端口]/路径（不支持https），GET用于下载、POST用于上传、HEAD用于延迟；为空时使用本机回环服务器
+    #[serde(alias = "testDuration")]
+    pub test_duration: u64, // 下载和上传各自运行的秒数
+    #[serde(alias = "parallelConnections")]
+    pub parallel_connections: usize,
+    #[serde(alias = "latencySamples")]
+    pub latency_samples: usize, // 测量延迟的请求次数
+}
+
+impl Default for NetworkTestConfig {
+    fn default() -> Self {
+        Self {
+            target: None,
+            test_duration: 10,
+            parallel_connections: 4,
+            latency_samples: 20,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct NetworkTestResult {
+    pub target: String, // 实际测试的地址，离线模式为回环服务器的地址
+    pub offline: bool,
+    pub download_mbps: f64, // 按10^6位计
+    pub upload_mbps: f64,
+    pub latency_avg_ms: f64,
+    pub latency_min_ms: f64,
+    pub latency_max_ms: f64,
+    pub jitter_ms: f64, // 相邻两次延迟之差的平均值
+    pub loss_percent: f64, // 延迟测试中失败或超时的请求比例，只是粗略的丢包估计
+    pub parallel_connections: usize,
+    pub test_duration: f64, // seconds
+}
+
+impl NetworkTestResult {
+    /// 下载和上传吞吐的平均值，单独作为总体评分的一部分
+    pub fn score(&self) -> f64 {
+        (self.download_mbps + self.upload_mbps) / 2.0
+    }
+}
+
+/// 检查目标地址的格式（不解析域名），供配置校验使用
+pub fn check_target(target: &str) -> Result<(), String> {
+    let url = url::Url::parse(target).map_err(|e| format!("无效的地址{}: {}", target, e))?;
+    if url.scheme() != "http" {
+        return Err(format!(
+            "网络测试只支持http://目标（不支持TLS），{}的协议为{}；请使用明文HTTP的测速地址，或留空使用本机回环服务器",
+            target,
+            url.scheme()
+        ));
+    }
+    if url.host_str().is_none_or(str::is_empty) {
+        return Err(format!("地址{}缺少主机名", target));
+    }
+    Ok(())
+}
+
+fn network_error(context: &str, error: impl std::fmt::Display) -> BenchmarkError {
+    BenchmarkError::NetworkTestError(format!("{}: {}", context, error))
+}
+
+/// 解析后的目标
+#[derive(Debug, Clone)]
+struct HttpTarget {
+    address: SocketAddr,
+    host: String, // Host请求头
+    path: String, // 包括查询参数
+}
+
+impl HttpTarget {
+    fn parse(target: &str) -> Result<Self, BenchmarkError> {
+        check_target(target).map_err(BenchmarkError::NetworkTestError)?;
+        let url = url::Url::parse(target).map_err(|e| network_error("无效的地址", e))?;
+        let host = url.host_str().unwrap_or_default();
+        let port = url.port_or_known_default().unwrap_or(80);
+        let address = (host, port)
+            .to_socket_addrs()
+            .map_err(|e| network_error(&format!("无法解析{}", host), e))?
+            .next()
+            .ok_or_else(|| BenchmarkError::NetworkTestError(format!("{}没有可用的地址", host)))?;
+        let path = match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        };
+        Ok(Self {
+            address,
+            host: url.port().map_or_else(|| host.to_string(), |port| format!("{}:{}", host, port)),
+            path,
+        })
+    }
+
+    fn loopback(address: SocketAddr) -> Self {
+        Self {
+            address,
+            host: address.to_string(),
+            path: "/".to_string(),
+        }
+    }
+
+    /// 建立连接并发送请求头，每个连接只发一个请求（Connection: close）
+    fn send_head(&self, method: &str, content_length: Option<u64>) -> io::Result<TcpStream> {
+        let mut stream = TcpStream::connect_timeout(&self.address, REQUEST_TIMEOUT)?;
+        stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+        stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+        stream.set_nodelay(true)?;
+        let mut head = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: tauri-benchmark-suite\r\nConnection: close\r\n",
+            method, self.path, self.host
+        );
+        if let Some(length) = content_length {
+            head.push_str(&format!("Content-Type: application/octet-stream\r\nContent-Length: {}\r\n", length));
+        }
+        head.push_str("\r\n");
+        stream.write_all(head.as_bytes())?;
+        Ok(stream)
+    }
+}
+
+/// 读取响应的状态行和头部，返回状态码
+fn read_status(reader: &mut impl BufRead) -> io::Result<u16> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let status = line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("无效的HTTP响应: {:?}", line.trim())))?;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            return Ok(status);
+        }
+    }
+}
+
+fn expect_success(status: u16) -> io::Result<()> {
+    if (200..300).contains(&status) {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("HTTP {}", status)))
+    }
+}
+
+/// 一个下载连接：反复GET目标并读取响应正文，直到期限或被停止
+fn download(target: &HttpTarget, deadline: Instant, stop: &AtomicBool, bytes: &AtomicU64) -> io::Result<()> {
+    let mut buffer = vec![0u8; IO_BUFFER_BYTES];
+    while Instant::now() < deadline && !stop.load(Ordering::Relaxed) {
+        let mut reader = BufReader::with_capacity(IO_BUFFER_BYTES, target.send_head("GET", None)?);
+        expect_success(read_status(&mut reader)?)?;
+        loop {
+            if Instant::now() >= deadline || stop.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            bytes.fetch_add(read as u64, Ordering::Relaxed);
+        }
+    }
+    Ok(())
+}
+
+/// 一个上传连接：反复POST固定大小的正文，到期时中断当前请求
+fn upload(target: &HttpTarget, deadline: Instant, stop: &AtomicBool, bytes: &AtomicU64) -> io::Result<()> {
+    let chunk = vec![0xA5u8; IO_BUFFER_BYTES];
+    while Instant::now() < deadline && !stop.load(Ordering::Relaxed) {
+        let mut stream = target.send_head("POST", Some(UPLOAD_REQUEST_BYTES))?;
+        let mut remaining = UPLOAD_REQUEST_BYTES;
+        while remaining > 0 {
+            if Instant::now() >= deadline || stop.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            let size = remaining.min(chunk.len() as u64) as usize;
+            stream.write_all(&chunk[..size])?;
+            bytes.fetch_add(size as u64, Ordering::Relaxed);
+            remaining -= size as u64;
+        }
+        expect_success(read_status(&mut BufReader::new(stream))?)?;
+    }
+    Ok(())
+}
+
+/// 离线模式的测试服务器：GET返回DOWNLOAD_REQUEST_BYTES字节，POST读取并丢弃正文，其它请求立即返回空响应。
+/// 固定MAX_NETWORK_CONNECTIONS个线程共用监听端口，各自依次接受连接并处理一个请求，
+/// 超出的连接在监听队列中等待，不会按连接数无限增加线程；drop时停止接受新连接
+struct LoopbackServer {
+    address: SocketAddr,
+    stop: Arc<AtomicBool>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl LoopbackServer {
+    fn start() -> Result<Self, BenchmarkError> {
+        let start_error = |e: io::Error| network_error("无法启动回环测试服务器", e);
+        let listener = TcpListener::bind(("127.0.0.1", 0)).map_err(start_error)?;
+        let address = listener.local_addr().map_err(start_error)?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let mut server = Self { address, stop, workers: Vec::with_capacity(MAX_NETWORK_CONNECTIONS) };
+        for _ in 0..MAX_NETWORK_CONNECTIONS {
+            // 启动到一半失败时，drop已启动的线程
+            let listener = listener.try_clone().map_err(start_error)?;
+            let stopped = server.stop.clone();
+            server.workers.push(thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stopped.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        let _ = serve(stream);
+                    }
+                }
+            }));
+        }
+        Ok(server)
+    }
+}
+
+impl Drop for LoopbackServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // 每个线程收到一个连接后退出，连接数与线程数相同
+        for _ in &self.workers {
+            let _ = TcpStream::connect_timeout(&self.address, REQUEST_TIMEOUT);
+        }
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn serve(stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let method = line.split_whitespace().next().unwrap_or_default().to_string();
+    let mut content_length = 0u64;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+
+    let mut stream = stream;
+    const EMPTY_RESPONSE: &[u8] = b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+    match method.as_str() {
+        "GET" => {
+            write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", DOWNLOAD_REQUEST_BYTES)?;
+            let chunk = [0x5Au8; IO_BUFFER_BYTES];
+            let mut remaining = DOWNLOAD_REQUEST_BYTES;
+            while remaining > 0 {
+                let size = remaining.min(chunk.len() as u64) as usize;
+                stream.write_all(&chunk[..size])?;
+                remaining -= size as u64;
+            }
+        }
+        "POST" => {
+            io::copy(&mut reader.take(content_length), &mut io::sink())?;
+            stream.write_all(EMPTY_RESPONSE)?;
+        }
+        _ => stream.write_all(EMPTY_RESPONSE)?,
+    }
+    Ok(())
+}
+
+/// 延迟测试的统计
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LatencyStats {
+    avg_ms: f64,
+    min_ms: f64,
+    max_ms: f64,
+    jitter_ms: f64,
+    loss_percent: f64,
+}
+
+impl LatencyStats {
+    /// samples为成功请求的延迟（按发送顺序），lost为失败或超时的请求数
+    fn from_samples(samples: &[f64], lost: usize) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let jitter_ms = if samples.len() > 1 {
+            samples.windows(2).map(|pair| (pair[1] - pair[0]).abs()).sum::<f64>() / (samples.len() - 1) as f64
+        } else {
+            0.0
+        };
+        Some(Self {
+            avg_ms: samples.iter().sum::<f64>() / samples.len() as f64,
+            min_ms: samples.iter().copied().fold(f64::INFINITY, f64::min),
+            max_ms: samples.iter().copied().fold(0.0, f64::max),
+            jitter_ms,
+            loss_percent: lost as f64 * 100.0 / (samples.len() + lost) as f64,
+        })
+    }
+}
+
+/// 一个传输方向：名称用于错误信息，worker返回前把传输的字节数累加到bytes
+struct Transfer {
+    name: &'static str,
+    progress: fn(f64) -> Message,
+    worker: fn(&HttpTarget, Instant, &AtomicBool, &AtomicU64) -> io::Result<()>,
+}
+
+const DOWNLOAD: Transfer = Transfer {
+    name: "下载",
+    progress: |at| Message::progress("network.download.progress", at),
+    worker: download,
+};
+
+const UPLOAD: Transfer = Transfer {
+    name: "上传",
+    progress: |at| Message::progress("network.upload.progress", at),
+    worker: upload,
+};
+
+pub struct NetworkBenchmark {
+    config: NetworkTestConfig,
+}
+
+impl NetworkBenchmark {
+    pub fn new(config: NetworkTestConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn get_config(&self) -> &NetworkTestConfig {
+        &self.config
+    }
+
+    /// 单独运行时使用，不检查取消，警告被忽略
+    pub fn run_benchmark_with_progress<F>(&self, progress_callback: F) -> Result<NetworkTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message) + Send + Sync + 'static,
+    {
+        self.run_benchmark_cancellable(progress_callback, &CancellationToken::never())
+    }
+
+    /// 单独运行时使用，取消后在下一次检查时停止并返回Cancelled，警告被忽略
+    pub fn run_benchmark_cancellable<F>(&self, progress_callback: F, cancellation: &CancellationToken) -> Result<NetworkTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message) + Send + Sync + 'static,
+    {
+        let ctx = RunContext {
+            progress: Arc::new(progress_callback),
+            cancellation: cancellation.clone(),
+            warnings: Arc::new(|_, _| {}),
+            issues: RunIssues::default(),
+        };
+        self.run_with_context(&ctx)
+    }
+
+    /// 依次测量延迟、下载和上传，进度按各阶段已用的时间计算
+    pub fn run_with_context(&self, ctx: &RunContext) -> Result<NetworkTestResult, BenchmarkError> {
+        let started = Instant::now();
+        let progress = ctx.progress_callback();
+        // 离线模式的服务器在测试结束时随server一起停止
+        let (target, server) = match &self.config.target {
+            Some(target) => (HttpTarget::parse(target)?, None),
+            None => {
+                let server = LoopbackServer::start()?;
+                (HttpTarget::loopback(server.address), Some(server))
+            }
+        };
+        let target_name = match &self.config.target {
+            Some(target) => target.clone(),
+            None => format!("http://{}/", target.host),
+        };
+
+        progress(0.0, Message::progress("network.latency.start", 0.0).param("target", &target_name));
+        let latency = self.measure_latency(ctx, &target, &progress)?;
+        let width = (100.0 - LATENCY_PROGRESS) / 2.0;
+        progress(LATENCY_PROGRESS, Message::progress("network.download.start", LATENCY_PROGRESS));
+        let download_mbps = self.measure_throughput(ctx, &target, (LATENCY_PROGRESS, width), &DOWNLOAD)?;
+        let upload_start = LATENCY_PROGRESS + width;
+        progress(upload_start, Message::progress("network.upload.start", upload_start));
+        let upload_mbps = self.measure_throughput(ctx, &target, (upload_start, width), &UPLOAD)?;
+        drop(server);
+        progress(100.0, Message::progress("network.complete", 100.0));
+
+        Ok(NetworkTestResult {
+            target: target_name,
+            offline: self.config.target.is_none(),
+            download_mbps,
+            upload_mbps,
+            latency_avg_ms: latency.avg_ms,
+            latency_min_ms: latency.min_ms,
+            latency_max_ms: latency.max_ms,
+            jitter_ms: latency.jitter_ms,
+            loss_percent: latency.loss_percent,
+            parallel_connections: self.config.parallel_connections,
+            test_duration: started.elapsed().as_secs_f64(),
+        })
+    }
+
+    /// 逐个发送HEAD请求，从建立连接到收到状态行的时间为一次延迟；任何状态码都算作收到响应
+    fn measure_latency(&self, ctx: &RunContext, target: &HttpTarget, progress: &impl Fn(f64, Message)) -> Result<LatencyStats, BenchmarkError> {
+        let count = self.config.latency_samples.max(1);
+        let mut samples = Vec::with_capacity(count);
+        let mut lost = 0;
+        let mut last_error = None;
+        for index in 0..count {
+            let at = LATENCY_PROGRESS * index as f64 / count as f64;
+            ctx.cancellation.check(TestType::Network, at)?;
+            let started = Instant::now();
+            let reply = target
+                .send_head("HEAD", None)
+                .and_then(|stream| read_status(&mut BufReader::new(stream)));
+            match reply {
+                Ok(_) => samples.push(started.elapsed().as_secs_f64() * 1000.0),
+                Err(error) => {
+                    lost += 1;
+                    last_error = Some(error);
+                }
+            }
+            progress(at, Message::progress("network.latency.progress", at));
+        }
+        LatencyStats::from_samples(&samples, lost).ok_or_else(|| {
+            let error = last_error.map(|error| error.to_string()).unwrap_or_default();
+            network_error(&format!("目标{}没有响应", target.host), error)
+        })
+    }
+
+    /// parallel_connections个连接同时传输test_duration秒，返回合计吞吐（Mbps）。
+    /// 工作线程到期后自行结束，调用线程按耗时报告进度并检查取消
+    fn measure_throughput(
+        &self,
+        ctx: &RunContext,
+        target: &HttpTarget,
+        (start, width): (f64, f64),
+        transfer: &Transfer,
+    ) -> Result<f64, BenchmarkError> {
+        let duration = Duration::from_secs(self.config.test_duration.max(1));
+        let bytes = AtomicU64::new(0);
+        let stop = AtomicBool::new(false);
+        let started = Instant::now();
+        let deadline = started + duration;
+        let progress = ctx.progress_callback();
+
+        let (cancelled, outcomes) = thread::scope(|scope| {
+            let workers: Vec<_> = (0..self.config.parallel_connections.max(1))
+                .map(|_| scope.spawn(|| (transfer.worker)(target, deadline, &stop, &bytes)))
+                .collect();
+            let mut cancelled = None;
+            while workers.iter().any(|worker| !worker.is_finished()) {
+                let at = start + width * (started.elapsed().as_secs_f64() / duration.as_secs_f64()).min(1.0);
+                if let Err(error) = ctx.cancellation.check(TestType::Network, at) {
+                    stop.store(true, Ordering::Relaxed);
+                    cancelled = Some(error);
+                    break;
+                }
+                progress(at, (transfer.progress)(at));
+                thread::sleep(PROGRESS_INTERVAL.min(deadline.saturating_duration_since(Instant::now())).max(Duration::from_millis(10)));
+            }
+            let outcomes: Vec<Result<io::Result<()>, BenchmarkError>> = workers
+                .into_iter()
+                .map(|worker| worker.join().map_err(|payload| BenchmarkError::from_panic("network", payload.as_ref())))
+                .collect();
+            (cancelled, outcomes)
+        });
+        if let Some(error) = cancelled {
+            return Err(error);
+        }
+        let elapsed = started.elapsed().min(duration).as_secs_f64();
+
+        let mut errors = Vec::new();
+        for outcome in outcomes {
+            if let Err(error) = outcome? {
+                errors.push(error);
+            }
+        }
+        let transferred = bytes.load(Ordering::Relaxed);
+        if transferred == 0 {
+            let error = errors.first().map(ToString::to_string).unwrap_or_else(|| "没有传输任何数据".to_string());
+            return Err(network_error(&format!("{}测试失败", transfer.name), error));
+        }
+        if !errors.is_empty() {
+            ctx.report(
+                Message::new("network.connection_errors")
+                    .param("count", errors.len())
+                    .param("error", &errors[0]),
+                WarningSeverity::Low,
+            );
+        }
+        Ok(transferred as f64 * 8.0 / elapsed / 1e6)
+    }
+}
+
+impl BenchmarkRunner for NetworkBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Network
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        network_test_seconds(self.config.test_duration, self.config.latency_samples).ceil() as u64
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        Ok(TestOutcome::Completed(TestResultPayload::Network(self.run_with_context(ctx)?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn quick_config() -> NetworkTestConfig {
+        NetworkTestConfig {
+            target: None,
+            test_duration: 1,
+            parallel_connections: 2,
+            latency_samples: 5,
+        }
+    }
+
+    #[test]
+    fn test_offline_mode_measures_loopback_server() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        let result = NetworkBenchmark::new(quick_config())
+            .run_benchmark_with_progress(move |progress, message| sink.lock().unwrap().push((progress, message.key)))
+            .unwrap();
+
+        assert!(result.offline);
+        assert!(result.target.starts_with("http://127.0.0.1:"), "{}", result.target);
+        assert!(result.download_mbps > 0.0 && result.upload_mbps > 0.0, "{:?}", result);
+        assert!(result.latency_min_ms <= result.latency_avg_ms && result.latency_avg_ms <= result.latency_max_ms);
+        assert_eq!(result.loss_percent, 0.0);
+        assert_eq!(result.parallel_connections, 2);
+        // 进度按阶段和耗时单调增加，最后为100
+        let reported = reported.lock().unwrap();
+        assert!(reported.windows(2).all(|pair| pair[0].0 <= pair[1].0), "{:?}", reported);
+        assert_eq!(reported.last().copied(), Some((100.0, "network.complete")));
+        assert!(reported.iter().any(|(_, key)| *key == "network.upload.progress"));
+    }
+
+    #[test]
+    fn test_target_validation_and_unreachable_target() {
+        assert!(check_target("http://speed.example.com:8080/file?size=10").is_ok());
+        let https = check_target("https://speed.example.com/").unwrap_err();
+        assert!(https.contains("http://") && https.contains("https"), "{}", https);
+        assert!(check_target("not a url").is_err());
+
+        let target = HttpTarget::parse("http://127.0.0.1:8080/file?size=10").unwrap();
+        assert_eq!((target.host.as_str(), target.path.as_str()), ("127.0.0.1:8080", "/file?size=10"));
+
+        // 绑定后立即释放的端口上没有服务，所有延迟请求都失败
+        let port = TcpListener::bind(("127.0.0.1", 0)).unwrap().local_addr().unwrap().port();
+        let config = NetworkTestConfig {
+            target: Some(format!("http://127.0.0.1:{}/", port)),
+            ..quick_config()
+        };
+        let error = NetworkBenchmark::new(config).run_benchmark_with_progress(|_, _| {}).unwrap_err();
+        assert!(matches!(error, BenchmarkError::NetworkTestError(_)), "{:?}", error);
+    }
+
+    #[test]
+    fn test_loopback_server_uses_fixed_worker_pool() {
+        let server = LoopbackServer::start().unwrap();
+        assert_eq!(server.workers.len(), MAX_NETWORK_CONNECTIONS);
+        // 同时到达的连接多于处理线程时，多出的连接排队等待而不是另开线程
+        let target = HttpTarget::loopback(server.address);
+        let clients: Vec<_> = (0..MAX_NETWORK_CONNECTIONS + 8)
+            .map(|_| {
+                let target = target.clone();
+                thread::spawn(move || read_status(&mut BufReader::new(target.send_head("HEAD", None)?)))
+            })
+            .collect();
+        for client in clients {
+            assert_eq!(client.join().unwrap().unwrap(), 200);
+        }
+        drop(server);
+    }
+
+    #[test]
+    fn test_latency_stats_and_cancellation() {
+        let stats = LatencyStats::from_samples(&[1.0, 3.0, 2.0], 1).unwrap();
+        assert_eq!(stats.avg_ms, 2.0);
+        assert_eq!((stats.min_ms, stats.max_ms), (1.0, 3.0));
+        assert_eq!(stats.jitter_ms, 1.5);
+        assert_eq!(stats.loss_percent, 25.0);
+        assert_eq!(LatencyStats::from_samples(&[], 3), None);
+
+        let ctx = RunContext {
+            progress: Arc::new(|_, _| {}),
+            cancellation: CancellationToken::new(|| true),
+            warnings: Arc::new(|_, _| {}),
+            issues: RunIssues::default(),
+        };
+        let error = NetworkBenchmark::new(quick_config()).run(&ctx).unwrap_err();
+        assert!(matches!(error, BenchmarkError::Cancelled { .. }), "{:?}", error);
+        let error = NetworkBenchmark::new(quick_config()).run_benchmark_cancellable(|_, _| {}, &CancellationToken::new(|| true)).unwrap_err();
+        assert!(matches!(error, BenchmarkError::Cancelled { .. }), "{:?}", error);
+    }
+}
@@ -0,0 +1,692 @@
+//! 电源计划：识别当前生效的计划，并对比当前计划与高性能计划下的CPU和内存成绩。
+//! Windows上在用户明确同意后通过powercfg自动切换，其他平台请前端引导用户手动切换并确认；
+//! 无论成功、失败还是取消，原计划都会恢复
+use crate::benchmark::core::CancellationToken;
+use crate::benchmark::cpu::{CpuBenchmark, CpuTestConfig};
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::memory::{MemoryBenchmark, MemoryTestConfig};
+use crate::ipc::TestType;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+// Windows内置的高性能和卓越性能计划，名称随系统语言变化，按GUID识别
+const HIGH_PERFORMANCE_GUID: &str = "8c5e7fda-e8bf-4a96-9a85-a6e23a8c635c";
+const ULTIMATE_PERFORMANCE_GUID: &str = "e9a42b02-d5df-448d-aa00-03f14749eb61";
+
+// Linux平台配置文件和CPU调速器中代表最高性能的取值
+const LINUX_PERFORMANCE: &str = "performance";
+
+// 等待用户手动切换时检查取消的间隔
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PowerPlan {
+    pub id: String, // Windows上为GUID，Linux上为"来源:取值"，macOS上为normal或low_power
+    pub name: String,
+    pub high_performance: bool,
+}
+
+/// 电源计划信息，无法识别时active_plan为空
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct PowerInfo {
+    pub active_plan: Option<PowerPlan>,
+    pub available_plans: Vec<PowerPlan>,
+}
+
+impl PowerInfo {
+    /// 可切换到的高性能计划，Windows上优先选择高性能而不是卓越性能计划（后者需要另行启用）
+    pub fn high_performance_plan(&self) -> Option<&PowerPlan> {
+        self.available_plans.iter().find(|plan| plan.high_performance)
+    }
+}
+
+fn is_guid(text: &str) -> bool {
+    text.len() == 36
+        && text.char_indices().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+/// 解析`powercfg /list`的输出，每行形如`Power Scheme GUID: <guid>  (Balanced) *`，星号标记当前计划。
+/// 只依赖GUID、括号和星号，不依赖随系统语言变化的文字
+pub fn parse_powercfg_list(output: &str) -> PowerInfo {
+    let mut info = PowerInfo::default();
+    for line in output.lines() {
+        let Some(guid) = line.split_whitespace().find(|word| is_guid(word)) else {
+            continue;
+        };
+        let guid = guid.to_ascii_lowercase();
+        let name = line
+            .split_once('(')
+            .and_then(|(_, rest)| rest.rsplit_once(')'))
+            .map(|(name, _)| name.trim().to_string())
+            .unwrap_or_else(|| guid.clone());
+        let plan = PowerPlan {
+            high_performance: guid == HIGH_PERFORMANCE_GUID || guid == ULTIMATE_PERFORMANCE_GUID,
+            id: guid,
+            name,
+        };
+        if line.trim_end().ends_with('*') {
+            info.active_plan = Some(plan.clone());
+        }
+        info.available_plans.push(plan);
+    }
+    // 高性能计划排在卓越性能之前
+    info.available_plans.sort_by_key(|plan| plan.id != HIGH_PERFORMANCE_GUID);
+    info
+}
+
+/// 由Linux的当前取值和可选取值（空格分隔）构造计划，source为platform_profile或scaling_governor
+pub fn parse_linux_plans(source: &str, active: &str, choices: &str) -> PowerInfo {
+    let plan = |value: &str| PowerPlan {
+        id: format!("{}:{}", source, value),
+        name: value.to_string(),
+        high_performance: value == LINUX_PERFORMANCE,
+    };
+    let active = active.trim();
+    PowerInfo {
+        active_plan: (!active.is_empty()).then(|| plan(active)),
+        available_plans: choices.split_whitespace().map(plan).collect(),
+    }
+}
+
+/// 解析`pmset -g`的输出，低电量模式开启时当前计划为low_power
+pub fn parse_pmset(output: &str) -> PowerInfo {
+    let low_power = output.lines().find_map(|line| {
+        let mut words = line.split_whitespace();
+        (words.next() == Some("lowpowermode")).then(|| words.next() == Some("1"))
+    });
+    let normal = PowerPlan {
+        id: "normal".to_string(),
+        name: "normal".to_string(),
+        high_performance: true,
+    };
+    let low = PowerPlan {
+        id: "low_power".to_string(),
+        name: "low_power".to_string(),
+        high_performance: false,
+    };
+    PowerInfo {
+        active_plan: low_power.map(|low_power| if low_power { low.clone() } else { normal.clone() }),
+        available_plans: vec![normal, low],
+    }
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program).args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// 读取当前平台的电源计划，无法识别时返回空信息
+pub fn detect_power_info() -> PowerInfo {
+    if cfg!(windows) {
+        command_output("powercfg", &["/list"]).map(|output| parse_powercfg_list(&output)).unwrap_or_default()
+    } else if cfg!(target_os = "macos") {
+        command_output("pmset", &["-g"]).map(|output| parse_pmset(&output)).unwrap_or_default()
+    } else {
+        // 优先使用ACPI平台配置文件，没有时退回CPU调速器
+        let read = |path: &str| std::fs::read_to_string(path).ok();
+        let sources = [
+            ("platform_profile", "/sys/firmware/acpi/platform_profile", "/sys/firmware/acpi/platform_profile_choices"),
+            (
+                "scaling_governor",
+                "/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor",
+                "/sys/devices/system/cpu/cpu0/cpufreq/scaling_available_governors",
+            ),
+        ];
+        sources
+            .iter()
+            .find_map(|(source, active, choices)| Some(parse_linux_plans(source, &read(active)?, &read(choices).unwrap_or_default())))
+            .unwrap_or_default()
+    }
+}
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct PowerComparisonConfig {
+    #[serde(alias = "cpuDuration")]
+    pub cpu_duration: u64, // seconds，CPU测试每个阶段的时长
+    #[serde(alias = "memoryBufferSize")]
+    pub memory_buffer_size: usize, // MB
+    #[serde(alias = "memoryIterations")]
+    pub memory_iterations: usize,
+    #[serde(alias = "allowSwitching")]
+    pub allow_switching: bool, // 用户明确同意后才在Windows上自动切换计划
+    #[serde(alias = "confirmationTimeoutSeconds")]
+    pub confirmation_timeout_seconds: u64, // 手动切换时等待用户确认的时长
+}
+
+impl Default for PowerComparisonConfig {
+    fn default() -> Self {
+        Self {
+            cpu_duration: 5,
+            memory_buffer_size: 256,
+            memory_iterations: 10,
+            allow_switching: false,
+            confirmation_timeout_seconds: 120,
+        }
+    }
+}
+
+/// 一个计划下的成绩，算法与总分中CPU和内存两项相同
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct PlanScores {
+    pub cpu_score: f64,
+    pub memory_score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PlanRun {
+    pub plan: Option<PowerPlan>, // 无法识别当前计划时为空
+    pub scores: PlanScores,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SwitchMethod {
+    Automatic, // 通过powercfg切换
+    Manual,    // 用户按提示切换并确认
+}
+
+/// 没有在高性能计划下重复测试的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ComparisonSkipped {
+    UnknownActivePlan,
+    AlreadyHighPerformance,
+    NoHighPerformancePlan,
+    NoConsent, // Windows上没有同意自动切换
+    Declined,  // 用户拒绝了手动切换
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PowerPlanComparison {
+    pub current: PlanRun,
+    pub high_performance: Option<PlanRun>,
+    pub switch_method: Option<SwitchMethod>,
+    pub skipped: Option<ComparisonSkipped>,
+    pub cpu_delta_percent: Option<f64>, // 高性能计划相对当前计划的变化
+    pub memory_delta_percent: Option<f64>,
+}
+
+/// 切换电源计划的方式，自动切换和引导手动切换各有一种实现，测试中可替换
+pub trait PlanSwitcher {
+    fn method(&self) -> SwitchMethod;
+
+    /// 切换到plan，返回false表示用户拒绝切换。restore为true时表示恢复原计划
+    fn activate(&self, plan: &PowerPlan, restore: bool) -> Result<bool, BenchmarkError>;
+}
+
+// 离开作用域时恢复原计划，出错和取消时同样生效
+struct RestoreGuard<'a> {
+    switcher: &'a dyn PlanSwitcher,
+    original: PowerPlan,
+    restored: bool,
+}
+
+impl RestoreGuard<'_> {
+    fn restore(mut self) -> Result<(), BenchmarkError> {
+        self.restored = true;
+        if self.switcher.activate(&self.original, true)? {
+            Ok(())
+        } else {
+            Err(BenchmarkError::PowerPlanError(format!("原电源计划{}没有恢复", self.original.name)))
+        }
+    }
+}
+
+impl Drop for RestoreGuard<'_> {
+    fn drop(&mut self) {
+        if !self.restored {
+            let _ = self.switcher.activate(&self.original, true);
+        }
+    }
+}
+
+fn delta_percent(current: f64, high: f64) -> Option<f64> {
+    (current > 0.0).then(|| (high - current) / current * 100.0)
+}
+
+/// 在当前计划下运行负载，条件允许时切换到高性能计划再运行一次并恢复原计划。
+/// switcher为空表示没有得到自动切换的同意
+pub fn compare_power_plans(
+    info: &PowerInfo,
+    switcher: Option<&dyn PlanSwitcher>,
+    mut run_workload: impl FnMut() -> Result<PlanScores, BenchmarkError>,
+    cancellation: &CancellationToken,
+) -> Result<PowerPlanComparison, BenchmarkError> {
+    cancellation.check(TestType::Cpu, 0.0)?;
+    let current = PlanRun {
+        plan: info.active_plan.clone(),
+        scores: run_workload()?,
+    };
+    let mut comparison = PowerPlanComparison {
+        current,
+        high_performance: None,
+        switch_method: None,
+        skipped: None,
+        cpu_delta_percent: None,
+        memory_delta_percent: None,
+    };
+
+    let target = match (&info.active_plan, info.high_performance_plan(), switcher) {
+        (None, _, _) => Err(ComparisonSkipped::UnknownActivePlan),
+        (Some(active), _, _) if active.high_performance => Err(ComparisonSkipped::AlreadyHighPerformance),
+        (_, None, _) => Err(ComparisonSkipped::NoHighPerformancePlan),
+        (_, _, None) => Err(ComparisonSkipped::NoConsent),
+        (Some(active), Some(target), Some(switcher)) => Ok((active, target, switcher)),
+    };
+    let (active, target, switcher) = match target {
+        Ok(target) => target,
+        Err(reason) => {
+            comparison.skipped = Some(reason);
+            return Ok(comparison);
+        }
+    };
+
+    cancellation.check(TestType::Cpu, 50.0)?;
+    let mut guard = RestoreGuard {
+        switcher,
+        original: active.clone(),
+        restored: false,
+    };
+    if !switcher.activate(target, false)? {
+        // 没有切换，也就不需要恢复
+        guard.restored = true;
+        comparison.skipped = Some(ComparisonSkipped::Declined);
+        return Ok(comparison);
+    }
+    cancellation.check(TestType::Cpu, 50.0)?;
+    let scores = run_workload()?;
+    guard.restore()?;
+
+    comparison.cpu_delta_percent = delta_percent(comparison.current.scores.cpu_score, scores.cpu_score);
+    comparison.memory_delta_percent = delta_percent(comparison.current.scores.memory_score, scores.memory_score);
+    comparison.switch_method = Some(switcher.method());
+    comparison.high_performance = Some(PlanRun {
+        plan: Some(target.clone()),
+        scores,
+    });
+    Ok(comparison)
+}
+
+/// 短时间的CPU和内存负载，关闭温度和占用监控以减少干扰
+pub fn measure_plan(config: &PowerComparisonConfig, cancellation: &CancellationToken) -> Result<PlanScores, BenchmarkError> {
+    let cpu = CpuBenchmark::new(CpuTestConfig {
+        thread_count: 0,
+        test_duration: config.cpu_duration,
+        enable_temperature_monitoring: false,
+    })
+    .run_benchmark()?;
+    let memory = MemoryBenchmark::new(MemoryTestConfig {
+        buffer_size: config.memory_buffer_size,
+        iterations: config.memory_iterations,
+        enable_usage_monitoring: false,
+        interference_duration: 0,
+        ..MemoryTestConfig::default()
+    })
+    .run_benchmark_cancellable(|_, _| {}, cancellation)?;
+    Ok(PlanScores {
+        cpu_score: (cpu.single_thread_score + cpu.multi_thread_score + cpu.floating_point_score) / 3.0,
+        memory_score: (memory.sequential_read_speed + memory.sequential_write_speed) / 2.0,
+    })
+}
+
+/// Windows上通过`powercfg /setactive`切换，切换后重新读取当前计划确认生效
+pub struct PowercfgSwitcher;
+
+impl PlanSwitcher for PowercfgSwitcher {
+    fn method(&self) -> SwitchMethod {
+        SwitchMethod::Automatic
+    }
+
+    fn activate(&self, plan: &PowerPlan, _restore: bool) -> Result<bool, BenchmarkError> {
+        let status = std::process::Command::new("powercfg")
+            .args(["/setactive", &plan.id])
+            .status()
+            .map_err(|e| BenchmarkError::PowerPlanError(format!("无法运行powercfg: {}", e)))?;
+        if !status.success() {
+            return Err(BenchmarkError::PowerPlanError(format!("powercfg无法切换到{}: {}", plan.name, status)));
+        }
+        verify_active(plan)?;
+        Ok(true)
+    }
+}
+
+// 能识别当前计划时确认切换已生效
+fn verify_active(plan: &PowerPlan) -> Result<(), BenchmarkError> {
+    match detect_power_info().active_plan {
+        Some(active) if active.id != plan.id => Err(BenchmarkError::PowerPlanError(format!(
+            "切换后当前计划仍是{}而不是{}",
+            active.name, plan.name
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// 请前端引导用户切换电源计划，用户切换后通过confirm_power_plan_switch确认
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerPlanSwitchRequest {
+    pub nonce: String,
+    pub target: PowerPlan,
+    pub restore: bool, // 为true时请用户切回原计划
+    pub timeout_ms: u64,
+}
+
+/// 在命令线程和前端确认之间传递手动切换的结果，同一时间只有一个请求
+#[derive(Default)]
+pub struct PowerSwitchCoordinator {
+    pending: Mutex<Option<(String, Option<bool>)>>,
+    answered: Condvar,
+}
+
+impl PowerSwitchCoordinator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 开始一次请求，上一次请求尚未结束时返回错误
+    pub fn begin(&self) -> Result<String, BenchmarkError> {
+        let mut pending = self.pending.lock().unwrap();
+        if pending.is_some() {
+            return Err(BenchmarkError::PowerPlanError("已有等待确认的电源计划切换".to_string()));
+        }
+        let nonce = Uuid::new_v4().to_string();
+        *pending = Some((nonce.clone(), None));
+        Ok(nonce)
+    }
+
+    /// 记录前端的确认，nonce与当前请求不符时拒绝
+    pub fn confirm(&self, nonce: &str, confirmed: bool) -> Result<(), BenchmarkError> {
+        let mut guard = self.pending.lock().unwrap();
+        match guard.as_mut() {
+            Some((pending, answer)) if pending == nonce && answer.is_none() => {
+                *answer = Some(confirmed);
+                self.answered.notify_all();
+                Ok(())
+            }
+            _ => Err(BenchmarkError::PowerPlanError("确认的nonce已过期或不属于当前请求".to_string())),
+        }
+    }
+
+    /// 等待nonce对应的确认，超时或取消时请求随即结束
+    pub fn wait(&self, nonce: &str, timeout: Duration, cancellation: &CancellationToken) -> Result<bool, BenchmarkError> {
+        let deadline = Instant::now() + timeout;
+        let mut guard = self.pending.lock().unwrap();
+        loop {
+            match guard.as_ref() {
+                Some((pending, answer)) if pending == nonce => {
+                    if let Some(confirmed) = *answer {
+                        *guard = None;
+                        return Ok(confirmed);
+                    }
+                }
+                _ => return Err(BenchmarkError::PowerPlanError("请求已结束".to_string())),
+            }
+            if cancellation.is_cancelled() {
+                *guard = None;
+                return Err(BenchmarkError::Cancelled {
+                    test: TestType::Cpu.as_str().to_string(),
+                    at_progress: 50.0,
+                });
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                *guard = None;
+                return Err(BenchmarkError::PowerPlanError(format!("用户在{}秒内没有确认切换", timeout.as_secs())));
+            }
+            guard = self.answered.wait_timeout(guard, (deadline - now).min(CONFIRMATION_POLL_INTERVAL)).unwrap().0;
+        }
+    }
+
+    /// 结束nonce对应的请求而不等待确认，用于请求没能发出时
+    pub fn abandon(&self, nonce: &str) {
+        let mut guard = self.pending.lock().unwrap();
+        if guard.as_ref().is_some_and(|(pending, _)| pending == nonce) {
+            *guard = None;
+        }
+    }
+}
+
+type EmitSwitchRequest = Box<dyn Fn(&PowerPlanSwitchRequest) -> Result<(), String> + Send + Sync>;
+
+/// 不能自动切换的平台上，通过事件请用户手动切换并等待确认
+pub struct ManualPlanSwitcher {
+    coordinator: std::sync::Arc<PowerSwitchCoordinator>,
+    emit: EmitSwitchRequest,
+    timeout: Duration,
+}
+
+impl ManualPlanSwitcher {
+    pub fn new(
+        coordinator: std::sync::Arc<PowerSwitchCoordinator>,
+        timeout: Duration,
+        emit: impl Fn(&PowerPlanSwitchRequest) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            coordinator,
+            emit: Box::new(emit),
+            timeout,
+        }
+    }
+}
+
+impl PlanSwitcher for ManualPlanSwitcher {
+    fn method(&self) -> SwitchMethod {
+        SwitchMethod::Manual
+    }
+
+    fn activate(&self, plan: &PowerPlan, restore: bool) -> Result<bool, BenchmarkError> {
+        let nonce = self.coordinator.begin()?;
+        let request = PowerPlanSwitchRequest {
+            nonce: nonce.clone(),
+            target: plan.clone(),
+            restore,
+            timeout_ms: self.timeout.as_millis() as u64,
+        };
+        if let Err(e) = (self.emit)(&request) {
+            self.coordinator.abandon(&nonce);
+            return Err(BenchmarkError::PowerPlanError(format!("无法发送切换请求: {}", e)));
+        }
+        let confirmed = self.coordinator.wait(&nonce, self.timeout, &CancellationToken::never())?;
+        if confirmed {
+            verify_active(plan)?;
+        }
+        Ok(confirmed)
+    }
+}
+
+/// 按平台和同意情况选择切换方式：Windows上同意后自动切换，其他平台引导手动切换
+pub fn platform_switcher(config: &PowerComparisonConfig, manual: ManualPlanSwitcher) -> Option<Box<dyn PlanSwitcher + Send>> {
+    if cfg!(windows) {
+        config.allow_switching.then(|| Box::new(PowercfgSwitcher) as Box<dyn PlanSwitcher + Send>)
+    } else {
+        Some(Box::new(manual))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    const POWERCFG_OUTPUT: &str = "
+Existing Power Schemes (* Active)
+-----------------------------------
+Power Scheme GUID: 381b4222-f694-41f0-9685-ff5bb260df2e  (Balanced) *
+Power Scheme GUID: 8C5E7FDA-E8BF-4A96-9A85-A6E23A8C635C  (High performance)
+Power Scheme GUID: a1841308-3541-4fab-bc81-f71556f20b4a  (Power saver)
+";
+
+    // 记录切换顺序，可设置拒绝切换或切换失败
+    struct FakeSwitcher {
+        calls: RefCell<Vec<(String, bool)>>,
+        decline: bool,
+        fail_restore: bool,
+    }
+
+    impl FakeSwitcher {
+        fn new() -> Self {
+            Self {
+                calls: RefCell::new(Vec::new()),
+                decline: false,
+                fail_restore: false,
+            }
+        }
+    }
+
+    impl PlanSwitcher for FakeSwitcher {
+        fn method(&self) -> SwitchMethod {
+            SwitchMethod::Automatic
+        }
+
+        fn activate(&self, plan: &PowerPlan, restore: bool) -> Result<bool, BenchmarkError> {
+            self.calls.borrow_mut().push((plan.name.clone(), restore));
+            if restore && self.fail_restore {
+                return Err(BenchmarkError::PowerPlanError("restore".to_string()));
+            }
+            Ok(restore || !self.decline)
+        }
+    }
+
+    fn scores(cpu_score: f64, memory_score: f64) -> PlanScores {
+        PlanScores { cpu_score, memory_score }
+    }
+
+    #[test]
+    fn test_parse_powercfg_list() {
+        let info = parse_powercfg_list(POWERCFG_OUTPUT);
+        let active = info.active_plan.as_ref().unwrap();
+        assert_eq!(active.name, "Balanced");
+        assert!(!active.high_performance);
+        assert_eq!(info.available_plans.len(), 3);
+        let high = info.high_performance_plan().unwrap();
+        assert_eq!(high.id, HIGH_PERFORMANCE_GUID);
+        assert_eq!(high.name, "High performance");
+
+        // 本地化的输出同样按GUID识别
+        let localized = parse_powercfg_list("电源方案 GUID: 8c5e7fda-e8bf-4a96-9a85-a6e23a8c635c  (高性能) *");
+        assert_eq!(localized.active_plan.unwrap().name, "高性能");
+        assert!(parse_powercfg_list("").active_plan.is_none());
+    }
+
+    #[test]
+    fn test_parse_linux_and_macos_plans() {
+        let info = parse_linux_plans("platform_profile", "balanced\n", "low-power balanced performance\n");
+        assert_eq!(info.active_plan.as_ref().unwrap().id, "platform_profile:balanced");
+        assert_eq!(info.high_performance_plan().unwrap().name, "performance");
+        assert!(parse_linux_plans("scaling_governor", "", "").active_plan.is_none());
+
+        let low = parse_pmset("System-wide power settings:\n lowpowermode         1\n sleep                1\n");
+        assert_eq!(low.active_plan.unwrap().id, "low_power");
+        assert_eq!(parse_pmset(" lowpowermode 0\n").active_plan.unwrap().id, "normal");
+        assert!(parse_pmset("sleep 1").active_plan.is_none());
+    }
+
+    #[test]
+    fn test_comparison_switches_and_restores() {
+        let info = parse_powercfg_list(POWERCFG_OUTPUT);
+        let switcher = FakeSwitcher::new();
+        let mut runs = vec![scores(150.0, 30.0), scores(100.0, 20.0)];
+        let comparison = compare_power_plans(&info, Some(&switcher), || Ok(runs.pop().unwrap()), &CancellationToken::never()).unwrap();
+
+        assert_eq!(
+            *switcher.calls.borrow(),
+            vec![("High performance".to_string(), false), ("Balanced".to_string(), true)]
+        );
+        assert_eq!(comparison.current.plan.unwrap().name, "Balanced");
+        assert_eq!(comparison.high_performance.unwrap().scores, scores(150.0, 30.0));
+        assert_eq!(comparison.switch_method, Some(SwitchMethod::Automatic));
+        assert!((comparison.cpu_delta_percent.unwrap() - 50.0).abs() < 1e-9);
+        assert!((comparison.memory_delta_percent.unwrap() - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_comparison_restores_on_error() {
+        let info = parse_powercfg_list(POWERCFG_OUTPUT);
+        let switcher = FakeSwitcher::new();
+        let mut runs = 0;
+        let error = compare_power_plans(
+            &info,
+            Some(&switcher),
+            || {
+                runs += 1;
+                if runs == 2 {
+                    Err(BenchmarkError::CpuTestError("失败".to_string()))
+                } else {
+                    Ok(scores(100.0, 20.0))
+                }
+            },
+            &CancellationToken::never(),
+        )
+        .unwrap_err();
+        assert_eq!(error.code(), "CPU_TEST_ERROR");
+        assert_eq!(switcher.calls.borrow().last().unwrap(), &("Balanced".to_string(), true));
+
+        // 恢复失败时报错，而不是报告成绩
+        let switcher = FakeSwitcher {
+            fail_restore: true,
+            ..FakeSwitcher::new()
+        };
+        let error = compare_power_plans(&info, Some(&switcher), || Ok(scores(1.0, 1.0)), &CancellationToken::never()).unwrap_err();
+        assert_eq!(error.code(), "POWER_PLAN_ERROR");
+        assert_eq!(switcher.calls.borrow().len(), 2);
+    }
+
+    #[test]
+    fn test_comparison_skip_reasons() {
+        let run = || Ok(scores(100.0, 20.0));
+        let never = CancellationToken::never();
+        let info = parse_powercfg_list(POWERCFG_OUTPUT);
+        let skipped = |info: &PowerInfo, switcher: Option<&dyn PlanSwitcher>| {
+            let comparison = compare_power_plans(info, switcher, run, &never).unwrap();
+            assert!(comparison.high_performance.is_none() && comparison.cpu_delta_percent.is_none());
+            comparison.skipped.unwrap()
+        };
+
+        let switcher = FakeSwitcher::new();
+        assert_eq!(skipped(&info, None), ComparisonSkipped::NoConsent);
+        assert_eq!(skipped(&PowerInfo::default(), Some(&switcher)), ComparisonSkipped::UnknownActivePlan);
+        let high = PowerInfo {
+            active_plan: info.high_performance_plan().cloned(),
+            ..info.clone()
+        };
+        assert_eq!(skipped(&high, Some(&switcher)), ComparisonSkipped::AlreadyHighPerformance);
+        let no_high = parse_linux_plans("scaling_governor", "powersave", "powersave");
+        assert_eq!(skipped(&no_high, Some(&switcher)), ComparisonSkipped::NoHighPerformancePlan);
+        assert!(switcher.calls.borrow().is_empty());
+
+        // 用户拒绝时没有切换，也不需要恢复
+        let declining = FakeSwitcher {
+            decline: true,
+            ..FakeSwitcher::new()
+        };
+        assert_eq!(skipped(&info, Some(&declining)), ComparisonSkipped::Declined);
+        assert_eq!(declining.calls.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_coordinator_confirmation() {
+        let coordinator = std::sync::Arc::new(PowerSwitchCoordinator::new());
+        let nonce = coordinator.begin().unwrap();
+        assert!(coordinator.begin().is_err());
+        assert!(coordinator.confirm("stale", true).is_err());
+
+        let confirming = coordinator.clone();
+        let confirm_nonce = nonce.clone();
+        let handle = std::thread::spawn(move || confirming.confirm(&confirm_nonce, true));
+        assert!(coordinator.wait(&nonce, Duration::from_secs(5), &CancellationToken::never()).unwrap());
+        handle.join().unwrap().unwrap();
+
+        // 超时后请求结束，迟到的确认被拒绝
+        let nonce = coordinator.begin().unwrap();
+        assert!(coordinator.wait(&nonce, Duration::from_millis(10), &CancellationToken::never()).is_err());
+        assert!(coordinator.confirm(&nonce, true).is_err());
+    }
+}
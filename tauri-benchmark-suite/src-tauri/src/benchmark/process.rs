@@ -0,0 +1,208 @@
+//! 进程启动速度：反复启动一个立即退出的辅助程序，测量从发起到退出的延迟和每秒可完成的启动次数，
+//! 代表构建系统、shell脚本等频繁创建短命进程的开发负载
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, TestOutcome};
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::metrics::percentile;
+use crate::i18n::Message;
+use crate::ipc::{ExtraTestResult, TestResultPayload, TestType};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::process::{Command, Stdio};
+use std::time::Instant;
+
+/// 在BenchmarkRegistry中的注册名，结果记录在extra_results下的同名键中
+pub const PROCESS_TEST_NAME: &str = "process";
+
+const CANCELLATION_CHECK_INTERVAL: usize = 10; // 每启动这么多次检查一次取消
+const ESTIMATED_SPAWN_SECONDS: f64 = 0.002; // 估计耗时用的单次启动时间，Windows上通常更慢
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ProcessTestConfig {
+    pub iterations: usize, // 计时的启动次数
+    #[serde(alias = "warmupIterations")]
+    pub warmup_iterations: usize, // 计时前先启动的次数，让辅助程序进入文件缓存
+    #[serde(alias = "measureWindowCreation")]
+    pub measure_window_creation: bool, // 额外测量本应用创建WebView窗口的耗时，只在通过run_process_benchmark命令运行时生效
+}
+
+impl Default for ProcessTestConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 200,
+            warmup_iterations: 5,
+            measure_window_creation: false,
+        }
+    }
+}
+
+/// 延迟单位为毫秒
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ProcessTestResult {
+    pub helper: String, // 辅助程序的命令行，如"true"或"cmd /C exit 0"
+    pub iterations: usize,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub mean_ms: f64,
+    pub spawns_per_second: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_creation_ms: Option<f64>, // 未请求或不在Tauri环境中运行时为空
+    pub test_duration: f64, // seconds
+}
+
+/// 立即以0退出的辅助程序
+fn helper_command() -> Command {
+    #[cfg(windows)]
+    {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "exit 0"]);
+        command
+    }
+    #[cfg(not(windows))]
+    {
+        Command::new("true")
+    }
+}
+
+fn describe(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|part| part.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 启动辅助程序并等待其退出，返回耗时（毫秒）
+fn spawn_once(command: &mut Command) -> Result<f64, BenchmarkError> {
+    let started = Instant::now();
+    let status = command.status().map_err(BenchmarkError::io("启动辅助进程失败"))?;
+    let elapsed = started.elapsed().as_secs_f64() * 1000.0;
+    if !status.success() {
+        return Err(BenchmarkError::ProcessTestError(format!("辅助进程{}异常退出: {}", describe(command), status)));
+    }
+    Ok(elapsed)
+}
+
+pub struct ProcessBenchmark {
+    config: ProcessTestConfig,
+}
+
+impl ProcessBenchmark {
+    pub fn new(config: ProcessTestConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn get_config(&self) -> &ProcessTestConfig {
+        &self.config
+    }
+
+    pub fn run_benchmark(&self) -> Result<ProcessTestResult, BenchmarkError> {
+        self.run_benchmark_cancellable(|_progress, _message| {}, &CancellationToken::never())
+    }
+
+    /// 窗口创建时间不在这里测量，由持有AppHandle的调用方填入结果
+    pub fn run_benchmark_cancellable<F>(&self, progress_callback: F, cancellation: &CancellationToken) -> Result<ProcessTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message),
+    {
+        let mut command = helper_command();
+        command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+        progress_callback(0.0, Message::new("process.start").param("helper", describe(&command)));
+
+        for _ in 0..self.config.warmup_iterations {
+            spawn_once(&mut command)?;
+        }
+
+        let started = Instant::now();
+        let iterations = self.config.iterations.max(1);
+        let progress_interval = (iterations / 10).max(1);
+        let mut latencies = Vec::with_capacity(iterations);
+        for i in 0..iterations {
+            let progress = i as f64 / iterations as f64 * 100.0;
+            if i % CANCELLATION_CHECK_INTERVAL == 0 {
+                cancellation.check(TestType::Extra, progress)?;
+            }
+            if i % progress_interval == 0 {
+                progress_callback(progress, Message::progress("process.progress", progress));
+            }
+            latencies.push(spawn_once(&mut command)?);
+        }
+        let test_duration = started.elapsed().as_secs_f64();
+
+        latencies.sort_by(|a, b| a.total_cmp(b));
+        progress_callback(100.0, Message::new("process.complete"));
+        Ok(ProcessTestResult {
+            helper: describe(&command),
+            iterations,
+            p50_ms: percentile(&latencies, 50.0),
+            p95_ms: percentile(&latencies, 95.0),
+            p99_ms: percentile(&latencies, 99.0),
+            mean_ms: latencies.iter().sum::<f64>() / iterations as f64,
+            spawns_per_second: iterations as f64 / test_duration,
+            window_creation_ms: None,
+            test_duration,
+        })
+    }
+}
+
+impl BenchmarkRunner for ProcessBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Extra
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        let spawns = (self.config.iterations + self.config.warmup_iterations) as f64;
+        (spawns * ESTIMATED_SPAWN_SECONDS).ceil().max(1.0) as u64
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        let result = self.run_benchmark_cancellable(ctx.progress_callback(), &ctx.cancellation)?;
+        Ok(TestOutcome::Completed(TestResultPayload::Extra(ExtraTestResult {
+            name: PROCESS_TEST_NAME.to_string(),
+            result: serde_json::to_value(&result)
+                .map_err(|e| BenchmarkError::DataSaveError(format!("测试结果序列化失败: {}", e)))?,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_spawn_latencies_are_positive_and_ordered() {
+        let config = ProcessTestConfig {
+            iterations: 20,
+            warmup_iterations: 1,
+            ..ProcessTestConfig::default()
+        };
+        let result = ProcessBenchmark::new(config).run_benchmark().unwrap();
+        assert_eq!(result.iterations, 20);
+        assert!(result.p50_ms > 0.0 && result.p50_ms <= result.p95_ms && result.p95_ms <= result.p99_ms, "{:?}", result);
+        // 启动一个立即退出的进程不会超过数秒，即使在负载很高的CI机器上
+        assert!(result.p99_ms < 5000.0, "{:?}", result);
+        assert!(result.spawns_per_second > 0.0);
+        assert!(result.window_creation_ms.is_none());
+        assert!(!result.helper.is_empty());
+    }
+
+    #[test]
+    fn test_cancellation_stops_spawning() {
+        let cancelled = Arc::new(AtomicBool::new(true));
+        let flag = cancelled.clone();
+        let token = CancellationToken::new(move || flag.load(Ordering::SeqCst));
+        let config = ProcessTestConfig {
+            iterations: 1000,
+            warmup_iterations: 0,
+            ..ProcessTestConfig::default()
+        };
+        let error = ProcessBenchmark::new(config)
+            .run_benchmark_cancellable(|_progress, _message| {}, &token)
+            .unwrap_err();
+        assert_eq!(error.code(), "CANCELLED");
+    }
+}
@@ -0,0 +1,200 @@
+//! 套件总体进度：按各测试的估计耗时加权，保证总体进度单调不减且不超过100，
+//! 并据此计算剩余时间和预计完成时间
+use crate::benchmark::estimate::TestEstimate;
+use chrono::{DateTime, Utc};
+use std::sync::Mutex;
+
+/// 某一时刻的总体进度
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SuiteProgress {
+    pub overall: f64,            // 0-100
+    pub eta_seconds: Option<u64>, // 剩余测试的估计耗时
+    pub estimated_completion: Option<DateTime<Utc>>, // 按后端时钟计算的预计完成时间
+}
+
+#[derive(Debug)]
+struct TestSlot {
+    test_type: String,
+    weight: f64,   // 估计耗时（秒）
+    progress: f64, // 0-100，只增不减
+    finished: bool,
+}
+
+#[derive(Debug)]
+struct TrackerState {
+    tests: Vec<TestSlot>,
+    reported: f64, // 已报告的最大总体进度
+}
+
+/// 各测试的进度回调和开始/结束点都通过它计算总体进度，
+/// 测试内部子阶段的进度回退、测试失败和跳过都不会让总体进度倒退
+#[derive(Debug)]
+pub struct SuiteProgressTracker {
+    state: Mutex<TrackerState>,
+}
+
+impl SuiteProgressTracker {
+    /// 按启用的测试及其估计耗时建立，估计过小的测试至少占1秒的权重
+    pub fn new(estimates: &[TestEstimate]) -> Self {
+        let tests = estimates
+            .iter()
+            .map(|estimate| TestSlot {
+                test_type: estimate.test_type.clone(),
+                weight: estimate.seconds.max(1.0),
+                progress: 0.0,
+                finished: false,
+            })
+            .collect();
+        Self {
+            state: Mutex::new(TrackerState { tests, reported: 0.0 }),
+        }
+    }
+
+    /// 测试开始时调用
+    pub fn start_test(&self, test_type: &str) -> SuiteProgress {
+        self.update(test_type, 0.0)
+    }
+
+    /// 测试自身的进度（0-100），子阶段之间的回退被忽略
+    pub fn update(&self, test_type: &str, test_progress: f64) -> SuiteProgress {
+        let mut state = self.state.lock().unwrap();
+        if let Some(slot) = state.tests.iter_mut().find(|slot| slot.test_type == test_type && !slot.finished) {
+            let progress = if test_progress.is_finite() { test_progress.clamp(0.0, 100.0) } else { 0.0 };
+            slot.progress = slot.progress.max(progress);
+        }
+        Self::snapshot(&mut state)
+    }
+
+    /// 测试结束时调用，成功或失败都计为该测试已完成。同一测试重复运行时依次对应各次的估计
+    pub fn finish_test(&self, test_type: &str) -> SuiteProgress {
+        let mut state = self.state.lock().unwrap();
+        if let Some(slot) = state.tests.iter_mut().find(|slot| slot.test_type == test_type && !slot.finished) {
+            slot.progress = 100.0;
+            slot.finished = true;
+        }
+        Self::snapshot(&mut state)
+    }
+
+    pub fn current(&self) -> SuiteProgress {
+        Self::snapshot(&mut self.state.lock().unwrap())
+    }
+
+    fn snapshot(state: &mut TrackerState) -> SuiteProgress {
+        let total: f64 = state.tests.iter().map(|slot| slot.weight).sum();
+        let done: f64 = state.tests.iter().map(|slot| slot.weight * slot.progress / 100.0).sum();
+        let all_finished = state.tests.iter().all(|slot| slot.finished);
+        // 浮点累加可能略小于100，全部完成时直接取100
+        let overall = if all_finished {
+            100.0
+        } else if total > 0.0 {
+            (done / total * 100.0).min(100.0)
+        } else {
+            0.0
+        };
+        state.reported = state.reported.max(overall);
+        let remaining = (total - done).max(0.0);
+        SuiteProgress {
+            overall: state.reported,
+            eta_seconds: Some(remaining.ceil() as u64),
+            estimated_completion: Some(Utc::now() + chrono::Duration::milliseconds((remaining * 1000.0) as i64)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn estimates(tests: &[(&str, f64)]) -> Vec<TestEstimate> {
+        tests
+            .iter()
+            .map(|(test_type, seconds)| TestEstimate {
+                test_type: test_type.to_string(),
+                seconds: *seconds,
+            })
+            .collect()
+    }
+
+    fn assert_monotonic(values: &[f64]) {
+        for pair in values.windows(2) {
+            assert!(pair[1] >= pair[0], "总体进度倒退: {:?}", values);
+        }
+        assert!(values.iter().all(|v| (0.0..=100.0).contains(v)), "超出范围: {:?}", values);
+    }
+
+    #[test]
+    fn test_successful_suite_is_monotonic_and_ends_at_100() {
+        let tracker = SuiteProgressTracker::new(&estimates(&[("cpu", 20.0), ("memory", 10.0), ("storage", 10.0)]));
+        let mut values = Vec::new();
+
+        values.push(tracker.start_test("cpu").overall);
+        // CPU的子阶段各自从0到100
+        for progress in [0.0, 50.0, 100.0, 0.0, 40.0, 100.0] {
+            values.push(tracker.update("cpu", progress).overall);
+        }
+        let after_cpu = tracker.finish_test("cpu");
+        values.push(after_cpu.overall);
+        assert_eq!(after_cpu.overall, 50.0);
+        assert_eq!(after_cpu.eta_seconds, Some(20));
+        let until_completion = after_cpu.estimated_completion.unwrap() - Utc::now();
+        assert!((19..=20).contains(&until_completion.num_seconds()), "{:?}", until_completion);
+
+        for test_type in ["memory", "storage"] {
+            values.push(tracker.start_test(test_type).overall);
+            for progress in [25.0, 150.0, 75.0, f64::NAN] {
+                values.push(tracker.update(test_type, progress).overall);
+            }
+            values.push(tracker.finish_test(test_type).overall);
+        }
+
+        assert_monotonic(&values);
+        assert_eq!(*values.last().unwrap(), 100.0);
+        assert_eq!(tracker.current().eta_seconds, Some(0));
+    }
+
+    #[test]
+    fn test_failed_test_does_not_move_progress_backwards() {
+        let tracker = SuiteProgressTracker::new(&estimates(&[("cpu", 10.0), ("memory", 10.0)]));
+        let mut values = vec![tracker.start_test("cpu").overall];
+        values.push(tracker.update("cpu", 60.0).overall);
+        // CPU测试中途失败，剩余部分计为完成，下一项测试从0开始也不会让总体进度回退
+        values.push(tracker.finish_test("cpu").overall);
+        values.push(tracker.start_test("memory").overall);
+        values.push(tracker.update("memory", 0.0).overall);
+        values.push(tracker.update("memory", 50.0).overall);
+
+        assert_monotonic(&values);
+        assert_eq!(values, [0.0, 30.0, 50.0, 50.0, 50.0, 75.0]);
+        // 已结束的测试不再接受进度
+        assert_eq!(tracker.update("cpu", 10.0).overall, 75.0);
+        assert_eq!(tracker.finish_test("memory").overall, 100.0);
+    }
+
+    #[test]
+    fn test_repeated_runs_advance_through_each_repetition() {
+        // 套件运行两次，同一测试的两次估计依次对应
+        let tracker = SuiteProgressTracker::new(&estimates(&[("cpu", 10.0), ("memory", 10.0), ("cpu", 10.0), ("memory", 10.0)]));
+        let mut values = Vec::new();
+        for _ in 0..2 {
+            for test_type in ["cpu", "memory"] {
+                values.push(tracker.start_test(test_type).overall);
+                values.push(tracker.update(test_type, 50.0).overall);
+                values.push(tracker.finish_test(test_type).overall);
+            }
+        }
+        assert_monotonic(&values);
+        assert_eq!(values, [0.0, 12.5, 25.0, 25.0, 37.5, 50.0, 50.0, 62.5, 75.0, 75.0, 87.5, 100.0]);
+    }
+
+    #[test]
+    fn test_uneven_weights_and_unknown_tests() {
+        // 估计为0的测试仍然占1秒的权重
+        let tracker = SuiteProgressTracker::new(&estimates(&[("cpu", 0.0), ("storage", 3.0)]));
+        assert_eq!(tracker.finish_test("cpu").overall, 25.0);
+        assert_eq!(tracker.update("gpu", 50.0).overall, 25.0);
+        assert_eq!(tracker.update("storage", 50.0).eta_seconds, Some(2));
+
+        let empty = SuiteProgressTracker::new(&[]);
+        assert_eq!(empty.current().overall, 100.0);
+    }
+}
@@ -0,0 +1,275 @@
+//! 与参考机器的对比：内置一组匿名的参考结果（老旧笔记本到工作站），
+//! 按总分和各项关键指标判断本机处于哪一档，并在相邻两档之间插值出大致的百分位
+use crate::benchmark::core::TestResult;
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::results::HeadlineMetrics;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// 当前支持的参考数据格式版本
+pub const REFERENCE_DATASET_VERSION: u32 = 1;
+
+// 随应用发布的参考数据
+const EMBEDDED_DATASET: &str = include_str!("../../assets/reference_results.json");
+
+/// 一台参考机器，`percentile`是它在整体机器中的大致百分位
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceTier {
+    pub id: String,
+    pub label: String, // 展示用的名称，如"2019 laptop"
+    pub percentile: f64,
+    pub metrics: BTreeMap<String, f64>, // 指标名见ranked_metric_names
+}
+
+/// 参考数据集，档位按百分位从低到高排列
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceDataset {
+    pub version: u32,
+    pub name: String,
+    pub tiers: Vec<ReferenceTier>,
+}
+
+/// 单项指标的排名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricRanking {
+    pub metric: String,
+    pub value: f64,
+    pub percentile: f64,              // 在相邻两档之间线性插值，保留一位小数
+    pub faster_than: Option<String>,  // 达到或超过的最高一档的名称
+    pub slower_than: Option<String>,  // 尚未达到的最低一档的名称
+}
+
+/// 结果相对参考数据集的排名，结果中缺少的指标不列出
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankingReport {
+    pub dataset: String,
+    pub dataset_version: u32,
+    pub verified: Option<bool>, // 结果的完整性校验状态，没有校验信息时为空
+    pub metrics: Vec<MetricRanking>,
+}
+
+impl RankingReport {
+    pub fn metric(&self, name: &str) -> Option<&MetricRanking> {
+        self.metrics.iter().find(|ranking| ranking.metric == name)
+    }
+}
+
+/// 参与排名的指标：总分和历史列表中的关键指标，数值都是越高越好
+pub fn ranked_metric_names() -> Vec<&'static str> {
+    std::iter::once("overall_score")
+        .chain(HeadlineMetrics::default().entries().iter().map(|(name, _)| *name))
+        .collect()
+}
+
+fn ranked_values(result: &TestResult) -> Vec<(&'static str, Option<f64>)> {
+    std::iter::once(("overall_score", Some(result.overall_score)))
+        .chain(HeadlineMetrics::from_result(result).entries())
+        .collect()
+}
+
+impl ReferenceDataset {
+    /// 随应用发布的参考数据，启动时加载并校验
+    pub fn embedded() -> Result<Self, BenchmarkError> {
+        Self::parse(EMBEDDED_DATASET)
+            .map_err(|e| BenchmarkError::DataLoadError(format!("内置参考数据 {}", e)))
+    }
+
+    /// 读取用户提供的参考数据（如实验室自己的基准机器）
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, BenchmarkError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| BenchmarkError::DataLoadError(format!("无法读取参考数据 {}: {}", path.display(), e)))?;
+        Self::parse(&contents)
+            .map_err(|e| BenchmarkError::DataLoadError(format!("参考数据 {} {}", path.display(), e)))
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let dataset: Self = serde_json::from_str(contents).map_err(|e| format!("解析失败: {}", e))?;
+        dataset.validate()?;
+        Ok(dataset)
+    }
+
+    /// 每一档都必须包含全部指标，且百分位和各项指标都随档位严格递增，插值才有意义
+    fn validate(&self) -> Result<(), String> {
+        if self.version != REFERENCE_DATASET_VERSION {
+            return Err(format!("版本 {} 不受支持，当前支持版本 {}", self.version, REFERENCE_DATASET_VERSION));
+        }
+        if self.tiers.len() < 2 {
+            return Err("至少需要两档参考机器".to_string());
+        }
+        let names = ranked_metric_names();
+        for tier in &self.tiers {
+            if !(tier.percentile > 0.0 && tier.percentile < 100.0) {
+                return Err(format!("{} 的百分位必须在0到100之间，当前为 {}", tier.id, tier.percentile));
+            }
+            if let Some(unknown) = tier.metrics.keys().find(|metric| !names.contains(&metric.as_str())) {
+                return Err(format!("{} 包含未知的指标 {}，可用的指标: {}", tier.id, unknown, names.join(", ")));
+            }
+            for name in &names {
+                match tier.metrics.get(*name) {
+                    Some(value) if value.is_finite() && *value > 0.0 => {}
+                    Some(value) => return Err(format!("{} 的指标 {} 必须是正数，当前为 {}", tier.id, name, value)),
+                    None => return Err(format!("{} 缺少指标 {}", tier.id, name)),
+                }
+            }
+        }
+        for pair in self.tiers.windows(2) {
+            let (lower, upper) = (&pair[0], &pair[1]);
+            if lower.id == upper.id {
+                return Err(format!("重复的档位 {}", upper.id));
+            }
+            if upper.percentile <= lower.percentile {
+                return Err(format!("档位须按百分位从低到高排列: {} 不高于 {}", upper.id, lower.id));
+            }
+            if let Some(name) = names.iter().find(|name| upper.metrics[**name] <= lower.metrics[**name]) {
+                return Err(format!("指标 {} 须随档位递增: {} 不高于 {}", name, upper.id, lower.id));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn rank(&self, result: &TestResult) -> RankingReport {
+        let metrics = ranked_values(result)
+            .into_iter()
+            .filter_map(|(metric, value)| value.map(|value| self.rank_metric(metric, value)))
+            .collect();
+        RankingReport {
+            dataset: self.name.clone(),
+            dataset_version: self.version,
+            verified: result.integrity_verified(),
+            metrics,
+        }
+    }
+
+    fn rank_metric(&self, metric: &str, value: f64) -> MetricRanking {
+        // 校验保证每一档都有该指标且随档位递增
+        let points: Vec<(f64, f64, &str)> = self
+            .tiers
+            .iter()
+            .map(|tier| (tier.metrics[metric], tier.percentile, tier.label.as_str()))
+            .collect();
+        let (lowest, highest) = (points[0], points[points.len() - 1]);
+
+        let percentile = if value <= lowest.0 {
+            // 低于最低一档时按比例趋向0
+            lowest.1 * (value / lowest.0).max(0.0)
+        } else if value >= highest.0 {
+            // 高于最高一档时按比例趋向100，达到最高一档的两倍时为100
+            (highest.1 + (100.0 - highest.1) * (value - highest.0) / highest.0).min(100.0)
+        } else {
+            let upper = points.iter().position(|point| point.0 > value).unwrap_or(points.len() - 1);
+            let (low, high) = (points[upper - 1], points[upper]);
+            low.1 + (value - low.0) / (high.0 - low.0) * (high.1 - low.1)
+        };
+
+        MetricRanking {
+            metric: metric.to_string(),
+            value,
+            percentile: (percentile * 10.0).round() / 10.0,
+            faster_than: points.iter().rev().find(|point| value >= point.0).map(|point| point.2.to_string()),
+            slower_than: points.iter().find(|point| value < point.0).map(|point| point.2.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::test_support::sample_test_result;
+
+    fn ranking(report: &RankingReport, metric: &str) -> (f64, Option<String>, Option<String>) {
+        let ranking = report.metric(metric).unwrap();
+        (ranking.percentile, ranking.faster_than.clone(), ranking.slower_than.clone())
+    }
+
+    fn labels(faster: Option<&str>, slower: Option<&str>) -> (Option<String>, Option<String>) {
+        (faster.map(str::to_string), slower.map(str::to_string))
+    }
+
+    #[test]
+    fn test_result_between_two_tiers() {
+        let dataset = ReferenceDataset::embedded().unwrap();
+        // 合成结果的各项指标都落在2015和2019笔记本之间
+        let result = sample_test_result("2024-01-01T00:00:00Z", 500.0);
+        let report = dataset.rank(&result);
+
+        assert_eq!(report.dataset, "builtin");
+        assert_eq!(report.dataset_version, REFERENCE_DATASET_VERSION);
+        assert_eq!(
+            report.metrics.iter().map(|r| r.metric.as_str()).collect::<Vec<_>>(),
+            ranked_metric_names()
+        );
+        let between = labels(Some("2015 laptop"), Some("2019 laptop"));
+        let expected = [
+            ("overall_score", 19.0),
+            ("cpu_single_thread", 34.0),
+            ("cpu_multi_thread", 17.3),
+            ("memory_read", 23.3),
+            ("memory_write", 22.9),
+            ("storage_read", 16.4),
+            ("storage_write", 17.8),
+        ];
+        for (metric, percentile) in expected {
+            let (actual, faster, slower) = ranking(&report, metric);
+            assert_eq!(actual, percentile, "{}", metric);
+            assert_eq!((faster, slower), between, "{}", metric);
+        }
+    }
+
+    #[test]
+    fn test_result_at_and_beyond_the_ends() {
+        let dataset = ReferenceDataset::embedded().unwrap();
+        let mut result = sample_test_result("2024-01-01T00:00:00Z", 2734.5);
+        result.storage_results = None;
+        let cpu = result.cpu_results.as_mut().unwrap();
+        cpu.single_thread_score = 170.0;
+        cpu.multi_thread_score = 3600.0;
+        let memory = result.memory_results.as_mut().unwrap();
+        memory.sequential_read_speed = 300.0;
+        memory.sequential_write_speed = 2200.0;
+        let report = dataset.rank(&result);
+
+        // 缺少的存储指标不参与排名
+        assert!(report.metric("storage_read").is_none());
+        assert_eq!(report.metrics.len(), 5);
+        // 总分和单线程分数都在台式机和工作站正中间
+        let between = labels(Some("2022 desktop"), Some("2023 workstation"));
+        for metric in ["overall_score", "cpu_single_thread"] {
+            let (percentile, faster, slower) = ranking(&report, metric);
+            assert_eq!((percentile, faster, slower), (85.0, between.0.clone(), between.1.clone()), "{}", metric);
+        }
+        // 高于最高一档
+        assert_eq!(ranking(&report, "cpu_multi_thread"), (97.5, Some("2023 workstation".to_string()), None));
+        // 低于最低一档
+        assert_eq!(ranking(&report, "memory_read"), (5.0, None, Some("2015 laptop".to_string())));
+        // 恰好等于某一档时取该档的百分位
+        assert_eq!(ranking(&report, "memory_write"), (75.0, Some("2022 desktop".to_string()), Some("2023 workstation".to_string())));
+    }
+
+    #[test]
+    fn test_user_dataset_is_validated() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lab.json");
+        let mut document: serde_json::Value = serde_json::from_str(EMBEDDED_DATASET).unwrap();
+        document["name"] = serde_json::json!("lab");
+        std::fs::write(&path, document.to_string()).unwrap();
+        assert_eq!(ReferenceDataset::from_file(&path).unwrap().name, "lab");
+
+        let invalid = |edit: fn(&mut serde_json::Value)| {
+            let mut document = document.clone();
+            edit(&mut document);
+            ReferenceDataset::parse(&document.to_string()).unwrap_err()
+        };
+        assert!(invalid(|d| d["version"] = serde_json::json!(2)).contains("版本"));
+        assert!(invalid(|d| d["tiers"][1]["metrics"]["cpu_single_thread"] = serde_json::json!(50.0)).contains("cpu_single_thread"));
+        assert!(invalid(|d| d["tiers"][2]["percentile"] = serde_json::json!(30.0)).contains("百分位"));
+        assert!(invalid(|d| d["tiers"][0]["metrics"]["gpu_score"] = serde_json::json!(1.0)).contains("gpu_score"));
+        assert!(invalid(|d| {
+            d["tiers"][0]["metrics"].as_object_mut().unwrap().remove("storage_write");
+        })
+        .contains("缺少指标 storage_write"));
+        assert!(invalid(|d| d["tiers"].as_array_mut().unwrap().truncate(1)).contains("两档"));
+        assert!(ReferenceDataset::from_file(dir.path().join("missing.json")).is_err());
+    }
+}
@@ -0,0 +1,125 @@
+//! 单个结果的HTML报告：一个不依赖外部资源的文件（样式内联），便于直接发给别人查看。
+//! 各测试的得分表按composite导出的映射表生成，没有结果的测试不出现在报告中
+use crate::benchmark::ci_output::escape_xml;
+use crate::benchmark::composite::CompositeExport;
+use crate::benchmark::core::TestResult;
+use crate::format::{format_number, NumberLocale};
+use std::fmt::Write as _;
+
+const STYLE: &str = "\
+body{font-family:-apple-system,'Segoe UI',Roboto,'Helvetica Neue',Arial,sans-serif;margin:0;padding:32px;background:#f5f6f8;color:#1f2328}
+main{max-width:960px;margin:0 auto}
+h1{margin:0 0 4px;font-size:24px}
+h2{margin:32px 0 8px;font-size:18px}
+.meta{color:#656d76;font-size:13px}
+.overall{margin:24px 0;padding:24px;border-radius:8px;background:#1f6feb;color:#fff;text-align:center}
+.overall .score{font-size:56px;font-weight:700;line-height:1.1}
+.overall .label{font-size:14px;opacity:.85}
+table{width:100%;border-collapse:collapse;background:#fff;border-radius:8px;overflow:hidden}
+th,td{padding:8px 12px;border-bottom:1px solid #e5e7eb;text-align:left;font-size:14px}
+th{background:#f0f2f5;font-weight:600}
+td.number{text-align:right;font-variant-numeric:tabular-nums}
+.warning{color:#9a6700}";
+
+/// 渲染完整的HTML文档
+pub fn render_html(result_id: &str, result: &TestResult) -> String {
+    let number = |value: f64| format_number(value, 2, NumberLocale::EnUs);
+    // 没有基准值的负载不显示得分
+    let score = |value: Option<u32>| value.map(|value| value.to_string()).unwrap_or_else(|| "-".to_string());
+    let mut html = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(html, "<title>Benchmark Report {}</title>", escape_xml(result_id));
+    let _ = writeln!(html, "<style>\n{}\n</style>\n</head>\n<body>\n<main>", STYLE);
+    let _ = writeln!(html, "<h1>Benchmark Report</h1>");
+    let _ = writeln!(
+        html,
+        "<div class=\"meta\">Result {} · {}</div>",
+        escape_xml(result_id),
+        escape_xml(&result.timestamp)
+    );
+    if result.partial {
+        let _ = writeln!(html, "<p class=\"warning\">This run was cancelled; only completed tests are included.</p>");
+    }
+    let _ = writeln!(
+        html,
+        "<section class=\"overall\"><div class=\"score\">{}</div><div class=\"label\">Overall Score</div></section>",
+        number(result.overall_score)
+    );
+
+    let system = &result.system_info;
+    let mut system_rows = vec![
+        ("Operating System", system.os.clone()),
+        ("CPU", system.cpu.name.clone()),
+        ("Cores / Threads", format!("{} / {}", system.cpu.cores, system.cpu.threads)),
+        ("Architecture", system.cpu.architecture.clone()),
+        ("Memory", format!("{} GB", system.memory.total)),
+    ];
+    system_rows.extend(
+        system
+            .storage
+            .iter()
+            .map(|disk| ("Storage", format!("{} ({} GB, {})", disk.name, disk.capacity, disk.file_system))),
+    );
+    if let Some(app) = &result.app_info {
+        system_rows.push(("App Version", app.app_version.clone()));
+    }
+    let _ = writeln!(html, "<h2>System Information</h2>\n<table>");
+    for (name, value) in system_rows {
+        let _ = writeln!(html, "<tr><th>{}</th><td>{}</td></tr>", name, escape_xml(&value));
+    }
+    html.push_str("</table>\n");
+
+    for section in CompositeExport::from_result(result_id, result).sections {
+        let _ = writeln!(html, "<h2>{}</h2>", escape_xml(&section.name));
+        html.push_str("<table>\n<tr><th>Metric</th><th>Value</th><th>Unit</th><th>Score</th></tr>\n");
+        for workload in &section.workloads {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"number\">{}</td><td>{}</td><td class=\"number\">{}</td></tr>",
+                escape_xml(&workload.name),
+                number(workload.value),
+                escape_xml(&workload.unit),
+                score(workload.score)
+            );
+        }
+        if let Some(section_score) = section.score {
+            let _ = writeln!(html, "<tr><th colspan=\"3\">Section Score</th><td class=\"number\">{}</td></tr>", section_score);
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("</main>\n</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::test_support::sample_test_result;
+
+    #[test]
+    fn test_report_contains_key_values_and_skips_missing_sections() {
+        let mut result = sample_test_result("2024-06-01T09:00:00Z", 1234.5);
+        result.system_info.cpu.name = "Test <CPU> & Co".to_string();
+        result.storage_results = None;
+        let html = render_html("r1", &result);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<div class=\"score\">1,234.50</div>"), "{}", html);
+        assert!(html.contains("<td>Test &lt;CPU&gt; &amp; Co</td>"));
+        assert!(html.contains("<td>4 / 8</td>"));
+        assert!(html.contains("<td>16 GB</td>"));
+        assert!(html.contains("<h2>CPU Single-Core</h2>"));
+        assert!(html.contains("<tr><td>Integer</td><td class=\"number\">100.00</td><td>points</td><td class=\"number\">"));
+        assert!(html.contains("<tr><td>Latency</td><td class=\"number\">100.00</td><td>ns</td><td class=\"number\">1000</td></tr>"));
+        // 没有运行的测试不出现，也不以0显示
+        assert!(!html.contains("<h2>Storage</h2>"));
+        assert!(!html.contains("<h2>GPU</h2>"));
+        // 不引用任何外部资源
+        assert!(!html.contains("<link") && !html.contains("<script") && !html.contains("src="));
+
+        result.storage_results = sample_test_result("2024-06-01T09:00:00Z", 0.0).storage_results;
+        let html = render_html("r1", &result);
+        assert!(html.contains("<h2>Storage</h2>"));
+        assert!(html.contains("<tr><td>Random Read IOPS</td><td class=\"number\">12,000.00</td><td>IOPS</td><td class=\"number\">"));
+    }
+}
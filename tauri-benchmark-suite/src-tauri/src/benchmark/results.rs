@@ -0,0 +1,1580 @@
+use crate::benchmark::comparison::{ComparisonReport, RegressionThresholds};
+use crate::benchmark::core::TestResult;
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::schema::{upgrade_result_document, RESULT_SCHEMA_VERSION};
+use crate::upload::UploadStatus;
+use chrono::{DateTime, FixedOffset};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const INDEX_FILE: &str = "index.json";
+const DELETE_JOURNAL_FILE: &str = "delete-journal.json";
+const ARTIFACTS_DIR: &str = "artifacts";
+// 新结果以zstd压缩保存，旧版本写入的未压缩文件仍可读取
+const COMPRESSED_EXTENSION: &str = "json.zst";
+const LEGACY_EXTENSION: &str = "json";
+// 无法解压或解析的压缩文件改名隔离，不再参与加载
+const QUARANTINE_SUFFIX: &str = "corrupt";
+// 同一台机器、相同配置、时间相差不超过该秒数的结果视为近似重复
+const NEAR_DUPLICATE_WINDOW_SECS: i64 = 120;
+
+/// 历史列表中展示的关键指标
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct HeadlineMetrics {
+    pub cpu_single_thread: Option<f64>,
+    pub cpu_multi_thread: Option<f64>,
+    pub memory_read: Option<f64>,      // MB/s
+    pub memory_write: Option<f64>,     // MB/s
+    pub storage_read: Option<f64>,     // MB/s
+    pub storage_write: Option<f64>,    // MB/s
+}
+
+impl HeadlineMetrics {
+    pub fn from_result(result: &TestResult) -> Self {
+        Self {
+            cpu_single_thread: result.cpu_results.as_ref().map(|r| r.single_thread_score),
+            cpu_multi_thread: result.cpu_results.as_ref().map(|r| r.multi_thread_score),
+            memory_read: result.memory_results.as_ref().map(|r| r.sequential_read_speed),
+            memory_write: result.memory_results.as_ref().map(|r| r.sequential_write_speed),
+            storage_read: result.storage_results.as_ref().map(|r| r.sequential_read.throughput),
+            storage_write: result.storage_results.as_ref().map(|r| r.sequential_write.throughput),
+        }
+    }
+
+    /// 以(指标名, 数值)列出所有关键指标，顺序固定
+    pub fn entries(&self) -> [(&'static str, Option<f64>); 6] {
+        [
+            ("cpu_single_thread", self.cpu_single_thread),
+            ("cpu_multi_thread", self.cpu_multi_thread),
+            ("memory_read", self.memory_read),
+            ("memory_write", self.memory_write),
+            ("storage_read", self.storage_read),
+            ("storage_write", self.storage_write),
+        ]
+    }
+}
+
+/// 两个历史结果的对比，指标和判定见`TestResult::compare`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ResultComparison {
+    pub baseline_id: String,
+    pub candidate_id: String,
+    pub baseline_verified: Option<bool>, // 完整性校验状态，没有校验信息时为空
+    pub candidate_verified: Option<bool>,
+    #[serde(flatten)]
+    pub report: ComparisonReport,
+}
+
+impl ResultComparison {
+    pub fn between(
+        baseline_id: &str,
+        baseline: &TestResult,
+        candidate_id: &str,
+        candidate: &TestResult,
+        thresholds: &RegressionThresholds,
+    ) -> Self {
+        Self {
+            baseline_id: baseline_id.to_string(),
+            candidate_id: candidate_id.to_string(),
+            baseline_verified: baseline.integrity_verified(),
+            candidate_verified: candidate.integrity_verified(),
+            report: candidate.compare(baseline, thresholds),
+        }
+    }
+}
+
+/// 结果索引条目，列出历史时无需读取完整结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResultSummary {
+    pub id: String,
+    pub timestamp: String,
+    pub overall_score: f64,
+    pub headline: HeadlineMetrics,
+    pub hardware_fingerprint: String,
+    pub has_cpu_results: bool,
+    pub has_memory_results: bool,
+    pub has_storage_results: bool,
+    #[serde(default)]
+    pub artifacts: Vec<PathBuf>, // 与该结果关联的导出文件（如HTML报告缓存）
+    #[serde(default)]
+    pub upload: Option<UploadStatus>,
+    #[serde(default)]
+    pub imported: bool,
+    #[serde(default)]
+    pub content_hash: Option<String>, // 导入文件内容的SHA-256，用于去重
+    #[serde(default)]
+    pub run_id: Option<String>, // 结果的运行标识，旧结果为空
+    #[serde(default)]
+    pub session_id: Option<String>, // 产生该结果的会话，重复运行的各次原始结果与汇总结果相同
+    #[serde(default)]
+    pub verified: Option<bool>, // 完整性校验状态，没有校验信息时为空
+    #[serde(default)]
+    pub config_hash: Option<String>, // 实际生效配置的SHA-256，旧结果为空
+    #[serde(default)]
+    pub duplicate_of: Option<String>, // 被标记为重复时指向同组中保留的最早结果
+    #[serde(default)]
+    pub schema_version: u32, // 生成摘要时结果的结构版本，没有该字段的旧索引为0，打开时低于当前版本的摘要重新生成
+}
+
+impl ResultSummary {
+    pub fn from_result(id: &str, result: &TestResult) -> Self {
+        Self {
+            id: id.to_string(),
+            timestamp: result.timestamp.clone(),
+            overall_score: result.overall_score,
+            headline: HeadlineMetrics::from_result(result),
+            hardware_fingerprint: result.system_info.hardware_fingerprint(),
+            has_cpu_results: result.cpu_results.is_some(),
+            has_memory_results: result.memory_results.is_some(),
+            has_storage_results: result.storage_results.is_some(),
+            artifacts: Vec::new(),
+            upload: None,
+            imported: result.imported,
+            content_hash: None,
+            run_id: result.identity.as_ref().map(|identity| identity.run_id.clone()),
+            session_id: result.session_id.clone(),
+            verified: result.integrity_verified(),
+            config_hash: config_hash(result),
+            duplicate_of: None,
+            schema_version: result.schema_version,
+        }
+    }
+
+    /// 重新生成摘要时保留只记录在索引中的状态（关联文件、上传状态、导入哈希和重复标记）
+    fn with_index_state(mut self, existing: &ResultSummary) -> Self {
+        self.artifacts = existing.artifacts.clone();
+        self.upload = existing.upload.clone();
+        self.content_hash = existing.content_hash.clone();
+        self.duplicate_of = existing.duplicate_of.clone();
+        self
+    }
+
+    /// 两个结果是否重复：run_id相同，或者同一台机器上配置相同、时间相差不超过两分钟。
+    /// 同一会话重复运行的各次结果不算重复
+    fn duplicates(&self, other: &ResultSummary) -> bool {
+        if self.run_id.is_some() && self.run_id == other.run_id {
+            return true;
+        }
+        if self.session_id.is_some() && self.session_id == other.session_id {
+            return false;
+        }
+        let (Ok(time), Ok(other_time)) = (
+            DateTime::parse_from_rfc3339(&self.timestamp),
+            DateTime::parse_from_rfc3339(&other.timestamp),
+        ) else {
+            return false;
+        };
+        self.hardware_fingerprint == other.hardware_fingerprint
+            && self.config_hash.is_some()
+            && self.config_hash == other.config_hash
+            && (time - other_time).num_seconds().abs() <= NEAR_DUPLICATE_WINDOW_SECS
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HistorySortField {
+    #[default]
+    Date,
+    Score,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// 历史查询条件，所有字段均可省略
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryQuery {
+    pub offset: usize,
+    pub limit: Option<usize>,
+    pub from: Option<String>, // RFC3339，包含
+    pub to: Option<String>,   // RFC3339，包含
+    pub has_storage_results: Option<bool>,
+    pub min_overall_score: Option<f64>,
+    pub sort_by: HistorySortField,
+    pub order: SortOrder,
+    pub include_duplicates: bool, // 默认不列出被标记为重复的结果
+}
+
+/// 套件历史中的一次运行，即以会话ID保存的最终结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub session_id: String,
+    pub timestamp: String,
+    pub overall_score: f64,
+}
+
+/// 删除操作的结果报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeletionReport {
+    pub removed: usize,
+    pub bytes_reclaimed: u64,
+    pub result_ids: Vec<String>,
+    pub dry_run: bool,
+}
+
+/// 压缩旧结果文件的报告
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompactionReport {
+    pub compacted: usize,   // 改写为压缩格式的结果数
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub bytes_saved: u64,
+}
+
+/// 删除日志：先记录待删除的文件再修改索引，崩溃后启动时重放
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DeleteJournal {
+    result_ids: Vec<String>,
+    files: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryPage {
+    pub total: usize, // 过滤后的总条数
+    pub offset: usize,
+    pub entries: Vec<ResultSummary>,
+}
+
+/// 基于文件的结果存储：每个结果一个zstd压缩的JSON文件，外加一个摘要索引。
+/// 列出历史只读索引，不需要解压完整结果
+pub struct ResultStore {
+    dir: PathBuf,
+    index: Vec<ResultSummary>,
+}
+
+impl ResultStore {
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, BenchmarkError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)
+            .map_err(|e| BenchmarkError::DataSaveError(format!("无法创建结果目录: {}", e)))?;
+
+        let mut store = Self { dir, index: Vec::new() };
+        let index_path = store.dir.join(INDEX_FILE);
+        match fs::read_to_string(&index_path) {
+            Ok(content) => {
+                store.index = serde_json::from_str(&content)
+                    .map_err(|e| BenchmarkError::DataLoadError(format!("结果索引损坏: {}", e)))?;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                // 索引缺失时从结果文件重建
+                store.rebuild_index()?;
+            }
+            Err(e) => {
+                return Err(BenchmarkError::DataLoadError(format!("无法读取结果索引: {}", e)));
+            }
+        }
+        store.replay_delete_journal()?;
+        // 结果文件被隔离或在外部删除后，索引中不再列出
+        let listed = store.index.len();
+        let dir = store.dir.clone();
+        store.index.retain(|s| result_files(&dir, &s.id).iter().any(|path| path.exists()));
+        if store.index.len() != listed {
+            store.write_index()?;
+        }
+        store.refresh_outdated_summaries()?;
+        Ok(store)
+    }
+
+    pub fn summaries(&self) -> &[ResultSummary] {
+        &self.index
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.index.iter().any(|s| s.id == id)
+    }
+
+    /// 按运行标识查找结果
+    pub fn find_by_run_id(&self, run_id: &str) -> Option<&ResultSummary> {
+        self.index.iter().find(|s| s.run_id.as_deref() == Some(run_id))
+    }
+
+    /// 同一台机器（硬件指纹相同）上之前最近的一次套件结果。只考虑以会话ID保存的最终结果，
+    /// 不包括同一会话、重复运行的各次原始结果和导入的结果
+    pub fn previous_on_machine(&self, id: &str, result: &TestResult) -> Option<&ResultSummary> {
+        let fingerprint = result.system_info.hardware_fingerprint();
+        let timestamp = DateTime::parse_from_rfc3339(&result.timestamp).ok()?;
+        self.index
+            .iter()
+            .filter(|s| {
+                s.id != id
+                    && !s.imported
+                    && s.hardware_fingerprint == fingerprint
+                    && s.session_id.as_deref() == Some(s.id.as_str())
+                    && s.session_id != result.session_id
+            })
+            .filter_map(|s| DateTime::parse_from_rfc3339(&s.timestamp).ok().map(|time| (s, time)))
+            .filter(|(_, time)| *time <= timestamp)
+            .max_by_key(|(_, time)| *time)
+            .map(|(s, _)| s)
+    }
+
+    /// 与同一台机器上一次的结果对比，没有可比的结果时为None
+    pub fn check_regression(
+        &self,
+        id: &str,
+        result: &TestResult,
+        thresholds: &RegressionThresholds,
+    ) -> Result<Option<ResultComparison>, BenchmarkError> {
+        let Some(previous) = self.previous_on_machine(id, result) else {
+            return Ok(None);
+        };
+        let baseline = self.load(&previous.id)?;
+        Ok(Some(ResultComparison::between(&previous.id, &baseline, id, result, thresholds)))
+    }
+
+    /// 保存结果。本机产生且尚无校验信息的结果在此计算哈希，导入的结果保留原有的校验信息
+    pub fn save(&mut self, id: &str, result: &TestResult) -> Result<ResultSummary, BenchmarkError> {
+        let path = self.result_path(id)?;
+        let mut sealed;
+        let result = if result.integrity.is_none() && !result.imported {
+            sealed = result.clone();
+            sealed.seal();
+            &sealed
+        } else {
+            result
+        };
+        let content = serde_json::to_string_pretty(result)
+            .map_err(|e| BenchmarkError::DataSaveError(format!("结果序列化失败: {}", e)))?;
+        write_atomically(&path, &compress(content.as_bytes())?)?;
+        remove_if_exists(&self.legacy_path(id)?)?;
+
+        let mut summary = ResultSummary::from_result(id, result);
+        if let Some(existing) = self.index.iter().find(|s| s.id == id) {
+            summary = summary.with_index_state(existing);
+        }
+        self.index.retain(|s| s.id != id);
+        self.index.push(summary.clone());
+        self.flag_duplicates(id);
+        self.write_index()?;
+        Ok(self.summary(id).cloned().unwrap_or(summary))
+    }
+
+    /// 新结果写入索引后检测重复：只与索引中的摘要比较，不读取结果文件。
+    /// 与已有结果重复时合并为一组，组内最早的结果保留，其余标记duplicate_of
+    fn flag_duplicates(&mut self, id: &str) {
+        let Some(summary) = self.summary(id).cloned() else {
+            return;
+        };
+        let mut roots: std::collections::BTreeSet<String> = self
+            .index
+            .iter()
+            .filter(|s| s.id != id && s.duplicates(&summary))
+            .map(|s| s.duplicate_of.clone().unwrap_or_else(|| s.id.clone()))
+            .collect();
+        if roots.is_empty() {
+            return;
+        }
+        roots.insert(id.to_string());
+        roots.extend(summary.duplicate_of);
+
+        let in_group = |s: &ResultSummary| {
+            roots.contains(&s.id) || s.duplicate_of.as_ref().is_some_and(|root| roots.contains(root))
+        };
+        let Some(keep) = earliest(self.index.iter().filter(|s| in_group(s))) else {
+            return;
+        };
+        for s in self.index.iter_mut().filter(|s| in_group(s)) {
+            s.duplicate_of = (s.id != keep).then(|| keep.clone());
+        }
+    }
+
+    /// 删除后保留的结果已不存在的组，由剩余结果中最早的一个接替
+    fn reassign_orphaned_duplicates(&mut self) {
+        let orphaned: std::collections::BTreeSet<String> = self
+            .index
+            .iter()
+            .filter_map(|s| s.duplicate_of.clone())
+            .filter(|root| !self.contains(root))
+            .collect();
+        for root in orphaned {
+            let in_group = |s: &ResultSummary| s.duplicate_of.as_deref() == Some(root.as_str());
+            let Some(keep) = earliest(self.index.iter().filter(|s| in_group(s))) else {
+                continue;
+            };
+            for s in self.index.iter_mut().filter(|s| in_group(s)) {
+                s.duplicate_of = (s.id != keep).then(|| keep.clone());
+            }
+        }
+    }
+
+    /// 删除所有被标记为重复的结果（含关联的导出文件），每组只保留最早的一个
+    pub fn merge_duplicates(&mut self, dry_run: bool) -> Result<DeletionReport, BenchmarkError> {
+        let ids = self
+            .index
+            .iter()
+            .filter(|s| s.duplicate_of.is_some())
+            .map(|s| s.id.clone())
+            .collect();
+        self.delete_many(ids, dry_run)
+    }
+
+    /// 为结果分配一个导出文件路径并记录在索引中，删除结果时一并清理
+    pub fn track_artifact(&mut self, id: &str, file_name: &str) -> Result<PathBuf, BenchmarkError> {
+        self.result_path(id)?;
+        if file_name.contains(['/', '\\']) || file_name.starts_with('.') {
+            return Err(BenchmarkError::DataSaveError(format!("无效的文件名: {}", file_name)));
+        }
+        let artifacts_dir = self.dir.join(ARTIFACTS_DIR);
+        fs::create_dir_all(&artifacts_dir)
+            .map_err(|e| BenchmarkError::DataSaveError(format!("无法创建导出目录: {}", e)))?;
+        let path = artifacts_dir.join(format!("{}-{}", id, file_name));
+
+        let summary = self
+            .index
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or_else(|| BenchmarkError::DataLoadError(format!("结果不存在: {}", id)))?;
+        if !summary.artifacts.contains(&path) {
+            summary.artifacts.push(path.clone());
+            self.write_index()?;
+        }
+        Ok(path)
+    }
+
+    /// 导入外部导出的结果文件：校验并升级结构，按run_id去重（旧结果没有run_id时按内容哈希），返回结果ID。
+    /// 内容与完整性哈希不符的结果仍然导入，标记为未通过校验
+    pub fn import_file(&mut self, path: &Path) -> Result<String, BenchmarkError> {
+        let content = fs::read(path)
+            .map_err(|e| BenchmarkError::DataLoadError(format!("无法读取导入文件 {}: {}", path.display(), e)))?;
+        let document: Value = serde_json::from_slice(&content).map_err(|e| {
+            BenchmarkError::DataLoadError(format!("导入文件 {} 不是有效的JSON: {}", path.display(), e))
+        })?;
+
+        // 对象键已排序，重新序列化后的哈希与原文件的缩进和换行无关
+        let canonical = serde_json::to_vec(&document)
+            .map_err(|e| BenchmarkError::DataLoadError(format!("导入文件序列化失败: {}", e)))?;
+        let content_hash = format!("{:x}", Sha256::digest(&canonical));
+        if let Some(existing) = self
+            .index
+            .iter()
+            .find(|s| s.content_hash.as_deref() == Some(content_hash.as_str()))
+        {
+            return Ok(existing.id.clone());
+        }
+
+        let mut result = upgrade_result_document(document)?;
+        validate_imported(&result)?;
+        result.verify_integrity();
+        // 同一次运行的结果即使导出后被修改过（如格式化、补充字段）也只保留一份
+        if let Some(existing) = result.identity.as_ref().and_then(|identity| self.find_by_run_id(&identity.run_id)) {
+            return Ok(existing.id.clone());
+        }
+        result.imported = true;
+
+        let id = format!("imported-{}", uuid::Uuid::new_v4());
+        self.save(&id, &result)?;
+        if let Some(summary) = self.index.iter_mut().find(|s| s.id == id) {
+            summary.content_hash = Some(content_hash);
+        }
+        self.write_index()?;
+        Ok(id)
+    }
+
+    pub fn summary(&self, id: &str) -> Option<&ResultSummary> {
+        self.index.iter().find(|s| s.id == id)
+    }
+
+    pub fn set_upload_status(&mut self, id: &str, status: UploadStatus) -> Result<(), BenchmarkError> {
+        let summary = self
+            .index
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or_else(|| BenchmarkError::DataLoadError(format!("结果不存在: {}", id)))?;
+        summary.upload = Some(status);
+        self.write_index()
+    }
+
+    pub fn delete(&mut self, id: &str, dry_run: bool) -> Result<DeletionReport, BenchmarkError> {
+        if !self.contains(id) {
+            return Err(BenchmarkError::DataLoadError(format!("结果不存在: {}", id)));
+        }
+        self.delete_many(vec![id.to_string()], dry_run)
+    }
+
+    /// 各会话的最终结果，最新的在前。重复运行的各次原始结果和导入的结果不列出
+    pub fn session_history(&self) -> Vec<HistoryEntry> {
+        let mut sessions: Vec<&ResultSummary> = self
+            .index
+            .iter()
+            .filter(|s| s.session_id.as_deref() == Some(s.id.as_str()))
+            .collect();
+        sessions.sort_by_key(|s| std::cmp::Reverse(DateTime::parse_from_rfc3339(&s.timestamp).ok()));
+        sessions
+            .into_iter()
+            .map(|s| HistoryEntry {
+                session_id: s.id.clone(),
+                timestamp: s.timestamp.clone(),
+                overall_score: s.overall_score,
+            })
+            .collect()
+    }
+
+    /// 读取会话的最终结果，即session_history中列出的结果；重复运行时各次的原始结果不能以此读取
+    pub fn load_session(&self, session_id: &str) -> Result<TestResult, BenchmarkError> {
+        if !self.index.iter().any(|s| s.id == session_id && s.session_id.as_deref() == Some(session_id)) {
+            return Err(BenchmarkError::DataLoadError(format!("会话结果不存在: {}", session_id)));
+        }
+        self.load(session_id)
+    }
+
+    /// 删除会话的最终结果，连同重复运行时各次的原始结果
+    pub fn delete_session(&mut self, session_id: &str, dry_run: bool) -> Result<DeletionReport, BenchmarkError> {
+        if !self.contains(session_id) {
+            return Err(BenchmarkError::DataLoadError(format!("结果不存在: {}", session_id)));
+        }
+        let ids = self
+            .index
+            .iter()
+            .filter(|s| s.id == session_id || s.session_id.as_deref() == Some(session_id))
+            .map(|s| s.id.clone())
+            .collect();
+        self.delete_many(ids, dry_run)
+    }
+
+    /// 清理早于指定时间的结果，`older_than`为空时清空全部历史
+    pub fn clear(&mut self, older_than: Option<&str>, dry_run: bool) -> Result<DeletionReport, BenchmarkError> {
+        let cutoff = older_than.map(parse_timestamp).transpose()?;
+        let ids = self
+            .index
+            .iter()
+            .filter(|s| match cutoff {
+                Some(cutoff) => DateTime::parse_from_rfc3339(&s.timestamp).is_ok_and(|t| t < cutoff),
+                None => true,
+            })
+            .map(|s| s.id.clone())
+            .collect();
+        self.delete_many(ids, dry_run)
+    }
+
+    fn delete_many(&mut self, ids: Vec<String>, dry_run: bool) -> Result<DeletionReport, BenchmarkError> {
+        let mut files = Vec::new();
+        for summary in self.index.iter().filter(|s| ids.contains(&s.id)) {
+            self.result_path(&summary.id)?;
+            files.extend(result_files(&self.dir, &summary.id));
+            files.extend(summary.artifacts.iter().cloned());
+        }
+        let bytes_reclaimed = files
+            .iter()
+            .filter_map(|f| fs::metadata(f).ok())
+            .map(|m| m.len())
+            .sum();
+
+        let report = DeletionReport {
+            removed: ids.len(),
+            bytes_reclaimed,
+            result_ids: ids,
+            dry_run,
+        };
+        if dry_run || report.removed == 0 {
+            return Ok(report);
+        }
+
+        // 1. 记录日志 2. 更新索引 3. 删除文件 4. 移除日志
+        let journal = DeleteJournal {
+            result_ids: report.result_ids.clone(),
+            files,
+        };
+        let content = serde_json::to_string(&journal)
+            .map_err(|e| BenchmarkError::DataSaveError(format!("删除日志序列化失败: {}", e)))?;
+        write_atomically(&self.dir.join(DELETE_JOURNAL_FILE), content.as_bytes())?;
+        self.apply_delete_journal(journal)?;
+        Ok(report)
+    }
+
+    fn replay_delete_journal(&mut self) -> Result<(), BenchmarkError> {
+        let journal_path = self.dir.join(DELETE_JOURNAL_FILE);
+        let Ok(content) = fs::read_to_string(&journal_path) else {
+            return Ok(());
+        };
+        match serde_json::from_str::<DeleteJournal>(&content) {
+            Ok(journal) => self.apply_delete_journal(journal),
+            // 日志本身未写完整说明索引尚未修改，直接丢弃
+            Err(_) => fs::remove_file(&journal_path)
+                .map_err(|e| BenchmarkError::DataSaveError(format!("无法移除删除日志: {}", e))),
+        }
+    }
+
+    fn apply_delete_journal(&mut self, journal: DeleteJournal) -> Result<(), BenchmarkError> {
+        self.index.retain(|s| !journal.result_ids.contains(&s.id));
+        self.reassign_orphaned_duplicates();
+        self.write_index()?;
+
+        for file in &journal.files {
+            remove_if_exists(file)?;
+        }
+
+        fs::remove_file(self.dir.join(DELETE_JOURNAL_FILE))
+            .map_err(|e| BenchmarkError::DataSaveError(format!("无法移除删除日志: {}", e)))
+    }
+
+    /// 读取完整结果并重新校验完整性，压缩文件损坏时将其隔离（改名为*.corrupt）并返回错误
+    pub fn load(&self, id: &str) -> Result<TestResult, BenchmarkError> {
+        let compressed = self.result_path(id)?;
+        let path = if compressed.exists() { compressed } else { self.legacy_path(id)? };
+        let content = fs::read(&path)
+            .map_err(|e| BenchmarkError::DataLoadError(format!("无法读取结果 {}: {}", id, e)))?;
+        if path.extension().and_then(|e| e.to_str()) == Some(LEGACY_EXTENSION) {
+            let document = serde_json::from_slice(&content)
+                .map_err(|e| BenchmarkError::DataLoadError(format!("结果 {} 解析失败: {}", id, e)))?;
+            return upgrade_and_verify(document);
+        }
+
+        let document = zstd::decode_all(content.as_slice())
+            .map_err(|e| format!("解压失败: {}", e))
+            .and_then(|json| serde_json::from_slice(&json).map_err(|e| format!("解析失败: {}", e)));
+        match document {
+            Ok(document) => upgrade_and_verify(document),
+            Err(reason) => {
+                let quarantined = quarantine(&path)?;
+                Err(BenchmarkError::DataLoadError(format!(
+                    "结果 {} {}，已隔离为 {}",
+                    id,
+                    reason,
+                    quarantined.display()
+                )))
+            }
+        }
+    }
+
+    /// 把旧版本写入的未压缩结果改写为压缩格式，返回节省的空间
+    pub fn compact(&mut self) -> Result<CompactionReport, BenchmarkError> {
+        let mut report = CompactionReport::default();
+        for summary in &self.index {
+            let legacy = self.legacy_path(&summary.id)?;
+            let content = match fs::read(&legacy) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => {
+                    return Err(BenchmarkError::DataLoadError(format!("无法读取 {}: {}", legacy.display(), e)));
+                }
+            };
+            let compressed = compress(&content)?;
+            // 先写入压缩文件再删除旧文件，中途崩溃时两者并存，读取时优先使用压缩文件
+            write_atomically(&self.result_path(&summary.id)?, &compressed)?;
+            remove_if_exists(&legacy)?;
+            report.compacted += 1;
+            report.bytes_before += content.len() as u64;
+            report.bytes_after += compressed.len() as u64;
+        }
+        report.bytes_saved = report.bytes_before.saturating_sub(report.bytes_after);
+        Ok(report)
+    }
+
+    pub fn compare(
+        &self,
+        baseline_id: &str,
+        candidate_id: &str,
+        thresholds: &RegressionThresholds,
+    ) -> Result<ResultComparison, BenchmarkError> {
+        let baseline = self.load(baseline_id)?;
+        let candidate = self.load(candidate_id)?;
+        Ok(ResultComparison::between(baseline_id, &baseline, candidate_id, &candidate, thresholds))
+    }
+
+    pub fn query(&self, query: &HistoryQuery) -> Result<HistoryPage, BenchmarkError> {
+        let from = query.from.as_deref().map(parse_timestamp).transpose()?;
+        let to = query.to.as_deref().map(parse_timestamp).transpose()?;
+
+        let mut matches: Vec<(&ResultSummary, Option<DateTime<FixedOffset>>)> = self
+            .index
+            .iter()
+            .map(|s| (s, DateTime::parse_from_rfc3339(&s.timestamp).ok()))
+            .filter(|(s, time)| {
+                if let Some(from) = from {
+                    if !time.is_some_and(|t| t >= from) {
+                        return false;
+                    }
+                }
+                if let Some(to) = to {
+                    if !time.is_some_and(|t| t <= to) {
+                        return false;
+                    }
+                }
+                if let Some(has_storage) = query.has_storage_results {
+                    if s.has_storage_results != has_storage {
+                        return false;
+                    }
+                }
+                if let Some(min_score) = query.min_overall_score {
+                    if s.overall_score < min_score {
+                        return false;
+                    }
+                }
+                query.include_duplicates || s.duplicate_of.is_none()
+            })
+            .collect();
+
+        matches.sort_by(|(a, a_time), (b, b_time)| {
+            let primary = match query.sort_by {
+                HistorySortField::Date => a_time.cmp(b_time),
+                HistorySortField::Score => a
+                    .overall_score
+                    .partial_cmp(&b.overall_score)
+                    .unwrap_or(Ordering::Equal),
+            };
+            // 相同排序键时按时间和ID排序，保证分页结果稳定
+            let ordering = primary.then_with(|| a_time.cmp(b_time)).then_with(|| a.id.cmp(&b.id));
+            match query.order {
+                SortOrder::Asc => ordering,
+                SortOrder::Desc => ordering.reverse(),
+            }
+        });
+
+        let total = matches.len();
+        let entries = matches
+            .into_iter()
+            .skip(query.offset)
+            .take(query.limit.unwrap_or(usize::MAX))
+            .map(|(s, _)| s.clone())
+            .collect();
+
+        Ok(HistoryPage {
+            total,
+            offset: query.offset,
+            entries,
+        })
+    }
+
+    fn result_path(&self, id: &str) -> Result<PathBuf, BenchmarkError> {
+        // ID直接作为文件名，拒绝可能造成路径穿越的字符
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(BenchmarkError::DataLoadError(format!("无效的结果ID: {}", id)));
+        }
+        Ok(self.dir.join(format!("{}.{}", id, COMPRESSED_EXTENSION)))
+    }
+
+    /// 旧版本写入的未压缩结果文件
+    fn legacy_path(&self, id: &str) -> Result<PathBuf, BenchmarkError> {
+        self.result_path(id)?;
+        Ok(self.dir.join(format!("{}.{}", id, LEGACY_EXTENSION)))
+    }
+
+    fn rebuild_index(&mut self) -> Result<(), BenchmarkError> {
+        let entries = fs::read_dir(&self.dir)
+            .map_err(|e| BenchmarkError::DataLoadError(format!("无法读取结果目录: {}", e)))?;
+
+        // 压缩和未压缩文件并存时只加载一次
+        let mut ids = std::collections::BTreeSet::new();
+        for entry in entries.flatten() {
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            if name == INDEX_FILE || name == DELETE_JOURNAL_FILE {
+                continue;
+            }
+            let id = name
+                .strip_suffix(&format!(".{}", COMPRESSED_EXTENSION))
+                .or_else(|| name.strip_suffix(&format!(".{}", LEGACY_EXTENSION)));
+            if let Some(id) = id {
+                ids.insert(id.to_string());
+            }
+        }
+
+        self.index.clear();
+        for id in ids {
+            // 无法解析的文件跳过（损坏的压缩文件同时被隔离），不影响其余结果
+            if let Ok(result) = self.load(&id) {
+                self.index.push(ResultSummary::from_result(&id, &result));
+                self.flag_duplicates(&id);
+            }
+        }
+        self.write_index()
+    }
+
+    /// 旧版本写入的摘要按升级后的结果重新生成，否则列表中的总分与load()读到的不一致（版本3改变了总分的计算方式）。
+    /// 无法读取的结果保留原摘要，下次打开时再试
+    fn refresh_outdated_summaries(&mut self) -> Result<(), BenchmarkError> {
+        let outdated: Vec<String> = self
+            .index
+            .iter()
+            .filter(|s| s.schema_version < RESULT_SCHEMA_VERSION)
+            .map(|s| s.id.clone())
+            .collect();
+        let mut refreshed = false;
+        for id in outdated {
+            let Ok(result) = self.load(&id) else {
+                continue;
+            };
+            if let Some(summary) = self.index.iter_mut().find(|s| s.id == id) {
+                *summary = ResultSummary::from_result(&id, &result).with_index_state(summary);
+                refreshed = true;
+            }
+        }
+        if refreshed {
+            self.write_index()?;
+        }
+        Ok(())
+    }
+
+    fn write_index(&self) -> Result<(), BenchmarkError> {
+        let content = serde_json::to_string(&self.index)
+            .map_err(|e| BenchmarkError::DataSaveError(format!("索引序列化失败: {}", e)))?;
+        write_atomically(&self.dir.join(INDEX_FILE), content.as_bytes())
+    }
+}
+
+fn parse_timestamp(value: &str) -> Result<DateTime<FixedOffset>, BenchmarkError> {
+    DateTime::parse_from_rfc3339(value)
+        .map_err(|e| BenchmarkError::DataLoadError(format!("无效的时间 {}: {}", value, e)))
+}
+
+/// 实际生效配置的哈希，配置经Value转换后对象键已排序，与字段声明顺序无关
+fn config_hash(result: &TestResult) -> Option<String> {
+    let config = serde_json::to_value(result.config_used.as_ref()?).ok()?;
+    let canonical = serde_json::to_vec(&config).ok()?;
+    Some(format!("{:x}", Sha256::digest(&canonical)))
+}
+
+/// 时间最早的结果ID，时间相同时取先写入索引的
+fn earliest<'a>(summaries: impl Iterator<Item = &'a ResultSummary>) -> Option<String> {
+    summaries
+        .min_by_key(|s| {
+            let time = DateTime::parse_from_rfc3339(&s.timestamp).ok();
+            (time.is_none(), time)
+        })
+        .map(|s| s.id.clone())
+}
+
+/// 导入结果的基本合理性检查，结构正确但数值明显异常的文件同样拒绝
+fn validate_imported(result: &TestResult) -> Result<(), BenchmarkError> {
+    parse_timestamp(&result.timestamp)?;
+    if !result.overall_score.is_finite() || result.overall_score < 0.0 {
+        return Err(BenchmarkError::DataLoadError(format!(
+            "无效的总分: {}",
+            result.overall_score
+        )));
+    }
+    if result.cpu_results.is_none() && result.memory_results.is_none() && result.storage_results.is_none() {
+        return Err(BenchmarkError::DataLoadError("导入文件不包含任何测试结果".to_string()));
+    }
+    Ok(())
+}
+
+fn upgrade_and_verify(document: Value) -> Result<TestResult, BenchmarkError> {
+    let mut result = upgrade_result_document(document)?;
+    result.verify_integrity();
+    Ok(result)
+}
+
+fn compress(content: &[u8]) -> Result<Vec<u8>, BenchmarkError> {
+    zstd::encode_all(content, zstd::DEFAULT_COMPRESSION_LEVEL)
+        .map_err(|e| BenchmarkError::DataSaveError(format!("结果压缩失败: {}", e)))
+}
+
+/// 结果可能对应的文件：压缩文件和旧版本的未压缩文件
+fn result_files(dir: &Path, id: &str) -> [PathBuf; 2] {
+    [
+        dir.join(format!("{}.{}", id, COMPRESSED_EXTENSION)),
+        dir.join(format!("{}.{}", id, LEGACY_EXTENSION)),
+    ]
+}
+
+/// 将损坏的文件改名为*.corrupt，保留原始内容以便排查
+fn quarantine(path: &Path) -> Result<PathBuf, BenchmarkError> {
+    let mut quarantined = path.as_os_str().to_owned();
+    quarantined.push(format!(".{}", QUARANTINE_SUFFIX));
+    let quarantined = PathBuf::from(quarantined);
+    fs::rename(path, &quarantined)
+        .map_err(|e| BenchmarkError::DataLoadError(format!("无法隔离损坏的文件 {}: {}", path.display(), e)))?;
+    Ok(quarantined)
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), BenchmarkError> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(BenchmarkError::DataSaveError(format!("无法删除 {}: {}", path.display(), e))),
+    }
+}
+
+/// 先写临时文件再重命名，避免崩溃时留下半个文件
+pub(crate) fn write_atomically(path: &Path, content: &[u8]) -> Result<(), BenchmarkError> {
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, content)
+        .map_err(|e| BenchmarkError::DataSaveError(format!("写入 {} 失败: {}", tmp_path.display(), e)))?;
+    fs::rename(&tmp_path, path)
+        .map_err(|e| BenchmarkError::DataSaveError(format!("重命名 {} 失败: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::test_support::sample_test_result;
+    use serde_json::json;
+
+    /// 25个结果：第i个的时间为2024-01-(i+1)，评分为i*10，偶数项包含存储结果
+    fn seeded_store(dir: &Path) -> ResultStore {
+        let mut store = ResultStore::open(dir).unwrap();
+        for i in 0..25 {
+            let timestamp = format!("2024-01-{:02}T12:00:00+00:00", i + 1);
+            let mut result = sample_test_result(&timestamp, i as f64 * 10.0);
+            if i % 2 == 1 {
+                result.storage_results = None;
+            }
+            store.save(&format!("result-{:02}", i), &result).unwrap();
+        }
+        store
+    }
+
+    fn ids(page: &HistoryPage) -> Vec<&str> {
+        page.entries.iter().map(|s| s.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_pagination_boundaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = seeded_store(dir.path());
+
+        let query = |offset, limit| HistoryQuery {
+            offset,
+            limit,
+            order: SortOrder::Asc,
+            ..Default::default()
+        };
+
+        let first = store.query(&query(0, Some(10))).unwrap();
+        assert_eq!(first.total, 25);
+        assert_eq!(first.entries.len(), 10);
+        assert_eq!(first.entries[0].id, "result-00");
+
+        let last = store.query(&query(20, Some(10))).unwrap();
+        assert_eq!(ids(&last), vec!["result-20", "result-21", "result-22", "result-23", "result-24"]);
+
+        assert!(store.query(&query(25, Some(10))).unwrap().entries.is_empty());
+        assert!(store.query(&query(100, None)).unwrap().entries.is_empty());
+        assert_eq!(store.query(&query(0, None)).unwrap().entries.len(), 25);
+        assert!(store.query(&query(0, Some(0))).unwrap().entries.is_empty());
+    }
+
+    #[test]
+    fn test_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = seeded_store(dir.path());
+
+        let with_storage = store
+            .query(&HistoryQuery {
+                has_storage_results: Some(true),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(with_storage.total, 13);
+        assert!(with_storage.entries.iter().all(|s| s.headline.storage_read.is_some()));
+
+        let high_score = store
+            .query(&HistoryQuery {
+                min_overall_score: Some(200.0),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(high_score.total, 5);
+
+        let date_range = store
+            .query(&HistoryQuery {
+                from: Some("2024-01-05T00:00:00Z".to_string()),
+                to: Some("2024-01-10T23:59:59Z".to_string()),
+                order: SortOrder::Asc,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(date_range.total, 6);
+        assert_eq!(date_range.entries[0].id, "result-04");
+
+        let combined = store
+            .query(&HistoryQuery {
+                from: Some("2024-01-05T00:00:00Z".to_string()),
+                has_storage_results: Some(false),
+                min_overall_score: Some(100.0),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(combined.total, 7); // 11, 13, ..., 23
+
+        assert!(store
+            .query(&HistoryQuery {
+                from: Some("not a date".to_string()),
+                ..Default::default()
+            })
+            .is_err());
+    }
+
+    #[test]
+    fn test_sort_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = seeded_store(dir.path());
+
+        // 默认按时间倒序
+        let newest_first = store.query(&HistoryQuery::default()).unwrap();
+        assert_eq!(newest_first.entries[0].id, "result-24");
+        assert_eq!(newest_first.entries[24].id, "result-00");
+
+        let by_score = store
+            .query(&HistoryQuery {
+                sort_by: HistorySortField::Score,
+                order: SortOrder::Asc,
+                limit: Some(3),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(ids(&by_score), vec!["result-00", "result-01", "result-02"]);
+
+        let scores: Vec<f64> = store
+            .query(&HistoryQuery {
+                sort_by: HistorySortField::Score,
+                ..Default::default()
+            })
+            .unwrap()
+            .entries
+            .iter()
+            .map(|s| s.overall_score)
+            .collect();
+        assert!(scores.windows(2).all(|w| w[0] >= w[1]));
+    }
+
+    #[test]
+    fn test_index_persistence_and_rebuild() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = seeded_store(dir.path());
+        let summaries = store.summaries().to_vec();
+        drop(store);
+
+        let reopened = ResultStore::open(dir.path()).unwrap();
+        assert_eq!(reopened.summaries(), summaries.as_slice());
+
+        fs::remove_file(dir.path().join(INDEX_FILE)).unwrap();
+        let rebuilt = ResultStore::open(dir.path()).unwrap();
+        assert_eq!(rebuilt.summaries().len(), 25);
+        assert_eq!(rebuilt.load("result-07").unwrap().overall_score, 70.0);
+    }
+
+    #[test]
+    fn test_outdated_index_summaries_are_refreshed_on_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = ResultStore::open(dir.path()).unwrap();
+        let mut legacy = sample_test_result("2024-01-01T00:00:00Z", 3000.0);
+        legacy.schema_version = 2;
+        legacy.session_id = Some("legacy".to_string());
+        store.save("legacy", &legacy).unwrap();
+        store.set_upload_status("legacy", UploadStatus::pending()).unwrap();
+        drop(store);
+
+        // 模拟版本3之前写入的索引：摘要中没有结构版本，总分是旧的计算结果
+        let index_path = dir.path().join(INDEX_FILE);
+        let mut index: Value = serde_json::from_str(&fs::read_to_string(&index_path).unwrap()).unwrap();
+        for entry in index.as_array_mut().unwrap() {
+            entry.as_object_mut().unwrap().remove("schema_version");
+        }
+        fs::write(&index_path, serde_json::to_string(&index).unwrap()).unwrap();
+
+        let store = ResultStore::open(dir.path()).unwrap();
+        let score = store.load("legacy").unwrap().overall_score;
+        assert_ne!(score, 3000.0);
+        let summary = store.summary("legacy").unwrap();
+        assert_eq!((summary.overall_score, summary.schema_version), (score, RESULT_SCHEMA_VERSION));
+        assert!(summary.upload.is_some()); // 只记录在索引中的上传状态保留
+        assert_eq!(store.session_history()[0].overall_score, score);
+        let query = |min_overall_score| HistoryQuery { min_overall_score: Some(min_overall_score), ..Default::default() };
+        assert_eq!(store.query(&query(score)).unwrap().total, 1);
+        assert_eq!(store.query(&query(score + 1.0)).unwrap().total, 0);
+
+        // 刷新后的索引已写回磁盘
+        let index: Value = serde_json::from_str(&fs::read_to_string(&index_path).unwrap()).unwrap();
+        assert_eq!(index[0]["schema_version"], json!(RESULT_SCHEMA_VERSION));
+        assert_eq!(index[0]["overall_score"], json!(score));
+    }
+
+    #[test]
+    fn test_results_are_stored_compressed() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = ResultStore::open(dir.path()).unwrap();
+        let result = sample_test_result("2024-01-01T00:00:00Z", 42.0);
+        store.save("result", &result).unwrap();
+
+        let compressed = dir.path().join("result.json.zst");
+        assert!(compressed.exists());
+        assert!(!dir.path().join("result.json").exists());
+        let json = serde_json::to_string_pretty(&result).unwrap();
+        assert!(fs::metadata(&compressed).unwrap().len() < json.len() as u64);
+        let mut sealed = result.clone();
+        sealed.seal();
+        assert_eq!(
+            serde_json::to_value(store.load("result").unwrap()).unwrap(),
+            serde_json::to_value(&sealed).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_integrity_is_verified_on_load_and_import() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = ResultStore::open(dir.path().join("results")).unwrap();
+        let summary = store.save("local", &sample_test_result("2024-03-01T08:00:00Z", 88.0)).unwrap();
+        assert_eq!(summary.verified, Some(true));
+        let loaded = store.load("local").unwrap();
+        assert_eq!(loaded.integrity_verified(), Some(true));
+
+        // 修改导出文件中的一个字段后导入：保留结果但标记为未通过校验
+        let mut document = serde_json::to_value(&loaded).unwrap();
+        document["overall_score"] = json!(95.0);
+        document["identity"] = Value::Null;
+        let export_path = dir.path().join("edited.json");
+        fs::write(&export_path, serde_json::to_string_pretty(&document).unwrap()).unwrap();
+        let id = store.import_file(&export_path).unwrap();
+        assert_eq!(store.summary(&id).unwrap().verified, Some(false));
+        let imported = store.load(&id).unwrap();
+        assert_eq!(imported.overall_score, 95.0);
+        assert_eq!(imported.integrity_verified(), Some(false));
+        // 对比结果同时报告两侧的校验状态
+        let comparison = store.compare("local", &id, &RegressionThresholds::default()).unwrap();
+        assert_eq!((comparison.baseline_verified, comparison.candidate_verified), (Some(true), Some(false)));
+
+        // 旧版本保存的结果没有校验信息
+        let legacy = sample_test_result("2024-02-01T08:00:00Z", 70.0);
+        fs::write(dir.path().join("results").join("legacy.json"), serde_json::to_string(&legacy).unwrap()).unwrap();
+        fs::remove_file(dir.path().join("results").join(INDEX_FILE)).unwrap();
+        let store = ResultStore::open(dir.path().join("results")).unwrap();
+        assert_eq!(store.summary("legacy").unwrap().verified, None);
+        assert_eq!(store.summary(&id).unwrap().verified, Some(false));
+
+        // 直接篡改存储中的文件同样在读取时发现
+        let path = dir.path().join("results").join("local.json.zst");
+        let mut tampered: Value = serde_json::from_slice(&zstd::decode_all(fs::read(&path).unwrap().as_slice()).unwrap()).unwrap();
+        tampered["overall_score"] = json!(1.0);
+        fs::write(&path, compress(serde_json::to_string(&tampered).unwrap().as_bytes()).unwrap()).unwrap();
+        assert_eq!(store.load("local").unwrap().integrity_verified(), Some(false));
+    }
+
+    #[test]
+    fn test_mixed_store_loads_and_compacts_legacy_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = seeded_store(dir.path());
+        drop(store);
+        // 旧版本写入的未压缩文件
+        for i in 25..28 {
+            let result = sample_test_result(&format!("2024-02-{:02}T12:00:00+00:00", i - 24), i as f64 * 10.0);
+            fs::write(dir.path().join(format!("result-{:02}.json", i)), serde_json::to_string_pretty(&result).unwrap()).unwrap();
+        }
+        fs::remove_file(dir.path().join(INDEX_FILE)).unwrap();
+
+        let mut store = ResultStore::open(dir.path()).unwrap();
+        assert_eq!(store.summaries().len(), 28);
+        assert_eq!(store.load("result-26").unwrap().overall_score, 260.0);
+        assert_eq!(store.load("result-07").unwrap().overall_score, 70.0);
+
+        let legacy_bytes: u64 = (25..28)
+            .map(|i| fs::metadata(dir.path().join(format!("result-{:02}.json", i))).unwrap().len())
+            .sum();
+        let report = store.compact().unwrap();
+        assert_eq!(report.compacted, 3);
+        assert_eq!(report.bytes_before, legacy_bytes);
+        assert_eq!(report.bytes_saved, report.bytes_before - report.bytes_after);
+        assert!(report.bytes_saved > 0);
+        assert!(!dir.path().join("result-26.json").exists());
+        assert_eq!(store.load("result-26").unwrap().overall_score, 260.0);
+        assert_eq!(store.compact().unwrap(), CompactionReport::default());
+    }
+
+    #[test]
+    fn test_corrupt_compressed_file_is_quarantined() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = seeded_store(dir.path());
+        let truncate = |id: &str| {
+            let path = dir.path().join(format!("{}.json.zst", id));
+            let content = fs::read(&path).unwrap();
+            fs::write(&path, &content[..content.len() / 2]).unwrap();
+        };
+
+        truncate("result-04");
+        let err = store.load("result-04").unwrap_err();
+        assert!(matches!(err, BenchmarkError::DataLoadError(ref msg) if msg.contains("隔离")), "{}", err);
+        assert!(!dir.path().join("result-04.json.zst").exists());
+        assert!(dir.path().join("result-04.json.zst.corrupt").exists());
+        drop(store);
+
+        // 重新打开时不再列出被隔离的结果
+        let store = ResultStore::open(dir.path()).unwrap();
+        assert!(!store.contains("result-04"));
+        assert_eq!(store.summaries().len(), 24);
+        drop(store);
+
+        // 重建索引时遇到损坏的文件同样隔离，其余结果正常加载
+        truncate("result-09");
+        fs::remove_file(dir.path().join(INDEX_FILE)).unwrap();
+        let store = ResultStore::open(dir.path()).unwrap();
+        assert_eq!(store.summaries().len(), 23);
+        assert!(dir.path().join("result-09.json.zst.corrupt").exists());
+        assert_eq!(store.load("result-10").unwrap().overall_score, 100.0);
+    }
+
+    #[test]
+    fn test_previous_on_machine_skips_other_sessions_runs_and_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = ResultStore::open(dir.path()).unwrap();
+        let result = |timestamp: &str, session_id: &str| {
+            let mut result = sample_test_result(timestamp, 1.0);
+            result.session_id = Some(session_id.to_string());
+            result
+        };
+        store.save("older", &result("2024-01-01T00:00:00Z", "older")).unwrap();
+        store.save("last", &result("2024-01-02T00:00:00Z", "last")).unwrap();
+        // 上一次套件重复运行的原始结果、导入的结果、之后的结果都不作为基准
+        store.save("last-run2", &result("2024-01-02T01:00:00Z", "last")).unwrap();
+        let mut imported = result("2024-01-03T00:00:00Z", "imported");
+        imported.imported = true;
+        store.save("imported", &imported).unwrap();
+        store.save("later", &result("2024-03-01T00:00:00Z", "later")).unwrap();
+        // 本次会话先保存的原始结果
+        store.save("current-run1", &result("2024-02-01T00:00:00Z", "current")).unwrap();
+
+        let current = result("2024-02-01T00:00:00Z", "current");
+        assert_eq!(store.previous_on_machine("current", &current).unwrap().id, "last");
+        let comparison = store.check_regression("current", &current, &RegressionThresholds::default()).unwrap().unwrap();
+        assert!(comparison.report.passed());
+
+        let mut other_machine = current.clone();
+        other_machine.system_info.cpu.cores += 2;
+        assert!(store.previous_on_machine("current", &other_machine).is_none());
+    }
+
+    #[test]
+    fn test_delete_missing_result() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = seeded_store(dir.path());
+        let err = store.delete("no-such-result", false).unwrap_err();
+        assert!(matches!(err, BenchmarkError::DataLoadError(_)));
+        assert_eq!(store.summaries().len(), 25);
+    }
+
+    #[test]
+    fn test_delete_removes_result_and_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = seeded_store(dir.path());
+        let report_path = store.track_artifact("result-03", "report.html").unwrap();
+        fs::write(&report_path, "<html></html>").unwrap();
+        let result_size = fs::metadata(dir.path().join("result-03.json.zst")).unwrap().len();
+
+        let report = store.delete("result-03", false).unwrap();
+        assert_eq!(report.removed, 1);
+        assert_eq!(report.bytes_reclaimed, result_size + 13);
+        assert!(!report_path.exists());
+        assert!(!dir.path().join("result-03.json.zst").exists());
+        assert!(!dir.path().join(DELETE_JOURNAL_FILE).exists());
+        assert!(!store.contains("result-03"));
+
+        // 索引已落盘
+        assert!(!ResultStore::open(dir.path()).unwrap().contains("result-03"));
+    }
+
+    #[test]
+    fn test_session_history_and_delete_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = ResultStore::open(dir.path()).unwrap();
+        let result = |timestamp: &str, session_id: &str, score: f64| {
+            let mut result = sample_test_result(timestamp, score);
+            result.session_id = Some(session_id.to_string());
+            result
+        };
+        store.save("older", &result("2024-01-01T00:00:00Z", "older", 10.0)).unwrap();
+        store.save("repeated-run1", &result("2024-01-02T00:00:00Z", "repeated", 18.0)).unwrap();
+        store.save("repeated-run2", &result("2024-01-02T00:01:00Z", "repeated", 22.0)).unwrap();
+        store.save("repeated", &result("2024-01-02T00:00:00Z", "repeated", 20.0)).unwrap();
+        store.save("imported", &sample_test_result("2024-01-03T00:00:00Z", 30.0)).unwrap();
+
+        // 只列出各会话的最终结果，最新的在前
+        let history = store.session_history();
+        assert_eq!(
+            history,
+            [
+                HistoryEntry { session_id: "repeated".to_string(), timestamp: "2024-01-02T00:00:00Z".to_string(), overall_score: 20.0 },
+                HistoryEntry { session_id: "older".to_string(), timestamp: "2024-01-01T00:00:00Z".to_string(), overall_score: 10.0 },
+            ]
+        );
+        assert_eq!(store.load_session("repeated").unwrap().overall_score, 20.0);
+        for id in ["repeated-run1", "imported", "missing"] {
+            assert!(matches!(store.load_session(id), Err(BenchmarkError::DataLoadError(_))), "{}", id);
+        }
+
+        // 删除会话时各次运行的原始结果一并删除，其它结果不受影响
+        let report = store.delete_session("repeated", false).unwrap();
+        assert_eq!(report.result_ids, ["repeated-run1", "repeated-run2", "repeated"]);
+        assert!(!dir.path().join("repeated-run1.json.zst").exists());
+        assert_eq!(store.summaries().iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), ["older", "imported"]);
+        assert!(matches!(store.delete_session("repeated", false), Err(BenchmarkError::DataLoadError(_))));
+    }
+
+    #[test]
+    fn test_clear_older_than() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = seeded_store(dir.path());
+
+        let report = store.clear(Some("2024-01-11T00:00:00Z"), false).unwrap();
+        assert_eq!(report.removed, 10);
+        assert_eq!(store.summaries().len(), 15);
+        assert!(store.summaries().iter().all(|s| s.timestamp.as_str() >= "2024-01-11"));
+
+        let report = store.clear(None, false).unwrap();
+        assert_eq!(report.removed, 15);
+        assert!(store.summaries().is_empty());
+    }
+
+    #[test]
+    fn test_dry_run_keeps_everything() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = seeded_store(dir.path());
+
+        let report = store.clear(Some("2024-01-06T00:00:00Z"), true).unwrap();
+        assert!(report.dry_run);
+        assert_eq!(report.removed, 5);
+        assert!(report.bytes_reclaimed > 0);
+        assert_eq!(store.summaries().len(), 25);
+        assert!(dir.path().join("result-00.json.zst").exists());
+
+        let report = store.delete("result-00", true).unwrap();
+        assert_eq!(report.removed, 1);
+        assert!(store.contains("result-00"));
+    }
+
+    #[test]
+    fn test_interrupted_delete_is_replayed_on_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = seeded_store(dir.path());
+        drop(store);
+
+        // 模拟写完日志后崩溃：索引和文件都还在
+        let journal = DeleteJournal {
+            result_ids: vec!["result-05".to_string()],
+            files: vec![dir.path().join("result-05.json.zst")],
+        };
+        fs::write(dir.path().join(DELETE_JOURNAL_FILE), serde_json::to_string(&journal).unwrap()).unwrap();
+
+        let store = ResultStore::open(dir.path()).unwrap();
+        assert!(!store.contains("result-05"));
+        assert!(!dir.path().join("result-05.json.zst").exists());
+        assert!(!dir.path().join(DELETE_JOURNAL_FILE).exists());
+        assert_eq!(store.summaries().len(), 24);
+    }
+
+    #[test]
+    fn test_upload_status_is_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = seeded_store(dir.path());
+        assert!(store.summary("result-01").unwrap().upload.is_none());
+        assert!(store.set_upload_status("missing", UploadStatus::pending()).is_err());
+
+        store.set_upload_status("result-01", UploadStatus::pending()).unwrap();
+        let reopened = ResultStore::open(dir.path()).unwrap();
+        let status = reopened.summary("result-01").unwrap().upload.clone().unwrap();
+        assert_eq!(status.state, crate::upload::UploadState::Pending);
+    }
+
+    #[test]
+    fn test_import_current_version_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = ResultStore::open(dir.path().join("results")).unwrap();
+        let result = sample_test_result("2024-03-01T08:00:00Z", 88.0);
+        let export_path = dir.path().join("export.json");
+        fs::write(&export_path, serde_json::to_string_pretty(&result).unwrap()).unwrap();
+
+        let id = store.import_file(&export_path).unwrap();
+        let summary = store.summary(&id).unwrap();
+        assert!(summary.imported);
+        assert_eq!(summary.hardware_fingerprint, result.system_info.hardware_fingerprint());
+        assert!(store.load(&id).unwrap().imported);
+
+        // 同一内容即使格式不同也只导入一次
+        fs::write(&export_path, serde_json::to_string(&result).unwrap()).unwrap();
+        assert_eq!(store.import_file(&export_path).unwrap(), id);
+        assert_eq!(store.summaries().len(), 1);
+    }
+
+    #[test]
+    fn test_identity_survives_export_import_and_dedups_by_run_id() {
+        use crate::app_info::{AppInfo, RunIdentity, RunMode};
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut source = ResultStore::open(dir.path().join("source")).unwrap();
+        let mut result = sample_test_result("2024-03-01T08:00:00Z", 88.0);
+        let identity = RunIdentity::new(&result.system_info, &AppInfo::new("1.2.3"), RunMode::Headless);
+        result.identity = Some(identity.clone());
+        source.save("local", &result).unwrap();
+        assert_eq!(source.find_by_run_id(&identity.run_id).unwrap().id, "local");
+        assert!(source.find_by_run_id("unknown").is_none());
+
+        // 导出再导入到另一个存储，运行标识原样保留
+        let export_path = dir.path().join("export.json");
+        fs::write(&export_path, serde_json::to_string_pretty(&source.load("local").unwrap()).unwrap()).unwrap();
+        let document: Value = serde_json::from_str(&fs::read_to_string(&export_path).unwrap()).unwrap();
+        assert_eq!(document["identity"]["run_id"], identity.run_id.as_str());
+        let mut target = ResultStore::open(dir.path().join("target")).unwrap();
+        let id = target.import_file(&export_path).unwrap();
+        assert_eq!(target.load(&id).unwrap().identity, Some(identity.clone()));
+        assert_eq!(target.summary(&id).unwrap().run_id.as_deref(), Some(identity.run_id.as_str()));
+
+        // 内容不同但run_id相同的文件视为同一次运行
+        result.overall_score = 90.0;
+        fs::write(&export_path, serde_json::to_string(&result).unwrap()).unwrap();
+        assert_eq!(target.import_file(&export_path).unwrap(), id);
+        assert_eq!(target.summaries().len(), 1);
+        // 本机已有的运行不会再作为导入结果保存一份
+        assert_eq!(source.import_file(&export_path).unwrap(), "local");
+        assert_eq!(source.summaries().len(), 1);
+    }
+
+    #[test]
+    fn test_flags_filters_and_merges_duplicates() {
+        use crate::app_info::{AppInfo, RunIdentity, RunMode};
+        use crate::benchmark::core::BenchmarkConfig;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = ResultStore::open(dir.path()).unwrap();
+        let result = |timestamp: &str| {
+            let mut result = sample_test_result(timestamp, 50.0);
+            result.config_used = Some(BenchmarkConfig::default());
+            result.identity = Some(RunIdentity::new(&result.system_info, &AppInfo::new("1.0.0"), RunMode::Headless));
+            result
+        };
+
+        let original = result("2024-05-01T10:01:00Z");
+        store.save("original", &original).unwrap();
+        // 同一run_id以不同ID保存（如自动保存后又手动保存）
+        assert_eq!(store.save("copy", &original).unwrap().duplicate_of.as_deref(), Some("original"));
+        // 近似重复：同一台机器、相同配置、相差90秒；更早的结果后写入时成为保留项
+        store.save("earlier", &result("2024-05-01T09:59:30Z")).unwrap();
+        let flagged = |store: &ResultStore, id: &str| store.summary(id).unwrap().duplicate_of.clone();
+        assert_eq!(flagged(&store, "earlier"), None);
+        assert_eq!(flagged(&store, "original").as_deref(), Some("earlier"));
+        assert_eq!(flagged(&store, "copy").as_deref(), Some("earlier"));
+
+        // 超出时间窗口、配置不同或没有配置的结果不算重复
+        store.save("later", &result("2024-05-01T10:05:00Z")).unwrap();
+        let mut other_config = result("2024-05-01T10:01:30Z");
+        other_config.config_used.as_mut().unwrap().repeat = 3;
+        store.save("other-config", &other_config).unwrap();
+        let mut no_config = result("2024-05-01T10:01:10Z");
+        no_config.config_used = None;
+        store.save("no-config", &no_config).unwrap();
+        // 同一会话重复运行的各次结果不算重复
+        let mut run1 = result("2024-05-01T11:00:20Z");
+        run1.session_id = Some("session".to_string());
+        let mut run2 = result("2024-05-01T11:00:40Z");
+        run2.session_id = Some("session".to_string());
+        store.save("session-run1", &run1).unwrap();
+        store.save("session-run2", &run2).unwrap();
+        for id in ["later", "other-config", "no-config", "session-run1", "session-run2"] {
+            assert_eq!(flagged(&store, id), None, "{}", id);
+        }
+
+        // 默认不列出重复结果
+        let page = store.query(&HistoryQuery::default()).unwrap();
+        assert_eq!(page.total, 6);
+        assert!(page.entries.iter().all(|s| s.duplicate_of.is_none()));
+        let all = store.query(&HistoryQuery { include_duplicates: true, ..Default::default() }).unwrap();
+        assert_eq!(all.total, 8);
+
+        // 标记写入索引，重新打开后仍然有效
+        drop(store);
+        let mut store = ResultStore::open(dir.path()).unwrap();
+        assert_eq!(flagged(&store, "copy").as_deref(), Some("earlier"));
+
+        // 删除保留项后由组内剩余最早的结果接替，时间相同时取先写入的
+        store.delete("earlier", false).unwrap();
+        assert_eq!(flagged(&store, "original"), None);
+        assert_eq!(flagged(&store, "copy").as_deref(), Some("original"));
+
+        let preview = store.merge_duplicates(true).unwrap();
+        assert_eq!(preview.result_ids, ["copy"]);
+        assert_eq!(store.summaries().len(), 7);
+        let report = store.merge_duplicates(false).unwrap();
+        assert_eq!(report.result_ids, ["copy"]);
+        assert_eq!(store.summaries().len(), 6);
+        assert!(store.summaries().iter().all(|s| s.duplicate_of.is_none()));
+        assert!(store.load("original").is_ok());
+        assert!(store.load("copy").is_err());
+        assert_eq!(store.merge_duplicates(false).unwrap().removed, 0);
+    }
+
+    #[test]
+    fn test_import_older_version_fixture() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = ResultStore::open(dir.path().join("results")).unwrap();
+        let fixture = dir.path().join("v0.json");
+        fs::write(&fixture, include_str!("../../tests/fixtures/result_v0.json")).unwrap();
+
+        let id = store.import_file(&fixture).unwrap();
+        let result = store.load(&id).unwrap();
+        assert_eq!(result.schema_version, crate::benchmark::schema::RESULT_SCHEMA_VERSION);
+        assert_eq!(result.system_info.cpu.vendor, "Unknown");
+        assert_eq!(result.system_info.memory.used, 8);
+        assert!(result.storage_results.is_none());
+        assert_eq!(store.summary(&id).unwrap().headline.cpu_multi_thread, Some(200.0));
+    }
+
+    #[test]
+    fn test_import_corrupt_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = ResultStore::open(dir.path().join("results")).unwrap();
+
+        let truncated = dir.path().join("truncated.json");
+        fs::write(&truncated, "{\"timestamp\": \"2024-01-01T00:00:00Z\", \"system_").unwrap();
+        let err = store.import_file(&truncated).unwrap_err();
+        assert!(matches!(err, BenchmarkError::DataLoadError(ref msg) if msg.contains("JSON")));
+
+        let wrong_shape = dir.path().join("wrong.json");
+        fs::write(&wrong_shape, r#"{"timestamp": "2024-01-01T00:00:00Z", "overall_score": "high"}"#).unwrap();
+        assert!(store.import_file(&wrong_shape).is_err());
+
+        assert!(store.import_file(&dir.path().join("missing.json")).is_err());
+        assert!(store.summaries().is_empty());
+    }
+
+    #[test]
+    fn test_compare_warns_on_engine_version_mismatch() {
+        let thresholds = RegressionThresholds::default();
+        let mut baseline = sample_test_result("2024-01-01T00:00:00Z", 100.0);
+        let mut candidate = sample_test_result("2024-01-02T00:00:00Z", 110.0);
+        candidate.storage_results = None;
+
+        let comparison = ResultComparison::between("a", &baseline, "b", &candidate, &thresholds);
+        let metrics = &comparison.report.metrics;
+        assert_eq!(metrics[0].metric, "overall_score");
+        assert!((metrics[0].change_percent.unwrap() - 10.0).abs() < 1e-9);
+        let storage = metrics.iter().find(|d| d.metric == "storage.sequential_read.throughput").unwrap();
+        assert_eq!(storage.candidate, None);
+        assert_eq!(storage.change_percent, None);
+
+        let mut newer = crate::app_info::AppInfo::new("0.1.0");
+        baseline.app_info = Some(newer.clone());
+        newer.engine_version = "0.2.0".to_string();
+        candidate.app_info = Some(newer);
+        let comparison = ResultComparison::between("a", &baseline, "b", &candidate, &thresholds);
+        assert_eq!(comparison.report.warnings.len(), 1);
+        assert!(comparison.report.warnings[0].contains("0.2.0"));
+
+        candidate.app_info = baseline.app_info.clone();
+        let comparison = ResultComparison::between("a", &baseline, "b", &candidate, &thresholds);
+        assert!(comparison.report.warnings.is_empty());
+        // 报告字段与ID平铺在同一层
+        let value = serde_json::to_value(&comparison).unwrap();
+        assert_eq!(value["baseline_id"], "a");
+        assert_eq!(value["verdict"], "pass");
+    }
+
+    #[test]
+    fn test_rejects_path_like_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = ResultStore::open(dir.path()).unwrap();
+        let result = sample_test_result("2024-01-01T00:00:00Z", 1.0);
+        assert!(store.save("../escape", &result).is_err());
+        assert!(store.load("").is_err());
+    }
+}
@@ -0,0 +1,107 @@
+//! 测试负载共用的伪随机数生成器：随机访问位置、查找顺序等只需要分布均匀且按种子可复现，不需要密码学强度
+//!
+//! Lcg是早期各测试内联的线性同余生成器，只保留用于哈希测试中的对照；其余模块使用Xoshiro256
+
+/// 早期内存和存储测试使用的线性同余生成器。低位周期很短，对2的幂取模时分布很差
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_mul(1103515245).wrapping_add(12345);
+        self.0
+    }
+}
+
+/// xoshiro256**，状态由splitmix64从种子展开，种子为0也能正常工作
+pub struct Xoshiro256([u64; 4]);
+
+impl Xoshiro256 {
+    pub fn new(seed: u64) -> Self {
+        let mut state = seed;
+        let mut splitmix = || {
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        };
+        Self([splitmix(), splitmix(), splitmix(), splitmix()])
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.0;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// [0, bound)内的随机数，用乘法取高位代替取模；bound远小于2^64时偏差可以忽略。bound为0时返回0
+    pub fn below(&mut self, bound: u64) -> u64 {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    /// [0, bound)内的随机下标
+    pub fn index(&mut self, bound: usize) -> usize {
+        self.below(bound as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 自由度255时p=0.001的卡方临界值
+    const CHI_SQUARE_CRITICAL: f64 = 330.5;
+
+    fn byte_chi_square(bytes: impl Iterator<Item = u8>) -> f64 {
+        let mut buckets = [0u64; 256];
+        let mut total = 0u64;
+        for byte in bytes {
+            buckets[byte as usize] += 1;
+            total += 1;
+        }
+        let expected = total as f64 / 256.0;
+        buckets.iter().map(|&count| (count as f64 - expected).powi(2) / expected).sum()
+    }
+
+    #[test]
+    fn test_xoshiro_bytes_are_uniform() {
+        let mut rng = Xoshiro256::new(42);
+        let bytes = (0..1 << 17).flat_map(|_| rng.next_u64().to_le_bytes());
+        let chi_square = byte_chi_square(bytes);
+        assert!(chi_square < CHI_SQUARE_CRITICAL, "chi-square {}", chi_square);
+
+        // 取下标时同样均匀
+        let mut rng = Xoshiro256::new(0);
+        let chi_square = byte_chi_square((0..1 << 20).map(|_| rng.index(256) as u8));
+        assert!(chi_square < CHI_SQUARE_CRITICAL, "chi-square {}", chi_square);
+    }
+
+    #[test]
+    fn test_seeded_sequences_are_reproducible() {
+        let sequence = |seed| {
+            let mut rng = Xoshiro256::new(seed);
+            (0..8).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(sequence(7), sequence(7));
+        assert_ne!(sequence(7), sequence(8));
+        assert!(sequence(0).iter().all(|&value| value != 0));
+
+        let mut rng = Xoshiro256::new(1);
+        assert!((0..10_000).all(|_| rng.below(10) < 10));
+        assert_eq!(rng.below(0), 0);
+
+        let mut lcg = Lcg::new(12345);
+        assert_eq!(lcg.next_u64(), 12345 * 1103515245 + 12345);
+    }
+}
@@ -0,0 +1,208 @@
+//! 结果文档的版本管理：读取旧版本导出的JSON时先升级到当前结构
+use crate::benchmark::core::TestResult;
+use crate::benchmark::error::BenchmarkError;
+use serde_json::{json, Map, Value};
+
+/// 当前结果文档的结构版本，修改`TestResult`及其子结构时递增
+pub const RESULT_SCHEMA_VERSION: u32 = 3;
+
+/// 能够读取并升级的最旧版本，当前版本之前的各个版本都保留升级路径
+pub const MIN_RESULT_SCHEMA_VERSION: u32 = 0;
+
+/// 可以读取的结果文档版本，从旧到新
+pub fn supported_result_versions() -> Vec<u32> {
+    (MIN_RESULT_SCHEMA_VERSION..=RESULT_SCHEMA_VERSION).collect()
+}
+
+/// 将任意受支持版本的结果文档升级并解析为`TestResult`
+pub fn upgrade_result_document(mut document: Value) -> Result<TestResult, BenchmarkError> {
+    let root = document
+        .as_object_mut()
+        .ok_or_else(|| BenchmarkError::DataLoadError("结果文档必须是JSON对象".to_string()))?;
+
+    // 版本0（引入版本号之前的导出）没有schema_version字段
+    let version = match root.get("schema_version") {
+        None => 0,
+        Some(value) => value
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| BenchmarkError::DataLoadError(format!("无效的schema_version: {}", value)))?,
+    };
+    if version > RESULT_SCHEMA_VERSION {
+        return Err(BenchmarkError::DataLoadError(format!(
+            "结果文档版本 {} 高于当前支持的版本 {}，请升级应用后再导入",
+            version, RESULT_SCHEMA_VERSION
+        )));
+    }
+
+    // 逐版本升级，每一步只处理相邻两个版本之间的差异。版本2之前的差异在JSON层面补齐字段
+    if version < 1 {
+        upgrade_v0(root)?;
+    }
+    if version < 2 {
+        upgrade_v1(root);
+        root.insert("schema_version".to_string(), json!(2));
+    }
+
+    let mut result: TestResult = serde_json::from_value(document)
+        .map_err(|e| BenchmarkError::DataLoadError(format!("结果文档不符合版本 {} 的结构: {}", version, e)))?;
+    if version < 3 {
+        upgrade_v2(&mut result);
+    }
+    Ok(result)
+}
+
+/// 版本0 -> 1：系统信息增加了CPU厂商/架构/缓存、内存占用/插槽和系统详情
+fn upgrade_v0(root: &mut Map<String, Value>) -> Result<(), BenchmarkError> {
+    let system_info = root
+        .get_mut("system_info")
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| BenchmarkError::DataLoadError("结果文档缺少system_info".to_string()))?;
+
+    if let Some(cpu) = system_info.get_mut("cpu").and_then(Value::as_object_mut) {
+        insert_missing(cpu, "vendor", json!("Unknown"));
+        insert_missing(cpu, "architecture", json!("Unknown"));
+        insert_missing(
+            cpu,
+            "cache_info",
+            json!({ "l1_data": null, "l1_instruction": null, "l2": null, "l3": null }),
+        );
+    }
+
+    if let Some(memory) = system_info.get_mut("memory").and_then(Value::as_object_mut) {
+        let used = match (
+            memory.get("total").and_then(Value::as_u64),
+            memory.get("available").and_then(Value::as_u64),
+        ) {
+            (Some(total), Some(available)) => total.saturating_sub(available),
+            _ => 0,
+        };
+        insert_missing(memory, "used", json!(used));
+        insert_missing(memory, "slots_used", json!(0));
+        insert_missing(memory, "slots_total", json!(0));
+    }
+
+    insert_missing(
+        system_info,
+        "system_details",
+        json!({
+            "hostname": "Unknown",
+            "uptime": 0,
+            "boot_time": 0,
+            "kernel_version": "Unknown",
+            "total_processes": 0,
+            "temperatures": {}
+        }),
+    );
+    Ok(())
+}
+
+/// 版本1 -> 2：结果增加了自动时长参数、应用版本、失败日志和警告历史
+fn upgrade_v1(root: &mut Map<String, Value>) {
+    insert_missing(root, "imported", json!(false));
+    insert_missing(root, "auto_duration", Value::Null);
+    insert_missing(root, "app_info", Value::Null);
+    insert_missing(root, "failure_log", json!([]));
+    insert_missing(root, "warnings", json!([]));
+}
+
+/// 版本2 -> 3：总体评分改为各部分按参考基准归一化后的加权平均，按新规则重新计算，
+/// 否则与新结果对比时总分的差异只是计算方式不同。
+/// 升级前通过完整性校验的结果重新计算哈希，未通过的保留原校验信息，读取时仍然不通过
+fn upgrade_v2(result: &mut TestResult) {
+    let verified = result.verify_integrity();
+    result.update_scores();
+    result.schema_version = 3;
+    if verified == Some(true) {
+        result.seal();
+    }
+}
+
+fn insert_missing(object: &mut Map<String, Value>, key: &str, value: Value) {
+    object.entry(key.to_string()).or_insert(value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::test_support::sample_test_result;
+
+    #[test]
+    fn test_current_document_round_trips() {
+        let result = sample_test_result("2024-01-01T00:00:00Z", 42.0);
+        let upgraded = upgrade_result_document(serde_json::to_value(&result).unwrap()).unwrap();
+        assert_eq!(upgraded.schema_version, RESULT_SCHEMA_VERSION);
+        assert_eq!(
+            upgraded.system_info.hardware_fingerprint(),
+            result.system_info.hardware_fingerprint()
+        );
+    }
+
+    #[test]
+    fn test_upgrades_previous_versions() {
+        assert_eq!(supported_result_versions(), [0, 1, 2, 3]);
+
+        let v0 = upgrade_result_document(serde_json::from_str(include_str!("../../tests/fixtures/result_v0.json")).unwrap()).unwrap();
+        assert_eq!(v0.schema_version, RESULT_SCHEMA_VERSION);
+        assert_eq!(v0.system_info.cpu.vendor, "Unknown");
+        assert_eq!(v0.system_info.memory.used, 8);
+        assert_eq!(v0.system_info.system_details.hostname, "Unknown");
+        assert!(!v0.imported);
+        assert!(v0.app_info.is_none() && v0.failure_log.is_empty() && v0.warnings.is_empty());
+
+        let v1 = upgrade_result_document(serde_json::from_str(include_str!("../../tests/fixtures/result_v1.json")).unwrap()).unwrap();
+        assert_eq!(v1.schema_version, RESULT_SCHEMA_VERSION);
+        // 版本1已有的字段原样保留
+        assert_eq!(v1.system_info.cpu.vendor, "GenuineIntel");
+        assert_eq!(v1.system_info.memory.slots_total, 4);
+        assert!(v1.imported);
+        assert_eq!(v1.storage_results.as_ref().unwrap().random_read.iops, 12000);
+        assert!(v1.auto_duration.is_none());
+        assert!(v1.app_info.is_none());
+        assert!(v1.failure_log.is_empty() && v1.warnings.is_empty());
+        // 旧结果没有运行上下文字段，按serde默认值读取
+        assert!(v1.config_used.is_none() && v1.session_id.is_none());
+        assert!(v1.per_test_durations.is_empty());
+
+        // 升级后的文档再次读取结果不变
+        let again = upgrade_result_document(serde_json::to_value(&v1).unwrap()).unwrap();
+        assert_eq!(serde_json::to_value(&again).unwrap(), serde_json::to_value(&v1).unwrap());
+    }
+
+    #[test]
+    fn test_v2_scores_are_recomputed_before_comparison() {
+        use crate::benchmark::comparison::{MetricStatus, RegressionThresholds};
+
+        let mut current = sample_test_result("2024-06-01T00:00:00Z", 0.0);
+        current.update_scores();
+        // 版本2的总分是未归一化的各部分平均，成绩相同时也远高于新的总分
+        let mut old = sample_test_result("2024-05-01T00:00:00Z", 3000.0);
+        old.schema_version = 2;
+        old.seal();
+        let mut tampered = serde_json::to_value(&old).unwrap();
+
+        let upgraded = upgrade_result_document(serde_json::to_value(&old).unwrap()).unwrap();
+        assert_eq!(upgraded.schema_version, RESULT_SCHEMA_VERSION);
+        assert_eq!(upgraded.overall_score, current.overall_score);
+        assert_eq!(upgraded.category_scores, current.category_scores);
+        assert_eq!(upgraded.integrity_verified(), Some(true));
+        let report = current.compare(&upgraded, &RegressionThresholds::default());
+        assert_eq!(report.metric("overall_score").unwrap().status, MetricStatus::Unchanged);
+        assert!(report.passed());
+
+        // 升级前未通过校验的结果升级后仍然不通过
+        tampered["cpu_results"]["single_thread_score"] = json!(1.0);
+        let mut upgraded = upgrade_result_document(tampered).unwrap();
+        assert_eq!(upgraded.verify_integrity(), Some(false));
+    }
+
+    #[test]
+    fn test_rejects_newer_and_malformed_documents() {
+        let mut document = serde_json::to_value(sample_test_result("2024-01-01T00:00:00Z", 1.0)).unwrap();
+        document["schema_version"] = json!(RESULT_SCHEMA_VERSION + 1);
+        assert!(upgrade_result_document(document).is_err());
+
+        assert!(upgrade_result_document(json!([1, 2, 3])).is_err());
+        assert!(upgrade_result_document(json!({ "timestamp": "2024-01-01T00:00:00Z" })).is_err());
+    }
+}
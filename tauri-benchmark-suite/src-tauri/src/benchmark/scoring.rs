@@ -0,0 +1,218 @@
+//! 总体评分：各项指标先按内置参考基准归一化（与参考机器相同的成绩得1000分），
+//! 同一部分的指标取平均作为该部分得分，各部分再按配置中的权重加权平均。
+//! 未运行或失败的部分不参与计算，其权重按比例分给其余部分
+use crate::benchmark::compression::{self, CompressionTestResult};
+use crate::benchmark::core::TestResult;
+use crate::benchmark::database::{self, DatabaseTestResult};
+use crate::benchmark::hashing::{self, HashingTestResult};
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+
+/// 与参考机器成绩相同的指标得分
+pub const REFERENCE_SCORE: f64 = 1000.0;
+
+/// 计入总分的各部分，也是score_weights中允许的键
+pub const CATEGORIES: [&str; 6] = ["cpu", "memory", "storage", "gpu", "webview", "network"];
+
+/// 配置中省略的部分的权重
+pub const DEFAULT_WEIGHT: f64 = 1.0;
+
+// 参考机器的成绩：CPU、内存和存储取参考数据中2019 laptop一档。
+// composite导出的映射表对相同的指标使用这里的基准值，两处得分一致
+pub const CPU_SINGLE_THREAD_BASELINE: f64 = 110.0; // points
+pub const CPU_MULTI_THREAD_BASELINE: f64 = 450.0; // points
+pub const CPU_FLOATING_POINT_BASELINE: f64 = 300.0; // points
+const COMPRESSION_BASELINE: f64 = 500.0; // MB/s，压缩和解压的平均
+const HASHING_BASELINE: f64 = 200.0; // 百万次每秒
+pub const MEMORY_SEQUENTIAL_READ_BASELINE: f64 = 1500.0; // MB/s
+pub const MEMORY_SEQUENTIAL_WRITE_BASELINE: f64 = 1200.0; // MB/s
+const MEMORY_SEQUENTIAL_BASELINE: f64 = (MEMORY_SEQUENTIAL_READ_BASELINE + MEMORY_SEQUENTIAL_WRITE_BASELINE) / 2.0;
+pub const STORAGE_SEQUENTIAL_READ_BASELINE: f64 = 1800.0; // MB/s
+pub const STORAGE_SEQUENTIAL_WRITE_BASELINE: f64 = 1200.0; // MB/s
+const STORAGE_SEQUENTIAL_BASELINE: f64 = (STORAGE_SEQUENTIAL_READ_BASELINE + STORAGE_SEQUENTIAL_WRITE_BASELINE) / 2.0;
+const DATABASE_BASELINE: f64 = 50.0; // 千次操作每秒
+pub const GPU_COMPUTE_BASELINE: f64 = 1000.0; // GFLOPS
+pub const GPU_BANDWIDTH_BASELINE: f64 = 100.0; // GB/s
+const WEBVIEW_BASELINE: f64 = 100.0; // 千次操作每秒
+pub const NETWORK_DOWNLOAD_BASELINE: f64 = 500.0; // Mbps
+pub const NETWORK_UPLOAD_BASELINE: f64 = 100.0; // Mbps
+
+fn normalize(value: f64, baseline: f64) -> f64 {
+    value / baseline * REFERENCE_SCORE
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+// 扩展测试的结果以JSON保存，格式不符时视为没有运行
+fn extra_result<T: DeserializeOwned>(result: &TestResult, name: &str) -> Option<T> {
+    result.extra_results.get(name).and_then(|value| serde_json::from_value(value.clone()).ok())
+}
+
+/// 各部分归一化后的得分，未运行的部分不列出。
+/// 压缩和哈希测试与CPU的三项得分一起平均为CPU部分，数据库测试计入存储部分，WebView和网络测试各自单独作为一部分，其余扩展测试不计入
+pub fn category_scores(result: &TestResult) -> BTreeMap<&'static str, f64> {
+    let mut cpu_scores = Vec::new();
+    if let Some(cpu_result) = &result.cpu_results {
+        cpu_scores.extend([
+            normalize(cpu_result.single_thread_score, CPU_SINGLE_THREAD_BASELINE),
+            normalize(cpu_result.multi_thread_score, CPU_MULTI_THREAD_BASELINE),
+            normalize(cpu_result.floating_point_score, CPU_FLOATING_POINT_BASELINE),
+        ]);
+    }
+    if let Some(compression_result) = extra_result::<CompressionTestResult>(result, compression::COMPRESSION_TEST_NAME) {
+        cpu_scores.push(normalize(compression_result.score(), COMPRESSION_BASELINE));
+    }
+    if let Some(hashing_result) = extra_result::<HashingTestResult>(result, hashing::HASHING_TEST_NAME) {
+        cpu_scores.push(normalize(hashing_result.score(), HASHING_BASELINE));
+    }
+
+    let memory_scores: Vec<f64> = result
+        .memory_results
+        .iter()
+        .map(|memory_result| {
+            let sequential = (memory_result.sequential_read_speed + memory_result.sequential_write_speed) / 2.0;
+            normalize(sequential, MEMORY_SEQUENTIAL_BASELINE)
+        })
+        .collect();
+
+    let mut storage_scores = Vec::new();
+    if let Some(storage_result) = &result.storage_results {
+        let sequential = (storage_result.sequential_read.throughput + storage_result.sequential_write.throughput) / 2.0;
+        storage_scores.push(normalize(sequential, STORAGE_SEQUENTIAL_BASELINE));
+    }
+    if let Some(database_result) = extra_result::<DatabaseTestResult>(result, database::DATABASE_TEST_NAME) {
+        storage_scores.push(normalize(database_result.score(), DATABASE_BASELINE));
+    }
+
+    let gpu_scores: Vec<f64> = result
+        .gpu_results
+        .iter()
+        .flat_map(|gpu_result| {
+            [
+                normalize(gpu_result.compute_score, GPU_COMPUTE_BASELINE),
+                normalize(gpu_result.memory_bandwidth_gb_s, GPU_BANDWIDTH_BASELINE),
+            ]
+        })
+        .collect();
+
+    let webview_scores: Vec<f64> = result
+        .webview_results
+        .iter()
+        .map(|webview_result| normalize(webview_result.score(), WEBVIEW_BASELINE))
+        .collect();
+
+    let network_scores: Vec<f64> = result
+        .network_results
+        .iter()
+        .flat_map(|network_result| {
+            [
+                normalize(network_result.download_mbps, NETWORK_DOWNLOAD_BASELINE),
+                normalize(network_result.upload_mbps, NETWORK_UPLOAD_BASELINE),
+            ]
+        })
+        .collect();
+
+    CATEGORIES
+        .into_iter()
+        .zip([cpu_scores, memory_scores, storage_scores, gpu_scores, webview_scores, network_scores])
+        .filter_map(|(category, scores)| mean(&scores).map(|score| (category, score)))
+        .collect()
+}
+
+/// 按权重加权平均各部分得分，weights中没有的部分权重为1；
+/// 只对已有得分的部分归一化权重，全部权重为0时总分为0
+pub fn weighted_score(scores: &BTreeMap<&'static str, f64>, weights: &BTreeMap<String, f64>) -> f64 {
+    let (sum, total_weight) = scores.iter().fold((0.0, 0.0), |(sum, total_weight), (category, score)| {
+        let weight = weights.get(*category).copied().unwrap_or(DEFAULT_WEIGHT);
+        (sum + score * weight, total_weight + weight)
+    });
+    if total_weight > 0.0 {
+        sum / total_weight
+    } else {
+        0.0
+    }
+}
+
+/// 按结果中记录的配置权重计算总体评分，没有配置的旧结果各部分等权
+pub fn calculate_overall_score(result: &TestResult) -> f64 {
+    let weights = result.config_used.as_ref().map(|config| &config.score_weights);
+    weighted_score(&category_scores(result), weights.unwrap_or(&BTreeMap::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::core::BenchmarkConfig;
+    use crate::benchmark::ranking::ReferenceDataset;
+    use crate::benchmark::test_support::sample_test_result;
+
+    #[test]
+    fn test_reference_machine_scores_1000_per_category() {
+        let mut result = sample_test_result("2024-01-01T00:00:00Z", 0.0);
+        let cpu = result.cpu_results.as_mut().unwrap();
+        cpu.single_thread_score = CPU_SINGLE_THREAD_BASELINE;
+        cpu.multi_thread_score = CPU_MULTI_THREAD_BASELINE;
+        cpu.floating_point_score = CPU_FLOATING_POINT_BASELINE;
+        let memory = result.memory_results.as_mut().unwrap();
+        memory.sequential_read_speed = MEMORY_SEQUENTIAL_READ_BASELINE;
+        memory.sequential_write_speed = MEMORY_SEQUENTIAL_WRITE_BASELINE;
+        let storage = result.storage_results.as_mut().unwrap();
+        storage.sequential_read.throughput = STORAGE_SEQUENTIAL_READ_BASELINE;
+        storage.sequential_write.throughput = STORAGE_SEQUENTIAL_WRITE_BASELINE;
+
+        let scores = category_scores(&result);
+        assert_eq!(scores.keys().copied().collect::<Vec<_>>(), ["cpu", "memory", "storage"]);
+        assert!(scores.values().all(|score| (score - REFERENCE_SCORE).abs() < 1e-9), "{:?}", scores);
+        assert!((calculate_overall_score(&result) - REFERENCE_SCORE).abs() < 1e-9);
+
+        // 内存快一倍只让内存部分翻倍，不会压过其余部分
+        let memory = result.memory_results.as_mut().unwrap();
+        memory.sequential_read_speed *= 2.0;
+        memory.sequential_write_speed *= 2.0;
+        assert!((calculate_overall_score(&result) - 4000.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_baselines_match_embedded_reference_tier() {
+        let dataset = ReferenceDataset::embedded().unwrap();
+        let tier = dataset.tiers.iter().find(|tier| tier.id == "modern_laptop").unwrap();
+        assert_eq!(tier.metrics["overall_score"], REFERENCE_SCORE);
+        for (metric, baseline) in [
+            ("cpu_single_thread", CPU_SINGLE_THREAD_BASELINE),
+            ("cpu_multi_thread", CPU_MULTI_THREAD_BASELINE),
+            ("memory_read", MEMORY_SEQUENTIAL_READ_BASELINE),
+            ("memory_write", MEMORY_SEQUENTIAL_WRITE_BASELINE),
+            ("storage_read", STORAGE_SEQUENTIAL_READ_BASELINE),
+            ("storage_write", STORAGE_SEQUENTIAL_WRITE_BASELINE),
+        ] {
+            assert_eq!(tier.metrics[metric], baseline, "{}", metric);
+        }
+    }
+
+    #[test]
+    fn test_weights_are_renormalized_over_present_categories() {
+        let scores = BTreeMap::from([("cpu", 1200.0), ("memory", 600.0)]);
+        assert_eq!(weighted_score(&scores, &BTreeMap::new()), 900.0);
+
+        let weights = BTreeMap::from([("cpu".to_string(), 3.0), ("memory".to_string(), 1.0), ("gpu".to_string(), 10.0)]);
+        // GPU没有结果，其权重不计入；cpu:memory = 3:1
+        assert_eq!(weighted_score(&scores, &weights), 1050.0);
+        let weights = BTreeMap::from([("memory".to_string(), 0.0)]);
+        assert_eq!(weighted_score(&scores, &weights), 1200.0);
+        let weights = BTreeMap::from([("cpu".to_string(), 0.0), ("memory".to_string(), 0.0)]);
+        assert_eq!(weighted_score(&scores, &weights), 0.0);
+        assert_eq!(weighted_score(&BTreeMap::new(), &BTreeMap::new()), 0.0);
+
+        // 总分使用结果中记录的配置权重
+        let mut result = sample_test_result("2024-01-01T00:00:00Z", 0.0);
+        result.storage_results = None;
+        let equal = calculate_overall_score(&result);
+        let mut config = BenchmarkConfig::default();
+        config.score_weights.insert("memory".to_string(), 0.0);
+        result.config_used = Some(config);
+        assert!((calculate_overall_score(&result) - category_scores(&result)["cpu"]).abs() < 1e-9);
+        assert_ne!(calculate_overall_score(&result), equal);
+    }
+}
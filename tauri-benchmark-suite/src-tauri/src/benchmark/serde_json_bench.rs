@@ -0,0 +1,277 @@
+//! JSON编码和解析吞吐：用serde_json处理三种由种子生成的文档（扁平的数值遥测、深层嵌套的配置、
+//! 大量字符串组成的数组），测量每种文档的MB/s和每秒文档数。Tauri前后端之间的数据都以JSON传递
+use crate::benchmark::compression::CorpusRng;
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, TestOutcome};
+use crate::benchmark::error::BenchmarkError;
+use crate::i18n::Message;
+use crate::ipc::{ExtraTestResult, TestResultPayload, TestType};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::time::Instant;
+
+/// 在BenchmarkRegistry中的注册名，结果记录在extra_results下的同名键中
+pub const JSON_TEST_NAME: &str = "json";
+
+const NESTING_DEPTH: usize = 16; // 配置文档中每个分节的嵌套层数
+const ESTIMATED_BYTES_PER_SECOND: f64 = 100.0 * 1024.0 * 1024.0; // 估计耗时用，编码和解析合计
+const WORDS: [&str; 12] = [
+    "tauri", "window", "invoke", "event", "plugin", "状态", "配置", "naïve", "quote\"d", "line\nbreak", "tab\tstop", "emoji🚀",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentShape {
+    Telemetry, // 对象数组，每个对象是若干数值字段
+    NestedConfig, // 多个分节，每节嵌套16层，混合布尔、数值、字符串和短数组
+    StringArray, // 长短不一的字符串，包含需要转义的字符和非ASCII字符
+}
+
+impl DocumentShape {
+    pub const ALL: [DocumentShape; 3] = [DocumentShape::Telemetry, DocumentShape::NestedConfig, DocumentShape::StringArray];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DocumentShape::Telemetry => "telemetry",
+            DocumentShape::NestedConfig => "nested_config",
+            DocumentShape::StringArray => "string_array",
+        }
+    }
+}
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct JsonTestConfig {
+    #[serde(alias = "documentSize")]
+    pub document_size: usize, // KB，每种文档至少达到该大小
+    pub iterations: usize, // 每种文档编码和解析的次数
+    pub seed: u64,
+    pub shapes: Vec<DocumentShape>,
+}
+
+impl Default for JsonTestConfig {
+    fn default() -> Self {
+        Self {
+            document_size: 1024,
+            iterations: 50,
+            seed: 0x5EED,
+            shapes: DocumentShape::ALL.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ShapeResult {
+    pub shape: DocumentShape,
+    pub document_bytes: usize,
+    pub parse_throughput: f64, // MB/s
+    pub stringify_throughput: f64, // MB/s
+    pub parse_docs_per_second: f64,
+    pub stringify_docs_per_second: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct JsonTestResult {
+    pub shapes: Vec<ShapeResult>,
+    pub test_duration: f64, // seconds
+}
+
+// 两位小数的数值：短十进制表示在解析后与原值完全相同，往返比较不受浮点格式影响
+fn decimal(rng: &mut CorpusRng, max: u64) -> f64 {
+    (rng.next() % (max * 100)) as f64 / 100.0
+}
+
+fn word(rng: &mut CorpusRng) -> &'static str {
+    WORDS[(rng.next() % WORDS.len() as u64) as usize]
+}
+
+fn telemetry_sample(rng: &mut CorpusRng, index: usize) -> Value {
+    json!({
+        "t": 1_700_000_000_000u64 + index as u64 * 250,
+        "cpu": decimal(rng, 100),
+        "mem": decimal(rng, 65536),
+        "read_mb_s": decimal(rng, 4000),
+        "write_mb_s": decimal(rng, 4000),
+        "temp_c": decimal(rng, 110),
+        "freq_mhz": rng.next() % 6000,
+        "threads": rng.next() % 256,
+    })
+}
+
+fn config_section(rng: &mut CorpusRng, index: usize) -> Value {
+    let mut node = json!({ "leaf": true, "value": decimal(rng, 1000) });
+    for depth in (0..NESTING_DEPTH).rev() {
+        node = json!({
+            "name": format!("section_{}_{}", index, depth),
+            "enabled": rng.next().is_multiple_of(2),
+            "threshold": decimal(rng, 100),
+            "tags": [word(rng), word(rng)],
+            "child": node,
+        });
+    }
+    node
+}
+
+fn string_entry(rng: &mut CorpusRng) -> Value {
+    let words = 2 + rng.next() % 30;
+    let text: Vec<&str> = (0..words).map(|_| word(rng)).collect();
+    Value::String(text.join(" "))
+}
+
+/// 生成至少target_bytes字节（编码后）的文档，同一种子和形状总是生成相同的文档
+pub fn generate_document(shape: DocumentShape, target_bytes: usize, seed: u64) -> Value {
+    let mut rng = CorpusRng::new(seed ^ shape as u64);
+    let mut items = Vec::new();
+    let mut bytes = 2; // 外层的括号
+    while bytes < target_bytes {
+        let item = match shape {
+            DocumentShape::Telemetry => telemetry_sample(&mut rng, items.len()),
+            DocumentShape::NestedConfig => config_section(&mut rng, items.len()),
+            DocumentShape::StringArray => string_entry(&mut rng),
+        };
+        bytes += serde_json::to_vec(&item).map(|encoded| encoded.len()).unwrap_or(0) + 1;
+        items.push(item);
+    }
+    match shape {
+        DocumentShape::NestedConfig => Value::Object(
+            items
+                .into_iter()
+                .enumerate()
+                .map(|(index, section)| (format!("section_{}", index), section))
+                .collect::<Map<String, Value>>(),
+        ),
+        _ => Value::Array(items),
+    }
+}
+
+pub struct JsonBenchmark {
+    config: JsonTestConfig,
+}
+
+impl JsonBenchmark {
+    pub fn new(config: JsonTestConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn get_config(&self) -> &JsonTestConfig {
+        &self.config
+    }
+
+    pub fn run_benchmark(&self) -> Result<JsonTestResult, BenchmarkError> {
+        self.run_benchmark_cancellable(|_progress, _message| {}, &CancellationToken::never())
+    }
+
+    /// 每种文档先验证一次往返结果与原文档相同，再分别计时编码和解析
+    pub fn run_benchmark_cancellable<F>(&self, progress_callback: F, cancellation: &CancellationToken) -> Result<JsonTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message),
+    {
+        let started = Instant::now();
+        let iterations = self.config.iterations.max(1);
+        let shapes = self.config.shapes.len().max(1);
+        let check_interval = (iterations / 10).max(1);
+        let mut results = Vec::with_capacity(shapes);
+        for (index, shape) in self.config.shapes.iter().copied().enumerate() {
+            let base = index as f64 / shapes as f64 * 100.0;
+            cancellation.check(TestType::Extra, base)?;
+            progress_callback(base, Message::progress("json.progress", base).param("shape", shape.as_str()));
+
+            let document = generate_document(shape, self.config.document_size * 1024, self.config.seed);
+            let encoded = serde_json::to_vec(&document).map_err(|e| BenchmarkError::JsonTestError(e.to_string()))?;
+            let decoded: Value = serde_json::from_slice(&encoded).map_err(|e| BenchmarkError::JsonTestError(e.to_string()))?;
+            if decoded != document {
+                return Err(BenchmarkError::JsonTestError(format!("{}文档往返后与原文档不一致", shape.as_str())));
+            }
+
+            let mut stringify_seconds = 0.0;
+            let mut parse_seconds = 0.0;
+            for iteration in 0..iterations {
+                if iteration % check_interval == 0 {
+                    cancellation.check(TestType::Extra, base + iteration as f64 / iterations as f64 * 100.0 / shapes as f64)?;
+                }
+                let timer = Instant::now();
+                let output = serde_json::to_vec(&document).map_err(|e| BenchmarkError::JsonTestError(e.to_string()))?;
+                stringify_seconds += timer.elapsed().as_secs_f64();
+
+                let timer = Instant::now();
+                let parsed: Value = serde_json::from_slice(&output).map_err(|e| BenchmarkError::JsonTestError(e.to_string()))?;
+                parse_seconds += timer.elapsed().as_secs_f64();
+
+                if output.len() != encoded.len() {
+                    return Err(BenchmarkError::JsonTestError(format!("{}文档的编码结果不稳定", shape.as_str())));
+                }
+                std::hint::black_box(parsed);
+            }
+
+            let megabytes = (encoded.len() * iterations) as f64 / (1024.0 * 1024.0);
+            results.push(ShapeResult {
+                shape,
+                document_bytes: encoded.len(),
+                parse_throughput: megabytes / parse_seconds,
+                stringify_throughput: megabytes / stringify_seconds,
+                parse_docs_per_second: iterations as f64 / parse_seconds,
+                stringify_docs_per_second: iterations as f64 / stringify_seconds,
+            });
+        }
+        progress_callback(100.0, Message::new("json.complete"));
+        Ok(JsonTestResult {
+            shapes: results,
+            test_duration: started.elapsed().as_secs_f64(),
+        })
+    }
+}
+
+impl BenchmarkRunner for JsonBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Extra
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        let bytes = (self.config.document_size * 1024 * self.config.iterations.max(1) * self.config.shapes.len()) as f64;
+        (bytes / ESTIMATED_BYTES_PER_SECOND).ceil().max(1.0) as u64
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        let result = self.run_benchmark_cancellable(ctx.progress_callback(), &ctx.cancellation)?;
+        Ok(TestOutcome::Completed(TestResultPayload::Extra(ExtraTestResult {
+            name: JSON_TEST_NAME.to_string(),
+            result: serde_json::to_value(&result)
+                .map_err(|e| BenchmarkError::DataSaveError(format!("测试结果序列化失败: {}", e)))?,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_documents_are_deterministic() {
+        let sizes: Vec<usize> = DocumentShape::ALL
+            .iter()
+            .map(|shape| serde_json::to_vec(&generate_document(*shape, 16 * 1024, 0x5EED)).unwrap().len())
+            .collect();
+        // 生成规则或serde_json的输出格式变化时这里会失败，此时旧结果的吞吐不再可比
+        assert_eq!(sizes, [16410, 16969, 16396]);
+        assert_eq!(generate_document(DocumentShape::StringArray, 4096, 1), generate_document(DocumentShape::StringArray, 4096, 1));
+        assert_ne!(generate_document(DocumentShape::StringArray, 4096, 1), generate_document(DocumentShape::StringArray, 4096, 2));
+    }
+
+    #[test]
+    fn test_parse_and_stringify_throughput() {
+        let config = JsonTestConfig {
+            document_size: 64,
+            iterations: 5,
+            ..JsonTestConfig::default()
+        };
+        let result = JsonBenchmark::new(config).run_benchmark().unwrap();
+        assert_eq!(result.shapes.len(), 3);
+        for shape in &result.shapes {
+            assert!(shape.document_bytes >= 64 * 1024);
+            assert!(shape.parse_throughput > 0.0 && shape.parse_docs_per_second > 0.0, "{:?}", shape);
+            // 编码通常快于解析，未优化的构建和繁忙的CI机器上也不会慢到解析的五分之一以下
+            assert!(shape.stringify_throughput >= shape.parse_throughput * 0.2, "{:?}", shape);
+        }
+    }
+}
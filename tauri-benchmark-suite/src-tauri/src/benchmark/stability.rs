@@ -0,0 +1,407 @@
+//! 稳定性压力测试：所有逻辑核心持续运行浮点测试的计算核心并逐位校验结果，同时一个线程反复写入和校验
+//! 一大块内存，期间记录温度和频率。用于超频或降压后确认系统在长时间满载下不出计算或内存错误
+use crate::benchmark::copy::detect_throttling;
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, TestOutcome};
+use crate::benchmark::cpu::{floating_point_kernel, FLOATING_POINT_KERNEL_OPERATIONS};
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::metrics::CpuSensors;
+use crate::i18n::Message;
+use crate::ipc::{ExtraTestResult, TestResultPayload, TestType, WarningSeverity};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use sysinfo::System;
+
+/// 在BenchmarkRegistry中的注册名，结果记录在extra_results下的同名键中
+pub const STABILITY_TEST_NAME: &str = "stability";
+
+const REFERENCE_INPUTS: usize = 16; // 浮点线程轮换使用的输入数，每个输入的参考结果在开始前计算
+const MEMORY_CHUNK_WORDS: usize = 128 * 1024; // 内存线程每处理这么多个字（1MB）检查一次停止标志
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(500); // 温度和频率的采样间隔
+const MAX_RECORDED_FAULTS: usize = 100; // 结果中保留的错误详情数，错误计数不受此限制
+const AUTO_BUFFER_RATIO: f64 = 0.25; // 自动选择时使用可用内存的比例
+const MAX_BUFFER_RATIO: f64 = 0.5; // 指定的缓冲区不超过可用内存的比例，避免系统开始换页
+const MAX_AUTO_BUFFER_MB: usize = 4096;
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct StabilityTestConfig {
+    pub duration: u64, // seconds
+    #[serde(alias = "threadCount")]
+    pub thread_count: usize, // 运行浮点核心的线程数，0表示每个逻辑核心一个
+    #[serde(alias = "bufferSize")]
+    pub buffer_size: usize, // MB，0表示按可用内存自动选择
+    #[serde(alias = "temperatureLimit")]
+    pub temperature_limit: f64, // °C，达到时视为过热降频
+}
+
+impl Default for StabilityTestConfig {
+    fn default() -> Self {
+        Self {
+            duration: 300,
+            thread_count: 0,
+            buffer_size: 0,
+            temperature_limit: 95.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StabilityFaultKind {
+    Computation, // 浮点结果与参考值不同
+    Memory, // 读回的内存与写入的模式不同
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct StabilityFault {
+    pub kind: StabilityFaultKind,
+    pub at_seconds: f64, // 距测试开始的时间
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StabilityVerdict {
+    Pass, // 整个测试期间没有发现错误
+    Fail,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct StabilityTestResult {
+    pub verdict: StabilityVerdict,
+    pub thread_count: usize,
+    pub buffer_size: usize, // MB，按可用内存调整后的实际大小
+    pub kernel_iterations: u64, // 所有浮点线程完成并校验的计算次数
+    pub kernel_operations_per_second: f64,
+    pub memory_passes: u64, // 完整写入并校验整个缓冲区的轮数
+    pub computation_errors: u64,
+    pub memory_errors: u64,
+    pub faults: Vec<StabilityFault>, // 按发生顺序，最多保留前100条
+    pub max_temperature: Option<f64>, // °C，读不到CPU温度传感器时为空
+    pub min_frequency_mhz: f64,
+    pub max_frequency_mhz: f64,
+    pub throttled: bool, // 后期频率明显低于初期，或温度达到temperature_limit
+    pub test_duration: f64, // seconds
+}
+
+/// 实际使用的缓冲区大小（MB）：自动时取可用内存的四分之一且不超过4GB，指定时不超过可用内存的一半，至少1MB
+pub fn buffer_size_mb(requested: usize, available_mb: u64) -> usize {
+    let limit = (available_mb as f64 * MAX_BUFFER_RATIO) as usize;
+    let size = if requested == 0 {
+        ((available_mb as f64 * AUTO_BUFFER_RATIO) as usize).min(MAX_AUTO_BUFFER_MB)
+    } else {
+        requested.min(limit)
+    };
+    size.max(1)
+}
+
+fn reference_input(index: usize) -> f64 {
+    1.0 + index as f64 * 0.125
+}
+
+// 每轮交替使用固定的位模式和与地址相关的模式，后者能发现地址线错误
+fn pattern_word(pass: u64, index: usize) -> u64 {
+    let index = index as u64;
+    match pass % 4 {
+        0 => 0x5555_5555_5555_5555,
+        1 => 0xAAAA_AAAA_AAAA_AAAA,
+        2 => index.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ pass,
+        _ => !index,
+    }
+}
+
+// 各线程共享的错误记录
+struct FaultLog {
+    started: Instant,
+    computation: AtomicU64,
+    memory: AtomicU64,
+    faults: Mutex<Vec<StabilityFault>>,
+}
+
+impl FaultLog {
+    fn new(started: Instant) -> Self {
+        Self {
+            started,
+            computation: AtomicU64::new(0),
+            memory: AtomicU64::new(0),
+            faults: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn record(&self, kind: StabilityFaultKind, detail: impl FnOnce() -> String) {
+        let counter = match kind {
+            StabilityFaultKind::Computation => &self.computation,
+            StabilityFaultKind::Memory => &self.memory,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        let mut faults = self.faults.lock().unwrap();
+        if faults.len() < MAX_RECORDED_FAULTS {
+            faults.push(StabilityFault {
+                kind,
+                at_seconds: self.started.elapsed().as_secs_f64(),
+                detail: detail(),
+            });
+        }
+    }
+
+    fn total(&self) -> u64 {
+        self.computation.load(Ordering::Relaxed) + self.memory.load(Ordering::Relaxed)
+    }
+}
+
+// 循环计算浮点核心，结果与参考值逐位比较；不同线程从不同的输入开始
+fn kernel_worker(worker: usize, references: &[f64], stop: &AtomicBool, iterations: &AtomicU64, log: &FaultLog) {
+    let mut round = worker;
+    while !stop.load(Ordering::Relaxed) {
+        let index = round % REFERENCE_INPUTS;
+        let value = floating_point_kernel(reference_input(index));
+        if value.to_bits() != references[index].to_bits() {
+            log.record(StabilityFaultKind::Computation, || {
+                format!("线程{}对输入{}的计算结果为{:e}，应为{:e}", worker, reference_input(index), value, references[index])
+            });
+        }
+        iterations.fetch_add(1, Ordering::Relaxed);
+        round += 1;
+    }
+}
+
+// 每轮先写满整个缓冲区再逐字校验；停止标志在每1MB之间检查，中途停止的一轮不计入
+fn memory_worker(buffer: &mut [u64], stop: &AtomicBool, passes: &AtomicU64, log: &FaultLog) {
+    let mut pass = 0u64;
+    'passes: loop {
+        for (chunk_index, chunk) in buffer.chunks_mut(MEMORY_CHUNK_WORDS).enumerate() {
+            if stop.load(Ordering::Relaxed) {
+                break 'passes;
+            }
+            let base = chunk_index * MEMORY_CHUNK_WORDS;
+            for (offset, word) in chunk.iter_mut().enumerate() {
+                *word = pattern_word(pass, base + offset);
+            }
+        }
+        // 阻止编译器根据刚写入的值省略下面的读取
+        let buffer = std::hint::black_box(&mut *buffer);
+        for (chunk_index, chunk) in buffer.chunks(MEMORY_CHUNK_WORDS).enumerate() {
+            if stop.load(Ordering::Relaxed) {
+                break 'passes;
+            }
+            let base = chunk_index * MEMORY_CHUNK_WORDS;
+            for (offset, &actual) in chunk.iter().enumerate() {
+                let expected = pattern_word(pass, base + offset);
+                if actual != expected {
+                    log.record(StabilityFaultKind::Memory, || {
+                        format!("第{}轮在偏移{:#x}处读到{:#018x}，应为{:#018x}", pass + 1, (base + offset) * 8, actual, expected)
+                    });
+                }
+            }
+        }
+        passes.fetch_add(1, Ordering::Relaxed);
+        pass += 1;
+    }
+}
+
+pub struct StabilityBenchmark {
+    config: StabilityTestConfig,
+}
+
+impl StabilityBenchmark {
+    pub fn new(config: StabilityTestConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn get_config(&self) -> &StabilityTestConfig {
+        &self.config
+    }
+
+    pub fn run_benchmark(&self) -> Result<StabilityTestResult, BenchmarkError> {
+        self.run_benchmark_cancellable(|_progress, _message| {}, &CancellationToken::never())
+    }
+
+    /// 压力线程在后台运行，当前线程采样温度和频率并检查取消；取消时先停止并等待所有压力线程，再返回Cancelled
+    pub fn run_benchmark_cancellable<F>(&self, progress_callback: F, cancellation: &CancellationToken) -> Result<StabilityTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message),
+    {
+        let thread_count = match self.config.thread_count {
+            0 => thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+            count => count,
+        };
+        let mut sys = System::new();
+        sys.refresh_memory();
+        let buffer_size = buffer_size_mb(self.config.buffer_size, sys.available_memory() / (1024 * 1024));
+        let words = buffer_size * 1024 * 1024 / std::mem::size_of::<u64>();
+        let mut buffer: Vec<u64> = Vec::new();
+        buffer
+            .try_reserve_exact(words)
+            .map_err(|e| BenchmarkError::StabilityTestError(format!("无法分配{}MB内存: {}", buffer_size, e)))?;
+        buffer.resize(words, 0);
+
+        let references: Vec<f64> = (0..REFERENCE_INPUTS).map(|index| floating_point_kernel(reference_input(index))).collect();
+        let duration = Duration::from_secs(self.config.duration.max(1));
+        progress_callback(
+            0.0,
+            Message::new("stability.start").param("threads", thread_count).param("buffer_mb", buffer_size),
+        );
+
+        let started = Instant::now();
+        let log = FaultLog::new(started);
+        let stop = AtomicBool::new(false);
+        let iterations = AtomicU64::new(0);
+        let passes = AtomicU64::new(0);
+        let mut sensors = CpuSensors::new();
+        let mut frequencies = Vec::new();
+        let mut max_temperature: Option<f64> = None;
+
+        let outcome = thread::scope(|scope| {
+            for worker in 0..thread_count {
+                let (references, stop, iterations, log) = (&references, &stop, &iterations, &log);
+                scope.spawn(move || kernel_worker(worker, references, stop, iterations, log));
+            }
+            let memory = &mut buffer;
+            let (stop_ref, passes_ref, log_ref) = (&stop, &passes, &log);
+            scope.spawn(move || memory_worker(memory, stop_ref, passes_ref, log_ref));
+
+            let mut outcome = Ok(());
+            while started.elapsed() < duration {
+                let progress = started.elapsed().as_secs_f64() / duration.as_secs_f64() * 100.0;
+                if let Err(e) = cancellation.check(TestType::Extra, progress) {
+                    outcome = Err(e);
+                    break;
+                }
+                progress_callback(progress, Message::progress("stability.progress", progress).param("errors", log.total()));
+                let (frequency_mhz, temperature_c) = sensors.read();
+                if frequency_mhz > 0.0 {
+                    frequencies.push(frequency_mhz);
+                }
+                if let Some(temperature) = temperature_c {
+                    max_temperature = Some(max_temperature.map_or(temperature, |max| max.max(temperature)));
+                }
+                thread::sleep(SAMPLE_INTERVAL.min(duration.saturating_sub(started.elapsed())));
+            }
+            stop.store(true, Ordering::Relaxed);
+            outcome
+        });
+        outcome?;
+        let test_duration = started.elapsed().as_secs_f64();
+
+        let computation_errors = log.computation.load(Ordering::Relaxed);
+        let memory_errors = log.memory.load(Ordering::Relaxed);
+        let kernel_iterations = iterations.load(Ordering::Relaxed);
+        let overheated = max_temperature.is_some_and(|temperature| temperature >= self.config.temperature_limit);
+        progress_callback(100.0, Message::new("stability.complete"));
+        Ok(StabilityTestResult {
+            verdict: if computation_errors + memory_errors == 0 { StabilityVerdict::Pass } else { StabilityVerdict::Fail },
+            thread_count,
+            buffer_size,
+            kernel_iterations,
+            kernel_operations_per_second: (kernel_iterations * FLOATING_POINT_KERNEL_OPERATIONS) as f64 / test_duration,
+            memory_passes: passes.load(Ordering::Relaxed),
+            computation_errors,
+            memory_errors,
+            faults: log.faults.into_inner().unwrap(),
+            max_temperature,
+            min_frequency_mhz: frequencies.iter().copied().reduce(f64::min).unwrap_or(0.0),
+            max_frequency_mhz: frequencies.iter().copied().reduce(f64::max).unwrap_or(0.0),
+            throttled: overheated || detect_throttling(&frequencies),
+            test_duration,
+        })
+    }
+}
+
+impl BenchmarkRunner for StabilityBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Extra
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        self.config.duration.max(1)
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        let result = self.run_benchmark_cancellable(ctx.progress_callback(), &ctx.cancellation)?;
+        // 降频作为运行期间的热警告上报；发现错误时测试记为"完成但有问题"
+        if result.throttled {
+            ctx.report(Message::new("stability.thermal_throttling"), WarningSeverity::High);
+        }
+        if result.verdict == StabilityVerdict::Fail {
+            ctx.report(
+                Message::new("stability.errors_detected")
+                    .param("computation", result.computation_errors)
+                    .param("memory", result.memory_errors),
+                WarningSeverity::Critical,
+            );
+        }
+        Ok(TestOutcome::Completed(TestResultPayload::Extra(ExtraTestResult {
+            name: STABILITY_TEST_NAME.to_string(),
+            result: serde_json::to_value(&result)
+                .map_err(|e| BenchmarkError::DataSaveError(format!("测试结果序列化失败: {}", e)))?,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_buffer_size_follows_available_memory() {
+        assert_eq!(buffer_size_mb(0, 8192), 2048);
+        assert_eq!(buffer_size_mb(0, 64 * 1024), MAX_AUTO_BUFFER_MB);
+        assert_eq!(buffer_size_mb(6000, 8192), 4096);
+        assert_eq!(buffer_size_mb(64, 8192), 64);
+        assert_eq!(buffer_size_mb(64, 0), 1);
+    }
+
+    #[test]
+    fn test_short_stress_run_is_stable() {
+        let config = StabilityTestConfig {
+            duration: 3,
+            thread_count: 2,
+            buffer_size: 8,
+            ..StabilityTestConfig::default()
+        };
+        let result = StabilityBenchmark::new(config).run_benchmark().unwrap();
+        assert_eq!(result.verdict, StabilityVerdict::Pass);
+        assert_eq!((result.computation_errors, result.memory_errors), (0, 0));
+        assert!(result.faults.is_empty());
+        assert_eq!((result.thread_count, result.buffer_size), (2, 8));
+        assert!(result.kernel_iterations > 0 && result.memory_passes > 0, "{:?}", result);
+        assert!(result.test_duration >= 3.0);
+
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(value["verdict"], "pass");
+        assert!(value["faults"].as_array().unwrap().is_empty());
+        assert!(value.get("throttled").unwrap().is_boolean());
+    }
+
+    #[test]
+    fn test_cancellation_stops_workers() {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+        let token = CancellationToken::new(move || flag.load(Ordering::SeqCst));
+        let config = StabilityTestConfig {
+            duration: 600,
+            thread_count: 1,
+            buffer_size: 4,
+            ..StabilityTestConfig::default()
+        };
+        let started = Instant::now();
+        let error = StabilityBenchmark::new(config)
+            .run_benchmark_cancellable(
+                move |progress, _message| {
+                    if progress > 0.0 {
+                        cancelled.store(true, Ordering::SeqCst);
+                    }
+                },
+                &token,
+            )
+            .unwrap_err();
+        assert_eq!(error.code(), "CANCELLED");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -1,12 +1,29 @@
+use crate::benchmark::calibration::Calibration;
+use crate::benchmark::compression::CorpusRng;
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, TestOutcome};
 use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::archive::{run_archive_test, ArchiveMetrics, ArchiveTestConfig};
+use crate::benchmark::estimate::{archive_test_seconds, available_disk_bytes, small_file_test_seconds, storage_test_seconds};
+use crate::benchmark::metrics::{percentile, LiveSample, MetricsSink, RateMeter, RollingLatency};
+use crate::benchmark::rng::Xoshiro256;
+use crate::i18n::Message;
+use crate::ipc::{TestResultPayload, TestType, WarningSeverity};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::fs::{File, OpenOptions};
 use std::io::{Read, Seek, SeekFrom, Write};
-use std::path::PathBuf;
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+const SMALL_FILES_PER_DIR: usize = 100; // 小文件测试每个子目录中的文件数
+const SMALL_FILE_MIN_BYTES: usize = 1024;
+const SMALL_FILE_MAX_BYTES: usize = 4096;
+const SMALL_FILE_SEED: u64 = 0x5EED;
+const SMALL_FILE_PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+const SMALL_FILE_HEADROOM: u64 = 2; // 可用空间和inode至少是小文件测试所需的这么多倍
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct StorageTestResult {
     pub sequential_read: StorageMetrics,
     pub sequential_write: StorageMetrics,
@@ -14,30 +31,228 @@ pub struct StorageTestResult {
     pub random_write: StorageMetrics,
     pub test_duration: u64,        // seconds
     pub total_data_processed: u64, // MB
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub small_files: Option<SmallFileMetrics>, // 小文件测试被关闭时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive: Option<ArchiveMetrics>, // 未启用压缩包解压测试时为空
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
 pub struct StorageTestConfig {
+    #[serde(alias = "fileSize")]
     pub file_size: u64,                 // MB
+    #[serde(alias = "blockSize")]
     pub block_size: usize,              // KB
+    #[serde(alias = "testDuration")]
     pub test_duration: u64,             // seconds
+    #[serde(alias = "testFilePath")]
     pub test_file_path: Option<String>, // 可选的测试文件路径
+    #[serde(alias = "smallFileCount")]
+    pub small_file_count: usize,        // 小文件测试的文件数，为0时跳过
+    pub archive: Option<ArchiveTestConfig>, // 压缩包解压测试，为空时跳过
+}
+
+impl Default for StorageTestConfig {
+    fn default() -> Self {
+        Self {
+            file_size: 1024, // 1GB
+            block_size: 4,   // 4KB
+            test_duration: 60,
+            test_file_path: None,
+            small_file_count: 10_000,
+            archive: None,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct StorageMetrics {
     pub throughput: f64, // MB/s
     pub iops: u64,
     pub latency: f64, // milliseconds
 }
 
+/// 小文件测试的单个阶段，延迟单位为毫秒
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SmallFilePhase {
+    pub files: usize, // 该阶段处理的文件数
+    pub files_per_second: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+}
+
+/// 在目录树中创建大量1~4KB的文件，再依次stat、按随机顺序读取和删除，代表依赖安装、源码检出等元数据密集的负载
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SmallFileMetrics {
+    pub file_count: usize,
+    pub total_bytes: u64,
+    pub create: SmallFilePhase,
+    pub stat: SmallFilePhase,
+    pub read: SmallFilePhase,
+    pub delete: SmallFilePhase,
+}
+
+/// 未指定路径时使用的测试文件（系统临时目录）
+pub fn default_test_file_path() -> PathBuf {
+    env::temp_dir().join("tauri_benchmark_test.dat")
+}
+
+/// 顺序写入按整块写满文件，实际占用为文件大小向上取整到块大小
+pub fn disk_usage_bytes(file_size_mb: u64, block_size_kb: usize) -> u64 {
+    let file_size_bytes = file_size_mb * 1024 * 1024;
+    let block_size_bytes = (block_size_kb as u64 * 1024).max(1);
+    file_size_bytes.div_ceil(block_size_bytes) * block_size_bytes
+}
+
+// 小文件测试的目录树，离开作用域时整个删除，取消和出错时同样生效
+struct SmallFileTree {
+    root: PathBuf,
+}
+
+impl SmallFileTree {
+    fn create(root: PathBuf) -> Result<Self, BenchmarkError> {
+        // 上次运行中断后残留的目录树先删除，避免文件数和大小对不上
+        if root.is_dir() {
+            std::fs::remove_dir_all(&root).map_err(BenchmarkError::io("无法删除残留的小文件目录"))?;
+        }
+        std::fs::create_dir_all(&root).map_err(BenchmarkError::io("无法创建小文件目录"))?;
+        Ok(Self { root })
+    }
+
+    fn file_path(&self, index: usize) -> PathBuf {
+        self.root
+            .join(format!("{:04}", index / SMALL_FILES_PER_DIR))
+            .join(format!("{:06}.dat", index))
+    }
+}
+
+impl Drop for SmallFileTree {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
+
+/// 路径所在文件系统的可用inode数；不使用固定inode表的文件系统（如btrfs）报告为0，此时为空
+#[cfg(unix)]
+pub(crate) fn available_inodes(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: path是以NUL结尾的C字符串，statvfs成功返回0时已完整写入stat
+    let stat = unsafe {
+        if libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stat.assume_init()
+    };
+    // f_favail在Linux上是u64，在macOS上是u32
+    #[allow(clippy::useless_conversion)]
+    let available = u64::from(stat.f_favail);
+    (stat.f_files != 0).then_some(available)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn available_inodes(_path: &Path) -> Option<u64> {
+    None
+}
+
+fn small_file_phase(mut latencies: Vec<f64>, elapsed: f64) -> SmallFilePhase {
+    latencies.sort_by(|a, b| a.total_cmp(b));
+    SmallFilePhase {
+        files: latencies.len(),
+        files_per_second: latencies.len() as f64 / elapsed.max(f64::EPSILON),
+        p50_ms: percentile(&latencies, 50.0),
+        p95_ms: percentile(&latencies, 95.0),
+        p99_ms: percentile(&latencies, 99.0),
+    }
+}
+
+// 单个阶段的实时指标：相邻采样间的吞吐和IOPS，以及最近操作的p99延迟
+struct LiveIo {
+    bytes: RateMeter,
+    operations: RateMeter,
+    latency: RollingLatency,
+}
+
+impl LiveIo {
+    fn new() -> Self {
+        Self {
+            bytes: RateMeter::new(),
+            operations: RateMeter::new(),
+            latency: RollingLatency::new(),
+        }
+    }
+
+    fn record(&mut self, op_start: Instant) {
+        self.latency.push(op_start.elapsed().as_secs_f64() * 1000.0);
+    }
+
+    fn sample(&mut self, phase: &'static str, phase_progress: f64, total_bytes: u64, operations: u64) -> LiveSample {
+        LiveSample::new(
+            phase,
+            phase_progress,
+            &[
+                ("mb_per_sec", self.bytes.rate(total_bytes as f64) / (1024.0 * 1024.0)),
+                ("iops", self.operations.rate(operations as f64)),
+                ("p99_latency_ms", self.latency.p99()),
+            ],
+        )
+    }
+
+    // 阶段结束时按整个阶段汇总吞吐和IOPS
+    fn summary(&self, phase: &'static str, metrics: &StorageMetrics) -> LiveSample {
+        LiveSample::new(
+            phase,
+            100.0,
+            &[
+                ("mb_per_sec", metrics.throughput),
+                ("iops", metrics.iops as f64),
+                ("p99_latency_ms", self.latency.p99()),
+            ],
+        )
+    }
+}
+
 pub struct StorageBenchmark {
     config: StorageTestConfig,
+    metrics_sink: Option<MetricsSink>,
+    calibration: Calibration, // 只用于估计耗时
 }
 
 impl StorageBenchmark {
     pub fn new(config: StorageTestConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            metrics_sink: None,
+            calibration: Calibration::default_estimates(),
+        }
+    }
+
+    pub fn get_config(&self) -> &StorageTestConfig {
+        &self.config
+    }
+
+    /// 测试过程中上报实时吞吐、IOPS和滚动p99延迟
+    pub fn with_metrics_sink(mut self, sink: MetricsSink) -> Self {
+        self.metrics_sink = Some(sink);
+        self
+    }
+
+    /// 用实测的校准结果估计耗时，默认使用内置估计
+    pub fn with_calibration(mut self, calibration: Calibration) -> Self {
+        self.calibration = calibration;
+        self
+    }
+
+    fn report_metrics(&self, sample: LiveSample) {
+        if let Some(sink) = &self.metrics_sink {
+            sink(sample);
+        }
     }
 
     pub fn run_benchmark(&self) -> Result<StorageTestResult, BenchmarkError> {
@@ -46,30 +261,83 @@ impl StorageBenchmark {
 
     pub fn run_benchmark_with_progress<F>(&self, progress_callback: F) -> Result<StorageTestResult, BenchmarkError>
     where
-        F: Fn(f64, String) + Send + Sync + 'static,
+        F: Fn(f64, Message) + Send + Sync + 'static,
     {
-        let start_time = Instant::now();
+        self.run_benchmark_cancellable(progress_callback, &CancellationToken::never())
+    }
+
+    /// 在各阶段之间和阶段内的进度更新时检查取消。失败或取消时立即删除测试文件，不等到运行器被释放
+    pub fn run_benchmark_cancellable<F>(
+        &self,
+        progress_callback: F,
+        cancellation: &CancellationToken,
+    ) -> Result<StorageTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message) + Send + Sync + 'static,
+    {
+        let result = self.run_phases(&progress_callback, cancellation);
+        if result.is_err() {
+            let _ = self.remove_test_file();
+            let _ = self.remove_small_files();
+            let _ = self.remove_archive_dir();
+        }
+        result
+    }
+
+    fn run_phases<F>(&self, progress_callback: &F, cancellation: &CancellationToken) -> Result<StorageTestResult, BenchmarkError>
+    where
+        F: Fn(f64, Message),
+    {
+        let clock = cancellation.clock();
+        // 四个基本阶段和启用的小文件、压缩包解压测试平分进度
+        let run_archive = self.config.archive.as_ref().is_some_and(|archive| !archive.formats.is_empty());
+        let phases = 4 + usize::from(self.config.small_file_count > 0) + usize::from(run_archive);
+        let step = 100.0 / phases as f64;
         
         // 运行顺序写入测试
-        progress_callback(0.0, "开始存储顺序写入测试...".to_string());
-        let sequential_write = self.test_sequential_write_with_progress(&progress_callback)?;
+        cancellation.check(TestType::Storage, 0.0)?;
+        progress_callback(0.0, Message::new("storage.seq_write.start"));
+        let sequential_write = tracing::info_span!("sequential_write").in_scope(|| self.test_sequential_write_with_progress(progress_callback, cancellation, 0.0))?;
         
         // 运行顺序读取测试
-        progress_callback(25.0, "开始存储顺序读取测试...".to_string());
-        let sequential_read = self.test_sequential_read_with_progress(&progress_callback)?;
+        cancellation.check(TestType::Storage, step)?;
+        progress_callback(step, Message::new("storage.seq_read.start"));
+        let sequential_read = tracing::info_span!("sequential_read").in_scope(|| self.test_sequential_read_with_progress(progress_callback, cancellation, step))?;
         
         // 运行随机写入测试
-        progress_callback(50.0, "开始存储随机写入测试...".to_string());
-        let random_write = self.test_random_write_with_progress(&progress_callback)?;
+        cancellation.check(TestType::Storage, step * 2.0)?;
+        progress_callback(step * 2.0, Message::new("storage.random_write.start"));
+        let random_write = tracing::info_span!("random_write").in_scope(|| self.test_random_write_with_progress(progress_callback, cancellation, step * 2.0))?;
         
         // 运行随机读取测试
-        progress_callback(75.0, "开始存储随机读取测试...".to_string());
-        let random_read = self.test_random_read_with_progress(&progress_callback)?;
+        cancellation.check(TestType::Storage, step * 3.0)?;
+        progress_callback(step * 3.0, Message::new("storage.random_read.start"));
+        let random_read = tracing::info_span!("random_read").in_scope(|| self.test_random_read_with_progress(progress_callback, cancellation, step * 3.0))?;
+
+        // 运行小文件测试
+        let small_files = if self.config.small_file_count > 0 {
+            cancellation.check(TestType::Storage, step * 4.0)?;
+            progress_callback(step * 4.0, Message::new("storage.small_files.start"));
+            Some(self.test_small_files(progress_callback, cancellation, step * 4.0, step)?)
+        } else {
+            None
+        };
 
-        let test_duration = std::cmp::max(start_time.elapsed().as_secs(), 1); // 至少1秒
+        // 运行压缩包解压测试
+        let archive = match self.config.archive.as_ref().filter(|_| run_archive) {
+            Some(config) => {
+                let base = 100.0 - step;
+                cancellation.check(TestType::Storage, base)?;
+                progress_callback(base, Message::new("storage.archive.start"));
+                Some(run_archive_test(config, &self.get_archive_dir(), progress_callback, cancellation, base, step)?)
+            }
+            None => None,
+        };
+
+        let test_duration = std::cmp::max(clock.elapsed().as_secs(), 1); // 至少1秒
         let total_data_processed = self.config.file_size * 4; // 4个测试，每个处理file_size的数据
         
-        progress_callback(100.0, "存储测试完成".to_string());
+        progress_callback(100.0, Message::new("storage.complete"));
         
         Ok(StorageTestResult {
             sequential_read,
@@ -78,27 +346,72 @@ impl StorageBenchmark {
             random_write,
             test_duration,
             total_data_processed,
+            small_files,
+            archive,
         })
     }
 
-    fn get_test_file_path(&self) -> PathBuf {
+    // 删除测试文件，文件不存在或不是普通文件（例如指向设备文件）时什么也不做
+    fn remove_test_file(&self) -> std::io::Result<()> {
+        let file_path = self.get_test_file_path();
+        if file_path.is_file() {
+            std::fs::remove_file(file_path)?;
+        }
+        Ok(())
+    }
+
+    fn remove_small_files(&self) -> std::io::Result<()> {
+        let dir = self.get_small_file_dir();
+        if self.config.small_file_count > 0 && dir.is_dir() {
+            std::fs::remove_dir_all(dir)?;
+        }
+        Ok(())
+    }
+
+    // 压缩包测试出错时工作目录已由测试自身删除，这里兜底处理进程中断后的残留
+    fn remove_archive_dir(&self) -> std::io::Result<()> {
+        let dir = self.get_archive_dir();
+        if self.config.archive.is_some() && dir.is_dir() {
+            std::fs::remove_dir_all(dir)?;
+        }
+        Ok(())
+    }
+
+    // 测试文件旁边的同名前缀路径
+    fn sibling_path(&self, suffix: &str) -> PathBuf {
+        let file_path = self.get_test_file_path();
+        let stem = file_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "tauri_benchmark".to_string());
+        file_path.with_file_name(format!("{}_{}", stem, suffix))
+    }
+
+    /// 小文件测试的目录树放在测试文件旁边，跟随存储测试的目标磁盘
+    pub fn get_small_file_dir(&self) -> PathBuf {
+        self.sibling_path("small_files")
+    }
+
+    /// 压缩包解压测试的工作目录，同样放在测试文件旁边
+    pub fn get_archive_dir(&self) -> PathBuf {
+        self.sibling_path("archive")
+    }
+
+    pub fn get_test_file_path(&self) -> PathBuf {
         if let Some(ref path) = self.config.test_file_path {
             PathBuf::from(path)
         } else {
-            // 使用临时目录
-            let mut temp_dir = env::temp_dir();
-            temp_dir.push("tauri_benchmark_test.dat");
-            temp_dir
+            default_test_file_path()
         }
     }
 
     fn test_sequential_write(&self) -> Result<StorageMetrics, BenchmarkError> {
-        self.test_sequential_write_with_progress(&|_progress, _message| {})
+        self.test_sequential_write_with_progress(&|_progress, _message| {}, &CancellationToken::never(), 0.0)
     }
 
-    fn test_sequential_write_with_progress<F>(&self, progress_callback: &F) -> Result<StorageMetrics, BenchmarkError>
+    fn test_sequential_write_with_progress<F>(&self, progress_callback: &F, cancellation: &CancellationToken, at_progress: f64) -> Result<StorageMetrics, BenchmarkError>
     where
-        F: Fn(f64, String),
+        F: Fn(f64, Message),
     {
         let file_path = self.get_test_file_path();
         let file_size_bytes = self.config.file_size * 1024 * 1024; // Convert MB to bytes
@@ -112,70 +425,78 @@ impl StorageBenchmark {
             .write(true)
             .truncate(true)
             .open(&file_path)
-            .map_err(|e| BenchmarkError::StorageTestError(format!("无法创建测试文件: {}", e)))?;
+            .map_err(BenchmarkError::io("无法创建测试文件"))?;
 
-        let start_time = Instant::now();
+        let clock = cancellation.clock();
         let mut total_bytes_written = 0u64;
         let mut operations = 0u64;
         let mut latencies = Vec::new();
         let mut last_progress_update = Instant::now();
+        let mut live = LiveIo::new();
 
         while total_bytes_written < file_size_bytes {
             let op_start = Instant::now();
             
             file.write_all(&test_data)
-                .map_err(|e| BenchmarkError::StorageTestError(format!("写入失败: {}", e)))?;
+                .map_err(BenchmarkError::io("写入失败"))?;
             
             let op_latency = op_start.elapsed().as_millis() as f64;
             latencies.push(op_latency);
+            live.record(op_start);
             
             total_bytes_written += test_data.len() as u64;
             operations += 1;
 
-            // 更新进度（每200ms更新一次）
+            // 更新进度并检查取消（每200ms一次）
             if last_progress_update.elapsed().as_millis() >= 200 {
+                cancellation.check(TestType::Storage, at_progress)?;
                 let progress = (total_bytes_written as f64 / file_size_bytes as f64) * 100.0;
-                progress_callback(progress, format!("顺序写入进行中... ({:.1}%)", progress));
+                progress_callback(progress, Message::progress("storage.seq_write.progress", progress));
+                self.report_metrics(live.sample("seq_write", progress, total_bytes_written, operations));
                 last_progress_update = Instant::now();
             }
         }
 
         file.sync_all()
-            .map_err(|e| BenchmarkError::StorageTestError(format!("同步失败: {}", e)))?;
+            .map_err(BenchmarkError::io("同步失败"))?;
 
-        let elapsed = start_time.elapsed().as_secs_f64();
+        let elapsed = clock.elapsed().as_secs_f64();
         let throughput = (total_bytes_written as f64) / (1024.0 * 1024.0) / elapsed;
         let iops = (operations as f64 / elapsed) as u64;
         let avg_latency = latencies.iter().sum::<f64>() / latencies.len() as f64;
 
-        Ok(StorageMetrics {
+        let metrics = StorageMetrics {
             throughput,
             iops,
             latency: avg_latency,
-        })
+        };
+        self.report_metrics(live.summary("seq_write", &metrics));
+        tracing::debug!(bytes = total_bytes_written, operations, elapsed_seconds = elapsed, throughput, iops, "顺序写入完成");
+        Ok(metrics)
     }
 
     fn test_sequential_read(&self) -> Result<StorageMetrics, BenchmarkError> {
-        self.test_sequential_read_with_progress(&|_progress, _message| {})
+        self.test_sequential_read_with_progress(&|_progress, _message| {}, &CancellationToken::never(), 0.0)
     }
 
-    fn test_sequential_read_with_progress<F>(&self, progress_callback: &F) -> Result<StorageMetrics, BenchmarkError>
+    fn test_sequential_read_with_progress<F>(&self, progress_callback: &F, cancellation: &CancellationToken, at_progress: f64) -> Result<StorageMetrics, BenchmarkError>
     where
-        F: Fn(f64, String),
+        F: Fn(f64, Message),
     {
         let file_path = self.get_test_file_path();
         let block_size_bytes = self.config.block_size * 1024;
         let file_size_bytes = self.config.file_size * 1024 * 1024;
         
         let mut file = File::open(&file_path)
-            .map_err(|e| BenchmarkError::StorageTestError(format!("无法打开测试文件: {}", e)))?;
+            .map_err(BenchmarkError::io("无法打开测试文件"))?;
 
-        let start_time = Instant::now();
+        let clock = cancellation.clock();
         let mut total_bytes_read = 0u64;
         let mut operations = 0u64;
         let mut latencies = Vec::new();
         let mut buffer = vec![0u8; block_size_bytes];
         let mut last_progress_update = Instant::now();
+        let mut live = LiveIo::new();
 
         loop {
             let op_start = Instant::now();
@@ -185,40 +506,46 @@ impl StorageBenchmark {
                 Ok(bytes_read) => {
                     let op_latency = op_start.elapsed().as_millis() as f64;
                     latencies.push(op_latency);
+                    live.record(op_start);
                     
                     total_bytes_read += bytes_read as u64;
                     operations += 1;
 
-                    // 更新进度（每200ms更新一次）
+                    // 更新进度并检查取消（每200ms一次）
                     if last_progress_update.elapsed().as_millis() >= 200 {
+                        cancellation.check(TestType::Storage, at_progress)?;
                         let progress = (total_bytes_read as f64 / file_size_bytes as f64) * 100.0;
-                        progress_callback(progress.min(100.0), format!("顺序读取进行中... ({:.1}%)", progress.min(100.0)));
+                        progress_callback(progress.min(100.0), Message::progress("storage.seq_read.progress", progress.min(100.0)));
+                        self.report_metrics(live.sample("seq_read", progress.min(99.9), total_bytes_read, operations));
                         last_progress_update = Instant::now();
                     }
                 }
-                Err(e) => return Err(BenchmarkError::StorageTestError(format!("读取失败: {}", e))),
+                Err(e) => return Err(BenchmarkError::io("读取失败")(e)),
             }
         }
 
-        let elapsed = start_time.elapsed().as_secs_f64();
+        let elapsed = clock.elapsed().as_secs_f64();
         let throughput = (total_bytes_read as f64) / (1024.0 * 1024.0) / elapsed;
         let iops = (operations as f64 / elapsed) as u64;
         let avg_latency = if latencies.is_empty() { 0.0 } else { latencies.iter().sum::<f64>() / latencies.len() as f64 };
 
-        Ok(StorageMetrics {
+        let metrics = StorageMetrics {
             throughput,
             iops,
             latency: avg_latency,
-        })
+        };
+        self.report_metrics(live.summary("seq_read", &metrics));
+        tracing::debug!(bytes = total_bytes_read, operations, elapsed_seconds = elapsed, throughput, iops, "顺序读取完成");
+        Ok(metrics)
     }
 
     fn test_random_write(&self) -> Result<StorageMetrics, BenchmarkError> {
-        self.test_random_write_with_progress(&|_progress, _message| {})
+        self.test_random_write_with_progress(&|_progress, _message| {}, &CancellationToken::never(), 0.0)
     }
 
-    fn test_random_write_with_progress<F>(&self, progress_callback: &F) -> Result<StorageMetrics, BenchmarkError>
+    fn test_random_write_with_progress<F>(&self, progress_callback: &F, cancellation: &CancellationToken, at_progress: f64) -> Result<StorageMetrics, BenchmarkError>
     where
-        F: Fn(f64, String),
+        F: Fn(f64, Message),
     {
         let file_path = self.get_test_file_path();
         let file_size_bytes = self.config.file_size * 1024 * 1024;
@@ -229,127 +556,288 @@ impl StorageBenchmark {
         let mut file = OpenOptions::new()
             .write(true)
             .open(&file_path)
-            .map_err(|e| BenchmarkError::StorageTestError(format!("无法打开测试文件: {}", e)))?;
+            .map_err(BenchmarkError::io("无法打开测试文件"))?;
 
-        let start_time = Instant::now();
+        let clock = cancellation.clock();
         let mut operations = 0u64;
         let mut latencies = Vec::new();
         let max_operations = 1000; // 限制随机操作数量以避免测试时间过长
+        let mut live = LiveIo::new();
         
-        // 简单的随机数生成器
-        let mut rng_state = 12345u64;
+        let mut rng = Xoshiro256::new(12345);
 
         for i in 0..max_operations {
             // 生成随机位置
-            rng_state = rng_state.wrapping_mul(1103515245).wrapping_add(12345);
-            let random_pos = (rng_state % (file_size_bytes / block_size_bytes as u64)) * block_size_bytes as u64;
+            let random_pos = rng.below(file_size_bytes / block_size_bytes as u64) * block_size_bytes as u64;
             
             let op_start = Instant::now();
             
             file.seek(SeekFrom::Start(random_pos))
-                .map_err(|e| BenchmarkError::StorageTestError(format!("定位失败: {}", e)))?;
+                .map_err(BenchmarkError::io("定位失败"))?;
             
             file.write_all(&test_data)
-                .map_err(|e| BenchmarkError::StorageTestError(format!("随机写入失败: {}", e)))?;
+                .map_err(BenchmarkError::io("随机写入失败"))?;
             
             let op_latency = op_start.elapsed().as_millis() as f64;
             latencies.push(op_latency);
+            live.record(op_start);
             operations += 1;
 
-            // 更新进度（每50次操作更新一次）
+            // 更新进度并检查取消（每50次操作一次）
             if i % 50 == 0 {
+                cancellation.check(TestType::Storage, at_progress)?;
                 let progress = (i as f64 / max_operations as f64) * 100.0;
-                progress_callback(progress, format!("随机写入进行中... ({:.1}%)", progress));
+                progress_callback(progress, Message::progress("storage.random_write.progress", progress));
+                self.report_metrics(live.sample("random_write", progress, operations * block_size_bytes as u64, operations));
             }
         }
 
         file.sync_all()
-            .map_err(|e| BenchmarkError::StorageTestError(format!("同步失败: {}", e)))?;
+            .map_err(BenchmarkError::io("同步失败"))?;
 
-        let elapsed = start_time.elapsed().as_secs_f64();
+        let elapsed = clock.elapsed().as_secs_f64();
         let total_bytes = operations * block_size_bytes as u64;
         let throughput = (total_bytes as f64) / (1024.0 * 1024.0) / elapsed;
         let iops = (operations as f64 / elapsed) as u64;
         let avg_latency = latencies.iter().sum::<f64>() / latencies.len() as f64;
 
-        Ok(StorageMetrics {
+        let metrics = StorageMetrics {
             throughput,
             iops,
             latency: avg_latency,
-        })
+        };
+        self.report_metrics(live.summary("random_write", &metrics));
+        tracing::debug!(bytes = total_bytes, operations, elapsed_seconds = elapsed, throughput, iops, "随机写入完成");
+        Ok(metrics)
     }
 
     fn test_random_read(&self) -> Result<StorageMetrics, BenchmarkError> {
-        self.test_random_read_with_progress(&|_progress, _message| {})
+        self.test_random_read_with_progress(&|_progress, _message| {}, &CancellationToken::never(), 0.0)
     }
 
-    fn test_random_read_with_progress<F>(&self, progress_callback: &F) -> Result<StorageMetrics, BenchmarkError>
+    fn test_random_read_with_progress<F>(&self, progress_callback: &F, cancellation: &CancellationToken, at_progress: f64) -> Result<StorageMetrics, BenchmarkError>
     where
-        F: Fn(f64, String),
+        F: Fn(f64, Message),
     {
         let file_path = self.get_test_file_path();
         let file_size_bytes = self.config.file_size * 1024 * 1024;
         let block_size_bytes = self.config.block_size * 1024;
         
         let mut file = File::open(&file_path)
-            .map_err(|e| BenchmarkError::StorageTestError(format!("无法打开测试文件: {}", e)))?;
+            .map_err(BenchmarkError::io("无法打开测试文件"))?;
 
-        let start_time = Instant::now();
+        let clock = cancellation.clock();
         let mut operations = 0u64;
         let mut latencies = Vec::new();
         let mut buffer = vec![0u8; block_size_bytes];
         let max_operations = 1000; // 限制随机操作数量
+        let mut live = LiveIo::new();
         
-        // 简单的随机数生成器
-        let mut rng_state = 54321u64;
+        let mut rng = Xoshiro256::new(54321);
 
         for i in 0..max_operations {
             // 生成随机位置
-            rng_state = rng_state.wrapping_mul(1103515245).wrapping_add(12345);
-            let random_pos = (rng_state % (file_size_bytes / block_size_bytes as u64)) * block_size_bytes as u64;
+            let random_pos = rng.below(file_size_bytes / block_size_bytes as u64) * block_size_bytes as u64;
             
             let op_start = Instant::now();
             
             file.seek(SeekFrom::Start(random_pos))
-                .map_err(|e| BenchmarkError::StorageTestError(format!("定位失败: {}", e)))?;
+                .map_err(BenchmarkError::io("定位失败"))?;
             
             match file.read(&mut buffer) {
                 Ok(_) => {
                     let op_latency = op_start.elapsed().as_millis() as f64;
                     latencies.push(op_latency);
+                    live.record(op_start);
                     operations += 1;
                 }
-                Err(e) => return Err(BenchmarkError::StorageTestError(format!("随机读取失败: {}", e))),
+                Err(e) => return Err(BenchmarkError::io("随机读取失败")(e)),
             }
 
-            // 更新进度（每50次操作更新一次）
+            // 更新进度并检查取消（每50次操作一次）
             if i % 50 == 0 {
+                cancellation.check(TestType::Storage, at_progress)?;
                 let progress = (i as f64 / max_operations as f64) * 100.0;
-                progress_callback(progress, format!("随机读取进行中... ({:.1}%)", progress));
+                progress_callback(progress, Message::progress("storage.random_read.progress", progress));
+                self.report_metrics(live.sample("random_read", progress, operations * block_size_bytes as u64, operations));
             }
         }
 
-        let elapsed = start_time.elapsed().as_secs_f64();
+        let elapsed = clock.elapsed().as_secs_f64();
         let total_bytes = operations * block_size_bytes as u64;
         let throughput = (total_bytes as f64) / (1024.0 * 1024.0) / elapsed;
         let iops = (operations as f64 / elapsed) as u64;
         let avg_latency = latencies.iter().sum::<f64>() / latencies.len() as f64;
 
-        Ok(StorageMetrics {
+        let metrics = StorageMetrics {
             throughput,
             iops,
             latency: avg_latency,
+        };
+        self.report_metrics(live.summary("random_read", &metrics));
+        tracing::debug!(bytes = total_bytes, operations, elapsed_seconds = elapsed, throughput, iops, "随机读取完成");
+        Ok(metrics)
+    }
+}
+
+impl StorageBenchmark {
+    // 目标磁盘的可用空间和inode不足所需的两倍时拒绝运行，无法确定时不检查
+    fn check_small_file_headroom(&self, dir: &Path) -> Result<(), BenchmarkError> {
+        let count = self.config.small_file_count as u64;
+        // 每个文件至少占用一个4KB的文件系统块，每个子目录占用一个inode
+        let required_bytes = count * SMALL_FILE_MAX_BYTES as u64 * SMALL_FILE_HEADROOM;
+        let required_inodes = (count + count.div_ceil(SMALL_FILES_PER_DIR as u64) + 1) * SMALL_FILE_HEADROOM;
+        let absolute = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        if let Some(available) = available_disk_bytes(&absolute) {
+            if available < required_bytes {
+                return Err(BenchmarkError::StorageTestError(format!(
+                    "{}的可用空间不足: 小文件测试需要{}MB，只剩{}MB",
+                    dir.display(),
+                    required_bytes / (1024 * 1024),
+                    available / (1024 * 1024)
+                )));
+            }
+        }
+        if let Some(available) = available_inodes(&absolute) {
+            if available < required_inodes {
+                return Err(BenchmarkError::StorageTestError(format!(
+                    "{}的可用inode不足: 小文件测试需要{}个，只剩{}个",
+                    dir.display(),
+                    required_inodes,
+                    available
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// 创建、stat、随机顺序读取和删除small_file_count个文件，进度落在[base, base + span]中
+    fn test_small_files<F>(&self, progress_callback: &F, cancellation: &CancellationToken, base: f64, span: f64) -> Result<SmallFileMetrics, BenchmarkError>
+    where
+        F: Fn(f64, Message),
+    {
+        let count = self.config.small_file_count;
+        let root = self.get_small_file_dir();
+        let parent = root.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        self.check_small_file_headroom(parent)?;
+        let tree = SmallFileTree::create(root)?;
+
+        let mut rng = CorpusRng::new(SMALL_FILE_SEED);
+        let sizes: Vec<usize> = (0..count)
+            .map(|_| SMALL_FILE_MIN_BYTES + (rng.next() % (SMALL_FILE_MAX_BYTES - SMALL_FILE_MIN_BYTES + 1) as u64) as usize)
+            .collect();
+        let data = vec![0xCC; SMALL_FILE_MAX_BYTES];
+        let sequential: Vec<usize> = (0..count).collect();
+        // Fisher-Yates洗牌，读取顺序与创建顺序无关
+        let mut shuffled = sequential.clone();
+        for i in (1..count).rev() {
+            shuffled.swap(i, (rng.next() % (i as u64 + 1)) as usize);
+        }
+
+        let phase_span = span / 4.0;
+        let mut last_progress_update = Instant::now();
+        let mut run_phase = |phase: &'static str, index: usize, order: &[usize], op: &mut dyn FnMut(usize) -> Result<(), BenchmarkError>| {
+            let phase_base = base + phase_span * index as f64;
+            let started = cancellation.clock();
+            let mut latencies = Vec::with_capacity(order.len());
+            for (done, &file) in order.iter().enumerate() {
+                let progress = phase_base + done as f64 / count as f64 * phase_span;
+                if done % SMALL_FILES_PER_DIR == 0 {
+                    cancellation.check(TestType::Storage, progress)?;
+                }
+                if last_progress_update.elapsed() >= SMALL_FILE_PROGRESS_INTERVAL {
+                    progress_callback(progress, Message::progress("storage.small_files.progress", progress).param("phase", phase));
+                    last_progress_update = Instant::now();
+                }
+                let op_start = Instant::now();
+                op(file)?;
+                latencies.push(op_start.elapsed().as_secs_f64() * 1000.0);
+            }
+            Ok::<_, BenchmarkError>(small_file_phase(latencies, started.elapsed().as_secs_f64()))
+        };
+
+        let create = run_phase("create", 0, &sequential, &mut |file| {
+            let path = tree.file_path(file);
+            if file % SMALL_FILES_PER_DIR == 0 {
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir).map_err(BenchmarkError::io("无法创建小文件目录"))?;
+                }
+            }
+            std::fs::write(&path, &data[..sizes[file]]).map_err(BenchmarkError::io("无法创建小文件"))
+        })?;
+        let stat = run_phase("stat", 1, &sequential, &mut |file| {
+            let metadata = std::fs::metadata(tree.file_path(file)).map_err(BenchmarkError::io("无法读取小文件元数据"))?;
+            if metadata.len() != sizes[file] as u64 {
+                return Err(BenchmarkError::StorageTestError(format!("小文件{}的大小为{}字节，应为{}字节", file, metadata.len(), sizes[file])));
+            }
+            Ok(())
+        })?;
+        let read = run_phase("read", 2, &shuffled, &mut |file| {
+            let contents = std::fs::read(tree.file_path(file)).map_err(BenchmarkError::io("无法读取小文件"))?;
+            if contents.len() != sizes[file] {
+                return Err(BenchmarkError::StorageTestError(format!("小文件{}只读到{}字节，应为{}字节", file, contents.len(), sizes[file])));
+            }
+            Ok(())
+        })?;
+        let delete = run_phase("delete", 3, &sequential, &mut |file| {
+            std::fs::remove_file(tree.file_path(file)).map_err(BenchmarkError::io("无法删除小文件"))
+        })?;
+
+        Ok(SmallFileMetrics {
+            file_count: count,
+            total_bytes: sizes.iter().map(|&size| size as u64).sum(),
+            create,
+            stat,
+            read,
+            delete,
         })
     }
 }
 
+impl BenchmarkRunner for StorageBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Storage
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        (storage_test_seconds(self.config.file_size, &self.calibration)
+            + small_file_test_seconds(self.config.small_file_count)
+            + self.config.archive.as_ref().map_or(0.0, archive_test_seconds))
+        .ceil() as u64
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        let result = self.run_benchmark_cancellable(ctx.progress_callback(), &ctx.cancellation)?;
+        // 测试完成后立即清理；删除失败不影响结果，但文件会占用磁盘空间，作为问题上报
+        if let Err(e) = self.remove_test_file() {
+            ctx.report(
+                Message::new("storage.cleanup_failed")
+                    .param("path", self.get_test_file_path().display())
+                    .param("error", e),
+                WarningSeverity::Low,
+            );
+        }
+        Ok(TestOutcome::Completed(TestResultPayload::Storage(result)))
+    }
+
+    fn artifacts(&self) -> Vec<PathBuf> {
+        let mut artifacts = vec![self.get_test_file_path()];
+        if self.config.small_file_count > 0 {
+            artifacts.push(self.get_small_file_dir());
+        }
+        if self.config.archive.is_some() {
+            artifacts.push(self.get_archive_dir());
+        }
+        artifacts
+    }
+}
+
 impl Drop for StorageBenchmark {
     fn drop(&mut self) {
         // 清理测试文件
-        let file_path = self.get_test_file_path();
-        if file_path.exists() {
-            let _ = std::fs::remove_file(file_path);
-        }
+        let _ = self.remove_test_file();
+        let _ = self.remove_small_files();
+        let _ = self.remove_archive_dir();
     }
 }
 #[cfg(test)]
@@ -364,6 +852,8 @@ mod tests {
             block_size: 4, // 4KB
             test_duration: 5,
             test_file_path: None,
+            small_file_count: 0,
+            archive: None,
         };
         
         let benchmark = StorageBenchmark::new(config);
@@ -378,6 +868,8 @@ mod tests {
             block_size: 4, // 4KB
             test_duration: 5,
             test_file_path: Some("test_seq_write.dat".to_string()),
+            small_file_count: 0,
+            archive: None,
         };
         
         let benchmark = StorageBenchmark::new(config);
@@ -400,6 +892,8 @@ mod tests {
             block_size: 4, // 4KB
             test_duration: 5,
             test_file_path: Some("test_seq_read.dat".to_string()),
+            small_file_count: 0,
+            archive: None,
         };
         
         let benchmark = StorageBenchmark::new(config);
@@ -427,6 +921,8 @@ mod tests {
             block_size: 4, // 4KB
             test_duration: 5,
             test_file_path: Some("test_rand_write.dat".to_string()),
+            small_file_count: 0,
+            archive: None,
         };
         
         let benchmark = StorageBenchmark::new(config);
@@ -454,6 +950,8 @@ mod tests {
             block_size: 4, // 4KB
             test_duration: 5,
             test_file_path: Some("test_rand_read.dat".to_string()),
+            small_file_count: 0,
+            archive: None,
         };
         
         let benchmark = StorageBenchmark::new(config);
@@ -481,6 +979,8 @@ mod tests {
             block_size: 4, // 4KB
             test_duration: 5,
             test_file_path: Some("test_full_benchmark.dat".to_string()),
+            small_file_count: 0,
+            archive: None,
         };
         
         let benchmark = StorageBenchmark::new(config);
@@ -506,6 +1006,142 @@ mod tests {
         let _ = fs::remove_file("test_full_benchmark.dat");
     }
 
+    #[test]
+    fn test_cancelled_run_removes_test_file() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("test_cancelled.dat");
+        let benchmark = StorageBenchmark::new(StorageTestConfig {
+            file_size: 1,
+            block_size: 4,
+            test_duration: 5,
+            test_file_path: Some(file_path.to_string_lossy().into_owned()),
+            small_file_count: 0,
+            archive: None,
+        });
+
+        // 顺序写入开始后取消，下一个阶段开始前停止
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let cancelled_clone = cancelled.clone();
+        let token = CancellationToken::new(move || cancelled_clone.load(Ordering::SeqCst));
+        let started = cancelled.clone();
+        let err = benchmark
+            .run_benchmark_cancellable(move |_, _| started.store(true, Ordering::SeqCst), &token)
+            .unwrap_err();
+        assert!(matches!(err, BenchmarkError::Cancelled { ref test, at_progress } if test == "storage" && at_progress == 25.0), "{}", err);
+        // 运行器仍然存在，测试文件已经删除
+        assert!(!file_path.exists());
+    }
+
+    #[test]
+    fn test_cancel_interrupts_large_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("test_cancel_large.dat");
+        let benchmark = StorageBenchmark::new(StorageTestConfig {
+            file_size: 4096,
+            block_size: 4,
+            test_duration: 60,
+            test_file_path: Some(file_path.to_string_lossy().into_owned()),
+            small_file_count: 0,
+            archive: None,
+        });
+
+        // 4GB的顺序写入不会在取消前完成，应在下一次进度检查时停止并删除写了一半的文件
+        let started = Instant::now();
+        let token = CancellationToken::new(move || started.elapsed() > Duration::from_millis(300));
+        let err = benchmark.run_benchmark_cancellable(|_, _| {}, &token).unwrap_err();
+        assert!(matches!(err, BenchmarkError::Cancelled { at_progress, .. } if at_progress == 0.0), "{}", err);
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(!file_path.exists());
+    }
+
+    #[test]
+    fn test_small_files_phases_and_cleanup() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = StorageTestConfig {
+            file_size: 1,
+            block_size: 4,
+            test_duration: 5,
+            test_file_path: Some(dir.path().join("small.dat").to_string_lossy().into_owned()),
+            small_file_count: 500,
+            archive: None,
+        };
+        let benchmark = StorageBenchmark::new(config.clone());
+        let tree = benchmark.get_small_file_dir();
+        assert!(tree.starts_with(dir.path()));
+        assert!(benchmark.artifacts().contains(&tree));
+
+        let small_files = benchmark.run_benchmark().unwrap().small_files.unwrap();
+        assert_eq!(small_files.file_count, 500);
+        assert!(small_files.total_bytes >= 500 * 1024 && small_files.total_bytes <= 500 * 4096);
+        for phase in [&small_files.create, &small_files.stat, &small_files.read, &small_files.delete] {
+            assert_eq!(phase.files, 500);
+            assert!(phase.files_per_second > 0.0);
+            assert!(phase.p50_ms <= phase.p95_ms && phase.p95_ms <= phase.p99_ms, "{:?}", phase);
+        }
+        assert!(!tree.exists());
+
+        // 在小文件阶段中途取消，目录树同样被删除
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+        let token = CancellationToken::new(move || flag.load(Ordering::SeqCst));
+        let benchmark = StorageBenchmark::new(config);
+        let err = benchmark
+            .run_benchmark_cancellable(
+                move |_, message| {
+                    if message.phase() == "storage.small_files" {
+                        cancelled.store(true, Ordering::SeqCst);
+                    }
+                },
+                &token,
+            )
+            .unwrap_err();
+        assert!(matches!(err, BenchmarkError::Cancelled { at_progress, .. } if at_progress >= 80.0), "{}", err);
+        assert!(!tree.exists());
+    }
+
+    #[test]
+    fn test_io_errors_keep_their_kind() {
+        use std::io::ErrorKind;
+
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing").join("test.dat");
+        let benchmark = StorageBenchmark::new(StorageTestConfig {
+            file_size: 1,
+            block_size: 4,
+            test_duration: 5,
+            test_file_path: Some(missing.to_string_lossy().into_owned()),
+            small_file_count: 0,
+            archive: None,
+        });
+        let err = benchmark.run_benchmark().unwrap_err();
+        assert_eq!(err.io_kind(), Some(ErrorKind::NotFound), "{}", err);
+        assert!(err.to_string().starts_with("无法创建测试文件: "));
+
+        // /dev/full的每次写入都返回ENOSPC，模拟磁盘已满；它不是普通文件，清理时不会被删除
+        #[cfg(target_os = "linux")]
+        if std::path::Path::new("/dev/full").exists() {
+            let benchmark = StorageBenchmark::new(StorageTestConfig {
+                file_size: 1,
+                block_size: 4,
+                test_duration: 5,
+                test_file_path: Some("/dev/full".to_string()),
+                small_file_count: 0,
+                archive: None,
+            });
+            let err = benchmark.run_benchmark().unwrap_err();
+            assert_eq!(err.io_kind(), Some(ErrorKind::StorageFull), "{}", err);
+            assert_eq!(crate::ipc::IpcError::from(err).code, "IO_STORAGE_FULL");
+            drop(benchmark);
+            assert!(std::path::Path::new("/dev/full").exists());
+        }
+    }
+
     #[test]
     fn test_file_path_generation() {
         let config_with_path = StorageTestConfig {
@@ -513,6 +1149,8 @@ mod tests {
             block_size: 4,
             test_duration: 5,
             test_file_path: Some("custom_test.dat".to_string()),
+            small_file_count: 0,
+            archive: None,
         };
         
         let benchmark_with_path = StorageBenchmark::new(config_with_path);
@@ -524,6 +1162,8 @@ mod tests {
             block_size: 4,
             test_duration: 5,
             test_file_path: None,
+            small_file_count: 0,
+            archive: None,
         };
         
         let benchmark_without_path = StorageBenchmark::new(config_without_path);
@@ -538,6 +1178,8 @@ mod tests {
             block_size: 4,
             test_duration: 5,
             test_file_path: Some("test_metrics.dat".to_string()),
+            small_file_count: 0,
+            archive: None,
         };
         
         let benchmark = StorageBenchmark::new(config);
@@ -0,0 +1,420 @@
+//! 线程同步开销：Mutex加锁解锁、原子fetch_add和通道收发，分别在每个线程使用自己的实例（无竞争）
+//! 和所有线程共享一个实例（有竞争）时测量吞吐。两种情况的总操作数相同，吞吐之比即竞争代价
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, TestOutcome};
+use crate::benchmark::error::BenchmarkError;
+use crate::i18n::Message;
+use crate::ipc::{ExtraTestResult, TestResultPayload, TestType};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Barrier, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 在BenchmarkRegistry中的注册名，结果记录在extra_results下的同名键中
+pub const SYNC_TEST_NAME: &str = "sync";
+
+const STOP_CHECK_INTERVAL: u64 = 4096; // 工作线程每完成这么多次操作检查一次停止标志
+const POLL_INTERVAL: Duration = Duration::from_millis(10); // 等待工作线程时检查取消的间隔
+const CHANNEL_CAPACITY: usize = 1024; // 有竞争时共享通道的容量，发送方在通道满时阻塞
+const ESTIMATED_OPS_PER_SECOND: f64 = 5_000_000.0; // 估计耗时用，竞争下的通道和Mutex明显更慢
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncPrimitive {
+    Mutex, // 加锁、递增计数、解锁
+    Atomic, // fetch_add
+    Channel, // 发送一条消息并被接收
+}
+
+impl SyncPrimitive {
+    pub const ALL: [SyncPrimitive; 3] = [SyncPrimitive::Mutex, SyncPrimitive::Atomic, SyncPrimitive::Channel];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SyncPrimitive::Mutex => "mutex",
+            SyncPrimitive::Atomic => "atomic",
+            SyncPrimitive::Channel => "channel",
+        }
+    }
+}
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct SyncTestConfig {
+    pub operations: u64, // 每种配置所有线程合计的操作数
+    #[serde(alias = "threadCounts")]
+    pub thread_counts: Vec<usize>, // 0表示逻辑核心数
+    pub primitives: Vec<SyncPrimitive>,
+}
+
+impl Default for SyncTestConfig {
+    fn default() -> Self {
+        Self {
+            operations: 2_000_000,
+            thread_counts: vec![2, 4, 0],
+            primitives: SyncPrimitive::ALL.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SyncMeasurement {
+    pub primitive: SyncPrimitive,
+    pub threads: usize,
+    pub operations: u64, // 有竞争和无竞争各自完成的操作数，与配置相同
+    pub uncontended_ops_per_second: f64,
+    pub contended_ops_per_second: f64,
+    pub contention_penalty: f64, // 无竞争吞吐 / 有竞争吞吐，越大说明竞争代价越高
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SyncBenchResult {
+    pub measurements: Vec<SyncMeasurement>,
+    pub test_duration: f64, // seconds
+}
+
+// 独占缓存行，避免无竞争时相邻实例之间的伪共享
+#[repr(align(128))]
+#[derive(Default)]
+struct Padded<T>(T);
+
+/// 把总操作数平分给各线程，余数分给前几个线程，合计总是等于total
+fn split_operations(total: u64, threads: usize) -> Vec<u64> {
+    let threads = threads.max(1) as u64;
+    (0..threads).map(|index| total / threads + u64::from(index < total % threads)).collect()
+}
+
+// 按计划执行count次操作，停止标志置位时提前返回，返回实际完成的次数
+fn repeat(count: u64, stop: &AtomicBool, mut op: impl FnMut()) -> u64 {
+    let mut done = 0;
+    while done < count {
+        if done % STOP_CHECK_INTERVAL == 0 && stop.load(Ordering::Relaxed) {
+            break;
+        }
+        op();
+        done += 1;
+    }
+    done
+}
+
+// 所有工作线程在栅栏处同时开始，等待期间检查取消；返回耗时和各线程完成的操作数
+fn run_workers<'env, W>(workers: Vec<W>, stop: &AtomicBool, cancellation: &CancellationToken) -> (Duration, Vec<u64>, bool)
+where
+    W: FnOnce() -> u64 + Send + 'env,
+{
+    let barrier = Barrier::new(workers.len() + 1);
+    thread::scope(|scope| {
+        let handles: Vec<_> = workers
+            .into_iter()
+            .map(|worker| {
+                let barrier = &barrier;
+                scope.spawn(move || {
+                    barrier.wait();
+                    let done = worker();
+                    (done, Instant::now())
+                })
+            })
+            .collect();
+        barrier.wait();
+        let started = Instant::now();
+        let mut cancelled = false;
+        while !handles.iter().all(|handle| handle.is_finished()) {
+            if !cancelled && cancellation.is_cancelled() {
+                cancelled = true;
+                stop.store(true, Ordering::Relaxed);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+        // 按最后一个线程自己记录的结束时间计算耗时，不受轮询间隔影响
+        let mut finished = started;
+        let mut counts = Vec::with_capacity(handles.len());
+        for handle in handles {
+            let (done, at) = handle.join().unwrap_or((0, started));
+            finished = finished.max(at);
+            counts.push(done);
+        }
+        (finished.duration_since(started), counts, cancelled)
+    })
+}
+
+// 一次测量：无竞争或有竞争下完成的操作数和耗时
+struct Run {
+    operations: u64,
+    elapsed: Duration,
+    cancelled: bool,
+}
+
+impl Run {
+    fn ops_per_second(&self) -> f64 {
+        self.operations as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+fn run_mutex(plan: &[u64], contended: bool, stop: &AtomicBool, cancellation: &CancellationToken) -> Result<Run, BenchmarkError> {
+    let counters: Vec<Padded<Mutex<u64>>> = (0..if contended { 1 } else { plan.len() }).map(|_| Padded::default()).collect();
+    let workers: Vec<_> = plan
+        .iter()
+        .enumerate()
+        .map(|(index, &count)| {
+            let counter = &counters[if contended { 0 } else { index }].0;
+            move || {
+                repeat(count, stop, || {
+                    let mut value = counter.lock().unwrap();
+                    *value = black_box(*value + 1);
+                })
+            }
+        })
+        .collect();
+    let (elapsed, counts, cancelled) = run_workers(workers, stop, cancellation);
+    let operations: u64 = counts.iter().sum();
+    let counted: u64 = counters.iter().map(|counter| *counter.0.lock().unwrap()).sum();
+    if counted != operations {
+        return Err(BenchmarkError::CpuTestError(format!("Mutex计数为{}，应为{}", counted, operations)));
+    }
+    Ok(Run { operations, elapsed, cancelled })
+}
+
+fn run_atomic(plan: &[u64], contended: bool, stop: &AtomicBool, cancellation: &CancellationToken) -> Result<Run, BenchmarkError> {
+    let counters: Vec<Padded<AtomicU64>> = (0..if contended { 1 } else { plan.len() }).map(|_| Padded::default()).collect();
+    let workers: Vec<_> = plan
+        .iter()
+        .enumerate()
+        .map(|(index, &count)| {
+            let counter = &counters[if contended { 0 } else { index }].0;
+            move || {
+                repeat(count, stop, || {
+                    black_box(counter.fetch_add(1, Ordering::Relaxed));
+                })
+            }
+        })
+        .collect();
+    let (elapsed, counts, cancelled) = run_workers(workers, stop, cancellation);
+    let operations: u64 = counts.iter().sum();
+    let counted: u64 = counters.iter().map(|counter| counter.0.load(Ordering::Relaxed)).sum();
+    if counted != operations {
+        return Err(BenchmarkError::CpuTestError(format!("原子计数为{}，应为{}", counted, operations)));
+    }
+    Ok(Run { operations, elapsed, cancelled })
+}
+
+// 无竞争时每个线程向自己的通道发送后立即接收；有竞争时所有线程向同一个有界通道发送，
+// 由一个额外的线程接收，操作数按接收到的消息计
+fn run_channel(plan: &[u64], contended: bool, stop: &AtomicBool, cancellation: &CancellationToken) -> Result<Run, BenchmarkError> {
+    if !contended {
+        let workers: Vec<_> = plan
+            .iter()
+            .map(|&count| {
+                move || {
+                    let (sender, receiver) = mpsc::channel::<u64>();
+                    repeat(count, stop, || {
+                        let _ = sender.send(black_box(1));
+                        black_box(receiver.recv().ok());
+                    })
+                }
+            })
+            .collect();
+        let (elapsed, counts, cancelled) = run_workers(workers, stop, cancellation);
+        return Ok(Run { operations: counts.iter().sum(), elapsed, cancelled });
+    }
+
+    let (sender, receiver) = mpsc::sync_channel::<u64>(CHANNEL_CAPACITY);
+    let received = AtomicU64::new(0);
+    let mut workers: Vec<Box<dyn FnOnce() -> u64 + Send + '_>> = plan
+        .iter()
+        .map(|&count| {
+            let sender = sender.clone();
+            Box::new(move || repeat(count, stop, || {
+                let _ = sender.send(black_box(1));
+            })) as Box<dyn FnOnce() -> u64 + Send>
+        })
+        .collect();
+    drop(sender);
+    let received_ref = &received;
+    // 所有发送方结束（包括因取消提前结束）后通道关闭，接收线程随之退出
+    workers.push(Box::new(move || {
+        let count = receiver.iter().map(black_box).count() as u64;
+        received_ref.store(count, Ordering::Relaxed);
+        0
+    }));
+    let (elapsed, counts, cancelled) = run_workers(workers, stop, cancellation);
+    let sent: u64 = counts.iter().sum();
+    let operations = received.load(Ordering::Relaxed);
+    if sent != operations {
+        return Err(BenchmarkError::CpuTestError(format!("通道收到{}条消息，发送了{}条", operations, sent)));
+    }
+    Ok(Run { operations, elapsed, cancelled })
+}
+
+pub struct SyncBenchmark {
+    config: SyncTestConfig,
+}
+
+impl SyncBenchmark {
+    pub fn new(config: SyncTestConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn get_config(&self) -> &SyncTestConfig {
+        &self.config
+    }
+
+    // 0换成逻辑核心数，去掉重复的线程数
+    fn thread_counts(&self) -> Vec<usize> {
+        let logical = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        let mut counts: Vec<usize> = Vec::new();
+        for count in &self.config.thread_counts {
+            let count = if *count == 0 { logical } else { *count };
+            if !counts.contains(&count) {
+                counts.push(count);
+            }
+        }
+        counts
+    }
+
+    pub fn run_benchmark(&self) -> Result<SyncBenchResult, BenchmarkError> {
+        self.run_benchmark_cancellable(|_progress, _message| {}, &CancellationToken::never())
+    }
+
+    /// 每种原语和线程数先测无竞争再测有竞争，两次的总操作数相同
+    pub fn run_benchmark_cancellable<F>(&self, progress_callback: F, cancellation: &CancellationToken) -> Result<SyncBenchResult, BenchmarkError>
+    where
+        F: Fn(f64, Message),
+    {
+        let started = Instant::now();
+        let thread_counts = self.thread_counts();
+        let total = (self.config.primitives.len() * thread_counts.len()).max(1);
+        let mut measurements = Vec::with_capacity(total);
+        for primitive in self.config.primitives.iter().copied() {
+            for &threads in &thread_counts {
+                let progress = measurements.len() as f64 / total as f64 * 100.0;
+                cancellation.check(TestType::Extra, progress)?;
+                progress_callback(
+                    progress,
+                    Message::progress("sync.progress", progress)
+                        .param("primitive", primitive.as_str())
+                        .param("threads", threads),
+                );
+
+                let plan = split_operations(self.config.operations, threads);
+                let stop = AtomicBool::new(false);
+                let run = |contended| match primitive {
+                    SyncPrimitive::Mutex => run_mutex(&plan, contended, &stop, cancellation),
+                    SyncPrimitive::Atomic => run_atomic(&plan, contended, &stop, cancellation),
+                    SyncPrimitive::Channel => run_channel(&plan, contended, &stop, cancellation),
+                };
+                // 中途取消的测量不完整，不计入结果
+                let cancelled = || BenchmarkError::Cancelled {
+                    test: TestType::Extra.as_str().to_string(),
+                    at_progress: progress,
+                };
+                let uncontended = run(false)?;
+                if uncontended.cancelled {
+                    return Err(cancelled());
+                }
+                let contended = run(true)?;
+                if contended.cancelled {
+                    return Err(cancelled());
+                }
+
+                let uncontended_ops_per_second = uncontended.ops_per_second();
+                let contended_ops_per_second = contended.ops_per_second();
+                measurements.push(SyncMeasurement {
+                    primitive,
+                    threads,
+                    operations: contended.operations,
+                    uncontended_ops_per_second,
+                    contended_ops_per_second,
+                    contention_penalty: uncontended_ops_per_second / contended_ops_per_second,
+                });
+            }
+        }
+        progress_callback(100.0, Message::new("sync.complete"));
+        Ok(SyncBenchResult {
+            measurements,
+            test_duration: started.elapsed().as_secs_f64(),
+        })
+    }
+}
+
+impl BenchmarkRunner for SyncBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Extra
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        let runs = (self.config.primitives.len() * self.thread_counts().len() * 2) as f64;
+        (runs * self.config.operations as f64 / ESTIMATED_OPS_PER_SECOND).ceil().max(1.0) as u64
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        let result = self.run_benchmark_cancellable(ctx.progress_callback(), &ctx.cancellation)?;
+        Ok(TestOutcome::Completed(TestResultPayload::Extra(ExtraTestResult {
+            name: SYNC_TEST_NAME.to_string(),
+            result: serde_json::to_value(&result)
+                .map_err(|e| BenchmarkError::DataSaveError(format!("测试结果序列化失败: {}", e)))?,
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_operations_are_split_fairly() {
+        assert_eq!(split_operations(10, 4), [3, 3, 2, 2]);
+        assert_eq!(split_operations(8, 4), [2, 2, 2, 2]);
+        assert_eq!(split_operations(5, 0), [5]);
+    }
+
+    #[test]
+    fn test_contention_costs_throughput() {
+        let config = SyncTestConfig {
+            operations: 200_000,
+            thread_counts: vec![2, 4],
+            ..SyncTestConfig::default()
+        };
+        let result = SyncBenchmark::new(config).run_benchmark().unwrap();
+        assert_eq!(result.measurements.len(), 6);
+        // 单核机器上线程轮流运行，共享的实例不会真正被争用，两种情况只差在噪声以内
+        let parallel = thread::available_parallelism().map(|n| n.get()).unwrap_or(1) > 1;
+        for measurement in &result.measurements {
+            assert_eq!(measurement.operations, 200_000, "{:?}", measurement);
+            assert!(measurement.contention_penalty > 0.0);
+            if parallel {
+                assert!(
+                    measurement.contended_ops_per_second <= measurement.uncontended_ops_per_second * 1.1,
+                    "{:?}",
+                    measurement
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_cancellation_stops_workers() {
+        let token = CancellationToken::new(|| true);
+        let error = SyncBenchmark::new(SyncTestConfig::default()).run_benchmark_cancellable(|_, _| {}, &token).unwrap_err();
+        assert_eq!(error.code(), "CANCELLED");
+
+        // 运行中途取消：工作线程在下一次检查时停止
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+        let token = CancellationToken::new(move || flag.load(Ordering::SeqCst));
+        let config = SyncTestConfig {
+            operations: u64::MAX / 2,
+            thread_counts: vec![2],
+            primitives: vec![SyncPrimitive::Channel],
+        };
+        let started = Instant::now();
+        let error = SyncBenchmark::new(config)
+            .run_benchmark_cancellable(move |_, _| cancelled.store(true, Ordering::SeqCst), &token)
+            .unwrap_err();
+        assert_eq!(error.code(), "CANCELLED");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -1,18 +1,22 @@
 use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::power::{detect_power_info, PowerInfo};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use sysinfo::System;
-use std::collections::HashMap;
+use sysinfo::{Components, DiskKind, Disks, System};
+use std::collections::{HashMap, HashSet};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SystemInfo {
     pub os: String,
     pub cpu: CpuInfo,
     pub memory: MemoryInfo,
     pub storage: Vec<StorageInfo>,
     pub system_details: SystemDetails,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power: Option<PowerInfo>, // 当前电源计划，无法识别时为空；为空时不序列化，旧结果的完整性哈希保持不变
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CpuInfo {
     pub name: String,
     pub vendor: String,
@@ -22,9 +26,11 @@ pub struct CpuInfo {
     pub max_frequency: u64,
     pub architecture: String,
     pub cache_info: CacheInfo,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<String>, // 与基准测试相关的指令集扩展，如aes、sha、avx2；为空时不序列化，旧结果的完整性哈希保持不变
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MemoryInfo {
     pub total: u64, // GB
     pub available: u64, // GB
@@ -35,7 +41,7 @@ pub struct MemoryInfo {
     pub slots_total: usize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct StorageInfo {
     pub name: String,
     pub storage_type: StorageType,
@@ -44,9 +50,11 @@ pub struct StorageInfo {
     pub interface: String,
     pub file_system: String,
     pub mount_point: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub removable: bool, // U盘、SD卡等可移动设备；为false时不序列化，旧结果的完整性哈希保持不变
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum StorageType {
     SSD,
     HDD,
@@ -54,7 +62,7 @@ pub enum StorageType {
     Unknown,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CacheInfo {
     pub l1_data: Option<u64>, // KB
     pub l1_instruction: Option<u64>, // KB
@@ -62,14 +70,38 @@ pub struct CacheInfo {
     pub l3: Option<u64>, // KB
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SystemDetails {
     pub hostname: String,
     pub uptime: u64, // seconds
     pub boot_time: u64, // timestamp
     pub kernel_version: String,
     pub total_processes: usize,
-    pub temperatures: HashMap<String, f32>, // component -> temperature
+    pub temperatures: HashMap<String, f32>, // component -> temperature (°C)，平台不提供传感器时为空
+}
+
+impl SystemInfo {
+    /// 硬件指纹：只由硬件相关字段计算（不含主机名和系统版本），
+    /// 用于在历史记录中识别同一台机器
+    pub fn hardware_fingerprint(&self) -> String {
+        let source = format!(
+            "{}|{}|{}|{}|{}|{}",
+            self.cpu.name,
+            self.cpu.vendor,
+            self.cpu.cores,
+            self.cpu.threads,
+            self.cpu.architecture,
+            self.memory.total,
+        );
+
+        // FNV-1a 64位哈希，结果在不同平台和Rust版本之间保持稳定
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in source.bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        format!("{:016x}", hash)
+    }
 }
 
 pub fn collect_system_info() -> Result<SystemInfo, BenchmarkError> {
@@ -100,9 +132,39 @@ pub fn collect_system_info() -> Result<SystemInfo, BenchmarkError> {
         memory: memory_info,
         storage: storage_info,
         system_details,
+        power: Some(detect_power_info()).filter(|power| power.active_plan.is_some()),
     })
 }
 
+/// 运行时检测的指令集扩展，名称与Rust的target_feature一致
+pub fn detect_cpu_features() -> Vec<String> {
+    #[allow(unused_mut)]
+    let mut features: Vec<&str> = Vec::new();
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        macro_rules! detect {
+            ($($feature:tt),*) => {
+                $(if std::arch::is_x86_feature_detected!($feature) {
+                    features.push($feature);
+                })*
+            };
+        }
+        detect!("aes", "pclmulqdq", "sha", "sse4.2", "avx", "avx2", "avx512f");
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        macro_rules! detect {
+            ($($feature:tt),*) => {
+                $(if std::arch::is_aarch64_feature_detected!($feature) {
+                    features.push($feature);
+                })*
+            };
+        }
+        detect!("neon", "aes", "pmull", "sha2", "sha3");
+    }
+    features.into_iter().map(str::to_string).collect()
+}
+
 fn collect_cpu_info(sys: &System) -> Result<CpuInfo, BenchmarkError> {
     if let Some(cpu) = sys.cpus().first() {
         let brand = cpu.brand().to_string();
@@ -138,6 +200,7 @@ fn collect_cpu_info(sys: &System) -> Result<CpuInfo, BenchmarkError> {
             max_frequency: cpu.frequency() as u64, // sysinfo doesn't provide max frequency
             architecture,
             cache_info,
+            features: detect_cpu_features(),
         })
     } else {
         Err(BenchmarkError::SystemInfoError("无法获取CPU信息".to_string()))
@@ -160,17 +223,63 @@ fn collect_memory_info(sys: &System) -> MemoryInfo {
     }
 }
 
+// 内存文件系统、容器层和只读镜像等伪文件系统，不对应实际的存储设备
+const PSEUDO_FILE_SYSTEMS: [&str; 12] = [
+    "tmpfs", "devtmpfs", "ramfs", "overlay", "squashfs", "proc", "sysfs", "devfs", "autofs", "efivarfs", "cgroup", "cgroup2",
+];
+
+// 网络挂载，容量和速度取决于远端
+const NETWORK_FILE_SYSTEMS: [&str; 10] = ["nfs", "nfs4", "cifs", "smbfs", "smb2", "smb3", "afpfs", "sshfs", "fuse.sshfs", "9p"];
+
+fn is_local_file_system(file_system: &str) -> bool {
+    let file_system = file_system.to_lowercase();
+    !PSEUDO_FILE_SYSTEMS.contains(&file_system.as_str()) && !NETWORK_FILE_SYSTEMS.contains(&file_system.as_str())
+}
+
 fn collect_storage_info(_sys: &System) -> Vec<StorageInfo> {
-    // 暂时返回空的存储信息，等待sysinfo API修复
-    vec![StorageInfo {
-        name: "Primary Storage".to_string(),
-        storage_type: StorageType::Unknown,
-        capacity: 0,
-        available: 0,
-        interface: "Unknown".to_string(),
-        file_system: "Unknown".to_string(),
-        mount_point: "/".to_string(),
-    }]
+    let disks = Disks::new_with_refreshed_list();
+    let mut seen = HashSet::new();
+    let mut storage = Vec::new();
+    for disk in disks.list() {
+        let file_system = disk.file_system().to_string_lossy().to_string();
+        if !is_local_file_system(&file_system) {
+            continue;
+        }
+        let mount_point = disk.mount_point().to_string_lossy().to_string();
+        // Windows上没有卷标的分区名称为空，用盘符代替
+        let name = match disk.name().to_string_lossy() {
+            name if name.is_empty() => mount_point.clone(),
+            name => name.to_string(),
+        };
+        // 同一设备的绑定挂载（如容器中的/etc/hosts）只保留第一个挂载点
+        if !seen.insert(name.clone()) {
+            continue;
+        }
+        // 名称中能看出类型时以名称为准（系统把NVMe报告为SSD），否则取系统报告的介质类型
+        let storage_type = match determine_storage_type(&name, &mount_point) {
+            StorageType::Unknown => match disk.kind() {
+                DiskKind::SSD => StorageType::SSD,
+                DiskKind::HDD => StorageType::HDD,
+                DiskKind::Unknown(_) => StorageType::Unknown,
+            },
+            detected => detected,
+        };
+        let interface = match storage_type {
+            StorageType::NVMe => "NVMe",
+            _ => "Unknown",
+        };
+        storage.push(StorageInfo {
+            name,
+            storage_type,
+            capacity: disk.total_space() / (1024 * 1024 * 1024), // Convert to GB
+            available: disk.available_space() / (1024 * 1024 * 1024), // Convert to GB
+            interface: interface.to_string(),
+            file_system,
+            mount_point,
+            removable: disk.is_removable(),
+        });
+    }
+    storage
 }
 
 fn determine_storage_type(name: &str, mount_point: &str) -> StorageType {
@@ -189,10 +298,23 @@ fn determine_storage_type(name: &str, mount_point: &str) -> StorageType {
     }
 }
 
+/// 各温度传感器的当前读数，同名的传感器（如多个NVMe）取最高值
+fn collect_temperatures() -> HashMap<String, f32> {
+    let mut temperatures = HashMap::new();
+    for component in Components::new_with_refreshed_list().iter() {
+        let Some(temperature) = component.temperature().filter(|temperature| temperature.is_finite()) else {
+            continue;
+        };
+        temperatures
+            .entry(component.label().to_string())
+            .and_modify(|current: &mut f32| *current = current.max(temperature))
+            .or_insert(temperature);
+    }
+    temperatures
+}
+
 fn collect_system_details(sys: &System) -> SystemDetails {
-    let temperatures = HashMap::new();
-    
-    // 暂时不收集温度信息，等待sysinfo API修复
+    let temperatures = collect_temperatures();
 
     SystemDetails {
         hostname: System::host_name().unwrap_or_else(|| "Unknown".to_string()),
@@ -224,6 +346,20 @@ mod tests {
         assert!(!system_info.system_details.hostname.is_empty(), "主机名不应为空");
     }
 
+    #[test]
+    fn test_hardware_fingerprint_ignores_host_details() {
+        let info = crate::benchmark::test_support::sample_system_info();
+        let mut other_host = info.clone();
+        other_host.os = "Other OS".to_string();
+        other_host.system_details.hostname = "other-host".to_string();
+        assert_eq!(info.hardware_fingerprint(), other_host.hardware_fingerprint());
+
+        let mut other_cpu = info.clone();
+        other_cpu.cpu.cores = 16;
+        assert_ne!(info.hardware_fingerprint(), other_cpu.hardware_fingerprint());
+        assert_eq!(info.hardware_fingerprint().len(), 16);
+    }
+
     #[test]
     fn test_determine_storage_type() {
         assert!(matches!(determine_storage_type("nvme0n1", "/"), StorageType::NVMe));
@@ -261,11 +397,33 @@ mod tests {
         let sys = System::new_all();
         let storage_info = collect_storage_info(&sys);
         
-        // 暂时只验证基本结构
         assert!(!storage_info.is_empty(), "存储信息不应为空");
-        for storage in storage_info {
+        // 实际的机器上至少有一个磁盘能读到容量
+        assert!(storage_info.iter().any(|storage| storage.capacity > 0), "{:?}", storage_info);
+        for storage in &storage_info {
             assert!(!storage.name.is_empty(), "存储设备名称不应为空");
             assert!(!storage.mount_point.is_empty(), "挂载点不应为空");
+            assert!(storage.available <= storage.capacity, "可用空间不应超过总容量");
+            assert!(is_local_file_system(&storage.file_system), "{} 不应列出", storage.file_system);
+        }
+    }
+
+    #[test]
+    fn test_component_temperatures_are_plausible() {
+        // 虚拟机和容器中通常没有温度传感器，此时为空
+        for (label, temperature) in collect_temperatures() {
+            assert!(!label.is_empty(), "传感器名称不应为空");
+            assert!((0.0..=120.0).contains(&temperature), "{}: {}°C", label, temperature);
+        }
+    }
+
+    #[test]
+    fn test_pseudo_and_network_file_systems_are_excluded() {
+        for file_system in ["ext4", "apfs", "NTFS", "btrfs", "vfat"] {
+            assert!(is_local_file_system(file_system), "{}", file_system);
+        }
+        for file_system in ["tmpfs", "overlay", "squashfs", "nfs4", "cifs", "fuse.sshfs", "SMBFS"] {
+            assert!(!is_local_file_system(file_system), "{}", file_system);
         }
     }
 }
\ No newline at end of file
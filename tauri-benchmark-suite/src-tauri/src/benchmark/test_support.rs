@@ -0,0 +1,191 @@
+//! 单元测试共用的合成数据
+use crate::benchmark::core::{extra_config, BenchmarkConfig, BenchmarkRegistry, BenchmarkRunner, RunContext, TestOutcome, TestResult};
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::cpu::CpuTestResult;
+use crate::benchmark::memory::MemoryTestResult;
+use crate::benchmark::schema::RESULT_SCHEMA_VERSION;
+use crate::benchmark::storage::{StorageMetrics, StorageTestResult};
+use crate::benchmark::system_info::{
+    CacheInfo, CpuInfo, MemoryInfo, StorageInfo, StorageType, SystemDetails, SystemInfo,
+};
+use crate::i18n::Message;
+use crate::ipc::{ExtraTestResult, TestResultPayload, TestType};
+use std::collections::{BTreeMap, HashMap};
+
+pub fn sample_system_info() -> SystemInfo {
+    SystemInfo {
+        os: "Test OS 1.0".to_string(),
+        cpu: CpuInfo {
+            name: "Test CPU".to_string(),
+            vendor: "Unknown".to_string(),
+            cores: 4,
+            threads: 8,
+            base_frequency: 2400,
+            max_frequency: 3600,
+            architecture: "x86_64".to_string(),
+            cache_info: CacheInfo {
+                l1_data: None,
+                l1_instruction: None,
+                l2: None,
+                l3: None,
+            },
+            features: vec!["aes".to_string(), "avx2".to_string()],
+        },
+        memory: MemoryInfo {
+            total: 16,
+            available: 8,
+            used: 8,
+            memory_type: "DDR4".to_string(),
+            speed: 3200,
+            slots_used: 2,
+            slots_total: 4,
+        },
+        storage: vec![StorageInfo {
+            name: "Test Disk".to_string(),
+            storage_type: StorageType::SSD,
+            capacity: 512,
+            available: 256,
+            interface: "Unknown".to_string(),
+            file_system: "ext4".to_string(),
+            mount_point: "/".to_string(),
+            removable: false,
+        }],
+        system_details: SystemDetails {
+            hostname: "test-host".to_string(),
+            uptime: 3600,
+            boot_time: 1_700_000_000,
+            kernel_version: "6.0.0".to_string(),
+            total_processes: 100,
+            temperatures: HashMap::new(),
+        },
+        power: None,
+    }
+}
+
+pub fn sample_cpu_result() -> CpuTestResult {
+    CpuTestResult {
+        single_thread_score: 100.0,
+        multi_thread_score: 200.0,
+        floating_point_score: 150.0,
+        average_temperature: Some(50.0),
+        max_temperature: Some(60.0),
+        test_duration: 60,
+        operations_per_second: 1000,
+    }
+}
+
+pub fn sample_memory_result() -> MemoryTestResult {
+    MemoryTestResult {
+        sequential_read_speed: 1000.0,
+        sequential_write_speed: 800.0,
+        random_access_speed: 500.0,
+        latency: 100.0,
+        memory_usage_peak: 1024,
+        error_rate: 0.0,
+        test_duration: 30,
+        interference: None,
+    }
+}
+
+pub fn sample_storage_result() -> StorageTestResult {
+    let metrics = |throughput: f64, iops: u64| StorageMetrics {
+        throughput,
+        iops,
+        latency: 0.5,
+    };
+    StorageTestResult {
+        sequential_read: metrics(500.0, 4000),
+        sequential_write: metrics(400.0, 3000),
+        random_read: metrics(50.0, 12000),
+        random_write: metrics(40.0, 10000),
+        test_duration: 60,
+        total_data_processed: 4096,
+        small_files: None,
+        archive: None,
+    }
+}
+
+/// 生成包含全部三类结果的合成测试结果
+pub fn sample_test_result(timestamp: &str, overall_score: f64) -> TestResult {
+    TestResult {
+        schema_version: RESULT_SCHEMA_VERSION,
+        timestamp: timestamp.to_string(),
+        system_info: sample_system_info(),
+        cpu_results: Some(sample_cpu_result()),
+        memory_results: Some(sample_memory_result()),
+        storage_results: Some(sample_storage_result()),
+        gpu_results: None,
+        webview_results: None,
+        network_results: None,
+        overall_score,
+        category_scores: BTreeMap::new(),
+        imported: false,
+        auto_duration: None,
+        app_info: None,
+        failure_log: Vec::new(),
+        warnings: Vec::new(),
+        config_used: None,
+        per_test_durations: Default::default(),
+        session_id: None,
+        runs: Vec::new(),
+        partial: false,
+        extra_results: Default::default(),
+        regression_check: None,
+        integrity: None,
+        identity: None,
+    }
+}
+
+// 并行运行的单元测试互相争用CPU，实际耗时可能是估计的许多倍
+const SUITE_TIMEOUT_FACTOR: f64 = 100.0;
+
+/// 实际运行内置测试的快速配置，放宽看门狗时限使结果不受并行测试负载影响；超时行为由看门狗的专门测试覆盖
+pub fn quick_suite_config() -> BenchmarkConfig {
+    BenchmarkConfig {
+        per_test_timeout_factor: SUITE_TIMEOUT_FACTOR,
+        ..BenchmarkConfig::quick()
+    }
+}
+
+/// 扩展测试示例：按配置休眠后返回固定分数
+pub struct SleepRunner {
+    pub millis: u64,
+    pub score: f64,
+}
+
+impl SleepRunner {
+    /// 以"sleep"注册，配置如 {"millis": 50, "score": 7.5}
+    pub fn register(registry: &mut BenchmarkRegistry) {
+        registry.register("sleep", |config| {
+            #[derive(serde::Deserialize)]
+            struct SleepConfig {
+                millis: u64,
+                score: f64,
+            }
+            let config: SleepConfig = extra_config("sleep", config)?;
+            Ok(Box::new(SleepRunner {
+                millis: config.millis,
+                score: config.score,
+            }))
+        });
+    }
+}
+
+impl BenchmarkRunner for SleepRunner {
+    fn name(&self) -> TestType {
+        TestType::Extra
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        1
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        (ctx.progress)(0.0, Message::new("extra.start").param("name", "sleep"));
+        std::thread::sleep(std::time::Duration::from_millis(self.millis));
+        Ok(TestOutcome::Completed(TestResultPayload::Extra(ExtraTestResult {
+            name: "sleep".to_string(),
+            result: serde_json::json!({ "score": self.score }),
+        })))
+    }
+}
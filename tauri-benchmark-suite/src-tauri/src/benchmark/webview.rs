@@ -0,0 +1,422 @@
+//! WebView/JS引擎测试：后端通过事件请求前端运行一组固定的JS负载（DOM节点创建、canvas填充、JSON序列化、WASM计算核心），
+//! 前端通过submit_webview_results提交计时。每次请求带一个nonce，只接受与当前请求nonce相同的第一次提交，
+//! 重复、过期或超时后才到达的提交都被拒绝
+use crate::benchmark::core::{BenchmarkRunner, CancellationToken, RunContext, TestOutcome};
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::estimate::webview_test_seconds;
+use crate::i18n::Message;
+use crate::ipc::{TestResultPayload, TestType};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+const CANCELLATION_POLL_INTERVAL: Duration = Duration::from_millis(100); // 等待前端期间检查取消的间隔
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WebviewWorkload {
+    DomNodes, // 创建并插入DOM节点，操作数为节点数
+    CanvasFill, // 在2D canvas上填充矩形，操作数为填充次数
+    JsonStringify, // JSON.stringify一组固定对象，操作数为序列化次数
+    WasmKernel, // 内嵌的WASM整数循环，操作数为循环次数
+}
+
+impl WebviewWorkload {
+    pub const ALL: [WebviewWorkload; 4] = [
+        WebviewWorkload::DomNodes,
+        WebviewWorkload::CanvasFill,
+        WebviewWorkload::JsonStringify,
+        WebviewWorkload::WasmKernel,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WebviewWorkload::DomNodes => "dom_nodes",
+            WebviewWorkload::CanvasFill => "canvas_fill",
+            WebviewWorkload::JsonStringify => "json_stringify",
+            WebviewWorkload::WasmKernel => "wasm_kernel",
+        }
+    }
+}
+
+// 省略的字段取默认值；别名兼容前端的驼峰字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct WebviewTestConfig {
+    pub workloads: Vec<WebviewWorkload>,
+    pub iterations: u32, // 每项负载的重复次数，前端报告合计的操作数和耗时
+    #[serde(alias = "timeoutSeconds")]
+    pub timeout_seconds: u64, // 前端在这段时间内没有提交结果时测试失败
+}
+
+impl Default for WebviewTestConfig {
+    fn default() -> Self {
+        Self {
+            workloads: WebviewWorkload::ALL.to_vec(),
+            iterations: 5,
+            timeout_seconds: 60,
+        }
+    }
+}
+
+/// 通过webview-benchmark-request事件发给前端的请求
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebviewBenchmarkRequest {
+    pub nonce: String, // 提交结果时原样带回
+    pub workloads: Vec<WebviewWorkload>,
+    pub iterations: u32,
+    pub timeout_ms: u64, // 超过这个时间后提交会被拒绝
+}
+
+/// 前端测得的一项负载
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebviewTiming {
+    pub workload: WebviewWorkload,
+    pub operations: u64,
+    pub duration_ms: f64,
+}
+
+/// submit_webview_results的参数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebviewSubmission {
+    pub nonce: String,
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    pub timings: Vec<WebviewTiming>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct WebviewWorkloadResult {
+    pub workload: WebviewWorkload,
+    pub operations: u64,
+    pub duration_ms: f64,
+    pub ops_per_second: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct WebviewTestResult {
+    pub workloads: Vec<WebviewWorkloadResult>, // 按请求中的顺序
+    #[serde(default)]
+    pub user_agent: Option<String>, // 前端报告的WebView版本
+    pub test_duration: f64, // seconds，从发出请求到收到结果
+}
+
+impl WebviewTestResult {
+    /// 各负载每秒千次操作的平均值，单独作为总体评分的一部分
+    pub fn score(&self) -> f64 {
+        if self.workloads.is_empty() {
+            return 0.0;
+        }
+        self.workloads.iter().map(|workload| workload.ops_per_second / 1000.0).sum::<f64>() / self.workloads.len() as f64
+    }
+}
+
+// 发出后尚未结束的请求；result在收到有效提交后填入，等待方取走后整个请求结束
+struct Pending {
+    nonce: String,
+    workloads: Vec<WebviewWorkload>,
+    issued: Instant,
+    result: Option<WebviewTestResult>,
+}
+
+/// 请求和提交的对应关系。同一时间最多一个请求，请求结束（取走结果、超时或取消）后它的nonce即失效
+#[derive(Default)]
+pub struct WebviewCoordinator {
+    pending: Mutex<Option<Pending>>,
+    submitted: Condvar,
+}
+
+impl WebviewCoordinator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 开始一次请求，上一次请求尚未结束时返回错误
+    pub fn begin(&self, config: &WebviewTestConfig) -> Result<WebviewBenchmarkRequest, BenchmarkError> {
+        if config.workloads.is_empty() {
+            return Err(BenchmarkError::WebviewTestError("至少需要一项负载".to_string()));
+        }
+        let mut pending = self.pending.lock().unwrap();
+        if pending.is_some() {
+            return Err(BenchmarkError::WebviewTestError("已有正在进行的WebView测试".to_string()));
+        }
+        let nonce = Uuid::new_v4().to_string();
+        *pending = Some(Pending {
+            nonce: nonce.clone(),
+            workloads: config.workloads.clone(),
+            issued: Instant::now(),
+            result: None,
+        });
+        Ok(WebviewBenchmarkRequest {
+            nonce,
+            workloads: config.workloads.clone(),
+            iterations: config.iterations,
+            timeout_ms: config.timeout_seconds * 1000,
+        })
+    }
+
+    /// 校验并记录前端的提交。nonce与当前请求不符（过期、伪造或请求已结束）或该请求已有结果时拒绝
+    pub fn submit(&self, submission: WebviewSubmission) -> Result<(), BenchmarkError> {
+        let mut guard = self.pending.lock().unwrap();
+        let pending = match guard.as_mut() {
+            Some(pending) if pending.nonce == submission.nonce => pending,
+            _ => return Err(BenchmarkError::WebviewTestError("提交的nonce已过期或不属于当前请求".to_string())),
+        };
+        if pending.result.is_some() {
+            return Err(BenchmarkError::WebviewTestError("该请求的结果已经提交过".to_string()));
+        }
+        pending.result = Some(normalize(&pending.workloads, pending.issued, submission)?);
+        self.submitted.notify_all();
+        Ok(())
+    }
+
+    /// 等待nonce对应的请求收到结果。超时或取消时请求随即结束，之后到达的提交被拒绝
+    pub fn wait(&self, nonce: &str, timeout: Duration, cancellation: &CancellationToken) -> Result<WebviewTestResult, BenchmarkError> {
+        let deadline = Instant::now() + timeout;
+        let mut guard = self.pending.lock().unwrap();
+        loop {
+            match guard.as_mut() {
+                Some(pending) if pending.nonce == nonce => {
+                    if let Some(result) = pending.result.take() {
+                        *guard = None;
+                        return Ok(result);
+                    }
+                }
+                _ => return Err(BenchmarkError::WebviewTestError("请求已结束".to_string())),
+            }
+            if cancellation.is_cancelled() {
+                *guard = None;
+                return Err(BenchmarkError::Cancelled {
+                    test: TestType::Webview.as_str().to_string(),
+                    at_progress: 0.0,
+                });
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                *guard = None;
+                return Err(BenchmarkError::WebviewTestError(format!("前端在{}秒内没有提交结果", timeout.as_secs())));
+            }
+            guard = self.submitted.wait_timeout(guard, (deadline - now).min(CANCELLATION_POLL_INTERVAL)).unwrap().0;
+        }
+    }
+
+    /// 结束nonce对应的请求而不等待结果，用于请求没能发出时
+    pub fn abandon(&self, nonce: &str) {
+        let mut pending = self.pending.lock().unwrap();
+        if pending.as_ref().is_some_and(|pending| pending.nonce == nonce) {
+            *pending = None;
+        }
+    }
+}
+
+// 每项请求的负载都必须恰好出现一次，操作数和耗时为正数；多出的负载视为无效提交
+fn normalize(requested: &[WebviewWorkload], issued: Instant, submission: WebviewSubmission) -> Result<WebviewTestResult, BenchmarkError> {
+    let invalid = |message: String| Err(BenchmarkError::WebviewTestError(message));
+    if let Some(timing) = submission.timings.iter().find(|timing| !requested.contains(&timing.workload)) {
+        return invalid(format!("提交了未请求的负载{}", timing.workload.as_str()));
+    }
+    let mut workloads = Vec::with_capacity(requested.len());
+    for &workload in requested {
+        let mut timings = submission.timings.iter().filter(|timing| timing.workload == workload);
+        let timing = match (timings.next(), timings.next()) {
+            (Some(timing), None) => timing,
+            (None, _) => return invalid(format!("缺少负载{}的结果", workload.as_str())),
+            (Some(_), Some(_)) => return invalid(format!("负载{}的结果重复", workload.as_str())),
+        };
+        if timing.operations == 0 || !timing.duration_ms.is_finite() || timing.duration_ms <= 0.0 {
+            return invalid(format!("负载{}的计时无效", workload.as_str()));
+        }
+        workloads.push(WebviewWorkloadResult {
+            workload,
+            operations: timing.operations,
+            duration_ms: timing.duration_ms,
+            ops_per_second: timing.operations as f64 / (timing.duration_ms / 1000.0),
+        });
+    }
+    Ok(WebviewTestResult {
+        workloads,
+        user_agent: submission.user_agent,
+        test_duration: issued.elapsed().as_secs_f64(),
+    })
+}
+
+/// 把请求发给前端的方式，应用中为发送webview-benchmark-request事件
+pub type WebviewEmitter = Arc<dyn Fn(&WebviewBenchmarkRequest) -> Result<(), String> + Send + Sync>;
+
+/// 协调器加上发送请求的方式，套件和单独的命令共用同一个协调器
+#[derive(Clone)]
+pub struct WebviewBridge {
+    pub coordinator: Arc<WebviewCoordinator>,
+    emit: WebviewEmitter,
+}
+
+impl WebviewBridge {
+    pub fn new(coordinator: Arc<WebviewCoordinator>, emit: impl Fn(&WebviewBenchmarkRequest) -> Result<(), String> + Send + Sync + 'static) -> Self {
+        Self {
+            coordinator,
+            emit: Arc::new(emit),
+        }
+    }
+
+    /// 发出请求并阻塞等待前端提交结果
+    pub fn run(&self, config: &WebviewTestConfig, cancellation: &CancellationToken) -> Result<WebviewTestResult, BenchmarkError> {
+        let request = self.coordinator.begin(config)?;
+        if let Err(e) = (self.emit)(&request) {
+            self.coordinator.abandon(&request.nonce);
+            return Err(BenchmarkError::WebviewTestError(format!("无法向前端发送请求: {}", e)));
+        }
+        self.coordinator.wait(&request.nonce, Duration::from_millis(request.timeout_ms), cancellation)
+    }
+}
+
+pub struct WebviewBenchmark {
+    config: WebviewTestConfig,
+    bridge: Option<WebviewBridge>,
+}
+
+impl WebviewBenchmark {
+    pub fn new(config: WebviewTestConfig) -> Self {
+        Self { config, bridge: None }
+    }
+
+    /// 没有前端（如BenchmarkCore）时测试直接失败
+    pub fn with_bridge(mut self, bridge: WebviewBridge) -> Self {
+        self.bridge = Some(bridge);
+        self
+    }
+
+    pub fn get_config(&self) -> &WebviewTestConfig {
+        &self.config
+    }
+}
+
+impl BenchmarkRunner for WebviewBenchmark {
+    fn name(&self) -> TestType {
+        TestType::Webview
+    }
+
+    fn estimated_duration(&self) -> u64 {
+        webview_test_seconds(self.config.iterations, self.config.workloads.len()).ceil() as u64
+    }
+
+    fn run(&self, ctx: &RunContext) -> Result<TestOutcome<TestResultPayload>, BenchmarkError> {
+        let bridge = self
+            .bridge
+            .as_ref()
+            .ok_or_else(|| BenchmarkError::WebviewTestError("没有可以运行测试负载的前端".to_string()))?;
+        (ctx.progress)(0.0, Message::new("webview.waiting"));
+        let result = bridge.run(&self.config, &ctx.cancellation)?;
+        (ctx.progress)(100.0, Message::new("webview.complete"));
+        Ok(TestOutcome::Completed(TestResultPayload::Webview(result)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn timings(workloads: &[WebviewWorkload]) -> Vec<WebviewTiming> {
+        workloads
+            .iter()
+            .map(|&workload| WebviewTiming { workload, operations: 1000, duration_ms: 250.0 })
+            .collect()
+    }
+
+    fn config(timeout_seconds: u64) -> WebviewTestConfig {
+        WebviewTestConfig {
+            workloads: vec![WebviewWorkload::DomNodes, WebviewWorkload::JsonStringify],
+            timeout_seconds,
+            ..WebviewTestConfig::default()
+        }
+    }
+
+    // 模拟前端：收到请求后在另一个线程提交
+    fn bridge_answering(submit: impl Fn(&WebviewBenchmarkRequest) -> WebviewSubmission + Send + Sync + 'static) -> WebviewBridge {
+        let coordinator = Arc::new(WebviewCoordinator::new());
+        let target = coordinator.clone();
+        WebviewBridge::new(coordinator, move |request| {
+            let (target, submission) = (target.clone(), submit(request));
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(20));
+                let _ = target.submit(submission);
+            });
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_submission_is_normalized() {
+        let bridge = bridge_answering(|request| WebviewSubmission {
+            nonce: request.nonce.clone(),
+            user_agent: Some("WebKit".to_string()),
+            timings: timings(&request.workloads),
+        });
+        let result = bridge.run(&config(5), &CancellationToken::never()).unwrap();
+        let workloads: Vec<WebviewWorkload> = result.workloads.iter().map(|w| w.workload).collect();
+        assert_eq!(workloads, [WebviewWorkload::DomNodes, WebviewWorkload::JsonStringify]);
+        assert_eq!(result.workloads[0].ops_per_second, 4000.0);
+        assert_eq!(result.score(), 4.0);
+        assert_eq!(result.user_agent.as_deref(), Some("WebKit"));
+
+        // 请求结束后协调器空闲，可以开始下一次
+        assert!(bridge.run(&config(5), &CancellationToken::never()).is_ok());
+    }
+
+    #[test]
+    fn test_stale_duplicate_and_invalid_submissions_are_rejected() {
+        let coordinator = WebviewCoordinator::new();
+        let request = coordinator.begin(&config(5)).unwrap();
+        assert!(coordinator.begin(&config(5)).is_err(), "同一时间只能有一个请求");
+
+        let submission = |nonce: &str, timings| WebviewSubmission { nonce: nonce.to_string(), user_agent: None, timings };
+        let workloads = request.workloads.clone();
+        // 伪造的nonce、缺少负载、多出未请求的负载、非正的计时
+        assert!(coordinator.submit(submission("forged", timings(&workloads))).is_err());
+        assert!(coordinator.submit(submission(&request.nonce, timings(&workloads[..1]))).is_err());
+        assert!(coordinator.submit(submission(&request.nonce, timings(&WebviewWorkload::ALL))).is_err());
+        let mut zero = timings(&workloads);
+        zero[1].duration_ms = 0.0;
+        assert!(coordinator.submit(submission(&request.nonce, zero)).is_err());
+
+        // 第一次有效提交被接受，重复提交被拒绝
+        coordinator.submit(submission(&request.nonce, timings(&workloads))).unwrap();
+        let error = coordinator.submit(submission(&request.nonce, timings(&workloads))).unwrap_err();
+        assert_eq!(error.code(), "WEBVIEW_TEST_ERROR");
+        coordinator.wait(&request.nonce, Duration::from_secs(1), &CancellationToken::never()).unwrap();
+
+        // 请求结束后它的nonce失效
+        assert!(coordinator.submit(submission(&request.nonce, timings(&workloads))).is_err());
+    }
+
+    #[test]
+    fn test_timeout_and_cancellation_end_the_request() {
+        let coordinator = WebviewCoordinator::new();
+        let request = coordinator.begin(&config(1)).unwrap();
+        let error = coordinator.wait(&request.nonce, Duration::from_millis(200), &CancellationToken::never()).unwrap_err();
+        assert_eq!(error.code(), "WEBVIEW_TEST_ERROR");
+        // 超时后迟到的提交被拒绝，新的请求可以开始
+        let late = WebviewSubmission { nonce: request.nonce.clone(), user_agent: None, timings: timings(&request.workloads) };
+        assert!(coordinator.submit(late).is_err());
+
+        let request = coordinator.begin(&config(60)).unwrap();
+        let error = coordinator.wait(&request.nonce, Duration::from_secs(60), &CancellationToken::new(|| true)).unwrap_err();
+        assert_eq!(error.code(), "CANCELLED");
+        assert!(coordinator.begin(&config(60)).is_ok());
+
+        // 没有前端时套件中的测试直接失败
+        let ctx = RunContext {
+            progress: Arc::new(|_, _| {}),
+            cancellation: CancellationToken::never(),
+            warnings: Arc::new(|_, _| {}),
+            issues: crate::benchmark::core::RunIssues::default(),
+        };
+        let error = WebviewBenchmark::new(config(1)).run(&ctx).unwrap_err();
+        assert_eq!(error.code(), "WEBVIEW_TEST_ERROR");
+    }
+}
@@ -0,0 +1,247 @@
+//! 报告和摘要中数值的展示格式：千位分隔符、小数点、精度和字节单位的自动换算。
+//! 只用于给人看的文本，JSON导出和机器可读的输出保持原始数值
+use crate::benchmark::error::BenchmarkError;
+use crate::upload::{load_config, save_config};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+// 精度上限，更多位数对基准测试结果没有意义
+const MAX_PRECISION: u8 = 6;
+
+// 结果中的MB按1024*1024字节计算
+const BYTES_PER_MB: f64 = 1024.0 * 1024.0;
+
+const SI_UNITS: [&str; 6] = ["B", "kB", "MB", "GB", "TB", "PB"];
+const IEC_UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+
+/// 数字的地区格式，决定千位分隔符和小数点
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NumberLocale {
+    #[default]
+    #[serde(rename = "en-US")]
+    EnUs, // 1,234.5
+    #[serde(rename = "de-DE")]
+    DeDe, // 1.234,5
+    #[serde(rename = "fr-FR")]
+    FrFr, // 1 234,5（窄不换行空格）
+    #[serde(rename = "zh-CN")]
+    ZhCn, // 1,234.5
+}
+
+impl NumberLocale {
+    /// (千位分隔符, 小数点)
+    fn separators(&self) -> (char, char) {
+        match self {
+            NumberLocale::EnUs | NumberLocale::ZhCn => (',', '.'),
+            NumberLocale::DeDe => ('.', ','),
+            NumberLocale::FrFr => ('\u{202f}', ','),
+        }
+    }
+}
+
+/// 字节单位：SI以1000进位（MB、GB），IEC以1024进位（MiB、GiB）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnitSystem {
+    #[default]
+    Si,
+    Iec,
+}
+
+/// 数值展示设置，持久化在应用数据目录中
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FormatOptions {
+    pub locale: NumberLocale,
+    pub unit_system: UnitSystem,
+    pub precision: u8, // 小数位数，超过6按6处理
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            locale: NumberLocale::default(),
+            unit_system: UnitSystem::default(),
+            precision: 2,
+        }
+    }
+}
+
+impl FormatOptions {
+    pub fn load(path: &Path) -> Result<Self, BenchmarkError> {
+        load_config(path, "数值格式设置")
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BenchmarkError> {
+        save_config(self, path, "数值格式设置")
+    }
+}
+
+/// 数值展示设置及其持久化位置
+pub struct FormatSettings {
+    pub path: PathBuf,
+    pub options: FormatOptions,
+}
+
+/// 数值的计量单位，字节类单位按unit_system自动换算到合适的量级
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricUnit {
+    MegabytesPerSecond, // 结果中的吞吐量
+    Megabytes,
+    Score,
+    Iops,
+    Milliseconds,
+    Percent,
+}
+
+/// 按地区格式输出数字，precision为小数位数
+pub fn format_number(value: f64, precision: usize, locale: NumberLocale) -> String {
+    if !value.is_finite() {
+        return value.to_string();
+    }
+    let (thousands, decimal) = locale.separators();
+    let fixed = format!("{:.*}", precision, value.abs());
+    let (integer, fraction) = match fixed.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (fixed.as_str(), None),
+    };
+
+    let mut formatted = String::with_capacity(fixed.len() + integer.len() / 3 + 1);
+    // 舍入后为0的负数不显示负号
+    if value < 0.0 && fixed.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        formatted.push('-');
+    }
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index).is_multiple_of(3) {
+            formatted.push(thousands);
+        }
+        formatted.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        formatted.push(decimal);
+        formatted.push_str(fraction);
+    }
+    formatted
+}
+
+/// 按设置格式化一项指标，字节类数值换算到不小于1的最大单位（如1500 MB/s显示为1.57 GB/s）
+pub fn format_metric(value: f64, unit: MetricUnit, options: &FormatOptions) -> String {
+    let precision = options.precision.min(MAX_PRECISION) as usize;
+    let number = |value: f64| format_number(value, precision, options.locale);
+    match unit {
+        MetricUnit::MegabytesPerSecond => format!("{}/s", format_bytes(value * BYTES_PER_MB, options)),
+        MetricUnit::Megabytes => format_bytes(value * BYTES_PER_MB, options),
+        MetricUnit::Score => number(value),
+        MetricUnit::Iops => format!("{} IOPS", number(value)),
+        MetricUnit::Milliseconds => format!("{} ms", number(value)),
+        MetricUnit::Percent => format!("{}%", number(value)),
+    }
+}
+
+/// CSV字段：包含逗号、引号或换行的字段加引号，内部的引号双写
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn format_bytes(bytes: f64, options: &FormatOptions) -> String {
+    let (base, units) = match options.unit_system {
+        UnitSystem::Si => (1000.0, SI_UNITS),
+        UnitSystem::Iec => (1024.0, IEC_UNITS),
+    };
+    let precision = options.precision.min(MAX_PRECISION) as usize;
+    // 按舍入后的值判断进位，避免999.999 kB显示为1,000.00 kB
+    let factor = 10f64.powi(precision as i32);
+    let mut scaled = bytes;
+    let mut index = 0;
+    while (scaled.abs() * factor).round() / factor >= base && index < units.len() - 1 {
+        scaled /= base;
+        index += 1;
+    }
+    format!("{} {}", format_number(scaled, precision, options.locale), units[index])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Write as _;
+
+    // 各单位的典型量级：小于1、接近进位点、刚超过进位点和很大的值
+    const SAMPLES: [(MetricUnit, f64); 14] = [
+        (MetricUnit::MegabytesPerSecond, 0.5),
+        (MetricUnit::MegabytesPerSecond, 12.345),
+        (MetricUnit::MegabytesPerSecond, 953.6),
+        (MetricUnit::MegabytesPerSecond, 999.9),
+        (MetricUnit::MegabytesPerSecond, 1500.0),
+        (MetricUnit::MegabytesPerSecond, 2_000_000.0),
+        (MetricUnit::Megabytes, 1024.0),
+        (MetricUnit::Megabytes, 0.001),
+        (MetricUnit::Score, 1_234_567.891),
+        (MetricUnit::Score, -0.004),
+        (MetricUnit::Iops, 250_000.0),
+        (MetricUnit::Milliseconds, 0.0123),
+        (MetricUnit::Percent, 45.678),
+        (MetricUnit::Score, f64::NAN),
+    ];
+
+    fn render(options: &FormatOptions) -> String {
+        let mut output = String::new();
+        for (unit, value) in SAMPLES {
+            let _ = writeln!(output, "{:?} {} => {}", unit, value, format_metric(value, unit, options));
+        }
+        output
+    }
+
+    #[test]
+    fn test_formatted_metrics_match_snapshots() {
+        // UPDATE_SNAPSHOTS=1 cargo test 重新生成
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/format");
+        let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+        let cases = [
+            ("en-US-si.txt", FormatOptions::default()),
+            (
+                "de-DE-iec.txt",
+                FormatOptions {
+                    locale: NumberLocale::DeDe,
+                    unit_system: UnitSystem::Iec,
+                    precision: 1,
+                },
+            ),
+        ];
+        for (file, options) in cases {
+            let path = dir.join(file);
+            let rendered = render(&options);
+            if update {
+                std::fs::create_dir_all(&dir).unwrap();
+                std::fs::write(&path, rendered).unwrap();
+            } else {
+                let committed = std::fs::read_to_string(&path).unwrap_or_default();
+                assert!(committed == rendered, "{} 已过期，运行 UPDATE_SNAPSHOTS=1 cargo test 更新", path.display());
+            }
+        }
+    }
+
+    #[test]
+    fn test_number_separators_and_scaling() {
+        let options = FormatOptions::default();
+        assert_eq!(format_number(1_234_567.891, 2, NumberLocale::EnUs), "1,234,567.89");
+        assert_eq!(format_number(1_234_567.891, 2, NumberLocale::DeDe), "1.234.567,89");
+        assert_eq!(format_number(1234.5, 1, NumberLocale::FrFr), "1\u{202f}234,5");
+        assert_eq!(format_number(-1234.0, 0, NumberLocale::EnUs), "-1,234");
+        assert_eq!(format_number(-0.001, 2, NumberLocale::EnUs), "0.00");
+        // 超过1000 MB/s换算为GB/s，IEC下1024 MiB/s才换算为GiB/s
+        assert_eq!(format_metric(1000.0, MetricUnit::MegabytesPerSecond, &options), "1.05 GB/s");
+        let iec = FormatOptions { unit_system: UnitSystem::Iec, ..options };
+        assert_eq!(format_metric(1000.0, MetricUnit::MegabytesPerSecond, &iec), "1,000.00 MiB/s");
+        assert_eq!(format_metric(1024.0, MetricUnit::MegabytesPerSecond, &iec), "1.00 GiB/s");
+        assert_eq!(format_metric(1023.999, MetricUnit::MegabytesPerSecond, &iec), "1.00 GiB/s");
+        let high_precision = FormatOptions { precision: 20, ..options };
+        assert_eq!(format_metric(1.0, MetricUnit::Score, &high_precision), "1.000000");
+
+        let json = serde_json::to_value(FormatOptions { locale: NumberLocale::DeDe, ..iec }).unwrap();
+        assert_eq!(json, serde_json::json!({"locale": "de-DE", "unit_system": "iec", "precision": 2}));
+    }
+}
@@ -0,0 +1,126 @@
+//! 心跳看门狗：会话长时间没有进度时定期发送心跳，让前端区分“仍在运行”和“卡住”；
+//! 安静时间超过停滞阈值后发出一次警告，进度恢复后重新计时
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeartbeatConfig {
+    pub interval_secs: u64,        // 没有进度时每隔多久发送一次心跳
+    pub stall_threshold_secs: u64, // 没有进度超过该时长视为停滞
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 5,
+            stall_threshold_secs: 120,
+        }
+    }
+}
+
+impl HeartbeatConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval_secs == 0 {
+            return Err("心跳间隔必须大于0".to_string());
+        }
+        if self.stall_threshold_secs < self.interval_secs {
+            return Err("停滞阈值不能小于心跳间隔".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchdogAction {
+    Heartbeat { seconds_since_progress: f64 },
+    Stalled { seconds_since_progress: f64 },
+}
+
+pub struct Watchdog {
+    config: HeartbeatConfig,
+    last_progress: Option<Instant>, // 上次检查时看到的进度时间
+    last_heartbeat: Option<Instant>,
+    stall_reported: bool,
+}
+
+impl Watchdog {
+    pub fn new(config: HeartbeatConfig) -> Self {
+        Self {
+            config,
+            last_progress: None,
+            last_heartbeat: None,
+            stall_reported: false,
+        }
+    }
+
+    /// 根据最近一次进度的时间决定现在要发送的事件
+    pub fn check(&mut self, last_progress: Instant, now: Instant) -> Vec<WatchdogAction> {
+        if self.last_progress != Some(last_progress) {
+            self.last_progress = Some(last_progress);
+            self.last_heartbeat = None;
+            self.stall_reported = false;
+        }
+
+        let quiet = now.saturating_duration_since(last_progress);
+        let interval = Duration::from_secs(self.config.interval_secs);
+        let seconds_since_progress = quiet.as_secs_f64();
+        let mut actions = Vec::new();
+        if quiet >= interval && self.last_heartbeat.is_none_or(|sent| now.saturating_duration_since(sent) >= interval) {
+            self.last_heartbeat = Some(now);
+            actions.push(WatchdogAction::Heartbeat { seconds_since_progress });
+        }
+        if quiet >= Duration::from_secs(self.config.stall_threshold_secs) && !self.stall_reported {
+            self.stall_reported = true;
+            actions.push(WatchdogAction::Stalled { seconds_since_progress });
+        }
+        actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_silent_phase_sends_heartbeats_then_stall_warning() {
+        let mut watchdog = Watchdog::new(HeartbeatConfig {
+            interval_secs: 5,
+            stall_threshold_secs: 10,
+        });
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        // 模拟12秒没有任何进度的阶段，看门狗每秒检查一次
+        let mut timeline = Vec::new();
+        for second in 1..=12 {
+            for action in watchdog.check(start, at(second)) {
+                timeline.push((second, action));
+            }
+        }
+        assert_eq!(
+            timeline,
+            vec![
+                (5, WatchdogAction::Heartbeat { seconds_since_progress: 5.0 }),
+                (10, WatchdogAction::Heartbeat { seconds_since_progress: 10.0 }),
+                (10, WatchdogAction::Stalled { seconds_since_progress: 10.0 }),
+            ]
+        );
+
+        // 进度恢复后重新计时，之后再次停滞会再警告一次
+        assert!(watchdog.check(at(13), at(14)).is_empty());
+        assert_eq!(
+            watchdog.check(at(13), at(23)),
+            vec![
+                WatchdogAction::Heartbeat { seconds_since_progress: 10.0 },
+                WatchdogAction::Stalled { seconds_since_progress: 10.0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_heartbeat_config_validation() {
+        assert!(HeartbeatConfig::default().validate().is_ok());
+        assert!(HeartbeatConfig { interval_secs: 0, stall_threshold_secs: 10 }.validate().is_err());
+        assert!(HeartbeatConfig { interval_secs: 10, stall_threshold_secs: 5 }.validate().is_err());
+    }
+}
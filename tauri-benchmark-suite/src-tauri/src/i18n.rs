@@ -0,0 +1,374 @@
+//! 事件消息目录：后端只产生稳定的消息键和参数，
+//! 人类可读的文本按后端语言从这里渲染，前端也可以用键自行翻译
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    En,
+    #[default]
+    Zh,
+}
+
+impl Locale {
+    /// 接受"en"、"zh-CN"、"en_US"等形式，只看语言部分
+    pub fn parse(locale: &str) -> Option<Locale> {
+        let language = locale.split(['-', '_']).next()?.to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Locale::En),
+            "zh" => Some(Locale::Zh),
+            _ => None,
+        }
+    }
+}
+
+// 后端当前语言的进程级副本：IpcError在类型转换中拿不到托管状态，从这里读取。
+// set_backend_locale命令同时更新它和SharedLocale
+static BACKEND_LOCALE: AtomicU8 = AtomicU8::new(0);
+
+pub fn backend_locale() -> Locale {
+    match BACKEND_LOCALE.load(Ordering::Relaxed) {
+        1 => Locale::En,
+        _ => Locale::Zh,
+    }
+}
+
+pub fn set_backend_locale(locale: Locale) {
+    let value = match locale {
+        Locale::Zh => 0,
+        Locale::En => 1,
+    };
+    BACKEND_LOCALE.store(value, Ordering::Relaxed);
+}
+
+// (键, 英文, 中文)，参数以{name}占位
+const CATALOG: &[(&str, &str, &str)] = &[
+    ("cpu.start", "Starting CPU benchmark...", "开始CPU性能测试..."),
+    ("cpu.single_thread.start", "Starting single-thread CPU test...", "开始单线程CPU测试..."),
+    ("cpu.single_thread.progress", "Single-thread test running... ({percent}%)", "单线程测试进行中... ({percent}%)"),
+    ("cpu.multi_thread.start", "Starting multi-thread CPU test...", "开始多线程CPU测试..."),
+    ("cpu.multi_thread.threads", "Multi-thread test started ({threads} threads)...", "多线程测试开始 (使用{threads}个线程)..."),
+    ("cpu.multi_thread.progress", "Multi-thread test running... ({percent}%)", "多线程测试进行中... ({percent}%)"),
+    ("cpu.floating_point.start", "Starting floating-point test...", "开始浮点运算测试..."),
+    ("cpu.floating_point.progress", "Floating-point test running... ({percent}%)", "浮点运算测试进行中... ({percent}%)"),
+    ("cpu.complete", "CPU test complete", "CPU测试完成"),
+    ("cpu.failed", "CPU test failed: {error}", "CPU测试失败: {error}"),
+    ("memory.start", "Starting memory benchmark...", "开始内存性能测试..."),
+    ("memory.seq_read.start", "Starting memory sequential read test...", "开始内存顺序读取测试..."),
+    ("memory.seq_read.progress", "Sequential read test running... ({percent}%)", "顺序读取测试进行中... ({percent}%)"),
+    ("memory.seq_write.start", "Starting memory sequential write test...", "开始内存顺序写入测试..."),
+    ("memory.seq_write.progress", "Sequential write test running... ({percent}%)", "顺序写入测试进行中... ({percent}%)"),
+    ("memory.random_access.start", "Starting memory random access test...", "开始内存随机访问测试..."),
+    ("memory.random_access.progress", "Random access test running... ({percent}%)", "随机访问测试进行中... ({percent}%)"),
+    ("memory.latency.start", "Starting memory latency test...", "开始内存延迟测试..."),
+    ("memory.latency.progress", "Memory latency test running... ({percent}%)", "内存延迟测试进行中... ({percent}%)"),
+    ("memory.interference.start", "Starting memory bandwidth interference test...", "开始内存带宽干扰测试..."),
+    (
+        "memory.interference.progress",
+        "Measuring read bandwidth with {threads} threads under load... ({percent}%)",
+        "正在测量{threads}个线程负载下的读取带宽... ({percent}%)",
+    ),
+    ("memory.usage", "Monitoring memory usage...", "监控内存使用量..."),
+    ("memory.complete", "Memory test complete", "内存测试完成"),
+    ("memory.failed", "Memory test failed: {error}", "内存测试失败: {error}"),
+    ("storage.start", "Starting storage benchmark...", "开始存储性能测试..."),
+    ("storage.seq_write.start", "Starting storage sequential write test...", "开始存储顺序写入测试..."),
+    ("storage.seq_write.progress", "Sequential write running... ({percent}%)", "顺序写入进行中... ({percent}%)"),
+    ("storage.seq_read.start", "Starting storage sequential read test...", "开始存储顺序读取测试..."),
+    ("storage.seq_read.progress", "Sequential read running... ({percent}%)", "顺序读取进行中... ({percent}%)"),
+    ("storage.random_write.start", "Starting storage random write test...", "开始存储随机写入测试..."),
+    ("storage.random_write.progress", "Random write running... ({percent}%)", "随机写入进行中... ({percent}%)"),
+    ("storage.random_read.start", "Starting storage random read test...", "开始存储随机读取测试..."),
+    ("storage.random_read.progress", "Random read running... ({percent}%)", "随机读取进行中... ({percent}%)"),
+    ("storage.small_files.start", "Starting small-file test...", "开始小文件测试..."),
+    ("storage.small_files.progress", "Small-file test running: {phase}... ({percent}%)", "小文件测试进行中: {phase}... ({percent}%)"),
+    ("storage.archive.start", "Starting archive extraction test...", "开始压缩包解压测试..."),
+    ("storage.archive.create", "Creating {format} archive (untimed)...", "正在生成{format}压缩包（不计时）..."),
+    ("storage.archive.extract", "Extracting {format} archive...", "正在解压{format}压缩包..."),
+    ("storage.complete", "Storage test complete", "存储测试完成"),
+    (
+        "storage.cleanup_failed",
+        "Could not remove the storage test file {path}: {error}",
+        "无法删除存储测试文件 {path}: {error}",
+    ),
+    ("storage.failed", "Storage test failed: {error}", "存储测试失败: {error}"),
+    ("copy.generate.start", "Generating the copy test source file...", "正在生成复制测试源文件..."),
+    ("copy.generate.progress", "Generating source file... ({percent}%)", "生成源文件中... ({percent}%)"),
+    ("copy.copy.start", "Starting copy test...", "开始复制测试..."),
+    ("copy.copy.progress", "Copy test running... ({percent}%)", "复制测试进行中... ({percent}%)"),
+    ("copy.complete", "Copy test complete", "复制测试完成"),
+    ("ipc.progress", "Measuring {transport} IPC round trips with {size}KB payloads... ({percent}%)", "测量{size}KB负载的{transport} IPC往返... ({percent}%)"),
+    ("ipc.complete", "IPC overhead test complete", "IPC开销测试完成"),
+    ("process.start", "Starting process spawn test ({helper})...", "开始进程启动测试（{helper}）..."),
+    ("process.progress", "Process spawn test running... ({percent}%)", "进程启动测试进行中... ({percent}%)"),
+    ("process.complete", "Process spawn test complete", "进程启动测试完成"),
+    ("compression.progress", "Compression test running: {codec}... ({percent}%)", "压缩测试进行中: {codec}... ({percent}%)"),
+    ("compression.complete", "Compression test complete", "压缩测试完成"),
+    ("crypto.progress", "Cryptography test running: {algorithm}, {size}KB messages... ({percent}%)", "加密测试进行中: {algorithm}，{size}KB消息... ({percent}%)"),
+    ("crypto.complete", "Cryptography test complete", "加密测试完成"),
+    ("database.progress", "Database test running: {phase} ({mode})... ({percent}%)", "数据库测试进行中: {phase}（{mode}）... ({percent}%)"),
+    ("database.complete", "Database test complete", "数据库测试完成"),
+    ("json.progress", "JSON test running: {shape}... ({percent}%)", "JSON测试进行中: {shape}... ({percent}%)"),
+    ("json.complete", "JSON test complete", "JSON测试完成"),
+    (
+        "stability.start",
+        "Starting stability test on {threads} threads with a {buffer_mb} MB buffer...",
+        "开始稳定性测试，{threads}个线程，缓冲区{buffer_mb}MB...",
+    ),
+    ("stability.progress", "Stability test running, {errors} errors so far... ({percent}%)", "稳定性测试进行中，已发现{errors}个错误... ({percent}%)"),
+    ("stability.complete", "Stability test complete", "稳定性测试完成"),
+    (
+        "stability.thermal_throttling",
+        "The CPU throttled or reached its temperature limit during the stability test",
+        "稳定性测试期间CPU降频或达到温度上限",
+    ),
+    (
+        "stability.errors_detected",
+        "Stability test found {computation} computation errors and {memory} memory errors; the system is not stable at these settings",
+        "稳定性测试发现{computation}个计算错误和{memory}个内存错误，系统在当前设置下不稳定",
+    ),
+    (
+        "sync.progress",
+        "Measuring {primitive} contention on {threads} threads... ({percent}%)",
+        "正在测量{threads}个线程下{primitive}的竞争开销... ({percent}%)",
+    ),
+    ("sync.complete", "Thread synchronization test complete", "线程同步测试完成"),
+    ("hashing.progress", "Hashing test running: {algorithm}... ({percent}%)", "哈希测试进行中: {algorithm}... ({percent}%)"),
+    ("hashing.complete", "Hashing test complete", "哈希测试完成"),
+    ("image.progress", "Image codec test running: {format}... ({percent}%)", "图像编解码测试进行中: {format}... ({percent}%)"),
+    ("image.complete", "Image codec test complete", "图像编解码测试完成"),
+    ("gpu.start", "Starting GPU benchmark...", "开始GPU性能测试..."),
+    ("gpu.compute.start", "Starting GPU compute test on {name}...", "开始在{name}上进行GPU计算测试..."),
+    ("gpu.compute.progress", "GPU compute test running... ({percent}%)", "GPU计算测试进行中... ({percent}%)"),
+    ("gpu.bandwidth.start", "Starting GPU memory bandwidth test...", "开始显存带宽测试..."),
+    ("gpu.bandwidth.progress", "GPU memory bandwidth test running... ({percent}%)", "显存带宽测试进行中... ({percent}%)"),
+    ("gpu.complete", "GPU test complete", "GPU测试完成"),
+    ("gpu.failed", "GPU test failed: {error}", "GPU测试失败: {error}"),
+    ("webview.start", "Starting WebView benchmark...", "开始WebView性能测试..."),
+    ("webview.waiting", "Waiting for the frontend to run the WebView workloads...", "等待前端运行WebView测试负载..."),
+    ("webview.complete", "WebView test complete", "WebView测试完成"),
+    ("webview.failed", "WebView test failed: {error}", "WebView测试失败: {error}"),
+    ("network.start", "Starting network benchmark...", "开始网络性能测试..."),
+    ("network.latency.start", "Measuring latency to {target}...", "正在测量到{target}的延迟..."),
+    ("network.latency.progress", "Latency test running... ({percent}%)", "延迟测试进行中... ({percent}%)"),
+    ("network.download.start", "Starting download test...", "开始下载测试..."),
+    ("network.download.progress", "Download test running... ({percent}%)", "下载测试进行中... ({percent}%)"),
+    ("network.upload.start", "Starting upload test...", "开始上传测试..."),
+    ("network.upload.progress", "Upload test running... ({percent}%)", "上传测试进行中... ({percent}%)"),
+    ("network.complete", "Network test complete", "网络测试完成"),
+    ("network.failed", "Network test failed: {error}", "网络测试失败: {error}"),
+    (
+        "network.connection_errors",
+        "{count} network connections failed during the test: {error}",
+        "测试期间有{count}个网络连接出错: {error}",
+    ),
+    (
+        "gpu.no_adapter",
+        "No GPU adapter is available; the GPU test was skipped",
+        "没有可用的GPU适配器，已跳过GPU测试",
+    ),
+    (
+        "gpu.adapter_fallback",
+        "GPU adapter {requested} does not exist; using {name} instead",
+        "GPU适配器{requested}不存在，改用{name}",
+    ),
+    (
+        "io.storage_full",
+        "Not enough disk space for the test file: {error}. Free some space or lower the storage test size",
+        "磁盘空间不足，无法写入测试文件: {error}。请清理空间或减小存储测试大小",
+    ),
+    (
+        "io.permission_denied",
+        "No permission to write the test file: {error}. Choose another test directory",
+        "没有写入测试文件的权限: {error}。请选择其他测试目录",
+    ),
+    ("extra.start", "Starting {name} benchmark...", "开始扩展测试{name}..."),
+    ("extra.complete", "{name} test complete", "扩展测试{name}完成"),
+    ("extra.failed", "{name} test failed: {error}", "扩展测试{name}失败: {error}"),
+    ("suite.calibrating", "Calibrating test durations...", "正在校准测试时长..."),
+    ("suite.failed", "Benchmark failed: {error}", "测试失败: {error}"),
+    ("suite.upload_failed", "Failed to upload test result: {error}", "测试结果上传失败: {error}"),
+    ("suite.webhook_failed", "Failed to send completion notification: {error}", "完成通知发送失败: {error}"),
+    ("suite.save_failed", "Failed to save test result: {error}", "测试结果保存失败: {error}"),
+    ("suite.resumed", "Test resumed", "测试已恢复"),
+    (
+        "suite.stalled",
+        "No progress in {phase} for {seconds} seconds; the test may be stuck",
+        "阶段 {phase} 已 {seconds} 秒没有进度，测试可能已卡住",
+    ),
+    (
+        "warning.repeated",
+        "{warning_type} occurred {count} more times",
+        "{warning_type} 又出现了 {count} 次",
+    ),
+    ("preflight.no_tests", "No tests are enabled", "没有启用任何测试"),
+    (
+        "preflight.cpu_threads",
+        "CPU thread count {threads} exceeds the {cpus} logical processors; the multi-thread score will be low",
+        "CPU测试线程数 {threads} 超过逻辑处理器数量 {cpus}，多线程分数会偏低",
+    ),
+    (
+        "preflight.memory_buffer",
+        "Memory buffer {buffer_mb}MB exceeds half of the {available_mb}MB available memory and may cause paging",
+        "内存测试缓冲区 {buffer_mb}MB 超过可用内存 {available_mb}MB 的一半，可能触发系统换页",
+    ),
+    (
+        "preflight.storage_size",
+        "Storage test file size and block size must be greater than 0",
+        "存储测试的文件大小和块大小必须大于0",
+    ),
+    (
+        "preflight.disk_space",
+        "Not enough free disk space: the storage test needs {required_mb}MB, {available_mb}MB available",
+        "磁盘可用空间不足：存储测试需要 {required_mb}MB，可用 {available_mb}MB",
+    ),
+    (
+        "preflight.capability_permission",
+        "{feature} needs elevated privileges and will be skipped; see the capability list for how to grant access",
+        "{feature} 需要更高权限，本次运行将跳过该功能；授权方法见功能检测结果",
+    ),
+];
+
+pub fn template(key: &str, locale: Locale) -> Option<&'static str> {
+    CATALOG
+        .iter()
+        .find(|(k, _, _)| *k == key)
+        .map(|(_, en, zh)| match locale {
+            Locale::En => *en,
+            Locale::Zh => *zh,
+        })
+}
+
+/// 一条待渲染的消息
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub key: &'static str,
+    pub params: HashMap<String, String>,
+}
+
+impl Message {
+    pub fn new(key: &'static str) -> Self {
+        Self {
+            key,
+            params: HashMap::new(),
+        }
+    }
+
+    pub fn param(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// 进度消息统一保留一位小数
+    pub fn progress(key: &'static str, progress: f64) -> Self {
+        Self::new(key).param("percent", format!("{:.1}", progress))
+    }
+
+    /// 去掉.start/.progress后缀的键，用作阶段标签，如 "storage.seq_write"
+    pub fn phase(&self) -> &'static str {
+        let key = self.key;
+        key.strip_suffix(".progress").or_else(|| key.strip_suffix(".start")).unwrap_or(key)
+    }
+
+    /// 目录中没有该键时返回键本身，避免丢失信息
+    pub fn render(&self, locale: Locale) -> String {
+        let Some(template) = template(self.key, locale) else {
+            return self.key.to_string();
+        };
+        self.params.iter().fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{}}}", name), value)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 所有产生消息的源文件，新增发送点时需要加入这里
+    const SOURCES: &[&str] = &[
+        include_str!("benchmark/core.rs"),
+        include_str!("benchmark/cpu.rs"),
+        include_str!("benchmark/memory.rs"),
+        include_str!("benchmark/storage.rs"),
+        include_str!("benchmark/archive.rs"),
+        include_str!("benchmark/gpu.rs"),
+        include_str!("benchmark/network.rs"),
+        include_str!("benchmark/copy.rs"),
+        include_str!("benchmark/ipc_overhead.rs"),
+        include_str!("benchmark/process.rs"),
+        include_str!("benchmark/compression.rs"),
+        include_str!("benchmark/crypto.rs"),
+        include_str!("benchmark/hashing.rs"),
+        include_str!("benchmark/image.rs"),
+        include_str!("benchmark/database.rs"),
+        include_str!("benchmark/serde_json_bench.rs"),
+        include_str!("benchmark/stability.rs"),
+        include_str!("benchmark/sync.rs"),
+        include_str!("benchmark/webview.rs"),
+        include_str!("benchmark/estimate.rs"),
+        include_str!("ipc.rs"),
+        include_str!("app.rs"),
+    ];
+
+    fn placeholders(template: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = template
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn emitted_keys() -> Vec<String> {
+        SOURCES
+            .iter()
+            .flat_map(|source| {
+                ["Message::new(\"", "Message::progress(\""]
+                    .iter()
+                    .flat_map(move |call| source.split(call).skip(1))
+            })
+            .filter_map(|rest| rest.split_once('"').map(|(key, _)| key.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_emitted_key_exists_in_both_catalogs() {
+        let keys = emitted_keys();
+        assert!(keys.len() > 30, "只找到 {} 个消息键", keys.len());
+        for key in &keys {
+            assert!(template(key, Locale::En).is_some(), "英文目录缺少 {}", key);
+            assert!(template(key, Locale::Zh).is_some(), "中文目录缺少 {}", key);
+        }
+        // 目录中也不应有不再使用的键
+        for (key, _, _) in CATALOG {
+            assert!(keys.iter().any(|k| k == key), "未使用的消息键 {}", key);
+        }
+    }
+
+    #[test]
+    fn test_catalog_entries_are_consistent() {
+        for (i, (key, en, zh)) in CATALOG.iter().enumerate() {
+            assert!(!en.is_empty() && !zh.is_empty(), "{} 缺少翻译", key);
+            assert_eq!(placeholders(en), placeholders(zh), "{} 的参数不一致", key);
+            assert!(CATALOG[..i].iter().all(|(k, _, _)| k != key), "重复的消息键 {}", key);
+        }
+    }
+
+    #[test]
+    fn test_render_substitutes_params() {
+        let message = Message::progress("storage.seq_write.progress", 42.13);
+        assert_eq!(message.render(Locale::Zh), "顺序写入进行中... (42.1%)");
+        assert_eq!(message.render(Locale::En), "Sequential write running... (42.1%)");
+        assert_eq!(Message::new("missing.key").render(Locale::En), "missing.key");
+        assert_eq!(message.phase(), "storage.seq_write");
+        assert_eq!(Message::new("cpu.complete").phase(), "cpu.complete");
+
+        assert_eq!(Locale::parse("en-US"), Some(Locale::En));
+        assert_eq!(Locale::parse("zh_CN"), Some(Locale::Zh));
+        assert_eq!(Locale::parse("fr"), None);
+    }
+}
@@ -1,141 +1,2567 @@
+use crate::benchmark::core::{describe_issues, BenchmarkConfig, ConfigIssue, Issue, IssueSeverity, LoadedConfig, TestResult};
+use crate::benchmark::comparison::MetricComparison;
+use crate::benchmark::cpu::CpuTestResult;
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::memory::MemoryTestResult;
+use crate::benchmark::storage::StorageTestResult;
+use crate::benchmark::gpu::GpuTestResult;
+use crate::benchmark::network::NetworkTestResult;
+use crate::benchmark::power::{PowerPlan, PowerPlanSwitchRequest};
+use crate::benchmark::webview::{WebviewBenchmarkRequest, WebviewTestResult};
+use crate::i18n::{Locale, Message};
+use crate::telemetry::{metric_schema, MetricSchema};
+#[cfg(feature = "tauri-app")]
+use crate::telemetry::Telemetry;
+#[cfg(feature = "tauri-app")]
+use crate::timeseries::TimeseriesStore;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+#[cfg(feature = "tauri-app")]
+use tauri::ipc::Channel;
+#[cfg(feature = "tauri-app")]
+use tauri::{AppHandle, Emitter, Runtime};
+
+// 对前端的IPC负载统一使用camelCase，旧版本保存的snake_case字段通过alias兼容。
+// 带message的负载同时携带messageKey和params，message只是按后端语言渲染的兼容文本
+
+/// 事件负载的结构版本，增删或重命名事件负载字段时递增。
+/// 全局事件和重放缓冲区中的对象负载都带有schemaVersion字段
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
 
 /// 基准测试进度信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct BenchmarkProgress {
+    #[serde(alias = "session_id")]
     pub session_id: String,
+    #[serde(alias = "current_test")]
     pub current_test: String,
+    #[serde(alias = "overall_progress")]
     pub overall_progress: f64,
+    #[serde(alias = "test_progress")]
     pub test_progress: f64,
     pub message: String,
+    #[serde(default, alias = "message_key")]
+    pub message_key: String,
+    #[serde(default)]
+    pub params: HashMap<String, String>,
+    #[serde(alias = "estimated_time_remaining")]
     pub estimated_time_remaining: Option<u64>, // seconds
+    #[serde(default, alias = "estimated_completion_time")]
+    pub estimated_completion_time: Option<String>, // RFC3339，不受前端计时漂移影响
 }
 
 /// 系统资源监控信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct SystemMonitoringData {
+    #[serde(alias = "cpu_usage")]
     pub cpu_usage: f64,
+    #[serde(alias = "memory_usage")]
     pub memory_usage: f64,
     pub temperature: Option<f64>,
     pub timestamp: String,
+    #[serde(default, alias = "session_id")]
+    pub session_id: Option<String>, // 套件运行期间的采样为所属会话，单独查询时为空
 }
 
 /// 测试状态枚举
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum TestStatus {
     Pending,
+    Queued,
     Running,
+    Paused,
     Completed,
     Failed,
     Cancelled,
+    Interrupted, // 应用崩溃或重启时仍在运行，启动时恢复为该状态
+}
+
+impl TestStatus {
+    pub const ALL: [TestStatus; 8] = [
+        TestStatus::Pending,
+        TestStatus::Queued,
+        TestStatus::Running,
+        TestStatus::Paused,
+        TestStatus::Completed,
+        TestStatus::Failed,
+        TestStatus::Cancelled,
+        TestStatus::Interrupted,
+    ];
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            TestStatus::Completed | TestStatus::Failed | TestStatus::Cancelled | TestStatus::Interrupted
+        )
+    }
+
+    /// 合法的状态转换：Pending→Queued→Running⇄Paused→Completed/Failed，
+    /// 未结束的会话都可以被取消，或在应用重启时标记为中断
+    pub fn can_transition_to(&self, to: &TestStatus) -> bool {
+        use TestStatus::*;
+        match (self, to) {
+            (Pending, Queued) | (Queued, Running) | (Running, Paused) | (Paused, Running) => true,
+            (Running | Paused, Completed | Failed) => true,
+            (from, Cancelled | Interrupted) => !from.is_terminal(),
+            _ => false,
+        }
+    }
+}
+
+/// 测试类型，序列化为历史上使用的小写字符串
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TestType {
+    Cpu,
+    Memory,
+    Storage,
+    Gpu, // 可选，默认不启用
+    Webview, // 可选，由前端运行负载并提交计时
+    Network, // 可选，默认不启用
+    Extra, // 通过BenchmarkRegistry注册的扩展测试，具体名称见事件中的测试名
+    Suite, // 不属于单项测试的事件，如预检和停滞警告
+}
+
+impl TestType {
+    pub const ALL: [TestType; 8] = [
+        TestType::Cpu,
+        TestType::Memory,
+        TestType::Storage,
+        TestType::Gpu,
+        TestType::Webview,
+        TestType::Network,
+        TestType::Extra,
+        TestType::Suite,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TestType::Cpu => "cpu",
+            TestType::Memory => "memory",
+            TestType::Storage => "storage",
+            TestType::Gpu => "gpu",
+            TestType::Webview => "webview",
+            TestType::Network => "network",
+            TestType::Extra => "extra",
+            TestType::Suite => "suite",
+        }
+    }
+}
+
+impl std::fmt::Display for TestType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// 进度更新事件
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct ProgressUpdate {
     pub progress: f64,
     pub message: String,
-    pub test_type: String,
+    #[serde(default, alias = "message_key")]
+    pub message_key: String,
+    #[serde(default)]
+    pub params: HashMap<String, String>,
+    #[serde(alias = "test_type")]
+    pub test_type: TestType,
+}
+
+/// 子测试结果，按type字段区分测试类型
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", content = "data", rename_all = "lowercase")]
+#[allow(clippy::large_enum_variant)] // 每项测试只产生一个，装箱不值得
+pub enum TestResultPayload {
+    Cpu(CpuTestResult),
+    Memory(MemoryTestResult),
+    Storage(StorageTestResult),
+    Gpu(GpuTestResult),
+    Webview(WebviewTestResult),
+    Network(NetworkTestResult),
+    Extra(ExtraTestResult),
+}
+
+/// 扩展测试的结果，结构由测试模块自行决定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ExtraTestResult {
+    pub name: String,
+    pub result: serde_json::Value,
 }
 
-/// 测试完成事件
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 测试完成事件，成功和失败都会发送
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct TestCompleteEvent {
+    #[serde(alias = "session_id")]
     pub session_id: String,
-    pub test_type: String,
+    #[serde(alias = "test_type")]
+    pub test_type: TestType,
     pub success: bool,
-    pub result: Option<serde_json::Value>,
+    pub result: Option<TestResultPayload>,
     pub error: Option<String>,
+    #[serde(default, alias = "elapsed_seconds")]
+    pub elapsed_seconds: f64,
+    #[serde(default)]
+    pub outcome: TestCompletion,
+    #[serde(default)]
+    pub issues: Vec<Issue>, // 运行期间上报的不中止测试的问题
+}
+
+/// 单项测试的完成情况。success仍为true的测试如有Critical问题记为CompletedWithIssues，
+/// 本机不支持而跳过的测试（如没有GPU适配器）记为Skipped，没有结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum TestCompletion {
+    #[default]
+    Completed,
+    CompletedWithIssues,
+    Failed,
+    Skipped,
+}
+
+impl TestCompletion {
+    pub fn classify(failed: bool, issues: &[Issue]) -> Self {
+        if failed {
+            TestCompletion::Failed
+        } else if issues.iter().any(|issue| issue.severity == WarningSeverity::Critical) {
+            TestCompletion::CompletedWithIssues
+        } else {
+            TestCompletion::Completed
+        }
+    }
 }
 
 /// 基准测试套件完成事件
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct BenchmarkSuiteCompleteEvent {
+    #[serde(alias = "session_id")]
     pub session_id: String,
     pub success: bool,
     pub results: Option<crate::benchmark::core::TestResult>,
     pub error: Option<String>,
+    #[serde(default, alias = "completed_at")]
+    pub completed_at: String, // 实际完成时间（RFC3339），用于对照进度中的预计完成时间
+    #[serde(default, alias = "save_error")]
+    pub save_error: Option<IpcError>, // 结果写入历史失败时的错误（DATA_SAVE_ERROR），此时结果只在本事件中
 }
 
 /// 测试会话信息
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct TestSession {
+    #[serde(alias = "session_id")]
     pub session_id: String,
     pub status: TestStatus,
+    #[serde(alias = "start_time")]
     pub start_time: String,
+    #[serde(alias = "end_time")]
     pub end_time: Option<String>,
     pub config: Option<crate::benchmark::core::BenchmarkConfig>,
+    #[serde(default, alias = "overall_progress")]
+    pub overall_progress: f64, // 0-100
+    #[serde(default, alias = "current_test")]
+    pub current_test: Option<String>,
+    #[serde(default, alias = "last_update")]
+    pub last_update: String,
+    #[serde(default, alias = "metric_samples")]
+    pub metric_samples: MetricSampleCounters,
+    #[serde(default, alias = "estimated_completion_time")]
+    pub estimated_completion_time: Option<String>,
+}
+
+/// 单项测试的进度，0-100
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TestProgressSnapshot {
+    pub test_type: String,
+    pub progress: f64,
+}
+
+/// get_ui_state返回的界面状态快照。某些环境下后端事件无法可靠送达WebView，
+/// 前端发现事件缺失时按1Hz轮询该快照，仅凭它即可渲染运行界面
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UiStateSnapshot {
+    pub session_id: String,
+    pub status: TestStatus,
+    pub overall_progress: f64, // 0-100
+    pub current_test: Option<String>,
+    pub phase: Option<String>,          // 当前阶段的说明，与最近一次进度事件的message一致
+    pub tests: Vec<TestProgressSnapshot>, // 已开始的测试，按开始顺序
+    pub estimated_time_remaining: Option<u64>,
+    pub estimated_completion_time: Option<String>,
+    pub recent_warnings: Vec<TestWarningEvent>, // 最近的警告，最旧的在前
+    pub monitoring: Option<SystemMonitoringData>,
+    pub latest_sample: Option<RealTimePerformanceData>,
+    pub result_id: Option<String>, // 会话结束且结果已写入结果存储时的ID
+    pub last_update: String,
+    pub end_time: Option<String>,
+}
+
+/// 会话状态变化事件
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStatusChangedEvent {
+    #[serde(alias = "session_id")]
+    pub session_id: String,
+    #[serde(alias = "old_status")]
+    pub old_status: TestStatus,
+    #[serde(alias = "new_status")]
+    pub new_status: TestStatus,
+    #[serde(default)]
+    pub timestamp: String,
+    #[serde(default)]
+    pub reason: Option<String>, // 失败、取消和中断时为错误码
+}
+
+/// 心跳事件，会话运行中长时间没有进度时定期发送
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HeartbeatEvent {
+    #[serde(alias = "session_id")]
+    pub session_id: String,
+    pub phase: String, // 最近一次进度所在的阶段，如 "storage.seq_write"
+    #[serde(alias = "seconds_since_progress")]
+    pub seconds_since_progress: f64,
+}
+
+/// 套件结果相对本机上一次结果出现回归，全局发送，不需要打开对比页面也能提示
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RegressionDetectedEvent {
+    #[serde(alias = "session_id")]
+    pub session_id: String,
+    #[serde(alias = "result_id")]
+    pub result_id: String,
+    #[serde(alias = "baseline_id")]
+    pub baseline_id: String,
+    #[serde(alias = "baseline_timestamp")]
+    pub baseline_timestamp: String,
+    pub regressions: Vec<MetricComparison>, // 超过阈值的指标及其变化
 }
 
 /// 实时性能数据
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct RealTimePerformanceData {
+    #[serde(alias = "session_id")]
     pub session_id: String,
-    pub test_type: String,
-    pub metrics: std::collections::HashMap<String, f64>,
+    #[serde(alias = "test_type")]
+    pub test_type: TestType,
+    #[serde(default)]
+    pub phase: String, // 测试内的阶段，如 "seq_write"
+    pub metrics: std::collections::HashMap<String, f64>, // 各测试类型的指标键见benchmark::metrics
+
     pub timestamp: String,
 }
 
 /// 测试警告事件
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct TestWarningEvent {
+    #[serde(alias = "session_id")]
     pub session_id: String,
-    pub test_type: String,
+    #[serde(alias = "test_type")]
+    pub test_type: TestType,
+    #[serde(alias = "warning_type")]
     pub warning_type: String,
     pub message: String,
+    #[serde(default, alias = "message_key")]
+    pub message_key: String,
+    #[serde(default)]
+    pub params: HashMap<String, String>,
     pub severity: WarningSeverity,
 }
 
-/// 警告严重程度
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 警告严重程度，按声明顺序从低到高比较
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
 pub enum WarningSeverity {
+    #[default]
     Low,
     Medium,
     High,
     Critical,
 }
 
-/// IPC错误类型
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 错误码目录：(错误码, 英文, 中文, 是否可重试)。错误码保持稳定，前端据此链接帮助文档；
+/// IpcError的默认消息和retryable都来自这里，BenchmarkError::code的每个取值都必须在目录中且retryable一致
+const ERROR_CATALOG: &[(&str, &str, &str, bool)] = &[
+    ("SYSTEM_INFO_ERROR", "Failed to collect system information", "系统信息获取失败", false),
+    ("CPU_TEST_ERROR", "CPU test failed", "CPU测试失败", false),
+    ("MEMORY_TEST_ERROR", "Memory test failed", "内存测试失败", false),
+    ("STORAGE_TEST_ERROR", "Storage test failed", "存储测试失败", true),
+    ("GPU_TEST_ERROR", "GPU test failed", "GPU测试失败", false),
+    ("IPC_TEST_ERROR", "IPC test failed", "IPC测试失败", false),
+    ("PROCESS_TEST_ERROR", "Process spawn test failed", "进程启动测试失败", false),
+    ("COMPRESSION_TEST_ERROR", "Compression test failed", "压缩测试失败", false),
+    ("CRYPTO_TEST_ERROR", "Cryptography test failed", "加密测试失败", false),
+    ("DATABASE_TEST_ERROR", "Database test failed", "数据库测试失败", true),
+    ("JSON_TEST_ERROR", "JSON test failed", "JSON测试失败", false),
+    ("STABILITY_TEST_ERROR", "Stability test failed", "稳定性测试失败", false),
+    ("IMAGE_TEST_ERROR", "Image codec test failed", "图像编解码测试失败", false),
+    ("POWER_PLAN_ERROR", "Power plan comparison failed", "电源计划对比失败", false),
+    ("WEBVIEW_TEST_ERROR", "Webview test failed", "WebView测试失败", true),
+    ("NETWORK_TEST_ERROR", "Network test failed", "网络测试失败", true),
+    ("DATA_SAVE_ERROR", "Failed to save data", "数据保存失败", true),
+    ("DATA_LOAD_ERROR", "Failed to load data", "数据加载失败", false),
+    ("UPLOAD_ERROR", "Failed to upload result", "结果上传失败", true),
+    ("PERMISSION_ERROR", "Permission denied", "权限不足", false),
+    ("INVALID_TRANSITION", "The test status does not allow this operation", "测试状态不允许该操作", false),
+    ("CANCELLED", "The test was cancelled", "测试已取消", false),
+    ("TIMEOUT", "The test timed out", "测试超时", true),
+    ("INTERNAL_PANIC", "An internal error occurred during the test", "测试过程中发生内部错误", false),
+    ("INVALID_CONFIG", "The test configuration is invalid", "测试配置无效", false),
+    ("SESSION_NOT_FOUND", "Test session not found", "测试会话不存在", false),
+    ("INVALID_ARGUMENT", "Invalid argument", "参数无效", false),
+    ("NOT_IMPLEMENTED", "Not implemented yet", "功能尚未实现", false),
+    ("INTERNAL_ERROR", "An internal error occurred", "发生内部错误", false),
+    ("IO_NOT_FOUND", "File or directory not found", "文件或目录不存在", false),
+    ("IO_PERMISSION_DENIED", "No permission to access the file", "没有访问文件的权限", false),
+    ("IO_ALREADY_EXISTS", "The file already exists", "文件已存在", false),
+    ("IO_STORAGE_FULL", "Not enough disk space", "磁盘空间不足", false),
+    ("IO_TIMED_OUT", "The I/O operation timed out", "IO操作超时", true),
+    ("IO_INTERRUPTED", "The I/O operation was interrupted", "IO操作被中断", true),
+    ("IO_INVALID_DATA", "The file content is invalid", "文件内容无效", false),
+    ("IO_ERROR", "I/O operation failed", "IO操作失败", false),
+    ("JSON_SYNTAX_ERROR", "Malformed JSON", "JSON格式错误", false),
+    ("JSON_DATA_ERROR", "JSON does not match the expected structure", "JSON内容与预期结构不符", false),
+    ("JSON_EOF_ERROR", "JSON content is truncated", "JSON内容不完整", false),
+    ("JSON_IO_ERROR", "I/O error while reading or writing JSON", "读写JSON时出错", true),
+];
+
+/// get_error_catalog返回的一个错误码
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorCatalogEntry {
+    pub code: String,
+    pub message: String, // 按当前后端语言
+    pub retryable: bool,
+}
+
+/// 全部错误码及其默认消息
+pub fn error_catalog(locale: Locale) -> Vec<ErrorCatalogEntry> {
+    ERROR_CATALOG
+        .iter()
+        .map(|(code, en, zh, retryable)| ErrorCatalogEntry {
+            code: code.to_string(),
+            message: match locale {
+                Locale::En => en,
+                Locale::Zh => zh,
+            }
+            .to_string(),
+            retryable: *retryable,
+        })
+        .collect()
+}
+
+/// IPC错误类型，Tauri命令失败时前端收到的结构
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct IpcError {
     pub code: String,
     pub message: String,
     pub details: Option<String>,
+    #[serde(default)]
+    pub retryable: bool, // 原样重试可能成功（如写入或上传失败）
+    #[serde(default)]
+    pub field: Option<String>, // 参数校验失败时对应的参数名
+    #[serde(default, skip_serializing_if = "<[ConfigIssue]>::is_empty")]
+    pub issues: Box<[ConfigIssue]>, // 配置校验失败时的全部问题，field为其中第一个错误的字段路径
 }
 
-impl From<crate::benchmark::error::BenchmarkError> for IpcError {
-    fn from(error: crate::benchmark::error::BenchmarkError) -> Self {
-        match error {
-            crate::benchmark::error::BenchmarkError::SystemInfoError(msg) => IpcError {
-                code: "SYSTEM_INFO_ERROR".to_string(),
-                message: "系统信息获取失败".to_string(),
-                details: Some(msg),
-            },
-            crate::benchmark::error::BenchmarkError::CpuTestError(msg) => IpcError {
-                code: "CPU_TEST_ERROR".to_string(),
-                message: "CPU测试失败".to_string(),
-                details: Some(msg),
-            },
-            crate::benchmark::error::BenchmarkError::MemoryTestError(msg) => IpcError {
-                code: "MEMORY_TEST_ERROR".to_string(),
-                message: "内存测试失败".to_string(),
-                details: Some(msg),
-            },
-            crate::benchmark::error::BenchmarkError::StorageTestError(msg) => IpcError {
-                code: "STORAGE_TEST_ERROR".to_string(),
-                message: "存储测试失败".to_string(),
-                details: Some(msg),
-            },
-            crate::benchmark::error::BenchmarkError::DataSaveError(msg) => IpcError {
-                code: "DATA_SAVE_ERROR".to_string(),
-                message: "数据保存失败".to_string(),
-                details: Some(msg),
+impl IpcError {
+    pub fn new(code: &str, message: &str, details: Option<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.to_string(),
+            details,
+            retryable: false,
+            field: None,
+            issues: Box::default(),
+        }
+    }
+
+    /// 按错误码目录以后端当前语言填写消息和retryable，目录中没有的错误码只保留错误码
+    pub fn coded(code: &str, details: Option<String>) -> Self {
+        Self::coded_in(code, details, crate::i18n::backend_locale())
+    }
+
+    /// 同coded，使用指定的语言；原始错误内容保留在details中
+    pub fn coded_in(code: &str, details: Option<String>, locale: Locale) -> Self {
+        match ERROR_CATALOG.iter().find(|(c, _, _, _)| *c == code) {
+            Some((_, en, zh, retryable)) => {
+                let message = match locale {
+                    Locale::En => en,
+                    Locale::Zh => zh,
+                };
+                Self {
+                    retryable: *retryable,
+                    ..Self::new(code, message, details)
+                }
+            }
+            None => Self::new(code, code, details),
+        }
+    }
+
+    pub fn session_not_found(session_id: &str) -> Self {
+        Self::coded("SESSION_NOT_FOUND", Some(session_id.to_string()))
+    }
+
+    pub fn invalid_argument(field: &str, details: impl Into<String>) -> Self {
+        Self {
+            field: Some(field.to_string()),
+            ..Self::coded("INVALID_ARGUMENT", Some(details.into()))
+        }
+    }
+
+    pub fn not_implemented(details: &str) -> Self {
+        Self::coded("NOT_IMPLEMENTED", Some(details.to_string()))
+    }
+}
+
+/// 事件名，只能由`events`中的常量构造，emit调用因此无法传入字符串字面量
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventName(&'static str);
+
+impl EventName {
+    pub const fn as_str(self) -> &'static str {
+        self.0
+    }
+}
+
+impl std::fmt::Display for EventName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl From<EventName> for String {
+    fn from(event: EventName) -> Self {
+        event.0.to_string()
+    }
+}
+
+impl PartialEq<EventName> for String {
+    fn eq(&self, other: &EventName) -> bool {
+        self == other.0
+    }
+}
+
+/// 后端发出的事件名
+pub mod events {
+    use super::EventName;
+
+    pub const BENCHMARK_PROGRESS: EventName = EventName("benchmark-progress");
+    pub const BENCHMARK_COMPLETE: EventName = EventName("benchmark-complete");
+    pub const BENCHMARK_ERROR: EventName = EventName("benchmark-error");
+    pub const CPU_TEST_PROGRESS: EventName = EventName("cpu-test-progress");
+    pub const MEMORY_TEST_PROGRESS: EventName = EventName("memory-test-progress");
+    pub const STORAGE_TEST_PROGRESS: EventName = EventName("storage-test-progress");
+    pub const COPY_TEST_PROGRESS: EventName = EventName("copy-test-progress");
+    pub const GPU_TEST_PROGRESS: EventName = EventName("gpu-test-progress");
+    pub const NETWORK_TEST_PROGRESS: EventName = EventName("network-test-progress");
+    pub const SYSTEM_MONITORING: EventName = EventName("system-monitoring");
+    pub const REAL_TIME_PERFORMANCE: EventName = EventName("real-time-performance");
+    pub const TEST_COMPLETE: EventName = EventName("test-complete");
+    pub const TEST_ERROR: EventName = EventName("test-error");
+    pub const TEST_WARNING: EventName = EventName("test-warning");
+    pub const SESSIONS_CLEANED: EventName = EventName("sessions-cleaned");
+    pub const SESSIONS_RECOVERED: EventName = EventName("sessions-recovered");
+    pub const SESSION_STATUS_CHANGED: EventName = EventName("session-status-changed");
+    pub const BENCHMARK_HEARTBEAT: EventName = EventName("benchmark-heartbeat");
+    pub const PERFORMANCE_REGRESSION_DETECTED: EventName = EventName("performance-regression-detected");
+    pub const WEBVIEW_BENCHMARK_REQUEST: EventName = EventName("webview-benchmark-request"); // 请前端运行WebView测试负载
+    pub const POWER_PLAN_SWITCH_REQUEST: EventName = EventName("power-plan-switch-request"); // 请用户手动切换电源计划
+
+    /// 全部事件，get_event_schema按此列出
+    pub const ALL: &[EventName] = &[
+        BENCHMARK_PROGRESS,
+        BENCHMARK_COMPLETE,
+        BENCHMARK_ERROR,
+        CPU_TEST_PROGRESS,
+        MEMORY_TEST_PROGRESS,
+        STORAGE_TEST_PROGRESS,
+        COPY_TEST_PROGRESS,
+        GPU_TEST_PROGRESS,
+        NETWORK_TEST_PROGRESS,
+        SYSTEM_MONITORING,
+        REAL_TIME_PERFORMANCE,
+        TEST_COMPLETE,
+        TEST_ERROR,
+        TEST_WARNING,
+        SESSIONS_CLEANED,
+        SESSIONS_RECOVERED,
+        SESSION_STATUS_CHANGED,
+        BENCHMARK_HEARTBEAT,
+        PERFORMANCE_REGRESSION_DETECTED,
+        WEBVIEW_BENCHMARK_REQUEST,
+        POWER_PLAN_SWITCH_REQUEST,
+    ];
+
+    /// 会话通道取代的全局事件，只在开启兼容广播时仍然全局发送
+    pub const SUITE_STREAM: &[EventName] = &[
+        BENCHMARK_PROGRESS,
+        SYSTEM_MONITORING,
+        REAL_TIME_PERFORMANCE,
+        TEST_WARNING,
+        BENCHMARK_HEARTBEAT,
+        TEST_COMPLETE,
+        TEST_ERROR,
+        BENCHMARK_COMPLETE,
+        BENCHMARK_ERROR,
+    ];
+}
+
+/// 通过会话通道发送的套件事件，前端只收到自己启动的会话的事件
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "event", content = "data", rename_all = "camelCase")]
+pub enum SuiteEvent {
+    Progress(BenchmarkProgress),
+    Monitoring(SystemMonitoringData),
+    Performance(RealTimePerformanceData),
+    Warning(TestWarningEvent),
+    Heartbeat(HeartbeatEvent),
+    TestComplete(Box<TestCompleteEvent>), // 失败的测试也在这里，不再单独发送test-error；包含测试结果，同样装箱
+    SuiteComplete(Box<BenchmarkSuiteCompleteEvent>), // 包含完整结果，装箱避免放大其他事件
+    Error { message: String },
+}
+
+impl SuiteEvent {
+    /// 按全局事件名转换已序列化的负载；test-error和不属于套件流的事件返回None
+    pub fn from_emitted(event: EventName, payload: &serde_json::Value) -> Option<SuiteEvent> {
+        let payload = payload.clone();
+        let parsed = match event {
+            events::BENCHMARK_PROGRESS => serde_json::from_value(payload).map(SuiteEvent::Progress),
+            events::SYSTEM_MONITORING => serde_json::from_value(payload).map(SuiteEvent::Monitoring),
+            events::REAL_TIME_PERFORMANCE => serde_json::from_value(payload).map(SuiteEvent::Performance),
+            events::TEST_WARNING => serde_json::from_value(payload).map(SuiteEvent::Warning),
+            events::BENCHMARK_HEARTBEAT => serde_json::from_value(payload).map(SuiteEvent::Heartbeat),
+            events::TEST_COMPLETE => serde_json::from_value(payload).map(|complete| SuiteEvent::TestComplete(Box::new(complete))),
+            events::BENCHMARK_COMPLETE => serde_json::from_value(payload).map(|complete| SuiteEvent::SuiteComplete(Box::new(complete))),
+            events::BENCHMARK_ERROR => serde_json::from_value(payload).map(|message| SuiteEvent::Error { message }),
+            _ => return None,
+        };
+        parsed.ok()
+    }
+}
+
+/// 不依赖Tauri的事件接收端，BenchmarkCore通过它发送测试完成和警告事件。
+/// 闭包可以直接作为接收端使用
+pub trait EventSink: Send + Sync {
+    fn send(&self, event: SuiteEvent);
+}
+
+impl<F: Fn(SuiteEvent) + Send + Sync> EventSink for F {
+    fn send(&self, event: SuiteEvent) {
+        self(event)
+    }
+}
+
+/// 每个(会话, 事件名)每秒最多转发的进度事件数
+pub const DEFAULT_PROGRESS_EVENTS_PER_SECOND: u32 = 10;
+
+/// 进度事件限速状态，所有会话共享。热路径上只取读锁和做一次原子比较交换
+#[derive(Debug)]
+pub struct ProgressThrottle {
+    epoch: Instant,
+    min_interval_nanos: AtomicU64, // 0表示不限速
+    last_sent: RwLock<HashMap<String, Arc<AtomicU64>>>, // 距epoch的纳秒数，0表示从未发送
+}
+
+impl Default for ProgressThrottle {
+    fn default() -> Self {
+        Self::new(DEFAULT_PROGRESS_EVENTS_PER_SECOND)
+    }
+}
+
+impl ProgressThrottle {
+    pub fn new(max_per_second: u32) -> Self {
+        let throttle = Self {
+            epoch: Instant::now(),
+            min_interval_nanos: AtomicU64::new(0),
+            last_sent: RwLock::new(HashMap::new()),
+        };
+        throttle.set_max_per_second(max_per_second);
+        throttle
+    }
+
+    /// 0表示不限速
+    pub fn set_max_per_second(&self, max_per_second: u32) {
+        let interval = if max_per_second == 0 {
+            0
+        } else {
+            1_000_000_000 / max_per_second as u64
+        };
+        self.min_interval_nanos.store(interval, Ordering::Relaxed);
+    }
+
+    /// 两次转发之间的最小间隔，不限速时为0
+    pub fn min_interval(&self) -> Duration {
+        Duration::from_nanos(self.min_interval_nanos.load(Ordering::Relaxed))
+    }
+
+    /// 距上次转发已超过最小间隔时占用本次名额
+    pub fn try_acquire(&self, key: &str) -> bool {
+        // +1保证第一次调用时不会与“从未发送”混淆
+        let now = self.epoch.elapsed().as_nanos() as u64 + 1;
+        let interval = self.min_interval_nanos.load(Ordering::Relaxed);
+        let slot = self.slot(key);
+        let last = slot.load(Ordering::Relaxed);
+        if last != 0 && now.saturating_sub(last) < interval {
+            return false;
+        }
+        // 并发回调中只有一个能拿到名额
+        slot.compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed).is_ok()
+    }
+
+    /// 会话结束后丢弃其计时状态
+    pub fn forget_session(&self, session_id: &str) {
+        let prefix = format!("{}/", session_id);
+        self.last_sent.write().unwrap().retain(|key, _| !key.starts_with(&prefix));
+    }
+
+    fn slot(&self, key: &str) -> Arc<AtomicU64> {
+        if let Some(slot) = self.last_sent.read().unwrap().get(key) {
+            return slot.clone();
+        }
+        self.last_sent.write().unwrap().entry(key.to_string()).or_default().clone()
+    }
+}
+
+/// 每个会话保留的最近事件数
+pub const REPLAY_EVENTS_PER_SESSION: usize = 500;
+
+/// 最多保留多少个会话的事件，超出时丢弃最早出现的会话
+pub const REPLAY_SESSIONS: usize = 16;
+
+/// 缓冲区中的一条事件，payload与实际发送的负载相同
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedEvent {
+    pub seq: u64,
+    pub event: String,
+    pub payload: serde_json::Value,
+}
+
+/// get_recent_events的返回值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentEvents {
+    pub events: Vec<RecordedEvent>,
+    #[serde(alias = "latest_seq")]
+    pub latest_seq: u64, // 该会话已分配的最大序号，0表示还没有事件
+    pub truncated: bool, // since_seq之后有事件已被挤出缓冲区
+}
+
+#[derive(Debug, Default)]
+struct SessionEvents {
+    latest_seq: u64,
+    events: VecDeque<RecordedEvent>,
+}
+
+#[derive(Debug, Default)]
+struct ReplayState {
+    sessions: HashMap<String, SessionEvents>,
+    order: VecDeque<String>, // 会话首次出现的顺序
+}
+
+/// 事件重放缓冲区：webview重新加载后前端据此补齐错过的事件，
+/// 之后按序号去重继续接收实时事件
+#[derive(Debug, Default)]
+pub struct EventBuffer {
+    state: Mutex<ReplayState>,
+}
+
+impl EventBuffer {
+    /// 分配序号并记录事件；对象负载会带上seq字段，返回实际要发送的负载
+    pub fn record(&self, session_id: &str, event: &str, mut payload: serde_json::Value) -> serde_json::Value {
+        let mut state = self.state.lock().unwrap();
+        if !state.sessions.contains_key(session_id) {
+            if state.order.len() >= REPLAY_SESSIONS {
+                if let Some(oldest) = state.order.pop_front() {
+                    state.sessions.remove(&oldest);
+                }
+            }
+            state.order.push_back(session_id.to_string());
+        }
+        let session = state.sessions.entry(session_id.to_string()).or_default();
+        session.latest_seq += 1;
+        if let serde_json::Value::Object(map) = &mut payload {
+            map.insert("seq".to_string(), session.latest_seq.into());
+        }
+        if session.events.len() >= REPLAY_EVENTS_PER_SESSION {
+            session.events.pop_front();
+        }
+        session.events.push_back(RecordedEvent {
+            seq: session.latest_seq,
+            event: event.to_string(),
+            payload: payload.clone(),
+        });
+        payload
+    }
+
+    /// 序号大于`since_seq`的事件，按序号排列，`limit`用于分页
+    pub fn recent(&self, session_id: &str, since_seq: u64, limit: Option<usize>) -> RecentEvents {
+        let state = self.state.lock().unwrap();
+        let Some(session) = state.sessions.get(session_id) else {
+            return RecentEvents {
+                events: Vec::new(),
+                latest_seq: 0,
+                truncated: false,
+            };
+        };
+        let first_seq = session.events.front().map_or(session.latest_seq + 1, |e| e.seq);
+        RecentEvents {
+            events: session
+                .events
+                .iter()
+                .filter(|e| e.seq > since_seq)
+                .take(limit.unwrap_or(usize::MAX))
+                .cloned()
+                .collect(),
+            latest_seq: session.latest_seq,
+            truncated: since_seq + 1 < first_seq,
+        }
+    }
+}
+
+/// 实时发送警告的过滤条件，被过滤的警告仍然记入会话历史和测试结果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WarningFilter {
+    #[serde(default, alias = "min_severity")]
+    pub min_severity: WarningSeverity,
+    #[serde(default, alias = "muted_types")]
+    pub muted_types: Vec<String>, // 不实时发送的warning_type
+}
+
+impl WarningFilter {
+    pub fn allows(&self, severity: &WarningSeverity, warning_type: &str) -> bool {
+        *severity >= self.min_severity && !self.muted_types.iter().any(|muted| muted == warning_type)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, BenchmarkError> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| BenchmarkError::DataLoadError(format!("警告过滤配置解析失败: {}", e))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(BenchmarkError::DataLoadError(format!("无法读取警告过滤配置: {}", e))),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BenchmarkError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| BenchmarkError::DataSaveError(format!("无法创建配置目录: {}", e)))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| BenchmarkError::DataSaveError(format!("警告过滤配置序列化失败: {}", e)))?;
+        std::fs::write(path, content)
+            .map_err(|e| BenchmarkError::DataSaveError(format!("警告过滤配置保存失败: {}", e)))
+    }
+}
+
+/// 同一警告在该时间内重复出现时不再发送，只计数
+pub const WARNING_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// 最多保留多少个会话的警告历史，超出时丢弃最早出现的会话
+pub const WARNING_HISTORY_SESSIONS: usize = 16;
+
+/// 会话中一种警告的历史，按(test_type, warning_type)合并
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WarningRecord {
+    #[serde(alias = "test_type")]
+    pub test_type: TestType,
+    #[serde(alias = "warning_type")]
+    pub warning_type: String,
+    pub severity: WarningSeverity,
+    pub message: String, // 首次出现时的消息
+    #[serde(alias = "message_key")]
+    pub message_key: String,
+    pub count: u32,   // 出现总次数
+    pub emitted: u32, // 实际发送的事件数，包括汇总事件；被过滤的警告为0
+    pub active: bool, // 条件尚未解除
+    #[serde(alias = "first_seen")]
+    pub first_seen: String,
+    #[serde(alias = "last_seen")]
+    pub last_seen: String,
+}
+
+#[derive(Debug)]
+struct WarningEntry {
+    record: WarningRecord,
+    session_id: String,
+    last_emitted: Instant,
+    suppressed: u32, // 上次发送后被抑制的次数
+}
+
+impl WarningEntry {
+    // 被抑制的重复汇总为一条事件，没有被抑制的重复时不发送
+    fn take_summary(&mut self, locale: Locale, now: Instant) -> Option<TestWarningEvent> {
+        if self.suppressed == 0 {
+            return None;
+        }
+        let message = Message::new("warning.repeated")
+            .param("warning_type", &self.record.warning_type)
+            .param("count", self.suppressed);
+        self.suppressed = 0;
+        self.last_emitted = now;
+        self.record.emitted += 1;
+        Some(TestWarningEvent {
+            session_id: self.session_id.clone(),
+            test_type: self.record.test_type,
+            warning_type: self.record.warning_type.clone(),
+            message: message.render(locale),
+            message_key: message.key.to_string(),
+            params: message.params,
+            severity: self.record.severity.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct WarningState {
+    sessions: HashMap<String, Vec<WarningEntry>>,
+    order: VecDeque<String>, // 会话首次出现的顺序
+}
+
+/// 警告去重：同一会话、测试和警告类型的首次出现立即发送，
+/// 冷却时间内的重复只计数，冷却结束、条件解除或测试结束时发送一条汇总。
+/// 不满足过滤条件的警告只记入历史，不发送
+#[derive(Debug)]
+pub struct WarningManager {
+    cooldown: Duration,
+    filter: RwLock<WarningFilter>,
+    state: Mutex<WarningState>,
+}
+
+impl Default for WarningManager {
+    fn default() -> Self {
+        Self::new(WARNING_COOLDOWN)
+    }
+}
+
+impl WarningManager {
+    pub fn new(cooldown: Duration) -> Self {
+        Self {
+            cooldown,
+            filter: RwLock::new(WarningFilter::default()),
+            state: Mutex::new(WarningState::default()),
+        }
+    }
+
+    pub fn set_filter(&self, filter: WarningFilter) {
+        *self.filter.write().unwrap() = filter;
+    }
+
+    pub fn filter(&self) -> WarningFilter {
+        self.filter.read().unwrap().clone()
+    }
+
+    /// 记录一次警告，返回现在需要发送的事件
+    pub fn report(&self, warning: TestWarningEvent, locale: Locale, now: Instant) -> Vec<TestWarningEvent> {
+        let live = self.filter.read().unwrap().allows(&warning.severity, &warning.warning_type);
+        let mut state = self.state.lock().unwrap();
+        if !state.sessions.contains_key(&warning.session_id) {
+            if state.order.len() >= WARNING_HISTORY_SESSIONS {
+                if let Some(oldest) = state.order.pop_front() {
+                    state.sessions.remove(&oldest);
+                }
+            }
+            state.order.push_back(warning.session_id.clone());
+        }
+        let entries = state.sessions.entry(warning.session_id.clone()).or_default();
+        let timestamp = chrono::Utc::now().to_rfc3339();
+        let existing = entries
+            .iter_mut()
+            .find(|e| e.record.test_type == warning.test_type && e.record.warning_type == warning.warning_type);
+
+        match existing {
+            Some(entry) if entry.record.active => {
+                entry.record.count += 1;
+                entry.record.last_seen = timestamp;
+                if !live {
+                    return Vec::new();
+                }
+                entry.suppressed += 1;
+                if now.saturating_duration_since(entry.last_emitted) < self.cooldown {
+                    return Vec::new();
+                }
+                entry.take_summary(locale, now).into_iter().collect()
+            }
+            // 条件解除后再次出现，视为新的一次警告
+            Some(entry) => {
+                entry.record.count += 1;
+                entry.record.active = true;
+                entry.record.last_seen = timestamp;
+                if !live {
+                    return Vec::new();
+                }
+                entry.record.emitted += 1;
+                entry.last_emitted = now;
+                vec![warning]
+            }
+            None => {
+                entries.push(WarningEntry {
+                    record: WarningRecord {
+                        test_type: warning.test_type,
+                        warning_type: warning.warning_type.clone(),
+                        severity: warning.severity.clone(),
+                        message: warning.message.clone(),
+                        message_key: warning.message_key.clone(),
+                        count: 1,
+                        emitted: live as u32,
+                        active: true,
+                        first_seen: timestamp.clone(),
+                        last_seen: timestamp,
+                    },
+                    session_id: warning.session_id.clone(),
+                    last_emitted: now,
+                    suppressed: 0,
+                });
+                if live {
+                    vec![warning]
+                } else {
+                    Vec::new()
+                }
+            }
+        }
+    }
+
+    /// 警告条件解除，返回被抑制重复的汇总
+    pub fn clear(&self, session_id: &str, test_type: TestType, warning_type: &str, locale: Locale, now: Instant) -> Vec<TestWarningEvent> {
+        self.close(session_id, locale, now, |record| {
+            record.test_type == test_type && record.warning_type == warning_type
+        })
+    }
+
+    /// 测试结束时解除其全部警告；`test_type`为None时解除整个会话的警告
+    pub fn finish(&self, session_id: &str, test_type: Option<TestType>, locale: Locale, now: Instant) -> Vec<TestWarningEvent> {
+        self.close(session_id, locale, now, |record| {
+            test_type.is_none_or(|test_type| record.test_type == test_type)
+        })
+    }
+
+    /// 会话的警告历史，按首次出现的顺序排列
+    pub fn history(&self, session_id: &str) -> Vec<WarningRecord> {
+        self.state
+            .lock()
+            .unwrap()
+            .sessions
+            .get(session_id)
+            .map(|entries| entries.iter().map(|e| e.record.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    fn close(&self, session_id: &str, locale: Locale, now: Instant, matches: impl Fn(&WarningRecord) -> bool) -> Vec<TestWarningEvent> {
+        let filter = self.filter();
+        let mut state = self.state.lock().unwrap();
+        let Some(entries) = state.sessions.get_mut(session_id) else {
+            return Vec::new();
+        };
+        entries
+            .iter_mut()
+            .filter(|e| e.record.active && matches(&e.record))
+            .filter_map(|entry| {
+                entry.record.active = false;
+                // 期间过滤条件可能已经改变
+                if !filter.allows(&entry.record.severity, &entry.record.warning_type) {
+                    entry.suppressed = 0;
+                    return None;
+                }
+                entry.take_summary(locale, now)
+            })
+            .collect()
+    }
+}
+
+// 会话最近一次真实进度，心跳看门狗据此判断是否安静
+
+/// 会话内高频指标采样的发送统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricSampleCounters {
+    pub delivered: u64, // 实际发送的采样数
+    pub dropped: u64,   // 被同一指标流的更新值覆盖而未发送的采样数
+}
+
+/// 高频指标的最新值槽，每个指标流一个。对仪表类数据只有最新值有意义，
+/// 新采样直接覆盖槽中未发送的值，由刷新任务按限速频率取出发送，不会排队积压
+#[derive(Debug, Default)]
+pub struct LatestValueSlots {
+    slots: Mutex<Vec<(String, EventName, serde_json::Value)>>, // 按指标流首次出现的顺序
+    delivered: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl LatestValueSlots {
+    /// 写入指标流的最新值，覆盖了未发送的值时计为丢弃
+    pub fn put(&self, stream: &str, event: EventName, value: serde_json::Value) {
+        let mut slots = self.slots.lock().unwrap();
+        match slots.iter_mut().find(|(key, _, _)| key == stream) {
+            Some(slot) => {
+                slot.1 = event;
+                slot.2 = value;
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            None => slots.push((stream.to_string(), event, value)),
+        }
+    }
+
+    /// 取出全部待发送的值并计为已发送
+    pub fn take(&self) -> Vec<(EventName, serde_json::Value)> {
+        let taken: Vec<_> = self.slots.lock().unwrap().drain(..).map(|(_, event, value)| (event, value)).collect();
+        self.delivered.fetch_add(taken.len() as u64, Ordering::Relaxed);
+        taken
+    }
+
+    pub fn counters(&self) -> MetricSampleCounters {
+        MetricSampleCounters {
+            delivered: self.delivered.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(feature = "tauri-app")]
+#[derive(Debug)]
+struct ProgressActivity {
+    at: Instant,
+    phase: String,
+}
+
+/// app.rs中所有事件都经由这里发送：进度类事件按会话限速，高频指标只保留每个流的最新值，
+/// 完成、错误、警告和状态变化立即按顺序发送。发送的事件都记录到重放缓冲区
+#[cfg(feature = "tauri-app")]
+pub struct ProgressEmitter<R: Runtime> {
+    app: AppHandle<R>,
+    throttle: Arc<ProgressThrottle>,
+    buffer: Arc<EventBuffer>,
+    warnings: Arc<WarningManager>,
+    latest: Arc<LatestValueSlots>,          // 克隆之间共享
+    activity: Arc<Mutex<ProgressActivity>>, // 克隆之间共享
+    channel: Option<Channel<SuiteEvent>>,
+    broadcast: bool, // 有通道时是否仍然全局发送套件事件
+    telemetry: Option<Arc<Telemetry>>,
+    timeseries: Option<Arc<TimeseriesStore>>,
+    session_id: String,
+    locale: Locale,
+}
+
+// 手动实现以避免derive给R加上Clone约束
+#[cfg(feature = "tauri-app")]
+impl<R: Runtime> Clone for ProgressEmitter<R> {
+    fn clone(&self) -> Self {
+        Self {
+            app: self.app.clone(),
+            throttle: self.throttle.clone(),
+            buffer: self.buffer.clone(),
+            warnings: self.warnings.clone(),
+            latest: self.latest.clone(),
+            activity: self.activity.clone(),
+            channel: self.channel.clone(),
+            broadcast: self.broadcast,
+            telemetry: self.telemetry.clone(),
+            timeseries: self.timeseries.clone(),
+            session_id: self.session_id.clone(),
+            locale: self.locale,
+        }
+    }
+}
+
+#[cfg(feature = "tauri-app")]
+impl<R: Runtime> ProgressEmitter<R> {
+    pub fn new(
+        app: AppHandle<R>,
+        throttle: Arc<ProgressThrottle>,
+        buffer: Arc<EventBuffer>,
+        session_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            app,
+            throttle,
+            buffer,
+            warnings: Arc::default(),
+            latest: Arc::default(),
+            activity: Arc::new(Mutex::new(ProgressActivity {
+                at: Instant::now(),
+                phase: String::new(),
+            })),
+            channel: None,
+            broadcast: true,
+            telemetry: None,
+            timeseries: None,
+            session_id: session_id.into(),
+            locale: Locale::default(),
+        }
+    }
+
+    /// 消息文本按该语言渲染，默认中文
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// 警告经由该管理器去重，默认每个发送器独立
+    pub fn with_warnings(mut self, warnings: Arc<WarningManager>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// 套件事件改为通过会话通道发送；`broadcast`为true时同时保留已弃用的全局事件
+    pub fn with_channel(mut self, channel: Channel<SuiteEvent>, broadcast: bool) -> Self {
+        self.channel = Some(channel);
+        self.broadcast = broadcast;
+        self
+    }
+
+    /// 发送的事件同时更新指标导出的数据
+    pub fn with_telemetry(mut self, telemetry: Arc<Telemetry>) -> Self {
+        self.telemetry = Some(telemetry);
+        self
+    }
+
+    pub fn with_timeseries(mut self, timeseries: Arc<TimeseriesStore>) -> Self {
+        self.timeseries = Some(timeseries);
+        self
+    }
+
+    pub fn session_id(&self) -> &str {
+        &self.session_id
+    }
+
+    pub fn render(&self, message: &Message) -> String {
+        message.render(self.locale)
+    }
+
+    /// 记录一次真实进度及其所在阶段，无论进度事件是否被限速
+    pub fn record_progress(&self, phase: &str) {
+        let mut activity = self.activity.lock().unwrap();
+        activity.at = Instant::now();
+        if activity.phase != phase {
+            activity.phase = phase.to_string();
+        }
+    }
+
+    /// 只重置安静计时，不改变阶段（排队或暂停期间）
+    pub fn touch(&self) {
+        self.activity.lock().unwrap().at = Instant::now();
+    }
+
+    /// 最近一次进度的时间和阶段
+    pub fn last_progress(&self) -> (Instant, String) {
+        let activity = self.activity.lock().unwrap();
+        (activity.at, activity.phase.clone())
+    }
+
+    pub fn emit<S: Serialize + Clone>(&self, event: EventName, payload: S) {
+        let value = match serde_json::to_value(&payload) {
+            Ok(mut value) => {
+                if let serde_json::Value::Object(map) = &mut value {
+                    map.insert("schemaVersion".to_string(), EVENT_SCHEMA_VERSION.into());
+                }
+                self.buffer.record(&self.session_id, event.as_str(), value)
+            }
+            Err(_) => {
+                let _ = self.app.emit(event.as_str(), payload);
+                return;
+            }
+        };
+        if let Some(telemetry) = self.telemetry.as_ref().filter(|_| Telemetry::OBSERVED.contains(&event)) {
+            if let Some(suite_event) = SuiteEvent::from_emitted(event, &value) {
+                telemetry.observe(&self.session_id, &suite_event);
+            }
+        }
+        if let Some(timeseries) = self.timeseries.as_ref().filter(|_| TimeseriesStore::RECORDED_ON_EMIT.contains(&event)) {
+            timeseries.observe(&self.session_id, event, &value);
+        }
+        if let Some(channel) = &self.channel {
+            if events::SUITE_STREAM.contains(&event) {
+                // test-error已包含在失败的TestComplete中；通道已关闭时退回全局事件
+                let delivered = SuiteEvent::from_emitted(event, &value).is_none_or(|suite_event| channel.send(suite_event).is_ok());
+                if delivered && !self.broadcast {
+                    return;
+                }
+            }
+        }
+        let _ = self.app.emit(event.as_str(), value);
+    }
+
+    /// 进度达到100%时总是发送，否则受限速约束；负载只在确实发送时才构造。
+    /// 返回是否发送
+    pub fn emit_progress<S, F>(&self, event: EventName, progress: f64, payload: F) -> bool
+    where
+        S: Serialize + Clone,
+        F: FnOnce() -> S,
+    {
+        let key = format!("{}/{}", self.session_id, event);
+        if progress < 100.0 && !self.throttle.try_acquire(&key) {
+            return false;
+        }
+        self.emit(event, payload());
+        true
+    }
+
+    /// 高频指标采样写入`stream`的最新值槽，由flush_latest按限速频率发送，
+    /// 两次刷新之间的中间值被丢弃（但都记入会话时间序列）
+    pub fn emit_latest<S: Serialize>(&self, event: EventName, stream: &str, payload: S) {
+        if let Ok(value) = serde_json::to_value(payload) {
+            if let Some(timeseries) = &self.timeseries {
+                timeseries.observe(&self.session_id, event, &value);
+            }
+            self.latest.put(stream, event, value);
+        }
+    }
+
+    /// 发送各指标流槽中的最新值，返回发送数
+    pub fn flush_latest(&self) -> usize {
+        let pending = self.latest.take();
+        let count = pending.len();
+        for (event, value) in pending {
+            self.emit(event, value);
+        }
+        count
+    }
+
+    /// 刷新任务的间隔，与进度事件的限速一致，不限速时也不低于10毫秒
+    pub fn flush_interval(&self) -> Duration {
+        self.throttle.min_interval().max(Duration::from_millis(10))
+    }
+
+    pub fn metric_samples(&self) -> MetricSampleCounters {
+        self.latest.counters()
+    }
+
+    /// 发送警告，同一警告的重复由WarningManager合并
+    pub fn warn(&self, warning: TestWarningEvent) {
+        for event in self.warnings.report(warning, self.locale, Instant::now()) {
+            self.emit(events::TEST_WARNING, event);
+        }
+    }
+
+    /// 警告条件解除，发送被抑制重复的汇总
+    pub fn clear_warning(&self, test_type: TestType, warning_type: &str) {
+        for event in self.warnings.clear(&self.session_id, test_type, warning_type, self.locale, Instant::now()) {
+            self.emit(events::TEST_WARNING, event);
+        }
+    }
+
+    /// 本会话到目前为止的警告历史
+    pub fn warning_history(&self) -> Vec<WarningRecord> {
+        self.warnings.history(&self.session_id)
+    }
+
+    /// 单项测试结束时调用，先发送其最后的指标值，再汇总该测试仍在抑制中的警告
+    pub fn finish_test(&self, test_type: TestType) {
+        self.flush_latest();
+        for event in self.warnings.finish(&self.session_id, Some(test_type), self.locale, Instant::now()) {
+            self.emit(events::TEST_WARNING, event);
+        }
+    }
+
+    /// 会话结束时调用，发送剩余的指标值和警告汇总并释放限速状态
+    pub fn finish(&self) {
+        self.flush_latest();
+        for event in self.warnings.finish(&self.session_id, None, self.locale, Instant::now()) {
+            self.emit(events::TEST_WARNING, event);
+        }
+        self.throttle.forget_session(&self.session_id);
+    }
+}
+
+/// 一个IPC结构体序列化后的字段名
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeSchema {
+    pub name: String,
+    pub fields: Vec<String>,
+}
+
+/// 事件名及其负载类型，负载为结构体时对应`types`中的名称
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventSchema {
+    pub name: String,
+    pub payload: String,
+}
+
+/// 前端在CI中校验绑定用的事件契约
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IpcSchema {
+    #[serde(default, alias = "schema_version")]
+    pub schema_version: u32,
+    pub events: Vec<EventSchema>,
+    pub types: Vec<TypeSchema>,
+    #[serde(default, alias = "test_types")]
+    pub test_types: Vec<TestType>, // testType字段的全部取值
+    #[serde(default)]
+    pub metrics: Vec<MetricSchema>, // 指标导出的指标名、类型和标签
+}
+
+/// get_supported_schema_versions的返回值，前端启动时据此检查自身是否过旧
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportedSchemaVersions {
+    pub event: u32,          // 当前事件负载版本
+    pub result: u32,         // 当前结果文档版本
+    pub readable_results: Vec<u32>, // 可以读取并升级的结果文档版本
+}
+
+// 字段名取自示例值的实际序列化结果，和serde属性保持一致
+fn type_schema<T: Serialize>(name: &str, sample: &T) -> TypeSchema {
+    let fields = match serde_json::to_value(sample) {
+        Ok(serde_json::Value::Object(map)) => map.keys().cloned().collect(),
+        _ => Vec::new(),
+    };
+    TypeSchema {
+        name: name.to_string(),
+        fields,
+    }
+}
+
+/// 事件负载的类型名，对应types中的条目或TypeScript基本类型
+fn event_payload(event: EventName) -> &'static str {
+    match event {
+        events::BENCHMARK_PROGRESS => "BenchmarkProgress",
+        events::BENCHMARK_COMPLETE => "BenchmarkSuiteCompleteEvent",
+        events::BENCHMARK_ERROR => "string",
+        events::CPU_TEST_PROGRESS => "ProgressUpdate",
+        events::MEMORY_TEST_PROGRESS => "ProgressUpdate",
+        events::STORAGE_TEST_PROGRESS => "ProgressUpdate",
+        events::COPY_TEST_PROGRESS => "ProgressUpdate",
+        events::GPU_TEST_PROGRESS => "ProgressUpdate",
+        events::NETWORK_TEST_PROGRESS => "ProgressUpdate",
+        events::SYSTEM_MONITORING => "SystemMonitoringData",
+        events::REAL_TIME_PERFORMANCE => "RealTimePerformanceData",
+        events::TEST_COMPLETE => "TestCompleteEvent",
+        events::TEST_ERROR => "TestCompleteEvent",
+        events::TEST_WARNING => "TestWarningEvent",
+        events::SESSIONS_CLEANED => "number",
+        events::SESSIONS_RECOVERED => "RecoveredSession[]",
+        events::SESSION_STATUS_CHANGED => "SessionStatusChangedEvent",
+        events::BENCHMARK_HEARTBEAT => "HeartbeatEvent",
+        events::PERFORMANCE_REGRESSION_DETECTED => "RegressionDetectedEvent",
+        events::WEBVIEW_BENCHMARK_REQUEST => "WebviewBenchmarkRequest",
+        events::POWER_PLAN_SWITCH_REQUEST => "PowerPlanSwitchRequest",
+        _ => "unknown",
+    }
+}
+
+pub fn event_schema() -> IpcSchema {
+    let types = vec![
+        type_schema("BenchmarkProgress", &BenchmarkProgress {
+            session_id: String::new(),
+            current_test: String::new(),
+            overall_progress: 0.0,
+            test_progress: 0.0,
+            message: String::new(),
+            message_key: String::new(),
+            params: HashMap::new(),
+            estimated_time_remaining: None,
+            estimated_completion_time: None,
+        }),
+        type_schema("SystemMonitoringData", &SystemMonitoringData {
+            cpu_usage: 0.0,
+            memory_usage: 0.0,
+            temperature: None,
+            timestamp: String::new(),
+            session_id: None,
+        }),
+        type_schema("ProgressUpdate", &ProgressUpdate {
+            progress: 0.0,
+            message: String::new(),
+            message_key: String::new(),
+            params: HashMap::new(),
+            test_type: TestType::Cpu,
+        }),
+        type_schema("TestCompleteEvent", &TestCompleteEvent {
+            session_id: String::new(),
+            test_type: TestType::Cpu,
+            success: false,
+            result: None,
+            error: None,
+            elapsed_seconds: 0.0,
+            outcome: TestCompletion::Completed,
+            issues: Vec::new(),
+        }),
+        type_schema("BenchmarkSuiteCompleteEvent", &BenchmarkSuiteCompleteEvent {
+            session_id: String::new(),
+            success: false,
+            results: None,
+            error: None,
+            completed_at: String::new(),
+            save_error: None,
+        }),
+        type_schema("TestSession", &TestSession {
+            session_id: String::new(),
+            status: TestStatus::Pending,
+            start_time: String::new(),
+            end_time: None,
+            config: None,
+            overall_progress: 0.0,
+            current_test: None,
+            last_update: String::new(),
+            metric_samples: MetricSampleCounters::default(),
+            estimated_completion_time: None,
+        }),
+        type_schema("SessionStatusChangedEvent", &SessionStatusChangedEvent {
+            session_id: String::new(),
+            old_status: TestStatus::Pending,
+            new_status: TestStatus::Queued,
+            timestamp: String::new(),
+            reason: None,
+        }),
+        type_schema("HeartbeatEvent", &HeartbeatEvent {
+            session_id: String::new(),
+            phase: String::new(),
+            seconds_since_progress: 0.0,
+        }),
+        type_schema("RegressionDetectedEvent", &RegressionDetectedEvent {
+            session_id: String::new(),
+            result_id: String::new(),
+            baseline_id: String::new(),
+            baseline_timestamp: String::new(),
+            regressions: Vec::new(),
+        }),
+        type_schema("RealTimePerformanceData", &RealTimePerformanceData {
+            session_id: String::new(),
+            test_type: TestType::Cpu,
+            phase: String::new(),
+            metrics: std::collections::HashMap::new(),
+            timestamp: String::new(),
+        }),
+        type_schema("TestWarningEvent", &TestWarningEvent {
+            session_id: String::new(),
+            test_type: TestType::Cpu,
+            warning_type: String::new(),
+            message: String::new(),
+            message_key: String::new(),
+            params: HashMap::new(),
+            severity: WarningSeverity::Low,
+        }),
+        type_schema("IpcError", &IpcError::new("", "", None)),
+        type_schema("RecoveredSession", &crate::session::RecoveredSession {
+            session_id: String::new(),
+            start_time: String::new(),
+            config: None,
+            removed_artifacts: Vec::new(),
+        }),
+        type_schema("WebviewBenchmarkRequest", &WebviewBenchmarkRequest {
+            nonce: String::new(),
+            workloads: Vec::new(),
+            iterations: 0,
+            timeout_ms: 0,
+        }),
+        type_schema("PowerPlanSwitchRequest", &PowerPlanSwitchRequest {
+            nonce: String::new(),
+            target: PowerPlan {
+                id: String::new(),
+                name: String::new(),
+                high_performance: false,
             },
-            crate::benchmark::error::BenchmarkError::PermissionError(msg) => IpcError {
-                code: "PERMISSION_ERROR".to_string(),
-                message: "权限不足".to_string(),
-                details: Some(msg),
+            restore: false,
+            timeout_ms: 0,
+        }),
+    ];
+
+    let events = events::ALL
+        .iter()
+        .map(|event| EventSchema {
+            name: event.to_string(),
+            payload: event_payload(*event).to_string(),
+        })
+        .collect();
+
+    IpcSchema {
+        schema_version: EVENT_SCHEMA_VERSION,
+        events,
+        types,
+        test_types: TestType::ALL.to_vec(),
+        metrics: metric_schema(),
+    }
+}
+
+/// 配置、结果和事件负载的JSON Schema，按类型名索引。前端和外部分析脚本据此校验或生成模型；
+/// src-tauri/schemas目录保存同样的内容，由测试保证与代码一致
+pub fn json_schemas() -> BTreeMap<String, serde_json::Value> {
+    fn schema<T: JsonSchema>() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(T)).unwrap_or_default()
+    }
+
+    [
+        ("BenchmarkConfig", schema::<BenchmarkConfig>()),
+        ("LoadedConfig", schema::<LoadedConfig>()),
+        ("ConfigIssue", schema::<ConfigIssue>()),
+        ("TestResult", schema::<TestResult>()),
+        ("SuiteEvent", schema::<SuiteEvent>()),
+        ("BenchmarkProgress", schema::<BenchmarkProgress>()),
+        ("SystemMonitoringData", schema::<SystemMonitoringData>()),
+        ("ProgressUpdate", schema::<ProgressUpdate>()),
+        ("TestCompleteEvent", schema::<TestCompleteEvent>()),
+        ("BenchmarkSuiteCompleteEvent", schema::<BenchmarkSuiteCompleteEvent>()),
+        ("TestSession", schema::<TestSession>()),
+        ("UiStateSnapshot", schema::<UiStateSnapshot>()),
+        ("SessionStatusChangedEvent", schema::<SessionStatusChangedEvent>()),
+        ("HeartbeatEvent", schema::<HeartbeatEvent>()),
+        ("RegressionDetectedEvent", schema::<RegressionDetectedEvent>()),
+        ("RealTimePerformanceData", schema::<RealTimePerformanceData>()),
+        ("TestWarningEvent", schema::<TestWarningEvent>()),
+        ("WarningRecord", schema::<WarningRecord>()),
+        ("IpcError", schema::<IpcError>()),
+        ("RecoveredSession", schema::<crate::session::RecoveredSession>()),
+        ("WebviewBenchmarkRequest", schema::<WebviewBenchmarkRequest>()),
+        ("WebviewSubmission", schema::<crate::benchmark::webview::WebviewSubmission>()),
+        ("PowerPlanSwitchRequest", schema::<PowerPlanSwitchRequest>()),
+        ("PowerPlanComparison", schema::<crate::benchmark::power::PowerPlanComparison>()),
+        ("CapabilityMap", schema::<crate::benchmark::capability::CapabilityMap>()),
+        ("ElevationOutcome", schema::<crate::benchmark::capability::ElevationOutcome>()),
+        ("LeaderboardEntry", schema::<crate::benchmark::leaderboard::LeaderboardEntry>()),
+        ("CompositeExport", schema::<crate::benchmark::composite::CompositeExport>()),
+    ]
+    .into_iter()
+    .map(|(name, schema)| (name.to_string(), schema))
+    .collect()
+}
+
+impl From<BenchmarkError> for IpcError {
+    fn from(error: BenchmarkError) -> Self {
+        let code = error.code();
+        let retryable = error.is_retryable();
+        let details = match error {
+            BenchmarkError::SystemInfoError(msg)
+            | BenchmarkError::CpuTestError(msg)
+            | BenchmarkError::MemoryTestError(msg)
+            | BenchmarkError::StorageTestError(msg)
+            | BenchmarkError::GpuTestError(msg)
+            | BenchmarkError::IpcTestError(msg)
+            | BenchmarkError::ProcessTestError(msg)
+            | BenchmarkError::CompressionTestError(msg)
+            | BenchmarkError::CryptoTestError(msg)
+            | BenchmarkError::DatabaseTestError(msg)
+            | BenchmarkError::JsonTestError(msg)
+            | BenchmarkError::StabilityTestError(msg)
+            | BenchmarkError::ImageTestError(msg)
+            | BenchmarkError::PowerPlanError(msg)
+            | BenchmarkError::WebviewTestError(msg)
+            | BenchmarkError::NetworkTestError(msg)
+            | BenchmarkError::DataSaveError(msg)
+            | BenchmarkError::DataLoadError(msg)
+            | BenchmarkError::UploadError(msg) => msg,
+            // 授权方法附在原因之后，前端无需另外查询功能检测结果
+            BenchmarkError::PermissionError { feature, message, remediation } => match remediation {
+                Some(remediation) => format!("{}: {}（{}）", feature, message, remediation),
+                None => format!("{}: {}", feature, message),
             },
+            BenchmarkError::InvalidConfig(issues) => {
+                // 前端按issues逐项标注表单，field指向第一个错误
+                let details = describe_issues(&issues);
+                let field = issues
+                    .iter()
+                    .find(|issue| issue.severity == IssueSeverity::Error)
+                    .map(|issue| issue.field.clone());
+                return Self {
+                    field,
+                    issues: issues.into(),
+                    retryable,
+                    ..Self::coded(code, Some(details))
+                };
+            }
+            BenchmarkError::InvalidTransition { from, to } => format!("{:?} -> {:?}", from, to),
+            BenchmarkError::Cancelled { test, at_progress } => format!("{} @ {:.0}%", test, at_progress),
+            BenchmarkError::Timeout { test, limit_seconds } => format!("{} > {}s", test, limit_seconds),
+            BenchmarkError::Internal { test, panic_message } => format!("{}: {}", test, panic_message),
+            BenchmarkError::Io { context, message, .. } => format!("{}: {}", context, message),
+        };
+        Self { retryable, ..Self::coded(code, Some(details)) }
+    }
+}
+
+// io::Error和BenchmarkError::Io共用的错误码
+pub(crate) fn io_error_code(kind: std::io::ErrorKind) -> &'static str {
+    use std::io::ErrorKind;
+    match kind {
+        ErrorKind::NotFound => "IO_NOT_FOUND",
+        ErrorKind::PermissionDenied | ErrorKind::ReadOnlyFilesystem => "IO_PERMISSION_DENIED",
+        ErrorKind::AlreadyExists => "IO_ALREADY_EXISTS",
+        ErrorKind::StorageFull | ErrorKind::QuotaExceeded => "IO_STORAGE_FULL",
+        ErrorKind::TimedOut => "IO_TIMED_OUT",
+        ErrorKind::Interrupted => "IO_INTERRUPTED",
+        ErrorKind::InvalidData | ErrorKind::UnexpectedEof => "IO_INVALID_DATA",
+        _ => "IO_ERROR",
+    }
+}
+
+// 命令中不属于任何BenchmarkError的失败（如第三方库只返回错误文本）
+impl From<String> for IpcError {
+    fn from(details: String) -> Self {
+        Self::coded("INTERNAL_ERROR", Some(details))
+    }
+}
+
+// 共享状态的锁在其他线程panic后被毒化
+impl<T> From<std::sync::PoisonError<T>> for IpcError {
+    fn from(error: std::sync::PoisonError<T>) -> Self {
+        Self::coded("INTERNAL_ERROR", Some(error.to_string()))
+    }
+}
+
+impl From<std::io::Error> for IpcError {
+    fn from(error: std::io::Error) -> Self {
+        Self::coded(io_error_code(error.kind()), Some(error.to_string()))
+    }
+}
+
+impl From<serde_json::Error> for IpcError {
+    fn from(error: serde_json::Error) -> Self {
+        use serde_json::error::Category;
+        let code = match error.classify() {
+            Category::Syntax => "JSON_SYNTAX_ERROR",
+            Category::Data => "JSON_DATA_ERROR",
+            Category::Eof => "JSON_EOF_ERROR",
+            Category::Io => "JSON_IO_ERROR",
+        };
+        Self::coded(code, Some(error.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+    use serde_json::json;
+
+    fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> serde_json::Value {
+        let json = serde_json::to_value(value).unwrap();
+        let back: T = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&back).unwrap(), json);
+        json
+    }
+
+    #[test]
+    fn test_payloads_round_trip_as_camel_case() {
+        let progress = round_trip(&BenchmarkProgress {
+            session_id: "s1".to_string(),
+            current_test: "CPU基准测试".to_string(),
+            overall_progress: 33.3,
+            test_progress: 50.0,
+            message: "顺序写入进行中... (42.1%)".to_string(),
+            message_key: "storage.seq_write.progress".to_string(),
+            params: [("percent".to_string(), "42.1".to_string())].into_iter().collect(),
+            estimated_time_remaining: Some(12),
+            estimated_completion_time: None,
+        });
+        assert_eq!(progress["estimatedTimeRemaining"], json!(12));
+        assert_eq!(progress["messageKey"], json!("storage.seq_write.progress"));
+        assert_eq!(progress["params"]["percent"], json!("42.1"));
+        assert_eq!(progress["sessionId"], json!("s1"));
+
+        round_trip(&SystemMonitoringData {
+            cpu_usage: 45.0,
+            memory_usage: 60.0,
+            temperature: Some(55.0),
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+            session_id: Some("s1".to_string()),
+        });
+        round_trip(&ProgressUpdate {
+            progress: 10.0,
+            message: "m".to_string(),
+            message_key: "cpu.start".to_string(),
+            params: HashMap::new(),
+            test_type: TestType::Cpu,
+        });
+        let sample = crate::benchmark::test_support::sample_test_result("2024-01-01T00:00:00Z", 100.0);
+        let complete = round_trip(&TestCompleteEvent {
+            session_id: "s1".to_string(),
+            test_type: TestType::Memory,
+            success: true,
+            result: Some(TestResultPayload::Memory(sample.memory_results.clone().unwrap())),
+            error: None,
+            elapsed_seconds: 1.5,
+            outcome: TestCompletion::Completed,
+            issues: Vec::new(),
+        });
+        assert_eq!(complete["result"]["type"], json!("memory"));
+        assert!(complete["result"]["data"]["sequential_read_speed"].is_number());
+        assert_eq!(complete["elapsedSeconds"], json!(1.5));
+        round_trip(&BenchmarkSuiteCompleteEvent {
+            session_id: "s1".to_string(),
+            success: true,
+            results: Some(sample),
+            error: None,
+            completed_at: "2024-01-01T00:01:05Z".to_string(),
+            save_error: Some(IpcError::from(BenchmarkError::DataSaveError("disk full".to_string()))),
+        });
+        let session = round_trip(&TestSession {
+            session_id: "s1".to_string(),
+            status: TestStatus::Running,
+            start_time: "2024-01-01T00:00:00Z".to_string(),
+            end_time: None,
+            config: None,
+            overall_progress: 42.5,
+            current_test: Some("memory".to_string()),
+            last_update: "2024-01-01T00:00:10Z".to_string(),
+            metric_samples: MetricSampleCounters { delivered: 3, dropped: 7 },
+            estimated_completion_time: Some("2024-01-01T00:01:00Z".to_string()),
+        });
+        assert!(session.get("startTime").is_some());
+        assert_eq!(session["overallProgress"], 42.5);
+        assert_eq!(session["currentTest"], "memory");
+        round_trip(&RealTimePerformanceData {
+            session_id: "s1".to_string(),
+            test_type: TestType::Storage,
+            phase: "seq_write".to_string(),
+            metrics: [("mb_per_sec".to_string(), 100.0)].into_iter().collect(),
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+        });
+        let warning = round_trip(&TestWarningEvent {
+            session_id: "s1".to_string(),
+            test_type: TestType::Cpu,
+            warning_type: "preflight".to_string(),
+            message: "m".to_string(),
+            message_key: "preflight.no_tests".to_string(),
+            params: HashMap::new(),
+            severity: WarningSeverity::Medium,
+        });
+        assert_eq!(warning["warningType"], json!("preflight"));
+        round_trip(&IpcError::invalid_argument("intervalSecs", "清理间隔必须大于0"));
+    }
+
+    #[test]
+    fn test_accepts_legacy_snake_case() {
+        let legacy = json!({
+            "session_id": "old",
+            "status": "Completed",
+            "start_time": "2024-01-01T00:00:00Z",
+            "end_time": "2024-01-01T00:01:00Z",
+            "config": null
+        });
+        let session: TestSession = serde_json::from_value(legacy).unwrap();
+        assert_eq!(session.session_id, "old");
+        assert_eq!(session.end_time.as_deref(), Some("2024-01-01T00:01:00Z"));
+
+        let progress: BenchmarkProgress = serde_json::from_value(json!({
+            "session_id": "old",
+            "current_test": "CPU",
+            "overall_progress": 1.0,
+            "test_progress": 2.0,
+            "message": "",
+            "estimated_time_remaining": 5
+        }))
+        .unwrap();
+        assert_eq!(progress.estimated_time_remaining, Some(5));
+        assert!(progress.message_key.is_empty() && progress.params.is_empty());
+    }
+
+    #[cfg(feature = "tauri-app")]
+    #[test]
+    fn test_progress_emitter_coalesces_bursts() {
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let forwarded = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let forwarded_clone = forwarded.clone();
+        app.handle().listen_any(events::BENCHMARK_PROGRESS, move |event| {
+            let payload: serde_json::Value = serde_json::from_str(event.payload()).unwrap();
+            forwarded_clone.lock().unwrap().push(payload["testProgress"].as_f64().unwrap());
+        });
+
+        let throttle = Arc::new(ProgressThrottle::default());
+        let emitter = ProgressEmitter::new(app.handle().clone(), throttle.clone(), Arc::default(), "s1");
+        let started = Instant::now();
+        for i in 1..=1000 {
+            let progress = i as f64 / 10.0;
+            emitter.emit_progress(events::BENCHMARK_PROGRESS, progress, || BenchmarkProgress {
+                session_id: "s1".to_string(),
+                current_test: "CPU基准测试".to_string(),
+                overall_progress: 0.0,
+                test_progress: progress,
+                message: String::new(),
+                message_key: String::new(),
+                params: HashMap::new(),
+                estimated_time_remaining: None,
+                estimated_completion_time: None,
+            });
+            std::thread::sleep(std::time::Duration::from_micros(100));
+        }
+        let elapsed = started.elapsed().as_secs_f64();
+
+        {
+            let forwarded = forwarded.lock().unwrap();
+            // 10次/秒，加上首个和最终的100%
+            let allowed = (elapsed * DEFAULT_PROGRESS_EVENTS_PER_SECOND as f64).ceil() as usize + 2;
+            assert!(forwarded.len() <= allowed.max(15), "转发了 {} 个事件", forwarded.len());
+            assert_eq!(forwarded.first(), Some(&0.1));
+            assert_eq!(forwarded.last(), Some(&100.0));
+        }
+
+        // 不同会话分别计时，会话结束后状态被释放
+        let other = ProgressEmitter::new(app.handle().clone(), throttle.clone(), Arc::default(), "s2");
+        assert!(other.emit_progress(events::REAL_TIME_PERFORMANCE, 1.0, || 1.0));
+        emitter.finish();
+        assert!(throttle.last_sent.read().unwrap().keys().all(|key| key.starts_with("s2/")));
+    }
+
+    #[cfg(feature = "tauri-app")]
+    #[test]
+    fn test_latest_value_slots_drop_intermediate_samples_only() {
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let samples = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let samples_clone = samples.clone();
+        app.handle().listen_any(events::REAL_TIME_PERFORMANCE, move |event| {
+            let payload: serde_json::Value = serde_json::from_str(event.payload()).unwrap();
+            samples_clone.lock().unwrap().push(payload["metrics"]["mb_per_sec"].as_f64().unwrap());
+        });
+        let discrete = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let discrete_clone = discrete.clone();
+        app.handle().listen_any(events::TEST_ERROR, move |event| {
+            discrete_clone.lock().unwrap().push(event.payload().to_string());
+        });
+
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), Arc::default(), "s1");
+        let sample = |i: usize| RealTimePerformanceData {
+            session_id: "s1".to_string(),
+            test_type: TestType::Storage,
+            phase: "seq_write".to_string(),
+            metrics: HashMap::from([("mb_per_sec".to_string(), i as f64)]),
+            timestamp: String::new(),
+        };
+        // 突发写入10000个采样，期间穿插离散事件，刷新任务只有10次机会
+        for i in 0..10_000 {
+            emitter.emit_latest(events::REAL_TIME_PERFORMANCE, "storage/seq_write", sample(i));
+            if i % 100 == 0 {
+                emitter.emit(events::TEST_ERROR, i);
+            }
+            if i % 1000 == 999 {
+                emitter.flush_latest();
+            }
+        }
+        assert_eq!(emitter.flush_latest(), 0);
+
+        let samples = samples.lock().unwrap().clone();
+        assert_eq!(samples.len(), 10);
+        assert_eq!(samples.last(), Some(&9999.0));
+        let counters = emitter.metric_samples();
+        assert_eq!(counters, MetricSampleCounters { delivered: 10, dropped: 9990 });
+
+        // 离散事件不经过最新值槽，全部按顺序送达
+        let discrete = discrete.lock().unwrap().clone();
+        let expected: Vec<String> = (0..10_000).step_by(100).map(|i: usize| i.to_string()).collect();
+        assert_eq!(discrete, expected);
+
+        // 不同指标流互不覆盖，测试结束时发送剩余的最新值
+        emitter.emit_latest(events::REAL_TIME_PERFORMANCE, "storage/seq_read", sample(1));
+        emitter.emit_latest(events::REAL_TIME_PERFORMANCE, "storage/rand_read", sample(2));
+        emitter.finish_test(TestType::Storage);
+        assert_eq!(emitter.metric_samples().delivered, 12);
+    }
+
+    fn thermal_warning(session_id: &str) -> TestWarningEvent {
+        TestWarningEvent {
+            session_id: session_id.to_string(),
+            test_type: TestType::Cpu,
+            warning_type: "thermal_throttling".to_string(),
+            message: "CPU降频".to_string(),
+            message_key: "cpu.thermal_throttling".to_string(),
+            params: HashMap::new(),
+            severity: WarningSeverity::Medium,
+        }
+    }
+
+    #[test]
+    fn test_warning_manager_suppresses_repeats_and_summarizes() {
+        let manager = WarningManager::new(Duration::from_secs(30));
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        // 首次立即发送，冷却时间内的14次重复全部抑制
+        let mut sent = manager.report(thermal_warning("s1"), Locale::En, at(0));
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].message, "CPU降频");
+        for second in 1..=14 {
+            sent.extend(manager.report(thermal_warning("s1"), Locale::En, at(second)));
         }
+        assert_eq!(sent.len(), 1);
+
+        // 其他会话和其他警告类型互不影响
+        assert_eq!(manager.report(thermal_warning("s2"), Locale::En, at(1)).len(), 1);
+        let mut swap = thermal_warning("s1");
+        swap.warning_type = "swap".to_string();
+        assert_eq!(manager.report(swap, Locale::En, at(2)).len(), 1);
+
+        // 测试结束时汇总被抑制的次数，已汇总的警告不再重复汇总
+        let summary = manager.finish("s1", Some(TestType::Cpu), Locale::En, at(20));
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].warning_type, "thermal_throttling");
+        assert_eq!(summary[0].message_key, "warning.repeated");
+        assert_eq!(summary[0].params["count"], "14");
+        assert_eq!(summary[0].message, "thermal_throttling occurred 14 more times");
+        assert_eq!(summary[0].severity, WarningSeverity::Medium);
+        assert!(manager.finish("s1", None, Locale::En, at(21)).is_empty());
+
+        let history = manager.history("s1");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].warning_type, "thermal_throttling");
+        assert_eq!((history[0].count, history[0].emitted, history[0].active), (15, 2, false));
+        assert_eq!((history[1].count, history[1].emitted), (1, 1));
+        assert!(manager.history("missing").is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_warning_manager_cooldown_and_clear() {
+        let manager = WarningManager::new(Duration::from_secs(10));
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        manager.report(thermal_warning("s1"), Locale::Zh, at(0));
+        assert!(manager.report(thermal_warning("s1"), Locale::Zh, at(5)).is_empty());
+        // 条件持续超过冷却时间时发送一条汇总，包括这一次
+        let summary = manager.report(thermal_warning("s1"), Locale::Zh, at(10));
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].params["count"], "2");
+        assert_eq!(summary[0].message, "thermal_throttling 又出现了 2 次");
+
+        // 条件解除：没有被抑制的重复时不发送汇总
+        assert!(manager.clear("s1", TestType::Cpu, "thermal_throttling", Locale::Zh, at(11)).is_empty());
+        // 解除后再次出现会立即发送原始警告
+        let again = manager.report(thermal_warning("s1"), Locale::Zh, at(12));
+        assert_eq!(again.len(), 1);
+        assert_eq!(again[0].message_key, "cpu.thermal_throttling");
+        manager.report(thermal_warning("s1"), Locale::Zh, at(13));
+        let cleared = manager.clear("s1", TestType::Cpu, "thermal_throttling", Locale::Zh, at(14));
+        assert_eq!(cleared[0].params["count"], "1");
+
+        let history = manager.history("s1");
+        assert_eq!((history[0].count, history[0].emitted), (5, 4));
+    }
+
+    #[test]
+    fn test_warning_filter_records_but_does_not_emit_filtered_warnings() {
+        let manager = WarningManager::new(Duration::from_secs(30));
+        manager.set_filter(WarningFilter {
+            min_severity: WarningSeverity::High,
+            muted_types: vec!["background_load".to_string()],
+        });
+        let now = Instant::now();
+
+        // Medium低于阈值：记入历史但不发送，重复也不会产生汇总
+        assert!(manager.report(thermal_warning("s1"), Locale::En, now).is_empty());
+        assert!(manager.report(thermal_warning("s1"), Locale::En, now + Duration::from_secs(60)).is_empty());
+
+        let mut critical = thermal_warning("s1");
+        critical.warning_type = "swap".to_string();
+        critical.severity = WarningSeverity::Critical;
+        assert_eq!(manager.report(critical.clone(), Locale::En, now).len(), 1);
+
+        // 屏蔽的类型即使是Critical也不发送
+        let mut muted = critical;
+        muted.warning_type = "background_load".to_string();
+        assert!(manager.report(muted, Locale::En, now).is_empty());
+        assert!(manager.finish("s1", None, Locale::En, now).is_empty());
+
+        let history = manager.history("s1");
+        let counts: Vec<(&str, u32, u32)> = history
+            .iter()
+            .map(|r| (r.warning_type.as_str(), r.count, r.emitted))
+            .collect();
+        assert_eq!(counts, [("thermal_throttling", 2, 0), ("swap", 1, 1), ("background_load", 1, 0)]);
+
+        assert!(WarningSeverity::Critical > WarningSeverity::High);
+        assert!(WarningSeverity::Low < WarningSeverity::Medium);
+    }
+
+    #[test]
+    fn test_warning_filter_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config").join("warning_filter.json");
+        assert_eq!(WarningFilter::load(&path).unwrap(), WarningFilter::default());
+
+        let filter = WarningFilter {
+            min_severity: WarningSeverity::Critical,
+            muted_types: vec!["stall".to_string()],
+        };
+        filter.save(&path).unwrap();
+        assert_eq!(WarningFilter::load(&path).unwrap(), filter);
+
+        // 旧格式的snake_case字段和缺失字段
+        std::fs::write(&path, r#"{"min_severity":"Medium"}"#).unwrap();
+        let legacy = WarningFilter::load(&path).unwrap();
+        assert_eq!(legacy.min_severity, WarningSeverity::Medium);
+        assert!(legacy.muted_types.is_empty());
+    }
+
+    #[test]
+    fn test_event_buffer_keeps_newest_events() {
+        let buffer = EventBuffer::default();
+        for i in 1..=600 {
+            buffer.record("s1", events::BENCHMARK_PROGRESS.as_str(), json!({ "testProgress": i }));
+        }
+
+        let all = buffer.recent("s1", 0, None);
+        assert_eq!(all.events.len(), REPLAY_EVENTS_PER_SESSION);
+        assert_eq!(all.events.first().unwrap().seq, 101);
+        assert_eq!(all.events.last().unwrap().payload, json!({ "testProgress": 600, "seq": 600 }));
+        assert_eq!(all.latest_seq, 600);
+        assert!(all.truncated);
+
+        // 按since_seq分页，最后一页之后为空
+        let mut since = 100;
+        let mut pages = 0;
+        loop {
+            let page = buffer.recent("s1", since, Some(200));
+            assert!(!page.truncated);
+            let Some(last) = page.events.last() else { break };
+            assert_eq!(page.events.first().unwrap().seq, since + 1);
+            since = last.seq;
+            pages += 1;
+        }
+        assert_eq!((since, pages), (600, 3));
+        assert_eq!(buffer.recent("s1", 550, None).events.len(), 50);
+        assert_eq!(buffer.recent("unknown", 0, None).latest_seq, 0);
+
+        // 会话数量也有上限
+        for i in 0..REPLAY_SESSIONS {
+            buffer.record(&format!("other-{}", i), events::TEST_COMPLETE.as_str(), json!({}));
+        }
+        assert_eq!(buffer.recent("s1", 0, None).latest_seq, 0);
+        assert_eq!(buffer.recent("other-0", 0, None).latest_seq, 1);
+    }
+
+    #[cfg(feature = "tauri-app")]
+    #[test]
+    fn test_live_events_carry_sequence_numbers() {
+        use tauri::Listener;
+
+        let app = tauri::test::mock_app();
+        let live = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let live_clone = live.clone();
+        app.handle().listen_any(events::SESSION_STATUS_CHANGED, move |event| {
+            let payload: serde_json::Value = serde_json::from_str(event.payload()).unwrap();
+            live_clone.lock().unwrap().push(payload);
+        });
+
+        let buffer = Arc::new(EventBuffer::default());
+        let emitter = ProgressEmitter::new(app.handle().clone(), Arc::default(), buffer.clone(), "s1");
+        for (old_status, new_status) in [(TestStatus::Pending, TestStatus::Queued), (TestStatus::Queued, TestStatus::Running)] {
+            emitter.emit(events::SESSION_STATUS_CHANGED, SessionStatusChangedEvent {
+                session_id: "s1".to_string(),
+                old_status,
+                new_status,
+                timestamp: String::new(),
+                reason: None,
+            });
+        }
+
+        let live = live.lock().unwrap();
+        assert_eq!(live[0]["seq"], json!(1));
+        assert_eq!(live[1]["seq"], json!(2));
+        assert!(live.iter().all(|payload| payload["schemaVersion"] == json!(EVENT_SCHEMA_VERSION)));
+        let replayed = buffer.recent("s1", 1, None);
+        assert_eq!(replayed.events.len(), 1);
+        assert_eq!(replayed.events[0].payload, live[1]);
+    }
+
+    #[test]
+    fn test_status_transition_graph() {
+        use TestStatus::*;
+        let legal = [
+            (Pending, Queued),
+            (Queued, Running),
+            (Running, Paused),
+            (Paused, Running),
+            (Running, Completed),
+            (Running, Failed),
+            (Paused, Completed),
+            (Paused, Failed),
+            (Pending, Cancelled),
+            (Queued, Cancelled),
+            (Running, Cancelled),
+            (Paused, Cancelled),
+            (Pending, Interrupted),
+            (Queued, Interrupted),
+            (Running, Interrupted),
+            (Paused, Interrupted),
+        ];
+        for from in &TestStatus::ALL {
+            for to in &TestStatus::ALL {
+                let expected = legal.iter().any(|(f, t)| f == from && t == to);
+                assert_eq!(from.can_transition_to(to), expected, "{:?} -> {:?}", from, to);
+            }
+        }
+    }
+
+    #[test]
+    fn test_event_schema_lists_serialized_field_names() {
+        let schema = event_schema();
+        assert_eq!(schema.schema_version, EVENT_SCHEMA_VERSION);
+        let progress = schema.types.iter().find(|t| t.name == "BenchmarkProgress").unwrap();
+        assert!(progress.fields.contains(&"estimatedTimeRemaining".to_string()));
+        assert!(progress.fields.iter().all(|field| !field.contains('_')));
+
+        // 每个事件的负载要么是基本类型，要么在types中有描述
+        for event in &schema.events {
+            let payload = event.payload.trim_end_matches("[]");
+            assert!(
+                payload == "string" || payload == "number" || schema.types.iter().any(|t| t.name == payload),
+                "事件 {} 的负载 {} 没有描述",
+                event.name,
+                event.payload
+            );
+        }
+        // 事件列表直接来自events常量，且没有重复
+        let names: Vec<&str> = schema.events.iter().map(|event| event.name.as_str()).collect();
+        let constants: Vec<&str> = events::ALL.iter().map(|event| event.as_str()).collect();
+        assert_eq!(names, constants);
+        let unique: std::collections::HashSet<&str> = constants.iter().copied().collect();
+        assert_eq!(unique.len(), constants.len());
+        assert!(events::SUITE_STREAM.iter().all(|event| events::ALL.contains(event)));
+        assert_eq!(schema.test_types, TestType::ALL);
+    }
+
+    fn schema_errors(name: &str, instance: &serde_json::Value) -> Vec<String> {
+        let validator = jsonschema::validator_for(&json_schemas()[name]).unwrap();
+        validator
+            .iter_errors(instance)
+            .map(|error| format!("{}: {}", error.instance_path, error))
+            .collect()
+    }
+
+    #[test]
+    fn test_serialized_values_match_json_schemas() {
+        use crate::benchmark::calibration::{plan_auto_duration, Calibration};
+        use crate::benchmark::test_support::{sample_memory_result, sample_test_result};
+
+        // 所有可选部分都有值的结果
+        let config = BenchmarkConfig::quick();
+        let mut result = sample_test_result("2024-01-01T00:00:00Z", 512.0);
+        result.auto_duration = Some(plan_auto_duration(&config, 60, Calibration::default_estimates()));
+        result.app_info = Some(crate::app_info::AppInfo::new("1.0.0"));
+        result.config_used = Some(config.clone());
+        result.per_test_durations.insert(TestType::Cpu, 4.2);
+        result.session_id = Some("session".to_string());
+        let result = serde_json::to_value(&result).unwrap();
+        assert_eq!(schema_errors("TestResult", &result), Vec::<String>::new());
+        let older = crate::benchmark::schema::upgrade_result_document(
+            serde_json::from_str(include_str!("../tests/fixtures/result_v0.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(schema_errors("TestResult", &serde_json::to_value(&older).unwrap()), Vec::<String>::new());
+        assert!(schema_errors("BenchmarkConfig", &serde_json::to_value(&config).unwrap()).is_empty());
+
+        // 邻接标记的枚举：事件名在event字段，负载在data字段
+        let event = serde_json::to_value(SuiteEvent::TestComplete(Box::new(TestCompleteEvent {
+            session_id: "session".to_string(),
+            test_type: TestType::Memory,
+            success: true,
+            result: Some(TestResultPayload::Memory(sample_memory_result())),
+            error: None,
+            elapsed_seconds: 1.5,
+            outcome: TestCompletion::Completed,
+            issues: Vec::new(),
+        })))
+        .unwrap();
+        assert_eq!(schema_errors("SuiteEvent", &event), Vec::<String>::new());
+        let error_event = serde_json::to_value(SuiteEvent::Error { message: "失败".to_string() }).unwrap();
+        assert!(schema_errors("SuiteEvent", &error_event).is_empty());
+        let issue = IpcError::from(BenchmarkError::InvalidConfig(BenchmarkConfig::builder().cpu_duration(0).build().unwrap_err()));
+        assert!(schema_errors("IpcError", &serde_json::to_value(&issue).unwrap()).is_empty());
+
+        // 与serde表示不符的值被拒绝
+        let mut renamed = event.clone();
+        renamed["event"] = json!("test_complete");
+        assert!(!schema_errors("SuiteEvent", &renamed).is_empty());
+        let mut wrong_tag = event;
+        wrong_tag["data"]["result"]["type"] = json!("Memory");
+        assert!(!schema_errors("SuiteEvent", &wrong_tag).is_empty());
+        let mut wrong_type = result;
+        wrong_type["per_test_durations"] = json!({ "cpu": "4.2" });
+        assert!(!schema_errors("TestResult", &wrong_type).is_empty());
+    }
+
+    #[test]
+    fn test_committed_json_schemas_are_current() {
+        // UPDATE_SCHEMAS=1 cargo test 重新生成
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("schemas");
+        let update = std::env::var_os("UPDATE_SCHEMAS").is_some();
+        let schemas = json_schemas();
+        for (name, schema) in &schemas {
+            let path = dir.join(format!("{}.json", name));
+            let generated = serde_json::to_string_pretty(schema).unwrap() + "\n";
+            if update {
+                std::fs::create_dir_all(&dir).unwrap();
+                std::fs::write(&path, generated).unwrap();
+            } else {
+                let committed = std::fs::read_to_string(&path).unwrap_or_default();
+                assert!(committed == generated, "{} 已过期，运行 UPDATE_SCHEMAS=1 cargo test 更新", path.display());
+            }
+        }
+        // 不再生成的类型不应留下旧文件
+        let committed = std::fs::read_dir(&dir).unwrap().count();
+        assert_eq!(committed, schemas.len());
+    }
+
+    #[test]
+    fn test_error_catalog_covers_every_conversion() {
+        let catalog = error_catalog(Locale::En);
+        let codes: std::collections::HashSet<&str> = catalog.iter().map(|entry| entry.code.as_str()).collect();
+        assert_eq!(codes.len(), catalog.len(), "错误码重复");
+        assert!(catalog.iter().all(|entry| !entry.message.is_empty()));
+        for code in ["CANCELLED", "TIMEOUT", "INVALID_CONFIG"] {
+            assert!(codes.contains(code), "缺少 {}", code);
+        }
+        // 两种语言的目录一一对应
+        let zh = error_catalog(Locale::Zh);
+        assert!(catalog.iter().zip(&zh).all(|(en, zh)| en.code == zh.code && en.message != zh.message));
+
+        let variants = [
+            BenchmarkError::SystemInfoError("e".to_string()),
+            BenchmarkError::CpuTestError("e".to_string()),
+            BenchmarkError::MemoryTestError("e".to_string()),
+            BenchmarkError::StorageTestError("e".to_string()),
+            BenchmarkError::GpuTestError("e".to_string()),
+            BenchmarkError::IpcTestError("e".to_string()),
+            BenchmarkError::ProcessTestError("e".to_string()),
+            BenchmarkError::CompressionTestError("e".to_string()),
+            BenchmarkError::CryptoTestError("e".to_string()),
+            BenchmarkError::DatabaseTestError("e".to_string()),
+            BenchmarkError::JsonTestError("e".to_string()),
+            BenchmarkError::StabilityTestError("e".to_string()),
+            BenchmarkError::ImageTestError("e".to_string()),
+            BenchmarkError::PowerPlanError("e".to_string()),
+            BenchmarkError::WebviewTestError("e".to_string()),
+            BenchmarkError::NetworkTestError("e".to_string()),
+            BenchmarkError::DataSaveError("e".to_string()),
+            BenchmarkError::DataLoadError("e".to_string()),
+            BenchmarkError::UploadError("e".to_string()),
+            BenchmarkError::PermissionError { feature: "smart".to_string(), message: "e".to_string(), remediation: None },
+            BenchmarkError::InvalidTransition { from: TestStatus::Completed, to: TestStatus::Running },
+            BenchmarkError::InvalidConfig(Vec::new()),
+            BenchmarkError::Cancelled { test: "storage".to_string(), at_progress: 50.0 },
+            BenchmarkError::Timeout { test: "cpu".to_string(), limit_seconds: 30 },
+            BenchmarkError::Internal { test: "memory".to_string(), panic_message: "boom".to_string() },
+            BenchmarkError::io("写入失败")(std::io::Error::other("e")),
+        ];
+        // 每个变体的错误码互不相同，IpcError的错误码和retryable与BenchmarkError本身一致
+        let variant_codes: std::collections::HashSet<&str> = variants.iter().map(BenchmarkError::code).collect();
+        assert_eq!(variant_codes.len(), variants.len(), "变体错误码重复");
+        for error in variants {
+            let (code, retryable) = (error.code(), error.is_retryable());
+            let ipc_error = IpcError::from(error);
+            assert_eq!(ipc_error.code, code);
+            assert_eq!(ipc_error.retryable, retryable, "{}", code);
+            let entry = zh.iter().find(|entry| entry.code == ipc_error.code).unwrap_or_else(|| panic!("{} 不在目录中", ipc_error.code));
+            assert_eq!(ipc_error.message, entry.message);
+            assert_eq!(ipc_error.retryable, entry.retryable, "{}", code);
+        }
+        // IO错误的每种错误码在目录中的retryable与is_retryable一致
+        for kind in [
+            std::io::ErrorKind::NotFound,
+            std::io::ErrorKind::PermissionDenied,
+            std::io::ErrorKind::AlreadyExists,
+            std::io::ErrorKind::StorageFull,
+            std::io::ErrorKind::TimedOut,
+            std::io::ErrorKind::Interrupted,
+            std::io::ErrorKind::InvalidData,
+            std::io::ErrorKind::Other,
+        ] {
+            let error = BenchmarkError::io("e")(std::io::Error::from(kind));
+            let entry = zh.iter().find(|entry| entry.code == error.code()).unwrap();
+            assert_eq!(entry.retryable, error.is_retryable(), "{}", entry.code);
+        }
+        for helper in [
+            IpcError::session_not_found("s1"),
+            IpcError::invalid_argument("f", "x"),
+            IpcError::not_implemented("x"),
+            IpcError::from("x".to_string()),
+        ] {
+            assert!(codes.contains(helper.code.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_every_error_code_has_en_and_zh_messages() {
+        let en = error_catalog(Locale::En);
+        let zh = error_catalog(Locale::Zh);
+        for (en, zh) in en.iter().zip(&zh) {
+            let english = IpcError::coded_in(&en.code, Some("detail".to_string()), Locale::En);
+            assert_eq!(english.message, en.message);
+            assert!(english.message.is_ascii(), "{}", english.message);
+            let chinese = IpcError::coded_in(&zh.code, Some("detail".to_string()), Locale::Zh);
+            assert_eq!(chinese.message, zh.message);
+            assert!(!chinese.message.is_ascii(), "{}", chinese.message);
+            // 两种语言只有消息不同
+            assert_eq!(english.retryable, chinese.retryable);
+            assert_eq!(english.details.as_deref(), Some("detail"));
+            assert_eq!(chinese.details, english.details);
+        }
+        // 目录外的错误码只保留错误码本身
+        assert_eq!(IpcError::coded_in("UNKNOWN_CODE", None, Locale::En).message, "UNKNOWN_CODE");
+
+        // Display是英文的开发者文本，不受语言设置影响
+        let error = BenchmarkError::Timeout { test: "cpu".to_string(), limit_seconds: 30 };
+        assert_eq!(error.to_string(), "cpu timed out after 30s");
+        assert_eq!(BenchmarkError::CpuTestError("x".to_string()).to_string(), "cpu test error: x");
+    }
+
+    #[test]
+    fn test_cancelled_error_is_not_retryable() {
+        let error = IpcError::from(BenchmarkError::Cancelled { test: "memory".to_string(), at_progress: 62.0 });
+        assert_eq!(error.code, "CANCELLED");
+        assert!(!error.retryable);
+        assert_eq!(error.details.as_deref(), Some("memory @ 62%"));
+        assert_eq!(error.message, "测试已取消");
+    }
+
+    #[test]
+    fn test_timeout_error_is_retryable() {
+        let error = IpcError::from(BenchmarkError::Timeout { test: "storage".to_string(), limit_seconds: 90 });
+        assert_eq!(error.code, "TIMEOUT");
+        assert!(error.retryable);
+        assert_eq!(error.details.as_deref(), Some("storage > 90s"));
+    }
+
+    #[test]
+    fn test_internal_panic_error_keeps_message() {
+        let error = IpcError::from(BenchmarkError::Internal { test: "memory".to_string(), panic_message: "index out of bounds".to_string() });
+        assert_eq!(error.code, "INTERNAL_PANIC");
+        assert!(!error.retryable);
+        assert_eq!(error.details.as_deref(), Some("memory: index out of bounds"));
+    }
+
+    #[test]
+    fn test_storage_io_error_code_follows_kind() {
+        use std::io::{Error, ErrorKind};
+
+        // ENOSPC：磁盘已满，重试没有意义
+        let enospc = Error::from_raw_os_error(28);
+        assert_eq!(enospc.kind(), ErrorKind::StorageFull);
+        let error = BenchmarkError::io("写入失败")(enospc);
+        assert_eq!(error.io_kind(), Some(ErrorKind::StorageFull));
+        let ipc_error = IpcError::from(error);
+        assert_eq!(ipc_error.code, "IO_STORAGE_FULL");
+        assert!(!ipc_error.retryable);
+        assert!(ipc_error.details.unwrap().starts_with("写入失败: "));
+
+        for (kind, code, retryable) in [
+            (ErrorKind::PermissionDenied, "IO_PERMISSION_DENIED", false),
+            (ErrorKind::Interrupted, "IO_INTERRUPTED", true),
+            (ErrorKind::Other, "IO_ERROR", false),
+        ] {
+            let error = IpcError::from(BenchmarkError::io("同步失败")(Error::new(kind, "e")));
+            assert_eq!(error.code, code);
+            assert_eq!(error.retryable, retryable, "{}", code);
+        }
+        assert_eq!(BenchmarkError::StorageTestError("e".to_string()).io_kind(), None);
+    }
+
+    #[test]
+    fn test_invalid_config_error_carries_issues() {
+        let mut config = crate::benchmark::core::BenchmarkConfig::quick();
+        config.storage_test.block_size = 0;
+        config.cpu_test.test_duration = 0;
+        let issues = config.validate(None).unwrap_err();
+        let error = IpcError::from(BenchmarkError::InvalidConfig(issues.clone()));
+        assert_eq!(error.code, "INVALID_CONFIG");
+        assert_eq!(error.field.as_deref(), Some("cpu_test.test_duration"));
+        assert_eq!(*error.issues, *issues);
+        assert!(error.details.unwrap().contains("storage_test.block_size"));
+
+        let json = serde_json::to_value(IpcError::from(BenchmarkError::InvalidConfig(issues))).unwrap();
+        assert_eq!(json["issues"][1]["field"], "storage_test.block_size");
+        assert_eq!(json["issues"][1]["severity"], "Error");
+        assert_eq!(json["issues"][1]["suggestedValue"], 4);
+        // 其他错误不带issues字段
+        assert!(serde_json::to_value(IpcError::not_implemented("x")).unwrap().get("issues").is_none());
+    }
+
+    #[test]
+    fn test_io_and_json_errors_keep_kind_and_source_text() {
+        use std::io::{Error, ErrorKind};
+
+        for (kind, code) in [
+            (ErrorKind::NotFound, "IO_NOT_FOUND"),
+            (ErrorKind::PermissionDenied, "IO_PERMISSION_DENIED"),
+            (ErrorKind::AlreadyExists, "IO_ALREADY_EXISTS"),
+            (ErrorKind::TimedOut, "IO_TIMED_OUT"),
+            (ErrorKind::InvalidData, "IO_INVALID_DATA"),
+            (ErrorKind::Other, "IO_ERROR"),
+        ] {
+            let error = IpcError::from(Error::new(kind, "results/index.json"));
+            assert_eq!(error.code, code);
+            assert_eq!(error.details.as_deref(), Some("results/index.json"));
+        }
+        let missing = std::fs::read("/nonexistent/benchmark/result.json").unwrap_err();
+        assert_eq!(IpcError::from(missing).code, "IO_NOT_FOUND");
+        assert!(IpcError::from(Error::new(ErrorKind::TimedOut, "t")).retryable);
+
+        let syntax = serde_json::from_str::<TestSession>("{not json").unwrap_err();
+        let text = syntax.to_string();
+        let error = IpcError::from(syntax);
+        assert_eq!(error.code, "JSON_SYNTAX_ERROR");
+        assert_eq!(error.details, Some(text));
+        let data = serde_json::from_value::<TestSession>(json!({ "sessionId": 1 })).unwrap_err();
+        assert_eq!(IpcError::from(data).code, "JSON_DATA_ERROR");
+        let eof = serde_json::from_str::<TestSession>("{\"sessionId\": ").unwrap_err();
+        assert_eq!(IpcError::from(eof).code, "JSON_EOF_ERROR");
+    }
+
+    #[test]
+    fn test_plain_and_poisoned_lock_errors_are_internal() {
+        let error = IpcError::from("webhook client unavailable".to_string());
+        assert_eq!((error.code.as_str(), error.retryable), ("INTERNAL_ERROR", false));
+        assert_eq!(error.details.as_deref(), Some("webhook client unavailable"));
+
+        let lock = std::sync::Arc::new(std::sync::Mutex::new(0));
+        let poisoner = lock.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("poison");
+        })
+        .join();
+        let error = lock.lock().map(|_| ()).map_err(IpcError::from).unwrap_err();
+        assert_eq!(error.code, "INTERNAL_ERROR");
+        // 序列化后前端按code区分，不需要解析中文消息
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], "INTERNAL_ERROR");
+        assert!(json["details"].as_str().unwrap().contains("poisoned"));
+    }
+
+    #[test]
+    fn test_test_type_serializes_to_historical_strings() {
+        for (test_type, name) in [
+            (TestType::Cpu, "cpu"),
+            (TestType::Memory, "memory"),
+            (TestType::Storage, "storage"),
+            (TestType::Webview, "webview"),
+            (TestType::Network, "network"),
+            (TestType::Suite, "suite"),
+        ] {
+            assert_eq!(serde_json::to_value(test_type).unwrap(), json!(name));
+            assert_eq!(serde_json::from_value::<TestType>(json!(name)).unwrap(), test_type);
+            assert_eq!(test_type.as_str(), name);
+        }
+        assert!(serde_json::from_value::<TestType>(json!("CPU")).is_err());
+
+        // 旧版本保存的事件负载仍能解析
+        let warning: TestWarningEvent = serde_json::from_value(json!({
+            "session_id": "s1",
+            "test_type": "storage",
+            "warning_type": "slow_disk",
+            "message": "m",
+            "severity": "Low",
+        }))
+        .unwrap();
+        assert_eq!(warning.test_type, TestType::Storage);
+    }
+}
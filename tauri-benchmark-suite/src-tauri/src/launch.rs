@@ -0,0 +1,262 @@
+//! 启动参数：脚本批量部署时无人值守地运行套件。参数在run()中解析，指定了预设或配置文件时
+//! 启动后立即开始一次运行，与图形界面发起的运行使用同一套会话机制，事件和历史记录照常可用
+use crate::benchmark::capability::{Feature, ELEVATED_PROBE_ARG};
+use crate::benchmark::ci_output::OutputFormat;
+use crate::benchmark::core::BenchmarkConfig;
+use crate::webhook::SuiteOutcome;
+use std::fmt;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "用法: tauri-benchmark-suite [--run-preset quick|standard|extended | --config <path>] [--output <path>] [--output-format json|junit|bencher] [--exit-when-done] [--no-window]";
+
+/// 参数有误时的退出码，与sysexits的EX_USAGE相同
+pub const EXIT_USAGE: i32 = 64;
+
+/// 自动运行结束后的退出码：全部通过为0，有指标超过回归阈值为1，
+/// 测试执行出错（失败、部分失败或被取消）为2，执行出错时不再看回归判定
+pub fn exit_code(outcome: SuiteOutcome, regressed: bool) -> i32 {
+    match outcome {
+        SuiteOutcome::Failure | SuiteOutcome::Partial => 2,
+        SuiteOutcome::Success if regressed => 1,
+        SuiteOutcome::Success => 0,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunPreset {
+    Quick,
+    Standard,
+    Extended,
+}
+
+impl RunPreset {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "quick" => Some(RunPreset::Quick),
+            "standard" => Some(RunPreset::Standard),
+            "extended" => Some(RunPreset::Extended),
+            _ => None,
+        }
+    }
+
+    pub fn config(&self) -> BenchmarkConfig {
+        match self {
+            RunPreset::Quick => BenchmarkConfig::quick(),
+            RunPreset::Standard => BenchmarkConfig::default(),
+            RunPreset::Extended => BenchmarkConfig::extended(),
+        }
+    }
+}
+
+/// 自动运行使用的配置来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchRun {
+    Preset(RunPreset),
+    ConfigFile(PathBuf),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LaunchOptions {
+    pub run: Option<LaunchRun>, // 为空时照常启动图形界面
+    pub output: Option<PathBuf>, // 结果的写入路径，未指定但指定了格式时写到标准输出
+    pub output_format: Option<OutputFormat>,
+    pub exit_when_done: bool,
+    pub no_window: bool,
+    pub elevated_probe: Option<Feature>, // 提权请求以管理员身份重新启动的探测子进程，结果写到output后退出
+}
+
+impl LaunchOptions {
+    /// 没有人能看到对话框或脚本在等待进程退出，参数有误时直接退出
+    pub fn is_unattended(&self) -> bool {
+        self.no_window || self.exit_when_done
+    }
+
+    /// 自动运行的配置，配置文件中的未知字段被忽略
+    pub fn config(&self) -> Result<Option<BenchmarkConfig>, LaunchArgError> {
+        match &self.run {
+            None => Ok(None),
+            Some(LaunchRun::Preset(preset)) => Ok(Some(preset.config())),
+            Some(LaunchRun::ConfigFile(path)) => BenchmarkConfig::load_file(path)
+                .map(|loaded| Some(loaded.config))
+                .map_err(|e| LaunchArgError {
+                    message: e.to_string(),
+                    unattended: self.is_unattended(),
+                }),
+        }
+    }
+}
+
+/// 启动参数错误。unattended表示参数中包含--no-window或--exit-when-done，此时不弹出对话框
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchArgError {
+    pub message: String,
+    pub unattended: bool,
+}
+
+impl fmt::Display for LaunchArgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "启动参数错误: {}", self.message)
+    }
+}
+
+impl std::error::Error for LaunchArgError {}
+
+/// 解析启动参数（不含程序名）。参数值可以写作`--output path`或`--output=path`；
+/// macOS从访达启动时附加的`-psn_`参数被忽略
+pub fn parse_launch_args<I, S>(args: I) -> Result<LaunchOptions, LaunchArgError>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let args: Vec<String> = args.into_iter().map(Into::into).collect();
+    let unattended = args
+        .iter()
+        .any(|arg| arg == "--no-window" || arg == "--exit-when-done");
+    let error = |message: String| LaunchArgError { message, unattended };
+
+    let mut options = LaunchOptions::default();
+    let mut preset = None;
+    let mut config = None;
+    let mut remaining = args.iter().filter(|arg| !arg.starts_with("-psn_"));
+    while let Some(arg) = remaining.next() {
+        let (name, inline) = match arg.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| remaining.next().filter(|next| !next.starts_with("--")).cloned())
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| error(format!("{} 需要一个参数值", name)))
+        };
+        match name {
+            "--run-preset" => {
+                let value = value()?;
+                let parsed = RunPreset::parse(&value)
+                    .ok_or_else(|| error(format!("未知的预设 {}，可选 quick、standard、extended", value)))?;
+                set_once(&mut preset, parsed, name).map_err(error)?;
+            }
+            "--config" => set_once(&mut config, PathBuf::from(value()?), name).map_err(error)?,
+            "--output" => set_once(&mut options.output, PathBuf::from(value()?), name).map_err(error)?,
+            "--output-format" => {
+                let value = value()?;
+                let parsed = OutputFormat::parse(&value)
+                    .ok_or_else(|| error(format!("未知的输出格式 {}，可选 {}", value, OutputFormat::NAMES)))?;
+                set_once(&mut options.output_format, parsed, name).map_err(error)?;
+            }
+            ELEVATED_PROBE_ARG => {
+                let value = value()?;
+                let parsed = Feature::parse(&value)
+                    .ok_or_else(|| error(format!("未知的功能 {}，可选 {}", value, Feature::NAMES)))?;
+                set_once(&mut options.elevated_probe, parsed, name).map_err(error)?;
+            }
+            "--exit-when-done" | "--no-window" if inline.is_some() => {
+                return Err(error(format!("{} 不接受参数值", name)));
+            }
+            "--exit-when-done" => options.exit_when_done = true,
+            "--no-window" => options.no_window = true,
+            _ => return Err(error(format!("无法识别的参数 {}", arg))),
+        }
+    }
+
+    options.run = match (preset, config) {
+        (Some(_), Some(_)) => return Err(error("--run-preset 和 --config 不能同时指定".to_string())),
+        (Some(preset), None) => Some(LaunchRun::Preset(preset)),
+        (None, Some(path)) => Some(LaunchRun::ConfigFile(path)),
+        (None, None) => None,
+    };
+    if options.elevated_probe.is_some() {
+        if options.run.is_some() || options.output_format.is_some() || options.exit_when_done || options.no_window {
+            return Err(error(format!("{} 只能与 --output 一起使用", ELEVATED_PROBE_ARG)));
+        }
+        return Ok(options);
+    }
+    let unattended_only = options.output.is_some() || options.output_format.is_some() || options.exit_when_done || options.no_window;
+    if options.run.is_none() && unattended_only {
+        return Err(error("--output、--output-format、--exit-when-done 和 --no-window 需要与 --run-preset 或 --config 一起使用".to_string()));
+    }
+    Ok(options)
+}
+
+fn set_once<T>(slot: &mut Option<T>, value: T, name: &str) -> Result<(), String> {
+    if slot.replace(value).is_some() {
+        return Err(format!("{} 只能指定一次", name));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::cpu::CpuTestConfig;
+
+    #[test]
+    fn test_parse_launch_args() {
+        assert_eq!(parse_launch_args(Vec::<String>::new()).unwrap(), LaunchOptions::default());
+        // macOS附加的进程序列号参数不影响图形界面启动
+        assert_eq!(parse_launch_args(["-psn_0_12345"]).unwrap(), LaunchOptions::default());
+
+        let options = parse_launch_args(["--run-preset", "quick", "--output=out/result.xml", "--output-format", "junit", "--exit-when-done", "--no-window"]).unwrap();
+        assert_eq!(
+            options,
+            LaunchOptions {
+                run: Some(LaunchRun::Preset(RunPreset::Quick)),
+                output: Some(PathBuf::from("out/result.xml")),
+                output_format: Some(OutputFormat::Junit),
+                exit_when_done: true,
+                no_window: true,
+                elevated_probe: None,
+            }
+        );
+        assert_eq!(options.config().unwrap().unwrap().cpu_test.test_duration, 1);
+
+        let options = parse_launch_args(["--config=bench.toml"]).unwrap();
+        assert_eq!(options.run, Some(LaunchRun::ConfigFile(PathBuf::from("bench.toml"))));
+        assert!(!options.is_unattended());
+        assert_eq!(RunPreset::Extended.config().repeat, 3);
+        assert_eq!(RunPreset::Standard.config().cpu_test.config, CpuTestConfig::default());
+
+        // 提权探测子进程不启动图形界面，output为结果文件
+        let options = parse_launch_args(["--elevated-probe", "smart", "--output", "C:\\Temp\\capability.json"]).unwrap();
+        assert_eq!(options.elevated_probe, Some(Feature::Smart));
+        assert_eq!(options.output, Some(PathBuf::from("C:\\Temp\\capability.json")));
+        assert_eq!(options.run, None);
+    }
+
+    #[test]
+    fn test_invalid_launch_args() {
+        let message = |args: &[&str]| parse_launch_args(args.iter().copied()).unwrap_err().message;
+        assert!(message(&["--run-preset", "turbo"]).contains("turbo"));
+        assert!(message(&["--run-preset"]).contains("需要一个参数值"));
+        assert!(message(&["--output", "--exit-when-done"]).contains("--output"));
+        assert!(message(&["--run-preset", "quick", "--config", "a.toml"]).contains("不能同时指定"));
+        assert!(message(&["--run-preset", "quick", "--run-preset", "standard"]).contains("只能指定一次"));
+        assert!(message(&["--run-preset", "quick", "--no-window=yes"]).contains("不接受参数值"));
+        assert!(message(&["--verbose"]).contains("--verbose"));
+        assert!(message(&["--output", "result.json"]).contains("需要与"));
+        assert!(message(&["--output-format", "bencher"]).contains("需要与"));
+        assert!(message(&["--run-preset", "quick", "--output-format", "xml"]).contains("json、junit、bencher"));
+        assert!(message(&["--elevated-probe", "turbo"]).contains("large_pages"));
+        assert!(message(&["--elevated-probe", "smart", "--run-preset", "quick"]).contains("只能与 --output"));
+
+        // 无人值守的参数出错时直接退出，否则弹出对话框
+        assert!(parse_launch_args(["--exit-when-done"]).unwrap_err().unattended);
+        assert!(!parse_launch_args(["--run-preset", "turbo"]).unwrap_err().unattended);
+
+        let options = parse_launch_args(["--config", "missing.toml", "--no-window"]).unwrap();
+        let error = options.config().unwrap_err();
+        assert!(error.unattended);
+        assert!(error.to_string().starts_with("启动参数错误"));
+    }
+
+    #[test]
+    fn test_exit_code_reflects_outcome() {
+        assert_eq!(exit_code(SuiteOutcome::Success, false), 0);
+        assert_eq!(exit_code(SuiteOutcome::Success, true), 1);
+        assert_eq!(exit_code(SuiteOutcome::Failure, false), 2);
+        // 执行出错优先于回归判定
+        assert_eq!(exit_code(SuiteOutcome::Partial, true), 2);
+        assert_ne!(EXIT_USAGE, exit_code(SuiteOutcome::Failure, false));
+    }
+}
@@ -0,0 +1,493 @@
+//! 基于tracing的结构化日志：套件运行器和各基准测试模块按会话、测试和子阶段建立span，
+//! 本模块的订阅层把事件写入每个会话一个的日志文件（应用日志目录下的`session-<id>.log`），
+//! 同时把Info及以上的事件送入会话记录的日志环形缓冲区，两处内容一致。
+//! 文件的详细程度可在运行时调整，环形缓冲区固定只收Info、Warning和Error
+use crate::session::{LogLevel, SessionRegistry};
+use std::collections::HashMap;
+use std::fmt::{self, Write as _};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Span, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::{LookupSpan, Registry};
+
+/// 会话span的名称，span上的session_id字段决定日志写入哪个文件
+pub const SESSION_SPAN: &str = "session";
+
+/// 日志目录中最多保留的会话日志文件数，超出后删除最旧的
+pub const SESSION_LOG_FILE_LIMIT: usize = 50;
+
+/// 会话日志文件的默认详细程度
+pub const DEFAULT_FILE_LEVEL: LevelFilter = LevelFilter::INFO;
+
+/// 会话日志文件的路径，会话ID中文件名不允许的字符替换为下划线
+pub fn session_log_path(dir: &Path, session_id: &str) -> PathBuf {
+    let sanitized: String = session_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    dir.join(format!("session-{}.log", sanitized))
+}
+
+/// 解析set_log_level的参数：error、warn、info、debug、trace（不区分大小写）
+pub fn parse_level(value: &str) -> Option<LevelFilter> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "error" => Some(LevelFilter::ERROR),
+        "warn" | "warning" => Some(LevelFilter::WARN),
+        "info" => Some(LevelFilter::INFO),
+        "debug" => Some(LevelFilter::DEBUG),
+        "trace" => Some(LevelFilter::TRACE),
+        _ => None,
+    }
+}
+
+/// 日志订阅层的运行时控制句柄，克隆后共享同一状态
+#[derive(Clone)]
+pub struct LogControl {
+    level: Arc<RwLock<LevelFilter>>,
+    files: Arc<Mutex<SessionFiles>>,
+}
+
+impl Default for LogControl {
+    fn default() -> Self {
+        Self {
+            level: Arc::new(RwLock::new(DEFAULT_FILE_LEVEL)),
+            files: Arc::new(Mutex::new(SessionFiles::default())),
+        }
+    }
+}
+
+impl LogControl {
+    /// 调整会话日志文件的详细程度，立即生效
+    pub fn set_level(&self, level: LevelFilter) {
+        *self.level.write().unwrap() = level;
+    }
+
+    pub fn level(&self) -> LevelFilter {
+        *self.level.read().unwrap()
+    }
+
+    /// 设置会话日志文件所在目录，None时只写环形缓冲区。已打开的文件随之关闭
+    pub fn set_dir(&self, dir: Option<PathBuf>) {
+        let mut files = self.files.lock().unwrap();
+        files.open.clear();
+        files.dir = dir;
+    }
+
+    // 事件和span至少要到Info，环形缓冲区才能收到
+    fn max_level(&self) -> LevelFilter {
+        self.level().max(LevelFilter::INFO)
+    }
+}
+
+#[derive(Default)]
+struct SessionFiles {
+    dir: Option<PathBuf>,
+    open: HashMap<String, File>,
+}
+
+impl SessionFiles {
+    // 写入失败不影响测试本身，直接忽略
+    fn write_line(&mut self, session_id: &str, line: &str) {
+        let Some(dir) = self.dir.clone() else {
+            return;
+        };
+        if !self.open.contains_key(session_id) {
+            let path = session_log_path(&dir, session_id);
+            let opened = fs::create_dir_all(&dir).and_then(|_| OpenOptions::new().create(true).append(true).open(&path));
+            let Ok(file) = opened else {
+                return;
+            };
+            self.open.insert(session_id.to_string(), file);
+            self.prune(&dir);
+        }
+        if let Some(file) = self.open.get_mut(session_id) {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+
+    // 按修改时间保留最新的SESSION_LOG_FILE_LIMIT个文件，正在写入的文件不删除
+    fn prune(&self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        let open: Vec<PathBuf> = self.open.keys().map(|session_id| session_log_path(dir, session_id)).collect();
+        let mut logs: Vec<(std::time::SystemTime, PathBuf)> = entries
+            .filter_map(Result::ok)
+            .filter(|entry| {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                name.starts_with("session-") && name.ends_with(".log")
+            })
+            .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+            .collect();
+        if logs.len() <= SESSION_LOG_FILE_LIMIT {
+            return;
+        }
+        logs.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+        for (_, path) in logs.into_iter().skip(SESSION_LOG_FILE_LIMIT) {
+            if !open.contains(&path) {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+}
+
+/// 把tracing事件写入会话日志文件和会话记录的订阅层
+pub struct SessionLogLayer {
+    control: LogControl,
+}
+
+impl SessionLogLayer {
+    pub fn new(control: LogControl) -> Self {
+        Self { control }
+    }
+}
+
+// span上格式化好的字段，写文件时拼成`session{session_id=..}:test{test_type=cpu}`
+struct SpanFields(String);
+
+// session span的会话ID，决定写入哪个日志文件
+struct SessionId(String);
+
+// 由session_span挂到span上，事件据此进入对应会话记录的环形缓冲区
+struct SessionTarget {
+    session_id: String,
+    sessions: Arc<Mutex<SessionRegistry>>,
+}
+
+#[derive(Default)]
+struct FieldWriter {
+    message: String,
+    fields: String,
+    session_id: Option<String>,
+}
+
+impl FieldWriter {
+    fn write(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message = value.to_string(),
+            name => {
+                if name == "session_id" {
+                    self.session_id = Some(value.to_string());
+                }
+                if !self.fields.is_empty() {
+                    self.fields.push(' ');
+                }
+                let _ = write!(self.fields, "{}={}", name, value);
+            }
+        }
+    }
+}
+
+impl Visit for FieldWriter {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.write(field, value);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.write(field, &format!("{:?}", value));
+    }
+}
+
+impl<S> Layer<S> for SessionLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    // 详细程度可在运行时调整，不能缓存调用点的判断结果
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, _ctx: Context<'_, S>) -> bool {
+        *metadata.level() <= self.control.max_level()
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = FieldWriter::default();
+        attrs.record(&mut fields);
+        let mut extensions = span.extensions_mut();
+        if span.name() == SESSION_SPAN {
+            if let Some(session_id) = fields.session_id {
+                extensions.insert(SessionId(session_id));
+            }
+        }
+        extensions.insert(SpanFields(fields.fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = FieldWriter::default();
+        values.record(&mut fields);
+        let mut extensions = span.extensions_mut();
+        if let Some(existing) = extensions.get_mut::<SpanFields>() {
+            if !existing.0.is_empty() && !fields.fields.is_empty() {
+                existing.0.push(' ');
+            }
+            existing.0.push_str(&fields.fields);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let Some(scope) = ctx.event_scope(event) else {
+            return;
+        };
+        let mut path = String::new();
+        let mut session_id = None;
+        let mut target = None;
+        // 从外到内遍历，内层的会话覆盖外层
+        for span in scope.from_root() {
+            let extensions = span.extensions();
+            if !path.is_empty() {
+                path.push(':');
+            }
+            path.push_str(span.name());
+            if let Some(SpanFields(fields)) = extensions.get::<SpanFields>().filter(|fields| !fields.0.is_empty()) {
+                let _ = write!(path, "{{{}}}", fields);
+            }
+            if let Some(SessionId(id)) = extensions.get::<SessionId>() {
+                session_id = Some(id.clone());
+            }
+            if let Some(session) = extensions.get::<SessionTarget>() {
+                target = Some((session.session_id.clone(), session.sessions.clone()));
+            }
+        }
+        let Some(session_id) = session_id else {
+            return;
+        };
+
+        let mut fields = FieldWriter::default();
+        event.record(&mut fields);
+        let message = match (fields.message.is_empty(), fields.fields.is_empty()) {
+            (_, true) => fields.message,
+            (true, false) => fields.fields,
+            (false, false) => format!("{} {}", fields.message, fields.fields),
+        };
+        let level = *event.metadata().level();
+
+        if level <= self.control.level() {
+            let line = format!(
+                "{} {:>5} {}: {}",
+                chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+                level,
+                path,
+                message
+            );
+            self.control.files.lock().unwrap().write_line(&session_id, &line);
+        }
+
+        // 只收本应用自己的事件，依赖库在会话span内打出的日志只写文件
+        let own_event = event.metadata().target().starts_with(env!("CARGO_CRATE_NAME"));
+        if let (Some((target_id, sessions)), Some(log_level), true) = (target, ring_level(level), own_event) {
+            if let Some(record) = sessions.lock().unwrap().get_mut(&target_id) {
+                record.logs.push(log_level, message);
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let extensions = span.extensions();
+        if let Some(SessionId(session_id)) = extensions.get::<SessionId>() {
+            self.control.files.lock().unwrap().open.remove(session_id);
+        }
+    }
+}
+
+fn ring_level(level: Level) -> Option<LogLevel> {
+    match level {
+        Level::ERROR => Some(LogLevel::Error),
+        Level::WARN => Some(LogLevel::Warning),
+        Level::INFO => Some(LogLevel::Info),
+        _ => None,
+    }
+}
+
+/// 创建订阅者和它的控制句柄，测试中配合`tracing::subscriber::with_default`使用
+pub fn subscriber() -> (impl Subscriber + Send + Sync, LogControl) {
+    let control = LogControl::default();
+    (Registry::default().with(SessionLogLayer::new(control.clone())), control)
+}
+
+static GLOBAL: OnceLock<Option<LogControl>> = OnceLock::new();
+
+/// 安装全局订阅者，重复调用返回同一个控制句柄；已有其他全局订阅者时返回None
+pub fn init() -> Option<&'static LogControl> {
+    GLOBAL
+        .get_or_init(|| {
+            let (subscriber, control) = subscriber();
+            tracing::subscriber::set_global_default(subscriber).ok().map(|_| control)
+        })
+        .as_ref()
+}
+
+/// 全局订阅者的控制句柄，尚未init时为None
+pub fn control() -> Option<&'static LogControl> {
+    GLOBAL.get().and_then(Option::as_ref)
+}
+
+/// 会话span：其中的事件写入该会话的日志文件，Info及以上同时进入会话记录的环形缓冲区
+pub fn session_span(session_id: &str, sessions: &Arc<Mutex<SessionRegistry>>) -> Span {
+    attached_session_span(session_id, sessions).0
+}
+
+// 第二项表示是否成功挂上了会话记录，当前订阅者不是本模块的订阅者时为false
+fn attached_session_span(session_id: &str, sessions: &Arc<Mutex<SessionRegistry>>) -> (Span, bool) {
+    let span = tracing::info_span!("session", session_id);
+    let attached = span
+        .with_subscriber(|(id, dispatch)| {
+            let Some(span) = dispatch.downcast_ref::<Registry>().and_then(|registry| registry.span(id)) else {
+                return false;
+            };
+            span.extensions_mut().insert(SessionTarget {
+                session_id: session_id.to_string(),
+                sessions: sessions.clone(),
+            });
+            true
+        })
+        .unwrap_or(false);
+    (span, attached)
+}
+
+// 当前span所属的（挂了会话记录的）会话
+fn current_session() -> Option<String> {
+    Span::current()
+        .with_subscriber(|(id, dispatch)| {
+            let registry = dispatch.downcast_ref::<Registry>()?;
+            let span = registry.span(id)?;
+            span.scope()
+                .find_map(|span| span.extensions().get::<SessionTarget>().map(|target| target.session_id.clone()))
+        })
+        .flatten()
+}
+
+/// 记录一条会话日志。已在该会话的span中时直接发出事件，否则临时进入会话span；
+/// 没有安装本模块的订阅者时直接写入会话记录，环形缓冲区不会因此缺少条目。
+/// 调用方不能持有会话记录的锁
+pub fn session_log(sessions: &Arc<Mutex<SessionRegistry>>, session_id: &str, level: LogLevel, message: impl Into<String>) {
+    let message = message.into();
+    if current_session().as_deref() == Some(session_id) {
+        emit(level, &message);
+        return;
+    }
+    let (span, attached) = attached_session_span(session_id, sessions);
+    if !attached {
+        if let Some(record) = sessions.lock().unwrap().get_mut(session_id) {
+            record.logs.push(level, message.clone());
+        }
+    }
+    span.in_scope(|| emit(level, &message));
+}
+
+fn emit(level: LogLevel, message: &str) {
+    match level {
+        LogLevel::Info => tracing::info!("{}", message),
+        LogLevel::Warning => tracing::warn!("{}", message),
+        LogLevel::Error => tracing::error!("{}", message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::core::BenchmarkCore;
+    use crate::benchmark::test_support::quick_suite_config;
+    use crate::session::SessionRecord;
+
+    fn session_log_files(dir: &Path) -> Vec<PathBuf> {
+        fs::read_dir(dir).unwrap().map(|entry| entry.unwrap().path()).collect()
+    }
+
+    #[test]
+    fn test_quick_benchmark_writes_session_log_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let (subscriber, control) = subscriber();
+        control.set_dir(Some(dir.path().to_path_buf()));
+        control.set_level(LevelFilter::DEBUG);
+
+        let mut config = quick_suite_config();
+        config.memory_test.enabled = false;
+        config.storage_test.enabled = false;
+        let mut core = BenchmarkCore::new();
+        core.set_config(config);
+        let result = tracing::subscriber::with_default(subscriber, || core.run_all(|_, _| {})).unwrap();
+
+        let run_id = &result.identity.as_ref().unwrap().run_id;
+        let files = session_log_files(dir.path());
+        assert_eq!(files, [session_log_path(dir.path(), run_id)]);
+        let content = fs::read_to_string(&files[0]).unwrap();
+        let session = format!("session{{session_id={}}}", run_id);
+        assert!(content.contains(&format!("{}:test{{test_type=cpu}}:single_thread", session)), "{}", content);
+        assert!(content.contains(":multi_thread"), "{}", content);
+        assert!(content.contains(":floating_point"), "{}", content);
+        // 子阶段的完成事件带有操作数和耗时字段
+        assert!(content.lines().any(|line| line.contains("DEBUG") && line.contains("operations=")), "{}", content);
+    }
+
+    #[test]
+    fn test_level_filtering_applies_to_file_not_ring_buffer() {
+        let dir = tempfile::tempdir().unwrap();
+        let (subscriber, control) = subscriber();
+        control.set_dir(Some(dir.path().to_path_buf()));
+        control.set_level(LevelFilter::WARN);
+        let sessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        sessions.lock().unwrap().insert("s1".to_string(), SessionRecord::new(None));
+
+        tracing::subscriber::with_default(subscriber, || {
+            session_log(&sessions, "s1", LogLevel::Info, "测试开始");
+            session_log(&sessions, "s1", LogLevel::Warning, "温度过高");
+            session_span("s1", &sessions).in_scope(|| {
+                tracing::debug!(bytes = 4096, "阶段完成");
+                // 已在会话span中时不再嵌套一层
+                session_log(&sessions, "s1", LogLevel::Error, "测试失败");
+            });
+            // 运行时调高详细程度后立即生效
+            control.set_level(LevelFilter::DEBUG);
+            session_span("s1", &sessions).in_scope(|| tracing::debug!(bytes = 8192, "阶段完成"));
+        });
+
+        let content = fs::read_to_string(session_log_path(dir.path(), "s1")).unwrap();
+        assert!(!content.contains("测试开始"), "{}", content);
+        assert!(content.contains("WARN session{session_id=s1}: 温度过高"), "{}", content);
+        assert!(content.contains("ERROR session{session_id=s1}: 测试失败"), "{}", content);
+        assert!(!content.contains("bytes=4096"), "{}", content);
+        assert!(content.contains("DEBUG session{session_id=s1}: 阶段完成 bytes=8192"), "{}", content);
+
+        // 环形缓冲区收到Info及以上的全部条目，不受文件详细程度影响，也不收Debug
+        let sessions = sessions.lock().unwrap();
+        let logs = sessions["s1"].logs.since(0);
+        let messages: Vec<(LogLevel, &str)> = logs.iter().map(|entry| (entry.level, entry.message.as_str())).collect();
+        assert_eq!(
+            messages,
+            [(LogLevel::Info, "测试开始"), (LogLevel::Warning, "温度过高"), (LogLevel::Error, "测试失败")]
+        );
+    }
+
+    #[test]
+    fn test_session_log_without_subscriber_pushes_directly() {
+        let sessions = Arc::new(Mutex::new(SessionRegistry::default()));
+        sessions.lock().unwrap().insert("s1".to_string(), SessionRecord::new(None));
+        session_log(&sessions, "s1", LogLevel::Info, "没有订阅者");
+        // 会话已被清理时忽略
+        session_log(&sessions, "gone", LogLevel::Info, "忽略");
+        assert_eq!(sessions.lock().unwrap()["s1"].logs.since(0)[0].message, "没有订阅者");
+
+        assert_eq!(parse_level("DEBUG"), Some(LevelFilter::DEBUG));
+        assert_eq!(parse_level("warning"), Some(LevelFilter::WARN));
+        assert_eq!(parse_level("verbose"), None);
+        assert_eq!(session_log_path(Path::new("logs"), "a/b c"), Path::new("logs").join("session-a_b_c.log"));
+    }
+}
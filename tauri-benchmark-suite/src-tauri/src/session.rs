@@ -0,0 +1,684 @@
+use crate::benchmark::core::{BenchmarkConfig, TestResult};
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::progress::SuiteProgress;
+use crate::benchmark::results::{write_atomically, ResultStore};
+use crate::ipc::{
+    MetricSampleCounters, RealTimePerformanceData, SystemMonitoringData, TestProgressSnapshot, TestSession, TestStatus, TestWarningEvent,
+    UiStateSnapshot,
+};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 每个会话最多保留的日志条数，超出后丢弃最旧的
+pub const SESSION_LOG_CAPACITY: usize = 2000;
+
+/// 会话记录中保留的最近警告条数，界面状态快照中的警告列表即为这些
+pub const RECENT_WARNING_LIMIT: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+/// 会话日志条目，`index`在会话内单调递增，不因丢弃旧条目而改变
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct LogEntry {
+    pub index: u64,
+    pub timestamp: String,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+/// 有界的会话日志环形缓冲区
+#[derive(Debug, Clone)]
+pub struct SessionLog {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+    next_index: u64,
+}
+
+impl Default for SessionLog {
+    fn default() -> Self {
+        Self::with_capacity(SESSION_LOG_CAPACITY)
+    }
+}
+
+impl SessionLog {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity,
+            next_index: 0,
+        }
+    }
+
+    pub fn push(&mut self, level: LogLevel, message: impl Into<String>) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEntry {
+            index: self.next_index,
+            timestamp: Utc::now().to_rfc3339(),
+            level,
+            message: message.into(),
+        });
+        self.next_index += 1;
+    }
+
+    /// 返回索引不小于`since_index`的条目，前端据此增量拉取
+    pub fn since(&self, since_index: u64) -> Vec<LogEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.index >= since_index)
+            .cloned()
+            .collect()
+    }
+
+    /// 最近的`limit`条日志，用于附加到失败的测试结果中
+    pub fn tail(&self, limit: usize) -> Vec<LogEntry> {
+        self.entries
+            .iter()
+            .skip(self.entries.len().saturating_sub(limit))
+            .cloned()
+            .collect()
+    }
+}
+
+/// 记录进度里程碑：进度每跨过一个25%档位返回一次该档位
+#[derive(Debug, Default)]
+pub struct ProgressMilestones {
+    last: AtomicU8,
+}
+
+impl ProgressMilestones {
+    pub fn crossed(&self, progress: f64) -> Option<u8> {
+        // 100%由测试结束标记记录，这里只报告25/50/75
+        let bucket = ((progress / 25.0).floor().clamp(0.0, 3.0)) as u8;
+        let previous = self.last.fetch_max(bucket, Ordering::Relaxed);
+        (bucket > previous).then_some(bucket * 25)
+    }
+}
+
+/// 内存中的测试会话记录
+#[derive(Debug, Clone)]
+pub struct SessionRecord {
+    pub status: TestStatus,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub config: Option<BenchmarkConfig>,
+    pub result: Option<TestResult>, // 套件完成后保留的结果，清理前确保已写入结果存储
+    pub logs: SessionLog,
+    pub artifacts: Vec<PathBuf>, // 测试过程中创建的临时文件，会话中断后启动时清理
+    pub overall_progress: f64,    // 0-100，由套件运行器在每次（节流后的）进度更新时写入
+    pub current_test: Option<String>,
+    pub last_update: DateTime<Utc>, // 最近一次进度或状态变化的时间
+    pub metric_samples: MetricSampleCounters, // 实时指标的发送和丢弃数，用于诊断前端卡顿
+    pub eta_seconds: Option<u64>,             // 最近一次进度时的剩余时间估计
+    pub estimated_completion: Option<DateTime<Utc>>,
+    // 以下字段只供get_ui_state轮询，事件投递不可靠时前端据此渲染
+    pub test_progress: Vec<TestProgressSnapshot>, // 按开始顺序的各测试进度
+    pub phase: Option<String>,                    // 最近一次进度消息，已按会话语言渲染
+    pub recent_warnings: VecDeque<TestWarningEvent>,
+    pub monitoring: Option<SystemMonitoringData>,
+    pub latest_sample: Option<RealTimePerformanceData>,
+    pub result_id: Option<String>, // 结果已写入结果存储时的ID
+}
+
+impl SessionRecord {
+    pub fn new(config: Option<BenchmarkConfig>) -> Self {
+        let now = Utc::now();
+        Self {
+            status: TestStatus::Pending,
+            start_time: now,
+            end_time: None,
+            config,
+            result: None,
+            logs: SessionLog::default(),
+            artifacts: Vec::new(),
+            overall_progress: 0.0,
+            current_test: None,
+            last_update: now,
+            metric_samples: MetricSampleCounters::default(),
+            eta_seconds: None,
+            estimated_completion: None,
+            test_progress: Vec::new(),
+            phase: None,
+            recent_warnings: VecDeque::new(),
+            monitoring: None,
+            latest_sample: None,
+            result_id: None,
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        self.status.is_terminal()
+    }
+
+    /// 所有状态变化都经过这里，非法转换返回错误且不修改状态；
+    /// 进入终止状态时记录结束时间。成功时返回原状态
+    pub fn transition(&mut self, to: TestStatus) -> Result<TestStatus, BenchmarkError> {
+        if !self.status.can_transition_to(&to) {
+            return Err(BenchmarkError::InvalidTransition {
+                from: self.status.clone(),
+                to,
+            });
+        }
+        if to.is_terminal() && self.end_time.is_none() {
+            self.end_time = Some(Utc::now());
+        }
+        if to == TestStatus::Completed {
+            self.overall_progress = 100.0;
+        }
+        if to.is_terminal() {
+            self.current_test = None;
+        }
+        self.last_update = Utc::now();
+        Ok(std::mem::replace(&mut self.status, to))
+    }
+
+    /// 记录套件的总体进度、正在运行的测试和预计完成时间，进度只增不减
+    pub fn record_progress(&mut self, current_test: &str, progress: &SuiteProgress) {
+        if self.is_terminal() {
+            return;
+        }
+        self.overall_progress = self.overall_progress.max(progress.overall.clamp(0.0, 100.0));
+        self.current_test = Some(current_test.to_string());
+        self.eta_seconds = progress.eta_seconds;
+        self.estimated_completion = progress.estimated_completion;
+        self.last_update = Utc::now();
+    }
+
+    /// 记录单项测试的进度和当前阶段说明，phase为None时保留上一次的说明
+    pub fn record_test_progress(&mut self, test_type: &str, progress: f64, phase: Option<String>) {
+        if self.is_terminal() {
+            return;
+        }
+        let progress = progress.clamp(0.0, 100.0);
+        match self.test_progress.iter_mut().find(|test| test.test_type == test_type) {
+            Some(test) => test.progress = progress,
+            None => self.test_progress.push(TestProgressSnapshot {
+                test_type: test_type.to_string(),
+                progress,
+            }),
+        }
+        if phase.is_some() {
+            self.phase = phase;
+        }
+    }
+
+    /// 只保留最近RECENT_WARNING_LIMIT条警告，包括被去重、未实际发送的重复警告
+    pub fn record_warning(&mut self, warning: TestWarningEvent) {
+        if self.recent_warnings.len() == RECENT_WARNING_LIMIT {
+            self.recent_warnings.pop_front();
+        }
+        self.recent_warnings.push_back(warning);
+    }
+
+    /// 暂停期间剩余时间不减少，预计完成时间随当前时间后移
+    pub fn estimated_completion(&self) -> Option<DateTime<Utc>> {
+        match (&self.status, self.eta_seconds) {
+            (TestStatus::Paused, Some(eta)) => {
+                let resumed = Utc::now() + chrono::Duration::seconds(eta as i64);
+                Some(self.estimated_completion.map_or(resumed, |estimate| estimate.max(resumed)))
+            }
+            _ => self.estimated_completion,
+        }
+    }
+
+    pub fn to_test_session(&self, session_id: &str) -> TestSession {
+        TestSession {
+            session_id: session_id.to_string(),
+            status: self.status.clone(),
+            start_time: self.start_time.to_rfc3339(),
+            end_time: self.end_time.map(|t| t.to_rfc3339()),
+            config: self.config.clone(),
+            overall_progress: self.overall_progress,
+            current_test: self.current_test.clone(),
+            last_update: self.last_update.to_rfc3339(),
+            metric_samples: self.metric_samples,
+            estimated_completion_time: self.estimated_completion().map(|t| t.to_rfc3339()),
+        }
+    }
+
+    /// 渲染运行界面所需的全部状态，只读取记录中已有的数据，不刷新系统信息
+    pub fn ui_state(&self, session_id: &str) -> UiStateSnapshot {
+        UiStateSnapshot {
+            session_id: session_id.to_string(),
+            status: self.status.clone(),
+            overall_progress: self.overall_progress,
+            current_test: self.current_test.clone(),
+            phase: self.phase.clone(),
+            tests: self.test_progress.clone(),
+            estimated_time_remaining: self.eta_seconds.filter(|_| !self.is_terminal()),
+            estimated_completion_time: self.estimated_completion().filter(|_| !self.is_terminal()).map(|t| t.to_rfc3339()),
+            recent_warnings: self.recent_warnings.iter().cloned().collect(),
+            monitoring: self.monitoring.clone(),
+            latest_sample: self.latest_sample.clone(),
+            result_id: self.result_id.clone(),
+            last_update: self.last_update.to_rfc3339(),
+            end_time: self.end_time.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+/// 落盘的会话记录，不包含日志和结果（结果已单独保存在结果存储中）
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedSession {
+    session_id: String,
+    status: TestStatus,
+    start_time: DateTime<Utc>,
+    end_time: Option<DateTime<Utc>>,
+    config: Option<BenchmarkConfig>,
+    #[serde(default)]
+    artifacts: Vec<PathBuf>,
+}
+
+/// 启动时发现的中断会话
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveredSession {
+    pub session_id: String,
+    pub start_time: String,
+    pub config: Option<BenchmarkConfig>,
+    pub removed_artifacts: Vec<PathBuf>,
+}
+
+/// 全部测试会话，配置了路径时每次状态变化后落盘，
+/// 这样应用崩溃后仍能知道哪些会话没有正常结束
+#[derive(Debug, Default)]
+pub struct SessionRegistry {
+    sessions: HashMap<String, SessionRecord>,
+    path: Option<PathBuf>,
+}
+
+impl Deref for SessionRegistry {
+    type Target = HashMap<String, SessionRecord>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.sessions
+    }
+}
+
+impl DerefMut for SessionRegistry {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.sessions
+    }
+}
+
+impl SessionRegistry {
+    /// 从文件加载上次保存的会话，文件不存在时为空
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, BenchmarkError> {
+        let path = path.into();
+        let mut sessions = HashMap::new();
+        match fs::read_to_string(&path) {
+            Ok(content) => {
+                let persisted: Vec<PersistedSession> = serde_json::from_str(&content)
+                    .map_err(|e| BenchmarkError::DataLoadError(format!("会话记录损坏: {}", e)))?;
+                for session in persisted {
+                    sessions.insert(
+                        session.session_id,
+                        SessionRecord {
+                            status: session.status,
+                            start_time: session.start_time,
+                            end_time: session.end_time,
+                            config: session.config,
+                            artifacts: session.artifacts,
+                            last_update: session.end_time.unwrap_or(session.start_time),
+                            ..SessionRecord::new(None)
+                        },
+                    );
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(BenchmarkError::DataLoadError(format!("无法读取会话记录: {}", e))),
+        }
+        Ok(Self {
+            sessions,
+            path: Some(path),
+        })
+    }
+
+    pub fn persist(&self) -> Result<(), BenchmarkError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let persisted: Vec<PersistedSession> = self
+            .sessions
+            .iter()
+            .map(|(id, record)| PersistedSession {
+                session_id: id.clone(),
+                status: record.status.clone(),
+                start_time: record.start_time,
+                end_time: record.end_time,
+                config: record.config.clone(),
+                artifacts: record.artifacts.clone(),
+            })
+            .collect();
+        let content = serde_json::to_string(&persisted)
+            .map_err(|e| BenchmarkError::DataSaveError(format!("会话记录序列化失败: {}", e)))?;
+        write_atomically(path, content.as_bytes())
+    }
+
+    /// 把上次未结束的会话标记为中断并删除它们遗留的临时文件
+    pub fn recover_interrupted(&mut self) -> Vec<RecoveredSession> {
+        let mut recovered = Vec::new();
+        for (id, record) in self.sessions.iter_mut().filter(|(_, r)| !r.is_terminal()) {
+            let _ = record.transition(TestStatus::Interrupted);
+            record.logs.push(LogLevel::Warning, "应用在测试过程中退出，会话已标记为中断");
+
+            let removed_artifacts = record
+                .artifacts
+                .drain(..)
+                .filter(|path| fs::remove_file(path).is_ok())
+                .collect();
+            recovered.push(RecoveredSession {
+                session_id: id.clone(),
+                start_time: record.start_time.to_rfc3339(),
+                config: record.config.clone(),
+                removed_artifacts,
+            });
+        }
+        recovered
+    }
+}
+
+/// 会话保留策略：终止超过`ttl_secs`的会话每隔`interval_secs`清理一次
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    pub ttl_secs: u64,
+    pub interval_secs: u64,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            ttl_secs: 24 * 60 * 60,
+            interval_secs: 10 * 60,
+        }
+    }
+}
+
+impl RetentionConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.interval_secs == 0 {
+            return Err("清理间隔必须大于0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 移除终止时间早于`now - ttl`的会话，返回移除的数量。
+/// 结果尚未写入存储的会话会先补写，写入失败则保留到下一轮
+pub fn cleanup_expired_sessions(
+    sessions: &mut HashMap<String, SessionRecord>,
+    results: &mut ResultStore,
+    ttl: chrono::Duration,
+    now: DateTime<Utc>,
+) -> usize {
+    let expired: Vec<String> = sessions
+        .iter()
+        .filter(|(_, record)| {
+            record.is_terminal() && record.end_time.is_some_and(|end| now - end >= ttl)
+        })
+        .map(|(id, _)| id.clone())
+        .collect();
+
+    let mut removed = 0;
+    for id in expired {
+        if let Some(result) = &sessions[&id].result {
+            if !results.contains(&id) && results.save(&id, result).is_err() {
+                continue;
+            }
+        }
+        sessions.remove(&id);
+        removed += 1;
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::test_support::sample_test_result;
+
+    fn finished(status: TestStatus) -> SessionRecord {
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        record.transition(TestStatus::Running).unwrap();
+        record.transition(status).unwrap();
+        record
+    }
+
+    fn finished_at(status: TestStatus, end_time: DateTime<Utc>) -> SessionRecord {
+        let mut record = finished(status);
+        record.start_time = end_time - chrono::Duration::minutes(5);
+        record.end_time = Some(end_time);
+        record
+    }
+
+    #[test]
+    fn test_cleanup_respects_ttl_boundary() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut results = ResultStore::open(dir.path()).unwrap();
+        let now = Utc::now();
+        let ttl = chrono::Duration::hours(24);
+
+        let mut sessions = HashMap::new();
+        sessions.insert("expired".to_string(), finished_at(TestStatus::Failed, now - ttl - chrono::Duration::seconds(1)));
+        sessions.insert("boundary".to_string(), finished_at(TestStatus::Cancelled, now - ttl));
+        sessions.insert("recent".to_string(), finished_at(TestStatus::Completed, now - ttl + chrono::Duration::seconds(1)));
+        let mut running = SessionRecord::new(None);
+        running.start_time = now - chrono::Duration::days(3);
+        sessions.insert("running".to_string(), running);
+
+        let removed = cleanup_expired_sessions(&mut sessions, &mut results, ttl, now);
+        assert_eq!(removed, 2);
+        let mut remaining: Vec<&str> = sessions.keys().map(String::as_str).collect();
+        remaining.sort();
+        assert_eq!(remaining, vec!["recent", "running"]);
+    }
+
+    #[test]
+    fn test_cleanup_flushes_retained_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut results = ResultStore::open(dir.path()).unwrap();
+        let now = Utc::now();
+
+        let mut record = finished_at(TestStatus::Completed, now - chrono::Duration::days(2));
+        record.result = Some(sample_test_result("2024-01-01T00:00:00Z", 10.0));
+        let mut sessions = HashMap::new();
+        sessions.insert("session-1".to_string(), record);
+
+        assert_eq!(cleanup_expired_sessions(&mut sessions, &mut results, chrono::Duration::hours(24), now), 1);
+        assert!(sessions.is_empty());
+        assert_eq!(results.load("session-1").unwrap().overall_score, 10.0);
+    }
+
+    #[test]
+    fn test_session_log_is_bounded() {
+        let mut log = SessionLog::with_capacity(3);
+        for i in 0..5 {
+            log.push(LogLevel::Info, format!("entry {}", i));
+        }
+        let indexes: Vec<u64> = log.since(0).iter().map(|e| e.index).collect();
+        assert_eq!(indexes, vec![2, 3, 4]);
+        assert_eq!(log.since(4).len(), 1);
+        assert!(log.since(5).is_empty());
+        assert_eq!(log.tail(2)[0].message, "entry 3");
+    }
+
+    #[test]
+    fn test_progress_milestones() {
+        let milestones = ProgressMilestones::default();
+        assert_eq!(milestones.crossed(10.0), None);
+        assert_eq!(milestones.crossed(26.0), Some(25));
+        assert_eq!(milestones.crossed(30.0), None);
+        assert_eq!(milestones.crossed(99.0), Some(75));
+        assert_eq!(milestones.crossed(100.0), None);
+    }
+
+    #[test]
+    fn test_startup_recovery_marks_running_sessions_interrupted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sessions.json");
+        let orphaned_file = dir.path().join("tauri_benchmark_test.dat");
+        fs::write(&orphaned_file, vec![0u8; 1024]).unwrap();
+
+        // 模拟上次运行：一个会话正在运行时应用被杀掉，另一个已正常完成
+        {
+            let mut registry = SessionRegistry::open(&path).unwrap();
+            let mut running = SessionRecord::new(None);
+            running.artifacts.push(orphaned_file.clone());
+            registry.insert("stale".to_string(), running);
+            registry.insert("done".to_string(), finished(TestStatus::Completed));
+            registry.persist().unwrap();
+        }
+
+        let mut registry = SessionRegistry::open(&path).unwrap();
+        let recovered = registry.recover_interrupted();
+        registry.persist().unwrap();
+
+        assert_eq!(recovered.len(), 1);
+        assert_eq!(recovered[0].session_id, "stale");
+        assert_eq!(recovered[0].removed_artifacts, vec![orphaned_file.clone()]);
+        assert!(!orphaned_file.exists());
+
+        let reopened = SessionRegistry::open(&path).unwrap();
+        assert!(matches!(reopened["stale"].status, TestStatus::Interrupted));
+        assert!(reopened["stale"].end_time.is_some());
+        assert!(reopened["stale"].artifacts.is_empty());
+        assert!(matches!(reopened["done"].status, TestStatus::Completed));
+        assert!(matches!(reopened["stale"].to_test_session("stale").status, TestStatus::Interrupted));
+    }
+
+    #[test]
+    fn test_transition_rejects_illegal_changes() {
+        let mut record = finished(TestStatus::Completed);
+        let end_time = record.end_time;
+        assert!(end_time.is_some());
+
+        // 已完成的会话不能再被取消
+        let err = record.transition(TestStatus::Cancelled).unwrap_err();
+        assert!(matches!(
+            err,
+            BenchmarkError::InvalidTransition { from: TestStatus::Completed, to: TestStatus::Cancelled }
+        ));
+        assert_eq!(record.status, TestStatus::Completed);
+        assert_eq!(record.end_time, end_time);
+
+        let mut record = SessionRecord::new(None);
+        assert!(record.transition(TestStatus::Running).is_err());
+        assert_eq!(record.transition(TestStatus::Queued).unwrap(), TestStatus::Pending);
+        assert!(record.end_time.is_none());
+    }
+
+    #[test]
+    fn test_session_reports_recorded_times_and_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sessions.json");
+        let started = Utc::now() - chrono::Duration::hours(1);
+        let config = BenchmarkConfig::default();
+        {
+            let mut registry = SessionRegistry::open(&path).unwrap();
+            let mut record = SessionRecord::new(Some(config.clone()));
+            record.start_time = started;
+            record.transition(TestStatus::Queued).unwrap();
+            record.transition(TestStatus::Running).unwrap();
+            record.transition(TestStatus::Cancelled).unwrap();
+            registry.insert("s1".to_string(), record);
+            registry.persist().unwrap();
+        }
+
+        // 一小时后查询，仍然是会话实际的开始时间和当时使用的配置
+        let reopened = SessionRegistry::open(&path).unwrap();
+        let session = reopened["s1"].to_test_session("s1");
+        assert_eq!(session.start_time, started.to_rfc3339());
+        assert_eq!(session.end_time, reopened["s1"].end_time.map(|t| t.to_rfc3339()));
+        assert!(session.end_time.is_some());
+        assert_eq!(
+            serde_json::to_value(session.config.unwrap()).unwrap(),
+            serde_json::to_value(config).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_completion_estimate_moves_out_while_paused() {
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        record.transition(TestStatus::Running).unwrap();
+        let estimate = Utc::now() + chrono::Duration::seconds(30);
+        record.record_progress("cpu", &SuiteProgress {
+            overall: 40.0,
+            eta_seconds: Some(30),
+            estimated_completion: Some(estimate),
+        });
+        assert_eq!(record.estimated_completion(), Some(estimate));
+
+        // 暂停后剩余的30秒从当前时间起算，不会早于暂停前的估计
+        record.transition(TestStatus::Paused).unwrap();
+        let paused_at = Utc::now();
+        let pushed = record.estimated_completion().unwrap();
+        assert!(pushed >= estimate);
+        assert!(pushed >= paused_at + chrono::Duration::seconds(30));
+        let session = record.to_test_session("s1");
+        assert!(session.estimated_completion_time.is_some());
+
+        record.transition(TestStatus::Running).unwrap();
+        assert_eq!(record.estimated_completion(), Some(estimate));
+    }
+
+    #[test]
+    fn test_ui_state_keeps_recent_warnings_and_test_progress() {
+        let mut record = SessionRecord::new(None);
+        record.transition(TestStatus::Queued).unwrap();
+        record.transition(TestStatus::Running).unwrap();
+        for i in 0..RECENT_WARNING_LIMIT + 3 {
+            record.record_warning(TestWarningEvent {
+                session_id: "s1".to_string(),
+                test_type: crate::ipc::TestType::Cpu,
+                warning_type: format!("w{}", i),
+                message: String::new(),
+                message_key: String::new(),
+                params: HashMap::new(),
+                severity: crate::ipc::WarningSeverity::Low,
+            });
+        }
+        record.record_test_progress("cpu", 100.0, Some("CPU测试完成".to_string()));
+        record.record_test_progress("memory", 30.0, Some("顺序读取".to_string()));
+        // 没有新的说明时保留上一次的阶段
+        record.record_test_progress("memory", 140.0, None);
+
+        let snapshot = record.ui_state("s1");
+        let warnings: Vec<&str> = snapshot.recent_warnings.iter().map(|w| w.warning_type.as_str()).collect();
+        assert_eq!(warnings.len(), RECENT_WARNING_LIMIT);
+        assert_eq!(warnings.first(), Some(&"w3"));
+        assert_eq!(warnings.last(), Some(&format!("w{}", RECENT_WARNING_LIMIT + 2).as_str()));
+        let tests: Vec<(&str, f64)> = snapshot.tests.iter().map(|t| (t.test_type.as_str(), t.progress)).collect();
+        assert_eq!(tests, [("cpu", 100.0), ("memory", 100.0)]);
+        assert_eq!(snapshot.phase.as_deref(), Some("顺序读取"));
+
+        // 结束后不再更新进度，也不报告剩余时间
+        record.eta_seconds = Some(10);
+        record.transition(TestStatus::Cancelled).unwrap();
+        record.record_test_progress("storage", 10.0, None);
+        let snapshot = record.ui_state("s1");
+        assert_eq!(snapshot.tests.len(), 2);
+        assert_eq!(snapshot.estimated_time_remaining, None);
+        assert!(snapshot.end_time.is_some());
+    }
+
+    #[test]
+    fn test_retention_config_validation() {
+        assert!(RetentionConfig::default().validate().is_ok());
+        assert!(RetentionConfig { ttl_secs: 0, interval_secs: 0 }.validate().is_err());
+    }
+}
@@ -0,0 +1,231 @@
+//! 应用自身的启动耗时：从run()开始，依次到setup完成、主窗口的WebView开始加载页面、页面加载完成，
+//! 以及前端报告首次有意义的绘制。每次完整的测量追加到历史中，历史视图据此绘制各版本的启动时间
+use crate::benchmark::error::BenchmarkError;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// 应用数据目录中的启动历史和上次启动标记
+pub const STARTUP_HISTORY_FILE: &str = "startup_history.json";
+pub const LAUNCH_MARKER_FILE: &str = "last_launch";
+
+/// 历史中最多保留的启动测量数，超出时丢弃最早的
+pub const STARTUP_HISTORY_LIMIT: usize = 50;
+
+// 距上次启动不超过这么久且系统期间没有重启时视为热启动
+const WARM_LAUNCH_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupStage {
+    Setup, // setup钩子执行完毕
+    WindowCreated, // 第一个WebView开始加载页面
+    PageLoaded, // 第一个WebView的页面加载完成
+    FrontendReady, // 前端完成首次有意义的绘制，测量到此结束
+}
+
+/// 冷启动时可执行文件和WebView资源多半不在系统页缓存中，耗时明显更长，两者分开比较
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchKind {
+    Cold,
+    Warm,
+}
+
+/// 一次启动的各阶段耗时，均为从run()开始计算的毫秒数，尚未到达的阶段为空
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupMetrics {
+    pub launched_at: String, // RFC 3339
+    pub app_version: String,
+    pub launch_kind: LaunchKind,
+    pub setup_ms: Option<f64>,
+    pub window_created_ms: Option<f64>,
+    pub page_loaded_ms: Option<f64>,
+    pub frontend_ready_ms: Option<f64>,
+}
+
+/// 记录各阶段第一次到达的时间。前端报告就绪后测量结束，之后的记录（如页面重新加载）都被忽略，
+/// 保证写入历史的测量与之后查询到的一致
+pub struct StartupTracker {
+    started: Instant,
+    launched_at: DateTime<Utc>,
+    app_version: String,
+    launch_kind: LaunchKind,
+    stages: Vec<(StartupStage, Duration)>,
+    history_path: Option<PathBuf>,
+}
+
+impl StartupTracker {
+    /// 在run()的最开始调用
+    pub fn start() -> Self {
+        Self::started_at(Instant::now(), Utc::now())
+    }
+
+    pub fn started_at(started: Instant, launched_at: DateTime<Utc>) -> Self {
+        Self {
+            started,
+            launched_at,
+            app_version: String::new(),
+            launch_kind: LaunchKind::Cold,
+            stages: Vec::new(),
+            history_path: None,
+        }
+    }
+
+    /// setup中补充只有拿到应用句柄后才知道的信息
+    pub fn configure(&mut self, app_version: impl Into<String>, launch_kind: LaunchKind, history_path: PathBuf) {
+        self.app_version = app_version.into();
+        self.launch_kind = launch_kind;
+        self.history_path = Some(history_path);
+    }
+
+    pub fn history_path(&self) -> Option<&Path> {
+        self.history_path.as_deref()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.elapsed(StartupStage::FrontendReady).is_some()
+    }
+
+    /// 记录阶段到达的时间，返回是否被记录；已记录过的阶段和测量结束后的记录返回false
+    pub fn record(&mut self, stage: StartupStage, at: Instant) -> bool {
+        if self.is_complete() || self.elapsed(stage).is_some() {
+            return false;
+        }
+        self.stages.push((stage, at.saturating_duration_since(self.started)));
+        true
+    }
+
+    fn elapsed(&self, stage: StartupStage) -> Option<Duration> {
+        self.stages.iter().find(|(recorded, _)| *recorded == stage).map(|(_, elapsed)| *elapsed)
+    }
+
+    pub fn metrics(&self) -> StartupMetrics {
+        let ms = |stage| self.elapsed(stage).map(|elapsed| elapsed.as_secs_f64() * 1000.0);
+        StartupMetrics {
+            launched_at: self.launched_at.to_rfc3339(),
+            app_version: self.app_version.clone(),
+            launch_kind: self.launch_kind,
+            setup_ms: ms(StartupStage::Setup),
+            window_created_ms: ms(StartupStage::WindowCreated),
+            page_loaded_ms: ms(StartupStage::PageLoaded),
+            frontend_ready_ms: ms(StartupStage::FrontendReady),
+        }
+    }
+}
+
+/// 没有上次启动的记录、上次启动早于本次开机，或者距今超过一小时时为冷启动
+pub fn launch_kind(previous_launch: Option<SystemTime>, boot_time: SystemTime, now: SystemTime) -> LaunchKind {
+    match previous_launch {
+        Some(previous) if previous >= boot_time && now.duration_since(previous).unwrap_or_default() <= WARM_LAUNCH_WINDOW => {
+            LaunchKind::Warm
+        }
+        _ => LaunchKind::Cold,
+    }
+}
+
+/// 按标记文件的修改时间判断本次是冷启动还是热启动，然后更新标记文件。标记写入失败时只影响下次判断
+pub fn detect_launch_kind(marker: &Path, boot_time: SystemTime) -> LaunchKind {
+    let previous = std::fs::metadata(marker).and_then(|metadata| metadata.modified()).ok();
+    let now = SystemTime::now();
+    if let Some(parent) = marker.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = std::fs::write(marker, Utc::now().to_rfc3339());
+    launch_kind(previous, boot_time, now)
+}
+
+pub fn load_startup_history(path: &Path) -> Result<Vec<StartupMetrics>, BenchmarkError> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| BenchmarkError::DataLoadError(format!("启动历史解析失败: {}", e))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(BenchmarkError::DataLoadError(format!("无法读取启动历史: {}", e))),
+    }
+}
+
+/// 追加一次测量，只保留最近limit次；无法解析的旧历史直接丢弃
+pub fn append_startup_history(path: &Path, metrics: &StartupMetrics, limit: usize) -> Result<(), BenchmarkError> {
+    let mut history = load_startup_history(path).unwrap_or_default();
+    history.push(metrics.clone());
+    let excess = history.len().saturating_sub(limit);
+    history.drain(..excess);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| BenchmarkError::DataSaveError(format!("无法创建数据目录: {}", e)))?;
+    }
+    let content = serde_json::to_string_pretty(&history)
+        .map_err(|e| BenchmarkError::DataSaveError(format!("启动历史序列化失败: {}", e)))?;
+    std::fs::write(path, content).map_err(|e| BenchmarkError::DataSaveError(format!("启动历史保存失败: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_accounting() {
+        let started = Instant::now();
+        let mut tracker = StartupTracker::started_at(started, Utc::now());
+        tracker.configure("1.2.3", LaunchKind::Warm, PathBuf::from("startup_history.json"));
+        let at = |ms: u64| started + Duration::from_millis(ms);
+
+        // 页面事件可能早于setup完成；每个阶段只记录第一次
+        assert!(tracker.record(StartupStage::WindowCreated, at(120)));
+        assert!(tracker.record(StartupStage::Setup, at(150)));
+        assert!(!tracker.record(StartupStage::WindowCreated, at(400)));
+        assert!(!tracker.is_complete());
+        let partial = tracker.metrics();
+        assert_eq!((partial.setup_ms, partial.window_created_ms, partial.frontend_ready_ms), (Some(150.0), Some(120.0), None));
+
+        assert!(tracker.record(StartupStage::FrontendReady, at(900)));
+        assert!(tracker.is_complete());
+        // 测量结束后到达的阶段和重复的就绪报告都被忽略
+        assert!(!tracker.record(StartupStage::PageLoaded, at(950)));
+        assert!(!tracker.record(StartupStage::FrontendReady, at(2000)));
+
+        let metrics = tracker.metrics();
+        assert_eq!(metrics.app_version, "1.2.3");
+        assert_eq!(metrics.launch_kind, LaunchKind::Warm);
+        assert_eq!(metrics.page_loaded_ms, None);
+        assert_eq!(metrics.frontend_ready_ms, Some(900.0));
+        let value = serde_json::to_value(&metrics).unwrap();
+        assert_eq!(value["launchKind"], "warm");
+        assert_eq!(value["frontendReadyMs"], 900.0);
+    }
+
+    #[test]
+    fn test_cold_and_warm_launches() {
+        let boot = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let now = boot + Duration::from_secs(6 * 3600);
+        assert_eq!(launch_kind(None, boot, now), LaunchKind::Cold);
+        assert_eq!(launch_kind(Some(now - Duration::from_secs(60)), boot, now), LaunchKind::Warm);
+        // 上次启动超过一小时，或者发生在本次开机之前
+        assert_eq!(launch_kind(Some(now - Duration::from_secs(2 * 3600)), boot, now), LaunchKind::Cold);
+        assert_eq!(launch_kind(Some(boot - Duration::from_secs(60)), boot, boot + Duration::from_secs(60)), LaunchKind::Cold);
+
+        // 标记文件：第一次启动为冷启动，紧接着的下一次为热启动
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("data").join(LAUNCH_MARKER_FILE);
+        assert_eq!(detect_launch_kind(&marker, SystemTime::UNIX_EPOCH), LaunchKind::Cold);
+        assert!(marker.exists());
+        assert_eq!(detect_launch_kind(&marker, SystemTime::UNIX_EPOCH), LaunchKind::Warm);
+    }
+
+    #[test]
+    fn test_history_keeps_most_recent() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STARTUP_HISTORY_FILE);
+        assert!(load_startup_history(&path).unwrap().is_empty());
+        for version in ["0.1.0", "0.2.0", "0.3.0"] {
+            let mut tracker = StartupTracker::start();
+            tracker.configure(version, LaunchKind::Cold, path.clone());
+            append_startup_history(&path, &tracker.metrics(), 2).unwrap();
+        }
+        let versions: Vec<String> = load_startup_history(&path).unwrap().into_iter().map(|m| m.app_version).collect();
+        assert_eq!(versions, ["0.2.0", "0.3.0"]);
+    }
+}
@@ -0,0 +1,570 @@
+//! Prometheus指标导出：可选的内嵌HTTP服务，在`/metrics`以文本格式暴露实时系统占用、当前会话进度、
+//! 测试完成和失败计数以及最近一次结果的关键指标。默认关闭，只绑定本机回环地址，除非显式允许远程访问。
+//! 会话相关的指标由ProgressEmitter发送事件时更新，系统占用在每次抓取时读取
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::results::HeadlineMetrics;
+use crate::ipc::{events, EventName, SuiteEvent};
+#[cfg(feature = "tauri-app")]
+use http_body_util::Full;
+#[cfg(feature = "tauri-app")]
+use hyper::body::{Bytes, Incoming};
+#[cfg(feature = "tauri-app")]
+use hyper::server::conn::http1;
+#[cfg(feature = "tauri-app")]
+use hyper::service::service_fn;
+#[cfg(feature = "tauri-app")]
+use hyper::{Method, Request, Response, StatusCode};
+#[cfg(feature = "tauri-app")]
+use hyper_util::rt::TokioIo;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+#[cfg(feature = "tauri-app")]
+use std::convert::Infallible;
+use std::fmt::Write as _;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+#[cfg(feature = "tauri-app")]
+use std::sync::Arc;
+use std::sync::Mutex;
+#[cfg(feature = "tauri-app")]
+use std::time::Duration;
+use sysinfo::{Components, System};
+#[cfg(feature = "tauri-app")]
+use tokio::net::TcpListener;
+#[cfg(feature = "tauri-app")]
+use tokio::sync::watch;
+#[cfg(feature = "tauri-app")]
+use tokio::task::JoinHandle;
+
+/// 未指定端口时使用的默认端口
+pub const DEFAULT_EXPORTER_PORT: u16 = 9464;
+
+// 关闭时等待进行中的抓取完成的时长
+#[cfg(feature = "tauri-app")]
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(2);
+
+#[cfg(feature = "tauri-app")]
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// 指标的名称、类型和标签，通过get_event_schema公开，名称和标签保持稳定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricSchema {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub metric_type: String, // gauge或counter
+    pub help: String,
+    pub labels: Vec<String>,
+}
+
+struct MetricFamily {
+    name: &'static str,
+    metric_type: &'static str,
+    help: &'static str,
+    labels: &'static [&'static str],
+}
+
+const CPU_USAGE: MetricFamily = MetricFamily {
+    name: "benchmark_cpu_usage_percent",
+    metric_type: "gauge",
+    help: "System-wide CPU usage since the previous scrape",
+    labels: &[],
+};
+const MEMORY_USAGE: MetricFamily = MetricFamily {
+    name: "benchmark_memory_usage_percent",
+    metric_type: "gauge",
+    help: "Used physical memory as a percentage of total",
+    labels: &[],
+};
+const TEMPERATURE: MetricFamily = MetricFamily {
+    name: "benchmark_temperature_celsius",
+    metric_type: "gauge",
+    help: "Temperature reported by each hardware sensor",
+    labels: &["component"],
+};
+const SESSION_PROGRESS: MetricFamily = MetricFamily {
+    name: "benchmark_session_progress_percent",
+    metric_type: "gauge",
+    help: "Overall progress of the most recent benchmark session",
+    labels: &["session_id", "current_test"],
+};
+const SESSION_RUNNING: MetricFamily = MetricFamily {
+    name: "benchmark_session_running",
+    metric_type: "gauge",
+    help: "1 while the most recent benchmark session is running, 0 after it finished",
+    labels: &["session_id"],
+};
+const TESTS_COMPLETED: MetricFamily = MetricFamily {
+    name: "benchmark_tests_completed_total",
+    metric_type: "counter",
+    help: "Tests that finished, successfully or not, since the application started",
+    labels: &["test_type"],
+};
+const TESTS_FAILED: MetricFamily = MetricFamily {
+    name: "benchmark_tests_failed_total",
+    metric_type: "counter",
+    help: "Tests that failed since the application started",
+    labels: &["test_type"],
+};
+const LAST_RESULT: MetricFamily = MetricFamily {
+    name: "benchmark_last_result",
+    metric_type: "gauge",
+    help: "Headline metrics of the most recent completed result (scores, or MB/s for throughput)",
+    labels: &["metric"],
+};
+const LAST_RESULT_TIMESTAMP: MetricFamily = MetricFamily {
+    name: "benchmark_last_result_timestamp_seconds",
+    metric_type: "gauge",
+    help: "Unix time at which the most recent result completed",
+    labels: &[],
+};
+
+const FAMILIES: [&MetricFamily; 9] = [
+    &CPU_USAGE,
+    &MEMORY_USAGE,
+    &TEMPERATURE,
+    &SESSION_PROGRESS,
+    &SESSION_RUNNING,
+    &TESTS_COMPLETED,
+    &TESTS_FAILED,
+    &LAST_RESULT,
+    &LAST_RESULT_TIMESTAMP,
+];
+
+/// 导出的全部指标
+pub fn metric_schema() -> Vec<MetricSchema> {
+    FAMILIES
+        .iter()
+        .map(|family| MetricSchema {
+            name: family.name.to_string(),
+            metric_type: family.metric_type.to_string(),
+            help: family.help.to_string(),
+            labels: family.labels.iter().map(|label| label.to_string()).collect(),
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct SessionState {
+    session_id: String,
+    current_test: String,
+    progress: f64,
+    running: bool,
+}
+
+#[derive(Default)]
+struct TelemetryState {
+    session: Option<SessionState>,
+    completed: BTreeMap<String, u64>,
+    failed: BTreeMap<String, u64>,
+    last_result: Option<(HeadlineMetrics, i64)>,
+}
+
+// 抓取时读取的系统占用，CPU占用为两次抓取之间的平均值
+struct SystemSampler {
+    system: System,
+    components: Components,
+}
+
+/// 导出器的数据源，由所有发送器共享
+pub struct Telemetry {
+    state: Mutex<TelemetryState>,
+    sampler: Mutex<SystemSampler>,
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        let mut system = System::new();
+        system.refresh_cpu_usage();
+        Self {
+            state: Mutex::default(),
+            sampler: Mutex::new(SystemSampler {
+                system,
+                components: Components::new_with_refreshed_list(),
+            }),
+        }
+    }
+}
+
+impl Telemetry {
+    /// 会更新指标的事件，其他事件不必解析
+    pub const OBSERVED: [EventName; 4] = [
+        events::BENCHMARK_PROGRESS,
+        events::TEST_COMPLETE,
+        events::BENCHMARK_COMPLETE,
+        events::BENCHMARK_ERROR,
+    ];
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 按session_id的会话发出的事件更新指标
+    pub fn observe(&self, session_id: &str, event: &SuiteEvent) {
+        let mut state = self.state.lock().unwrap();
+        match event {
+            SuiteEvent::Progress(progress) => {
+                state.session = Some(SessionState {
+                    session_id: progress.session_id.clone(),
+                    current_test: progress.current_test.clone(),
+                    progress: progress.overall_progress,
+                    running: true,
+                });
+            }
+            SuiteEvent::TestComplete(complete) => {
+                let test_type = complete.test_type.as_str().to_string();
+                *state.completed.entry(test_type.clone()).or_default() += 1;
+                if !complete.success {
+                    *state.failed.entry(test_type).or_default() += 1;
+                }
+            }
+            SuiteEvent::SuiteComplete(complete) => {
+                if let Some(result) = &complete.results {
+                    let completed_at = chrono::DateTime::parse_from_rfc3339(&complete.completed_at)
+                        .map(|at| at.timestamp())
+                        .unwrap_or_else(|_| chrono::Utc::now().timestamp());
+                    state.last_result = Some((HeadlineMetrics::from_result(result), completed_at));
+                }
+                state.finish(session_id, complete.success);
+            }
+            SuiteEvent::Error { .. } => state.finish(session_id, false),
+            _ => {}
+        }
+    }
+
+    /// 按Prometheus文本格式（0.0.4）输出全部指标，每个指标族都带HELP和TYPE，即使还没有样本
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+        {
+            let mut sampler = self.sampler.lock().unwrap();
+            let SystemSampler { system, components } = &mut *sampler;
+            system.refresh_cpu_usage();
+            system.refresh_memory();
+            components.refresh(false);
+            write_family(&mut output, &CPU_USAGE, &[(Vec::new(), system.global_cpu_usage() as f64)]);
+            let memory = if system.total_memory() > 0 {
+                system.used_memory() as f64 / system.total_memory() as f64 * 100.0
+            } else {
+                0.0
+            };
+            write_family(&mut output, &MEMORY_USAGE, &[(Vec::new(), memory)]);
+            let temperatures: Vec<_> = components
+                .iter()
+                .filter_map(|component| Some((vec![component.label().to_string()], component.temperature()? as f64)))
+                .collect();
+            write_family(&mut output, &TEMPERATURE, &temperatures);
+        }
+
+        let state = self.state.lock().unwrap();
+        let (progress, running) = match &state.session {
+            Some(session) => (
+                vec![(vec![session.session_id.clone(), session.current_test.clone()], session.progress)],
+                vec![(vec![session.session_id.clone()], if session.running { 1.0 } else { 0.0 })],
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+        write_family(&mut output, &SESSION_PROGRESS, &progress);
+        write_family(&mut output, &SESSION_RUNNING, &running);
+        let counts = |counts: &BTreeMap<String, u64>| -> Vec<_> {
+            counts.iter().map(|(test_type, count)| (vec![test_type.clone()], *count as f64)).collect()
+        };
+        write_family(&mut output, &TESTS_COMPLETED, &counts(&state.completed));
+        write_family(&mut output, &TESTS_FAILED, &counts(&state.failed));
+        let (headline, timestamp) = match &state.last_result {
+            Some((metrics, at)) => (
+                metrics
+                    .entries()
+                    .iter()
+                    .filter_map(|(name, value)| Some((vec![name.to_string()], (*value)?)))
+                    .collect(),
+                vec![(Vec::new(), *at as f64)],
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+        write_family(&mut output, &LAST_RESULT, &headline);
+        write_family(&mut output, &LAST_RESULT_TIMESTAMP, &timestamp);
+        output
+    }
+}
+
+impl TelemetryState {
+    fn finish(&mut self, session_id: &str, success: bool) {
+        if let Some(session) = self.session.as_mut().filter(|session| session.session_id == session_id) {
+            session.running = false;
+            if success {
+                session.progress = 100.0;
+            }
+        }
+    }
+}
+
+// 标签值中的反斜杠、双引号和换行按文本格式转义
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn write_family(output: &mut String, family: &MetricFamily, samples: &[(Vec<String>, f64)]) {
+    let _ = writeln!(output, "# HELP {} {}", family.name, family.help);
+    let _ = writeln!(output, "# TYPE {} {}", family.name, family.metric_type);
+    for (values, value) in samples {
+        let labels: Vec<String> = family
+            .labels
+            .iter()
+            .zip(values)
+            .map(|(label, value)| format!("{}=\"{}\"", label, escape_label(value)))
+            .collect();
+        if labels.is_empty() {
+            let _ = writeln!(output, "{} {}", family.name, value);
+        } else {
+            let _ = writeln!(output, "{}{{{}}} {}", family.name, labels.join(","), value);
+        }
+    }
+}
+
+#[cfg(feature = "tauri-app")]
+fn respond(telemetry: &Telemetry, request: &Request<Incoming>) -> Response<Full<Bytes>> {
+    let (status, body) = match (request.method(), request.uri().path()) {
+        (&Method::GET, "/metrics") => (StatusCode::OK, telemetry.render()),
+        (_, "/metrics") => (StatusCode::METHOD_NOT_ALLOWED, String::new()),
+        _ => (StatusCode::NOT_FOUND, String::new()),
+    };
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = status;
+    if status == StatusCode::OK {
+        response
+            .headers_mut()
+            .insert(hyper::header::CONTENT_TYPE, hyper::header::HeaderValue::from_static(CONTENT_TYPE));
+    }
+    response
+}
+
+#[cfg(feature = "tauri-app")]
+async fn serve(listener: TcpListener, telemetry: Arc<Telemetry>, mut shutdown: watch::Receiver<bool>) {
+    let mut connections = tokio::task::JoinSet::new();
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let Ok((stream, _)) = accepted else { continue };
+                let telemetry = telemetry.clone();
+                let mut shutdown = shutdown.clone();
+                connections.spawn(async move {
+                    let service = service_fn(move |request| {
+                        let response = respond(&telemetry, &request);
+                        async move { Ok::<_, Infallible>(response) }
+                    });
+                    let connection = http1::Builder::new().serve_connection(TokioIo::new(stream), service);
+                    tokio::pin!(connection);
+                    tokio::select! {
+                        _ = connection.as_mut() => {}
+                        _ = shutdown.changed() => {
+                            // 处理完当前请求后关闭保持的连接
+                            connection.as_mut().graceful_shutdown();
+                            let _ = connection.await;
+                        }
+                    }
+                });
+            }
+            _ = shutdown.changed() => break,
+        }
+    }
+    drop(listener);
+    let _ = tokio::time::timeout(SHUTDOWN_GRACE, async {
+        while connections.join_next().await.is_some() {}
+    })
+    .await;
+}
+
+/// configure_metrics_exporter的返回值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExporterStatus {
+    pub enabled: bool,
+    pub address: Option<String>, // 实际监听的地址，端口为0时为系统分配的端口
+}
+
+#[cfg(feature = "tauri-app")]
+struct RunningExporter {
+    address: SocketAddr,
+    shutdown: watch::Sender<bool>,
+    task: JoinHandle<()>,
+}
+
+#[cfg(feature = "tauri-app")]
+/// 导出服务的生命周期，同一时间最多运行一个
+#[derive(Default)]
+pub struct MetricsExporter {
+    running: Option<RunningExporter>,
+}
+
+#[cfg(feature = "tauri-app")]
+impl MetricsExporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(&self) -> ExporterStatus {
+        ExporterStatus {
+            enabled: self.running.is_some(),
+            address: self.running.as_ref().map(|running| running.address.to_string()),
+        }
+    }
+
+    /// 在address上启动服务，已在运行时先停止。非回环地址须allow_remote为true
+    pub async fn start(&mut self, telemetry: Arc<Telemetry>, address: SocketAddr, allow_remote: bool) -> Result<ExporterStatus, BenchmarkError> {
+        if !address.ip().is_loopback() && !allow_remote {
+            return Err(BenchmarkError::PermissionError {
+                feature: "metrics_exporter".to_string(),
+                message: format!("指标导出只允许绑定本机回环地址，{}须显式允许远程访问", address.ip()),
+                remediation: Some("设置allow_remote为true以允许远程访问".to_string()),
+            });
+        }
+        self.stop().await;
+        let listener = TcpListener::bind(address).await.map_err(BenchmarkError::io("无法绑定指标导出端口"))?;
+        let address = listener.local_addr().map_err(BenchmarkError::io("无法读取指标导出地址"))?;
+        let (shutdown, receiver) = watch::channel(false);
+        let task = tokio::spawn(serve(listener, telemetry, receiver));
+        self.running = Some(RunningExporter { address, shutdown, task });
+        Ok(self.status())
+    }
+
+    /// 停止服务，等待进行中的抓取完成，最多等待SHUTDOWN_GRACE
+    pub async fn stop(&mut self) {
+        if let Some(running) = self.running.take() {
+            let _ = running.shutdown.send(true);
+            let _ = tokio::time::timeout(SHUTDOWN_GRACE * 2, running.task).await;
+        }
+    }
+}
+
+/// 未指定地址时绑定127.0.0.1
+pub fn exporter_address(bind_address: Option<&str>, port: u16) -> Result<SocketAddr, BenchmarkError> {
+    let ip = match bind_address {
+        Some(address) => address
+            .parse::<IpAddr>()
+            .map_err(|e| BenchmarkError::PermissionError {
+                feature: "metrics_exporter".to_string(),
+                message: format!("无效的绑定地址{}: {}", address, e),
+                remediation: None,
+            })?,
+        None => IpAddr::V4(Ipv4Addr::LOCALHOST),
+    };
+    Ok(SocketAddr::new(ip, port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "tauri-app")]
+    use crate::benchmark::test_support::sample_test_result;
+    #[cfg(feature = "tauri-app")]
+    use crate::ipc::{BenchmarkProgress, BenchmarkSuiteCompleteEvent, TestCompleteEvent, TestCompletion, TestType};
+    #[cfg(feature = "tauri-app")]
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[cfg(feature = "tauri-app")]
+    async fn get(address: &str, path: &str) -> String {
+        let mut stream = tokio::net::TcpStream::connect(address).await.unwrap();
+        let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", path);
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[cfg(feature = "tauri-app")]
+    fn complete(test_type: TestType, success: bool) -> SuiteEvent {
+        SuiteEvent::TestComplete(Box::new(TestCompleteEvent {
+            session_id: "s1".to_string(),
+            test_type,
+            success,
+            result: None,
+            error: None,
+            elapsed_seconds: 1.0,
+            outcome: TestCompletion::Completed,
+            issues: Vec::new(),
+        }))
+    }
+
+    #[cfg(feature = "tauri-app")]
+    #[tokio::test]
+    async fn test_exporter_serves_metric_families() {
+        let telemetry = Arc::new(Telemetry::new());
+        telemetry.observe(
+            "s1",
+            &SuiteEvent::Progress(BenchmarkProgress {
+                session_id: "s1".to_string(),
+                current_test: "cpu".to_string(),
+                overall_progress: 40.0,
+                test_progress: 80.0,
+                message: String::new(),
+                message_key: String::new(),
+                params: Default::default(),
+                estimated_time_remaining: None,
+                estimated_completion_time: None,
+            }),
+        );
+        telemetry.observe("s1", &complete(TestType::Cpu, true));
+        telemetry.observe("s1", &complete(TestType::Storage, false));
+
+        let mut exporter = MetricsExporter::new();
+        let status = exporter.start(telemetry.clone(), exporter_address(None, 0).unwrap(), false).await.unwrap();
+        let address = status.address.unwrap();
+        assert!(address.starts_with("127.0.0.1:") && !address.ends_with(":0"));
+
+        let response = get(&address, "/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.contains(CONTENT_TYPE));
+        for family in metric_schema() {
+            assert!(response.contains(&format!("# TYPE {} {}", family.name, family.metric_type)), "{}", family.name);
+        }
+        assert!(response.contains("benchmark_session_progress_percent{session_id=\"s1\",current_test=\"cpu\"} 40"));
+        assert!(response.contains("benchmark_session_running{session_id=\"s1\"} 1"));
+        assert!(response.contains("benchmark_tests_completed_total{test_type=\"cpu\"} 1"));
+        assert!(response.contains("benchmark_tests_completed_total{test_type=\"storage\"} 1"));
+        assert!(response.contains("benchmark_tests_failed_total{test_type=\"storage\"} 1"));
+        assert!(!response.contains("benchmark_tests_failed_total{test_type=\"cpu\"}"));
+        assert!(get(&address, "/other").await.starts_with("HTTP/1.1 404"));
+
+        // 会话完成后更新最近一次结果
+        telemetry.observe(
+            "s1",
+            &SuiteEvent::SuiteComplete(Box::new(BenchmarkSuiteCompleteEvent {
+                session_id: "s1".to_string(),
+                success: true,
+                results: Some(sample_test_result("2026-01-01T00:00:00Z", 0.0)),
+                error: None,
+                completed_at: "2026-01-01T00:00:00Z".to_string(),
+                save_error: None,
+            })),
+        );
+        let response = get(&address, "/metrics").await;
+        assert!(response.contains("benchmark_session_running{session_id=\"s1\"} 0"));
+        assert!(response.contains("benchmark_session_progress_percent{session_id=\"s1\",current_test=\"cpu\"} 100"));
+        assert!(response.contains("benchmark_last_result{metric=\"cpu_single_thread\"} 100"));
+        assert!(response.contains("benchmark_last_result_timestamp_seconds 1767225600"));
+
+        exporter.stop().await;
+        assert!(!exporter.status().enabled);
+        assert!(tokio::net::TcpStream::connect(&address).await.is_err());
+    }
+
+    #[cfg(feature = "tauri-app")]
+    #[tokio::test]
+    async fn test_exporter_refuses_remote_bind() {
+        let mut exporter = MetricsExporter::new();
+        let telemetry = Arc::new(Telemetry::new());
+        let error = exporter
+            .start(telemetry, exporter_address(Some("0.0.0.0"), 0).unwrap(), false)
+            .await
+            .unwrap_err();
+        assert_eq!(error.code(), "PERMISSION_ERROR");
+        assert!(!exporter.status().enabled);
+        assert!(exporter_address(Some("not an ip"), 0).is_err());
+    }
+
+    #[test]
+    fn test_label_values_are_escaped() {
+        let mut output = String::new();
+        write_family(&mut output, &TEMPERATURE, &[(vec!["a \"b\"\\c".to_string()], 40.5)]);
+        assert!(output.ends_with("benchmark_temperature_celsius{component=\"a \\\"b\\\"\\\\c\"} 40.5\n"));
+    }
+}
@@ -0,0 +1,407 @@
+//! 会话时间序列：保留系统监控采样、存储吞吐、持续写入曲线和CPU实时指标，
+//! 供测试结束后导出为CSV。采样由ProgressEmitter在提交时记录（不受事件限速影响），
+//! 每个会话、每个序列都有上限，超出时丢弃最早的采样
+use crate::benchmark::copy::CopyTestResult;
+use crate::benchmark::error::BenchmarkError;
+use crate::format::csv_field;
+use crate::ipc::{events, EventName, RealTimePerformanceData, SystemMonitoringData, TestType};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+// 保留时间序列的会话数，超出时丢弃最早的会话
+const MAX_SESSIONS: usize = 8;
+// 每个序列保留的采样数
+const MAX_POINTS_PER_STREAM: usize = 50_000;
+
+const LONG_HEADER: &str = "timestamp,stream,metric,value";
+
+/// 时间序列的种类，名称用作CSV文件名和长格式的stream列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TimeseriesStream {
+    SystemMonitoring,
+    StorageThroughput,
+    SustainedWrite,
+    CpuMetrics,
+}
+
+impl TimeseriesStream {
+    pub const ALL: [TimeseriesStream; 4] = [
+        TimeseriesStream::SystemMonitoring,
+        TimeseriesStream::StorageThroughput,
+        TimeseriesStream::SustainedWrite,
+        TimeseriesStream::CpuMetrics,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimeseriesStream::SystemMonitoring => "system_monitoring",
+            TimeseriesStream::StorageThroughput => "storage_throughput",
+            TimeseriesStream::SustainedWrite => "sustained_write",
+            TimeseriesStream::CpuMetrics => "cpu_metrics",
+        }
+    }
+
+    // 实时指标只保留CPU和存储两类，其余测试的采样不进入时间序列
+    fn for_test(test_type: TestType) -> Option<Self> {
+        match test_type {
+            TestType::Cpu => Some(TimeseriesStream::CpuMetrics),
+            TestType::Storage => Some(TimeseriesStream::StorageThroughput),
+            _ => None,
+        }
+    }
+}
+
+/// 导出格式：每个序列一个CSV文件，或所有序列合并为一个长格式CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeseriesFormat {
+    #[default]
+    PerStream,
+    Long,
+}
+
+/// 一个时间点上的各项指标。实时指标的名称带阶段前缀，如"seq_write/mb_per_sec"
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeseriesPoint {
+    pub timestamp: DateTime<Utc>,
+    pub values: Vec<(String, f64)>,
+}
+
+/// 一个会话保留的全部时间序列
+#[derive(Debug, Clone, Default)]
+pub struct SessionTimeseries {
+    streams: BTreeMap<TimeseriesStream, VecDeque<TimeseriesPoint>>,
+}
+
+impl SessionTimeseries {
+    pub fn push(&mut self, stream: TimeseriesStream, point: TimeseriesPoint) {
+        let points = self.streams.entry(stream).or_default();
+        if points.len() >= MAX_POINTS_PER_STREAM {
+            points.pop_front();
+        }
+        points.push_back(point);
+    }
+
+    pub fn points(&self, stream: TimeseriesStream) -> impl Iterator<Item = &TimeseriesPoint> {
+        self.streams.get(&stream).into_iter().flatten()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.streams.values().all(VecDeque::is_empty)
+    }
+
+    /// 按格式写出CSV，返回写出的文件。按序列导出时path是目录，只写出有采样的序列；
+    /// 长格式时path是文件
+    pub fn export(&self, path: &Path, format: TimeseriesFormat) -> Result<Vec<PathBuf>, BenchmarkError> {
+        match format {
+            TimeseriesFormat::PerStream => {
+                fs::create_dir_all(path).map_err(BenchmarkError::io("创建时间序列导出目录"))?;
+                let mut written = Vec::new();
+                for stream in TimeseriesStream::ALL {
+                    if self.points(stream).next().is_none() {
+                        continue;
+                    }
+                    let file = path.join(format!("{}.csv", stream.as_str()));
+                    fs::write(&file, self.stream_csv(stream)).map_err(BenchmarkError::io("写入时间序列CSV"))?;
+                    written.push(file);
+                }
+                Ok(written)
+            }
+            TimeseriesFormat::Long => {
+                if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+                    fs::create_dir_all(parent).map_err(BenchmarkError::io("创建时间序列导出目录"))?;
+                }
+                fs::write(path, self.long_csv()).map_err(BenchmarkError::io("写入时间序列CSV"))?;
+                Ok(vec![path.to_path_buf()])
+            }
+        }
+    }
+
+    /// 一个序列的宽格式CSV：timestamp列加上该序列出现过的所有指标列，缺失的值留空
+    pub fn stream_csv(&self, stream: TimeseriesStream) -> String {
+        let columns: BTreeSet<&str> = self
+            .points(stream)
+            .flat_map(|point| point.values.iter().map(|(metric, _)| metric.as_str()))
+            .collect();
+        let mut csv = String::from("timestamp");
+        for column in &columns {
+            csv.push(',');
+            csv.push_str(&csv_field(column));
+        }
+        csv.push('\n');
+        for point in self.points(stream) {
+            let values: HashMap<&str, f64> = point.values.iter().map(|(metric, value)| (metric.as_str(), *value)).collect();
+            csv.push_str(&format_timestamp(point.timestamp));
+            for column in &columns {
+                csv.push(',');
+                if let Some(value) = values.get(column).filter(|value| value.is_finite()) {
+                    let _ = write!(csv, "{}", value);
+                }
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// 所有序列的长格式CSV，每个指标值一行，按序列内的采样顺序排列
+    pub fn long_csv(&self) -> String {
+        let mut csv = format!("{}\n", LONG_HEADER);
+        for stream in TimeseriesStream::ALL {
+            for point in self.points(stream) {
+                let timestamp = format_timestamp(point.timestamp);
+                for (metric, value) in point.values.iter().filter(|(_, value)| value.is_finite()) {
+                    let _ = writeln!(csv, "{},{},{},{}", timestamp, stream.as_str(), csv_field(metric), value);
+                }
+            }
+        }
+        csv
+    }
+}
+
+#[derive(Debug, Default)]
+struct StoreState {
+    sessions: HashMap<String, SessionTimeseries>,
+    order: VecDeque<String>,
+}
+
+/// 各会话的时间序列，在ProgressEmitter的克隆之间共享
+#[derive(Debug, Default)]
+pub struct TimeseriesStore {
+    state: Mutex<StoreState>,
+}
+
+impl TimeseriesStore {
+    /// 通过emit发送时记录的事件；实时指标在emit_latest提交时记录，避免刷新时重复
+    pub const RECORDED_ON_EMIT: [EventName; 1] = [events::SYSTEM_MONITORING];
+
+    pub fn record(&self, session_id: &str, stream: TimeseriesStream, point: TimeseriesPoint) {
+        let mut state = self.state.lock().unwrap();
+        if !state.sessions.contains_key(session_id) {
+            if state.order.len() >= MAX_SESSIONS {
+                if let Some(oldest) = state.order.pop_front() {
+                    state.sessions.remove(&oldest);
+                }
+            }
+            state.order.push_back(session_id.to_string());
+        }
+        state.sessions.entry(session_id.to_string()).or_default().push(stream, point);
+    }
+
+    /// 从发送的事件负载中记录监控采样或实时指标，其他事件忽略
+    pub fn observe(&self, session_id: &str, event: EventName, payload: &serde_json::Value) {
+        if event == events::SYSTEM_MONITORING {
+            if let Ok(data) = serde_json::from_value::<SystemMonitoringData>(payload.clone()) {
+                let mut values = vec![
+                    ("cpu_usage".to_string(), data.cpu_usage),
+                    ("memory_usage".to_string(), data.memory_usage),
+                ];
+                if let Some(temperature) = data.temperature {
+                    values.push(("temperature".to_string(), temperature));
+                }
+                let timestamp = parse_timestamp(&data.timestamp);
+                self.record(session_id, TimeseriesStream::SystemMonitoring, TimeseriesPoint { timestamp, values });
+            }
+        } else if event == events::REAL_TIME_PERFORMANCE {
+            let Ok(data) = serde_json::from_value::<RealTimePerformanceData>(payload.clone()) else {
+                return;
+            };
+            let Some(stream) = TimeseriesStream::for_test(data.test_type) else {
+                return;
+            };
+            let mut values: Vec<(String, f64)> = data
+                .metrics
+                .into_iter()
+                .map(|(metric, value)| match data.phase.as_str() {
+                    "" => (metric, value),
+                    phase => (format!("{}/{}", phase, metric), value),
+                })
+                .collect();
+            values.sort_by(|a, b| a.0.cmp(&b.0));
+            let timestamp = parse_timestamp(&data.timestamp);
+            self.record(session_id, stream, TimeseriesPoint { timestamp, values });
+        }
+    }
+
+    /// 记录复制测试的持续写入曲线，采样时间由开始时间加上各区间的结束时刻得到
+    pub fn record_copy(&self, session_id: &str, started: DateTime<Utc>, result: &CopyTestResult) {
+        for sample in &result.timeline {
+            let offset = chrono::Duration::milliseconds((sample.elapsed_seconds * 1000.0) as i64);
+            self.record(session_id, TimeseriesStream::SustainedWrite, TimeseriesPoint {
+                timestamp: started + offset,
+                values: vec![
+                    ("throughput".to_string(), sample.throughput),
+                    ("read_throughput".to_string(), sample.read_throughput),
+                    ("write_throughput".to_string(), sample.write_throughput),
+                ],
+            });
+        }
+    }
+
+    pub fn session(&self, session_id: &str) -> Option<SessionTimeseries> {
+        self.state.lock().unwrap().sessions.get(session_id).cloned()
+    }
+}
+
+/// RFC3339，毫秒精度，UTC以Z结尾
+pub fn format_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+// 事件负载中的时间戳无法解析时以记录时间代替
+fn parse_timestamp(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::copy::CopySample;
+
+    fn synthetic_store(started: DateTime<Utc>) -> TimeseriesStore {
+        let store = TimeseriesStore::default();
+        for second in 0..3 {
+            let timestamp = format_timestamp(started + chrono::Duration::seconds(second));
+            let monitoring = SystemMonitoringData {
+                cpu_usage: 40.0 + second as f64,
+                memory_usage: 60.5,
+                temperature: (second > 0).then_some(55.25),
+                timestamp: timestamp.clone(),
+                session_id: Some("s1".to_string()),
+            };
+            store.observe("s1", events::SYSTEM_MONITORING, &serde_json::to_value(monitoring).unwrap());
+            let cpu = RealTimePerformanceData {
+                session_id: "s1".to_string(),
+                test_type: TestType::Cpu,
+                phase: "multi_thread".to_string(),
+                metrics: HashMap::from([("ops_per_sec".to_string(), 1234.5), ("threads".to_string(), 8.0)]),
+                timestamp,
+            };
+            store.observe("s1", events::REAL_TIME_PERFORMANCE, &serde_json::to_value(cpu).unwrap());
+        }
+        let storage = RealTimePerformanceData {
+            session_id: "s1".to_string(),
+            test_type: TestType::Storage,
+            phase: "seq_write".to_string(),
+            metrics: HashMap::from([("mb_per_sec".to_string(), 1500.0)]),
+            timestamp: format_timestamp(started),
+        };
+        store.observe("s1", events::REAL_TIME_PERFORMANCE, &serde_json::to_value(storage).unwrap());
+        // 内存测试的采样不属于任何导出的序列
+        let memory = RealTimePerformanceData {
+            session_id: "s1".to_string(),
+            test_type: TestType::Memory,
+            phase: "copy".to_string(),
+            metrics: HashMap::from([("mb_per_sec".to_string(), 9000.0)]),
+            timestamp: format_timestamp(started),
+        };
+        store.observe("s1", events::REAL_TIME_PERFORMANCE, &serde_json::to_value(memory).unwrap());
+        let copy = CopyTestResult {
+            sustained_throughput: 800.0,
+            file_size: 1024,
+            size_clamped: false,
+            buffer_size: 1024,
+            duration_seconds: 2.0,
+            timeline: (1..=4)
+                .map(|i| CopySample {
+                    elapsed_seconds: i as f64 * 0.5,
+                    throughput: 800.0,
+                    read_throughput: 2000.0,
+                    write_throughput: 900.0,
+                })
+                .collect(),
+            source_throttled: false,
+            destination_throttled: false,
+        };
+        store.record_copy("s1", started, &copy);
+        store
+    }
+
+    fn parse_csv(content: &str) -> (Vec<String>, Vec<Vec<String>>) {
+        let mut lines = content.lines();
+        let header = lines.next().unwrap().split(',').map(str::to_string).collect::<Vec<_>>();
+        let rows = lines.map(|line| line.split(',').map(str::to_string).collect()).collect();
+        (header, rows)
+    }
+
+    fn assert_timestamp(value: &str) {
+        let parsed = DateTime::parse_from_rfc3339(value).unwrap();
+        assert_eq!(value.len(), "2026-01-01T00:00:00.000Z".len(), "{}", value);
+        assert!(value.ends_with('Z'));
+        assert_eq!(parsed.timestamp_subsec_millis() % 250, 0);
+    }
+
+    #[test]
+    fn per_stream_export_writes_one_csv_per_stream() {
+        let started = "2026-03-01T12:00:00.250Z".parse::<DateTime<Utc>>().unwrap();
+        let series = synthetic_store(started).session("s1").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let written = series.export(dir.path(), TimeseriesFormat::PerStream).unwrap();
+        let names: Vec<_> = written.iter().map(|path| path.file_name().unwrap().to_string_lossy().to_string()).collect();
+        assert_eq!(names, ["system_monitoring.csv", "storage_throughput.csv", "sustained_write.csv", "cpu_metrics.csv"]);
+
+        let (header, rows) = parse_csv(&fs::read_to_string(dir.path().join("system_monitoring.csv")).unwrap());
+        assert_eq!(header, ["timestamp", "cpu_usage", "memory_usage", "temperature"]);
+        assert_eq!(rows.len(), 3);
+        assert!(rows.iter().all(|row| row.len() == header.len()));
+        assert_eq!(rows[0], ["2026-03-01T12:00:00.250Z", "40", "60.5", ""]);
+        assert_eq!(rows[2][3], "55.25");
+
+        let (header, rows) = parse_csv(&fs::read_to_string(dir.path().join("cpu_metrics.csv")).unwrap());
+        assert_eq!(header, ["timestamp", "multi_thread/ops_per_sec", "multi_thread/threads"]);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1][1], "1234.5");
+
+        let (header, rows) = parse_csv(&fs::read_to_string(dir.path().join("sustained_write.csv")).unwrap());
+        assert_eq!(header, ["timestamp", "read_throughput", "throughput", "write_throughput"]);
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0][0], "2026-03-01T12:00:00.750Z");
+        for row in &rows {
+            assert_timestamp(&row[0]);
+        }
+    }
+
+    #[test]
+    fn long_export_has_one_row_per_value() {
+        let started = "2026-03-01T12:00:00.250Z".parse::<DateTime<Utc>>().unwrap();
+        let series = synthetic_store(started).session("s1").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("s1.csv");
+
+        assert_eq!(series.export(&path, TimeseriesFormat::Long).unwrap(), std::slice::from_ref(&path));
+        let (header, rows) = parse_csv(&fs::read_to_string(&path).unwrap());
+        assert_eq!(header, ["timestamp", "stream", "metric", "value"]);
+        // 监控2+3+3，存储1，持续写入4×3，CPU 3×2
+        assert_eq!(rows.len(), 8 + 1 + 12 + 6);
+        for row in &rows {
+            assert_eq!(row.len(), 4);
+            assert_timestamp(&row[0]);
+            assert!(row[3].parse::<f64>().unwrap().is_finite());
+        }
+        let count = |stream: &str| rows.iter().filter(|row| row[1] == stream).count();
+        assert_eq!(count("system_monitoring"), 8);
+        assert_eq!(count("storage_throughput"), 1);
+        assert_eq!(count("sustained_write"), 12);
+        assert_eq!(count("cpu_metrics"), 6);
+        assert!(rows.contains(&vec!["2026-03-01T12:00:00.250Z".to_string(), "storage_throughput".into(), "seq_write/mb_per_sec".into(), "1500".into()]));
+    }
+
+    #[test]
+    fn store_keeps_a_bounded_number_of_sessions() {
+        let store = TimeseriesStore::default();
+        let point = || TimeseriesPoint { timestamp: Utc::now(), values: vec![("cpu_usage".to_string(), 1.0)] };
+        for i in 0..=MAX_SESSIONS {
+            store.record(&format!("s{}", i), TimeseriesStream::SystemMonitoring, point());
+        }
+        assert!(store.session("s0").is_none());
+        assert!(store.session(&format!("s{}", MAX_SESSIONS)).is_some());
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+    }
+}
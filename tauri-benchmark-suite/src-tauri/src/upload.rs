@@ -0,0 +1,564 @@
+#[cfg(feature = "tauri-app")]
+use crate::benchmark::anonymous::AnonymousSummary;
+#[cfg(feature = "tauri-app")]
+use crate::benchmark::core::TestResult;
+use crate::benchmark::error::BenchmarkError;
+#[cfg(feature = "tauri-app")]
+use crate::benchmark::leaderboard::LeaderboardEntry;
+#[cfg(feature = "tauri-app")]
+use flate2::write::GzEncoder;
+#[cfg(feature = "tauri-app")]
+use flate2::Compression;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "tauri-app")]
+use std::io::Write;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "tauri-app")]
+use std::time::Duration;
+
+pub const FINGERPRINT_HEADER: &str = "X-Machine-Fingerprint";
+
+// 结果带有运行标识时附带run_id，服务器无需解压请求体即可去重
+pub const RUN_ID_HEADER: &str = "X-Run-Id";
+
+/// 上传配置，默认关闭，只有用户显式配置地址后才会上传
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UploadConfig {
+    pub endpoint: Option<String>,
+    pub token: Option<String>,
+    pub auto_upload: bool,
+}
+
+impl UploadConfig {
+    pub fn load(path: &Path) -> Result<Self, BenchmarkError> {
+        load_config(path, "上传配置")
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BenchmarkError> {
+        save_config(self, path, "上传配置")
+    }
+
+    pub fn validate(&self) -> Result<(), BenchmarkError> {
+        if let Some(endpoint) = &self.endpoint {
+            validate_endpoint(endpoint)?;
+        } else if self.auto_upload {
+            return Err(BenchmarkError::UploadError("启用自动上传前需要配置上传地址".to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.endpoint.is_some()
+    }
+}
+
+/// 上传配置及其持久化位置
+pub struct UploadSettings {
+    pub path: PathBuf,
+    pub config: UploadConfig,
+}
+
+/// 匿名摘要的提交设置，默认不同意，用户同意并配置地址后才能提交
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnonymousSummaryConfig {
+    pub consent: bool,
+    pub consented_at: Option<String>, // 最近一次同意的时间，撤回同意时清空
+    pub endpoint: Option<String>,
+}
+
+impl AnonymousSummaryConfig {
+    pub fn load(path: &Path) -> Result<Self, BenchmarkError> {
+        load_config(path, "匿名摘要设置")
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BenchmarkError> {
+        save_config(self, path, "匿名摘要设置")
+    }
+
+    pub fn validate(&self) -> Result<(), BenchmarkError> {
+        match &self.endpoint {
+            Some(endpoint) => validate_endpoint(endpoint),
+            None => Ok(()),
+        }
+    }
+
+    /// 更新同意状态，首次同意时记录时间，撤回时清空
+    pub fn set_consent(&mut self, consent: bool) {
+        if consent && !self.consent {
+            self.consented_at = Some(chrono::Utc::now().to_rfc3339());
+        } else if !consent {
+            self.consented_at = None;
+        }
+        self.consent = consent;
+    }
+}
+
+/// 匿名摘要设置及其持久化位置
+pub struct AnonymousSummarySettings {
+    pub path: PathBuf,
+    pub config: AnonymousSummaryConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum UploadState {
+    Pending,
+    Succeeded,
+    Failed,
+}
+
+/// 与结果一同保存的上传状态
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UploadStatus {
+    pub state: UploadState,
+    pub server_id: Option<String>,
+    pub attempts: u32,
+    pub error: Option<String>,
+    pub updated_at: String,
+}
+
+impl UploadStatus {
+    pub fn pending() -> Self {
+        Self {
+            state: UploadState::Pending,
+            server_id: None,
+            attempts: 0,
+            error: None,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// 网络相关参数，测试中可调小
+#[cfg(feature = "tauri-app")]
+#[derive(Debug, Clone)]
+pub struct UploadOptions {
+    pub timeout: Duration,
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+}
+
+#[cfg(feature = "tauri-app")]
+impl Default for UploadOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+#[cfg(feature = "tauri-app")]
+#[derive(Debug, Deserialize)]
+struct UploadResponse {
+    id: Option<String>,
+}
+
+#[cfg(feature = "tauri-app")]
+pub struct ResultUploader {
+    endpoint: String,
+    token: Option<String>,
+    options: UploadOptions,
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "tauri-app")]
+impl ResultUploader {
+    pub fn new(config: &UploadConfig, options: UploadOptions) -> Result<Self, BenchmarkError> {
+        config.validate()?;
+        let endpoint = config
+            .endpoint
+            .clone()
+            .ok_or_else(|| BenchmarkError::UploadError("未配置上传地址".to_string()))?;
+        let client = reqwest::Client::builder()
+            .timeout(options.timeout)
+            .build()
+            .map_err(|e| BenchmarkError::UploadError(format!("无法创建HTTP客户端: {}", e)))?;
+
+        Ok(Self {
+            endpoint,
+            token: config.token.clone(),
+            options,
+            client,
+        })
+    }
+
+    /// 匿名摘要使用的上传器：不携带令牌，请求中也不附带指纹和run_id
+    pub fn anonymous(config: &AnonymousSummaryConfig, options: UploadOptions) -> Result<Self, BenchmarkError> {
+        if !config.consent {
+            return Err(BenchmarkError::UploadError("未同意提交匿名摘要".to_string()));
+        }
+        let upload_config = UploadConfig {
+            endpoint: Some(
+                config
+                    .endpoint
+                    .clone()
+                    .ok_or_else(|| BenchmarkError::UploadError("未配置匿名摘要的提交地址".to_string()))?,
+            ),
+            token: None,
+            auto_upload: false,
+        };
+        Self::new(&upload_config, options)
+    }
+
+    /// 上传结果，5xx和网络超时会按指数退避重试，返回最终状态（不会返回Pending）
+    pub async fn upload(&self, result: &TestResult) -> UploadStatus {
+        let mut headers = vec![(FINGERPRINT_HEADER, result.system_info.hardware_fingerprint())];
+        if let Some(identity) = &result.identity {
+            headers.push((RUN_ID_HEADER, identity.run_id.clone()));
+        }
+        self.post(result, &headers).await
+    }
+
+    /// 提交匿名摘要，重试规则与上传结果相同
+    pub async fn submit_anonymous(&self, summary: &AnonymousSummary) -> UploadStatus {
+        self.post(summary, &[]).await
+    }
+
+    /// 提交排行榜条目，与上传结果一样附带机器指纹
+    pub async fn submit_leaderboard(&self, entry: &LeaderboardEntry) -> UploadStatus {
+        self.post(entry, &[(FINGERPRINT_HEADER, entry.machine_fingerprint.clone())]).await
+    }
+
+    async fn post<T: Serialize>(&self, payload: &T, headers: &[(&'static str, String)]) -> UploadStatus {
+        let body = match gzip_json(payload) {
+            Ok(body) => body,
+            Err(e) => return finished(UploadState::Failed, None, 0, Some(e.to_string())),
+        };
+
+        let mut backoff = self.options.initial_backoff;
+        let mut last_error = None;
+        for attempt in 1..=self.options.max_attempts {
+            let mut request = self
+                .client
+                .post(&self.endpoint)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(reqwest::header::CONTENT_ENCODING, "gzip")
+                .body(body.clone());
+            for (name, value) in headers {
+                request = request.header(*name, value);
+            }
+            if let Some(token) = &self.token {
+                request = request.bearer_auth(token);
+            }
+
+            let retryable = match request.send().await {
+                Ok(response) if response.status().is_success() => {
+                    // 响应体中的id是可选的
+                    let server_id = response
+                        .json::<UploadResponse>()
+                        .await
+                        .ok()
+                        .and_then(|r| r.id);
+                    return finished(UploadState::Succeeded, server_id, attempt, None);
+                }
+                Ok(response) => {
+                    last_error = Some(format!("服务器返回 {}", response.status()));
+                    response.status().is_server_error()
+                }
+                Err(e) => {
+                    last_error = Some(format!("请求失败: {}", e));
+                    e.is_timeout() || e.is_connect()
+                }
+            };
+
+            if !retryable || attempt == self.options.max_attempts {
+                return finished(UploadState::Failed, None, attempt, last_error);
+            }
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+
+        finished(UploadState::Failed, None, self.options.max_attempts, last_error)
+    }
+}
+
+#[cfg(feature = "tauri-app")]
+fn finished(state: UploadState, server_id: Option<String>, attempts: u32, error: Option<String>) -> UploadStatus {
+    UploadStatus {
+        state,
+        server_id,
+        attempts,
+        error,
+        updated_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+fn validate_endpoint(endpoint: &str) -> Result<(), BenchmarkError> {
+    validate_http_url(endpoint, "上传地址")
+}
+
+/// 只接受http和https地址，label用于错误消息
+pub(crate) fn validate_http_url(endpoint: &str, label: &str) -> Result<(), BenchmarkError> {
+    let url = url::Url::parse(endpoint)
+        .map_err(|e| BenchmarkError::UploadError(format!("无效的{} {}: {}", label, endpoint, e)))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(BenchmarkError::UploadError(format!("不支持的协议: {}", url.scheme())));
+    }
+    Ok(())
+}
+
+pub(crate) fn load_config<T: DeserializeOwned + Default>(path: &Path, label: &str) -> Result<T, BenchmarkError> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| BenchmarkError::DataLoadError(format!("{}解析失败: {}", label, e))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(BenchmarkError::DataLoadError(format!("无法读取{}: {}", label, e))),
+    }
+}
+
+pub(crate) fn save_config<T: Serialize>(config: &T, path: &Path, label: &str) -> Result<(), BenchmarkError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| BenchmarkError::DataSaveError(format!("无法创建配置目录: {}", e)))?;
+    }
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|e| BenchmarkError::DataSaveError(format!("{}序列化失败: {}", label, e)))?;
+    std::fs::write(path, content)
+        .map_err(|e| BenchmarkError::DataSaveError(format!("{}保存失败: {}", label, e)))
+}
+
+#[cfg(feature = "tauri-app")]
+fn gzip_json<T: Serialize>(payload: &T) -> Result<Vec<u8>, BenchmarkError> {
+    let json = serde_json::to_vec(payload)
+        .map_err(|e| BenchmarkError::UploadError(format!("请求内容序列化失败: {}", e)))?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&json)
+        .and_then(|_| encoder.finish())
+        .map_err(|e| BenchmarkError::UploadError(format!("请求内容压缩失败: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "tauri-app")]
+    use crate::app_info::{AppInfo, RunIdentity, RunMode};
+    #[cfg(feature = "tauri-app")]
+    use crate::benchmark::test_support::sample_test_result;
+    #[cfg(feature = "tauri-app")]
+    use flate2::read::GzDecoder;
+    #[cfg(feature = "tauri-app")]
+    use std::io::Read;
+    #[cfg(feature = "tauri-app")]
+    use wiremock::matchers::{header, header_exists, method, path};
+    #[cfg(feature = "tauri-app")]
+    use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+    #[cfg(feature = "tauri-app")]
+    fn uploader(server: &MockServer, options: UploadOptions) -> ResultUploader {
+        let config = UploadConfig {
+            endpoint: Some(format!("{}/results", server.uri())),
+            token: Some("secret".to_string()),
+            auto_upload: false,
+        };
+        ResultUploader::new(&config, options).unwrap()
+    }
+
+    #[cfg(feature = "tauri-app")]
+    fn fast_options() -> UploadOptions {
+        UploadOptions {
+            timeout: Duration::from_millis(500),
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(10),
+        }
+    }
+
+    #[cfg(feature = "tauri-app")]
+    #[tokio::test]
+    async fn test_upload_success() {
+        let server = MockServer::start().await;
+        let mut result = sample_test_result("2024-01-01T00:00:00Z", 42.0);
+        let identity = RunIdentity::new(&result.system_info, &AppInfo::new("1.0.0"), RunMode::Interactive);
+        result.identity = Some(identity.clone());
+        Mock::given(method("POST"))
+            .and(path("/results"))
+            .and(header("content-encoding", "gzip"))
+            .and(header("authorization", "Bearer secret"))
+            .and(header(FINGERPRINT_HEADER, result.system_info.hardware_fingerprint().as_str()))
+            .and(header(RUN_ID_HEADER, identity.run_id.as_str()))
+            .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({ "id": "srv-1" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let status = uploader(&server, fast_options()).upload(&result).await;
+        assert_eq!(status.state, UploadState::Succeeded);
+        assert_eq!(status.server_id.as_deref(), Some("srv-1"));
+        assert_eq!(status.attempts, 1);
+
+        // 请求体是gzip压缩的完整结果
+        let requests: Vec<Request> = server.received_requests().await.unwrap();
+        let mut json = String::new();
+        GzDecoder::new(requests[0].body.as_slice()).read_to_string(&mut json).unwrap();
+        let uploaded: TestResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(uploaded.overall_score, 42.0);
+        // 运行标识位于文档顶层
+        let document: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(document["identity"]["run_id"], identity.run_id.as_str());
+        assert_eq!(document["identity"]["mode"], "interactive");
+    }
+
+    #[cfg(feature = "tauri-app")]
+    #[tokio::test]
+    async fn test_upload_retries_server_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(header_exists(FINGERPRINT_HEADER))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": "srv-2" })))
+            .mount(&server)
+            .await;
+
+        let result = sample_test_result("2024-01-01T00:00:00Z", 1.0);
+        let status = uploader(&server, fast_options()).upload(&result).await;
+        assert_eq!(status.state, UploadState::Succeeded);
+        assert_eq!(status.attempts, 3);
+        assert_eq!(status.server_id.as_deref(), Some("srv-2"));
+    }
+
+    #[cfg(feature = "tauri-app")]
+    #[tokio::test]
+    async fn test_upload_does_not_retry_client_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = sample_test_result("2024-01-01T00:00:00Z", 1.0);
+        let status = uploader(&server, fast_options()).upload(&result).await;
+        assert_eq!(status.state, UploadState::Failed);
+        assert_eq!(status.attempts, 1);
+    }
+
+    #[cfg(feature = "tauri-app")]
+    #[tokio::test]
+    async fn test_upload_timeout() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(2)))
+            .mount(&server)
+            .await;
+
+        let options = UploadOptions {
+            timeout: Duration::from_millis(100),
+            max_attempts: 2,
+            initial_backoff: Duration::from_millis(10),
+        };
+        let result = sample_test_result("2024-01-01T00:00:00Z", 1.0);
+        let status = uploader(&server, options).upload(&result).await;
+        assert_eq!(status.state, UploadState::Failed);
+        assert_eq!(status.attempts, 2);
+        assert!(status.error.is_some());
+    }
+
+    #[cfg(feature = "tauri-app")]
+    #[tokio::test]
+    async fn test_anonymous_submission_sends_only_the_summary() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/stats"))
+            .and(header("content-encoding", "gzip"))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut config = AnonymousSummaryConfig {
+            endpoint: Some(format!("{}/stats", server.uri())),
+            ..Default::default()
+        };
+        // 未同意时不会创建上传器
+        assert!(ResultUploader::anonymous(&config, fast_options()).is_err());
+        config.set_consent(true);
+
+        let mut result = sample_test_result("2024-01-01T00:00:00Z", 42.0);
+        result.identity = Some(RunIdentity::new(&result.system_info, &AppInfo::new("1.0.0"), RunMode::Interactive));
+        let summary = AnonymousSummary::from(&result);
+        let status = ResultUploader::anonymous(&config, fast_options())
+            .unwrap()
+            .submit_anonymous(&summary)
+            .await;
+        assert_eq!(status.state, UploadState::Succeeded);
+
+        let requests: Vec<Request> = server.received_requests().await.unwrap();
+        assert!(!requests[0].headers.contains_key(FINGERPRINT_HEADER));
+        assert!(!requests[0].headers.contains_key(RUN_ID_HEADER));
+        assert!(!requests[0].headers.contains_key("authorization"));
+        let mut json = String::new();
+        GzDecoder::new(requests[0].body.as_slice()).read_to_string(&mut json).unwrap();
+        assert_eq!(serde_json::from_str::<AnonymousSummary>(&json).unwrap(), summary);
+    }
+
+    #[test]
+    fn test_anonymous_consent_is_off_by_default_and_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("anonymous_summary.json");
+        let mut config = AnonymousSummaryConfig::load(&path).unwrap();
+        assert!(!config.consent);
+        assert!(config.endpoint.is_none());
+
+        config.endpoint = Some("https://stats.example.com/v1/summaries".to_string());
+        config.set_consent(true);
+        assert!(config.validate().is_ok());
+        config.save(&path).unwrap();
+        let loaded = AnonymousSummaryConfig::load(&path).unwrap();
+        assert_eq!(loaded, config);
+        assert!(loaded.consented_at.is_some());
+
+        // 撤回同意后不能再提交
+        config.set_consent(false);
+        assert!(config.consented_at.is_none());
+        #[cfg(feature = "tauri-app")]
+        assert!(ResultUploader::anonymous(&config, UploadOptions::default()).is_err());
+
+        config.endpoint = Some("file:///tmp/stats".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_defaults_and_validation() {
+        let config = UploadConfig::default();
+        assert!(!config.is_enabled());
+        assert!(!config.auto_upload);
+        assert!(config.validate().is_ok());
+
+        let auto_without_endpoint = UploadConfig {
+            auto_upload: true,
+            ..Default::default()
+        };
+        assert!(auto_without_endpoint.validate().is_err());
+
+        let bad_scheme = UploadConfig {
+            endpoint: Some("ftp://example.com".to_string()),
+            ..Default::default()
+        };
+        assert!(bad_scheme.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_persistence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("upload.json");
+        assert!(!UploadConfig::load(&path).unwrap().is_enabled());
+
+        let config = UploadConfig {
+            endpoint: Some("https://lab.example.com/api/results".to_string()),
+            token: None,
+            auto_upload: true,
+        };
+        config.save(&path).unwrap();
+        let loaded = UploadConfig::load(&path).unwrap();
+        assert_eq!(loaded.endpoint, config.endpoint);
+        assert!(loaded.auto_upload);
+    }
+}
@@ -0,0 +1,379 @@
+//! 套件完成通知：用户配置地址后，套件结束（成功、部分完成或失败）时把摘要POST到该地址。
+//! 默认关闭；可用模板生成Slack/Discord等聊天工具接受的请求体。发送失败不影响会话结果
+use crate::benchmark::core::TestResult;
+use crate::benchmark::error::BenchmarkError;
+use crate::benchmark::results::HeadlineMetrics;
+use crate::format::{format_metric, FormatOptions, MetricUnit};
+use crate::upload::{load_config, save_config, validate_http_url, UploadOptions};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// 完成通知的配置，持久化在应用数据目录中
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    pub url: Option<String>,
+    pub template: Option<String>, // 为空时发送CompletionNotification的JSON
+    pub enabled: bool,
+}
+
+impl WebhookConfig {
+    pub fn load(path: &Path) -> Result<Self, BenchmarkError> {
+        load_config(path, "完成通知配置")
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), BenchmarkError> {
+        save_config(self, path, "完成通知配置")
+    }
+
+    /// 地址只接受http(s)；模板用示例通知渲染后必须是合法的JSON
+    pub fn validate(&self) -> Result<(), BenchmarkError> {
+        match &self.url {
+            Some(url) => validate_http_url(url, "通知地址")?,
+            None if self.enabled => {
+                return Err(BenchmarkError::UploadError("启用完成通知前需要配置通知地址".to_string()));
+            }
+            None => {}
+        }
+        if let Some(template) = &self.template {
+            let sample = CompletionNotification::failed("session-id", "error");
+            serde_json::from_str::<serde_json::Value>(&render_template(template, &sample))
+                .map_err(|e| BenchmarkError::UploadError(format!("通知模板渲染后不是合法的JSON: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled && self.url.is_some()
+    }
+}
+
+/// 完成通知配置及其持久化位置
+pub struct WebhookSettings {
+    pub path: PathBuf,
+    pub config: WebhookConfig,
+}
+
+/// 套件的结束方式：partial表示有测试失败或运行被中途取消
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuiteOutcome {
+    Success,
+    Partial,
+    Failure,
+}
+
+impl SuiteOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SuiteOutcome::Success => "success",
+            SuiteOutcome::Partial => "partial",
+            SuiteOutcome::Failure => "failure",
+        }
+    }
+}
+
+/// 未配置模板时POST的请求体
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompletionNotification {
+    pub session_id: String,
+    pub outcome: SuiteOutcome,
+    pub overall_score: Option<f64>, // 套件失败时为空
+    pub headline_metrics: HeadlineMetrics,
+    pub regression_passed: Option<bool>, // 没有可对比的历史结果或跳过检查时为空
+    pub result_id: Option<String>, // 结果已保存时可用于load_result
+    pub error: Option<String>,
+    pub completed_at: String,
+    #[serde(skip)]
+    pub format: FormatOptions, // 只影响摘要文本，JSON中的数值保持原样
+}
+
+impl CompletionNotification {
+    pub fn from_result(session_id: &str, result: &TestResult, outcome: SuiteOutcome, result_id: Option<String>) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            outcome,
+            overall_score: Some(result.overall_score),
+            headline_metrics: HeadlineMetrics::from_result(result),
+            regression_passed: result.regression_check.as_ref().map(|check| check.report.passed()),
+            result_id,
+            error: None,
+            completed_at: chrono::Utc::now().to_rfc3339(),
+            format: FormatOptions::default(),
+        }
+    }
+
+    pub fn failed(session_id: &str, error: impl Into<String>) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            outcome: SuiteOutcome::Failure,
+            overall_score: None,
+            headline_metrics: HeadlineMetrics::default(),
+            regression_passed: None,
+            result_id: None,
+            error: Some(error.into()),
+            completed_at: chrono::Utc::now().to_rfc3339(),
+            format: FormatOptions::default(),
+        }
+    }
+
+    /// 摘要中的数值使用的展示设置
+    pub fn with_format(mut self, format: FormatOptions) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// 供模板使用的一行摘要，数值按展示设置格式化
+    pub fn summary(&self) -> String {
+        let mut summary = format!("Benchmark {} {}", self.session_id, self.outcome.as_str());
+        if let Some(score) = self.overall_score {
+            summary.push_str(&format!(", score {}", format_metric(score, MetricUnit::Score, &self.format)));
+        }
+        for (name, value) in self.headline_metrics.entries() {
+            if let Some(value) = value {
+                // CPU指标是分数，其余是吞吐量
+                let unit = if name.starts_with("cpu") { MetricUnit::Score } else { MetricUnit::MegabytesPerSecond };
+                summary.push_str(&format!(", {} {}", name, format_metric(value, unit, &self.format)));
+            }
+        }
+        match self.regression_passed {
+            Some(true) => summary.push_str(", regression check passed"),
+            Some(false) => summary.push_str(", regression check FAILED"),
+            None => {}
+        }
+        if let Some(error) = &self.error {
+            summary.push_str(&format!(": {}", error));
+        }
+        summary
+    }
+
+    // 模板占位符及其取值，缺失的数值渲染为空字符串
+    fn placeholders(&self) -> Vec<(String, String)> {
+        let number = |value: Option<f64>| value.map(|value| format!("{:.2}", value)).unwrap_or_default();
+        let mut values = vec![
+            ("session_id".to_string(), self.session_id.clone()),
+            ("outcome".to_string(), self.outcome.as_str().to_string()),
+            ("overall_score".to_string(), number(self.overall_score)),
+            (
+                "regression".to_string(),
+                match self.regression_passed {
+                    Some(true) => "pass",
+                    Some(false) => "fail",
+                    None => "skipped",
+                }
+                .to_string(),
+            ),
+            ("result_id".to_string(), self.result_id.clone().unwrap_or_default()),
+            ("error".to_string(), self.error.clone().unwrap_or_default()),
+            ("completed_at".to_string(), self.completed_at.clone()),
+            ("summary".to_string(), self.summary()),
+        ];
+        for (name, value) in self.headline_metrics.entries() {
+            values.push((name.to_string(), number(value)));
+        }
+        values
+    }
+}
+
+/// 把模板中的`{{name}}`替换为对应的值。值按JSON字符串内容转义（不含引号），
+/// 因此占位符应写在字符串字面量中，如`{"text": "{{summary}}"}`；未知占位符原样保留
+pub fn render_template(template: &str, notification: &CompletionNotification) -> String {
+    let mut rendered = template.to_string();
+    for (name, value) in notification.placeholders() {
+        let escaped = serde_json::to_string(&value).unwrap_or_default();
+        rendered = rendered.replace(&format!("{{{{{}}}}}", name), &escaped[1..escaped.len() - 1]);
+    }
+    rendered
+}
+
+/// 完成通知的网络参数：10秒超时，失败后重试一次
+pub fn webhook_options() -> UploadOptions {
+    UploadOptions {
+        timeout: Duration::from_secs(10),
+        max_attempts: 2,
+        initial_backoff: Duration::from_secs(1),
+    }
+}
+
+pub struct WebhookNotifier {
+    url: String,
+    template: Option<String>,
+    options: UploadOptions,
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(config: &WebhookConfig, options: UploadOptions) -> Result<Self, BenchmarkError> {
+        config.validate()?;
+        let url = config
+            .url
+            .clone()
+            .filter(|_| config.enabled)
+            .ok_or_else(|| BenchmarkError::UploadError("未启用完成通知".to_string()))?;
+        let client = reqwest::Client::builder()
+            .timeout(options.timeout)
+            .build()
+            .map_err(|e| BenchmarkError::UploadError(format!("无法创建HTTP客户端: {}", e)))?;
+        Ok(Self {
+            url,
+            template: config.template.clone(),
+            options,
+            client,
+        })
+    }
+
+    /// 发送通知，5xx和网络错误重试，成功时返回尝试次数，失败时返回最后一次的错误
+    pub async fn notify(&self, notification: &CompletionNotification) -> Result<u32, String> {
+        let body = match &self.template {
+            Some(template) => render_template(template, notification),
+            None => serde_json::to_string(notification).map_err(|e| format!("通知内容序列化失败: {}", e))?,
+        };
+
+        let mut last_error = String::new();
+        for attempt in 1..=self.options.max_attempts {
+            let request = self
+                .client
+                .post(&self.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone());
+            let retryable = match request.send().await {
+                Ok(response) if response.status().is_success() => return Ok(attempt),
+                Ok(response) => {
+                    last_error = format!("服务器返回 {}", response.status());
+                    response.status().is_server_error()
+                }
+                Err(e) => {
+                    last_error = format!("请求失败: {}", e);
+                    e.is_timeout() || e.is_connect()
+                }
+            };
+            if !retryable || attempt == self.options.max_attempts {
+                break;
+            }
+            tokio::time::sleep(self.options.initial_backoff).await;
+        }
+        Err(last_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::test_support::sample_test_result;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn fast_options() -> UploadOptions {
+        UploadOptions {
+            timeout: Duration::from_millis(500),
+            max_attempts: 2,
+            initial_backoff: Duration::from_millis(10),
+        }
+    }
+
+    fn notifier(server: &MockServer, template: Option<&str>) -> WebhookNotifier {
+        let config = WebhookConfig {
+            url: Some(format!("{}/hook", server.uri())),
+            template: template.map(str::to_string),
+            enabled: true,
+        };
+        WebhookNotifier::new(&config, fast_options()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_default_payload_shape() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(header("content-type", "application/json"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = sample_test_result("2024-01-01T00:00:00Z", 42.0);
+        let notification = CompletionNotification::from_result("s1", &result, SuiteOutcome::Success, Some("s1".to_string()));
+        assert_eq!(notifier(&server, None).notify(&notification).await, Ok(1));
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["session_id"], "s1");
+        assert_eq!(body["outcome"], "success");
+        assert_eq!(body["overall_score"], 42.0);
+        assert_eq!(body["result_id"], "s1");
+        assert!(body["regression_passed"].is_null());
+        assert!(body["headline_metrics"].is_object());
+        assert!(body["completed_at"].is_string());
+        assert!(body.get("format").is_none());
+
+        let german = FormatOptions { locale: crate::format::NumberLocale::DeDe, ..FormatOptions::default() };
+        let result = sample_test_result("2024-01-01T00:00:00Z", 1234.5);
+        let notification = CompletionNotification::from_result("s1", &result, SuiteOutcome::Success, None).with_format(german);
+        assert!(notification.summary().starts_with("Benchmark s1 success, score 1.234,50"));
+    }
+
+    #[tokio::test]
+    async fn test_template_renders_escaped_values() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST")).respond_with(ResponseTemplate::new(200)).mount(&server).await;
+
+        let template = r#"{"content": "{{summary}}", "outcome": "{{outcome}}", "score": "{{overall_score}}"}"#;
+        let notification = CompletionNotification::failed("s2", "磁盘 \"D:\" 已满");
+        notifier(&server, Some(template)).notify(&notification).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["content"], "Benchmark s2 failure: 磁盘 \"D:\" 已满");
+        assert_eq!(body["outcome"], "failure");
+        assert_eq!(body["score"], "");
+    }
+
+    #[tokio::test]
+    async fn test_retries_once_then_gives_up() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST")).respond_with(ResponseTemplate::new(200)).mount(&server).await;
+        let notification = CompletionNotification::failed("s3", "x");
+        assert_eq!(notifier(&server, None).notify(&notification).await, Ok(2));
+
+        let failing = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(2)
+            .mount(&failing)
+            .await;
+        let error = notifier(&failing, None).notify(&notification).await.unwrap_err();
+        assert!(error.contains("500"));
+    }
+
+    #[test]
+    fn test_config_is_opt_in_and_validated() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("webhook.json");
+        let config = WebhookConfig::load(&path).unwrap();
+        assert!(!config.is_enabled());
+        assert!(WebhookNotifier::new(&config, webhook_options()).is_err());
+
+        let enabled_without_url = WebhookConfig { enabled: true, ..Default::default() };
+        assert!(enabled_without_url.validate().is_err());
+        let invalid_template = WebhookConfig {
+            url: Some("https://hooks.example.com/x".to_string()),
+            template: Some("{\"text\": {{summary}}}".to_string()),
+            enabled: true,
+        };
+        assert!(invalid_template.validate().is_err());
+
+        let config = WebhookConfig {
+            template: Some("{\"text\": \"{{summary}}\"}".to_string()),
+            ..invalid_template
+        };
+        config.save(&path).unwrap();
+        assert_eq!(WebhookConfig::load(&path).unwrap(), config);
+        assert!(config.is_enabled());
+    }
+}
@@ -2,6 +2,7 @@ import { ref, reactive, computed, onUnmounted } from 'vue';
 import { TauriApiService, EventListenerManager, handleTauriError } from '../services/tauri-api';
 import {
     TestStatus,
+    EVENT_SCHEMA_VERSION,
 } from '../types';
 import type {
     BenchmarkConfig,
@@ -9,13 +10,17 @@ import type {
     TestResults,
     TestSession,
     SystemMonitoringData,
-    BenchmarkProgress,
     RealTimePerformanceData,
     TestWarningEvent,
-    TestCompleteEvent,
-    BenchmarkSuiteCompleteEvent,
+    SessionStatusChangedEvent,
+    SuiteEvent,
+    UiStateSnapshot,
 } from '../types';
 
+// 超过该时间没有收到套件事件时改为每秒轮询界面状态快照（部分Linux/WebKitGTK环境下事件无法送达WebView）
+const EVENT_SILENCE_MS = 3000;
+const TERMINAL_STATUSES: TestStatus[] = [TestStatus.Completed, TestStatus.Failed, TestStatus.Cancelled, TestStatus.Interrupted];
+
 /**
  * 基准测试组合式 API
  */
@@ -36,6 +41,7 @@ export function useBenchmark() {
         currentTest: '',
         message: '',
         estimatedTimeRemaining: null as number | null,
+        estimatedCompletionTime: null as string | null,
     });
 
     // 监控数据
@@ -46,6 +52,10 @@ export function useBenchmark() {
     // 事件监听器管理
     const eventManager = new EventListenerManager();
 
+    // 最近一次收到套件事件的时间，用于判断是否需要轮询
+    let lastEventAt = 0;
+    let uiStateTimer: ReturnType<typeof setInterval> | null = null;
+
     // 计算属性
     const isRunning = computed(() => testStatus.value === 'Running');
     const isCompleted = computed(() => testStatus.value === 'Completed');
@@ -60,6 +70,11 @@ export function useBenchmark() {
             isLoading.value = true;
             error.value = null;
             systemInfo.value = await TauriApiService.getSystemInfo();
+            // 后端事件负载版本与前端不一致时提示，避免静默地读错字段
+            const versions = await TauriApiService.getSupportedSchemaVersions();
+            if (versions.event !== EVENT_SCHEMA_VERSION) {
+                console.warn(`事件负载版本不一致：后端 ${versions.event}，前端 ${EVENT_SCHEMA_VERSION}`);
+            }
         } catch (err) {
             error.value = handleTauriError(err);
             console.error('Failed to get system info:', err);
@@ -76,11 +91,7 @@ export function useBenchmark() {
             isLoading.value = true;
             error.value = null;
             
-            const sessionId = await TauriApiService.startBenchmarkSuite(config);
-            currentSession.value = sessionId;
-            testStatus.value = TestStatus.Running;
-            
-            // 重置进度和数据
+            // 重置进度和数据；通道事件可能在命令返回前就到达，必须先重置
             progress.overall = 0;
             progress.current = 0;
             progress.currentTest = '';
@@ -88,6 +99,12 @@ export function useBenchmark() {
             performanceData.value = [];
             warnings.value = [];
             
+            const sessionId = await TauriApiService.startBenchmarkSuite(config, handleSuiteEvent);
+            currentSession.value = sessionId;
+            testStatus.value = TestStatus.Running;
+            lastEventAt = Date.now();
+            startUiStatePolling();
+            
             return sessionId;
         } catch (err) {
             error.value = handleTauriError(err);
@@ -198,75 +215,122 @@ export function useBenchmark() {
     };
 
     /**
-     * 设置事件监听器
+     * 用界面状态快照更新进度、警告和监控数据，事件缺失时代替事件驱动的更新
      */
-    const setupEventListeners = async () => {
-        try {
-            // 监听基准测试进度
-            await eventManager.addListener<BenchmarkProgress>('benchmark-progress', (data) => {
-                if (data.session_id === currentSession.value) {
-                    progress.overall = data.overall_progress;
-                    progress.current = data.test_progress;
-                    progress.currentTest = data.current_test;
-                    progress.message = data.message;
-                    progress.estimatedTimeRemaining = data.estimated_time_remaining || null;
-                }
-            });
+    const applyUiState = (snapshot: UiStateSnapshot) => {
+        testStatus.value = snapshot.status;
+        progress.overall = snapshot.overallProgress;
+        progress.currentTest = snapshot.currentTest ?? '';
+        progress.current = snapshot.tests.find((test) => test.testType === snapshot.currentTest)?.progress ?? 0;
+        progress.message = snapshot.phase ?? progress.message;
+        progress.estimatedTimeRemaining = snapshot.estimatedTimeRemaining ?? null;
+        progress.estimatedCompletionTime = snapshot.estimatedCompletionTime ?? null;
+        warnings.value = snapshot.recentWarnings;
+        if (snapshot.monitoring) {
+            monitoringData.value = snapshot.monitoring;
+        }
+        const latest = performanceData.value[performanceData.value.length - 1];
+        if (snapshot.latestSample && snapshot.latestSample.timestamp !== latest?.timestamp) {
+            performanceData.value = [...performanceData.value, snapshot.latestSample].slice(-100);
+        }
+        if (TERMINAL_STATUSES.includes(snapshot.status)) {
+            stopUiStatePolling();
+        }
+    };
 
-            // 监听系统监控数据
-            await eventManager.addListener<SystemMonitoringData>('system-monitoring', (data) => {
-                monitoringData.value = data;
-            });
+    /**
+     * 每秒检查一次，超过EVENT_SILENCE_MS没有事件时从get_ui_state更新，会话结束后停止
+     */
+    const startUiStatePolling = () => {
+        stopUiStatePolling();
+        uiStateTimer = setInterval(async () => {
+            const sessionId = currentSession.value;
+            if (!sessionId) {
+                stopUiStatePolling();
+                return;
+            }
+            if (Date.now() - lastEventAt < EVENT_SILENCE_MS) return;
+            try {
+                applyUiState(await TauriApiService.getUiState(sessionId));
+            } catch (err) {
+                console.error('Failed to poll ui state:', err);
+            }
+        }, 1000);
+    };
 
-            // 监听实时性能数据
-            await eventManager.addListener<RealTimePerformanceData>('real-time-performance', (data) => {
-                if (data.session_id === currentSession.value) {
-                    performanceData.value.push(data);
-                    // 保持最近的100条记录
-                    if (performanceData.value.length > 100) {
-                        performanceData.value = performanceData.value.slice(-100);
-                    }
-                }
-            });
+    const stopUiStatePolling = () => {
+        if (uiStateTimer) {
+            clearInterval(uiStateTimer);
+            uiStateTimer = null;
+        }
+    };
 
-            // 监听测试警告
-            await eventManager.addListener<TestWarningEvent>('test-warning', (data) => {
-                if (data.session_id === currentSession.value) {
-                    warnings.value.push(data);
+    /**
+     * 处理当前会话通道中的事件，通道只包含本会话的事件，无需按sessionId过滤
+     */
+    const handleSuiteEvent = (suiteEvent: SuiteEvent) => {
+        lastEventAt = Date.now();
+        switch (suiteEvent.event) {
+            case 'progress': {
+                const data = suiteEvent.data;
+                progress.overall = data.overallProgress;
+                progress.current = data.testProgress;
+                progress.currentTest = data.currentTest;
+                progress.message = data.message;
+                progress.estimatedTimeRemaining = data.estimatedTimeRemaining || null;
+                progress.estimatedCompletionTime = data.estimatedCompletionTime || null;
+                break;
+            }
+            case 'monitoring':
+                monitoringData.value = suiteEvent.data;
+                break;
+            case 'performance':
+                performanceData.value.push(suiteEvent.data);
+                // 保持最近的100条记录
+                if (performanceData.value.length > 100) {
+                    performanceData.value = performanceData.value.slice(-100);
                 }
-            });
-
-            // 监听测试完成
-            await eventManager.addListener<TestCompleteEvent>('test-complete', (data) => {
-                if (data.session_id === currentSession.value) {
-                    if (!data.success) {
-                        error.value = data.error || '测试失败';
-                        testStatus.value = TestStatus.Failed;
-                    }
+                break;
+            case 'warning':
+                warnings.value.push(suiteEvent.data);
+                break;
+            case 'testComplete':
+                if (!suiteEvent.data.success) {
+                    error.value = suiteEvent.data.error || '测试失败';
+                    testStatus.value = TestStatus.Failed;
                 }
-            });
-
-            // 监听基准测试套件完成
-            await eventManager.addListener<BenchmarkSuiteCompleteEvent>('benchmark-complete', (data) => {
-                if (data.session_id === currentSession.value) {
-                    if (data.success && data.results) {
-                        testResults.value = data.results;
-                        testStatus.value = TestStatus.Completed;
-                    } else {
-                        error.value = data.error || '测试套件失败';
-                        testStatus.value = TestStatus.Failed;
-                    }
+                break;
+            case 'suiteComplete':
+                if (suiteEvent.data.success && suiteEvent.data.results) {
+                    testResults.value = suiteEvent.data.results;
+                    testStatus.value = TestStatus.Completed;
+                } else if (suiteEvent.data.results) {
+                    // 被取消的运行：保留已完成的测试结果
+                    testResults.value = suiteEvent.data.results;
+                    testStatus.value = TestStatus.Cancelled;
+                } else {
+                    error.value = suiteEvent.data.error || '测试套件失败';
+                    testStatus.value = TestStatus.Failed;
                 }
-            });
+                break;
+            case 'error':
+                error.value = suiteEvent.data.message || '测试套件失败';
+                testStatus.value = TestStatus.Failed;
+                break;
+        }
+    };
 
-            // 监听测试错误
-            await eventManager.addListener<TestCompleteEvent>('test-error', (data) => {
-                if (data.session_id === currentSession.value) {
-                    error.value = data.error || '测试出现错误';
-                    testStatus.value = TestStatus.Failed;
+    /**
+     * 设置事件监听器
+     * 套件事件通过会话通道送达，这里只监听跨会话的状态变化
+     */
+    const setupEventListeners = async () => {
+        try {
+            await eventManager.addListener<SessionStatusChangedEvent>('session-status-changed', (data) => {
+                if (data.sessionId === currentSession.value) {
+                    testStatus.value = data.newStatus;
                 }
             });
-
         } catch (err) {
             console.error('Failed to setup event listeners:', err);
         }
@@ -277,6 +341,7 @@ export function useBenchmark() {
      */
     const cleanup = () => {
         eventManager.removeAllListeners();
+        stopUiStatePolling();
         currentSession.value = null;
         testStatus.value = TestStatus.Pending;
         testResults.value = null;
@@ -2,6 +2,8 @@ import { createApp } from "vue";
 import { createPinia } from 'pinia'
 import App from "./App.vue";
 import router from './router'
+import { TauriApiService } from './services/tauri-api'
+import { runWebviewWorkloads } from './services/webview-benchmark'
 
 const app = createApp(App)
 const pinia = createPinia()
@@ -9,3 +11,32 @@ const pinia = createPinia()
 app.use(pinia)
 app.use(router)
 app.mount("#app")
+
+// 后端的WebView测试请求由前端运行负载并提交计时，运行失败时后端按超时处理
+TauriApiService.onWebviewBenchmarkRequest((request) => {
+    runWebviewWorkloads(request)
+        .then((submission) => TauriApiService.submitWebviewResults(submission))
+        .catch(() => {})
+}).catch(() => {})
+
+// 不能自动切换电源计划的平台上，请用户手动切换后确认，拒绝时后端跳过高性能计划的测试
+TauriApiService.onPowerPlanSwitchRequest((request) => {
+    const prompt = request.restore
+        ? `测试已完成，请将电源计划切换回“${request.target.name}”，完成后点击确定`
+        : `请将电源计划切换为“${request.target.name}”，完成后点击确定；点击取消则跳过对比`
+    TauriApiService.confirmPowerPlanSwitch(request.nonce, window.confirm(prompt)).catch(() => {})
+}).catch(() => {})
+
+// 启动参数有误时后端照常打开界面，在这里提示错误
+TauriApiService.takeLaunchError().then((error) => {
+    if (error) {
+        window.alert(error)
+    }
+}).catch(() => {})
+
+// 等待两帧，确保首屏内容已经绘制，再结束启动测量
+requestAnimationFrame(() => {
+    requestAnimationFrame(() => {
+        TauriApiService.reportFrontendReady().catch(() => {})
+    })
+})
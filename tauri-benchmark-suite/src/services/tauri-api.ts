@@ -1,17 +1,31 @@
-import { invoke } from '@tauri-apps/api/core';
+import { invoke, Channel } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import type {
     BenchmarkConfig,
     SystemInfo,
     TestResults,
+    HistoryEntry,
+    DeletionReport,
     CpuTestConfig,
     CpuTestResult,
     MemoryTestConfig,
     MemoryTestResult,
     StorageTestConfig,
     StorageTestResult,
+    GpuTestConfig,
+    GpuTestResult,
+    NetworkTestConfig,
+    NetworkTestResult,
+    CopyTestConfig,
+    CopyTestResult,
+    IpcBenchmarkConfig,
+    IpcBenchmarkResult,
+    IpcEchoReply,
+    ProcessTestConfig,
+    ProcessTestResult,
     TestStatus,
     TestSession,
+    UiStateSnapshot,
     SystemMonitoringData,
     BenchmarkProgress,
     RealTimePerformanceData,
@@ -19,6 +33,27 @@ import type {
     TestCompleteEvent,
     BenchmarkSuiteCompleteEvent,
     ProgressUpdate,
+    RecentEvents,
+    WarningRecord,
+    WarningSeverity,
+    SuiteEvent,
+    SupportedSchemaVersions,
+    ErrorCatalogEntry,
+    LoadedConfig,
+    StartupMetrics,
+    WebviewTestConfig,
+    WebviewTestResult,
+    WebviewBenchmarkRequest,
+    WebviewSubmission,
+    PowerComparisonConfig,
+    ExporterStatus,
+    TimeseriesFormat,
+    PowerPlanComparison,
+    PowerPlanSwitchRequest,
+    CapabilityMap,
+    FormatOptions,
+    ElevationOutcome,
+    Feature,
 } from '../types';
 
 /**
@@ -34,10 +69,49 @@ export class TauriApiService {
     }
 
     /**
-     * 启动完整基准测试套件
+     * 启动完整基准测试套件，该会话的事件只通过onEvent回调送达
      */
-    static async startBenchmarkSuite(config: BenchmarkConfig): Promise<string> {
-        return await invoke<string>('start_benchmark_suite', { config });
+    static async startBenchmarkSuite(config: BenchmarkConfig, onEvent: (event: SuiteEvent) => void): Promise<string> {
+        const channel = new Channel<SuiteEvent>();
+        channel.onmessage = onEvent;
+        return await invoke<string>('start_benchmark_suite', { config, onEvent: channel });
+    }
+
+    /**
+     * 开启后套件事件同时以全局事件广播（已弃用，仅供旧的监听代码使用）
+     */
+    static async configureLegacyEvents(enabled: boolean): Promise<void> {
+        return await invoke<void>('configure_legacy_events', { enabled });
+    }
+
+    /**
+     * 开启或关闭Prometheus指标导出（/metrics），默认只监听127.0.0.1，绑定其他地址须允许远程访问
+     */
+    static async configureMetricsExporter(
+        enabled: boolean,
+        port?: number,
+        options?: { bindAddress?: string; allowRemote?: boolean },
+    ): Promise<ExporterStatus> {
+        return await invoke<ExporterStatus>('configure_metrics_exporter', {
+            enabled,
+            port,
+            bindAddress: options?.bindAddress,
+            allowRemote: options?.allowRemote,
+        });
+    }
+
+    /**
+     * 配置套件完成通知（默认关闭），模板中可使用{{summary}}、{{overall_score}}等占位符
+     */
+    static async configureCompletionWebhook(url: string | null, template: string | null, enabled: boolean): Promise<void> {
+        return await invoke('configure_completion_webhook', { url, template, enabled });
+    }
+
+    /**
+     * 把会话的监控和实时指标时间序列导出为CSV，per_stream时path为目录，返回写出的文件
+     */
+    static async exportTimeseries(sessionId: string, path: string, format?: TimeseriesFormat): Promise<string[]> {
+        return await invoke<string[]>('export_timeseries', { sessionId, path, format });
     }
 
     /**
@@ -61,6 +135,41 @@ export class TauriApiService {
         return await invoke<TestSession[]>('get_all_test_sessions');
     }
 
+    /**
+     * 按会话ID读取一次套件运行的完整结果，错过完成事件时据此取回
+     */
+    static async getBenchmarkResult(sessionId: string): Promise<TestResults> {
+        return await invoke<TestResults>('get_benchmark_result', { sessionId });
+    }
+
+    /**
+     * 列出各次套件运行（会话ID、时间和总分），最新的在前
+     */
+    static async listBenchmarkHistory(): Promise<HistoryEntry[]> {
+        return await invoke<HistoryEntry[]>('list_benchmark_history');
+    }
+
+    /**
+     * 删除一次套件运行的结果，重复运行时各次的原始结果一并删除
+     */
+    static async deleteBenchmarkResult(sessionId: string): Promise<DeletionReport> {
+        return await invoke<DeletionReport>('delete_benchmark_result', { sessionId });
+    }
+
+    /**
+     * 按会话ID把一次套件运行的结果导出为CSV（section,metric,value,unit，每个数值一行），便于在电子表格中对比多台机器
+     */
+    static async exportResultCsv(sessionId: string, path: string): Promise<void> {
+        return await invoke<void>('export_result_csv', { sessionId, path });
+    }
+
+    /**
+     * 把已保存的测试结果导出为独立的HTML报告（样式内联，可直接在浏览器中打开）
+     */
+    static async exportResultHtml(resultId: string, path: string): Promise<void> {
+        return await invoke<void>('export_result_html', { resultId, path });
+    }
+
     /**
      * 获取系统监控数据
      */
@@ -89,6 +198,90 @@ export class TauriApiService {
         return await invoke<void>('resume_benchmark', { sessionId });
     }
 
+    /**
+     * 获取会话中序号大于sinceSeq的事件，重新连接后补齐错过的事件
+     */
+    static async getRecentEvents(sessionId: string, sinceSeq?: number, limit?: number): Promise<RecentEvents> {
+        return await invoke<RecentEvents>('get_recent_events', { sessionId, sinceSeq, limit });
+    }
+
+    /**
+     * 获取渲染运行界面所需的全部状态，不依赖事件送达，事件缺失时按1Hz轮询
+     */
+    static async getUiState(sessionId: string): Promise<UiStateSnapshot> {
+        return await invoke<UiStateSnapshot>('get_ui_state', { sessionId });
+    }
+
+    /**
+     * 获取会话的警告历史，包括被去重抑制的次数
+     */
+    static async getSessionWarnings(sessionId: string): Promise<WarningRecord[]> {
+        return await invoke<WarningRecord[]>('get_session_warnings', { sessionId });
+    }
+
+    /**
+     * 只实时发送不低于minSeverity且类型未被屏蔽的警告，被过滤的警告仍记入历史
+     */
+    static async configureWarningFilter(minSeverity: WarningSeverity, mutedTypes: string[]): Promise<void> {
+        return await invoke<void>('configure_warning_filter', { minSeverity, mutedTypes });
+    }
+
+    /**
+     * 获取后端的事件负载版本和可读取的结果文档版本
+     */
+    static async getSupportedSchemaVersions(): Promise<SupportedSchemaVersions> {
+        return await invoke<SupportedSchemaVersions>('get_supported_schema_versions');
+    }
+
+    /**
+     * 从TOML或JSON文件读取测试配置，省略的字段取标准预设
+     */
+    static async loadBenchmarkConfig(path: string): Promise<LoadedConfig> {
+        return await invoke<LoadedConfig>('load_benchmark_config', { path });
+    }
+
+    /**
+     * 把测试配置保存为TOML或JSON文件，格式由扩展名决定
+     */
+    static async saveBenchmarkConfig(config: BenchmarkConfig, path: string): Promise<void> {
+        return await invoke<void>('save_benchmark_config', { config, path });
+    }
+
+    /**
+     * 获取全部错误码及其默认消息，用于把IpcError.code映射到帮助文档
+     */
+    static async getErrorCatalog(): Promise<ErrorCatalogEntry[]> {
+        return await invoke<ErrorCatalogEntry[]>('get_error_catalog');
+    }
+
+    /**
+     * 设置报告和通知摘要中数值的地区格式、单位制（SI/IEC）和小数位数，JSON导出不受影响
+     */
+    static async setFormatOptions(options: FormatOptions): Promise<void> {
+        return await invoke<void>('set_format_options', { options });
+    }
+
+    /**
+     * 设置后端事件消息文本的语言（en/zh）
+     */
+    static async setBackendLocale(locale: string): Promise<void> {
+        return await invoke<void>('set_backend_locale', { locale });
+    }
+
+    /**
+     * 配置心跳间隔和停滞警告阈值（秒），对之后启动的测试生效
+     */
+    static async configureHeartbeat(intervalSecs: number, stallThresholdSecs: number): Promise<void> {
+        return await invoke<void>('configure_heartbeat', { intervalSecs, stallThresholdSecs });
+    }
+
+    /**
+     * 运行时调整会话日志文件的详细程度，会话日志面板始终包含info及以上
+     */
+    static async setLogLevel(level: 'error' | 'warn' | 'info' | 'debug' | 'trace'): Promise<void> {
+        return await invoke<void>('set_log_level', { level });
+    }
+
     /**
      * 运行单个CPU基准测试
      */
@@ -110,6 +303,140 @@ export class TauriApiService {
         return await invoke<StorageTestResult>('run_storage_benchmark', { config });
     }
 
+    /**
+     * 运行单个GPU基准测试，本机没有可用的适配器时失败（GPU_TEST_ERROR）
+     */
+    static async runGpuBenchmark(config: GpuTestConfig): Promise<GpuTestResult> {
+        return await invoke<GpuTestResult>('run_gpu_benchmark', { config });
+    }
+
+    /**
+     * 运行单个网络基准测试，不指定target时对本机回环服务器测试，目标不可达时失败（NETWORK_TEST_ERROR），
+     * target不是http://地址（包括https）、时长或并发连接数超出上限时失败（INVALID_CONFIG）
+     */
+    static async runNetworkBenchmark(config: NetworkTestConfig): Promise<NetworkTestResult> {
+        return await invoke<NetworkTestResult>('run_network_benchmark', { config });
+    }
+
+    /**
+     * 取消正在运行的网络测试，runNetworkBenchmark以CANCELLED失败
+     */
+    static async cancelNetworkBenchmark(): Promise<void> {
+        return await invoke<void>('cancel_network_benchmark');
+    }
+
+    /**
+     * 在两个卷之间运行复制测试
+     */
+    static async runCopyBenchmark(config: CopyTestConfig): Promise<CopyTestResult> {
+        return await invoke<CopyTestResult>('run_copy_benchmark', { config });
+    }
+
+    /**
+     * 取消正在运行的复制测试
+     */
+    static async cancelCopyBenchmark(): Promise<void> {
+        return await invoke<void>('cancel_copy_benchmark');
+    }
+
+    /**
+     * 回显JSON负载，用于测量经过WebView的IPC往返
+     */
+    static async ipcEcho(payload: string): Promise<IpcEchoReply> {
+        return await invoke<IpcEchoReply>('ipc_echo', { payload });
+    }
+
+    /**
+     * 回显原始字节，用于测量不经过JSON编码的IPC往返
+     */
+    static async ipcEchoRaw(payload: Uint8Array): Promise<ArrayBuffer> {
+        return await invoke<ArrayBuffer>('ipc_echo_raw', payload);
+    }
+
+    /**
+     * 在进程内运行IPC开销测试
+     */
+    static async runIpcBenchmark(config: IpcBenchmarkConfig): Promise<IpcBenchmarkResult> {
+        return await invoke<IpcBenchmarkResult>('run_ipc_benchmark', { config });
+    }
+
+    /**
+     * 运行进程启动测试
+     */
+    static async runProcessBenchmark(config: ProcessTestConfig): Promise<ProcessTestResult> {
+        return await invoke<ProcessTestResult>('run_process_benchmark', { config });
+    }
+
+    /**
+     * 单独运行WebView测试，后端发出webview-benchmark-request后等待前端提交结果
+     */
+    static async startWebviewBenchmark(config: WebviewTestConfig): Promise<WebviewTestResult> {
+        return await invoke<WebviewTestResult>('start_webview_benchmark', { config });
+    }
+
+    /**
+     * 提交WebView测试负载的计时，nonce须与请求中的一致
+     */
+    static async submitWebviewResults(submission: WebviewSubmission): Promise<void> {
+        return await invoke<void>('submit_webview_results', { submission });
+    }
+
+    /**
+     * 在当前电源计划和高性能计划下各运行一次短时负载并对比，结束时恢复原计划
+     */
+    static async runPowerPlanComparison(config: PowerComparisonConfig): Promise<PowerPlanComparison> {
+        return await invoke<PowerPlanComparison>('run_power_plan_comparison', { config });
+    }
+
+    /**
+     * 确认用户已经按提示切换电源计划，confirmed为false表示拒绝切换
+     */
+    static async confirmPowerPlanSwitch(nonce: string, confirmed: boolean): Promise<void> {
+        return await invoke<void>('confirm_power_plan_switch', { nonce, confirmed });
+    }
+
+    /**
+     * 检测需要更高权限的功能是否可用，以及不可用时的授权方法
+     */
+    static async getCapabilities(): Promise<CapabilityMap> {
+        return await invoke<CapabilityMap>('get_capabilities');
+    }
+
+    /**
+     * 请求使用需要更高权限的功能，Windows上弹出UAC提示；其他平台以PERMISSION_ERROR返回授权方法
+     */
+    static async requestElevation(feature: Feature): Promise<ElevationOutcome> {
+        return await invoke<ElevationOutcome>('request_elevation', { feature });
+    }
+
+    /**
+     * 取出启动参数错误，没有错误或已取出过时返回null
+     */
+    static async takeLaunchError(): Promise<string | null> {
+        return await invoke<string | null>('take_launch_error');
+    }
+
+    /**
+     * 报告前端已完成首次有意义的绘制，结束本次启动测量
+     */
+    static async reportFrontendReady(): Promise<StartupMetrics> {
+        return await invoke<StartupMetrics>('report_frontend_ready');
+    }
+
+    /**
+     * 获取本次启动各阶段的耗时
+     */
+    static async getStartupMetrics(): Promise<StartupMetrics> {
+        return await invoke<StartupMetrics>('get_startup_metrics');
+    }
+
+    /**
+     * 获取最近的启动测量，用于按版本绘制启动耗时
+     */
+    static async getStartupHistory(): Promise<StartupMetrics[]> {
+        return await invoke<StartupMetrics[]>('get_startup_history');
+    }
+
     /**
      * 监听基准测试进度事件
      */
@@ -200,6 +527,51 @@ export class TauriApiService {
         });
     }
 
+    /**
+     * 监听复制测试进度事件
+     */
+    static async onCopyTestProgress(callback: (progress: ProgressUpdate) => void) {
+        return await listen<ProgressUpdate>('copy-test-progress', (event) => {
+            callback(event.payload);
+        });
+    }
+
+    /**
+     * 监听GPU测试进度事件
+     */
+    static async onGpuTestProgress(callback: (progress: ProgressUpdate) => void) {
+        return await listen<ProgressUpdate>('gpu-test-progress', (event) => {
+            callback(event.payload);
+        });
+    }
+
+    /**
+     * 监听网络测试进度事件
+     */
+    static async onNetworkTestProgress(callback: (progress: ProgressUpdate) => void) {
+        return await listen<ProgressUpdate>('network-test-progress', (event) => {
+            callback(event.payload);
+        });
+    }
+
+    /**
+     * 监听后端的WebView测试请求
+     */
+    static async onWebviewBenchmarkRequest(callback: (request: WebviewBenchmarkRequest) => void) {
+        return await listen<WebviewBenchmarkRequest>('webview-benchmark-request', (event) => {
+            callback(event.payload);
+        });
+    }
+
+    /**
+     * 监听后端请用户手动切换电源计划的请求
+     */
+    static async onPowerPlanSwitchRequest(callback: (request: PowerPlanSwitchRequest) => void) {
+        return await listen<PowerPlanSwitchRequest>('power-plan-switch-request', (event) => {
+            callback(event.payload);
+        });
+    }
+
     /**
      * 取消所有事件监听器
      */
@@ -216,8 +588,9 @@ export function handleTauriError(error: any): string {
     if (typeof error === 'string') {
         return error;
     }
+    // 命令失败时后端返回IpcError
     if (error?.message) {
-        return error.message;
+        return error.details ? `${error.message}: ${error.details}` : error.message;
     }
     return '未知错误';
 }
@@ -0,0 +1,119 @@
+import type { WebviewBenchmarkRequest, WebviewSubmission, WebviewTiming, WebviewWorkload } from '../types';
+
+// 每轮各负载的操作数
+const DOM_NODES_PER_ITERATION = 2_000;
+const CANVAS_FILLS_PER_ITERATION = 5_000;
+const JSON_STRINGIFY_PER_ITERATION = 2_000;
+const WASM_LOOPS_PER_ITERATION = 5_000_000;
+
+// 手工汇编的WASM模块，导出run(n)：acc = acc * 31 + n，n递减到0，返回acc
+const WASM_KERNEL = new Uint8Array([
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // 魔数和版本
+    0x01, 0x06, 0x01, 0x60, 0x01, 0x7f, 0x01, 0x7f, // 类型：(i32) -> i32
+    0x03, 0x02, 0x01, 0x00, // 函数
+    0x07, 0x07, 0x01, 0x03, 0x72, 0x75, 0x6e, 0x00, 0x00, // 导出"run"
+    0x0a, 0x26, 0x01, 0x24, 0x01, 0x01, 0x7f, // 代码，一个i32局部变量
+    0x02, 0x40, 0x03, 0x40,
+    0x20, 0x00, 0x45, 0x0d, 0x01,
+    0x20, 0x01, 0x41, 0x1f, 0x6c, 0x20, 0x00, 0x6a, 0x21, 0x01,
+    0x20, 0x00, 0x41, 0x01, 0x6b, 0x21, 0x00,
+    0x0c, 0x00, 0x0b, 0x0b,
+    0x20, 0x01, 0x0b,
+]);
+
+// 序列化负载使用的固定对象
+const JSON_SAMPLE = {
+    id: 42,
+    name: 'benchmark',
+    tags: ['cpu', 'memory', 'storage', 'gpu'],
+    values: Array.from({ length: 32 }, (_, i) => i * 1.5),
+    nested: { enabled: true, ratio: 0.75, label: 'nested object' },
+};
+
+// 返回操作数
+function domNodes(): number {
+    const container = document.createElement('div');
+    container.style.position = 'absolute';
+    container.style.visibility = 'hidden';
+    document.body.appendChild(container);
+    for (let i = 0; i < DOM_NODES_PER_ITERATION; i++) {
+        const node = document.createElement('span');
+        node.textContent = String(i);
+        container.appendChild(node);
+    }
+    // 读取布局属性，强制浏览器完成样式计算
+    void container.offsetHeight;
+    container.remove();
+    return DOM_NODES_PER_ITERATION;
+}
+
+function canvasFill(canvas: HTMLCanvasElement): number {
+    const context = canvas.getContext('2d');
+    if (!context) {
+        throw new Error('canvas 2d context unavailable');
+    }
+    for (let i = 0; i < CANVAS_FILLS_PER_ITERATION; i++) {
+        context.fillStyle = `rgb(${i % 256}, ${(i * 7) % 256}, ${(i * 13) % 256})`;
+        context.fillRect(i % 224, (i * 3) % 224, 32, 32);
+    }
+    // 读回像素，等待绘制真正完成
+    context.getImageData(0, 0, 1, 1);
+    return CANVAS_FILLS_PER_ITERATION;
+}
+
+function jsonStringify(): number {
+    let length = 0;
+    for (let i = 0; i < JSON_STRINGIFY_PER_ITERATION; i++) {
+        length += JSON.stringify({ ...JSON_SAMPLE, id: i }).length;
+    }
+    if (length === 0) {
+        throw new Error('empty JSON output');
+    }
+    return JSON_STRINGIFY_PER_ITERATION;
+}
+
+async function wasmKernel(): Promise<() => number> {
+    const { instance } = await WebAssembly.instantiate(WASM_KERNEL);
+    const run = instance.exports.run as (n: number) => number;
+    return () => {
+        run(WASM_LOOPS_PER_ITERATION);
+        return WASM_LOOPS_PER_ITERATION;
+    };
+}
+
+async function workloadRunner(workload: WebviewWorkload): Promise<() => number> {
+    switch (workload) {
+        case 'dom_nodes':
+            return domNodes;
+        case 'canvas_fill': {
+            const canvas = document.createElement('canvas');
+            canvas.width = 256;
+            canvas.height = 256;
+            return () => canvasFill(canvas);
+        }
+        case 'json_stringify':
+            return jsonStringify;
+        case 'wasm_kernel':
+            // 模块编译不计入耗时
+            return await wasmKernel();
+    }
+}
+
+/**
+ * 按请求运行各项负载，返回可直接提交的结果。每项负载重复iterations轮，报告合计的操作数和耗时
+ */
+export async function runWebviewWorkloads(request: WebviewBenchmarkRequest): Promise<WebviewSubmission> {
+    const timings: WebviewTiming[] = [];
+    for (const workload of request.workloads) {
+        const run = await workloadRunner(workload);
+        let operations = 0;
+        const started = performance.now();
+        for (let i = 0; i < request.iterations; i++) {
+            operations += run();
+        }
+        timings.push({ workload, operations, durationMs: performance.now() - started });
+        // 让出主线程，避免界面长时间无响应
+        await new Promise((resolve) => setTimeout(resolve, 0));
+    }
+    return { nonce: request.nonce, userAgent: navigator.userAgent, timings };
+}
@@ -15,6 +15,36 @@ export interface BenchmarkConfig {
         fileSize: number; // MB
         blockSize: number; // KB
     };
+    gpuTest?: { // 省略时不运行GPU测试
+        enabled: boolean;
+        adapterIndex?: number; // listGpuAdapters中的序号，省略时自动选择
+        testDuration: number; // 每项负载的秒数
+        workloads: GpuWorkload[];
+    };
+    webviewTest?: { // 省略时不运行WebView测试
+        enabled: boolean;
+        workloads: WebviewWorkload[];
+        iterations: number;
+        timeoutSeconds: number; // 前端在此时间内未提交结果时测试失败
+    };
+    networkTest?: { // 省略时不运行网络测试
+        enabled: boolean;
+        target?: string; // http://主机:端口/路径（不支持https），省略时对本机回环服务器测试
+        testDuration: number; // 下载和上传各自的秒数
+        parallelConnections: number;
+        latencySamples: number;
+    };
+    monitoringIntervalMs?: number; // 运行期间发送系统监控数据的间隔，默认1000
+    scoreWeights?: Partial<Record<ScoreCategory, number>>; // 各部分在总分中的权重，省略的部分为1
+}
+
+// 计入总分的各部分
+export type ScoreCategory = 'cpu' | 'memory' | 'storage' | 'gpu' | 'webview' | 'network';
+
+// 从配置文件读取的配置，unknownKeys为被忽略的未知字段（如拼写错误）
+export interface LoadedConfig {
+    config: BenchmarkConfig;
+    unknownKeys: string[];
 }
 
 // 测试结果接口
@@ -24,15 +54,34 @@ export interface TestResults {
     cpuResults?: CpuTestResult;
     memoryResults?: MemoryTestResult;
     storageResults?: StorageTestResult;
-    overallScore: number;
+    gpuResults?: GpuTestResult; // 未启用GPU测试或本机没有适配器时为空
+    webviewResults?: WebviewTestResult; // 未启用WebView测试时为空
+    networkResults?: NetworkTestResult; // 未启用网络测试时为空
+    overallScore: number; // 各部分得分按scoreWeights加权平均
+    categoryScores?: Partial<Record<ScoreCategory, number>>; // 按参考基准归一化的各部分得分，参考机器为1000
+    warnings?: WarningRecord[]; // 运行期间的警告，包括被过滤未实时发送的
+}
+
+// 套件历史中的一次运行，与后端字段名一致
+export interface HistoryEntry {
+    session_id: string;
+    timestamp: string;
+    overall_score: number;
+}
+
+export interface DeletionReport {
+    removed: number;
+    bytes_reclaimed: number;
+    result_ids: string[];
+    dry_run: boolean;
 }
 
 export interface CpuTestResult {
     single_thread_score: number;
     multi_thread_score: number;
     floating_point_score: number;
-    average_temperature: number;
-    max_temperature: number;
+    average_temperature: number | null; // °C，测试期间的CPU温度，读不到温度传感器时为空
+    max_temperature: number | null;
     test_duration: number; // seconds
     operations_per_second: number;
 }
@@ -52,6 +101,19 @@ export interface MemoryTestResult {
     memory_usage_peak: number; // MB
     error_rate: number; // percentage
     test_duration: number; // seconds
+    interference?: InterferenceResult; // 干扰测试被关闭时为空
+}
+
+// 干扰测试的一个阶段：其他线程运行浮点负载时的顺序读取带宽
+export interface InterferenceMeasurement {
+    load_threads: number;
+    sequential_read_speed: number; // MB/s
+    retention: number; // percentage，相对无负载时的带宽
+}
+
+export interface InterferenceResult {
+    logical_cores: number;
+    measurements: InterferenceMeasurement[]; // 依次为无负载、一半核心负载、其余核心全部负载
 }
 
 // 内存测试配置接口
@@ -60,6 +122,7 @@ export interface MemoryTestConfig {
     iterations: number;
     test_duration: number; // seconds
     enable_usage_monitoring: boolean;
+    interference_duration?: number; // 干扰测试每个阶段的秒数，为0时跳过，默认2
 }
 
 export interface StorageTestResult {
@@ -69,6 +132,8 @@ export interface StorageTestResult {
     random_write: StorageMetrics;
     test_duration: number; // seconds
     total_data_processed: number; // MB
+    small_files?: SmallFileMetrics; // 小文件测试被关闭时为空
+    archive?: ArchiveMetrics; // 未启用压缩包解压测试时为空
 }
 
 export interface StorageMetrics {
@@ -77,12 +142,447 @@ export interface StorageMetrics {
     latency: number; // milliseconds
 }
 
+// 小文件测试的单个阶段，延迟单位为毫秒
+export interface SmallFilePhase {
+    files: number;
+    files_per_second: number;
+    p50_ms: number;
+    p95_ms: number;
+    p99_ms: number;
+}
+
+export interface SmallFileMetrics {
+    file_count: number;
+    total_bytes: number;
+    create: SmallFilePhase;
+    stat: SmallFilePhase;
+    read: SmallFilePhase;
+    delete: SmallFilePhase;
+}
+
+export type ArchiveFormat = 'zip' | 'tar_zst';
+
+// 单种格式的解压结果，压缩包生成不计时
+export interface ArchiveExtraction {
+    format: ArchiveFormat;
+    archive_bytes: number;
+    seconds: number;
+    throughput: number; // MB/s，按解压出的数据量计算
+    files_per_second: number;
+}
+
+export interface ArchiveMetrics {
+    file_count: number;
+    total_bytes: number;
+    extractions: ArchiveExtraction[];
+}
+
+// 压缩包解压测试配置接口，省略的字段取默认值
+export interface ArchiveTestConfig {
+    formats?: ArchiveFormat[]; // 默认两种格式都测
+    small_files?: number; // 1~64KB的小文件数，默认5000
+    large_files?: number; // 默认4
+    large_file_size?: number; // MB，默认32
+    seed?: number;
+}
+
 // 存储测试配置接口
 export interface StorageTestConfig {
     file_size: number; // MB
     block_size: number; // KB
     test_duration: number; // seconds
     test_file_path?: string; // 可选的测试文件路径
+    small_file_count?: number; // 小文件测试的文件数，为0时跳过，默认10000
+    archive?: ArchiveTestConfig | null; // 压缩包解压测试，为空时跳过
+}
+
+// 卷间复制测试配置接口
+export interface CopyTestConfig {
+    source_dir: string; // 源卷上的目录，如挂载点
+    destination_dir: string;
+    file_size: number; // MB，超过可用空间时自动减小
+    buffer_size: number; // KB
+}
+
+export interface CopyTestResult {
+    sustained_throughput: number; // MB/s
+    file_size: number; // MB，实际复制的大小
+    size_clamped: boolean;
+    buffer_size: number; // KB
+    duration_seconds: number;
+    timeline: CopySample[];
+    source_throttled: boolean;
+    destination_throttled: boolean;
+}
+
+export interface CopySample {
+    elapsed_seconds: number;
+    throughput: number; // MB/s
+    read_throughput: number; // MB/s
+    write_throughput: number; // MB/s
+}
+
+// IPC开销测试配置接口
+export type IpcTransport = 'json' | 'binary';
+
+export interface IpcBenchmarkConfig {
+    payload_sizes: number[]; // KB
+    iterations: number;
+    warmup_iterations: number;
+    trim_percent: number; // 两端各去掉的比例（%）
+    transports: IpcTransport[];
+}
+
+export interface IpcMeasurement {
+    transport: IpcTransport;
+    payload_bytes: number;
+    samples: number;
+    p50_us: number;
+    p95_us: number;
+    p99_us: number;
+    mean_us: number;
+    server_mean_us: number; // 处理函数一侧的耗时
+    throughput_mb_s: number;
+}
+
+export interface IpcBenchmarkResult {
+    measurements: IpcMeasurement[];
+    test_duration: number; // seconds
+}
+
+export interface IpcEchoReply {
+    payload: string;
+    serverMicros: number;
+}
+
+// 进程启动测试配置接口
+export interface ProcessTestConfig {
+    iterations: number;
+    warmup_iterations: number;
+    measure_window_creation: boolean; // 额外测量创建隐藏WebView窗口的耗时
+}
+
+export interface ProcessTestResult {
+    helper: string; // 辅助程序的命令行
+    iterations: number;
+    p50_ms: number;
+    p95_ms: number;
+    p99_ms: number;
+    mean_ms: number;
+    spawns_per_second: number;
+    window_creation_ms?: number;
+    test_duration: number; // seconds
+}
+
+// 压缩测试配置接口，结果记录在extra_results.compression中
+export type CompressionAlgorithm = 'zstd' | 'gzip';
+
+export interface CompressionCodec {
+    algorithm: CompressionAlgorithm;
+    level: number;
+}
+
+export interface CompressionTestConfig {
+    corpus_size: number; // MB
+    seed: number;
+    iterations: number;
+    codecs: CompressionCodec[];
+}
+
+export interface CodecResult {
+    algorithm: CompressionAlgorithm;
+    level: number;
+    compress_throughput: number; // MB/s
+    decompress_throughput: number; // MB/s
+    ratio: number;
+}
+
+export interface CompressionTestResult {
+    corpus_bytes: number;
+    seed: number;
+    codecs: CodecResult[];
+    test_duration: number; // seconds
+}
+
+// 加密测试配置接口，结果记录在extra_results.crypto中
+export type CryptoAlgorithm = 'aes_256_gcm' | 'chacha20_poly1305' | 'sha256' | 'blake3';
+
+export interface CryptoTestConfig {
+    message_sizes: number[]; // KB
+    data_size: number; // MB
+    algorithms: CryptoAlgorithm[];
+}
+
+export interface CryptoMeasurement {
+    algorithm: CryptoAlgorithm;
+    message_size: number; // bytes
+    throughput: number; // MB/s，加密或哈希
+    decrypt_throughput: number | null; // MB/s，哈希算法为空
+    hardware_accelerated: boolean;
+}
+
+export interface CryptoTestResult {
+    measurements: CryptoMeasurement[];
+    cpu_features: string[];
+    test_duration: number; // seconds
+}
+
+// 数据库测试配置接口，结果记录在extra_results.database中
+export type JournalMode = 'wal' | 'delete';
+export type DatabasePhase = 'insert' | 'point_query' | 'range_scan' | 'update';
+
+export interface DatabaseTestConfig {
+    rows: number;
+    batch_size: number;
+    point_queries: number;
+    range_scans: number;
+    journal_modes: JournalMode[];
+    test_dir?: string; // 为空时使用存储测试的目标目录
+}
+
+export interface PhaseResult {
+    phase: DatabasePhase;
+    operations: number;
+    ops_per_second: number;
+    p95_latency_ms: number;
+}
+
+export interface DatabaseRun {
+    journal_mode: string; // SQLite实际采用的日志模式
+    rows: number;
+    phases: PhaseResult[];
+}
+
+export interface DatabaseTestResult {
+    runs: DatabaseRun[];
+    test_duration: number; // seconds
+}
+
+// JSON测试配置接口，结果记录在extra_results.json中
+export type DocumentShape = 'telemetry' | 'nested_config' | 'string_array';
+
+export interface JsonTestConfig {
+    document_size: number; // KB
+    iterations: number;
+    seed: number;
+    shapes: DocumentShape[];
+}
+
+export interface ShapeResult {
+    shape: DocumentShape;
+    document_bytes: number;
+    parse_throughput: number; // MB/s
+    stringify_throughput: number; // MB/s
+    parse_docs_per_second: number;
+    stringify_docs_per_second: number;
+}
+
+export interface JsonTestResult {
+    shapes: ShapeResult[];
+    test_duration: number; // seconds
+}
+
+// 稳定性测试配置接口，结果记录在extra_results.stability中
+export interface StabilityTestConfig {
+    duration: number; // seconds
+    thread_count: number; // 0表示每个逻辑核心一个
+    buffer_size: number; // MB，0表示按可用内存自动选择
+    temperature_limit: number; // °C
+}
+
+export type StabilityFaultKind = 'computation' | 'memory';
+
+export interface StabilityFault {
+    kind: StabilityFaultKind;
+    at_seconds: number;
+    detail: string;
+}
+
+export interface StabilityTestResult {
+    verdict: 'pass' | 'fail';
+    thread_count: number;
+    buffer_size: number; // MB，按可用内存调整后的实际大小
+    kernel_iterations: number;
+    kernel_operations_per_second: number;
+    memory_passes: number;
+    computation_errors: number;
+    memory_errors: number;
+    faults: StabilityFault[]; // 最多保留前100条
+    max_temperature: number | null; // °C，读不到温度传感器时为空
+    min_frequency_mhz: number;
+    max_frequency_mhz: number;
+    throttled: boolean;
+    test_duration: number; // seconds
+}
+
+// 线程同步测试配置接口，结果记录在extra_results.sync中
+export type SyncPrimitive = 'mutex' | 'atomic' | 'channel';
+
+export interface SyncTestConfig {
+    operations: number; // 每种配置所有线程合计的操作数
+    thread_counts: number[]; // 0表示逻辑核心数
+    primitives: SyncPrimitive[];
+}
+
+export interface SyncMeasurement {
+    primitive: SyncPrimitive;
+    threads: number;
+    operations: number;
+    uncontended_ops_per_second: number;
+    contended_ops_per_second: number;
+    contention_penalty: number; // 无竞争吞吐 / 有竞争吞吐
+}
+
+export interface SyncBenchResult {
+    measurements: SyncMeasurement[];
+    test_duration: number; // seconds
+}
+
+// 哈希和伪随机数测试配置接口，结果记录在extra_results.hashing中
+export type HashAlgorithm = 'siphash' | 'fxhash' | 'ahash';
+export type PrngAlgorithm = 'lcg' | 'xoshiro256';
+
+export interface HashingTestConfig {
+    keys: number; // HashMap中的键数
+    lookups: number; // 每种哈希算法的查找次数，也是每种生成器生成的随机数个数
+    hashers: HashAlgorithm[];
+    prngs: PrngAlgorithm[];
+}
+
+export interface HasherResult {
+    algorithm: HashAlgorithm;
+    insert_ops_per_second: number;
+    lookup_ops_per_second: number;
+}
+
+export interface PrngResult {
+    algorithm: PrngAlgorithm;
+    numbers_per_second: number;
+}
+
+export interface HashingTestResult {
+    keys: number;
+    lookups: number;
+    hashers: HasherResult[];
+    prngs: PrngResult[];
+    test_duration: number; // seconds
+}
+
+// 图像编解码测试配置接口，结果记录在extra_results.image中
+export type ImageFormat = 'png' | 'jpeg';
+export type SyntheticImage = 'gradient' | 'noise' | 'composite';
+
+export interface ImageTestConfig {
+    width: number;
+    height: number;
+    seed: number;
+    iterations: number; // 每种格式编码和解码整个图像集的次数
+    formats: ImageFormat[];
+    jpegQuality: number; // 1-100
+}
+
+export interface ImageFormatResult {
+    format: ImageFormat;
+    encode_megapixels_per_second: number;
+    decode_megapixels_per_second: number;
+    encoded_bytes: number;
+    pixel_hash: string; // 解码后像素的SHA-256
+}
+
+export interface ImageTestResult {
+    width: number;
+    height: number;
+    images: SyntheticImage[];
+    formats: ImageFormatResult[];
+    test_duration: number; // seconds
+}
+
+export type WebviewWorkload = 'dom_nodes' | 'canvas_fill' | 'json_stringify' | 'wasm_kernel';
+
+export interface WebviewTestConfig {
+    workloads: WebviewWorkload[];
+    iterations: number;
+    timeoutSeconds: number;
+}
+
+// webview-benchmark-request事件的负载，nonce须在提交时原样带回
+export interface WebviewBenchmarkRequest {
+    nonce: string;
+    workloads: WebviewWorkload[];
+    iterations: number;
+    timeoutMs: number;
+}
+
+export interface WebviewTiming {
+    workload: WebviewWorkload;
+    operations: number;
+    durationMs: number;
+}
+
+// submit_webview_results的参数
+export interface WebviewSubmission {
+    nonce: string;
+    userAgent?: string;
+    timings: WebviewTiming[];
+}
+
+export interface WebviewWorkloadResult {
+    workload: WebviewWorkload;
+    operations: number;
+    duration_ms: number;
+    ops_per_second: number;
+}
+
+export interface WebviewTestResult {
+    workloads: WebviewWorkloadResult[];
+    user_agent?: string;
+    test_duration: number; // seconds
+}
+
+// 单独运行GPU测试的配置接口
+export interface GpuTestConfig {
+    adapter_index?: number; // listGpuAdapters中的序号，省略时自动选择
+    test_duration: number; // 每项负载的秒数
+    workloads: GpuWorkload[];
+}
+
+export interface GpuTestResult {
+    compute_score: number; // GFLOPS
+    memory_bandwidth_gb_s: number; // GB/s
+    adapter_name: string;
+    backend: string; // vulkan / metal / dx12 / gl
+}
+
+export type GpuWorkload = 'compute' | 'bandwidth';
+
+// 单独运行网络测试的配置接口
+export interface NetworkTestConfig {
+    target?: string; // http://主机:端口/路径（不支持https），省略时对本机回环服务器测试（离线模式）
+    test_duration: number; // 下载和上传各自的秒数
+    parallel_connections: number;
+    latency_samples: number;
+}
+
+export interface NetworkTestResult {
+    target: string;
+    offline: boolean; // 对本机回环服务器测试时为true
+    download_mbps: number;
+    upload_mbps: number;
+    latency_avg_ms: number;
+    latency_min_ms: number;
+    latency_max_ms: number;
+    jitter_ms: number;
+    loss_percent: number;
+    parallel_connections: number;
+    test_duration: number; // seconds
+}
+
+// list_gpu_adapters返回的适配器
+export interface GpuAdapterInfo {
+    index: number;
+    name: string;
+    backend: string;
+    device_type: 'discrete' | 'integrated' | 'virtual' | 'other' | 'cpu';
 }
 
 // 系统信息接口
@@ -102,6 +602,7 @@ export interface SystemInfo {
             l2?: number;
             l3?: number;
         };
+        features?: string[]; // 指令集扩展，如aes、sha、avx2
     };
     memory: {
         total: number; // GB
@@ -120,6 +621,7 @@ export interface SystemInfo {
         interface: string;
         file_system: string;
         mount_point: string;
+        removable?: boolean; // U盘、SD卡等可移动设备
     }>;
     system_details: {
         hostname: string;
@@ -129,46 +631,181 @@ export interface SystemInfo {
         total_processes: number;
         temperatures: Record<string, number>; // component -> temperature
     };
+    power?: PowerInfo; // 当前电源计划，无法识别时为空
+}
+
+// configure_metrics_exporter的返回值，address为实际监听的地址
+export interface ExporterStatus {
+    enabled: boolean;
+    address?: string;
+}
+
+// export_timeseries的导出格式：每个序列一个CSV，或timestamp,stream,metric,value的长格式单个CSV
+export type TimeseriesFormat = 'per_stream' | 'long';
+
+// Windows上id为GUID，Linux上为"来源:取值"，macOS上为normal或low_power
+export interface PowerPlan {
+    id: string;
+    name: string;
+    high_performance: boolean;
+}
+
+export interface PowerInfo {
+    active_plan?: PowerPlan;
+    available_plans: PowerPlan[];
+}
+
+// 电源计划对比配置接口，省略的字段取默认值
+export interface PowerComparisonConfig {
+    cpu_duration?: number; // seconds，默认5
+    memory_buffer_size?: number; // MB，默认256
+    memory_iterations?: number; // 默认10
+    allow_switching?: boolean; // 用户明确同意后才在Windows上自动切换计划
+    confirmation_timeout_seconds?: number; // 手动切换时等待确认的时长，默认120
+}
+
+// power-plan-switch-request事件的负载，用户切换后调用confirm_power_plan_switch并带回nonce
+export interface PowerPlanSwitchRequest {
+    nonce: string;
+    target: PowerPlan;
+    restore: boolean; // 为true时请用户切回原计划
+    timeoutMs: number;
+}
+
+export interface PlanRun {
+    plan?: PowerPlan;
+    scores: {
+        cpu_score: number;
+        memory_score: number;
+    };
+}
+
+export interface PowerPlanComparison {
+    current: PlanRun;
+    high_performance?: PlanRun;
+    switch_method?: 'automatic' | 'manual';
+    skipped?: 'unknown_active_plan' | 'already_high_performance' | 'no_high_performance_plan' | 'no_consent' | 'declined';
+    cpu_delta_percent?: number; // 高性能计划相对当前计划的变化
+    memory_delta_percent?: number;
+}
+
+// 需要更高权限的功能
+export type Feature = 'direct_io' | 'rapl_power' | 'smart' | 'large_pages';
+
+export interface Capability {
+    feature: Feature;
+    status: 'available' | 'permission_denied' | 'unsupported';
+    detail?: string; // 不可用的原因
+    remediation?: string; // 本平台上的授权方法
+}
+
+export interface CapabilityMap {
+    capabilities: Capability[];
+}
+
+// 报告中数值的展示格式：SI以1000进位（MB/s、GB/s），IEC以1024进位（MiB/s、GiB/s）
+export interface FormatOptions {
+    locale: 'en-US' | 'de-DE' | 'fr-FR' | 'zh-CN';
+    unit_system: 'si' | 'iec';
+    precision: number; // 小数位数，0-6
+}
+
+export interface ElevationOutcome {
+    feature: Feature;
+    prompted: boolean; // 是否弹出了UAC提示（仅Windows）
+    capability: Capability; // 以管理员身份探测的结果
 }
 
 // IPC 通信相关接口
 export interface BenchmarkProgress {
-    session_id: string;
-    current_test: string;
-    overall_progress: number;
-    test_progress: number;
+    sessionId: string;
+    currentTest: string;
+    overallProgress: number;
+    testProgress: number;
     message: string;
-    estimated_time_remaining?: number; // seconds
+    messageKey: string; // 稳定的消息键，如 storage.seq_write.progress
+    params: Record<string, string>;
+    estimatedTimeRemaining?: number; // seconds
+    estimatedCompletionTime?: string; // RFC3339，按后端时钟计算
+    seq?: number; // 会话内的事件序号，用于与get_recent_events的结果去重
+    schemaVersion?: number; // 事件负载版本，见EVENT_SCHEMA_VERSION
 }
 
 export interface SystemMonitoringData {
-    cpu_usage: number;
-    memory_usage: number;
+    cpuUsage: number;
+    memoryUsage: number;
     temperature?: number;
     timestamp: string;
+    sessionId?: string; // 套件运行期间的采样为所属会话，单独查询时为空
+    seq?: number; // 会话内的事件序号，用于与get_recent_events的结果去重
+    schemaVersion?: number; // 事件负载版本，见EVENT_SCHEMA_VERSION
 }
 
 export interface TestSession {
-    session_id: string;
+    sessionId: string;
     status: TestStatus;
-    start_time: string;
-    end_time?: string;
+    startTime: string;
+    endTime?: string;
     config?: BenchmarkConfig;
+    overallProgress: number; // 0-100
+    currentTest?: string; // 正在运行的测试类型，如 "cpu"
+    lastUpdate: string;
+    metricSamples?: MetricSampleCounters;
+    estimatedCompletionTime?: string; // 暂停期间随当前时间后移
+}
+
+// get_ui_state返回的界面状态快照，事件无法送达时轮询它即可渲染运行界面
+export interface UiStateSnapshot {
+    sessionId: string;
+    status: TestStatus;
+    overallProgress: number; // 0-100
+    currentTest?: string;
+    phase?: string; // 当前阶段说明，与进度事件的message一致
+    tests: TestProgressSnapshot[]; // 已开始的测试，按开始顺序
+    estimatedTimeRemaining?: number;
+    estimatedCompletionTime?: string;
+    recentWarnings: TestWarningEvent[]; // 最旧的在前
+    monitoring?: SystemMonitoringData;
+    latestSample?: RealTimePerformanceData;
+    resultId?: string; // 结果已写入结果存储时的ID
+    lastUpdate: string;
+    endTime?: string;
+}
+
+export interface TestProgressSnapshot {
+    testType: string;
+    progress: number; // 0-100
+}
+
+// 实时指标只发送每个指标流的最新值，被覆盖的中间值计为丢弃
+export interface MetricSampleCounters {
+    delivered: number;
+    dropped: number;
 }
 
 export interface RealTimePerformanceData {
-    session_id: string;
-    test_type: string;
+    sessionId: string;
+    testType: TestType;
+    phase: string; // 测试内的阶段，如 seq_write
+    // cpu: ops_per_sec, frequency_mhz, temperature_c(可选)
+    // memory: mb_per_sec
+    // storage: mb_per_sec, iops, p99_latency_ms
     metrics: Record<string, number>;
     timestamp: string;
+    seq?: number; // 会话内的事件序号，用于与get_recent_events的结果去重
+    schemaVersion?: number; // 事件负载版本，见EVENT_SCHEMA_VERSION
 }
 
 export interface TestWarningEvent {
-    session_id: string;
-    test_type: string;
-    warning_type: string;
+    sessionId: string;
+    testType: TestType;
+    warningType: string;
     message: string;
+    messageKey: string; // 稳定的消息键，如 storage.seq_write.progress
+    params: Record<string, string>;
     severity: WarningSeverity;
+    seq?: number; // 会话内的事件序号，用于与get_recent_events的结果去重
+    schemaVersion?: number; // 事件负载版本，见EVENT_SCHEMA_VERSION
 }
 
 export enum WarningSeverity {
@@ -180,35 +817,189 @@ export enum WarningSeverity {
 
 export enum TestStatus {
     Pending = 'Pending',
+    Queued = 'Queued',
     Running = 'Running',
+    Paused = 'Paused',
     Completed = 'Completed',
     Failed = 'Failed',
     Cancelled = 'Cancelled',
+    Interrupted = 'Interrupted',
+}
+
+// 事件中testType字段的取值，suite表示不属于单项测试的事件
+export type TestType = 'cpu' | 'memory' | 'storage' | 'gpu' | 'webview' | 'network' | 'extra' | 'suite';
+
+export interface SessionStatusChangedEvent {
+    sessionId: string;
+    oldStatus: TestStatus;
+    newStatus: TestStatus;
+    timestamp: string;
+    reason?: string; // 失败、取消和中断时为错误码，见get_error_catalog
+    seq?: number; // 会话内的事件序号，用于与get_recent_events的结果去重
+    schemaVersion?: number; // 事件负载版本，见EVENT_SCHEMA_VERSION
+}
+
+export type TestResultPayload =
+    | { type: 'cpu'; data: CpuTestResult }
+    | { type: 'memory'; data: MemoryTestResult }
+    | { type: 'storage'; data: StorageTestResult }
+    | { type: 'gpu'; data: GpuTestResult }
+    | { type: 'webview'; data: WebviewTestResult }
+    | { type: 'network'; data: NetworkTestResult }
+    | { type: 'extra'; data: ExtraTestResult };
+
+// 注册的自定义测试模块的结果，result为模块自定义的JSON
+export interface ExtraTestResult {
+    name: string;
+    result: unknown;
 }
 
 export interface TestCompleteEvent {
-    session_id: string;
-    test_type: string;
+    sessionId: string;
+    testType: TestType;
     success: boolean;
-    result?: any;
+    result?: TestResultPayload;
     error?: string;
+    elapsedSeconds: number; // seconds
+    outcome: TestCompletion; // success为true但有Critical问题时为CompletedWithIssues，本机不支持时为Skipped
+    issues: Issue[]; // 运行期间上报的不中止测试的问题
+    seq?: number; // 会话内的事件序号，用于与get_recent_events的结果去重
+    schemaVersion?: number; // 事件负载版本，见EVENT_SCHEMA_VERSION
+}
+
+export type TestCompletion = 'Completed' | 'CompletedWithIssues' | 'Failed' | 'Skipped';
+
+export interface Issue {
+    code: string; // 消息键
+    message: string;
+    severity: WarningSeverity;
 }
 
 export interface BenchmarkSuiteCompleteEvent {
-    session_id: string;
+    sessionId: string;
     success: boolean;
     results?: TestResults;
     error?: string;
+    completedAt?: string; // 实际完成时间，可与进度中的estimatedCompletionTime对照
+    saveError?: IpcError; // 结果写入历史失败时的错误（code为DATA_SAVE_ERROR），此时结果只在本事件中
+    seq?: number; // 会话内的事件序号，用于与get_recent_events的结果去重
+    schemaVersion?: number; // 事件负载版本，见EVENT_SCHEMA_VERSION
 }
 
 export interface ProgressUpdate {
     progress: number;
     message: string;
-    test_type: string;
+    messageKey: string; // 稳定的消息键，如 storage.seq_write.progress
+    params: Record<string, string>;
+    testType: TestType;
+    seq?: number; // 会话内的事件序号，用于与get_recent_events的结果去重
+    schemaVersion?: number; // 事件负载版本，见EVENT_SCHEMA_VERSION
+}
+
+export interface RecordedEvent {
+    seq: number;
+    event: string;
+    payload: unknown;
+}
+
+export interface RecentEvents {
+    events: RecordedEvent[];
+    latestSeq: number;
+    truncated: boolean; // sinceSeq之后有事件已被挤出缓冲区
+}
+
+// 前端按该版本的事件负载编写，与后端不一致时需要更新前端
+export const EVENT_SCHEMA_VERSION = 1;
+
+export interface SupportedSchemaVersions {
+    event: number; // 后端当前的事件负载版本
+    result: number; // 当前结果文档版本
+    readableResults: number[]; // 可以读取并升级的结果文档版本
+}
+
+// 通过start_benchmark_suite的会话通道收到的事件，按event区分
+export type SuiteEvent =
+    | { event: 'progress'; data: BenchmarkProgress }
+    | { event: 'monitoring'; data: SystemMonitoringData }
+    | { event: 'performance'; data: RealTimePerformanceData }
+    | { event: 'warning'; data: TestWarningEvent }
+    | { event: 'heartbeat'; data: HeartbeatEvent }
+    | { event: 'testComplete'; data: TestCompleteEvent } // 失败的测试也在这里
+    | { event: 'suiteComplete'; data: BenchmarkSuiteCompleteEvent }
+    | { event: 'error'; data: { message: string } };
+
+// 实时发送警告的过滤条件，保存在应用数据目录
+export interface WarningFilter {
+    minSeverity: WarningSeverity;
+    mutedTypes: string[];
+}
+
+// 会话中一种警告的历史，同一(testType, warningType)的重复合并为一条
+export interface WarningRecord {
+    testType: TestType;
+    warningType: string;
+    severity: WarningSeverity;
+    message: string; // 首次出现时的消息
+    messageKey: string;
+    count: number; // 出现总次数
+    emitted: number; // 实际发送的事件数，包括汇总事件
+    active: boolean;
+    firstSeen: string;
+    lastSeen: string;
+}
+
+// 会话长时间没有进度时定期发送，区分“仍在运行”和“卡住”
+export interface HeartbeatEvent {
+    sessionId: string;
+    phase: string;
+    secondsSinceProgress: number;
+    seq?: number;
+}
+
+// 套件结果相对本机上一次结果出现回归时全局发送（performance-regression-detected）
+export interface RegressionDetectedEvent {
+    sessionId: string;
+    resultId: string;
+    baselineId: string;
+    baselineTimestamp: string;
+    regressions: MetricComparison[];
+    schemaVersion?: number;
+}
+
+// 单项指标的对比，字段名与结果文档一致
+export interface MetricComparison {
+    metric: string; // 点分隔的字段路径，如 storage.sequential_write.throughput
+    baseline?: number;
+    candidate?: number;
+    change_percent?: number;
+    threshold_percent: number;
+    higher_is_better: boolean;
+    status: 'improved' | 'unchanged' | 'regressed' | 'missing';
 }
 
 export interface IpcError {
     code: string;
     message: string;
     details?: string;
-}
\ No newline at end of file
+    retryable: boolean;
+    field?: string; // 参数校验失败时对应的参数名
+    issues?: ConfigIssue[]; // 配置校验失败（INVALID_CONFIG）时的全部问题
+}
+
+// 配置中的一个问题，field为点分隔的字段路径，如 storage_test.block_size
+export interface ConfigIssue {
+    field: string;
+    severity: 'Error' | 'Warning';
+    message: string;
+    suggestedValue?: unknown; // 可以直接填回该字段的建议值
+}
+// 应用自身的启动耗时，均为从进程启动开始计算的毫秒数，尚未到达的阶段为空
+export interface StartupMetrics {
+    launchedAt: string;
+    appVersion: string;
+    launchKind: 'cold' | 'warm';
+    setupMs: number | null;
+    windowCreatedMs: number | null;
+    pageLoadedMs: number | null;
+    frontendReadyMs: number | null;
+}